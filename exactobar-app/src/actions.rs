@@ -48,7 +48,7 @@ fn refresh_provider_async(provider: ProviderKind, usage: Entity<UsageModel>, cx:
         // Execute fetch on Tokio runtime - MUST use this bridge!
         // Direct pipeline.execute() calls will panic because tokio::process::Command
         // requires a Tokio runtime, but GPUI runs on smol.
-        let result = crate::refresh::fetch_on_tokio(provider).await;
+        let result = crate::refresh::fetch_on_tokio(provider, true).await;
 
         // Update state
         let _ = cx.update_entity(&usage, |model, cx| {
@@ -1,6 +1,6 @@
 //! Provider card component.
 
-use exactobar_core::ProviderKind;
+use exactobar_core::{ProviderKind, ProviderMetadata, WindowSlot};
 use exactobar_providers::ProviderRegistry;
 use gpui::*;
 
@@ -13,8 +13,7 @@ pub struct ProviderCard {
     snapshot: Option<exactobar_core::UsageSnapshot>,
     is_refreshing: bool,
     error: Option<String>,
-    session_label: &'static str,
-    weekly_label: &'static str,
+    metadata: Option<&'static ProviderMetadata>,
     name: String,
 }
 
@@ -29,20 +28,14 @@ impl ProviderCard {
         let name = descriptor
             .map(|d| d.display_name().to_string())
             .unwrap_or_else(|| format!("{:?}", provider));
-        let session_label = descriptor
-            .map(|d| d.metadata.session_label.as_str())
-            .unwrap_or("Session");
-        let weekly_label = descriptor
-            .map(|d| d.metadata.weekly_label.as_str())
-            .unwrap_or("Weekly");
+        let metadata = descriptor.map(|d| &d.metadata);
 
         Self {
             provider,
             snapshot,
             is_refreshing,
             error,
-            session_label,
-            weekly_label,
+            metadata,
             name,
         }
     }
@@ -108,15 +101,27 @@ impl IntoElement for ProviderCard {
             if let Some(snap) = &self.snapshot {
                 let mut usage_div = div().mt(px(12.0)).flex().flex_col().gap(px(8.0));
 
-                if let Some(primary) = &snap.primary {
-                    let remaining = 100.0 - primary.used_percent;
+                for window in &snap.windows {
+                    let is_primary = window.slot == Some(WindowSlot::Primary);
+                    let remaining = 100.0 - window.used_percent;
+                    let label = self
+                        .metadata
+                        .map(|m| m.window_label(window))
+                        .or_else(|| window.label.clone())
+                        .unwrap_or_else(|| window.id.clone());
+                    let bar = if is_primary {
+                        UsageBar::new(remaining as f32)
+                    } else {
+                        UsageBar::new(remaining as f32).height(px(4.0))
+                    };
+
                     usage_div = usage_div.child(
                         div()
                             .flex()
                             .items_center()
                             .gap(px(8.0))
-                            .child(div().w(px(60.0)).text_sm().child(self.session_label))
-                            .child(UsageBar::new(remaining as f32).flex_1())
+                            .child(div().w(px(60.0)).text_sm().child(label))
+                            .child(bar.flex_1())
                             .child(
                                 div()
                                     .w(px(45.0))
@@ -126,33 +131,16 @@ impl IntoElement for ProviderCard {
                             ),
                     );
 
-                    if let Some(desc) = &primary.reset_description {
-                        usage_div = usage_div.child(
-                            div()
-                                .text_xs()
-                                .text_color(muted_color)
-                                .child(format!("Resets {}", desc)),
-                        );
-                    }
-                }
-
-                if let Some(secondary) = &snap.secondary {
-                    let remaining = 100.0 - secondary.used_percent;
-                    usage_div = usage_div.child(
-                        div()
-                            .flex()
-                            .items_center()
-                            .gap(px(8.0))
-                            .child(div().w(px(60.0)).text_sm().child(self.weekly_label))
-                            .child(UsageBar::new(remaining as f32).height(px(4.0)).flex_1())
-                            .child(
+                    if is_primary {
+                        if let Some(desc) = &window.reset_description {
+                            usage_div = usage_div.child(
                                 div()
-                                    .w(px(45.0))
-                                    .text_sm()
-                                    .text_right()
-                                    .child(format!("{:.0}%", remaining)),
-                            ),
-                    );
+                                    .text_xs()
+                                    .text_color(muted_color)
+                                    .child(format!("Resets {}", desc)),
+                            );
+                        }
+                    }
                 }
 
                 card = card.child(usage_div);
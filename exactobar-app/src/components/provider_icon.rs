@@ -1,6 +1,7 @@
 //! Provider icon component.
 
 use exactobar_core::ProviderKind;
+use exactobar_providers::ProviderRegistry;
 use gpui::*;
 
 /// Provider icon with brand color.
@@ -37,6 +38,18 @@ impl ProviderIcon {
             ProviderKind::MiniMax => hsla(195.0 / 360.0, 1.0, 0.50, 1.0),
             ProviderKind::Antigravity => hsla(282.0 / 360.0, 1.0, 0.41, 1.0),
             ProviderKind::Synthetic => hsla(168.0 / 360.0, 1.0, 0.40, 1.0), // Teal
+            ProviderKind::Anthropic => hsla(15.0 / 360.0, 0.63, 0.59, 1.0), // Clay
+            ProviderKind::OpenRouter => hsla(239.0 / 360.0, 0.84, 0.67, 1.0), // Indigo
+            ProviderKind::Windsurf => hsla(173.0 / 360.0, 0.89, 0.37, 1.0), // Sea green
+            ProviderKind::LiteLlm => hsla(38.0 / 360.0, 0.92, 0.50, 1.0),   // Amber
+            ProviderKind::Bedrock => hsla(36.0 / 360.0, 1.0, 0.50, 1.0),    // AWS orange
+            ProviderKind::Azure => hsla(206.0 / 360.0, 1.0, 0.42, 1.0),     // Azure blue
+            ProviderKind::Custom(_) => ProviderRegistry::get(self.provider)
+                .map(|desc| {
+                    let (r, g, b) = desc.branding.color.to_rgb8();
+                    rgb((u32::from(r) << 16) | (u32::from(g) << 8) | u32::from(b)).into()
+                })
+                .unwrap_or_else(|| hsla(0.0, 0.0, 0.50, 1.0)),
         }
     }
 
@@ -55,6 +68,17 @@ impl ProviderIcon {
             ProviderKind::MiniMax => "M",
             ProviderKind::Antigravity => "∞",
             ProviderKind::Synthetic => "S",
+            ProviderKind::Anthropic => "A",
+            ProviderKind::OpenRouter => "R",
+            ProviderKind::Windsurf => "W",
+            ProviderKind::LiteLlm => "L",
+            ProviderKind::Bedrock => "B",
+            ProviderKind::Azure => "Z",
+            ProviderKind::Custom(id) => {
+                // Monogram from the display name
+                let name = id.display_name();
+                &name[..name.chars().next().map_or(0, char::len_utf8)]
+            }
         }
     }
 }
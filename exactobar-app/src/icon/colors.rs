@@ -3,7 +3,7 @@
 //! This module contains the [`IconColors`] struct and helper functions
 //! for managing colors in both template (grayscale) and colored modes.
 
-use exactobar_core::ProviderKind;
+use exactobar_core::{ProviderKind, UsageLevel};
 use tiny_skia::{Color, Paint};
 
 /// Color palette for icon rendering.
@@ -40,8 +40,8 @@ impl IconColors {
             track: Color::from_rgba8(80, 80, 80, 180),
             fill_stale: with_alpha(brand, 0.6),
             good: with_alpha(brand, alpha_mult),
-            warning: with_alpha(Color::from_rgba8(255, 193, 7, 255), alpha_mult),
-            danger: with_alpha(Color::from_rgba8(244, 67, 54, 255), alpha_mult),
+            warning: with_alpha(level_color(UsageLevel::Warning), alpha_mult),
+            danger: with_alpha(level_color(UsageLevel::Danger), alpha_mult),
             loading: Color::from_rgba8(150, 150, 150, 200),
         }
     }
@@ -63,9 +63,27 @@ pub fn provider_brand_color(provider: ProviderKind) -> Color {
         ProviderKind::MiniMax => Color::from_rgba8(0, 191, 255, 255), // Deep sky blue
         ProviderKind::Antigravity => Color::from_rgba8(148, 0, 211, 255), // Violet
         ProviderKind::Synthetic => Color::from_rgba8(0, 204, 179, 255), // Teal/cyan
+        ProviderKind::Anthropic => Color::from_rgba8(217, 119, 87, 255), // Anthropic clay
+        ProviderKind::OpenRouter => Color::from_rgba8(99, 102, 241, 255), // Indigo
+        ProviderKind::Windsurf => Color::from_rgba8(10, 181, 163, 255), // Sea green
+        ProviderKind::LiteLlm => Color::from_rgba8(245, 158, 11, 255), // Amber
+        ProviderKind::Bedrock => Color::from_rgba8(255, 153, 0, 255), // AWS orange
+        ProviderKind::Azure => Color::from_rgba8(0, 120, 212, 255),  // Azure blue
+        ProviderKind::Custom(_) => {
+            let (r, g, b) = exactobar_providers::ProviderRegistry::get(provider)
+                .map(|desc| desc.branding.color.to_rgb8())
+                .unwrap_or((128, 128, 128));
+            Color::from_rgba8(r, g, b, 255)
+        }
     }
 }
 
+/// Gets the warning or danger color shared with the status bar outputs.
+fn level_color(level: UsageLevel) -> Color {
+    let (r, g, b) = level.rgb8((128, 128, 128));
+    Color::from_rgba8(r, g, b, 255)
+}
+
 /// Adjusts color alpha.
 pub fn with_alpha(color: Color, alpha: f64) -> Color {
     Color::from_rgba(
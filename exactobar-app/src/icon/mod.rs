@@ -19,7 +19,7 @@ pub use animation::IconAnimationState;
 pub use rendered::RenderedIcon;
 
 use colors::{IconColors, create_paint};
-use exactobar_core::{ProviderKind, StatusIndicator, UsageSnapshot};
+use exactobar_core::{ProviderKind, StatusIndicator, UsageLevel, UsageSnapshot};
 use tiny_skia::*;
 
 // ============================================================================
@@ -129,7 +129,7 @@ impl IconRenderer {
                 // fill_percent = used percentage (bar fills as usage increases)
                 let fill_percent = snapshot
                     .as_ref()
-                    .and_then(|s| s.primary.as_ref())
+                    .and_then(|s| s.primary())
                     .map(|w| w.used_percent as f32)
                     .unwrap_or(50.0);
                 let blink = animation.map(|a| a.blink_phase).unwrap_or(0.0);
@@ -246,7 +246,7 @@ impl IconRenderer {
 
         // Primary bar (session usage) - thicker
         // Fill = used percentage (bar fills left→right as usage increases)
-        if let Some(primary) = &snapshot.primary {
+        if let Some(primary) = snapshot.primary() {
             let used = primary.used_percent as f32;
             self.draw_bar(
                 pixmap,
@@ -272,7 +272,7 @@ impl IconRenderer {
 
         // Secondary bar (weekly - hairline)
         // Fill = used percentage (bar fills left→right as usage increases)
-        if let Some(secondary) = &snapshot.secondary {
+        if let Some(secondary) = snapshot.secondary() {
             let used = secondary.used_percent as f32;
             self.draw_bar(
                 pixmap,
@@ -539,12 +539,10 @@ impl IconRenderer {
     /// Returns color based on USAGE percentage (not remaining!).
     /// Green = low usage (good), Red = high usage (warning)
     pub(crate) fn percent_to_color(&self, used_percent: f32, colors: &IconColors) -> Color {
-        if used_percent < 50.0 {
-            colors.good // Green - low usage is good!
-        } else if used_percent < 80.0 {
-            colors.warning // Yellow/Orange - moderate usage
-        } else {
-            colors.danger // Red - high usage, approaching limit!
+        match UsageLevel::from_used_percent(f64::from(used_percent)) {
+            UsageLevel::Good => colors.good,       // Green - low usage is good!
+            UsageLevel::Warning => colors.warning, // Yellow/Orange - moderate usage
+            UsageLevel::Danger => colors.danger,   // Red - high usage, approaching limit!
         }
     }
 }
@@ -22,8 +22,8 @@ fn test_render_with_snapshot() {
     let renderer = IconRenderer::new();
 
     let mut snapshot = UsageSnapshot::new();
-    snapshot.primary = Some(UsageWindow::new(25.0));
-    snapshot.secondary = Some(UsageWindow::new(50.0));
+    snapshot.set_primary(Some(UsageWindow::new(25.0)));
+    snapshot.set_secondary(Some(UsageWindow::new(50.0)));
 
     let icon = renderer.render(ProviderKind::Claude, Some(&snapshot), false, None, None);
     assert!(!icon.data.is_empty());
@@ -33,7 +33,7 @@ fn test_render_with_snapshot() {
 fn test_render_stale() {
     let renderer = IconRenderer::new();
     let mut snapshot = UsageSnapshot::new();
-    snapshot.primary = Some(UsageWindow::new(25.0));
+    snapshot.set_primary(Some(UsageWindow::new(25.0)));
 
     let icon = renderer.render(ProviderKind::Claude, Some(&snapshot), true, None, None);
     assert!(!icon.data.is_empty());
@@ -116,7 +116,7 @@ fn test_render_codex_eye_with_usage() {
     let renderer = IconRenderer::new();
 
     let mut snapshot = UsageSnapshot::new();
-    snapshot.primary = Some(UsageWindow::new(30.0)); // 70% remaining
+    snapshot.set_primary(Some(UsageWindow::new(30.0))); // 70% remaining
 
     let icon = renderer.render(ProviderKind::Codex, Some(&snapshot), false, None, None);
     assert!(!icon.data.is_empty());
@@ -169,7 +169,7 @@ fn test_render_codex_eye_colored_mode() {
 fn test_render_codex_eye_stale() {
     let renderer = IconRenderer::new();
     let mut snapshot = UsageSnapshot::new();
-    snapshot.primary = Some(UsageWindow::new(50.0));
+    snapshot.set_primary(Some(UsageWindow::new(50.0)));
 
     let icon = renderer.render(ProviderKind::Codex, Some(&snapshot), true, None, None);
     assert!(!icon.data.is_empty());
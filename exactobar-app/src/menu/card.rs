@@ -3,8 +3,9 @@
 //! The MenuCard shows provider identity, status, usage metrics,
 //! and action buttons in a cohesive card layout.
 
-use exactobar_core::{ProviderKind, UsageSnapshot};
+use exactobar_core::{ProviderKind, ProviderMetadata, UsageSnapshot};
 use exactobar_providers::ProviderRegistry;
+use exactobar_store::Settings;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 
@@ -30,8 +31,12 @@ pub struct MenuCardData {
     pub error: Option<String>,
     /// Install hint when CLI is missing
     pub install_hint: Option<InstallHint>,
-    pub session_label: &'static str,
-    pub weekly_label: &'static str,
+    /// Provider metadata used to label usage windows
+    pub metadata: Option<&'static ProviderMetadata>,
+    /// Whether to show credits and extra usage
+    pub show_credits: bool,
+    /// Settings snapshot used for currency display
+    pub settings: Settings,
     /// Whether to show "X% used" instead of "X% remaining"
     pub show_used: bool,
     /// Whether to show "Resets at 3:00 PM" instead of "Resets in 2h 30m"
@@ -50,18 +55,14 @@ impl MenuCardData {
         let settings = state.settings.read(cx).settings();
         let show_used = settings.usage_bars_show_used;
         let show_absolute = settings.reset_times_show_absolute;
+        let show_credits = settings.show_optional_credits_and_extra_usage;
+        let settings = settings.clone();
 
         let provider_name = descriptor
             .map(|d| d.display_name().to_string())
             .unwrap_or_else(|| format!("{:?}", provider));
 
-        let session_label = descriptor
-            .map(|d| d.metadata.session_label.as_str())
-            .unwrap_or("Session");
-
-        let weekly_label = descriptor
-            .map(|d| d.metadata.weekly_label.as_str())
-            .unwrap_or("Weekly");
+        let metadata = descriptor.map(|d| &d.metadata);
 
         // Extract identity info from snapshot
         let identity = snapshot.as_ref().and_then(|s| s.identity.as_ref());
@@ -83,8 +84,9 @@ impl MenuCardData {
             is_refreshing,
             error,
             install_hint,
-            session_label,
-            weekly_label,
+            metadata,
+            show_credits,
+            settings,
             show_used,
             show_absolute,
         }
@@ -138,13 +140,16 @@ impl IntoElement for MenuCard {
             });
         } else if let Some(ref snap) = self.data.snapshot {
             // Usage metrics
-            card = card.child(UsageMetricsSection::new(
-                snap,
-                self.data.session_label,
-                self.data.weekly_label,
-                self.data.show_used,
-                self.data.show_absolute,
-            ));
+            let credits = snap.credits.as_ref().filter(|_| self.data.show_credits);
+            card = card.child(
+                UsageMetricsSection::new(
+                    snap,
+                    self.data.metadata,
+                    self.data.show_used,
+                    self.data.show_absolute,
+                )
+                .with_credits(credits, &self.data.settings),
+            );
         } else if !self.data.is_refreshing {
             card = card.child(PlaceholderSection);
         }
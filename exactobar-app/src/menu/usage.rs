@@ -1,10 +1,12 @@
 //! Usage metrics display components.
 //!
 //! Provides progress bars and usage metric rows for displaying
-//! session, weekly, and premium usage limits.
+//! every usage window a provider reports (session, weekly, premium,
+//! per-model quotas).
 
 use chrono::{DateTime, Local, Utc};
-use exactobar_core::UsageSnapshot;
+use exactobar_core::{Credits, ProviderMetadata, UsageSnapshot, UsageWindow};
+use exactobar_store::Settings;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 
@@ -16,6 +18,8 @@ use crate::theme;
 
 pub struct UsageMetricsSection {
     metrics: Vec<UsageMetric>,
+    /// Credit balance line (e.g., "Credits: $37.50 left of $50.00")
+    credits_line: Option<String>,
 }
 
 struct UsageMetric {
@@ -32,55 +36,91 @@ struct UsageMetric {
 impl UsageMetricsSection {
     pub fn new(
         snapshot: &UsageSnapshot,
-        session_label: &str,
-        weekly_label: &str,
+        metadata: Option<&ProviderMetadata>,
         show_used: bool,
         show_absolute: bool,
     ) -> Self {
-        let mut metrics = Vec::new();
-
-        if let Some(primary) = &snapshot.primary {
-            metrics.push(UsageMetric {
-                title: session_label.to_string(),
-                used_percent: primary.used_percent,
-                resets_at: primary.resets_at,
-                reset_description: primary.reset_description.clone(),
+        let metrics = snapshot
+            .windows
+            .iter()
+            .map(|window| UsageMetric {
+                title: window_title(window, metadata),
+                used_percent: window.used_percent,
+                resets_at: window.resets_at,
+                reset_description: window.reset_description.clone(),
                 show_used,
                 show_absolute,
-            });
-        }
+            })
+            .collect();
 
-        if let Some(secondary) = &snapshot.secondary {
-            metrics.push(UsageMetric {
-                title: weekly_label.to_string(),
-                used_percent: secondary.used_percent,
-                resets_at: secondary.resets_at,
-                reset_description: secondary.reset_description.clone(),
-                show_used,
-                show_absolute,
-            });
+        Self {
+            metrics,
+            credits_line: None,
         }
+    }
 
-        if let Some(tertiary) = &snapshot.tertiary {
-            metrics.push(UsageMetric {
-                title: "Premium".to_string(),
-                used_percent: tertiary.used_percent,
-                resets_at: tertiary.resets_at,
-                reset_description: tertiary.reset_description.clone(),
-                show_used,
-                show_absolute,
-            });
+    /// Adds a credit balance line and, when capped, a spend bar.
+    pub fn with_credits(mut self, credits: Option<&Credits>, settings: &Settings) -> Self {
+        let Some(credits) = credits else {
+            return self;
+        };
+
+        if let Some(spend) = &credits.spend_limit {
+            if let Some(used_percent) = spend.used_percent() {
+                let (show_used, show_absolute) = self
+                    .metrics
+                    .first()
+                    .map(|m| (m.show_used, m.show_absolute))
+                    .unwrap_or((false, false));
+                self.metrics.push(UsageMetric {
+                    title: "Extra usage".to_string(),
+                    used_percent,
+                    resets_at: spend.resets_at,
+                    reset_description: None,
+                    show_used,
+                    show_absolute,
+                });
+            }
         }
 
-        Self { metrics }
+        self.credits_line = Some(format_credits_line(credits, settings));
+        self
     }
 }
 
+/// Formats the credit balance in the display currency (if configured).
+pub fn format_credits_line(credits: &Credits, settings: &Settings) -> String {
+    let remaining = settings.display_amount(&credits.remaining_amount());
+    match credits.total_amount() {
+        Some(total) => format!(
+            "Credits: {} left of {}",
+            remaining,
+            settings.display_amount(&total)
+        ),
+        None => format!("Credits: {}", remaining),
+    }
+}
+
+/// Resolves the row title for a window, falling back to the slot's default
+/// label.
+fn window_title(window: &UsageWindow, metadata: Option<&ProviderMetadata>) -> String {
+    if let Some(metadata) = metadata {
+        return metadata.window_label(window);
+    }
+    if let Some(label) = &window.label {
+        return label.clone();
+    }
+    window.slot.map_or_else(
+        || window.id.clone(),
+        |slot| slot.default_label().to_string(),
+    )
+}
+
 impl IntoElement for UsageMetricsSection {
     type Element = Div;
 
     fn into_element(self) -> Self::Element {
-        if self.metrics.is_empty() {
+        if self.metrics.is_empty() && self.credits_line.is_none() {
             return div();
         }
 
@@ -91,6 +131,14 @@ impl IntoElement for UsageMetricsSection {
             .flex_col()
             .gap(px(10.))
             .children(self.metrics.into_iter().map(UsageMetricRow::new))
+            .when_some(self.credits_line, |el, line| {
+                el.child(
+                    div()
+                        .text_xs()
+                        .text_color(theme::text_secondary())
+                        .child(line),
+                )
+            })
     }
 }
 
@@ -42,7 +42,7 @@ impl NotificationTracker {
         provider: ProviderKind,
         snapshot: &UsageSnapshot,
     ) -> Option<NotificationLevel> {
-        let used_percent = snapshot.primary.as_ref()?.used_percent;
+        let used_percent = snapshot.primary()?.used_percent;
 
         let current_level = if used_percent >= CRITICAL_THRESHOLD {
             NotificationLevel::Critical
@@ -146,7 +146,7 @@ mod tests {
 
     fn make_snapshot(used_percent: f64) -> UsageSnapshot {
         let mut snapshot = UsageSnapshot::new();
-        snapshot.primary = Some(UsageWindow::new(used_percent));
+        snapshot.set_primary(Some(UsageWindow::new(used_percent)));
         snapshot
     }
 
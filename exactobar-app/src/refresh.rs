@@ -12,6 +12,7 @@ use std::time::Duration;
 use exactobar_core::{ProviderKind, UsageSnapshot};
 use exactobar_fetch::FetchContext;
 use exactobar_providers::ProviderRegistry;
+use exactobar_store::{Settings, SettingsStore};
 use gpui::*;
 use smol::Timer;
 use tracing::{debug, error, info};
@@ -69,7 +70,7 @@ pub fn spawn_refresh_task(cx: &mut App) {
         Timer::after(Duration::from_secs(2)).await;
 
         for provider in &initial_providers {
-            refresh_provider(*provider, usage.clone(), &mut cx).await;
+            refresh_provider(*provider, usage.clone(), false, &mut cx).await;
         }
 
         loop {
@@ -99,7 +100,7 @@ pub fn spawn_refresh_task(cx: &mut App) {
 
             if let Some(providers) = Some(providers_result) {
                 for provider in providers {
-                    refresh_provider(provider, usage.clone(), &mut cx).await;
+                    refresh_provider(provider, usage.clone(), false, &mut cx).await;
                 }
             }
         }
@@ -113,14 +114,30 @@ pub fn spawn_refresh_task(cx: &mut App) {
 /// **IMPORTANT**: All fetch operations MUST go through this function!
 /// The fetch/providers libraries use tokio::process::Command which requires
 /// a Tokio runtime. Calling them directly from smol will panic.
-pub async fn fetch_on_tokio(provider: ProviderKind) -> Result<UsageSnapshot, String> {
+///
+/// `manual` is set when the user asked for the refresh, so a running daemon
+/// fetches anew instead of answering from its cache.
+pub async fn fetch_on_tokio(provider: ProviderKind, manual: bool) -> Result<UsageSnapshot, String> {
     let rt = tokio_runtime();
 
     // Use spawn_blocking to run the tokio future on the tokio runtime
     // from within a smol context
     let result = smol::unblock(move || {
         rt.block_on(async move {
-            let ctx = FetchContext::new();
+            // Share the daemon's fetch when one is running
+            if let Some(result) = fetch_via_daemon(provider, manual).await {
+                return result;
+            }
+
+            let settings = match SettingsStore::load_default().await {
+                Ok(store) => store.get().await,
+                Err(_) => Settings::default(),
+            };
+            let ctx = FetchContext::builder()
+                .persist_refreshed_credentials(settings.claude_oauth_write_back_enabled)
+                .web_extras(settings.claude_web_extras_enabled)
+                .provider_options(settings.provider_options())
+                .build();
             if let Some(desc) = ProviderRegistry::get(provider) {
                 let pipeline = desc.build_pipeline(&ctx);
                 let outcome = pipeline.execute(&ctx).await;
@@ -172,8 +189,52 @@ pub async fn fetch_on_tokio(provider: ProviderKind) -> Result<UsageSnapshot, Str
     result
 }
 
-/// Refreshes a single provider.
-async fn refresh_provider(provider: ProviderKind, usage: Entity<UsageModel>, cx: &mut AsyncApp) {
+/// Fetches a provider through a running `exactobar daemon`.
+///
+/// Manual refreshes send the daemon a refresh request; background ones take
+/// whatever it last fetched. Returns `None` if no daemon is listening or the
+/// request failed, in which case the caller fetches in-process.
+async fn fetch_via_daemon(
+    provider: ProviderKind,
+    manual: bool,
+) -> Option<Result<UsageSnapshot, String>> {
+    #[cfg(unix)]
+    {
+        let mut client = exactobar_store::DaemonClient::connect_default().await?;
+        let response = if manual {
+            client.refresh(&[provider]).await
+        } else {
+            client.get(&[provider], None).await
+        };
+        match response {
+            Ok(entries) => {
+                debug!("Provider {:?} fetched through daemon", provider);
+                entries
+                    .into_iter()
+                    .next()
+                    .map(|entry| entry.into_result().map_err(|e| e.message))
+            }
+            Err(e) => {
+                debug!("Daemon request failed, fetching in-process: {}", e);
+                None
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = (provider, manual);
+        None
+    }
+}
+
+/// Refreshes a single provider. `manual` is passed on to [`fetch_on_tokio`].
+async fn refresh_provider(
+    provider: ProviderKind,
+    usage: Entity<UsageModel>,
+    manual: bool,
+    cx: &mut AsyncApp,
+) {
     debug!("Refreshing provider {:?}", provider);
 
     // Mark as refreshing
@@ -183,7 +244,7 @@ async fn refresh_provider(provider: ProviderKind, usage: Entity<UsageModel>, cx:
     });
 
     // Execute fetch on Tokio runtime
-    let result = fetch_on_tokio(provider).await;
+    let result = fetch_on_tokio(provider, manual).await;
 
     // Check if notifications are enabled before we move result
     let notify_enabled = cx.update(|cx| {
@@ -199,11 +260,7 @@ async fn refresh_provider(provider: ProviderKind, usage: Entity<UsageModel>, cx:
         if notify_enabled {
             if let Ok(mut tracker) = NOTIFICATION_TRACKER.lock() {
                 if let Some(level) = tracker.should_notify(provider, snapshot) {
-                    let percent = snapshot
-                        .primary
-                        .as_ref()
-                        .map(|w| w.used_percent)
-                        .unwrap_or(0.0);
+                    let percent = snapshot.primary().map(|w| w.used_percent).unwrap_or(0.0);
                     send_quota_notification(provider, level, percent);
                 }
             }
@@ -234,7 +291,7 @@ pub fn trigger_refresh(cx: &mut App) {
 
     cx.spawn(async move |mut cx| {
         for provider in providers {
-            refresh_provider(provider, usage.clone(), &mut cx).await;
+            refresh_provider(provider, usage.clone(), true, &mut cx).await;
         }
     })
     .detach();
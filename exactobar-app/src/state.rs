@@ -66,10 +66,18 @@ impl AppState {
     pub fn init(cx: &mut App) -> Self {
         // Load settings from disk (sync for simplicity at init)
         let settings_store = tokio_runtime().block_on(async {
-            match SettingsStore::load_default().await {
+            let store = match SettingsStore::load_default().await {
                 Ok(store) => store,
                 Err(_) => SettingsStore::new(exactobar_store::default_config_dir()),
+            };
+
+            // Custom providers must be registered before the registry is first used
+            let custom = store.custom_providers().await;
+            if !custom.is_empty() {
+                exactobar_providers::ProviderRegistry::register_custom(&custom);
             }
+
+            store
         });
 
         let settings = cx.new(|_| SettingsModel::new(settings_store));
@@ -132,7 +140,7 @@ impl AppState {
             // Execute fetch on Tokio runtime - MUST use this bridge!
             // Direct pipeline.execute() calls will panic because tokio::process::Command
             // requires a Tokio runtime, but GPUI runs on smol.
-            let result = crate::refresh::fetch_on_tokio(provider).await;
+            let result = crate::refresh::fetch_on_tokio(provider, true).await;
 
             // Update state
             let _ = cx.update_entity(&usage, |model, cx| {
@@ -303,6 +311,12 @@ impl SettingsModel {
         self.save_async();
     }
 
+    /// Sets whether refreshed Claude OAuth credentials are written back.
+    pub fn set_claude_oauth_write_back_enabled(&mut self, value: bool) {
+        self.cached_settings.claude_oauth_write_back_enabled = value;
+        self.save_async();
+    }
+
     /// Sets whether optional credits and extra usage are shown.
     pub fn set_show_optional_credits_and_extra_usage(&mut self, value: bool) {
         self.cached_settings.show_optional_credits_and_extra_usage = value;
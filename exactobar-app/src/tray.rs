@@ -19,6 +19,8 @@ use objc::{class, msg_send, sel, sel_impl};
 use std::sync::Once;
 
 use exactobar_core::{ProviderKind, StatusIndicator};
+#[cfg(target_os = "macos")]
+use exactobar_providers::ProviderRegistry;
 use gpui::*;
 use std::collections::HashMap;
 use std::sync::mpsc::{self, Receiver, Sender};
@@ -70,7 +72,7 @@ fn register_delegate_class() -> &'static Class {
 
         // Add instance variable to store the channel sender pointer
         decl.add_ivar::<*mut std::ffi::c_void>("sender_ptr");
-        // Add instance variable to store the provider (as u8 for simplicity)
+        // Add instance variable to store the provider (see `encode_provider`)
         decl.add_ivar::<u8>("provider_index");
 
         // Add the action method that handles clicks
@@ -85,12 +87,7 @@ fn register_delegate_class() -> &'static Class {
                         let sender: &Sender<StatusItemClickEvent> =
                             &*(sender_ptr as *const Sender<StatusItemClickEvent>);
 
-                        // Decode provider from index (255 = merged/none)
-                        let provider = if provider_index == 255 {
-                            None
-                        } else {
-                            ProviderKind::from_index(provider_index as usize)
-                        };
+                        let provider = decode_provider(provider_index);
 
                         // Send the click event
                         let _ = sender.send(StatusItemClickEvent { provider });
@@ -114,6 +111,39 @@ fn register_delegate_class() -> &'static Class {
         .expect("ExactoBarDelegate class not found after registration - this is a bug")
 }
 
+/// Ivar value for the merged (no provider) status item.
+#[cfg(target_os = "macos")]
+const MERGED_PROVIDER_INDEX: u8 = u8::MAX;
+
+/// Encodes a provider as its position in the registry.
+///
+/// Registry order covers custom providers too, and is fixed once the
+/// registry is in use.
+#[cfg(target_os = "macos")]
+fn encode_provider(provider: Option<ProviderKind>) -> u8 {
+    let Some(provider) = provider else {
+        return MERGED_PROVIDER_INDEX;
+    };
+    ProviderRegistry::kinds()
+        .iter()
+        .position(|&kind| kind == provider)
+        .and_then(|index| u8::try_from(index).ok())
+        .filter(|&index| index != MERGED_PROVIDER_INDEX)
+        .unwrap_or_else(|| {
+            warn!(provider = ?provider, "Provider not in registry, using merged panel");
+            MERGED_PROVIDER_INDEX
+        })
+}
+
+/// Decodes a provider stored by [`encode_provider`].
+#[cfg(target_os = "macos")]
+fn decode_provider(index: u8) -> Option<ProviderKind> {
+    if index == MERGED_PROVIDER_INDEX {
+        return None;
+    }
+    ProviderRegistry::kinds().get(usize::from(index)).copied()
+}
+
 /// Event sent when a status item is clicked.
 #[derive(Debug, Clone)]
 struct StatusItemClickEvent {
@@ -132,9 +162,7 @@ fn create_delegate(sender: &Sender<StatusItemClickEvent>, provider: Option<Provi
         let sender_ptr = sender as *const Sender<StatusItemClickEvent> as *mut std::ffi::c_void;
         (*delegate).set_ivar("sender_ptr", sender_ptr);
 
-        // Store the provider index (255 = none/merged)
-        let provider_index: u8 = provider.map(|p| p.to_index() as u8).unwrap_or(255);
-        (*delegate).set_ivar("provider_index", provider_index);
+        (*delegate).set_ivar("provider_index", encode_provider(provider));
 
         delegate
     }
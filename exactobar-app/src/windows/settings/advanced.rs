@@ -15,6 +15,7 @@ pub struct AdvancedPane {
     cost_usage_enabled: bool,
     random_blink_enabled: bool,
     claude_web_extras_enabled: bool,
+    claude_oauth_write_back_enabled: bool,
     show_optional_credits_and_extra_usage: bool,
     openai_web_access_enabled: bool,
     theme: SettingsTheme,
@@ -32,6 +33,7 @@ impl AdvancedPane {
             cost_usage_enabled: settings.cost_usage_enabled,
             random_blink_enabled: settings.random_blink_enabled,
             claude_web_extras_enabled: settings.claude_web_extras_enabled,
+            claude_oauth_write_back_enabled: settings.claude_oauth_write_back_enabled,
             show_optional_credits_and_extra_usage: settings.show_optional_credits_and_extra_usage,
             openai_web_access_enabled: settings.openai_web_access_enabled,
             theme,
@@ -344,6 +346,42 @@ impl IntoElement for AdvancedPane {
                             }),
                     ),
             )
+            // Claude OAuth Write-back
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .py(px(12.0))
+                    .border_b_1()
+                    .border_color(theme.border)
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap(px(2.0))
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .font_weight(FontWeight::MEDIUM)
+                                    .child("Save Refreshed Claude Login"),
+                            )
+                            .child(div().text_xs().text_color(theme.text_muted).child(
+                                "Write refreshed OAuth tokens back to the Claude CLI's credentials",
+                            )),
+                    )
+                    .child(
+                        Toggle::new("toggle-claude-oauth-write-back")
+                            .checked(self.claude_oauth_write_back_enabled)
+                            .on_toggle(|enabled, cx| {
+                                cx.update_global::<AppState, _>(|state, cx| {
+                                    state.settings.update(cx, |model, _| {
+                                        model.set_claude_oauth_write_back_enabled(enabled);
+                                    });
+                                });
+                            }),
+                    ),
+            )
             // Show Credits & Extras
             .child(
                 div()
@@ -130,7 +130,77 @@ pub fn detect_provider_status(provider: ProviderKind) -> ProviderStatus {
             }
             return ProviderStatus::AuthRequired;
         }
-        ProviderKind::VertexAI | ProviderKind::Antigravity => {
+        ProviderKind::OpenRouter => {
+            if exactobar_store::has_api_key("openrouter")
+                || std::env::var("OPENROUTER_API_KEY").is_ok()
+            {
+                return ProviderStatus::Available;
+            }
+            return ProviderStatus::AuthRequired;
+        }
+        ProviderKind::LiteLlm => {
+            // Virtual key plus proxy URL (Settings or env var)
+            if exactobar_store::has_api_key("litellm") || std::env::var("LITELLM_API_KEY").is_ok() {
+                return ProviderStatus::Available;
+            }
+            return ProviderStatus::AuthRequired;
+        }
+        ProviderKind::Anthropic => {
+            // Admin key from Keychain or env var
+            if exactobar_store::has_api_key("anthropic")
+                || std::env::var("ANTHROPIC_ADMIN_KEY").is_ok()
+            {
+                return ProviderStatus::Available;
+            }
+            return ProviderStatus::AuthRequired;
+        }
+        ProviderKind::Bedrock => {
+            // AWS credential chain: env vars or ~/.aws profiles/SSO cache
+            let has_aws_dir = std::env::var("HOME")
+                .is_ok_and(|home| std::path::Path::new(&home).join(".aws").exists());
+            if std::env::var("AWS_ACCESS_KEY_ID").is_ok()
+                || std::env::var("AWS_PROFILE").is_ok()
+                || has_aws_dir
+            {
+                return ProviderStatus::Available;
+            }
+            return ProviderStatus::AuthRequired;
+        }
+        ProviderKind::Azure => {
+            // Service principal env vars, or an az CLI login
+            let has_az_cache = std::env::var("HOME").is_ok_and(|home| {
+                std::path::Path::new(&home)
+                    .join(".azure/msal_token_cache.json")
+                    .exists()
+            });
+            if std::env::var("AZURE_CLIENT_ID").is_ok()
+                || has_az_cache
+                || which::which("az").is_ok()
+            {
+                return ProviderStatus::Available;
+            }
+            return ProviderStatus::AuthRequired;
+        }
+        ProviderKind::Custom(_) => {
+            // User-defined endpoints and commands: check the configured secret
+            let Some(custom) = ProviderRegistry::custom_provider(provider) else {
+                return ProviderStatus::Unknown;
+            };
+            if let Some(command) = &custom.config().command {
+                let program = exactobar_providers::custom::command::program_path(command);
+                if which::which(program).is_err() {
+                    return ProviderStatus::CliMissing;
+                }
+            }
+            if custom.uses_cookies() {
+                return ProviderStatus::Unknown;
+            }
+            if custom.secret().is_ok() {
+                return ProviderStatus::Available;
+            }
+            return ProviderStatus::AuthRequired;
+        }
+        ProviderKind::VertexAI | ProviderKind::Antigravity | ProviderKind::Windsurf => {
             // These use local credentials/probes
             return ProviderStatus::Unknown;
         }
@@ -156,6 +226,13 @@ pub fn get_install_command(provider: ProviderKind) -> &'static str {
         ProviderKind::Kiro => "npm install -g kiro-cli",
         ProviderKind::Synthetic => "Configure API key in Settings",
         ProviderKind::Zai => "Configure API key in Settings",
+        ProviderKind::Anthropic => "Configure admin API key in Settings",
+        ProviderKind::OpenRouter => "Configure API key in Settings",
+        ProviderKind::Windsurf => "Install Windsurf from https://windsurf.com",
+        ProviderKind::LiteLlm => "Configure proxy URL and virtual key in Settings",
+        ProviderKind::Bedrock => "brew install awscli && aws configure",
+        ProviderKind::Azure => "brew install azure-cli && az login",
+        ProviderKind::Custom(_) => "Edit custom_providers in settings.json",
         _ => "See provider documentation",
     }
 }
@@ -171,7 +248,12 @@ pub fn get_install_command(provider: ProviderKind) -> &'static str {
 pub fn provider_needs_api_key(provider: ProviderKind) -> bool {
     matches!(
         provider,
-        ProviderKind::Synthetic | ProviderKind::Zai | ProviderKind::Codex
+        ProviderKind::Synthetic
+            | ProviderKind::Zai
+            | ProviderKind::Codex
+            | ProviderKind::Anthropic
+            | ProviderKind::OpenRouter
+            | ProviderKind::LiteLlm
     )
 }
 
@@ -184,6 +266,9 @@ pub fn provider_api_key_name(provider: ProviderKind) -> &'static str {
         ProviderKind::Synthetic => "synthetic",
         ProviderKind::Zai => "zai",
         ProviderKind::Codex => "codex",
+        ProviderKind::Anthropic => "anthropic",
+        ProviderKind::OpenRouter => "openrouter",
+        ProviderKind::LiteLlm => "litellm",
         _ => "",
     }
 }
@@ -207,6 +292,9 @@ pub fn provider_has_api_key(provider: ProviderKind) -> bool {
         ProviderKind::Synthetic => std::env::var("SYNTHETIC_API_KEY").is_ok(),
         ProviderKind::Zai => std::env::var("ZAI_API_KEY").is_ok(),
         ProviderKind::Codex => std::env::var("OPENAI_API_KEY").is_ok(),
+        ProviderKind::Anthropic => std::env::var("ANTHROPIC_ADMIN_KEY").is_ok(),
+        ProviderKind::OpenRouter => std::env::var("OPENROUTER_API_KEY").is_ok(),
+        ProviderKind::LiteLlm => std::env::var("LITELLM_API_KEY").is_ok(),
         _ => false,
     }
 }
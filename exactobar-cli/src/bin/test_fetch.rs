@@ -71,7 +71,7 @@ async fn main() {
 
                 println!("Source: {:?}", snapshot.fetch_source);
 
-                if let Some(primary) = &snapshot.primary {
+                if let Some(primary) = snapshot.primary() {
                     println!();
                     println!("Session Usage:");
                     println!("  Used: {:.1}%", primary.used_percent);
@@ -88,7 +88,7 @@ async fn main() {
                     }
                 }
 
-                if let Some(secondary) = &snapshot.secondary {
+                if let Some(secondary) = snapshot.secondary() {
                     println!();
                     println!("Weekly Usage:");
                     println!("  Used: {:.1}%", secondary.used_percent);
@@ -98,7 +98,7 @@ async fn main() {
                     }
                 }
 
-                if let Some(tertiary) = &snapshot.tertiary {
+                if let Some(tertiary) = snapshot.tertiary() {
                     println!();
                     println!("Opus/Sonnet Usage:");
                     println!("  Used: {:.1}%", tertiary.used_percent);
@@ -161,7 +161,7 @@ async fn main() {
 
                 println!("Source: {:?}", snapshot.fetch_source);
 
-                if let Some(primary) = &snapshot.primary {
+                if let Some(primary) = snapshot.primary() {
                     println!();
                     println!("5-Hour Usage:");
                     println!("  Used: {:.1}%", primary.used_percent);
@@ -171,7 +171,7 @@ async fn main() {
                     }
                 }
 
-                if let Some(secondary) = &snapshot.secondary {
+                if let Some(secondary) = snapshot.secondary() {
                     println!();
                     println!("Weekly Usage:");
                     println!("  Used: {:.1}%", secondary.used_percent);
@@ -182,7 +182,7 @@ async fn main() {
                 }
 
                 // Credits would be in tertiary window for Codex
-                if let Some(tertiary) = &snapshot.tertiary {
+                if let Some(tertiary) = snapshot.tertiary() {
                     println!();
                     println!("Credits/Additional:");
                     println!("  Used: {:.1}%", tertiary.used_percent);
@@ -0,0 +1,101 @@
+//! Bar command - modules for Waybar, i3blocks, Polybar and tmux.
+
+use anyhow::Result;
+use clap::Args;
+use std::time::Duration;
+use tracing::debug;
+
+use crate::Cli;
+use crate::commands::serve::{fetch_context, refresh_interval, served_providers};
+use crate::commands::usage::{fetch_with_refresher, load_settings};
+use crate::output::{BarFormat, BarFormatter};
+use crate::server::Refresher;
+
+/// Polling interval for `--watch` when refreshes are manual.
+const DEFAULT_WATCH_SECS: u64 = 60;
+
+/// Arguments for the bar command.
+#[derive(Args)]
+pub struct BarArgs {
+    /// Status bar protocol.
+    #[arg(value_enum)]
+    pub protocol: BarFormat,
+
+    /// Keep running and print a new line whenever usage changes.
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Refresh interval in seconds for --watch without a daemon.
+    #[arg(long, short)]
+    pub interval: Option<u64>,
+
+    /// Request timeout in seconds for each provider fetch.
+    #[arg(long, default_value = "60")]
+    pub timeout: u64,
+}
+
+/// Runs the bar command.
+pub async fn run(args: &BarArgs, cli: &Cli) -> Result<()> {
+    let settings = load_settings().await.unwrap_or_default();
+    let providers = served_providers(cli, &settings)?;
+    let refresher = Refresher::new(providers, fetch_context(&settings, args.timeout), false);
+    let formatter = BarFormatter::new(args.protocol).with_continuous(args.watch);
+    let use_daemon = !cli.no_daemon;
+
+    if !args.watch {
+        let results = fetch_with_refresher(&refresher, use_daemon).await;
+        println!("{}", formatter.render(&results));
+        return Ok(());
+    }
+
+    let mut last = String::new();
+
+    #[cfg(unix)]
+    if use_daemon {
+        follow_daemon(&refresher, &formatter, &mut last).await;
+    }
+
+    let interval = refresh_interval(args.interval, &settings)
+        .unwrap_or(Duration::from_secs(DEFAULT_WATCH_SECS));
+    loop {
+        let results = fetch_with_refresher(&refresher, use_daemon).await;
+        emit(&formatter.render(&results), &mut last);
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Prints `line` unless it repeats the previous one.
+fn emit(line: &str, last: &mut String) {
+    if line != last {
+        println!("{}", line);
+        *last = line.to_string();
+    }
+}
+
+/// Prints every daemon update until the daemon goes away.
+#[cfg(unix)]
+async fn follow_daemon(refresher: &Refresher, formatter: &BarFormatter, last: &mut String) {
+    let Some(client) = exactobar_store::DaemonClient::connect_default().await else {
+        return;
+    };
+    let mut updates = match client.subscribe(refresher.providers()).await {
+        Ok(updates) => updates,
+        Err(e) => {
+            debug!(error = %e, "Subscribe failed, polling instead");
+            return;
+        }
+    };
+
+    while let Ok(Some(entries)) = updates.next().await {
+        // Skip updates that only mark a refresh as started.
+        if entries.iter().any(|e| e.refreshing) {
+            continue;
+        }
+        let results: Vec<_> = entries
+            .into_iter()
+            .map(|entry| (entry.provider, entry.into_result()))
+            .collect();
+        emit(&formatter.render(&results), last);
+    }
+    debug!("Daemon went away, polling instead");
+}
@@ -0,0 +1,54 @@
+//! Calendar command - iCalendar file of upcoming quota resets.
+//!
+//! `exactobar serve` exposes the same feed at `/calendar.ics` for calendar
+//! apps to subscribe to.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use clap::Args;
+use std::path::PathBuf;
+use tracing::warn;
+
+use crate::Cli;
+use crate::commands::serve::{fetch_context, served_providers};
+use crate::commands::usage::{fetch_with_refresher, load_settings};
+use crate::server::{Refresher, calendar};
+
+/// Arguments for the calendar command.
+#[derive(Args)]
+pub struct CalendarArgs {
+    /// Write the calendar to this file instead of stdout.
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+
+    /// Request timeout in seconds for each provider fetch.
+    #[arg(long, default_value = "60")]
+    pub timeout: u64,
+}
+
+/// Runs the calendar command.
+pub async fn run(args: &CalendarArgs, cli: &Cli) -> Result<()> {
+    let settings = load_settings().await.unwrap_or_default();
+    let providers = served_providers(cli, &settings)?;
+    let refresher = Refresher::new(providers, fetch_context(&settings, args.timeout), false);
+    let results = fetch_with_refresher(&refresher, !cli.no_daemon).await;
+
+    let mut snapshots = Vec::new();
+    for (provider, result) in &results {
+        match result {
+            Ok(snapshot) => snapshots.push((*provider, snapshot)),
+            Err(e) => warn!(provider = ?provider, error = %e, "Skipping provider"),
+        }
+    }
+    if snapshots.is_empty() && !results.is_empty() {
+        anyhow::bail!("No provider returned usage");
+    }
+
+    let ics = calendar::render(&snapshots, Utc::now());
+    match &args.output {
+        Some(path) => std::fs::write(path, ics)
+            .with_context(|| format!("Failed to write {}", path.display()))?,
+        None => print!("{}", ics),
+    }
+    Ok(())
+}
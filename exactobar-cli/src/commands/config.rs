@@ -1,9 +1,14 @@
 //! Config command - manage configuration.
 
 use anyhow::Result;
-use clap::{Args, Subcommand};
+use clap::{Args, Subcommand, ValueEnum};
 use exactobar_providers::ProviderRegistry;
-use exactobar_store::{SettingsStore, default_config_dir, default_settings_path};
+use exactobar_providers::custom::CustomProvider;
+use exactobar_store::{
+    CustomAuth, CustomCommand, CustomFieldPaths, CustomProviderConfig, SettingsStore,
+    default_config_dir, default_settings_path,
+};
+use std::collections::BTreeMap;
 use tracing::info;
 
 use crate::output::JsonFormatter;
@@ -43,10 +48,156 @@ pub enum ConfigAction {
         cadence: String,
     },
 
+    /// Set or clear a provider's API base URL (self-hosted providers).
+    BaseUrl {
+        /// Provider to configure.
+        provider: String,
+
+        /// Base URL (e.g., http://localhost:4000). Omit to clear.
+        url: Option<String>,
+    },
+
+    /// Set or clear a provider's cloud resource ID (e.g., Azure OpenAI).
+    Resource {
+        /// Provider to configure.
+        provider: String,
+
+        /// Resource ID (e.g., /subscriptions/.../accounts/name). Omit to clear.
+        id: Option<String>,
+    },
+
+    /// Manage user-defined HTTP/JSON providers.
+    Custom {
+        #[command(subcommand)]
+        action: CustomAction,
+    },
+
     /// Reset to defaults.
     Reset,
 }
 
+/// Custom provider subcommands.
+#[derive(Subcommand)]
+pub enum CustomAction {
+    /// List custom providers.
+    List,
+
+    /// Add or replace a custom provider.
+    Add(Box<CustomAddArgs>),
+
+    /// Remove a custom provider.
+    Remove {
+        /// Provider ID.
+        id: String,
+    },
+}
+
+/// How a custom provider authenticates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum CustomAuthKind {
+    /// No authentication.
+    #[default]
+    None,
+    /// Bearer token from --secret-env or the keychain.
+    Bearer,
+    /// Named header (--header-name) with a secret from --secret-env or the keychain.
+    Header,
+    /// Browser cookies for --cookie-domain.
+    Cookie,
+}
+
+/// Arguments for `config custom add`.
+#[derive(Args)]
+pub struct CustomAddArgs {
+    /// Provider ID, used as its CLI name (e.g., gateway).
+    pub id: String,
+
+    /// Display name.
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// Usage endpoint URL (fetched with GET).
+    #[arg(long, required_unless_present = "command", conflicts_with = "command")]
+    pub url: Option<String>,
+
+    /// Command that prints UsageSnapshot JSON, instead of --url.
+    #[arg(long)]
+    pub command: Option<String>,
+
+    /// Argument for --command (repeatable).
+    #[arg(
+        long = "arg",
+        value_name = "ARG",
+        requires = "command",
+        allow_hyphen_values = true
+    )]
+    pub args: Vec<String>,
+
+    /// Environment variable for --command as KEY=VALUE (repeatable).
+    #[arg(long = "env", value_name = "KEY=VALUE", requires = "command")]
+    pub env: Vec<String>,
+
+    /// Timeout for --command in seconds.
+    #[arg(long, requires = "command")]
+    pub timeout: Option<u64>,
+
+    /// Authentication method.
+    #[arg(long, value_enum, default_value_t = CustomAuthKind::None)]
+    pub auth: CustomAuthKind,
+
+    /// Environment variable holding the token or header value.
+    #[arg(long)]
+    pub secret_env: Option<String>,
+
+    /// Keychain account holding the token or header value (default: the ID).
+    #[arg(long)]
+    pub keychain: Option<String>,
+
+    /// Header name for --auth header (e.g., X-Api-Key).
+    #[arg(long)]
+    pub header_name: Option<String>,
+
+    /// Cookie domain for --auth cookie.
+    #[arg(long)]
+    pub cookie_domain: Option<String>,
+
+    /// Path to the amount used (e.g., $.usage.used).
+    #[arg(long)]
+    pub used: Option<String>,
+
+    /// Path to the limit.
+    #[arg(long)]
+    pub limit: Option<String>,
+
+    /// Path to the percent used (0-100).
+    #[arg(long)]
+    pub percent: Option<String>,
+
+    /// Path to the reset time (RFC 3339 or Unix time).
+    #[arg(long)]
+    pub resets_at: Option<String>,
+
+    /// Path to the remaining credits.
+    #[arg(long)]
+    pub credits: Option<String>,
+
+    /// Brand color (#RRGGBB).
+    #[arg(long)]
+    pub color: Option<String>,
+
+    /// Usage window label.
+    #[arg(long)]
+    pub label: Option<String>,
+
+    /// Unit of used/limit (e.g., requests).
+    #[arg(long)]
+    pub unit: Option<String>,
+
+    /// Dashboard URL.
+    #[arg(long)]
+    pub dashboard: Option<String>,
+}
+
 /// Runs the config command.
 pub async fn run(args: &ConfigArgs, cli: &Cli) -> Result<()> {
     match &args.action {
@@ -55,6 +206,17 @@ pub async fn run(args: &ConfigArgs, cli: &Cli) -> Result<()> {
         ConfigAction::Enable { provider } => enable_provider(provider, cli).await,
         ConfigAction::Disable { provider } => disable_provider(provider, cli).await,
         ConfigAction::Refresh { cadence } => set_refresh(cadence, cli).await,
+        ConfigAction::BaseUrl { provider, url } => {
+            set_base_url(provider, url.as_deref(), cli).await
+        }
+        ConfigAction::Resource { provider, id } => {
+            set_resource_id(provider, id.as_deref(), cli).await
+        }
+        ConfigAction::Custom { action } => match action {
+            CustomAction::List => list_custom(cli).await,
+            CustomAction::Add(add) => add_custom(add, cli).await,
+            CustomAction::Remove { id } => remove_custom(id, cli).await,
+        },
         ConfigAction::Reset => reset_config(cli).await,
     }
 }
@@ -165,6 +327,192 @@ async fn set_refresh(cadence: &str, _cli: &Cli) -> Result<()> {
     Ok(())
 }
 
+async fn set_base_url(name: &str, url: Option<&str>, _cli: &Cli) -> Result<()> {
+    let desc = ProviderRegistry::get_by_cli_name(name)
+        .ok_or_else(|| anyhow::anyhow!("Unknown provider: {}", name))?;
+
+    let url = url.map(str::trim).filter(|u| !u.is_empty());
+    if let Some(url) = url {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            anyhow::bail!("Base URL must start with http:// or https://: {}", url);
+        }
+    }
+
+    let store = SettingsStore::load_default().await?;
+    store.set_base_url(desc.id, url.map(String::from)).await;
+    store.save().await?;
+
+    if let Some(url) = url {
+        info!(provider = %desc.display_name(), url, "Base URL updated");
+        println!("{} base URL set to: {}", desc.display_name(), url);
+    } else {
+        info!(provider = %desc.display_name(), "Base URL cleared");
+        println!("{} base URL cleared", desc.display_name());
+    }
+
+    Ok(())
+}
+
+async fn set_resource_id(name: &str, id: Option<&str>, _cli: &Cli) -> Result<()> {
+    let desc = ProviderRegistry::get_by_cli_name(name)
+        .ok_or_else(|| anyhow::anyhow!("Unknown provider: {}", name))?;
+
+    let id = id.map(str::trim).filter(|i| !i.is_empty());
+    if let Some(id) = id {
+        if !id.starts_with('/') {
+            anyhow::bail!("Resource ID must start with '/': {}", id);
+        }
+    }
+
+    let store = SettingsStore::load_default().await?;
+    store.set_resource_id(desc.id, id.map(String::from)).await;
+    store.save().await?;
+
+    if let Some(id) = id {
+        info!(provider = %desc.display_name(), id, "Resource ID updated");
+        println!("{} resource set to: {}", desc.display_name(), id);
+    } else {
+        info!(provider = %desc.display_name(), "Resource ID cleared");
+        println!("{} resource cleared", desc.display_name());
+    }
+
+    Ok(())
+}
+
+async fn list_custom(cli: &Cli) -> Result<()> {
+    let store = SettingsStore::load_default().await?;
+    let configs = store.custom_providers().await;
+
+    match cli.format {
+        OutputFormat::Text => {
+            if configs.is_empty() {
+                println!("No custom providers");
+                return Ok(());
+            }
+            for config in &configs {
+                let status = match CustomProvider::new(config.clone()) {
+                    Ok(_) => "ok".to_string(),
+                    Err(e) => format!("invalid: {}", e),
+                };
+                println!("{:<15} {} ({})", config.id, config.display_name(), status);
+                match &config.command {
+                    Some(command) => {
+                        let line = std::iter::once(&command.program)
+                            .chain(&command.args)
+                            .map(String::as_str)
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        println!("  $ {}", line);
+                    }
+                    None => println!("  {}", config.url),
+                }
+            }
+        }
+        OutputFormat::Json => {
+            let formatter = JsonFormatter::new(cli.pretty);
+            println!("{}", formatter.format(&configs)?);
+        }
+    }
+
+    Ok(())
+}
+
+async fn add_custom(args: &CustomAddArgs, _cli: &Cli) -> Result<()> {
+    let auth = match args.auth {
+        CustomAuthKind::None => CustomAuth::None,
+        CustomAuthKind::Bearer => CustomAuth::Bearer {
+            env: args.secret_env.clone(),
+            keychain: args.keychain.clone(),
+        },
+        CustomAuthKind::Header => CustomAuth::Header {
+            name: args
+                .header_name
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("--auth header needs --header-name"))?,
+            env: args.secret_env.clone(),
+            keychain: args.keychain.clone(),
+        },
+        CustomAuthKind::Cookie => CustomAuth::Cookie {
+            domain: args
+                .cookie_domain
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("--auth cookie needs --cookie-domain"))?,
+        },
+    };
+
+    let command = match &args.command {
+        Some(program) => {
+            let mut env = BTreeMap::new();
+            for pair in &args.env {
+                let (key, value) = pair
+                    .split_once('=')
+                    .ok_or_else(|| anyhow::anyhow!("--env expects KEY=VALUE, got: {}", pair))?;
+                env.insert(key.to_string(), value.to_string());
+            }
+            Some(CustomCommand {
+                program: program.trim().to_string(),
+                args: args.args.clone(),
+                env,
+                timeout_secs: args.timeout,
+            })
+        }
+        None => None,
+    };
+
+    let config = CustomProviderConfig {
+        id: args.id.trim().to_string(),
+        name: args.name.clone().unwrap_or_else(|| args.id.clone()),
+        color: args.color.clone(),
+        url: args.url.as_deref().unwrap_or_default().trim().to_string(),
+        command,
+        auth,
+        headers: BTreeMap::new(),
+        fields: CustomFieldPaths {
+            used: args.used.clone(),
+            limit: args.limit.clone(),
+            percent: args.percent.clone(),
+            resets_at: args.resets_at.clone(),
+            credits: args.credits.clone(),
+        },
+        window_label: args.label.clone(),
+        unit: args.unit.clone(),
+        dashboard_url: args.dashboard.clone(),
+    };
+
+    // Validates the definition and its paths
+    let provider = CustomProvider::new(config.clone())?;
+    if ProviderRegistry::get_by_cli_name(&config.id).is_some_and(|d| !d.id.is_custom()) {
+        anyhow::bail!("'{}' is already a built-in provider name", config.id);
+    }
+
+    let store = SettingsStore::load_default().await?;
+    store.set_custom_provider(config).await;
+    store.set_provider_enabled(provider.kind(), true).await;
+    store.save().await?;
+
+    info!(provider = %provider.kind().display_name(), "Custom provider saved");
+    println!(
+        "Saved custom provider: {} ({})",
+        provider.kind().display_name(),
+        provider.kind().cli_name()
+    );
+
+    Ok(())
+}
+
+async fn remove_custom(id: &str, _cli: &Cli) -> Result<()> {
+    let store = SettingsStore::load_default().await?;
+    if !store.remove_custom_provider(id).await {
+        anyhow::bail!("No custom provider with ID: {}", id);
+    }
+    store.save().await?;
+
+    info!(id, "Custom provider removed");
+    println!("Removed custom provider: {}", id);
+
+    Ok(())
+}
+
 async fn reset_config(_cli: &Cli) -> Result<()> {
     let path = default_settings_path();
 
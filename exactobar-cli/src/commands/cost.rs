@@ -45,9 +45,22 @@ pub async fn run(args: &CostArgs, cli: &Cli) -> Result<()> {
     let providers = parse_cost_providers(&args.provider)?;
 
     // Scan logs for each provider
+    let results = scan_costs(&providers, args.days)?;
+
+    // Output results
+    output_cost_results(&results, args, cli)?;
+
+    Ok(())
+}
+
+/// Scans the local logs of each provider that tracks token cost.
+pub(crate) fn scan_costs(
+    providers: &[ProviderKind],
+    days: u32,
+) -> Result<HashMap<ProviderKind, CostUsageSnapshot>> {
     let mut results: HashMap<ProviderKind, CostUsageSnapshot> = HashMap::new();
 
-    for provider in &providers {
+    for provider in providers {
         let desc = ProviderRegistry::get(*provider);
         if desc.is_none() {
             continue;
@@ -64,7 +77,7 @@ pub async fn run(args: &CostArgs, cli: &Cli) -> Result<()> {
                 if log_dir.exists() {
                     debug!(provider = ?provider, dir = %log_dir.display(), "Scanning logs");
 
-                    let snapshot = scan_logs(&log_dir, args.days)?;
+                    let snapshot = scan_logs(&log_dir, days)?;
                     results.insert(*provider, snapshot);
                 } else {
                     debug!(provider = ?provider, "Log directory not found");
@@ -73,10 +86,7 @@ pub async fn run(args: &CostArgs, cli: &Cli) -> Result<()> {
         }
     }
 
-    // Output results
-    output_cost_results(&results, args, cli)?;
-
-    Ok(())
+    Ok(results)
 }
 
 /// Scans log files and aggregates token usage.
@@ -182,7 +192,7 @@ impl LogEntry {
 }
 
 /// Parses provider selection for cost command.
-fn parse_cost_providers(arg: &str) -> Result<Vec<ProviderKind>> {
+pub(crate) fn parse_cost_providers(arg: &str) -> Result<Vec<ProviderKind>> {
     match arg.to_lowercase().as_str() {
         "all" => {
             // Only providers that support token cost
@@ -0,0 +1,159 @@
+//! Daemon command - shared background fetcher for the CLI and app.
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use std::path::PathBuf;
+
+use crate::Cli;
+
+/// Arguments for the daemon command.
+#[derive(Args)]
+pub struct DaemonArgs {
+    #[command(subcommand)]
+    pub action: Option<DaemonAction>,
+
+    /// Socket path (defaults to $EXACTOBAR_SOCKET or the runtime directory).
+    #[arg(long, global = true)]
+    pub socket: Option<PathBuf>,
+
+    /// Refresh interval in seconds (defaults to the configured refresh cadence).
+    #[arg(long, short)]
+    pub interval: Option<u64>,
+
+    /// Request timeout in seconds for each provider fetch.
+    #[arg(long, default_value = "60")]
+    pub timeout: u64,
+}
+
+/// Daemon actions.
+#[derive(Subcommand)]
+pub enum DaemonAction {
+    /// Run the daemon in the foreground (default).
+    Run,
+    /// Show whether a daemon is running (exits 1 if not).
+    Status,
+    /// Stop the running daemon.
+    Stop,
+}
+
+/// Runs the daemon command.
+#[cfg(unix)]
+pub async fn run(args: &DaemonArgs, cli: &Cli) -> Result<()> {
+    let path = args
+        .socket
+        .clone()
+        .unwrap_or_else(exactobar_store::default_socket_path);
+
+    match args.action {
+        None | Some(DaemonAction::Run) => unix::run(args, cli, &path).await,
+        Some(DaemonAction::Status) => unix::status(cli, &path).await,
+        Some(DaemonAction::Stop) => unix::stop(cli, &path).await,
+    }
+}
+
+/// Runs the daemon command.
+#[cfg(not(unix))]
+pub async fn run(_args: &DaemonArgs, _cli: &Cli) -> Result<()> {
+    anyhow::bail!("The daemon needs Unix domain sockets, which this platform lacks")
+}
+
+#[cfg(unix)]
+mod unix {
+    use anyhow::{Context, Result};
+    use exactobar_store::{DaemonClient, PROTOCOL_VERSION};
+    use std::path::Path;
+    use std::sync::Arc;
+    use tracing::info;
+
+    use super::DaemonArgs;
+    use crate::commands::serve::{fetch_context, refresh_interval, served_providers};
+    use crate::commands::usage::load_settings;
+    use crate::server::Refresher;
+    use crate::server::daemon::{Daemon, bind};
+    use crate::{Cli, OutputFormat};
+
+    pub async fn run(args: &DaemonArgs, cli: &Cli, path: &Path) -> Result<()> {
+        let settings = load_settings().await.unwrap_or_default();
+        let providers = served_providers(cli, &settings)?;
+        let interval = refresh_interval(args.interval, &settings);
+        let ctx = fetch_context(&settings, args.timeout);
+
+        let listener = bind(path)?;
+        let refresher =
+            Refresher::new(providers, ctx, settings.status_checks_enabled || cli.status);
+        let daemon = Arc::new(Daemon::new(refresher, interval));
+
+        info!(socket = %path.display(), interval = ?interval, "Daemon started");
+        if !cli.quiet {
+            eprintln!("Daemon listening on {}", path.display());
+        }
+
+        tokio::spawn(Arc::clone(&daemon).run_refresh_loop());
+
+        let result = tokio::select! {
+            result = daemon.serve(listener) => result,
+            _ = tokio::signal::ctrl_c() => Ok(()),
+        };
+        let _ = std::fs::remove_file(path);
+        info!("Daemon stopped");
+        result
+    }
+
+    pub async fn status(cli: &Cli, path: &Path) -> Result<()> {
+        let Ok(client) = DaemonClient::connect(path).await else {
+            if cli.format == OutputFormat::Json {
+                println!(
+                    "{}",
+                    serde_json::json!({ "running": false, "socket": path.display().to_string() })
+                );
+            } else if !cli.quiet {
+                println!("Daemon not running ({})", path.display());
+            }
+            std::process::exit(crate::ExitCode::Error as i32);
+        };
+
+        let info = client.info();
+        let providers: Vec<&str> = info.providers.iter().map(|p| p.cli_name()).collect();
+        let interval = info.interval.map(|d| d.as_secs());
+        if cli.format == OutputFormat::Json {
+            let status = serde_json::json!({
+                "running": true,
+                "socket": path.display().to_string(),
+                "version": info.daemon_version,
+                "protocol": PROTOCOL_VERSION,
+                "providers": providers,
+                "intervalSecs": interval,
+            });
+            let json = if cli.pretty {
+                serde_json::to_string_pretty(&status)?
+            } else {
+                serde_json::to_string(&status)?
+            };
+            println!("{}", json);
+        } else {
+            println!(
+                "Daemon running on {} (v{}, protocol {})",
+                path.display(),
+                info.daemon_version,
+                PROTOCOL_VERSION
+            );
+            println!("  Providers: {}", providers.join(", "));
+            match interval {
+                Some(secs) => println!("  Refresh:   every {}s", secs),
+                None => println!("  Refresh:   manual"),
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn stop(cli: &Cli, path: &Path) -> Result<()> {
+        let client = DaemonClient::connect(path)
+            .await
+            .with_context(|| format!("No daemon running on {}", path.display()))?;
+        client.shutdown().await?;
+        if !cli.quiet {
+            println!("Daemon stopped");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+//! MCP command - stdio Model Context Protocol server for coding agents.
+
+use anyhow::Result;
+use clap::Args;
+use std::time::Duration;
+use tracing::info;
+
+use crate::Cli;
+use crate::commands::serve::{fetch_context, served_providers};
+use crate::commands::usage::load_settings;
+use crate::server::Refresher;
+use crate::server::mcp::McpServer;
+
+/// Arguments for the mcp command.
+#[derive(Args)]
+pub struct McpArgs {
+    /// Serve cached snapshots younger than this many seconds.
+    #[arg(long, default_value = "60")]
+    pub max_age: u64,
+
+    /// Request timeout in seconds for each provider fetch.
+    #[arg(long, default_value = "60")]
+    pub timeout: u64,
+}
+
+/// Runs the mcp command.
+pub async fn run(args: &McpArgs, cli: &Cli) -> Result<()> {
+    let settings = load_settings().await.unwrap_or_default();
+    let providers = served_providers(cli, &settings)?;
+    let ctx = fetch_context(&settings, args.timeout);
+
+    let refresher = Refresher::new(providers, ctx, false);
+    info!(providers = ?refresher.providers(), "MCP server on stdio");
+
+    McpServer::new(refresher, Duration::from_secs(args.max_age), !cli.no_daemon)
+        .run_stdio()
+        .await
+}
@@ -1,8 +1,16 @@
 //! CLI command implementations.
 
+pub mod bar;
+pub mod calendar;
 pub mod config;
 pub mod cost;
+pub mod daemon;
+pub mod mcp;
+pub mod pick;
 pub mod providers;
+pub mod serve;
+pub mod statusline;
 pub mod summary;
 pub mod usage;
+pub mod wait;
 pub mod watch;
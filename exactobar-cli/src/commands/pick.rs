@@ -0,0 +1,166 @@
+//! Pick command - choose the provider with the most quota headroom.
+//!
+//! Prints the best provider's CLI name for scripts, e.g.
+//! `run-agent --provider "$(exactobar pick --candidates claude,codex)"`.
+//! Ranking lives in [`exactobar_providers::ranking`] so orchestrators can
+//! call it directly.
+
+use anyhow::Result;
+use clap::Args;
+use exactobar_core::ProviderKind;
+use exactobar_fetch::FetchFailure;
+use exactobar_providers::{Candidate, Ranker, Ranking};
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::commands::serve::{fetch_context, served_providers};
+use crate::commands::usage::{
+    Failure, fetch_failure, fetch_statuses, fetch_with_refresher, load_settings,
+    parse_provider_selection,
+};
+use crate::server::Refresher;
+use crate::{Cli, ExitCode, OutputFormat};
+
+/// Arguments for the pick command.
+#[derive(Args)]
+pub struct PickArgs {
+    /// Providers to choose from, comma-separated. Defaults to `--provider`
+    /// or the enabled providers.
+    #[arg(long)]
+    pub candidates: Option<String>,
+
+    /// Providers to favor when headroom is close, comma-separated, best first.
+    #[arg(long)]
+    pub prefer: Option<String>,
+
+    /// Print the full ranking to stderr.
+    #[arg(long)]
+    pub explain: bool,
+
+    /// Skip status page checks.
+    #[arg(long)]
+    pub no_status: bool,
+
+    /// Request timeout in seconds for each provider fetch.
+    #[arg(long, default_value = "60")]
+    pub timeout: u64,
+}
+
+/// Runs the pick command.
+pub async fn run(args: &PickArgs, cli: &Cli) -> Result<()> {
+    let settings = load_settings().await.unwrap_or_default();
+    let providers = match &args.candidates {
+        Some(_) => parse_provider_selection(args.candidates.as_ref())?,
+        None => served_providers(cli, &settings)?,
+    };
+    let prefer = match &args.prefer {
+        Some(_) => parse_provider_selection(args.prefer.as_ref())?,
+        None => Vec::new(),
+    };
+
+    let ctx = fetch_context(&settings, args.timeout);
+    let statuses = if args.no_status {
+        HashMap::new()
+    } else {
+        fetch_statuses(&providers, &ctx).await
+    };
+    let refresher = Refresher::new(providers, ctx, false);
+    let results = fetch_with_refresher(&refresher, !cli.no_daemon).await;
+
+    let candidates: Vec<Candidate<'_>> = results
+        .iter()
+        .map(|(provider, result)| {
+            Candidate::new(*provider, result).with_status(statuses.get(provider))
+        })
+        .collect();
+    let rankings = Ranker::new().with_prefer(prefer).rank(&candidates);
+
+    if args.explain && !cli.quiet {
+        for ranking in &rankings {
+            eprintln!("{:<12} {}", ranking.provider.cli_name(), ranking.reason);
+        }
+    }
+
+    let Some(best) = rankings.first().filter(|r| r.available) else {
+        no_pick(&results, &rankings).exit(cli);
+    };
+
+    if cli.format == OutputFormat::Json {
+        let output = json!({ "pick": best.provider.cli_name(), "rankings": rankings });
+        if cli.pretty {
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        } else {
+            println!("{}", output);
+        }
+    } else {
+        println!("{}", best.provider.cli_name());
+    }
+    Ok(())
+}
+
+/// Explains why no candidate can take work.
+fn no_pick(
+    results: &[(
+        ProviderKind,
+        Result<exactobar_core::UsageSnapshot, FetchFailure>,
+    )],
+    rankings: &[Ranking],
+) -> Failure {
+    let errors: Vec<(ProviderKind, &FetchFailure)> = results
+        .iter()
+        .filter_map(|(provider, result)| result.as_ref().err().map(|e| (*provider, e)))
+        .collect();
+    if !errors.is_empty() && errors.len() == results.len() {
+        return fetch_failure(&errors);
+    }
+
+    let reasons: Vec<String> = rankings
+        .iter()
+        .map(|r| format!("{}: {}", r.provider.cli_name(), r.reason))
+        .collect();
+    Failure {
+        code: ExitCode::OverThreshold,
+        message: format!("No provider has headroom ({})", reasons.join("; ")),
+        details: json!({ "rankings": rankings }),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exactobar_core::{UsageSnapshot, UsageWindow, WindowKind};
+    use exactobar_fetch::FetchErrorKind;
+
+    #[test]
+    fn test_no_pick() {
+        let mut full = UsageSnapshot::new();
+        full.push_window(UsageWindow::named("session", WindowKind::Session, 100.0));
+        let results = vec![
+            (ProviderKind::Claude, Ok(full)),
+            (
+                ProviderKind::Codex,
+                Err(FetchFailure::new(
+                    FetchErrorKind::Timeout,
+                    "Request timed out",
+                )),
+            ),
+        ];
+        let candidates: Vec<_> = results
+            .iter()
+            .map(|(provider, result)| Candidate::new(*provider, result))
+            .collect();
+        let rankings = Ranker::new().rank(&candidates);
+        assert!(!rankings[0].available);
+
+        let failure = no_pick(&results, &rankings);
+        assert_eq!(failure.code, ExitCode::OverThreshold);
+        assert!(failure.message.contains("claude: 0% headroom"));
+
+        let failure = no_pick(&results[1..], &rankings[1..]);
+        assert_eq!(failure.code, ExitCode::Timeout);
+    }
+}
@@ -0,0 +1,143 @@
+//! Serve command - local HTTP API and Prometheus metrics.
+
+use anyhow::{Context, Result};
+use clap::Args;
+use exactobar_core::ProviderKind;
+use exactobar_fetch::{FetchContext, SourceMode};
+use exactobar_providers::ProviderRegistry;
+use exactobar_store::Settings;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tracing::{info, warn};
+
+use crate::Cli;
+use crate::commands::usage::{load_settings, parse_provider_selection};
+use crate::server::{Refresher, Server, SnapshotCache};
+
+/// Shortest refresh interval accepted by `--interval`.
+const MIN_INTERVAL_SECS: u64 = 10;
+
+/// Arguments for the serve command.
+#[derive(Args)]
+pub struct ServeArgs {
+    /// Address to listen on.
+    #[arg(long, default_value = "127.0.0.1:9477")]
+    pub bind: SocketAddr,
+
+    /// Require this bearer token (visible in the process list; prefer --token-env).
+    #[arg(long, conflicts_with = "token_env")]
+    pub token: Option<String>,
+
+    /// Read the bearer token from this environment variable.
+    #[arg(long, value_name = "VAR")]
+    pub token_env: Option<String>,
+
+    /// Refresh interval in seconds (defaults to the configured refresh cadence).
+    #[arg(long, short)]
+    pub interval: Option<u64>,
+
+    /// Request timeout in seconds for each provider fetch.
+    #[arg(long, default_value = "60")]
+    pub timeout: u64,
+}
+
+/// Runs the serve command.
+pub async fn run(args: &ServeArgs, cli: &Cli) -> Result<()> {
+    let settings = load_settings().await.unwrap_or_default();
+    let providers = served_providers(cli, &settings)?;
+    let token = resolve_token(args)?;
+
+    if token.is_none() && !args.bind.ip().is_loopback() {
+        warn!(bind = %args.bind, "Serving on a non-loopback address without --token");
+    }
+
+    let interval = refresh_interval(args.interval, &settings);
+
+    let ctx = fetch_context(&settings, args.timeout);
+
+    let listener = TcpListener::bind(args.bind)
+        .await
+        .with_context(|| format!("Failed to bind {}", args.bind))?;
+
+    let cache = Arc::new(SnapshotCache::new());
+    let refresher = Refresher::new(providers, ctx, settings.status_checks_enabled || cli.status);
+    info!(
+        bind = %args.bind,
+        providers = ?refresher.providers(),
+        interval = ?interval,
+        "Serving"
+    );
+    if !cli.quiet {
+        eprintln!(
+            "Serving on http://{} (/metrics, /v1/snapshots, /calendar.ics)",
+            args.bind
+        );
+    }
+
+    tokio::spawn(refresher.run(Arc::clone(&cache), interval));
+
+    let server = Server::new(cache)
+        .with_token(token)
+        .with_pretty(cli.pretty)
+        .with_display_currency(settings.display_currency, settings.exchange_rates);
+
+    tokio::select! {
+        result = server.serve(listener) => result.context("Server stopped"),
+        _ = tokio::signal::ctrl_c() => {
+            info!("Shutting down");
+            Ok(())
+        }
+    }
+}
+
+/// Returns `--interval` (at least [`MIN_INTERVAL_SECS`]) or the configured cadence.
+pub(crate) fn refresh_interval(interval: Option<u64>, settings: &Settings) -> Option<Duration> {
+    match interval {
+        Some(secs) => Some(Duration::from_secs(secs.max(MIN_INTERVAL_SECS))),
+        None => settings.refresh_cadence.as_duration(),
+    }
+}
+
+/// Builds the context used for background refreshes.
+pub(crate) fn fetch_context(settings: &Settings, timeout_secs: u64) -> FetchContext {
+    FetchContext::builder()
+        .source_mode(SourceMode::Auto)
+        .timeout(Duration::from_secs(timeout_secs))
+        .persist_refreshed_credentials(settings.claude_oauth_write_back_enabled)
+        .web_extras(settings.claude_web_extras_enabled)
+        .provider_options(settings.provider_options())
+        .build()
+}
+
+/// Returns the providers named by `--provider`, or the enabled ones.
+pub(crate) fn served_providers(cli: &Cli, settings: &Settings) -> Result<Vec<ProviderKind>> {
+    if cli.provider.is_some() {
+        return parse_provider_selection(cli.provider.as_ref());
+    }
+
+    let enabled: Vec<ProviderKind> = ProviderRegistry::kinds()
+        .into_iter()
+        .filter(|kind| settings.enabled_providers.contains(kind))
+        .collect();
+    if enabled.is_empty() {
+        parse_provider_selection(None)
+    } else {
+        Ok(enabled)
+    }
+}
+
+/// Returns the bearer token from `--token` or `--token-env`.
+fn resolve_token(args: &ServeArgs) -> Result<Option<String>> {
+    if let Some(token) = &args.token {
+        return Ok(Some(token.clone()));
+    }
+    let Some(var) = &args.token_env else {
+        return Ok(None);
+    };
+    match std::env::var(var) {
+        Ok(token) if !token.trim().is_empty() => Ok(Some(token.trim().to_string())),
+        _ => anyhow::bail!("Environment variable {} is not set", var),
+    }
+}
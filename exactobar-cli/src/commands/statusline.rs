@@ -0,0 +1,341 @@
+//! Statusline command - one-line usage for coding agent status bars.
+//!
+//! Claude Code runs its `statusLine` command on every update and passes
+//! the session as JSON on stdin, so this must return quickly. The line is
+//! built from a small on-disk cache; when the cache is older than
+//! `--max-age`, a detached `statusline --refresh-cache` process refreshes
+//! it for the next call.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use clap::Args;
+use exactobar_core::{ProviderKind, UsageSnapshot};
+use exactobar_providers::ProviderRegistry;
+use exactobar_store::{default_cache_dir, load_json, save_json};
+use serde::{Deserialize, Serialize};
+use std::io::{IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime};
+use tracing::debug;
+
+use crate::Cli;
+use crate::commands::cost::scan_costs;
+use crate::commands::serve::fetch_context;
+use crate::commands::usage::{fetch_one, fetch_via_daemon, load_settings};
+use crate::output::TextFormatter;
+
+/// A refresh lock older than this is assumed abandoned.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Arguments for the statusline command.
+#[derive(Args)]
+pub struct StatuslineArgs {
+    /// Claude Code mode: read its statusLine JSON on stdin, show Claude usage.
+    #[arg(long, conflicts_with = "codex")]
+    pub claude: bool,
+
+    /// Codex mode: show Codex usage.
+    #[arg(long)]
+    pub codex: bool,
+
+    /// Refresh the cached usage in the background once it is this many seconds old.
+    #[arg(long, default_value = "120")]
+    pub max_age: u64,
+
+    /// Refresh the cache in the foreground and exit.
+    #[arg(long, hide = true)]
+    pub refresh_cache: bool,
+}
+
+impl StatuslineArgs {
+    fn provider(&self) -> ProviderKind {
+        if self.codex {
+            ProviderKind::Codex
+        } else {
+            ProviderKind::Claude
+        }
+    }
+}
+
+/// Session JSON passed on stdin (the fields we show; everything is optional).
+#[derive(Debug, Default, Deserialize)]
+struct Session {
+    #[serde(default)]
+    model: Option<SessionModel>,
+    #[serde(default)]
+    cost: Option<SessionCost>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionModel {
+    #[serde(default)]
+    display_name: Option<String>,
+    #[serde(default)]
+    id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionCost {
+    #[serde(default)]
+    total_cost_usd: Option<f64>,
+}
+
+/// Cached usage for one provider.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedUsage {
+    fetched_at: DateTime<Utc>,
+    #[serde(default)]
+    snapshot: Option<UsageSnapshot>,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    today_cost_usd: Option<f64>,
+}
+
+/// Runs the statusline command.
+pub async fn run(args: &StatuslineArgs, cli: &Cli) -> Result<()> {
+    let provider = args.provider();
+    let path = cache_path(provider);
+
+    if args.refresh_cache {
+        let result = refresh_cache(provider, &path, cli).await;
+        let _ = std::fs::remove_file(lock_path(&path));
+        return result;
+    }
+
+    let session = read_session();
+    let cached: Option<CachedUsage> = load_json(&path).await.ok();
+
+    let max_age = chrono::Duration::seconds(i64::try_from(args.max_age).unwrap_or(i64::MAX));
+    if cached
+        .as_ref()
+        .is_none_or(|c| Utc::now() - c.fetched_at > max_age)
+    {
+        spawn_refresh(provider, &path, cli);
+    }
+
+    let formatter = TextFormatter::new(!cli.no_color);
+    println!(
+        "{}",
+        format_line(&formatter, provider, &session, cached.as_ref())
+    );
+    Ok(())
+}
+
+/// Builds the status line.
+fn format_line(
+    formatter: &TextFormatter,
+    provider: ProviderKind,
+    session: &Session,
+    cached: Option<&CachedUsage>,
+) -> String {
+    let desc = ProviderRegistry::get(provider);
+    let model = session
+        .model
+        .as_ref()
+        .and_then(|m| m.display_name.clone().or_else(|| m.id.clone()));
+    let title = model.or_else(|| desc.map(|d| d.display_name().to_string()));
+
+    let mut segments = Vec::new();
+    match cached {
+        Some(CachedUsage {
+            snapshot: Some(snapshot),
+            ..
+        }) => {
+            segments.extend(
+                snapshot
+                    .windows
+                    .iter()
+                    .map(|w| formatter.format_window_compact(w, desc)),
+            );
+        }
+        Some(CachedUsage { error: Some(_), .. }) => segments.push("usage unavailable".to_string()),
+        _ => segments.push("usage …".to_string()),
+    }
+
+    if let Some(usd) = session.cost.as_ref().and_then(|c| c.total_cost_usd) {
+        segments.push(format!("${:.2} session", usd));
+    }
+    if let Some(usd) = cached.and_then(|c| c.today_cost_usd) {
+        segments.push(format!("${:.2} today", usd));
+    }
+
+    formatter.format_statusline(title.as_deref(), &segments)
+}
+
+/// Reads session JSON from stdin, if any was piped.
+fn read_session() -> Session {
+    let mut stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        return Session::default();
+    }
+    let mut input = String::new();
+    if stdin.read_to_string(&mut input).is_err() || input.trim().is_empty() {
+        return Session::default();
+    }
+    serde_json::from_str(&input).unwrap_or_else(|e| {
+        debug!(error = %e, "Ignoring unparseable session JSON");
+        Session::default()
+    })
+}
+
+// ============================================================================
+// Cache
+// ============================================================================
+
+fn cache_path(provider: ProviderKind) -> PathBuf {
+    default_cache_dir()
+        .join("statusline")
+        .join(format!("{}.json", provider.cli_name()))
+}
+
+fn lock_path(cache: &Path) -> PathBuf {
+    cache.with_extension("lock")
+}
+
+/// Starts a detached refresh unless one is already running.
+fn spawn_refresh(provider: ProviderKind, cache: &Path, cli: &Cli) {
+    let lock = lock_path(cache);
+    if let Some(dir) = lock.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let abandoned = std::fs::metadata(&lock)
+        .and_then(|m| m.modified())
+        .is_ok_and(|at| {
+            SystemTime::now()
+                .duration_since(at)
+                .is_ok_and(|age| age > LOCK_TIMEOUT)
+        });
+    if abandoned {
+        let _ = std::fs::remove_file(&lock);
+    }
+    if std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&lock)
+        .is_err()
+    {
+        debug!("Refresh already running");
+        return;
+    }
+
+    let Ok(exe) = std::env::current_exe() else {
+        let _ = std::fs::remove_file(&lock);
+        return;
+    };
+    let mut command = Command::new(exe);
+    command
+        .args(["--quiet", "statusline", "--refresh-cache"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    if provider == ProviderKind::Codex {
+        command.arg("--codex");
+    }
+    if cli.no_daemon {
+        command.arg("--no-daemon");
+    }
+    if let Err(e) = command.spawn() {
+        debug!(error = %e, "Failed to start refresh");
+        let _ = std::fs::remove_file(&lock);
+    }
+}
+
+/// Fetches usage and today's cost into the cache.
+async fn refresh_cache(provider: ProviderKind, path: &Path, cli: &Cli) -> Result<()> {
+    let daemon = fetch_via_daemon(&[provider], !cli.no_daemon)
+        .await
+        .and_then(|mut results| results.remove(&provider));
+    let result = if let Some(result) = daemon {
+        result
+    } else {
+        let settings = load_settings().await.unwrap_or_default();
+        fetch_one(provider, &fetch_context(&settings, 30)).await
+    };
+
+    let today = Utc::now().date_naive();
+    let today_cost_usd = scan_costs(&[provider], 1)
+        .ok()
+        .and_then(|mut costs| costs.remove(&provider))
+        .map(|cost| {
+            cost.daily
+                .iter()
+                .filter(|d| d.date.date_naive() == today)
+                .map(|d| d.cost_usd)
+                .sum()
+        });
+
+    // Keep showing the last good snapshot when a refresh fails.
+    let previous: Option<CachedUsage> = load_json(path).await.ok();
+    let (snapshot, error) = match result {
+        Ok(snapshot) => (Some(snapshot), None),
+        Err(e) => (previous.and_then(|p| p.snapshot), Some(e.message)),
+    };
+
+    let cached = CachedUsage {
+        fetched_at: Utc::now(),
+        snapshot,
+        error,
+        today_cost_usd,
+    };
+    save_json(path, &cached)
+        .await
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exactobar_core::{UsageWindow, WindowKind};
+
+    #[test]
+    fn test_parses_claude_session() {
+        let input = r#"{
+            "hook_event_name": "Status",
+            "session_id": "abc",
+            "model": { "id": "claude-opus-4-1", "display_name": "Opus" },
+            "workspace": { "current_dir": "/tmp", "project_dir": "/tmp" },
+            "cost": { "total_cost_usd": 1.234, "total_duration_ms": 4500 }
+        }"#;
+        let session: Session = serde_json::from_str(input).unwrap();
+        assert_eq!(session.model.unwrap().display_name.as_deref(), Some("Opus"));
+        assert_eq!(session.cost.unwrap().total_cost_usd, Some(1.234));
+    }
+
+    #[test]
+    fn test_format_line() {
+        let formatter = TextFormatter::new(false);
+        let session: Session = serde_json::from_str(
+            r#"{ "model": { "display_name": "Sonnet" }, "cost": { "total_cost_usd": 0.5 } }"#,
+        )
+        .unwrap();
+
+        let line = format_line(&formatter, ProviderKind::Claude, &session, None);
+        assert_eq!(line, "Sonnet │ usage … │ $0.50 session");
+
+        let mut snapshot = UsageSnapshot::new();
+        snapshot
+            .push_window(UsageWindow::named("session", WindowKind::Session, 42.0).with_label("5h"));
+        let cached = CachedUsage {
+            fetched_at: Utc::now(),
+            snapshot: Some(snapshot),
+            error: None,
+            today_cost_usd: Some(3.0),
+        };
+        let line = format_line(
+            &formatter,
+            ProviderKind::Codex,
+            &Session::default(),
+            Some(&cached),
+        );
+        assert!(line.starts_with("Codex │ "));
+        assert!(line.contains("42%"));
+        assert!(line.ends_with("$3.00 today"));
+    }
+}
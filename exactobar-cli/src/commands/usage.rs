@@ -1,14 +1,20 @@
 //! Usage command - fetch and display provider usage.
 
 use anyhow::Result;
-use clap::Args;
-use exactobar_core::{ProviderKind, UsageSnapshot};
-use exactobar_fetch::{FetchContext, SourceMode};
+use clap::{Args, ValueEnum};
+use exactobar_core::{
+    ExchangeRates, ProviderKind, ProviderStatus, StatusIndicator, UsageSnapshot, WindowKind,
+};
+use exactobar_fetch::{FetchContext, FetchFailure, SourceMode};
 use exactobar_providers::ProviderRegistry;
+use exactobar_store::{Settings, SettingsStore};
+use serde_json::{Value, json};
 use std::collections::HashMap;
 use tracing::{debug, info, warn};
 
 use crate::output::{JsonFormatter, TextFormatter};
+use crate::server::Refresher;
+use crate::server::cache::status_api_url;
 use crate::{Cli, ExitCode, OutputFormat};
 
 /// Arguments for the usage command.
@@ -23,6 +29,11 @@ pub struct UsageArgs {
     #[arg(long)]
     pub no_credits: bool,
 
+    /// Also show money amounts in this currency (e.g. USD), using the
+    /// exchange rates from settings. Defaults to the configured display currency.
+    #[arg(long)]
+    pub currency: Option<String>,
+
     /// Web timeout in seconds.
     #[arg(long, default_value = "60")]
     pub web_timeout: u64,
@@ -34,8 +45,108 @@ pub struct UsageArgs {
     /// Show raw debug output.
     #[arg(long)]
     pub debug: bool,
+
+    /// Exit with code 7 if a window is above this percent used
+    /// (or with the fetch error's code if a provider can't be checked).
+    #[arg(long, value_name = "PCT")]
+    pub fail_above: Option<f64>,
+
+    /// Windows checked by --fail-above.
+    #[arg(long, value_enum, default_value = "any")]
+    pub window: WindowFilter,
+
+    /// Exit with code 8 if a status page reports this level or worse.
+    #[arg(long, value_enum, value_name = "LEVEL")]
+    pub fail_if_status: Option<StatusLevel>,
+}
+
+/// Windows checked by `--fail-above`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum WindowFilter {
+    /// Session windows only.
+    Session,
+    /// Weekly windows only.
+    Weekly,
+    /// Every window.
+    #[default]
+    Any,
 }
 
+impl WindowFilter {
+    /// Returns true if windows of `kind` are checked.
+    pub(crate) fn matches(self, kind: WindowKind) -> bool {
+        match self {
+            Self::Session => kind == WindowKind::Session,
+            Self::Weekly => kind == WindowKind::Weekly,
+            Self::Any => true,
+        }
+    }
+}
+
+/// Incident level for `--fail-if-status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StatusLevel {
+    /// Degraded performance or worse.
+    Minor,
+    /// Partial outage or worse.
+    Major,
+    /// Major outage.
+    Critical,
+}
+
+impl StatusLevel {
+    /// Returns true if `indicator` is an incident at this level or worse.
+    fn is_met_by(self, indicator: StatusIndicator) -> bool {
+        let floor = match self {
+            Self::Minor => StatusIndicator::Minor,
+            Self::Major => StatusIndicator::Major,
+            Self::Critical => StatusIndicator::Critical,
+        };
+        // Maintenance and unknown sort above critical but aren't incidents.
+        indicator.severity() >= floor.severity()
+            && indicator.severity() <= StatusIndicator::Critical.severity()
+    }
+}
+
+/// A failed check, reported on stderr before exiting.
+#[derive(Debug)]
+pub(crate) struct Failure {
+    /// Exit code.
+    pub code: ExitCode,
+    /// Human-readable message.
+    pub message: String,
+    /// Extra fields for the JSON reason.
+    pub details: Value,
+}
+
+impl Failure {
+    /// Prints the failure to stderr and exits with its code.
+    pub fn exit(self, cli: &Cli) -> ! {
+        if cli.format == OutputFormat::Json {
+            let mut reason = json!({
+                "exitCode": self.code as i32,
+                "reason": self.code.reason(),
+                "message": self.message,
+            });
+            if let (Some(reason), Value::Object(details)) = (reason.as_object_mut(), self.details) {
+                reason.extend(details);
+            }
+            eprintln!("{}", reason);
+        } else if !cli.quiet {
+            eprintln!("{}", self.message);
+        }
+        std::process::exit(self.code as i32);
+    }
+}
+
+impl std::fmt::Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Failure {}
+
 /// Runs the usage command.
 pub async fn run(args: &UsageArgs, cli: &Cli) -> Result<()> {
     // Determine which providers to query
@@ -44,35 +155,209 @@ pub async fn run(args: &UsageArgs, cli: &Cli) -> Result<()> {
 
     info!(providers = ?providers, "Fetching usage");
 
+    let settings = load_settings().await;
+
     // Create fetch context
     let source_mode = parse_source_mode(&args.source)?;
     let ctx = FetchContext::builder()
         .source_mode(source_mode)
         .timeout(std::time::Duration::from_secs(args.web_timeout))
+        .persist_refreshed_credentials(
+            settings
+                .as_ref()
+                .is_some_and(|s| s.claude_oauth_write_back_enabled),
+        )
+        .web_extras(
+            settings
+                .as_ref()
+                .is_some_and(|s| s.claude_web_extras_enabled),
+        )
+        .provider_options(
+            settings
+                .as_ref()
+                .map(Settings::provider_options)
+                .unwrap_or_default(),
+        )
         .build();
 
-    // Fetch usage from each provider (in parallel if multiple)
-    let results = fetch_all(&providers, &ctx).await;
-
-    // Check for any successful results
-    let has_success = results.values().any(|r| r.is_ok());
+    // Fetch usage through the daemon if one is running, else in-process
+    let daemon_results = if source_mode == SourceMode::Auto {
+        fetch_via_daemon(&providers, !cli.no_daemon).await
+    } else {
+        None
+    };
+    let results = match daemon_results {
+        Some(results) => results,
+        None => fetch_all(&providers, &ctx).await,
+    };
+
+    let statuses = match args.fail_if_status {
+        Some(_) => fetch_statuses(&providers, &ctx).await,
+        None => HashMap::new(),
+    };
 
     // Format and output
-    output_results(&results, args, cli)?;
+    let money = money_settings(args, settings);
+    output_results(&results, args, cli, money)?;
 
-    // Exit code based on results
-    if !has_success {
-        std::process::exit(ExitCode::ProviderMissing as i32);
+    // Exit code based on results and checks
+    if let Some(failure) = check_results(&results, &statuses, args) {
+        failure.exit(cli);
     }
 
     Ok(())
 }
 
+// ============================================================================
+// Checks
+// ============================================================================
+
+/// Fetches status page indicators, skipping providers without one.
+pub(crate) async fn fetch_statuses(
+    providers: &[ProviderKind],
+    ctx: &FetchContext,
+) -> HashMap<ProviderKind, ProviderStatus> {
+    let mut statuses = HashMap::new();
+    for provider in providers {
+        let Some(url) = ProviderRegistry::get(*provider).and_then(status_api_url) else {
+            continue;
+        };
+        match ctx.status.fetch_status(&url).await {
+            Ok(status) => {
+                statuses.insert(*provider, status);
+            }
+            Err(e) => warn!(provider = ?provider, error = %e, "Status check failed"),
+        }
+    }
+    statuses
+}
+
+/// Returns the first failed check, if any.
+///
+/// Without `--fail-above` or `--fail-if-status`, only a run where every
+/// provider failed is an error. With them, any failed fetch is, since the
+/// provider can't be checked.
+pub(crate) fn check_results(
+    results: &HashMap<ProviderKind, Result<UsageSnapshot, FetchFailure>>,
+    statuses: &HashMap<ProviderKind, ProviderStatus>,
+    args: &UsageArgs,
+) -> Option<Failure> {
+    let gated = args.fail_above.is_some() || args.fail_if_status.is_some();
+    let mut errors: Vec<(ProviderKind, &FetchFailure)> = results
+        .iter()
+        .filter_map(|(provider, result)| result.as_ref().err().map(|e| (*provider, e)))
+        .collect();
+    errors.sort_by_key(|(provider, _)| provider.cli_name());
+
+    if !errors.is_empty() && (gated || errors.len() == results.len()) {
+        return Some(fetch_failure(&errors));
+    }
+
+    if let Some(threshold) = args.fail_above {
+        let mut over: Vec<Value> = Vec::new();
+        let mut messages: Vec<String> = Vec::new();
+        let mut sorted: Vec<_> = results.iter().collect();
+        sorted.sort_by_key(|(provider, _)| provider.cli_name());
+        for (provider, result) in sorted {
+            let Ok(snapshot) = result else { continue };
+            for window in &snapshot.windows {
+                if args.window.matches(window.kind) && window.used_percent > threshold {
+                    over.push(json!({
+                        "provider": provider.cli_name(),
+                        "window": window.id,
+                        "usedPercent": window.used_percent,
+                    }));
+                    messages.push(format!(
+                        "{} {} window at {:.0}%",
+                        provider.cli_name(),
+                        window.id,
+                        window.used_percent
+                    ));
+                }
+            }
+        }
+        if !over.is_empty() {
+            return Some(Failure {
+                code: ExitCode::OverThreshold,
+                message: format!("Above {}%: {}", threshold, messages.join(", ")),
+                details: json!({ "threshold": threshold, "windows": over }),
+            });
+        }
+    }
+
+    if let Some(level) = args.fail_if_status {
+        let mut incidents: Vec<_> = statuses
+            .iter()
+            .filter(|(_, status)| level.is_met_by(status.indicator))
+            .collect();
+        incidents.sort_by_key(|(provider, _)| provider.cli_name());
+        if !incidents.is_empty() {
+            let messages: Vec<String> = incidents
+                .iter()
+                .map(|(provider, status)| {
+                    format!("{} {}", provider.cli_name(), status.indicator.label())
+                })
+                .collect();
+            let details: Vec<Value> = incidents
+                .iter()
+                .map(|(provider, status)| {
+                    json!({
+                        "provider": provider.cli_name(),
+                        "indicator": status.indicator,
+                        "description": status.description,
+                    })
+                })
+                .collect();
+            return Some(Failure {
+                code: ExitCode::StatusIncident,
+                message: format!("Status incident: {}", messages.join(", ")),
+                details: json!({ "statuses": details }),
+            });
+        }
+    }
+
+    None
+}
+
+/// Builds the failure for providers that couldn't be fetched.
+///
+/// The code is the errors' shared kind, or [`ExitCode::FetchFailed`] when
+/// they differ.
+pub(crate) fn fetch_failure(errors: &[(ProviderKind, &FetchFailure)]) -> Failure {
+    let codes: Vec<ExitCode> = errors
+        .iter()
+        .map(|(_, e)| ExitCode::for_fetch_error(e))
+        .collect();
+    let code = if codes.iter().all(|c| *c == codes[0]) {
+        codes[0]
+    } else {
+        ExitCode::FetchFailed
+    };
+
+    let messages: Vec<String> = errors
+        .iter()
+        .map(|(provider, e)| format!("{}: {}", provider.cli_name(), e))
+        .collect();
+    let failures: Vec<Value> = errors
+        .iter()
+        .zip(&codes)
+        .map(|((provider, e), code)| {
+            json!({ "provider": provider.cli_name(), "reason": code.reason(), "error": e })
+        })
+        .collect();
+
+    Failure {
+        code,
+        message: format!("Fetch failed: {}", messages.join("; ")),
+        details: json!({ "providers": failures }),
+    }
+}
+
 /// Fetches usage from all providers.
 async fn fetch_all(
     providers: &[ProviderKind],
     ctx: &FetchContext,
-) -> HashMap<ProviderKind, Result<UsageSnapshot, String>> {
+) -> HashMap<ProviderKind, Result<UsageSnapshot, FetchFailure>> {
     // Note: This runs sequentially because FetchContext isn't Clone.
     // For true parallelism, we'd need to restructure the context.
     let mut results = HashMap::new();
@@ -84,10 +369,99 @@ async fn fetch_all(
     results
 }
 
+/// What to ask a running daemon for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DaemonFetch {
+    /// Cached snapshots no older than this (the daemon's interval if `None`).
+    Cached(Option<std::time::Duration>),
+    /// Snapshots refreshed now.
+    Refresh,
+}
+
+/// Fetches usage from a running daemon.
+///
+/// Returns `None` when the caller should fetch in-process: `enabled` is
+/// false (`--no-daemon`), no daemon is listening, the protocol differs, or
+/// the request failed.
+pub(crate) async fn fetch_via_daemon(
+    providers: &[ProviderKind],
+    enabled: bool,
+) -> Option<HashMap<ProviderKind, Result<UsageSnapshot, FetchFailure>>> {
+    fetch_via_daemon_with(providers, enabled, DaemonFetch::Cached(None)).await
+}
+
+/// Like [`fetch_via_daemon`], with control over snapshot freshness.
+pub(crate) async fn fetch_via_daemon_with(
+    providers: &[ProviderKind],
+    enabled: bool,
+    fetch: DaemonFetch,
+) -> Option<HashMap<ProviderKind, Result<UsageSnapshot, FetchFailure>>> {
+    if !enabled {
+        return None;
+    }
+
+    #[cfg(unix)]
+    {
+        let mut client = exactobar_store::DaemonClient::connect_default().await?;
+        let response = match fetch {
+            DaemonFetch::Cached(max_age) => client.get(providers, max_age).await,
+            DaemonFetch::Refresh => client.refresh(providers).await,
+        };
+        match response {
+            Ok(entries) => {
+                debug!(providers = ?providers, "Fetched through daemon");
+                Some(
+                    entries
+                        .into_iter()
+                        .map(|entry| (entry.provider, entry.into_result()))
+                        .collect(),
+                )
+            }
+            Err(e) => {
+                warn!(error = %e, "Daemon request failed, fetching in-process");
+                None
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = (providers, fetch);
+        None
+    }
+}
+
+/// Fetches `refresher`'s providers, through the daemon when one is running.
+pub(crate) async fn fetch_with_refresher(
+    refresher: &Refresher,
+    use_daemon: bool,
+) -> Vec<(ProviderKind, Result<UsageSnapshot, FetchFailure>)> {
+    let mut shared = fetch_via_daemon(refresher.providers(), use_daemon)
+        .await
+        .unwrap_or_default();
+
+    let mut results = Vec::with_capacity(refresher.providers().len());
+    for provider in refresher.providers() {
+        let result = match shared.remove(provider) {
+            Some(result) => result,
+            None => match refresher.fetch_provider(*provider).await {
+                Some(state) => state.result,
+                None => Err(FetchFailure::provider_missing("Provider not registered")),
+            },
+        };
+        results.push((*provider, result));
+    }
+    results
+}
+
 /// Fetches usage from a single provider.
-async fn fetch_one(provider: ProviderKind, ctx: &FetchContext) -> Result<UsageSnapshot, String> {
-    let desc = ProviderRegistry::get(provider)
-        .ok_or_else(|| format!("Provider {:?} not found", provider))?;
+pub(crate) async fn fetch_one(
+    provider: ProviderKind,
+    ctx: &FetchContext,
+) -> Result<UsageSnapshot, FetchFailure> {
+    let desc = ProviderRegistry::get(provider).ok_or_else(|| {
+        FetchFailure::provider_missing(format!("Provider {:?} not found", provider))
+    })?;
 
     debug!(provider = ?provider, "Building pipeline");
 
@@ -105,13 +479,13 @@ async fn fetch_one(provider: ProviderKind, ctx: &FetchContext) -> Result<UsageSn
         }
         Err(e) => {
             warn!(provider = ?provider, error = %e, "Fetch failed");
-            Err(e.to_string())
+            Err(FetchFailure::from(e))
         }
     }
 }
 
 /// Parses provider selection from argument.
-fn parse_provider_selection(arg: Option<&String>) -> Result<Vec<ProviderKind>> {
+pub(crate) fn parse_provider_selection(arg: Option<&String>) -> Result<Vec<ProviderKind>> {
     match arg.map(|s| s.to_lowercase()).as_deref() {
         None | Some("both") | Some("default") => {
             // Default: Codex and Claude (primary providers)
@@ -124,16 +498,25 @@ fn parse_provider_selection(arg: Option<&String>) -> Result<Vec<ProviderKind>> {
         Some(names) => {
             // Could be comma-separated
             let mut providers = Vec::new();
-            for name in names.split(',') {
-                let name = name.trim();
+            for name in names.split(',').map(str::trim).filter(|n| !n.is_empty()) {
                 if let Some(desc) = ProviderRegistry::get_by_cli_name(name) {
                     providers.push(desc.id);
                 } else {
-                    anyhow::bail!("Unknown provider: {}", name);
+                    return Err(Failure {
+                        code: ExitCode::ProviderMissing,
+                        message: format!("Unknown provider: {}", name),
+                        details: json!({ "provider": name }),
+                    }
+                    .into());
                 }
             }
             if providers.is_empty() {
-                anyhow::bail!("No valid providers specified");
+                return Err(Failure {
+                    code: ExitCode::ParseError,
+                    message: "No valid providers specified".to_string(),
+                    details: json!({ "provider": names }),
+                }
+                .into());
             }
             Ok(providers)
         }
@@ -149,22 +532,51 @@ fn parse_source_mode(s: &str) -> Result<SourceMode> {
         "api" | "apikey" | "api_key" => Ok(SourceMode::ApiKey),
         "web" | "cookies" => Ok(SourceMode::Web),
 
-        _ => anyhow::bail!(
-            "Unknown source mode: {}. Valid options: auto, cli, oauth, api, web, local, rpc",
-            s
-        ),
+        _ => Err(Failure {
+            code: ExitCode::ParseError,
+            message: format!(
+                "Unknown source mode: {}. Valid options: auto, cli, oauth, api, web",
+                s
+            ),
+            details: json!({ "source": s }),
+        }
+        .into()),
+    }
+}
+
+/// Loads settings, falling back to defaults if they can't be read.
+pub(crate) async fn load_settings() -> Option<Settings> {
+    match SettingsStore::load_default().await {
+        Ok(store) => Some(store.get().await),
+        Err(e) => {
+            debug!(error = %e, "Could not load settings, using defaults");
+            None
+        }
     }
 }
 
+/// Resolves the display currency and exchange rates.
+fn money_settings(args: &UsageArgs, settings: Option<Settings>) -> (Option<String>, ExchangeRates) {
+    let currency = args
+        .currency
+        .clone()
+        .or_else(|| settings.as_ref().and_then(|s| s.display_currency.clone()));
+    let rates = settings.map(|s| s.exchange_rates).unwrap_or_default();
+
+    (currency, rates)
+}
+
 /// Outputs results in the appropriate format.
 fn output_results(
-    results: &HashMap<ProviderKind, Result<UsageSnapshot, String>>,
+    results: &HashMap<ProviderKind, Result<UsageSnapshot, FetchFailure>>,
     args: &UsageArgs,
     cli: &Cli,
+    (currency, rates): (Option<String>, ExchangeRates),
 ) -> Result<()> {
     match cli.format {
         OutputFormat::Text => {
-            let formatter = TextFormatter::new(!cli.no_color);
+            let formatter =
+                TextFormatter::new(!cli.no_color).with_display_currency(currency, rates);
 
             // Sort providers for consistent output
             let mut sorted: Vec<_> = results.iter().collect();
@@ -185,13 +597,13 @@ fn output_results(
                     }
                     Err(e) => {
                         let name = desc.map(|d| d.display_name()).unwrap_or("Unknown");
-                        println!("{}", formatter.format_error(name, e));
+                        println!("{}", formatter.format_error(name, &e.message));
                     }
                 }
             }
         }
         OutputFormat::Json => {
-            let formatter = JsonFormatter::new(cli.pretty);
+            let formatter = JsonFormatter::new(cli.pretty).with_display_currency(currency, rates);
             let output = formatter.format_results(results)?;
             println!("{}", output);
         }
@@ -207,6 +619,7 @@ fn output_results(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use exactobar_fetch::{FetchError, FetchErrorKind, ProcessError};
 
     #[test]
     fn test_parse_provider_default() {
@@ -250,6 +663,127 @@ mod tests {
 
     #[test]
     fn test_parse_source_mode_invalid() {
-        assert!(parse_source_mode("invalid").is_err());
+        let err = parse_source_mode("invalid").unwrap_err();
+        let failure = err.downcast_ref::<Failure>().unwrap();
+        assert_eq!(failure.code, ExitCode::ParseError);
+    }
+
+    #[test]
+    fn test_parse_provider_selection_errors() {
+        let err = parse_provider_selection(Some(&"codex,nope".to_string())).unwrap_err();
+        let failure = err.downcast_ref::<Failure>().unwrap();
+        assert_eq!(failure.code, ExitCode::ProviderMissing);
+        assert_eq!(failure.details["provider"], "nope");
+
+        let err = parse_provider_selection(Some(&" , ".to_string())).unwrap_err();
+        let failure = err.downcast_ref::<Failure>().unwrap();
+        assert_eq!(failure.code, ExitCode::ParseError);
+    }
+
+    fn results() -> HashMap<ProviderKind, Result<UsageSnapshot, FetchFailure>> {
+        let mut claude = UsageSnapshot::new();
+        claude.push_window(exactobar_core::UsageWindow::named(
+            "session",
+            WindowKind::Session,
+            91.0,
+        ));
+        claude.push_window(exactobar_core::UsageWindow::named(
+            "weekly",
+            WindowKind::Weekly,
+            40.0,
+        ));
+        HashMap::from([(ProviderKind::Claude, Ok(claude))])
+    }
+
+    #[test]
+    fn test_exit_code_classification() {
+        let code = |error: FetchError| ExitCode::for_fetch_error(&error.into());
+        assert_eq!(
+            code(FetchError::AuthenticationFailed(
+                "OAuth credentials not found".to_string()
+            )),
+            ExitCode::AuthMissing
+        );
+        assert_eq!(code(FetchError::Timeout(30)), ExitCode::Timeout);
+        assert_eq!(
+            code(FetchError::Process(ProcessError::NotFound(
+                "codex".to_string()
+            ))),
+            ExitCode::ProviderMissing
+        );
+        assert_eq!(code(FetchError::AllStrategiesFailed), ExitCode::FetchFailed);
+        // The message doesn't matter, only the variant.
+        assert_eq!(
+            code(FetchError::InvalidResponse("no api key field".to_string())),
+            ExitCode::FetchFailed
+        );
+        assert_eq!(
+            ExitCode::for_fetch_error(&FetchFailure::provider_missing("Provider Zai not found")),
+            ExitCode::ProviderMissing
+        );
+    }
+
+    #[test]
+    fn test_fail_above() {
+        let mut args = UsageArgs {
+            fail_above: Some(80.0),
+            ..UsageArgs::default()
+        };
+        let failure = check_results(&results(), &HashMap::new(), &args).unwrap();
+        assert_eq!(failure.code, ExitCode::OverThreshold);
+        assert_eq!(failure.details["windows"][0]["window"], "session");
+
+        args.window = WindowFilter::Weekly;
+        assert!(check_results(&results(), &HashMap::new(), &args).is_none());
+
+        args.fail_above = None;
+        assert!(check_results(&results(), &HashMap::new(), &args).is_none());
+    }
+
+    #[test]
+    fn test_failed_fetches() {
+        let mut results = results();
+        results.insert(
+            ProviderKind::Codex,
+            Err(FetchFailure::new(FetchErrorKind::Auth, "not logged in")),
+        );
+
+        // One provider still answered, so a plain run succeeds...
+        assert!(check_results(&results, &HashMap::new(), &UsageArgs::default()).is_none());
+
+        // ...but a gated run can't vouch for codex.
+        let args = UsageArgs {
+            fail_above: Some(95.0),
+            ..UsageArgs::default()
+        };
+        let failure = check_results(&results, &HashMap::new(), &args).unwrap();
+        assert_eq!(failure.code, ExitCode::AuthMissing);
+        assert_eq!(failure.details["providers"][0]["provider"], "codex");
+
+        results.insert(
+            ProviderKind::Cursor,
+            Err(FetchFailure::new(FetchErrorKind::Timeout, "timed out")),
+        );
+        let failure = check_results(&results, &HashMap::new(), &args).unwrap();
+        assert_eq!(failure.code, ExitCode::FetchFailed);
+    }
+
+    #[test]
+    fn test_fail_if_status() {
+        let mut status = ProviderStatus::operational();
+        status.indicator = StatusIndicator::Major;
+        let statuses = HashMap::from([(ProviderKind::Claude, status)]);
+
+        let mut args = UsageArgs {
+            fail_if_status: Some(StatusLevel::Major),
+            ..UsageArgs::default()
+        };
+        let failure = check_results(&results(), &statuses, &args).unwrap();
+        assert_eq!(failure.code, ExitCode::StatusIncident);
+        assert_eq!(failure.details["statuses"][0]["indicator"], "major");
+
+        args.fail_if_status = Some(StatusLevel::Critical);
+        assert!(check_results(&results(), &statuses, &args).is_none());
+        assert!(!StatusLevel::Minor.is_met_by(StatusIndicator::Maintenance));
     }
 }
@@ -0,0 +1,413 @@
+//! Wait command - block until a quota window has headroom.
+//!
+//! Polls on a doubling backoff, waking early at the next reset, and exits
+//! 0 once every checked window is below the threshold. Missing credentials
+//! end the wait at once; other fetch errors are retried.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use clap::Args;
+use exactobar_core::{ProviderKind, UsageSnapshot};
+use exactobar_fetch::FetchFailure;
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+use crate::commands::serve::{fetch_context, served_providers};
+use crate::commands::usage::{
+    Failure, WindowFilter, fetch_failure, fetch_with_refresher, load_settings,
+};
+use crate::output::TextFormatter;
+use crate::server::Refresher;
+use crate::{Cli, ExitCode, OutputFormat};
+
+/// Shortest accepted `--poll`.
+const MIN_POLL: Duration = Duration::from_secs(10);
+
+/// Longest gap between checks.
+const MAX_POLL: Duration = Duration::from_secs(15 * 60);
+
+/// Extra time after `resets_at` before checking, for clock skew.
+const RESET_GRACE: Duration = Duration::from_secs(30);
+
+/// Arguments for the wait command.
+#[derive(Args)]
+pub struct WaitArgs {
+    /// Exit once the checked windows are below this percent used.
+    #[arg(long, value_name = "PCT")]
+    pub below: f64,
+
+    /// Windows to check.
+    #[arg(long, value_enum, default_value = "any")]
+    pub window: WindowFilter,
+
+    /// Give up after this long (e.g. 90m, 6h, 1h30m) and exit with code 4.
+    #[arg(long, value_parser = parse_duration)]
+    pub timeout: Option<Duration>,
+
+    /// First poll interval in seconds; doubles while waiting, up to 15 minutes.
+    #[arg(long, default_value = "60", value_name = "SECS")]
+    pub poll: u64,
+
+    /// Request timeout in seconds for each provider fetch.
+    #[arg(long, default_value = "60", value_name = "SECS")]
+    pub fetch_timeout: u64,
+}
+
+/// A window (or provider) that is keeping the wait going.
+#[derive(Debug)]
+struct Blocker {
+    provider: ProviderKind,
+    window: String,
+    used_percent: Option<f64>,
+    until_reset: Option<chrono::Duration>,
+    error: Option<String>,
+}
+
+/// Runs the wait command.
+pub async fn run(args: &WaitArgs, cli: &Cli) -> Result<()> {
+    let settings = load_settings().await.unwrap_or_default();
+    let providers = served_providers(cli, &settings)?;
+    let refresher = Refresher::new(
+        providers,
+        fetch_context(&settings, args.fetch_timeout),
+        false,
+    );
+    let formatter = TextFormatter::new(!cli.no_color);
+
+    let started = Instant::now();
+    // A timeout too large to represent is as good as none.
+    let deadline = args
+        .timeout
+        .and_then(|timeout| started.checked_add(timeout));
+    let mut backoff = Duration::from_secs(args.poll).max(MIN_POLL);
+
+    loop {
+        let results = fetch_with_refresher(&refresher, !cli.no_daemon).await;
+        let blockers = match blockers(&results, args) {
+            Ok(blockers) => blockers,
+            Err(failure) => failure.exit(cli),
+        };
+
+        if blockers.is_empty() {
+            report_ready(cli, started.elapsed());
+            return Ok(());
+        }
+
+        let now = Instant::now();
+        if deadline.is_some_and(|deadline| now >= deadline) {
+            timeout_failure(&blockers, args).exit(cli);
+        }
+
+        let eta = eta(&blockers);
+        let mut sleep = backoff;
+        if let Some(eta) = eta {
+            let until = eta.to_std().unwrap_or_default() + RESET_GRACE;
+            sleep = sleep.min(until);
+        }
+        if let Some(deadline) = deadline {
+            sleep = sleep.min(deadline - now);
+        }
+
+        report_progress(cli, &formatter, &blockers, args, eta, sleep);
+        tokio::time::sleep(sleep).await;
+        backoff = (backoff * 2).min(MAX_POLL);
+    }
+}
+
+/// Returns what is still over the threshold, or a failure that ends the wait.
+fn blockers(
+    results: &[(ProviderKind, Result<UsageSnapshot, FetchFailure>)],
+    args: &WaitArgs,
+) -> Result<Vec<Blocker>, Failure> {
+    let fatal: Vec<(ProviderKind, &FetchFailure)> = results
+        .iter()
+        .filter_map(|(provider, result)| result.as_ref().err().map(|e| (*provider, e)))
+        .filter(|(_, e)| {
+            matches!(
+                ExitCode::for_fetch_error(e),
+                ExitCode::AuthMissing | ExitCode::ProviderMissing
+            )
+        })
+        .collect();
+    if !fatal.is_empty() {
+        return Err(fetch_failure(&fatal));
+    }
+
+    let mut blockers = Vec::new();
+    for (provider, result) in results {
+        let snapshot = match result {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                blockers.push(Blocker {
+                    provider: *provider,
+                    window: String::new(),
+                    used_percent: None,
+                    until_reset: None,
+                    error: Some(e.message.clone()),
+                });
+                continue;
+            }
+        };
+
+        let mut checked = snapshot
+            .windows
+            .iter()
+            .filter(|w| args.window.matches(w.kind))
+            .peekable();
+        if checked.peek().is_none() {
+            return Err(Failure {
+                code: ExitCode::Error,
+                message: format!(
+                    "{} reports no {:?} window",
+                    provider.cli_name(),
+                    args.window
+                ),
+                details: json!({ "provider": provider.cli_name() }),
+            });
+        }
+
+        blockers.extend(
+            checked
+                .filter(|w| w.used_percent >= args.below)
+                .map(|w| Blocker {
+                    provider: *provider,
+                    window: w.id.clone(),
+                    used_percent: Some(w.used_percent),
+                    until_reset: w.time_until_reset(),
+                    error: None,
+                }),
+        );
+    }
+    Ok(blockers)
+}
+
+/// Returns when every blocking window will have reset, if all reset times are known.
+fn eta(blockers: &[Blocker]) -> Option<chrono::Duration> {
+    blockers
+        .iter()
+        .map(|b| b.until_reset)
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .max()
+        .map(|until| until.max(chrono::Duration::zero()))
+}
+
+fn timeout_failure(blockers: &[Blocker], args: &WaitArgs) -> Failure {
+    let windows: Vec<_> = blockers.iter().map(blocker_json).collect();
+    Failure {
+        code: ExitCode::Timeout,
+        message: format!("Timed out waiting for usage below {}%", args.below),
+        details: json!({ "below": args.below, "windows": windows }),
+    }
+}
+
+// ============================================================================
+// Progress
+// ============================================================================
+
+fn blocker_json(blocker: &Blocker) -> serde_json::Value {
+    json!({
+        "provider": blocker.provider.cli_name(),
+        "window": blocker.window,
+        "usedPercent": blocker.used_percent,
+        "secondsUntilReset": blocker.until_reset.map(|d| d.num_seconds().max(0)),
+        "error": blocker.error,
+    })
+}
+
+fn report_progress(
+    cli: &Cli,
+    formatter: &TextFormatter,
+    blockers: &[Blocker],
+    args: &WaitArgs,
+    eta: Option<chrono::Duration>,
+    sleep: Duration,
+) {
+    if cli.format == OutputFormat::Json {
+        let windows: Vec<_> = blockers.iter().map(blocker_json).collect();
+        println!(
+            "{}",
+            json!({
+                "event": "waiting",
+                "below": args.below,
+                "windows": windows,
+                "etaSecs": eta.map(|d| d.num_seconds()),
+                "nextCheckSecs": sleep.as_secs(),
+            })
+        );
+        return;
+    }
+    if cli.quiet {
+        return;
+    }
+
+    let now = Utc::now();
+    let at = |d: Duration| now + chrono::Duration::from_std(d).unwrap_or_default();
+    let status: Vec<String> = blockers
+        .iter()
+        .map(|b| match (&b.error, b.used_percent) {
+            (Some(e), _) => format!("{}: {}", b.provider.cli_name(), e),
+            (None, Some(pct)) => format!("{} {} {:.0}%", b.provider.cli_name(), b.window, pct),
+            (None, None) => b.provider.cli_name().to_string(),
+        })
+        .collect();
+    let eta = match eta {
+        Some(eta) => {
+            let reset: DateTime<Utc> = now + eta;
+            format!(
+                "ETA {} ({})",
+                formatter.format_countdown(reset),
+                reset.with_timezone(&chrono::Local).format("%H:%M")
+            )
+        }
+        None => "ETA unknown".to_string(),
+    };
+    eprintln!(
+        "Waiting for < {}%: {} · {} · next check in {}",
+        args.below,
+        status.join(", "),
+        eta,
+        formatter.format_countdown(at(sleep))
+    );
+}
+
+fn report_ready(cli: &Cli, waited: Duration) {
+    if cli.format == OutputFormat::Json {
+        println!(
+            "{}",
+            json!({ "event": "ready", "waitedSecs": waited.as_secs() })
+        );
+    } else if !cli.quiet {
+        eprintln!("Usage has headroom");
+    }
+}
+
+/// Parses `90`, `45s`, `30m`, `6h`, `1d` or combinations like `1h30m`.
+fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    if let Ok(secs) = input.parse::<u64>() {
+        return Ok(Duration::from_secs(secs));
+    }
+
+    let mut total: u64 = 0;
+    let mut digits = String::new();
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86_400,
+            _ => {
+                return Err(format!(
+                    "invalid duration '{}': unknown unit '{}'",
+                    input, c
+                ));
+            }
+        };
+        let value: u64 = digits
+            .parse()
+            .map_err(|_| format!("invalid duration '{}'", input))?;
+        total = value
+            .checked_mul(unit)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(|| format!("invalid duration '{}': too large", input))?;
+        digits.clear();
+    }
+    if !digits.is_empty() || total == 0 {
+        return Err(format!("invalid duration '{}'", input));
+    }
+    Ok(Duration::from_secs(total))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exactobar_core::{UsageWindow, WindowKind};
+    use exactobar_fetch::FetchErrorKind;
+
+    fn args(below: f64, window: WindowFilter) -> WaitArgs {
+        WaitArgs {
+            below,
+            window,
+            timeout: None,
+            poll: 60,
+            fetch_timeout: 60,
+        }
+    }
+
+    fn claude(session: f64, weekly: f64) -> (ProviderKind, Result<UsageSnapshot, FetchFailure>) {
+        let mut snapshot = UsageSnapshot::new();
+        let mut window = UsageWindow::named("session", WindowKind::Session, session);
+        window.resets_at = Some(Utc::now() + chrono::Duration::hours(2));
+        snapshot.push_window(window);
+        snapshot.push_window(UsageWindow::named("weekly", WindowKind::Weekly, weekly));
+        (ProviderKind::Claude, Ok(snapshot))
+    }
+
+    #[test]
+    fn test_blockers() {
+        let results = vec![claude(70.0, 20.0)];
+        let blocking = blockers(&results, &args(50.0, WindowFilter::Any)).unwrap();
+        assert_eq!(blocking.len(), 1);
+        assert_eq!(blocking[0].window, "session");
+        let until = eta(&blocking).unwrap();
+        assert!(until > chrono::Duration::minutes(119));
+
+        assert!(
+            blockers(&results, &args(50.0, WindowFilter::Weekly))
+                .unwrap()
+                .is_empty()
+        );
+
+        // Without a reset time there's no ETA.
+        let results = vec![claude(10.0, 90.0)];
+        let blocking = blockers(&results, &args(50.0, WindowFilter::Weekly)).unwrap();
+        assert!(eta(&blocking).is_none());
+    }
+
+    #[test]
+    fn test_errors() {
+        let results = vec![
+            claude(10.0, 10.0),
+            (
+                ProviderKind::Codex,
+                Err(FetchFailure::new(
+                    FetchErrorKind::Failed,
+                    "All strategies failed",
+                )),
+            ),
+        ];
+        let blocking = blockers(&results, &args(50.0, WindowFilter::Any)).unwrap();
+        assert_eq!(blocking.len(), 1);
+        assert!(blocking[0].error.is_some());
+
+        let results = vec![(
+            ProviderKind::Codex,
+            Err(FetchFailure::new(FetchErrorKind::Auth, "not logged in")),
+        )];
+        let failure = blockers(&results, &args(50.0, WindowFilter::Any)).unwrap_err();
+        assert_eq!(failure.code, ExitCode::AuthMissing);
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("6h"), Ok(Duration::from_secs(6 * 3600)));
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("2d"), Ok(Duration::from_secs(2 * 86_400)));
+        assert!(parse_duration("6x").is_err());
+        assert!(parse_duration("1h30").is_err());
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("99999999999999999999s").is_err());
+        assert!(parse_duration("9999999999999999999d").is_err());
+        assert!(parse_duration("18446744073709551615s1s").is_err());
+    }
+}
@@ -1,17 +1,34 @@
 //! Watch command - real-time usage monitoring.
+//!
+//! With `--format json`, prints one JSON event per line instead of
+//! redrawing the screen. The first fetch of each provider is the baseline:
+//! it emits `snapshot_updated` (and `status_changed` when status checks are
+//! on), and later fetches emit events only for what changed.
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use clap::Args;
-use exactobar_core::{ProviderKind, UsageSnapshot};
-use exactobar_fetch::{FetchContext, SourceMode};
+use exactobar_core::{
+    ProviderKind, ProviderStatus, StatusIndicator, UsageLevel, UsageSnapshot, UsageWindow,
+};
+use exactobar_fetch::{FetchContext, FetchFailure, SourceMode};
 use exactobar_providers::ProviderRegistry;
+use exactobar_store::{Settings, SettingsStore};
+use serde::Serialize;
 use std::collections::HashMap;
-use std::io::{Write, stdout};
+use std::io::{ErrorKind, Write, stdout};
 use tokio::time::{Duration, interval};
 use tracing::info;
 
-use crate::Cli;
-use crate::output::TextFormatter;
+use crate::commands::usage;
+use crate::output::{JsonFormatter, ProviderOutput, TextFormatter};
+use crate::{Cli, OutputFormat};
+
+/// Percentages that emit `threshold_crossed` when a window rises past them.
+const THRESHOLDS: [f64; 2] = [UsageLevel::WARNING_PERCENT, UsageLevel::DANGER_PERCENT];
+
+/// A later `resets_at` only counts as a new period if it moved this much.
+const RESET_JITTER_SECS: i64 = 60;
 
 /// Arguments for watch command.
 #[derive(Args)]
@@ -48,11 +65,24 @@ pub async fn run(args: &WatchArgs, cli: &Cli) -> Result<()> {
         None => vec![ProviderKind::Codex, ProviderKind::Claude],
     };
 
+    let settings = match SettingsStore::load_default().await {
+        Ok(store) => store.get().await,
+        Err(_) => Settings::default(),
+    };
+
     let ctx = FetchContext::builder()
         .source_mode(SourceMode::Auto)
         .timeout(Duration::from_secs(30))
+        .persist_refreshed_credentials(settings.claude_oauth_write_back_enabled)
+        .web_extras(settings.claude_web_extras_enabled)
+        .provider_options(settings.provider_options())
         .build();
 
+    if cli.format == OutputFormat::Json {
+        let status_checks = settings.status_checks_enabled || cli.status;
+        return run_events(&providers, &ctx, cli, refresh_interval, status_checks).await;
+    }
+
     let formatter = TextFormatter::new(!cli.no_color);
 
     let mut ticker = interval(Duration::from_secs(refresh_interval));
@@ -75,31 +105,360 @@ pub async fn run(args: &WatchArgs, cli: &Cli) -> Result<()> {
         println!("{}", "─".repeat(50));
         println!();
 
-        // Fetch each provider
-        let mut results: HashMap<ProviderKind, Option<UsageSnapshot>> = HashMap::new();
+        let results: HashMap<ProviderKind, Option<UsageSnapshot>> =
+            fetch_all(&providers, &ctx, cli)
+                .await
+                .into_iter()
+                .map(|(k, v)| (k, v.ok()))
+                .collect();
 
-        for provider in &providers {
-            if let Some(desc) = ProviderRegistry::get(*provider) {
-                let pipeline = desc.build_pipeline(&ctx);
-                let outcome = pipeline.execute(&ctx).await;
+        // Display results
+        println!("{}", formatter.format_summary(&results));
+        println!();
+        println!("Press Ctrl+C to exit");
 
-                match outcome.result {
-                    Ok(fetch_result) => {
-                        results.insert(*provider, Some(fetch_result.snapshot));
-                    }
-                    Err(_) => {
-                        results.insert(*provider, None);
+        // Wait for next tick
+        ticker.tick().await;
+    }
+}
+
+/// Fetches each provider, through the daemon if one is running.
+async fn fetch_all(
+    providers: &[ProviderKind],
+    ctx: &FetchContext,
+    cli: &Cli,
+) -> HashMap<ProviderKind, Result<UsageSnapshot, FetchFailure>> {
+    let mut results = usage::fetch_via_daemon(providers, !cli.no_daemon)
+        .await
+        .unwrap_or_default();
+
+    for provider in providers {
+        if !results.contains_key(provider) {
+            results.insert(*provider, usage::fetch_one(*provider, ctx).await);
+        }
+    }
+    results
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+/// One line of `--format json` output.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Event {
+    /// Increases by one per event, starting at 1.
+    seq: u64,
+    timestamp: DateTime<Utc>,
+    provider: ProviderKind,
+    #[serde(flatten)]
+    kind: EventKind,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum EventKind {
+    /// New usage, in the same shape as `usage --format json`.
+    SnapshotUpdated { snapshot: Box<ProviderOutput> },
+    /// A window rose past a warning or danger threshold.
+    #[serde(rename_all = "camelCase")]
+    ThresholdCrossed {
+        window: String,
+        threshold: f64,
+        level: UsageLevel,
+        used_percent: f64,
+        previous_percent: f64,
+    },
+    /// A window started a new period.
+    #[serde(rename_all = "camelCase")]
+    WindowReset {
+        window: String,
+        used_percent: f64,
+        previous_percent: f64,
+        resets_at: Option<DateTime<Utc>>,
+    },
+    /// A fetch failed (emitted again only if the error changes).
+    ProviderError { error: String },
+    /// The status page indicator changed.
+    StatusChanged {
+        status: StatusIndicator,
+        previous: Option<StatusIndicator>,
+        description: String,
+    },
+}
+
+/// Turns successive fetches into change events.
+#[derive(Default)]
+struct EventTracker {
+    seq: u64,
+    last: HashMap<ProviderKind, UsageSnapshot>,
+    errors: HashMap<ProviderKind, String>,
+    statuses: HashMap<ProviderKind, StatusIndicator>,
+}
+
+impl EventTracker {
+    /// Records a fetch and returns the events it caused.
+    fn update(
+        &mut self,
+        provider: ProviderKind,
+        result: Result<UsageSnapshot, FetchFailure>,
+        status: Option<&ProviderStatus>,
+        now: DateTime<Utc>,
+        formatter: &JsonFormatter,
+    ) -> Vec<Event> {
+        let mut kinds = Vec::new();
+
+        if let Some(status) = status {
+            let previous = self.statuses.insert(provider, status.indicator);
+            if previous != Some(status.indicator) {
+                kinds.push(EventKind::StatusChanged {
+                    status: status.indicator,
+                    previous,
+                    description: status.description.clone(),
+                });
+            }
+        }
+
+        match &result {
+            Err(error) => {
+                if self.errors.get(&provider) != Some(&error.message) {
+                    kinds.push(EventKind::ProviderError {
+                        error: error.message.clone(),
+                    });
+                    self.errors.insert(provider, error.message.clone());
+                }
+            }
+            Ok(snapshot) => {
+                let recovered = self.errors.remove(&provider).is_some();
+                let previous = self.last.get(&provider);
+                if recovered || previous.is_none_or(|p| snapshot_changed(p, snapshot)) {
+                    kinds.push(EventKind::SnapshotUpdated {
+                        snapshot: Box::new(formatter.provider_output(provider, &result, status)),
+                    });
+                }
+                if let Some(previous) = previous {
+                    for window in &snapshot.windows {
+                        if let Some(before) = previous.window(&window.id) {
+                            window_events(before, window, now, &mut kinds);
+                        }
                     }
                 }
             }
         }
 
-        // Display results
-        println!("{}", formatter.format_summary(&results));
-        println!();
-        println!("Press Ctrl+C to exit");
+        // The last good snapshot stays the baseline across errors.
+        if let Ok(snapshot) = result {
+            self.last.insert(provider, snapshot);
+        }
 
-        // Wait for next tick
+        kinds
+            .into_iter()
+            .map(|kind| {
+                self.seq += 1;
+                Event {
+                    seq: self.seq,
+                    timestamp: now,
+                    provider,
+                    kind,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Returns true if any window or the credit balance changed.
+fn snapshot_changed(before: &UsageSnapshot, after: &UsageSnapshot) -> bool {
+    let key = |w: &UsageWindow| (w.id.clone(), w.used_percent, w.used, w.limit, w.resets_at);
+    before.windows.len() != after.windows.len()
+        || before
+            .windows
+            .iter()
+            .map(key)
+            .ne(after.windows.iter().map(key))
+        || before.credits.as_ref().map(|c| c.remaining)
+            != after.credits.as_ref().map(|c| c.remaining)
+}
+
+/// Adds reset and threshold events for one window.
+fn window_events(
+    before: &UsageWindow,
+    after: &UsageWindow,
+    now: DateTime<Utc>,
+    kinds: &mut Vec<EventKind>,
+) {
+    let passed = before.resets_at.is_some_and(|at| at <= now);
+    let advanced = matches!(
+        (before.resets_at, after.resets_at),
+        (Some(b), Some(a)) if (a - b).num_seconds() > RESET_JITTER_SECS
+    );
+    let dropped = after.used_percent < before.used_percent;
+    if (advanced && (dropped || passed)) || (passed && dropped) {
+        kinds.push(EventKind::WindowReset {
+            window: after.id.clone(),
+            used_percent: after.used_percent,
+            previous_percent: before.used_percent,
+            resets_at: after.resets_at,
+        });
+    }
+
+    for threshold in THRESHOLDS {
+        if before.used_percent < threshold && after.used_percent >= threshold {
+            kinds.push(EventKind::ThresholdCrossed {
+                window: after.id.clone(),
+                threshold,
+                level: UsageLevel::from_used_percent(threshold),
+                used_percent: after.used_percent,
+                previous_percent: before.used_percent,
+            });
+        }
+    }
+}
+
+/// Prints events as NDJSON until interrupted or stdout closes.
+async fn run_events(
+    providers: &[ProviderKind],
+    ctx: &FetchContext,
+    cli: &Cli,
+    refresh_interval: u64,
+    status_checks: bool,
+) -> Result<()> {
+    let formatter = JsonFormatter::new(false);
+    let mut tracker = EventTracker::default();
+    let mut ticker = interval(Duration::from_secs(refresh_interval));
+
+    loop {
         ticker.tick().await;
+
+        let mut results = fetch_all(providers, ctx, cli).await;
+        let statuses = if status_checks {
+            usage::fetch_statuses(providers, ctx).await
+        } else {
+            HashMap::new()
+        };
+
+        let now = Utc::now();
+        let mut out = stdout().lock();
+        for provider in providers {
+            let Some(result) = results.remove(provider) else {
+                continue;
+            };
+            for event in tracker.update(*provider, result, statuses.get(provider), now, &formatter)
+            {
+                let written = writeln!(out, "{}", serde_json::to_string(&event)?);
+                if let Err(e) = written.and_then(|()| out.flush()) {
+                    if e.kind() == ErrorKind::BrokenPipe {
+                        return Ok(());
+                    }
+                    return Err(e.into());
+                }
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exactobar_core::WindowKind;
+    use exactobar_fetch::FetchErrorKind;
+
+    fn snapshot(used: f64, resets_at: DateTime<Utc>) -> UsageSnapshot {
+        let mut snapshot = UsageSnapshot::new();
+        let mut window = UsageWindow::named("session", WindowKind::Session, used);
+        window.resets_at = Some(resets_at);
+        snapshot.push_window(window);
+        snapshot
+    }
+
+    fn types(events: &[Event]) -> Vec<String> {
+        events
+            .iter()
+            .map(|e| {
+                serde_json::to_value(e).unwrap()["type"]
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_event_sequence() {
+        let formatter = JsonFormatter::new(false);
+        let mut tracker = EventTracker::default();
+        let now = Utc::now();
+        let reset = now + chrono::Duration::hours(2);
+        let claude = ProviderKind::Claude;
+
+        let events = tracker.update(claude, Ok(snapshot(40.0, reset)), None, now, &formatter);
+        assert_eq!(types(&events), ["snapshot_updated"]);
+        let value = serde_json::to_value(&events[0]).unwrap();
+        assert_eq!(value["seq"], 1);
+        assert_eq!(value["provider"], "claude");
+        assert_eq!(
+            value["snapshot"]["usage"]["windows"][0]["usedPercent"],
+            40.0
+        );
+
+        // Unchanged: nothing.
+        let events = tracker.update(claude, Ok(snapshot(40.0, reset)), None, now, &formatter);
+        assert!(events.is_empty());
+
+        let events = tracker.update(claude, Ok(snapshot(85.0, reset)), None, now, &formatter);
+        assert_eq!(
+            types(&events),
+            ["snapshot_updated", "threshold_crossed", "threshold_crossed"]
+        );
+        let value = serde_json::to_value(&events[2]).unwrap();
+        assert_eq!(value["seq"], 4);
+        assert_eq!(value["level"], "danger");
+        assert_eq!(value["previousPercent"], 40.0);
+
+        // Errors are reported once, and the last snapshot stays the baseline.
+        let error: Result<UsageSnapshot, FetchFailure> =
+            Err(FetchFailure::new(FetchErrorKind::Timeout, "timed out"));
+        let events = tracker.update(claude, error.clone(), None, now, &formatter);
+        assert_eq!(types(&events), ["provider_error"]);
+        assert!(
+            tracker
+                .update(claude, error, None, now, &formatter)
+                .is_empty()
+        );
+
+        let later = reset + chrono::Duration::minutes(1);
+        let next = reset + chrono::Duration::hours(5);
+        let events = tracker.update(claude, Ok(snapshot(2.0, next)), None, later, &formatter);
+        assert_eq!(types(&events), ["snapshot_updated", "window_reset"]);
+    }
+
+    #[test]
+    fn test_status_changed() {
+        let formatter = JsonFormatter::new(false);
+        let mut tracker = EventTracker::default();
+        let now = Utc::now();
+        let error: Result<UsageSnapshot, FetchFailure> =
+            Err(FetchFailure::new(FetchErrorKind::Failed, "down"));
+        let operational = ProviderStatus::operational();
+        let outage = ProviderStatus::new(StatusIndicator::Major, "Partial outage");
+
+        let events = tracker.update(
+            ProviderKind::Codex,
+            error.clone(),
+            Some(&operational),
+            now,
+            &formatter,
+        );
+        assert_eq!(types(&events), ["status_changed", "provider_error"]);
+
+        let events = tracker.update(ProviderKind::Codex, error, Some(&outage), now, &formatter);
+        let value = serde_json::to_value(&events[0]).unwrap();
+        assert_eq!(value["type"], "status_changed");
+        assert_eq!(value["status"], "major");
+        assert_eq!(value["previous"], "none");
     }
 }
@@ -58,16 +58,56 @@
 //!
 //! # Watch mode
 //! exactobar watch --interval 30
+//!
+//! # Stream change events as NDJSON
+//! exactobar watch --format json | jq -c 'select(.type == "threshold_crossed")'
+//!
+//! # Add a custom HTTP/JSON provider
+//! exactobar config custom add gateway --url https://gw.example.com/usage \
+//!     --auth bearer --secret-env GATEWAY_TOKEN --used '$.used' --limit '$.limit'
+//!
+//! # Add a provider backed by a script that prints UsageSnapshot JSON
+//! exactobar config custom add budget --command ~/bin/team-budget --arg --json \
+//!     --name "Team Budget" --color '#aa3300'
+//!
+//! # Serve Prometheus metrics and snapshot JSON on localhost:9477
+//! exactobar serve --provider all --token-env EXACTOBAR_TOKEN
+//!
+//! # Share one fetcher between the app, prompts and scripts
+//! exactobar daemon
+//!
+//! # Let a coding agent check its own quota over MCP
+//! claude mcp add exactobar -- exactobar mcp
+//!
+//! # Claude Code status line (settings.json: "statusLine": {"type": "command", ...})
+//! exactobar statusline --claude
+//!
+//! # Waybar custom module (return-type: json), updating on every refresh
+//! exactobar bar waybar --watch
+//!
+//! # Start an agent job once the weekly window is below 50%
+//! exactobar wait --provider claude --below 50 --window weekly --timeout 6h && run-agent
+//!
+//! # Send the next job to whichever subscription has the most room
+//! exactobar pick --candidates claude,codex,gemini --prefer claude
+//!
+//! # Export upcoming quota resets to your calendar
+//! exactobar calendar -o resets.ics
 //! ```
 
 mod commands;
 mod output;
+mod server;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand, ValueEnum};
+use exactobar_fetch::{FetchErrorKind, FetchFailure};
 use tracing_subscriber::{EnvFilter, fmt, prelude::*};
 
-use commands::{config, cost, providers, summary, usage, watch};
+use commands::{
+    bar, calendar, config, cost, daemon, mcp, pick, providers, serve, statusline, summary, usage,
+    wait, watch,
+};
 
 // ============================================================================
 // CLI Definition
@@ -93,6 +133,13 @@ Supported providers:
   • Kiro (kiro)
   • Antigravity (antigravity)
   • MiniMax (minimax)
+  • Synthetic.new (synthetic)
+  • Anthropic API (anthropic)
+  • OpenRouter (openrouter)
+  • Windsurf (windsurf)
+  • LiteLLM (litellm)
+  • Amazon Bedrock (bedrock)
+  • Azure OpenAI (azure)
 
 Examples:
   exactobar                      # Default providers (Codex + Claude)
@@ -100,6 +147,26 @@ Examples:
   exactobar --provider codex     # Single provider
   exactobar --format json        # JSON output
   exactobar cost                 # Token cost report
+  exactobar serve                # Prometheus metrics + JSON API
+  exactobar daemon               # Shared background fetcher
+  exactobar mcp                  # MCP server for coding agents
+  exactobar statusline --claude  # Claude Code status line
+  exactobar bar waybar           # Waybar/i3blocks/Polybar/tmux module
+  exactobar usage --fail-above 80 --window session   # Gate a CI job on quota
+  exactobar wait -p claude --below 50 --timeout 6h   # Block until there's headroom
+  exactobar pick --candidates claude,codex,gemini    # Provider with the most headroom
+  exactobar calendar -o resets.ics                   # Quota resets as iCalendar
+
+Exit codes:
+  0  Success
+  1  General error
+  2  Provider not found or not installed
+  3  Parse error
+  4  Timeout
+  5  Authentication missing
+  6  Fetch failed
+  7  Usage above --fail-above
+  8  Status page at or above --fail-if-status
 "#)]
 #[command(version)]
 #[command(author = "ExactoBar Contributors")]
@@ -136,6 +203,10 @@ pub struct Cli {
     /// Quiet mode (minimal output).
     #[arg(long, short, global = true)]
     pub quiet: bool,
+
+    /// Fetch in-process even if a daemon is running.
+    #[arg(long, global = true)]
+    pub no_daemon: bool,
 }
 
 /// CLI commands.
@@ -161,6 +232,30 @@ pub enum Commands {
     #[command(visible_alias = "w")]
     Watch(watch::WatchArgs),
 
+    /// Serve usage as Prometheus metrics and a JSON API.
+    Serve(serve::ServeArgs),
+
+    /// Run the background fetcher shared by the CLI and app.
+    Daemon(daemon::DaemonArgs),
+
+    /// Run an MCP server on stdio so coding agents can query their quota.
+    Mcp(mcp::McpArgs),
+
+    /// Print a one-line usage summary for a coding agent's status line.
+    Statusline(statusline::StatuslineArgs),
+
+    /// Print a module for Waybar, i3blocks, Polybar or tmux.
+    Bar(bar::BarArgs),
+
+    /// Wait until usage drops below a threshold.
+    Wait(wait::WaitArgs),
+
+    /// Pick the provider with the most quota headroom.
+    Pick(pick::PickArgs),
+
+    /// Export upcoming quota resets as an iCalendar file.
+    Calendar(calendar::CalendarArgs),
+
     /// Manage configuration.
     Config(config::ConfigArgs),
 
@@ -187,6 +282,10 @@ pub enum OutputFormat {
 }
 
 /// CLI exit codes.
+///
+/// With `--format json`, commands that exit with a code above 1 print a
+/// `{"exitCode", "reason", ...}` object on stderr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(i32)]
 pub enum ExitCode {
     /// Success.
@@ -199,6 +298,41 @@ pub enum ExitCode {
     ParseError = 3,
     /// Timeout.
     Timeout = 4,
+    /// No credentials, or the provider rejected them.
+    AuthMissing = 5,
+    /// The fetch failed for another reason.
+    FetchFailed = 6,
+    /// A window is above the `--fail-above` threshold.
+    OverThreshold = 7,
+    /// A status page reports an incident at or above `--fail-if-status`.
+    StatusIncident = 8,
+}
+
+impl ExitCode {
+    /// Returns the machine-readable reason.
+    pub fn reason(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Error => "error",
+            Self::ProviderMissing => "provider_missing",
+            Self::ParseError => "parse_error",
+            Self::Timeout => "timeout",
+            Self::AuthMissing => "auth_missing",
+            Self::FetchFailed => "fetch_failed",
+            Self::OverThreshold => "over_threshold",
+            Self::StatusIncident => "status_incident",
+        }
+    }
+
+    /// Returns the exit code for a provider fetch error.
+    pub fn for_fetch_error(error: &FetchFailure) -> Self {
+        match error.kind {
+            FetchErrorKind::Auth => Self::AuthMissing,
+            FetchErrorKind::Timeout => Self::Timeout,
+            FetchErrorKind::ProviderMissing => Self::ProviderMissing,
+            FetchErrorKind::Failed => Self::FetchFailed,
+        }
+    }
 }
 
 // ============================================================================
@@ -236,6 +370,7 @@ async fn main() -> Result<()> {
     let cli = Cli::parse();
 
     setup_logging(cli.verbose, cli.quiet);
+    register_custom_providers().await;
 
     let result = match &cli.command {
         Some(Commands::Usage(args)) => usage::run(args, &cli).await,
@@ -243,6 +378,14 @@ async fn main() -> Result<()> {
         Some(Commands::Providers) => providers::run(&cli).await,
         Some(Commands::Summary) => summary::run(&cli).await,
         Some(Commands::Watch(args)) => watch::run(args, &cli).await,
+        Some(Commands::Serve(args)) => serve::run(args, &cli).await,
+        Some(Commands::Daemon(args)) => daemon::run(args, &cli).await,
+        Some(Commands::Mcp(args)) => mcp::run(args, &cli).await,
+        Some(Commands::Statusline(args)) => statusline::run(args, &cli).await,
+        Some(Commands::Bar(args)) => bar::run(args, &cli).await,
+        Some(Commands::Wait(args)) => wait::run(args, &cli).await,
+        Some(Commands::Pick(args)) => pick::run(args, &cli).await,
+        Some(Commands::Calendar(args)) => calendar::run(args, &cli).await,
         Some(Commands::Config(args)) => config::run(args, &cli).await,
         Some(Commands::Check(args)) => run_check(args, &cli).await,
         None => {
@@ -252,6 +395,12 @@ async fn main() -> Result<()> {
     };
 
     if let Err(e) = result {
+        // Errors that carry their own exit code (unknown provider, bad
+        // arguments) are reported like any other failed check.
+        let e = match e.downcast::<usage::Failure>() {
+            Ok(failure) => failure.exit(&cli),
+            Err(e) => e,
+        };
         if !cli.quiet {
             eprintln!("Error: {}", e);
         }
@@ -261,6 +410,17 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Registers custom providers from settings so they resolve like built-ins.
+async fn register_custom_providers() {
+    let Ok(store) = exactobar_store::SettingsStore::load_default().await else {
+        return;
+    };
+    let configs = store.custom_providers().await;
+    if !configs.is_empty() {
+        exactobar_providers::ProviderRegistry::register_custom(&configs);
+    }
+}
+
 /// Runs the check command.
 async fn run_check(args: &CheckArgs, cli: &Cli) -> Result<()> {
     use exactobar_providers::ProviderRegistry;
@@ -270,7 +430,12 @@ async fn run_check(args: &CheckArgs, cli: &Cli) -> Result<()> {
             if let Some(desc) = ProviderRegistry::get_by_cli_name(name) {
                 vec![desc.id]
             } else {
-                anyhow::bail!("Unknown provider: {}", name);
+                return Err(usage::Failure {
+                    code: ExitCode::ProviderMissing,
+                    message: format!("Unknown provider: {}", name),
+                    details: serde_json::json!({ "provider": name }),
+                }
+                .into());
             }
         }
         None => ProviderRegistry::kinds(),
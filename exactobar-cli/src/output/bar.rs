@@ -0,0 +1,297 @@
+//! Status bar module output for Waybar, i3blocks, Polybar and tmux.
+//!
+//! Each provider shows its most-used window. Colors follow
+//! [`UsageLevel`], the same thresholds as the menu bar icon.
+
+use clap::ValueEnum;
+use exactobar_core::{ProviderKind, UsageLevel, UsageSnapshot};
+use exactobar_fetch::FetchFailure;
+use exactobar_providers::ProviderRegistry;
+use serde_json::json;
+
+use super::TextFormatter;
+
+/// Status bar protocols.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum BarFormat {
+    /// Waybar custom module JSON (`return-type: json`).
+    Waybar,
+    /// i3blocks full_text / short_text / color lines.
+    I3blocks,
+    /// Polybar `%{F#rrggbb}` format tags.
+    Polybar,
+    /// tmux `#[fg=#rrggbb]` style tags.
+    Tmux,
+}
+
+/// One provider's part of the bar.
+struct Segment {
+    name: String,
+    used_percent: Option<f64>,
+    color: Option<String>,
+    tooltip: String,
+}
+
+impl Segment {
+    fn new(provider: ProviderKind, result: &Result<UsageSnapshot, FetchFailure>) -> Self {
+        let desc = ProviderRegistry::get(provider);
+        let name = desc.map_or_else(
+            || provider.cli_name().to_string(),
+            |d| d.display_name().to_string(),
+        );
+
+        match result {
+            Ok(snapshot) => {
+                let used_percent = snapshot
+                    .windows
+                    .iter()
+                    .map(|w| w.used_percent)
+                    .reduce(f64::max);
+                let brand = desc.map_or((128, 128, 128), |d| d.branding.color.to_rgb8());
+                let color = used_percent.map(|pct| {
+                    let (r, g, b) = UsageLevel::from_used_percent(pct).rgb8(brand);
+                    format!("#{:02X}{:02X}{:02X}", r, g, b)
+                });
+
+                let plain = TextFormatter::new(false);
+                let mut tooltip = vec![name.clone()];
+                tooltip.extend(
+                    snapshot
+                        .windows
+                        .iter()
+                        .map(|w| format!("  {}", plain.format_window_compact(w, desc))),
+                );
+
+                Self {
+                    name,
+                    used_percent,
+                    color,
+                    tooltip: tooltip.join("\n"),
+                }
+            }
+            Err(e) => Self {
+                tooltip: format!("{}\n  {}", name, e),
+                name,
+                used_percent: None,
+                color: None,
+            },
+        }
+    }
+
+    fn text(&self) -> String {
+        match self.used_percent {
+            Some(pct) => format!("{} {:.0}%", self.name, pct),
+            None => format!("{} –", self.name),
+        }
+    }
+}
+
+// ============================================================================
+// Formatter
+// ============================================================================
+
+/// Renders provider results in a status bar's native protocol.
+pub struct BarFormatter {
+    format: BarFormat,
+    continuous: bool,
+}
+
+impl BarFormatter {
+    /// Creates a formatter for one-shot output.
+    pub fn new(format: BarFormat) -> Self {
+        Self {
+            format,
+            continuous: false,
+        }
+    }
+
+    /// Emits one line per update, for bars that keep the process running.
+    ///
+    /// Only changes i3blocks output, which switches to its JSON form
+    /// (`format=json` with `interval=persist`).
+    pub fn with_continuous(mut self, continuous: bool) -> Self {
+        self.continuous = continuous;
+        self
+    }
+
+    /// Renders results, in provider order.
+    pub fn render(
+        &self,
+        results: &[(ProviderKind, Result<UsageSnapshot, FetchFailure>)],
+    ) -> String {
+        let segments: Vec<Segment> = results
+            .iter()
+            .map(|(provider, result)| Segment::new(*provider, result))
+            .collect();
+
+        match self.format {
+            BarFormat::Waybar => self.waybar(&segments),
+            BarFormat::I3blocks => self.i3blocks(&segments),
+            BarFormat::Polybar => join_tagged(&segments, |color, text| {
+                format!("%{{F{}}}{}%{{F-}}", color, text)
+            }),
+            BarFormat::Tmux => join_tagged(&segments, |color, text| {
+                format!("#[fg={}]{}#[default]", color, text.replace('#', "##"))
+            }),
+        }
+    }
+
+    fn waybar(&self, segments: &[Segment]) -> String {
+        let text: Vec<String> = segments.iter().map(Segment::text).collect();
+        let tooltip: Vec<&str> = segments.iter().map(|s| s.tooltip.as_str()).collect();
+        let worst = worst(segments);
+        let class = match worst {
+            Some(segment) => {
+                UsageLevel::from_used_percent(segment.used_percent.unwrap_or_default()).as_str()
+            }
+            None => "error",
+        };
+        let percentage = worst
+            .and_then(|s| s.used_percent)
+            .map_or(0, |pct| pct.round().clamp(0.0, 100.0) as u8);
+
+        json!({
+            "text": escape_pango(&text.join(" · ")),
+            "tooltip": escape_pango(&tooltip.join("\n\n")),
+            "class": class,
+            "percentage": percentage,
+        })
+        .to_string()
+    }
+
+    fn i3blocks(&self, segments: &[Segment]) -> String {
+        let full_text: Vec<String> = segments.iter().map(Segment::text).collect();
+        let full_text = full_text.join(" · ");
+        let worst = worst(segments);
+        let short_text = worst
+            .and_then(|s| s.used_percent)
+            .map_or_else(|| "–".to_string(), |pct| format!("{:.0}%", pct));
+        let color = worst.and_then(|s| s.color.clone());
+
+        if self.continuous {
+            let mut block = json!({ "full_text": full_text, "short_text": short_text });
+            if let Some(color) = color {
+                block["color"] = json!(color);
+            }
+            return block.to_string();
+        }
+
+        let mut lines = vec![full_text, short_text];
+        lines.extend(color);
+        lines.join("\n")
+    }
+}
+
+/// Returns the segment with the most-used window.
+fn worst(segments: &[Segment]) -> Option<&Segment> {
+    segments
+        .iter()
+        .filter_map(|s| Some((s, s.used_percent?)))
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(s, _)| s)
+}
+
+fn join_tagged(segments: &[Segment], tag: impl Fn(&str, &str) -> String) -> String {
+    segments
+        .iter()
+        .map(|s| match &s.color {
+            Some(color) => tag(color, &s.text()),
+            None => s.text(),
+        })
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+/// Escapes text for Waybar, which renders Pango markup.
+fn escape_pango(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exactobar_core::{UsageWindow, WindowKind};
+    use exactobar_fetch::FetchErrorKind;
+
+    fn results() -> Vec<(ProviderKind, Result<UsageSnapshot, FetchFailure>)> {
+        let mut claude = UsageSnapshot::new();
+        claude.push_window(UsageWindow::named("session", WindowKind::Session, 42.0));
+        claude.push_window(UsageWindow::named("weekly", WindowKind::Weekly, 85.0));
+        let mut codex = UsageSnapshot::new();
+        codex.push_window(UsageWindow::named("session", WindowKind::Session, 10.0));
+        vec![
+            (ProviderKind::Claude, Ok(claude)),
+            (ProviderKind::Codex, Ok(codex)),
+            (
+                ProviderKind::Zai,
+                Err(FetchFailure::new(FetchErrorKind::Auth, "Not logged in")),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_waybar() {
+        let output = BarFormatter::new(BarFormat::Waybar).render(&results());
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value["class"], "danger");
+        assert_eq!(value["percentage"], 85);
+        assert!(value["text"].as_str().unwrap().contains("Codex 10%"));
+        assert!(value["tooltip"].as_str().unwrap().contains("Not logged in"));
+
+        let failed = vec![(
+            ProviderKind::Zai,
+            Err(FetchFailure::new(FetchErrorKind::Failed, "<down>")),
+        )];
+        let output = BarFormatter::new(BarFormat::Waybar).render(&failed);
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value["class"], "error");
+        assert!(value["tooltip"].as_str().unwrap().contains("&lt;down&gt;"));
+    }
+
+    #[test]
+    fn test_i3blocks() {
+        let output = BarFormatter::new(BarFormat::I3blocks).render(&results());
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1], "85%");
+        assert_eq!(lines[2], "#F44336");
+
+        let output = BarFormatter::new(BarFormat::I3blocks)
+            .with_continuous(true)
+            .render(&results());
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value["color"], "#F44336");
+    }
+
+    #[test]
+    fn test_tagged_formats() {
+        let polybar = BarFormatter::new(BarFormat::Polybar).render(&results());
+        assert!(polybar.contains("%{F#F44336}"));
+        assert!(polybar.contains("%{F-}"));
+        assert!(polybar.ends_with("z.ai –"));
+
+        let tmux = BarFormatter::new(BarFormat::Tmux).render(&results());
+        assert!(tmux.contains("#[fg=#F44336]"));
+        assert!(tmux.contains("#[default]"));
+    }
+
+    #[test]
+    fn test_worst_tolerates_nan() {
+        let mut broken = UsageSnapshot::new();
+        broken.push_window(UsageWindow::named("session", WindowKind::Session, f64::NAN));
+        let mut results = results();
+        results.push((ProviderKind::Gemini, Ok(broken)));
+
+        let segments: Vec<Segment> = results
+            .iter()
+            .map(|(provider, result)| Segment::new(*provider, result))
+            .collect();
+        assert!(worst(&segments).is_some());
+    }
+}
@@ -2,8 +2,12 @@
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use exactobar_core::{FetchSource, ProviderKind, UsageSnapshot, UsageWindow};
-use exactobar_providers::ProviderDescriptor;
+use exactobar_core::{
+    Amount, Credits, ExchangeRates, FetchSource, ProviderKind, ProviderStatus, StatusIndicator,
+    UsageSnapshot, UsageWindow, WindowKind,
+};
+use exactobar_fetch::FetchFailure;
+use exactobar_providers::{ProviderDescriptor, ProviderRegistry};
 use exactobar_store::CostUsageSnapshot;
 use serde::{Serialize, Serializer};
 use std::collections::HashMap;
@@ -48,6 +52,8 @@ pub struct UsageOutput {
     pub secondary: Option<WindowOutput>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tertiary: Option<WindowOutput>,
+    /// Every window reported by the provider, in display order.
+    pub windows: Vec<WindowOutput>,
     #[serde(serialize_with = "serialize_datetime")]
     pub updated_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -58,8 +64,17 @@ pub struct UsageOutput {
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WindowOutput {
+    pub id: String,
+    pub label: String,
+    pub kind: WindowKind,
     pub used_percent: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub used: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub window_minutes: Option<u32>,
     #[serde(
         skip_serializing_if = "Option::is_none",
@@ -86,9 +101,50 @@ pub struct IdentityOutput {
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreditsOutput {
-    pub remaining_usd: f64,
+    pub remaining: AmountOutput,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<AmountOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spend_limit: Option<SpendLimitOutput>,
+}
+
+/// A money or unit amount.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmountOutput {
+    pub value: f64,
+    pub unit: String,
+    pub formatted: String,
+    /// Equivalent in the display currency, when conversion is configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub converted: Option<ConvertedAmountOutput>,
+}
+
+/// An amount converted to the display currency.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertedAmountOutput {
+    pub value: f64,
+    pub unit: String,
+    pub formatted: String,
+}
+
+/// Spend against a cap.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendLimitOutput {
+    pub used: AmountOutput,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cap: Option<AmountOutput>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub total_usd: Option<f64>,
+    pub used_percent: Option<f64>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_datetime_opt"
+    )]
+    pub resets_at: Option<DateTime<Utc>>,
+    pub overage_enabled: bool,
+    pub over_cap: bool,
 }
 
 /// Cost report output.
@@ -148,6 +204,18 @@ where
     }
 }
 
+/// Returns the statuspage.io name of an indicator.
+fn indicator_name(indicator: StatusIndicator) -> &'static str {
+    match indicator {
+        StatusIndicator::None => "none",
+        StatusIndicator::Minor => "minor",
+        StatusIndicator::Major => "major",
+        StatusIndicator::Critical => "critical",
+        StatusIndicator::Maintenance => "maintenance",
+        StatusIndicator::Unknown => "unknown",
+    }
+}
+
 // ============================================================================
 // JSON Formatter
 // ============================================================================
@@ -155,12 +223,25 @@ where
 /// JSON formatter.
 pub struct JsonFormatter {
     pretty: bool,
+    display_currency: Option<String>,
+    exchange_rates: ExchangeRates,
 }
 
 impl JsonFormatter {
     /// Creates a new JSON formatter.
     pub fn new(pretty: bool) -> Self {
-        Self { pretty }
+        Self {
+            pretty,
+            display_currency: None,
+            exchange_rates: ExchangeRates::default(),
+        }
+    }
+
+    /// Adds display-currency equivalents to money amounts using `rates`.
+    pub fn with_display_currency(mut self, currency: Option<String>, rates: ExchangeRates) -> Self {
+        self.display_currency = currency;
+        self.exchange_rates = rates;
+        self
     }
 
     /// Formats any serializable value.
@@ -176,7 +257,7 @@ impl JsonFormatter {
     /// Formats usage results.
     pub fn format_results(
         &self,
-        results: &HashMap<ProviderKind, Result<UsageSnapshot, String>>,
+        results: &HashMap<ProviderKind, Result<UsageSnapshot, FetchFailure>>,
     ) -> Result<String> {
         let outputs: Vec<ProviderOutput> = results
             .iter()
@@ -190,23 +271,41 @@ impl JsonFormatter {
         }
     }
 
+    /// Converts a result to output, with the provider's status if known.
+    pub fn provider_output(
+        &self,
+        provider: ProviderKind,
+        result: &Result<UsageSnapshot, FetchFailure>,
+        status: Option<&ProviderStatus>,
+    ) -> ProviderOutput {
+        let mut output = self.snapshot_to_output(provider, result);
+        output.status = status.map(|s| StatusOutput {
+            indicator: indicator_name(s.indicator).to_string(),
+            description: s.description.clone(),
+        });
+        output
+    }
+
     /// Converts a snapshot result to output.
     fn snapshot_to_output(
         &self,
         provider: ProviderKind,
-        result: &Result<UsageSnapshot, String>,
+        result: &Result<UsageSnapshot, FetchFailure>,
     ) -> ProviderOutput {
-        let provider_name = format!("{:?}", provider).to_lowercase();
+        let provider_name = provider.cli_name().to_string();
 
         match result {
             Ok(snapshot) => {
+                let desc = ProviderRegistry::get(provider);
                 let usage = UsageOutput {
-                    primary: snapshot.primary.as_ref().map(|w| self.window_to_output(w)),
-                    secondary: snapshot
-                        .secondary
-                        .as_ref()
-                        .map(|w| self.window_to_output(w)),
-                    tertiary: snapshot.tertiary.as_ref().map(|w| self.window_to_output(w)),
+                    primary: snapshot.primary().map(|w| self.window_to_output(w, desc)),
+                    secondary: snapshot.secondary().map(|w| self.window_to_output(w, desc)),
+                    tertiary: snapshot.tertiary().map(|w| self.window_to_output(w, desc)),
+                    windows: snapshot
+                        .windows
+                        .iter()
+                        .map(|w| self.window_to_output(w, desc))
+                        .collect(),
                     updated_at: snapshot.updated_at,
                     identity: snapshot.identity.as_ref().map(|id| IdentityOutput {
                         account_email: id.account_email.clone(),
@@ -216,8 +315,7 @@ impl JsonFormatter {
                     }),
                 };
 
-                // Credits would come from separate store
-                let credits: Option<CreditsOutput> = None;
+                let credits = snapshot.credits.as_ref().map(|c| self.credits_to_output(c));
 
                 ProviderOutput {
                     provider: provider_name,
@@ -236,20 +334,77 @@ impl JsonFormatter {
                 status: None,
                 usage: None,
                 credits: None,
-                error: Some(e.clone()),
+                error: Some(e.message.clone()),
             },
         }
     }
 
     /// Converts a window to output.
-    fn window_to_output(&self, window: &UsageWindow) -> WindowOutput {
+    fn window_to_output(
+        &self,
+        window: &UsageWindow,
+        desc: Option<&ProviderDescriptor>,
+    ) -> WindowOutput {
+        let label = match desc {
+            Some(d) => d.metadata.window_label(window),
+            None => window.label.clone().unwrap_or_else(|| {
+                window.slot.map_or_else(
+                    || window.id.clone(),
+                    |slot| slot.default_label().to_string(),
+                )
+            }),
+        };
+
         WindowOutput {
+            id: window.id.clone(),
+            label,
+            kind: window.kind,
             used_percent: window.used_percent,
+            used: window.used,
+            limit: window.limit,
+            unit: window.unit.clone(),
             window_minutes: window.window_minutes,
             resets_at: window.resets_at,
         }
     }
 
+    /// Converts credits to output.
+    fn credits_to_output(&self, credits: &Credits) -> CreditsOutput {
+        CreditsOutput {
+            remaining: self.amount_to_output(&credits.remaining_amount()),
+            total: credits.total_amount().map(|t| self.amount_to_output(&t)),
+            spend_limit: credits.spend_limit.as_ref().map(|spend| SpendLimitOutput {
+                used: self.amount_to_output(&spend.used),
+                cap: spend.cap.as_ref().map(|c| self.amount_to_output(c)),
+                used_percent: spend.used_percent(),
+                resets_at: spend.resets_at,
+                overage_enabled: spend.overage_enabled,
+                over_cap: spend.is_over_cap(),
+            }),
+        }
+    }
+
+    /// Converts an amount to output, adding the display-currency equivalent.
+    fn amount_to_output(&self, amount: &Amount) -> AmountOutput {
+        let converted = self
+            .display_currency
+            .as_deref()
+            .filter(|to| !amount.unit.eq_ignore_ascii_case(to))
+            .and_then(|to| self.exchange_rates.convert(amount, to))
+            .map(|c| ConvertedAmountOutput {
+                value: c.value,
+                formatted: c.to_string(),
+                unit: c.unit,
+            });
+
+        AmountOutput {
+            value: amount.value,
+            unit: amount.unit.clone(),
+            formatted: amount.to_string(),
+            converted,
+        }
+    }
+
     /// Formats fetch source.
     fn format_source(&self, source: &FetchSource) -> String {
         match source {
@@ -270,7 +425,7 @@ impl JsonFormatter {
         let outputs: Vec<CostOutput> = results
             .iter()
             .map(|(provider, cost)| CostOutput {
-                provider: format!("{:?}", provider).to_lowercase(),
+                provider: provider.cli_name().to_string(),
                 total_tokens: cost.total_tokens,
                 total_cost_usd: cost.total_cost_usd,
                 daily: cost
@@ -297,7 +452,7 @@ impl JsonFormatter {
         let outputs: Vec<ProviderInfoOutput> = providers
             .iter()
             .map(|desc| ProviderInfoOutput {
-                id: format!("{:?}", desc.id).to_lowercase(),
+                id: desc.id.cli_name().to_string(),
                 display_name: desc.display_name().to_string(),
                 cli_name: desc.cli_name().to_string(),
                 default_enabled: desc.metadata.default_enabled,
@@ -334,14 +489,14 @@ impl JsonFormatter {
                 let (status, primary, secondary) = match snapshot {
                     Some(snap) => (
                         "ok".to_string(),
-                        snap.primary.as_ref().map(|w| w.used_percent),
-                        snap.secondary.as_ref().map(|w| w.used_percent),
+                        snap.primary().map(|w| w.used_percent),
+                        snap.secondary().map(|w| w.used_percent),
                     ),
                     None => ("error".to_string(), None, None),
                 };
 
                 SummaryItem {
-                    provider: format!("{:?}", provider).to_lowercase(),
+                    provider: provider.cli_name().to_string(),
                     status,
                     primary_percent: primary,
                     secondary_percent: secondary,
@@ -382,10 +537,66 @@ mod tests {
     fn test_window_output() {
         let formatter = JsonFormatter::new(false);
         let window = UsageWindow::new(50.0);
-        let output = formatter.window_to_output(&window);
+        let output = formatter.window_to_output(&window, None);
         assert_eq!(output.used_percent, 50.0);
     }
 
+    #[test]
+    fn test_window_output_falls_back_to_slot_label() {
+        let formatter = JsonFormatter::new(false);
+        let mut snapshot = UsageSnapshot::new();
+        snapshot.set_tertiary(Some(UsageWindow::new(10.0)));
+        snapshot.push_window(UsageWindow::named("gpt-5", WindowKind::Weekly, 20.0));
+
+        let tertiary = formatter.window_to_output(snapshot.tertiary().unwrap(), None);
+        assert_eq!(tertiary.label, "Opus");
+        let named = formatter.window_to_output(snapshot.window("gpt-5").unwrap(), None);
+        assert_eq!(named.label, "gpt-5");
+    }
+
+    #[test]
+    fn test_results_include_every_window() {
+        let formatter = JsonFormatter::new(false);
+        let mut snapshot = UsageSnapshot::new();
+        snapshot.set_primary(Some(UsageWindow::new(10.0)));
+        snapshot.push_window(
+            UsageWindow::named("sonnet", WindowKind::Weekly, 40.0).with_counts(
+                400.0,
+                Some(1000.0),
+                "messages",
+            ),
+        );
+
+        let mut results = HashMap::new();
+        results.insert(ProviderKind::Claude, Ok(snapshot));
+        let json: serde_json::Value =
+            serde_json::from_str(&formatter.format_results(&results).unwrap()).unwrap();
+
+        let windows = json["usage"]["windows"].as_array().unwrap();
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0]["label"], "Session");
+        assert_eq!(windows[1]["id"], "sonnet");
+        assert_eq!(windows[1]["kind"], "weekly");
+        assert_eq!(windows[1]["limit"], 1000.0);
+        assert_eq!(windows[1]["unit"], "messages");
+    }
+
+    #[test]
+    fn test_credits_output() {
+        let formatter = JsonFormatter::new(false).with_display_currency(
+            Some("USD".to_string()),
+            ExchangeRates::new("USD").with_rate("CNY", 8.0),
+        );
+        let credits = Credits::from_amount(Amount::new(80.0, "CNY")).with_total(160.0);
+
+        let json = serde_json::to_value(formatter.credits_to_output(&credits)).unwrap();
+        assert_eq!(json["remaining"]["unit"], "CNY");
+        assert_eq!(json["remaining"]["formatted"], "CN¥80.00");
+        assert_eq!(json["remaining"]["converted"]["value"], 10.0);
+        assert_eq!(json["total"]["value"], 160.0);
+        assert!(json.get("spendLimit").is_none());
+    }
+
     #[test]
     fn test_source_format() {
         let formatter = JsonFormatter::new(false);
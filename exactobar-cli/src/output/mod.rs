@@ -1,9 +1,11 @@
 //! Output formatting for CLI.
 
+mod bar;
 mod json;
 mod text;
 
-pub use json::JsonFormatter;
+pub use bar::{BarFormat, BarFormatter};
+pub use json::{JsonFormatter, ProviderOutput};
 pub use text::TextFormatter;
 #[cfg(test)]
 mod tests;
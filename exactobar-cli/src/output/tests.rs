@@ -72,7 +72,7 @@ mod text_formatter_tests {
         let formatter = TextFormatter::new(false);
 
         let mut snapshot = UsageSnapshot::new();
-        snapshot.primary = Some(UsageWindow::new(50.0));
+        snapshot.set_primary(Some(UsageWindow::new(50.0)));
         snapshot.fetch_source = FetchSource::CLI;
 
         let output = formatter.format_usage(&snapshot, None, false);
@@ -87,9 +87,9 @@ mod text_formatter_tests {
         let formatter = TextFormatter::new(false);
 
         let mut snapshot = UsageSnapshot::new();
-        snapshot.primary = Some(UsageWindow::new(25.0));
-        snapshot.secondary = Some(UsageWindow::new(50.0));
-        snapshot.tertiary = Some(UsageWindow::new(75.0));
+        snapshot.set_primary(Some(UsageWindow::new(25.0)));
+        snapshot.set_secondary(Some(UsageWindow::new(50.0)));
+        snapshot.set_tertiary(Some(UsageWindow::new(75.0)));
 
         let output = formatter.format_usage(&snapshot, None, false);
 
@@ -97,6 +97,11 @@ mod text_formatter_tests {
         assert!(output.contains("75%")); // 100 - 25 = 75% remaining for primary
         assert!(output.contains("50%")); // 100 - 50 = 50% remaining for secondary
         assert!(output.contains("25%")); // 100 - 75 = 25% remaining for tertiary
+
+        // Without a descriptor, slots get their default labels
+        assert!(output.contains("Session"));
+        assert!(output.contains("Weekly"));
+        assert!(output.contains("Opus"));
     }
 
     #[test]
@@ -104,7 +109,7 @@ mod text_formatter_tests {
         let formatter = TextFormatter::new(false);
 
         let mut snapshot = UsageSnapshot::new();
-        snapshot.primary = Some(UsageWindow::new(50.0));
+        snapshot.set_primary(Some(UsageWindow::new(50.0)));
 
         let mut identity = ProviderIdentity::new(ProviderKind::Claude);
         identity.account_email = Some("user@example.com".to_string());
@@ -124,11 +129,11 @@ mod text_formatter_tests {
         let mut results = HashMap::new();
 
         let mut snapshot1 = UsageSnapshot::new();
-        snapshot1.primary = Some(UsageWindow::new(25.0));
+        snapshot1.set_primary(Some(UsageWindow::new(25.0)));
         results.insert(ProviderKind::Claude, Some(snapshot1));
 
         let mut snapshot2 = UsageSnapshot::new();
-        snapshot2.primary = Some(UsageWindow::new(75.0));
+        snapshot2.set_primary(Some(UsageWindow::new(75.0)));
         results.insert(ProviderKind::Codex, Some(snapshot2));
 
         results.insert(ProviderKind::Cursor, None); // Error case
@@ -155,6 +160,7 @@ mod text_formatter_tests {
 mod json_formatter_tests {
     use super::super::json::JsonFormatter;
     use exactobar_core::{ProviderKind, UsageSnapshot, UsageWindow};
+    use exactobar_fetch::{FetchErrorKind, FetchFailure};
     use std::collections::HashMap;
 
     #[test]
@@ -200,7 +206,13 @@ mod json_formatter_tests {
         let formatter = JsonFormatter::new(true);
 
         let mut results = HashMap::new();
-        results.insert(ProviderKind::Claude, Err("Connection timeout".to_string()));
+        results.insert(
+            ProviderKind::Claude,
+            Err(FetchFailure::new(
+                FetchErrorKind::Timeout,
+                "Connection timeout",
+            )),
+        );
 
         let output = formatter.format_results(&results).unwrap();
 
@@ -215,8 +227,8 @@ mod json_formatter_tests {
         let mut results = HashMap::new();
 
         let mut snapshot = UsageSnapshot::new();
-        snapshot.primary = Some(UsageWindow::new(45.5));
-        snapshot.secondary = Some(UsageWindow::new(20.0));
+        snapshot.set_primary(Some(UsageWindow::new(45.5)));
+        snapshot.set_secondary(Some(UsageWindow::new(20.0)));
         results.insert(ProviderKind::Claude, Some(snapshot));
 
         results.insert(ProviderKind::Codex, None);
@@ -231,7 +243,7 @@ mod json_formatter_tests {
     fn test_format_empty_results() {
         let formatter = JsonFormatter::new(true);
 
-        let results: HashMap<ProviderKind, Result<UsageSnapshot, String>> = HashMap::new();
+        let results: HashMap<ProviderKind, Result<UsageSnapshot, FetchFailure>> = HashMap::new();
         let output = formatter.format_results(&results).unwrap();
 
         let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
@@ -284,7 +296,7 @@ mod output_snapshot_tests {
         for (source, expected_label) in sources {
             let mut snapshot = UsageSnapshot::new();
             snapshot.fetch_source = source;
-            snapshot.primary = Some(UsageWindow::new(50.0));
+            snapshot.set_primary(Some(UsageWindow::new(50.0)));
 
             let output = formatter.format_usage(&snapshot, None, false);
             assert!(
@@ -1,7 +1,9 @@
 //! Text output formatting with progress bars and colors.
 
 use chrono::{DateTime, Duration, Local, Utc};
-use exactobar_core::{FetchSource, ProviderKind, UsageSnapshot, UsageWindow};
+use exactobar_core::{
+    Amount, Credits, ExchangeRates, FetchSource, ProviderKind, UsageSnapshot, UsageWindow,
+};
 use exactobar_providers::ProviderDescriptor;
 use exactobar_store::CostUsageSnapshot;
 use std::collections::HashMap;
@@ -28,6 +30,8 @@ pub struct TextFormatter {
     use_colors: bool,
     show_reset_countdown: bool,
     bar_width: usize,
+    display_currency: Option<String>,
+    exchange_rates: ExchangeRates,
 }
 
 impl TextFormatter {
@@ -37,9 +41,18 @@ impl TextFormatter {
             use_colors,
             show_reset_countdown: true,
             bar_width: 10,
+            display_currency: None,
+            exchange_rates: ExchangeRates::default(),
         }
     }
 
+    /// Converts money amounts to `currency` for display using `rates`.
+    pub fn with_display_currency(mut self, currency: Option<String>, rates: ExchangeRates) -> Self {
+        self.display_currency = currency;
+        self.exchange_rates = rates;
+        self
+    }
+
     /// Set the progress bar width.
     #[allow(dead_code)]
     pub fn with_bar_width(mut self, width: usize) -> Self {
@@ -52,7 +65,7 @@ impl TextFormatter {
         &self,
         snapshot: &UsageSnapshot,
         desc: Option<&ProviderDescriptor>,
-        _show_credits: bool,
+        show_credits: bool,
     ) -> String {
         let mut lines = Vec::new();
 
@@ -62,33 +75,19 @@ impl TextFormatter {
 
         lines.push(format!("{} ({})", self.bold(name), source));
 
-        // Primary window (Session)
-        if let Some(primary) = &snapshot.primary {
-            let label = desc
-                .map(|d| d.metadata.session_label.as_str())
-                .unwrap_or("Session");
-            lines.push(self.format_window(primary, label));
+        // Every window, in provider order (session, weekly, opus, models...)
+        for window in &snapshot.windows {
+            let label = self.window_label(window, desc);
+            lines.push(self.format_window(window, &label));
         }
 
-        // Secondary window (Weekly)
-        if let Some(secondary) = &snapshot.secondary {
-            let label = desc
-                .map(|d| d.metadata.weekly_label.as_str())
-                .unwrap_or("Weekly");
-            lines.push(self.format_window(secondary, label));
-        }
-
-        // Tertiary window (Opus)
-        if let Some(tertiary) = &snapshot.tertiary {
-            let label = desc
-                .and_then(|d| d.metadata.opus_label.as_deref())
-                .unwrap_or("Opus");
-            lines.push(self.format_window(tertiary, label));
+        // Credits and spend
+        if show_credits {
+            if let Some(credits) = &snapshot.credits {
+                lines.extend(self.format_credits(credits));
+            }
         }
 
-        // Credits would come from separate store
-        // For now we skip this as UsageSnapshot doesn't have credits directly
-
         // Identity
         if let Some(identity) = &snapshot.identity {
             if let Some(email) = &identity.account_email {
@@ -105,6 +104,22 @@ impl TextFormatter {
         lines.join("\n")
     }
 
+    /// Resolves the display label for a window.
+    ///
+    /// Same as the JSON output: the provider's metadata decides, and
+    /// without a descriptor the window's own label or ID is used.
+    fn window_label(&self, window: &UsageWindow, desc: Option<&ProviderDescriptor>) -> String {
+        match desc {
+            Some(d) => d.metadata.window_label(window),
+            None => window.label.clone().unwrap_or_else(|| {
+                window.slot.map_or_else(
+                    || window.id.clone(),
+                    |slot| slot.default_label().to_string(),
+                )
+            }),
+        }
+    }
+
     /// Formats a usage window with progress bar.
     fn format_window(&self, window: &UsageWindow, label: &str) -> String {
         let remaining = 100.0 - window.used_percent;
@@ -113,6 +128,21 @@ impl TextFormatter {
 
         let mut result = format!("{:<8} {} {}", format!("{}:", label), bar, pct_str);
 
+        // Add absolute counts when the provider reports them
+        if let Some(used) = window.used {
+            let unit = window.unit.as_deref().unwrap_or("");
+            let counts = match window.limit {
+                Some(limit) => format!(
+                    "{} / {} {}",
+                    self.format_number(used),
+                    self.format_number(limit),
+                    unit
+                ),
+                None => format!("{} {}", self.format_number(used), unit),
+            };
+            result.push_str(&format!(" {}", self.dim(counts.trim_end())));
+        }
+
         // Add reset time
         if let Some(resets_at) = window.resets_at {
             let reset_str = self.format_reset_time(resets_at);
@@ -124,6 +154,64 @@ impl TextFormatter {
         result
     }
 
+    /// Formats credit balance and spend lines.
+    fn format_credits(&self, credits: &Credits) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        let remaining = self.format_amount(&credits.remaining_amount());
+        match credits.total_amount() {
+            Some(total) => lines.push(format!(
+                "Credits: {} left of {}",
+                self.green(&remaining),
+                self.format_amount(&total)
+            )),
+            None => lines.push(format!("Credits: {}", self.green(&remaining))),
+        }
+
+        if let Some(spend) = &credits.spend_limit {
+            let mut line = format!("Spend:   {}", self.format_amount(&spend.used));
+            if let Some(cap) = &spend.cap {
+                line.push_str(&format!(" / {}", self.format_amount(cap)));
+            }
+            if let Some(pct) = spend.used_percent() {
+                let remaining = 100.0 - pct;
+                line.push_str(&format!(
+                    " {}",
+                    self.color_for_percent(remaining, &format!("({:.0}%)", pct))
+                ));
+            }
+            if spend.is_over_cap() {
+                line.push_str(&format!(" {}", self.red("cap reached")));
+            } else if spend.overage_enabled {
+                line.push_str(&format!(" {}", self.dim("overage on")));
+            }
+            lines.push(line);
+
+            if let Some(resets_at) = spend.resets_at {
+                lines.push(format!(
+                    "         Resets {}",
+                    self.dim(&self.format_reset_time(resets_at))
+                ));
+            }
+        }
+
+        lines
+    }
+
+    /// Formats an amount, with the display-currency equivalent if configured.
+    pub fn format_amount(&self, amount: &Amount) -> String {
+        let converted = self
+            .display_currency
+            .as_deref()
+            .filter(|to| !amount.unit.eq_ignore_ascii_case(to))
+            .and_then(|to| self.exchange_rates.convert(amount, to));
+
+        match converted {
+            Some(converted) => format!("{} (≈ {})", amount, converted),
+            None => amount.to_string(),
+        }
+    }
+
     /// Formats a progress bar.
     pub fn progress_bar(&self, percent_remaining: f64) -> String {
         let filled = ((percent_remaining / 100.0) * self.bar_width as f64).round() as usize;
@@ -297,7 +385,7 @@ impl TextFormatter {
             let name = desc.map(|d| d.display_name()).unwrap_or("Unknown");
 
             if let Some(snap) = snapshot {
-                if let Some(primary) = &snap.primary {
+                if let Some(primary) = snap.primary() {
                     let remaining = 100.0 - primary.used_percent;
                     let bar = self.progress_bar(remaining);
                     let pct = self.color_for_percent(remaining, &format!("{:.0}%", remaining));
@@ -318,6 +406,58 @@ impl TextFormatter {
         format!("{}: {} - {}", self.bold(provider), self.red("Error"), error)
     }
 
+    // ========================================================================
+    // Status line
+    // ========================================================================
+
+    /// Formats a window for one-line output: `Session 42% ↻2h10m`.
+    pub fn format_window_compact(
+        &self,
+        window: &UsageWindow,
+        desc: Option<&ProviderDescriptor>,
+    ) -> String {
+        let remaining = 100.0 - window.used_percent;
+        let mut result = format!(
+            "{} {}",
+            self.window_label(window, desc),
+            self.color_for_percent(remaining, &format!("{:.0}%", window.used_percent))
+        );
+        if let Some(resets_at) = window.resets_at {
+            result.push_str(&format!(
+                " {}",
+                self.dim(&format!("↻{}", self.format_countdown(resets_at)))
+            ));
+        }
+        result
+    }
+
+    /// Joins status line segments after a bold `title`.
+    pub fn format_statusline(&self, title: Option<&str>, segments: &[String]) -> String {
+        let mut parts: Vec<String> = title.map(|t| self.bold(t)).into_iter().collect();
+        parts.extend(segments.iter().cloned());
+        parts.join(&self.dim(" │ "))
+    }
+
+    /// Formats time until `resets_at` as `3d4h`, `2h10m` or `12m`.
+    pub fn format_countdown(&self, resets_at: DateTime<Utc>) -> String {
+        let diff = resets_at - Utc::now();
+        if diff <= Duration::zero() {
+            return "now".to_string();
+        }
+        let (days, hours, mins) = (
+            diff.num_days(),
+            diff.num_hours() % 24,
+            diff.num_minutes() % 60,
+        );
+        if days > 0 {
+            format!("{}d{}h", days, hours)
+        } else if hours > 0 {
+            format!("{}h{}m", hours, mins)
+        } else {
+            format!("{}m", mins)
+        }
+    }
+
     // ========================================================================
     // Color/style helpers
     // ========================================================================
@@ -462,4 +602,83 @@ mod tests {
         assert!(output.contains("Session:"));
         assert!(output.contains("72% left"));
     }
+
+    #[test]
+    fn test_format_window_counts() {
+        let formatter = TextFormatter::new(false);
+        let window = UsageWindow::new(40.0).with_counts(400.0, Some(1000.0), "requests");
+        let output = formatter.format_window(&window, "Monthly");
+        assert!(output.contains("400 / 1.0K requests"));
+    }
+
+    #[test]
+    fn test_format_credits_currency() {
+        let formatter = TextFormatter::new(false);
+        let mut snapshot = UsageSnapshot::new();
+        snapshot.credits = Some(Credits::from_amount(Amount::new(88.0, "CNY")));
+
+        let output = formatter.format_usage(&snapshot, None, true);
+        assert!(output.contains("Credits: CN¥88.00"));
+
+        let hidden = formatter.format_usage(&snapshot, None, false);
+        assert!(!hidden.contains("Credits"));
+    }
+
+    #[test]
+    fn test_format_amount_converted() {
+        let formatter = TextFormatter::new(false).with_display_currency(
+            Some("USD".to_string()),
+            ExchangeRates::new("USD").with_rate("CNY", 8.0),
+        );
+        assert_eq!(
+            formatter.format_amount(&Amount::new(80.0, "CNY")),
+            "CN¥80.00 (≈ $10.00)"
+        );
+        assert_eq!(formatter.format_amount(&Amount::usd(5.0)), "$5.00");
+        assert_eq!(formatter.format_amount(&Amount::credits(5.0)), "5 credits");
+    }
+
+    #[test]
+    fn test_format_spend_limit() {
+        let formatter = TextFormatter::new(false);
+        let spend = exactobar_core::SpendLimit::new(Amount::usd(12.5)).with_cap(Amount::usd(50.0));
+        let credits = Credits::from_amount(Amount::usd(37.5)).with_spend_limit(spend);
+
+        let lines = formatter.format_credits(&credits);
+        assert_eq!(lines[1], "Spend:   $12.50 / $50.00 (25%)");
+    }
+
+    #[test]
+    fn test_format_usage_lists_every_window() {
+        let formatter = TextFormatter::new(false);
+        let mut snapshot = UsageSnapshot::new();
+        snapshot.set_primary(Some(UsageWindow::new(10.0)));
+        snapshot.push_window(
+            UsageWindow::named(
+                "gemini-2.5-flash-lite",
+                exactobar_core::WindowKind::Model,
+                5.0,
+            )
+            .with_label("Flash Lite"),
+        );
+
+        let desc = exactobar_providers::ProviderRegistry::get(ProviderKind::Claude);
+        let output = formatter.format_usage(&snapshot, desc, false);
+        assert!(output.contains("Session:"));
+        assert!(output.contains("Flash Lite:"));
+    }
+
+    #[test]
+    fn test_format_statusline() {
+        let formatter = TextFormatter::new(false);
+        let mut window = UsageWindow::new(42.4).with_label("5h");
+        window.resets_at = Some(Utc::now() + Duration::minutes(130) + Duration::seconds(30));
+
+        let segment = formatter.format_window_compact(&window, None);
+        assert_eq!(segment, "5h 42% ↻2h10m");
+
+        let line = formatter.format_statusline(Some("Opus"), &[segment, "$1.20".to_string()]);
+        assert_eq!(line, "Opus │ 5h 42% ↻2h10m │ $1.20");
+        assert_eq!(formatter.format_statusline(None, &[]), "");
+    }
 }
@@ -0,0 +1,232 @@
+//! Background refresh and the latest results per provider.
+
+use chrono::{DateTime, Utc};
+use exactobar_core::{ProviderKind, ProviderStatus, UsageSnapshot};
+use exactobar_fetch::host::status::urls;
+use exactobar_fetch::{FetchAttempt, FetchContext, FetchFailure};
+use exactobar_providers::{ProviderDescriptor, ProviderRegistry};
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+// ============================================================================
+// Provider State
+// ============================================================================
+
+/// The latest fetch for one provider.
+#[derive(Debug, Clone)]
+pub struct ProviderState {
+    /// The provider.
+    pub provider: ProviderKind,
+    /// The snapshot, or the error of the last attempt.
+    pub result: Result<UsageSnapshot, FetchFailure>,
+    /// Strategies tried, in order.
+    pub attempts: Vec<FetchAttempt>,
+    /// Total pipeline duration.
+    pub duration: Duration,
+    /// When the fetch finished.
+    pub fetched_at: DateTime<Utc>,
+    /// Status page indicator, when status checks are enabled.
+    pub status: Option<ProviderStatus>,
+}
+
+// ============================================================================
+// Snapshot Cache
+// ============================================================================
+
+/// Latest results, shared between the refresher and request handlers.
+#[derive(Debug, Default)]
+pub struct SnapshotCache {
+    states: RwLock<Vec<ProviderState>>,
+}
+
+impl SnapshotCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores a provider's state, keeping first-seen order.
+    pub async fn put(&self, state: ProviderState) {
+        let mut states = self.states.write().await;
+        match states.iter_mut().find(|s| s.provider == state.provider) {
+            Some(existing) => *existing = state,
+            None => states.push(state),
+        }
+    }
+
+    /// Returns all states.
+    pub async fn states(&self) -> Vec<ProviderState> {
+        self.states.read().await.clone()
+    }
+
+    /// Returns one provider's state.
+    pub async fn get(&self, provider: ProviderKind) -> Option<ProviderState> {
+        self.states
+            .read()
+            .await
+            .iter()
+            .find(|s| s.provider == provider)
+            .cloned()
+    }
+
+    /// Returns true once any provider has been fetched.
+    pub async fn is_ready(&self) -> bool {
+        !self.states.read().await.is_empty()
+    }
+}
+
+// ============================================================================
+// Refresher
+// ============================================================================
+
+/// Fetches a fixed set of providers into a cache.
+pub struct Refresher {
+    providers: Vec<ProviderKind>,
+    ctx: FetchContext,
+    status_checks: bool,
+}
+
+impl Refresher {
+    /// Creates a refresher.
+    pub fn new(providers: Vec<ProviderKind>, ctx: FetchContext, status_checks: bool) -> Self {
+        Self {
+            providers,
+            ctx,
+            status_checks,
+        }
+    }
+
+    /// Returns the providers being refreshed.
+    pub fn providers(&self) -> &[ProviderKind] {
+        &self.providers
+    }
+
+    /// Fetches every provider once.
+    pub async fn refresh(&self, cache: &SnapshotCache) {
+        for provider in &self.providers {
+            if let Some(state) = self.fetch_provider(*provider).await {
+                cache.put(state).await;
+            }
+        }
+    }
+
+    /// Fetches one provider, which need not be in [`Self::providers`].
+    ///
+    /// Returns `None` if the provider isn't registered.
+    pub async fn fetch_provider(&self, provider: ProviderKind) -> Option<ProviderState> {
+        let registry = ProviderRegistry::for_context(&self.ctx);
+        let desc = registry.get(provider)?;
+        Some(self.fetch(desc).await)
+    }
+
+    /// Refreshes now, then every `interval` (or never, for manual cadence).
+    pub async fn run(self, cache: std::sync::Arc<SnapshotCache>, interval: Option<Duration>) {
+        loop {
+            self.refresh(&cache).await;
+            let Some(interval) = interval else {
+                info!("Manual refresh cadence: serving the startup fetch");
+                return;
+            };
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    async fn fetch(&self, desc: &ProviderDescriptor) -> ProviderState {
+        let pipeline = desc.build_pipeline(&self.ctx);
+        let outcome = pipeline.execute(&self.ctx).await;
+
+        let result = match outcome.result {
+            Ok(fetch) => {
+                debug!(provider = %desc.cli_name(), strategy = %fetch.strategy_id, "Refreshed");
+                Ok(fetch.snapshot)
+            }
+            Err(e) => {
+                warn!(provider = %desc.cli_name(), error = %e, "Refresh failed");
+                Err(FetchFailure::from(e))
+            }
+        };
+
+        let status = match status_api_url(desc) {
+            Some(url) if self.status_checks => self.ctx.status.fetch_status(&url).await.ok(),
+            _ => None,
+        };
+
+        ProviderState {
+            provider: desc.id,
+            result,
+            attempts: outcome.attempts,
+            duration: outcome.duration,
+            fetched_at: Utc::now(),
+            status,
+        }
+    }
+}
+
+/// Returns the statuspage.io API URL for a provider, if it has one.
+pub fn status_api_url(desc: &ProviderDescriptor) -> Option<String> {
+    desc.metadata
+        .status_page_url
+        .clone()
+        .filter(|url| url.ends_with("/api/v2/status.json"))
+        .or_else(|| urls::api_url_for_provider(desc.cli_name()).map(str::to_string))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exactobar_fetch::FetchErrorKind;
+
+    fn state(provider: ProviderKind, result: Result<UsageSnapshot, FetchFailure>) -> ProviderState {
+        ProviderState {
+            provider,
+            result,
+            attempts: Vec::new(),
+            duration: Duration::ZERO,
+            fetched_at: Utc::now(),
+            status: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_keeps_order() {
+        let cache = SnapshotCache::new();
+        assert!(!cache.is_ready().await);
+
+        cache
+            .put(state(
+                ProviderKind::Claude,
+                Err(FetchFailure::new(FetchErrorKind::Failed, "down")),
+            ))
+            .await;
+        cache
+            .put(state(ProviderKind::Codex, Ok(UsageSnapshot::new())))
+            .await;
+        cache
+            .put(state(ProviderKind::Claude, Ok(UsageSnapshot::new())))
+            .await;
+
+        let states = cache.states().await;
+        assert_eq!(states.len(), 2);
+        assert_eq!(states[0].provider, ProviderKind::Claude);
+        assert!(states[0].result.is_ok());
+        assert!(cache.get(ProviderKind::Cursor).await.is_none());
+    }
+
+    #[test]
+    fn test_status_api_url() {
+        let codex = ProviderRegistry::get(ProviderKind::Codex).unwrap();
+        assert_eq!(
+            status_api_url(codex).as_deref(),
+            Some("https://status.openai.com/api/v2/status.json")
+        );
+        let claude = ProviderRegistry::get(ProviderKind::Claude).unwrap();
+        assert_eq!(status_api_url(claude).as_deref(), Some(urls::ANTHROPIC));
+        let zai = ProviderRegistry::get(ProviderKind::Zai).unwrap();
+        assert!(status_api_url(zai).is_none());
+    }
+}
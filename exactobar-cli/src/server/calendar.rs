@@ -0,0 +1,225 @@
+//! iCalendar feed of upcoming quota resets.
+//!
+//! One event per window with a known `resets_at`. UIDs are stable per
+//! provider and window, so a subscribed calendar moves the event when the
+//! reset time changes instead of adding a new one. Windows with a known
+//! length repeat with an RRULE; session windows don't, since they start on
+//! first use rather than on a schedule.
+
+use chrono::{DateTime, Utc};
+use exactobar_core::{ProviderKind, UsageSnapshot, UsageWindow, WindowKind};
+use exactobar_providers::ProviderRegistry;
+
+/// Content type for iCalendar.
+pub const CONTENT_TYPE: &str = "text/calendar; charset=utf-8";
+
+/// How long each reset event lasts in the calendar.
+const EVENT_DURATION: &str = "PT15M";
+
+/// How often subscribed calendars should re-fetch the feed.
+const REFRESH_INTERVAL: &str = "PT1H";
+
+/// Longest content line before folding, in octets (RFC 5545 §3.1).
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Renders reset events for the given snapshots.
+pub fn render(snapshots: &[(ProviderKind, &UsageSnapshot)], now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//ExactoBar//Quota resets//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        "X-WR-CALNAME:ExactoBar quota resets".to_string(),
+        format!("REFRESH-INTERVAL;VALUE=DURATION:{}", REFRESH_INTERVAL),
+        format!("X-PUBLISHED-TTL:{}", REFRESH_INTERVAL),
+    ];
+
+    for (provider, snapshot) in snapshots {
+        for window in &snapshot.windows {
+            if let Some(resets_at) = window.resets_at {
+                push_event(&mut lines, *provider, snapshot, window, resets_at, now);
+            }
+        }
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+    let mut out = String::new();
+    for line in &lines {
+        fold_line(line, &mut out);
+    }
+    out
+}
+
+fn push_event(
+    lines: &mut Vec<String>,
+    provider: ProviderKind,
+    snapshot: &UsageSnapshot,
+    window: &UsageWindow,
+    resets_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) {
+    let name = ProviderRegistry::get(provider).map_or_else(
+        || provider.cli_name().to_string(),
+        |d| d.display_name().to_string(),
+    );
+    let window_name = window
+        .label
+        .clone()
+        .unwrap_or_else(|| window.id.replace('_', " "));
+
+    lines.push("BEGIN:VEVENT".to_string());
+    lines.push(format!(
+        "UID:{}-{}@exactobar",
+        provider.cli_name(),
+        window.id
+    ));
+    lines.push(format!("DTSTAMP:{}", timestamp(now)));
+    lines.push(format!("LAST-MODIFIED:{}", timestamp(snapshot.updated_at)));
+    lines.push(format!("DTSTART:{}", timestamp(resets_at)));
+    lines.push(format!("DURATION:{}", EVENT_DURATION));
+    if let Some(rule) = rrule(window) {
+        lines.push(format!("RRULE:{}", rule));
+    }
+    lines.push(format!(
+        "SUMMARY:{}",
+        escape_text(&format!("{} {} resets", name, window_name))
+    ));
+    lines.push(format!(
+        "DESCRIPTION:{}",
+        escape_text(&format!(
+            "{:.0}% used as of {}.",
+            window.used_percent,
+            snapshot.updated_at.format("%Y-%m-%d %H:%M UTC")
+        ))
+    ));
+    lines.push("TRANSP:TRANSPARENT".to_string());
+    lines.push("END:VEVENT".to_string());
+}
+
+/// Returns the recurrence rule for a window, if it repeats on a schedule.
+fn rrule(window: &UsageWindow) -> Option<String> {
+    if window.kind == WindowKind::Session {
+        return None;
+    }
+    let Some(minutes) = window.window_minutes.filter(|m| *m > 0) else {
+        return (window.kind == WindowKind::Monthly).then(|| "FREQ=MONTHLY".to_string());
+    };
+
+    let (freq, interval) = if minutes % (7 * 24 * 60) == 0 {
+        ("WEEKLY", minutes / (7 * 24 * 60))
+    } else if minutes % (24 * 60) == 0 {
+        ("DAILY", minutes / (24 * 60))
+    } else if minutes % 60 == 0 {
+        ("HOURLY", minutes / 60)
+    } else {
+        ("MINUTELY", minutes)
+    };
+    Some(if interval == 1 {
+        format!("FREQ={}", freq)
+    } else {
+        format!("FREQ={};INTERVAL={}", freq, interval)
+    })
+}
+
+/// Formats a UTC date-time (`20240105T093000Z`).
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escapes a TEXT value.
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Appends a content line, folded at 75 octets, with CRLF endings.
+fn fold_line(line: &str, out: &mut String) {
+    let mut octets = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if octets + len > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            // The leading space counts towards the next line.
+            octets = 1;
+        }
+        out.push(c);
+        octets += len;
+    }
+    out.push_str("\r\n");
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_render() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 5, 9, 30, 0).unwrap();
+        let mut snapshot = UsageSnapshot::new();
+        snapshot.updated_at = now;
+        let mut session = UsageWindow::named("session", WindowKind::Session, 42.0);
+        session.window_minutes = Some(300);
+        session.resets_at = Some(now + chrono::Duration::hours(2));
+        let mut weekly = UsageWindow::named("weekly", WindowKind::Weekly, 70.0);
+        weekly.window_minutes = Some(7 * 24 * 60);
+        weekly.resets_at = Some(now + chrono::Duration::days(3));
+        snapshot.push_window(session);
+        snapshot.push_window(weekly);
+        snapshot.push_window(UsageWindow::named("opus", WindowKind::Model, 5.0));
+
+        let ics = render(&[(ProviderKind::Claude, &snapshot)], now);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("UID:claude-weekly@exactobar\r\n"));
+        assert!(ics.contains("DTSTART:20240108T093000Z\r\n"));
+        assert!(ics.contains("SUMMARY:Claude weekly resets\r\n"));
+        assert_eq!(ics.matches("RRULE:").count(), 1);
+        assert!(ics.contains("RRULE:FREQ=WEEKLY\r\n"));
+    }
+
+    #[test]
+    fn test_rrule() {
+        let window = |kind, minutes| UsageWindow {
+            window_minutes: minutes,
+            ..UsageWindow::named("w", kind, 0.0)
+        };
+        assert_eq!(
+            rrule(&window(WindowKind::Other, Some(2 * 24 * 60))).as_deref(),
+            Some("FREQ=DAILY;INTERVAL=2")
+        );
+        assert_eq!(
+            rrule(&window(WindowKind::Model, Some(90))).as_deref(),
+            Some("FREQ=MINUTELY;INTERVAL=90")
+        );
+        assert_eq!(
+            rrule(&window(WindowKind::Monthly, None)).as_deref(),
+            Some("FREQ=MONTHLY")
+        );
+        assert_eq!(rrule(&window(WindowKind::Session, Some(300))), None);
+        assert_eq!(rrule(&window(WindowKind::Other, None)), None);
+    }
+
+    #[test]
+    fn test_text_and_folding() {
+        assert_eq!(escape_text("a;b,c\\d\ne"), "a\\;b\\,c\\\\d\\ne");
+
+        let mut out = String::new();
+        fold_line(&format!("SUMMARY:{}", "é".repeat(50)), &mut out);
+        for line in out.split("\r\n") {
+            assert!(line.len() <= MAX_LINE_OCTETS);
+        }
+        assert_eq!(
+            out.replace("\r\n ", ""),
+            format!("SUMMARY:{}\r\n", "é".repeat(50))
+        );
+    }
+}
@@ -0,0 +1,399 @@
+//! Background daemon serving a shared `UsageStore` over a Unix socket.
+//!
+//! See [`exactobar_store::ipc`] for the protocol. Refreshes of the same
+//! provider are never run twice at once: a request that arrives while one
+//! is in flight waits for it and returns its result.
+
+use anyhow::{Context, Result};
+use exactobar_core::ProviderKind;
+use exactobar_fetch::FetchFailure;
+use exactobar_store::ipc::{read_message, write_message};
+use exactobar_store::{
+    DaemonRequest, DaemonResponse, PROTOCOL_VERSION, ProviderEntry, StoreError, UsageStore,
+};
+use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncWrite, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::Notify;
+use tracing::{debug, info};
+
+use super::cache::Refresher;
+
+// ============================================================================
+// Daemon
+// ============================================================================
+
+/// Owns the store and refresh loop shared by every client.
+pub struct Daemon {
+    store: UsageStore,
+    refresher: Refresher,
+    interval: Option<Duration>,
+    shutdown: Notify,
+}
+
+impl Daemon {
+    /// Creates a daemon refreshing `refresher`'s providers every `interval`.
+    pub fn new(refresher: Refresher, interval: Option<Duration>) -> Self {
+        let store = UsageStore::with_enabled(refresher.providers().iter().copied().collect());
+        Self {
+            store,
+            refresher,
+            interval,
+            shutdown: Notify::new(),
+        }
+    }
+
+    /// Returns the store.
+    pub fn store(&self) -> &UsageStore {
+        &self.store
+    }
+
+    /// Refreshes a provider, or waits for the refresh already running.
+    pub async fn refresh(&self, provider: ProviderKind) {
+        if self.store.start_refresh(provider).await.is_err() {
+            self.wait_for_refresh(provider).await;
+            return;
+        }
+
+        match self.refresher.fetch_provider(provider).await {
+            Some(state) => {
+                match state.result {
+                    Ok(snapshot) => self.store.set_snapshot(provider, snapshot).await,
+                    Err(e) => self.store.set_error(provider, e).await,
+                }
+                if let Some(status) = state.status {
+                    self.store.set_status(provider, status).await;
+                }
+            }
+            None => {
+                self.store
+                    .set_error(
+                        provider,
+                        FetchFailure::provider_missing("Provider not registered"),
+                    )
+                    .await;
+            }
+        }
+        self.store.end_refresh(provider).await;
+    }
+
+    async fn wait_for_refresh(&self, provider: ProviderKind) {
+        let mut changes = self.store.subscribe();
+        while self.store.is_refreshing(provider).await {
+            if changes.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Refreshes every provider now, then on the interval.
+    pub async fn run_refresh_loop(self: Arc<Self>) {
+        loop {
+            for provider in self.refresher.providers() {
+                self.refresh(*provider).await;
+            }
+            let Some(interval) = self.interval else {
+                info!("Manual refresh cadence: refreshing only on request");
+                return;
+            };
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// Returns the current state of a provider.
+    pub async fn entry(&self, provider: ProviderKind) -> ProviderEntry {
+        let error = self.store.get_error(provider).await;
+        ProviderEntry {
+            provider,
+            snapshot: self.store.get_snapshot(provider).await,
+            error_kind: error.as_ref().map(|e| e.kind),
+            error: error.map(|e| e.message),
+            status: self.store.get_status(provider).await,
+            refreshing: self.store.is_refreshing(provider).await,
+        }
+    }
+
+    async fn entries(&self, providers: &[ProviderKind]) -> Vec<ProviderEntry> {
+        let mut entries = Vec::with_capacity(providers.len());
+        for provider in providers {
+            entries.push(self.entry(*provider).await);
+        }
+        entries
+    }
+
+    /// Returns true if a provider has a snapshot younger than `max_age`
+    /// and its last refresh succeeded.
+    async fn is_fresh(&self, provider: ProviderKind, max_age: Option<Duration>) -> bool {
+        if self.store.get_error(provider).await.is_some() {
+            return false;
+        }
+        let Some(age) = self.store.snapshot_age(provider).await else {
+            return false;
+        };
+        max_age.is_none_or(|max| age.to_std().unwrap_or_default() <= max)
+    }
+
+    fn resolve(&self, providers: Vec<ProviderKind>) -> Vec<ProviderKind> {
+        if providers.is_empty() {
+            self.refresher.providers().to_vec()
+        } else {
+            providers
+        }
+    }
+
+    /// Answers a request (other than the handshake and `subscribe`).
+    pub async fn respond(&self, request: DaemonRequest) -> DaemonResponse {
+        match request {
+            DaemonRequest::Get {
+                providers,
+                max_age_secs,
+            } => {
+                let providers = self.resolve(providers);
+                let max_age = max_age_secs.map(Duration::from_secs).or(self.interval);
+                for provider in &providers {
+                    if !self.is_fresh(*provider, max_age).await {
+                        self.refresh(*provider).await;
+                    }
+                }
+                DaemonResponse::Snapshots {
+                    entries: self.entries(&providers).await,
+                }
+            }
+            DaemonRequest::Refresh { providers } => {
+                let providers = self.resolve(providers);
+                for provider in &providers {
+                    self.refresh(*provider).await;
+                }
+                DaemonResponse::Snapshots {
+                    entries: self.entries(&providers).await,
+                }
+            }
+            DaemonRequest::Shutdown => {
+                self.shutdown.notify_one();
+                DaemonResponse::Ok
+            }
+            DaemonRequest::Hello { .. } | DaemonRequest::Subscribe { .. } => {
+                DaemonResponse::Error {
+                    message: "unexpected request".to_string(),
+                }
+            }
+        }
+    }
+
+    fn hello(&self) -> DaemonResponse {
+        DaemonResponse::Hello {
+            version: PROTOCOL_VERSION,
+            daemon_version: env!("CARGO_PKG_VERSION").to_string(),
+            providers: self.refresher.providers().to_vec(),
+            interval_secs: self.interval.map(|d| d.as_secs()),
+        }
+    }
+
+    // ========================================================================
+    // Connections
+    // ========================================================================
+
+    /// Accepts clients until a `shutdown` request arrives.
+    pub async fn serve(self: Arc<Self>, listener: UnixListener) -> Result<()> {
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => {
+                    let (stream, _) = accepted.context("Failed to accept connection")?;
+                    let daemon = Arc::clone(&self);
+                    tokio::spawn(async move {
+                        if let Err(e) = daemon.handle_connection(stream).await {
+                            debug!(error = %e, "Client connection ended");
+                        }
+                    });
+                }
+                () = self.shutdown.notified() => {
+                    info!("Shutdown requested");
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    async fn handle_connection(&self, stream: UnixStream) -> Result<(), StoreError> {
+        let (read, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(read);
+
+        match read_message(&mut reader).await? {
+            Some(DaemonRequest::Hello { version }) => {
+                write_message(&mut writer, &self.hello()).await?;
+                if version != PROTOCOL_VERSION {
+                    debug!(version, "Client speaks another protocol version");
+                    return Ok(());
+                }
+            }
+            Some(_) => {
+                let error = DaemonResponse::Error {
+                    message: "expected hello".to_string(),
+                };
+                return write_message(&mut writer, &error).await;
+            }
+            None => return Ok(()),
+        }
+
+        loop {
+            let request = match read_message(&mut reader).await {
+                Ok(Some(request)) => request,
+                Ok(None) => return Ok(()),
+                Err(StoreError::Serialization(e)) => {
+                    let error = DaemonResponse::Error {
+                        message: format!("invalid request: {e}"),
+                    };
+                    write_message(&mut writer, &error).await?;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            if let DaemonRequest::Subscribe { providers } = request {
+                return self
+                    .stream_updates(self.resolve(providers), &mut writer)
+                    .await;
+            }
+            let response = self.respond(request).await;
+            write_message(&mut writer, &response).await?;
+        }
+    }
+
+    async fn stream_updates<W: AsyncWrite + Unpin>(
+        &self,
+        providers: Vec<ProviderKind>,
+        writer: &mut W,
+    ) -> Result<(), StoreError> {
+        let mut changes = self.store.subscribe();
+        loop {
+            let update = DaemonResponse::Snapshots {
+                entries: self.entries(&providers).await,
+            };
+            write_message(writer, &update).await?;
+            if changes.changed().await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Socket
+// ============================================================================
+
+/// Binds the daemon socket, readable only by the current user.
+///
+/// A leftover socket from a daemon that exited uncleanly is replaced; a
+/// live one is an error.
+pub fn bind(path: &Path) -> Result<UnixListener> {
+    if let Some(dir) = path.parent() {
+        std::fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+
+    if path.exists() {
+        if std::os::unix::net::UnixStream::connect(path).is_ok() {
+            anyhow::bail!("A daemon is already listening on {}", path.display());
+        }
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove stale socket {}", path.display()))?;
+    }
+
+    let listener =
+        UnixListener::bind(path).with_context(|| format!("Failed to bind {}", path.display()))?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exactobar_fetch::FetchContext;
+    use exactobar_providers::ProviderSet;
+    use exactobar_store::DaemonClient;
+
+    fn daemon() -> Arc<Daemon> {
+        // An empty registry makes every refresh fail without any I/O.
+        let mut ctx = FetchContext::new();
+        Arc::new(ProviderSet::new()).attach(&mut ctx);
+        let refresher = Refresher::new(vec![ProviderKind::Zai], ctx, false);
+        Arc::new(Daemon::new(refresher, None))
+    }
+
+    #[tokio::test]
+    async fn test_client_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run").join("daemon.sock");
+        let listener = bind(&path).unwrap();
+        assert!(bind(&path).is_err());
+
+        let daemon = daemon();
+        let server = tokio::spawn(Arc::clone(&daemon).serve(listener));
+
+        let mut client = DaemonClient::connect(&path).await.unwrap();
+        assert_eq!(client.info().providers, vec![ProviderKind::Zai]);
+        assert!(client.info().interval.is_none());
+
+        let entries = client.get(&[], None).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].provider, ProviderKind::Zai);
+        assert_eq!(entries[0].error.as_deref(), Some("Provider not registered"));
+        assert!(!entries[0].refreshing);
+
+        let mut updates = DaemonClient::connect(&path)
+            .await
+            .unwrap()
+            .subscribe(&[ProviderKind::Zai])
+            .await
+            .unwrap();
+        let first = updates.next().await.unwrap().unwrap();
+        assert_eq!(first[0].provider, ProviderKind::Zai);
+
+        client.refresh(&[ProviderKind::Zai]).await.unwrap();
+        assert!(updates.next().await.unwrap().is_some());
+
+        client.shutdown().await.unwrap();
+        server.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejects_other_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("daemon.sock");
+        let listener = bind(&path).unwrap();
+        tokio::spawn(daemon().serve(listener));
+
+        let stream = UnixStream::connect(&path).await.unwrap();
+        let (read, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(read);
+        write_message(&mut writer, &DaemonRequest::Hello { version: 99 })
+            .await
+            .unwrap();
+        let hello: DaemonResponse = read_message(&mut reader).await.unwrap().unwrap();
+        assert!(matches!(hello, DaemonResponse::Hello { version: 1, .. }));
+        let closed: Option<DaemonResponse> = read_message(&mut reader).await.unwrap();
+        assert!(closed.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_refreshes() {
+        let daemon = daemon();
+        let changes = daemon.store().subscribe();
+        tokio::join!(
+            daemon.refresh(ProviderKind::Zai),
+            daemon.refresh(ProviderKind::Zai)
+        );
+        assert!(!daemon.store().is_refreshing(ProviderKind::Zai).await);
+        assert!(changes.has_changed().unwrap());
+    }
+}
@@ -0,0 +1,261 @@
+//! Minimal HTTP/1.1 for the local API.
+//!
+//! Handles one `GET` per connection with `Connection: close`, which is all
+//! Prometheus scrapers and `curl` need. Request bodies are ignored.
+
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Largest request head we accept.
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+
+/// How long a client may take to send its request head.
+pub const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+// ============================================================================
+// Request
+// ============================================================================
+
+/// A parsed request head.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    /// Method, uppercase (e.g., `GET`).
+    pub method: String,
+    /// Path without the query string.
+    pub path: String,
+    /// Query string, without the `?`.
+    pub query: Option<String>,
+    /// Headers with lowercase names.
+    pub headers: Vec<(String, String)>,
+}
+
+impl Request {
+    /// Parses a request head (request line and headers).
+    pub fn parse(head: &str) -> Option<Self> {
+        let mut lines = head.split("\r\n");
+        let mut parts = lines.next()?.split_whitespace();
+        let method = parts.next()?.to_ascii_uppercase();
+        let target = parts.next()?;
+        if !parts.next()?.starts_with("HTTP/1.") {
+            return None;
+        }
+
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path, Some(query.to_string())),
+            None => (target, None),
+        };
+
+        let headers = lines
+            .take_while(|line| !line.is_empty())
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+            .collect();
+
+        Some(Self {
+            method,
+            path: path.to_string(),
+            query,
+            headers,
+        })
+    }
+
+    /// Returns a header value by (case-insensitive) name.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns a query parameter (no percent-decoding).
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.query
+            .as_deref()?
+            .split('&')
+            .filter_map(|pair| pair.split_once('=').or(Some((pair, ""))))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns true if the request carries the bearer token.
+    pub fn has_bearer(&self, token: &str) -> bool {
+        self.header("authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|given| constant_time_eq(given.trim().as_bytes(), token.as_bytes()))
+    }
+
+    /// Returns true if the `token` query parameter matches.
+    pub fn has_token_param(&self, token: &str) -> bool {
+        self.query_param("token")
+            .is_some_and(|given| constant_time_eq(given.as_bytes(), token.as_bytes()))
+    }
+}
+
+/// Compares secrets without exiting early on the first difference.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Reads a request head from a connection.
+///
+/// Returns `None` if the client closed the connection, sent something that
+/// isn't HTTP/1.x, or sent too much.
+pub async fn read_request<S: AsyncRead + Unpin>(stream: &mut S) -> Option<Request> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    loop {
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            return Request::parse(std::str::from_utf8(&buf[..end]).ok()?);
+        }
+        if buf.len() > MAX_HEAD_BYTES {
+            return None;
+        }
+    }
+}
+
+// ============================================================================
+// Response
+// ============================================================================
+
+/// A response to write back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    /// Status code.
+    pub status: u16,
+    /// Content type of the body.
+    pub content_type: String,
+    /// Extra headers.
+    pub headers: Vec<(String, String)>,
+    /// Body.
+    pub body: String,
+}
+
+impl Response {
+    /// Creates a response.
+    pub fn new(status: u16, content_type: &str, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type: content_type.to_string(),
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Creates a `200 OK` plain-text response.
+    pub fn text(body: impl Into<String>) -> Self {
+        Self::new(200, "text/plain; charset=utf-8", body)
+    }
+
+    /// Creates a `200 OK` JSON response.
+    pub fn json(body: impl Into<String>) -> Self {
+        Self::new(200, "application/json", body)
+    }
+
+    /// Creates a JSON error response.
+    pub fn error(status: u16, message: &str) -> Self {
+        let body = serde_json::json!({ "error": message }).to_string();
+        Self::new(status, "application/json", body)
+    }
+
+    /// Adds a header.
+    pub fn with_header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    /// Serializes the status line, headers and body.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+            self.status,
+            reason(self.status),
+            self.content_type,
+            self.body.len()
+        );
+        for (name, value) in &self.headers {
+            out.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        out.push_str("\r\n");
+        out.push_str(&self.body);
+        out.into_bytes()
+    }
+
+    /// Writes the response and shuts down the write side.
+    pub async fn write_to<S: AsyncWrite + Unpin>(&self, stream: &mut S) -> std::io::Result<()> {
+        stream.write_all(&self.to_bytes()).await?;
+        stream.shutdown().await
+    }
+}
+
+/// Returns the reason phrase for the codes we send.
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request() {
+        let req = Request::parse(
+            "GET /v1/snapshots?provider=claude&pretty HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer s3cret\r\n",
+        )
+        .unwrap();
+        assert_eq!(req.method, "GET");
+        assert_eq!(req.path, "/v1/snapshots");
+        assert_eq!(req.query_param("provider"), Some("claude"));
+        assert_eq!(req.query_param("pretty"), Some(""));
+        assert_eq!(req.query_param("missing"), None);
+        assert_eq!(req.header("HOST"), Some("localhost"));
+        assert!(req.has_bearer("s3cret"));
+        assert!(!req.has_bearer("s3cre"));
+        assert!(!req.has_bearer("other"));
+
+        assert!(Request::parse("GET /\r\n").is_none());
+        assert!(Request::parse("HELLO\r\n").is_none());
+    }
+
+    #[test]
+    fn test_response_bytes() {
+        let bytes = Response::error(401, "unauthorized")
+            .with_header("WWW-Authenticate", "Bearer")
+            .to_bytes();
+        let text = String::from_utf8(bytes).unwrap();
+        assert!(text.starts_with("HTTP/1.1 401 Unauthorized\r\n"));
+        assert!(text.contains("Content-Length: 24\r\n"));
+        assert!(text.contains("WWW-Authenticate: Bearer\r\n"));
+        assert!(text.ends_with("\r\n\r\n{\"error\":\"unauthorized\"}"));
+    }
+
+    #[tokio::test]
+    async fn test_read_request() {
+        let (mut client, mut server) = tokio::io::duplex(64);
+        tokio::spawn(async move {
+            client
+                .write_all(b"GET /metrics HTTP/1.1\r\nAccept: text/plain\r\n\r\n")
+                .await
+                .unwrap();
+        });
+        let req = read_request(&mut server).await.unwrap();
+        assert_eq!(req.path, "/metrics");
+        assert_eq!(req.header("accept"), Some("text/plain"));
+    }
+}
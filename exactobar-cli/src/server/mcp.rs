@@ -0,0 +1,605 @@
+//! Model Context Protocol server over stdio.
+//!
+//! Speaks JSON-RPC 2.0, one message per line, so coding agents can check
+//! their own quota before starting expensive work. Snapshots are cached in
+//! a [`UsageStore`] for `max_age` and fetched through the daemon when one
+//! is running, so agents polling between tool calls don't re-run probes.
+
+use anyhow::Result;
+use chrono::Utc;
+use exactobar_core::{ProviderKind, UsageSnapshot, UsageWindow, WindowKind};
+use exactobar_fetch::{FetchErrorKind, FetchFailure};
+use exactobar_providers::ProviderRegistry;
+use exactobar_store::UsageStore;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tracing::debug;
+
+use super::cache::Refresher;
+use crate::commands::cost::{parse_cost_providers, scan_costs};
+use crate::commands::usage::{DaemonFetch, fetch_via_daemon_with, parse_provider_selection};
+use crate::output::{JsonFormatter, ProviderOutput};
+
+/// Protocol revisions this server understands, newest first.
+const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// JSON-RPC error codes.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Server instructions shown to the agent on connect.
+const INSTRUCTIONS: &str = "Reports rate-limit windows, credits and local token costs for LLM \
+providers. Call get_usage or time_until_reset before long tasks; if a window is nearly used up, \
+slow down or switch to a provider with headroom.";
+
+// ============================================================================
+// Server
+// ============================================================================
+
+/// An MCP server answering quota questions.
+pub struct McpServer {
+    store: UsageStore,
+    refresher: Refresher,
+    max_age: Duration,
+    use_daemon: bool,
+}
+
+/// A JSON-RPC request or notification.
+#[derive(Debug, Deserialize)]
+struct RpcMessage {
+    #[serde(default)]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+impl McpServer {
+    /// Creates a server.
+    ///
+    /// `refresher`'s providers answer `get_usage` calls without a provider;
+    /// snapshots younger than `max_age` are served from cache.
+    pub fn new(refresher: Refresher, max_age: Duration, use_daemon: bool) -> Self {
+        Self {
+            store: UsageStore::with_enabled(refresher.providers().iter().copied().collect()),
+            refresher,
+            max_age,
+            use_daemon,
+        }
+    }
+
+    /// Serves stdin/stdout until stdin closes.
+    pub async fn run_stdio(&self) -> Result<()> {
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        let mut stdout = tokio::io::stdout();
+
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = self.handle_line(&line).await {
+                stdout.write_all(response.to_string().as_bytes()).await?;
+                stdout.write_all(b"\n").await?;
+                stdout.flush().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles one message, returning the response (none for notifications).
+    pub async fn handle_line(&self, line: &str) -> Option<Value> {
+        let value: Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(e) => return Some(error_response(Value::Null, PARSE_ERROR, &e.to_string())),
+        };
+
+        let message: RpcMessage = match serde_json::from_value(value) {
+            Ok(message) => message,
+            Err(e) => {
+                return Some(error_response(Value::Null, INVALID_REQUEST, &e.to_string()));
+            }
+        };
+
+        // Notifications (no ID) never get a response.
+        let Some(id) = message.id else {
+            debug!(method = %message.method, "MCP notification");
+            return None;
+        };
+
+        debug!(method = %message.method, "MCP request");
+        let result = match message.method.as_str() {
+            "initialize" => Ok(self.initialize(&message.params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+            "tools/call" => self.call(&message.params).await,
+            method => Err((METHOD_NOT_FOUND, format!("Method not found: {}", method))),
+        };
+
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, &message),
+        })
+    }
+
+    fn initialize(&self, params: &Value) -> Value {
+        let requested = params.get("protocolVersion").and_then(Value::as_str);
+        let version = requested
+            .filter(|v| PROTOCOL_VERSIONS.contains(v))
+            .unwrap_or(PROTOCOL_VERSIONS[0]);
+
+        json!({
+            "protocolVersion": version,
+            "capabilities": { "tools": { "listChanged": false } },
+            "serverInfo": { "name": "exactobar", "version": env!("CARGO_PKG_VERSION") },
+            "instructions": INSTRUCTIONS,
+        })
+    }
+
+    async fn call(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or((INVALID_PARAMS, "Missing tool name".to_string()))?;
+        let args = params.get("arguments").cloned().unwrap_or(json!({}));
+
+        let output = match name {
+            "get_usage" => self.get_usage(&args).await,
+            "list_providers" => self.list_providers(),
+            "get_cost" => self.get_cost(&args).await,
+            "time_until_reset" => self.time_until_reset(&args).await,
+            other => return Err((INVALID_PARAMS, format!("Unknown tool: {}", other))),
+        };
+
+        // Tool failures are results the agent can read, not protocol errors.
+        Ok(match output {
+            Ok(text) => json!({ "content": [{ "type": "text", "text": text }], "isError": false }),
+            Err(e) => json!({ "content": [{ "type": "text", "text": e }], "isError": true }),
+        })
+    }
+
+    // ========================================================================
+    // Tools
+    // ========================================================================
+
+    async fn get_usage(&self, args: &Value) -> Result<String, String> {
+        let providers = self.providers_arg(args)?;
+        let refresh = args
+            .get("refresh")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        let results = self.usage(&providers, refresh).await;
+
+        let formatter = JsonFormatter::new(true);
+        let outputs: Vec<ProviderOutput> = results
+            .iter()
+            .map(|(provider, result)| formatter.provider_output(*provider, result, None))
+            .collect();
+        formatter.format(&outputs).map_err(|e| e.to_string())
+    }
+
+    fn list_providers(&self) -> Result<String, String> {
+        JsonFormatter::new(true)
+            .format_providers(ProviderRegistry::all())
+            .map_err(|e| e.to_string())
+    }
+
+    async fn get_cost(&self, args: &Value) -> Result<String, String> {
+        let provider = args
+            .get("provider")
+            .and_then(Value::as_str)
+            .unwrap_or("all");
+        let days = args.get("days").and_then(Value::as_u64).unwrap_or(30);
+        if !(1..=365).contains(&days) {
+            return Err("days must be between 1 and 365".to_string());
+        }
+
+        let providers = parse_cost_providers(provider).map_err(|e| e.to_string())?;
+        // Log scanning reads whole directories; keep it off the runtime threads
+        let results = tokio::task::spawn_blocking(move || scan_costs(&providers, days as u32))
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| e.to_string())?;
+        if results.is_empty() {
+            return Ok("No local token cost logs found.".to_string());
+        }
+        JsonFormatter::new(true)
+            .format_cost_results(&results)
+            .map_err(|e| e.to_string())
+    }
+
+    async fn time_until_reset(&self, args: &Value) -> Result<String, String> {
+        let name = args
+            .get("provider")
+            .and_then(Value::as_str)
+            .ok_or("provider is required")?;
+        let provider = ProviderRegistry::get_by_cli_name(name)
+            .map(|desc| desc.id)
+            .ok_or_else(|| format!("Unknown provider: {}", name))?;
+        let window = args.get("window").and_then(Value::as_str);
+
+        let (_, result) = self
+            .usage(&[provider], false)
+            .await
+            .into_iter()
+            .next()
+            .ok_or("no result")?;
+        let snapshot = result.map_err(|e| e.message)?;
+
+        let windows: Vec<&UsageWindow> = match window {
+            Some(window) => snapshot
+                .windows
+                .iter()
+                .filter(|w| window_matches(w, window))
+                .collect(),
+            None => snapshot.windows.iter().collect(),
+        };
+        if windows.is_empty() {
+            let known: Vec<&str> = snapshot.windows.iter().map(|w| w.id.as_str()).collect();
+            return Err(format!(
+                "No window '{}' for {}; available: {}",
+                window.unwrap_or_default(),
+                name,
+                known.join(", ")
+            ));
+        }
+
+        let now = Utc::now();
+        let resets: Vec<Value> = windows
+            .iter()
+            .map(|w| {
+                let seconds = w.resets_at.map(|at| (at - now).num_seconds().max(0));
+                json!({
+                    "provider": name,
+                    "window": w.id,
+                    "kind": w.kind,
+                    "usedPercent": w.used_percent,
+                    "resetsAt": w.resets_at.map(|at| at.to_rfc3339()),
+                    "secondsUntilReset": seconds,
+                    "humanized": seconds.map(humanize),
+                })
+            })
+            .collect();
+        serde_json::to_string_pretty(&resets).map_err(|e| e.to_string())
+    }
+
+    fn providers_arg(&self, args: &Value) -> Result<Vec<ProviderKind>, String> {
+        match args.get("provider").and_then(Value::as_str) {
+            Some(name) => {
+                parse_provider_selection(Some(&name.to_string())).map_err(|e| e.to_string())
+            }
+            None => Ok(self.refresher.providers().to_vec()),
+        }
+    }
+
+    // ========================================================================
+    // Cache
+    // ========================================================================
+
+    /// Returns results for `providers`, fetching those without a fresh snapshot.
+    async fn usage(
+        &self,
+        providers: &[ProviderKind],
+        refresh: bool,
+    ) -> Vec<(ProviderKind, Result<UsageSnapshot, FetchFailure>)> {
+        let mut outdated = Vec::new();
+        for provider in providers {
+            let fresh = !self.store.is_stale(*provider, self.max_age).await
+                && self.store.get_error(*provider).await.is_none();
+            if refresh || !fresh {
+                outdated.push(*provider);
+            }
+        }
+
+        if !outdated.is_empty() {
+            let fetch = if refresh {
+                DaemonFetch::Refresh
+            } else {
+                DaemonFetch::Cached(Some(self.max_age))
+            };
+            let fetched = if let Some(results) =
+                fetch_via_daemon_with(&outdated, self.use_daemon, fetch).await
+            {
+                results.into_iter().collect()
+            } else {
+                let mut results = Vec::with_capacity(outdated.len());
+                for provider in &outdated {
+                    let result = match self.refresher.fetch_provider(*provider).await {
+                        Some(state) => state.result,
+                        None => Err(FetchFailure::provider_missing("Provider not registered")),
+                    };
+                    results.push((*provider, result));
+                }
+                results
+            };
+            for (provider, result) in fetched {
+                match result {
+                    Ok(snapshot) => self.store.set_snapshot(provider, snapshot).await,
+                    Err(e) => self.store.set_error(provider, e).await,
+                }
+            }
+        }
+
+        let mut results = Vec::with_capacity(providers.len());
+        for provider in providers {
+            let result = match self.store.get_error(*provider).await {
+                Some(e) => Err(e),
+                None => self
+                    .store
+                    .get_snapshot(*provider)
+                    .await
+                    .ok_or_else(|| FetchFailure::new(FetchErrorKind::Failed, "No snapshot")),
+            };
+            results.push((*provider, result));
+        }
+        results
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+/// Matches a window by ID, or by kind name (`session`, `weekly`, `monthly`).
+fn window_matches(window: &UsageWindow, name: &str) -> bool {
+    if window.id.eq_ignore_ascii_case(name) {
+        return true;
+    }
+    let kind = match name.to_ascii_lowercase().as_str() {
+        "session" => WindowKind::Session,
+        "weekly" => WindowKind::Weekly,
+        "monthly" => WindowKind::Monthly,
+        _ => return false,
+    };
+    window.kind == kind
+}
+
+/// Formats seconds as `2d 3h`, `3h 12m` or `12m`.
+fn humanize(seconds: i64) -> String {
+    let (days, hours, mins) = (
+        seconds / 86_400,
+        (seconds % 86_400) / 3600,
+        (seconds % 3600) / 60,
+    );
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, mins)
+    } else {
+        format!("{}m", mins)
+    }
+}
+
+/// Returns the `tools/list` definitions.
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "get_usage",
+            "description": "Current rate-limit windows (percent used, reset time) and credits for \
+    LLM providers. Omit provider for the configured defaults.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "provider": {
+                        "type": "string",
+                        "description": "Provider ID (e.g. claude, codex), a comma-separated list, or \"all\"."
+                    },
+                    "refresh": {
+                        "type": "boolean",
+                        "description": "Fetch now instead of using a recent cached snapshot."
+                    }
+                }
+            }
+        },
+        {
+            "name": "list_providers",
+            "description": "Providers ExactoBar can report on, with their IDs.",
+            "inputSchema": { "type": "object", "properties": {} }
+        },
+        {
+            "name": "get_cost",
+            "description": "Token usage and estimated USD cost from local CLI logs.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "provider": {
+                        "type": "string",
+                        "description": "Provider ID, or \"all\" (default)."
+                    },
+                    "days": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": 365,
+                        "description": "Days to include (default 30)."
+                    }
+                }
+            }
+        },
+        {
+            "name": "time_until_reset",
+            "description": "How long until a provider's usage window resets.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "provider": { "type": "string", "description": "Provider ID (e.g. claude)." },
+                    "window": {
+                        "type": "string",
+                        "description": "Window ID or kind (session, weekly, monthly). Omit for all windows."
+                    }
+                },
+                "required": ["provider"]
+            }
+        }
+    ])
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exactobar_fetch::FetchContext;
+    use exactobar_providers::ProviderSet;
+    use std::sync::Arc;
+
+    fn server() -> McpServer {
+        // An empty registry makes every fetch fail without any I/O.
+        let mut ctx = FetchContext::new();
+        Arc::new(ProviderSet::new()).attach(&mut ctx);
+        let refresher = Refresher::new(vec![ProviderKind::Claude], ctx, false);
+        McpServer::new(refresher, Duration::from_secs(60), false)
+    }
+
+    async fn request(server: &McpServer, method: &str, params: Value) -> Value {
+        let line = json!({ "jsonrpc": "2.0", "id": 7, "method": method, "params": params });
+        server.handle_line(&line.to_string()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_initialize() {
+        let server = server();
+        let res = request(
+            &server,
+            "initialize",
+            json!({ "protocolVersion": "2024-11-05" }),
+        )
+        .await;
+        assert_eq!(res["id"], 7);
+        assert_eq!(res["result"]["protocolVersion"], "2024-11-05");
+        assert_eq!(res["result"]["serverInfo"]["name"], "exactobar");
+        assert!(res["result"]["capabilities"]["tools"].is_object());
+
+        let res = request(
+            &server,
+            "initialize",
+            json!({ "protocolVersion": "1999-01-01" }),
+        )
+        .await;
+        assert_eq!(res["result"]["protocolVersion"], PROTOCOL_VERSIONS[0]);
+
+        let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        assert!(
+            server
+                .handle_line(&notification.to_string())
+                .await
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_errors() {
+        let server = server();
+        let res = server.handle_line("{not json").await.unwrap();
+        assert_eq!(res["error"]["code"], PARSE_ERROR);
+        assert!(res["id"].is_null());
+
+        let res = request(&server, "resources/list", json!({})).await;
+        assert_eq!(res["error"]["code"], METHOD_NOT_FOUND);
+
+        let res = request(&server, "tools/call", json!({ "name": "nope" })).await;
+        assert_eq!(res["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn test_tools() {
+        let server = server();
+        let res = request(&server, "tools/list", json!({})).await;
+        let names: Vec<&str> = res["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "get_usage",
+                "list_providers",
+                "get_cost",
+                "time_until_reset"
+            ]
+        );
+
+        let res = request(&server, "tools/call", json!({ "name": "list_providers" })).await;
+        assert_eq!(res["result"]["isError"], false);
+        let text = res["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("\"cliName\": \"claude\""));
+
+        // Fetch failures come back as tool errors the agent can read.
+        let res = request(&server, "tools/call", json!({ "name": "get_usage" })).await;
+        let text = res["result"]["content"][0]["text"].as_str().unwrap();
+        let outputs: Value = serde_json::from_str(text).unwrap();
+        assert_eq!(outputs[0]["provider"], "claude");
+        assert_eq!(outputs[0]["error"], "Provider not registered");
+
+        let res = request(
+            &server,
+            "tools/call",
+            json!({ "name": "time_until_reset", "arguments": {} }),
+        )
+        .await;
+        assert_eq!(res["result"]["isError"], true);
+
+        let res = request(
+            &server,
+            "tools/call",
+            json!({ "name": "get_cost", "arguments": { "days": 0 } }),
+        )
+        .await;
+        assert_eq!(res["result"]["isError"], true);
+    }
+
+    #[tokio::test]
+    async fn test_cached_snapshots() {
+        let server = server();
+        let mut snapshot = UsageSnapshot::new();
+        let mut session = UsageWindow::named("session", WindowKind::Session, 80.0);
+        session.resets_at = Some(Utc::now() + chrono::Duration::seconds(5400));
+        snapshot.windows.push(session);
+        snapshot
+            .windows
+            .push(UsageWindow::named("weekly", WindowKind::Weekly, 30.0));
+        server
+            .store
+            .set_snapshot(ProviderKind::Claude, snapshot)
+            .await;
+
+        let res = request(
+            &server,
+            "tools/call",
+            json!({ "name": "time_until_reset", "arguments": { "provider": "claude", "window": "session" } }),
+        )
+        .await;
+        let text = res["result"]["content"][0]["text"].as_str().unwrap();
+        let resets: Value = serde_json::from_str(text).unwrap();
+        assert_eq!(resets.as_array().unwrap().len(), 1);
+        assert_eq!(resets[0]["window"], "session");
+        assert_eq!(resets[0]["usedPercent"], 80.0);
+        assert!(resets[0]["secondsUntilReset"].as_i64().unwrap() > 5300);
+        assert_eq!(resets[0]["humanized"], "1h 29m");
+
+        let res = request(
+            &server,
+            "tools/call",
+            json!({ "name": "time_until_reset", "arguments": { "provider": "claude", "window": "daily" } }),
+        )
+        .await;
+        assert_eq!(res["result"]["isError"], true);
+    }
+
+    #[test]
+    fn test_humanize() {
+        assert_eq!(humanize(59), "0m");
+        assert_eq!(humanize(3 * 3600 + 12 * 60), "3h 12m");
+        assert_eq!(humanize(2 * 86_400 + 3 * 3600), "2d 3h");
+    }
+}
@@ -0,0 +1,336 @@
+//! Prometheus text exposition of cached provider state.
+//!
+//! Every series is a gauge. Windows are labelled by provider, window ID and
+//! window kind; fetch series by provider and strategy ID. Values follow
+//! Prometheus base units (seconds, ratios as 0-100 percent where the name
+//! says so).
+
+use chrono::{DateTime, Utc};
+use exactobar_core::WindowKind;
+
+use super::cache::ProviderState;
+
+/// Content type for the Prometheus text format.
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Content type for the OpenMetrics text format.
+pub const OPENMETRICS_CONTENT_TYPE: &str =
+    "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Returns true if the `Accept` header asks for OpenMetrics.
+pub fn wants_openmetrics(accept: Option<&str>) -> bool {
+    accept.is_some_and(|accept| accept.contains("application/openmetrics-text"))
+}
+
+// ============================================================================
+// Families
+// ============================================================================
+
+/// One metric family and its samples.
+struct Family {
+    name: &'static str,
+    help: &'static str,
+    samples: Vec<(Vec<(&'static str, String)>, f64)>,
+}
+
+impl Family {
+    fn new(name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            samples: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, labels: Vec<(&'static str, String)>, value: f64) {
+        self.samples.push((labels, value));
+    }
+
+    fn render(&self, out: &mut String) {
+        if self.samples.is_empty() {
+            return;
+        }
+        out.push_str(&format!("# HELP {} {}\n", self.name, self.help));
+        out.push_str(&format!("# TYPE {} gauge\n", self.name));
+        for (labels, value) in &self.samples {
+            out.push_str(self.name);
+            if !labels.is_empty() {
+                let labels: Vec<String> = labels
+                    .iter()
+                    .map(|(k, v)| format!("{}=\"{}\"", k, escape_label(v)))
+                    .collect();
+                out.push_str(&format!("{{{}}}", labels.join(",")));
+            }
+            out.push_str(&format!(" {}\n", format_value(*value)));
+        }
+    }
+}
+
+/// Escapes a label value.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Formats a sample value.
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Returns the label value for a window kind.
+fn kind_label(kind: WindowKind) -> &'static str {
+    match kind {
+        WindowKind::Session => "session",
+        WindowKind::Weekly => "weekly",
+        WindowKind::Monthly => "monthly",
+        WindowKind::Model => "model",
+        WindowKind::Other => "other",
+    }
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+/// Renders provider states as Prometheus (or OpenMetrics) text.
+pub fn render(states: &[ProviderState], now: DateTime<Utc>, openmetrics: bool) -> String {
+    let mut used_percent = Family::new(
+        "exactobar_window_used_percent",
+        "Percent of the window quota used (0-100).",
+    );
+    let mut reset_seconds = Family::new(
+        "exactobar_window_reset_seconds",
+        "Seconds until the window resets.",
+    );
+    let mut used = Family::new(
+        "exactobar_window_used",
+        "Amount used in the window, in the window's unit.",
+    );
+    let mut limit = Family::new(
+        "exactobar_window_limit",
+        "Window limit, in the window's unit.",
+    );
+    let mut credits = Family::new(
+        "exactobar_credits_remaining",
+        "Remaining prepaid credits or balance.",
+    );
+    let mut fetch_duration = Family::new(
+        "exactobar_fetch_duration_seconds",
+        "Duration of the last attempt of each fetch strategy.",
+    );
+    let mut fetch_success = Family::new(
+        "exactobar_fetch_success",
+        "Whether the last attempt of each fetch strategy succeeded (1) or failed (0).",
+    );
+    let mut up = Family::new(
+        "exactobar_provider_up",
+        "Whether the last refresh of the provider produced a snapshot.",
+    );
+    let mut last_refresh = Family::new(
+        "exactobar_last_refresh_timestamp_seconds",
+        "Unix time of the provider's last refresh.",
+    );
+    let mut severity = Family::new(
+        "exactobar_status_severity",
+        "Status page indicator: 0 none, 1 minor, 2 major, 3 critical, 4 maintenance, 5 unknown.",
+    );
+
+    for state in states {
+        let provider = state.provider.cli_name().to_string();
+
+        up.push(
+            vec![("provider", provider.clone())],
+            f64::from(u8::from(state.result.is_ok())),
+        );
+        last_refresh.push(
+            vec![("provider", provider.clone())],
+            state.fetched_at.timestamp() as f64,
+        );
+
+        for attempt in &state.attempts {
+            let labels = vec![
+                ("provider", provider.clone()),
+                ("strategy", attempt.strategy_id.clone()),
+            ];
+            fetch_duration.push(labels.clone(), attempt.duration.as_secs_f64());
+            fetch_success.push(labels, f64::from(u8::from(attempt.success)));
+        }
+
+        if let Some(status) = &state.status {
+            severity.push(
+                vec![("provider", provider.clone())],
+                f64::from(status.indicator.severity()),
+            );
+        }
+
+        let Ok(snapshot) = &state.result else {
+            continue;
+        };
+
+        for window in &snapshot.windows {
+            let labels = vec![
+                ("provider", provider.clone()),
+                ("window", window.id.clone()),
+                ("kind", kind_label(window.kind).to_string()),
+            ];
+            used_percent.push(labels.clone(), window.used_percent);
+            if let Some(resets_at) = window.resets_at {
+                let secs = (resets_at - now).num_seconds().max(0);
+                reset_seconds.push(labels.clone(), secs as f64);
+            }
+            if let Some(value) = window.used {
+                used.push(labels.clone(), value);
+            }
+            if let Some(value) = window.limit {
+                limit.push(labels, value);
+            }
+        }
+
+        if let Some(c) = &snapshot.credits {
+            credits.push(
+                vec![("provider", provider.clone()), ("unit", c.unit.clone())],
+                c.remaining,
+            );
+        }
+    }
+
+    let mut out = String::new();
+    for family in [
+        &used_percent,
+        &reset_seconds,
+        &used,
+        &limit,
+        &credits,
+        &fetch_duration,
+        &fetch_success,
+        &up,
+        &last_refresh,
+        &severity,
+    ] {
+        family.render(&mut out);
+    }
+    if openmetrics {
+        out.push_str("# EOF\n");
+    }
+    out
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exactobar_core::{
+        Credits, ProviderKind, ProviderStatus, StatusIndicator, UsageSnapshot, UsageWindow,
+    };
+    use exactobar_fetch::{FetchAttempt, FetchErrorKind, FetchFailure, FetchKind};
+    use std::time::Duration;
+
+    fn sample(now: DateTime<Utc>) -> Vec<ProviderState> {
+        let mut snapshot = UsageSnapshot::new();
+        let mut session = UsageWindow::named("session", WindowKind::Session, 42.5);
+        session.resets_at = Some(now + chrono::Duration::seconds(3600));
+        snapshot.windows.push(session);
+        snapshot.windows.push(
+            UsageWindow::named("weekly", WindowKind::Weekly, 10.0).with_counts(
+                100.0,
+                Some(1000.0),
+                "requests",
+            ),
+        );
+        snapshot.credits = Some(Credits::new(12.5));
+
+        let mut status = ProviderStatus::operational();
+        status.indicator = StatusIndicator::Major;
+
+        vec![
+            ProviderState {
+                provider: ProviderKind::Claude,
+                result: Ok(snapshot),
+                attempts: vec![
+                    FetchAttempt::failure(
+                        "claude.oauth",
+                        FetchKind::OAuth,
+                        "expired",
+                        Duration::from_millis(250),
+                    ),
+                    FetchAttempt::success("claude.cli", FetchKind::CLI, Duration::from_secs(2)),
+                ],
+                duration: Duration::from_millis(2250),
+                fetched_at: now,
+                status: Some(status),
+            },
+            ProviderState {
+                provider: ProviderKind::Codex,
+                result: Err(FetchFailure::new(FetchErrorKind::Auth, "no credentials")),
+                attempts: Vec::new(),
+                duration: Duration::ZERO,
+                fetched_at: now,
+                status: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render() {
+        let now = Utc::now();
+        let text = render(&sample(now), now, false);
+
+        assert!(text.contains("# TYPE exactobar_window_used_percent gauge\n"));
+        assert!(text.contains(
+            "exactobar_window_used_percent{provider=\"claude\",window=\"session\",kind=\"session\"} 42.5\n"
+        ));
+        assert!(text.contains(
+            "exactobar_window_reset_seconds{provider=\"claude\",window=\"session\",kind=\"session\"} 3600\n"
+        ));
+        assert!(text.contains(
+            "exactobar_window_limit{provider=\"claude\",window=\"weekly\",kind=\"weekly\"} 1000\n"
+        ));
+        assert!(
+            text.contains(
+                "exactobar_credits_remaining{provider=\"claude\",unit=\"credits\"} 12.5\n"
+            )
+        );
+        assert!(text.contains(
+            "exactobar_fetch_success{provider=\"claude\",strategy=\"claude.oauth\"} 0\n"
+        ));
+        assert!(text.contains(
+            "exactobar_fetch_duration_seconds{provider=\"claude\",strategy=\"claude.cli\"} 2\n"
+        ));
+        assert!(text.contains("exactobar_provider_up{provider=\"codex\"} 0\n"));
+        assert!(text.contains("exactobar_status_severity{provider=\"claude\"} 2\n"));
+        assert!(!text.contains("provider=\"codex\",window"));
+        assert!(!text.contains("# EOF"));
+
+        // Each family is declared once.
+        assert_eq!(text.matches("# TYPE exactobar_provider_up ").count(), 1);
+    }
+
+    #[test]
+    fn test_render_openmetrics() {
+        let now = Utc::now();
+        assert!(render(&sample(now), now, true).ends_with("# EOF\n"));
+        assert_eq!(render(&[], now, true), "# EOF\n");
+        assert!(wants_openmetrics(Some(
+            "application/openmetrics-text;version=1.0.0,text/plain;q=0.5"
+        )));
+        assert!(!wants_openmetrics(Some("text/plain")));
+        assert!(!wants_openmetrics(None));
+    }
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(escape_label("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+        assert_eq!(format_value(f64::NAN), "NaN");
+    }
+}
@@ -0,0 +1,337 @@
+//! Local HTTP API for `exactobar serve`, plus the `exactobar daemon` socket
+//! and the `exactobar mcp` stdio server.
+//!
+//! Routes:
+//!
+//! - `GET /metrics` - Prometheus text (OpenMetrics if the scraper asks)
+//! - `GET /v1/snapshots` - every provider, same shape as `--format json`
+//! - `GET /v1/snapshots/<provider>` - one provider
+//! - `GET /calendar.ics` - upcoming resets as iCalendar; also accepts the
+//!   token as `?token=`, since calendar apps can't send headers
+//! - `GET /healthz` - liveness, never authenticated
+
+pub mod cache;
+pub mod calendar;
+#[cfg(unix)]
+pub mod daemon;
+pub mod http;
+pub mod mcp;
+pub mod metrics;
+
+use chrono::Utc;
+use exactobar_core::ExchangeRates;
+use exactobar_providers::ProviderRegistry;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, warn};
+
+pub use cache::{ProviderState, Refresher, SnapshotCache};
+use http::{Request, Response};
+
+use crate::output::{JsonFormatter, ProviderOutput};
+
+// ============================================================================
+// Server
+// ============================================================================
+
+/// Serves cached provider state over HTTP.
+pub struct Server {
+    cache: Arc<SnapshotCache>,
+    token: Option<String>,
+    pretty: bool,
+    display_currency: Option<String>,
+    exchange_rates: ExchangeRates,
+}
+
+impl Server {
+    /// Creates a server reading from `cache`.
+    pub fn new(cache: Arc<SnapshotCache>) -> Self {
+        Self {
+            cache,
+            token: None,
+            pretty: false,
+            display_currency: None,
+            exchange_rates: ExchangeRates::default(),
+        }
+    }
+
+    /// Requires `Authorization: Bearer <token>` on every route but `/healthz`.
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// Pretty-prints JSON responses.
+    pub fn with_pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Adds display-currency equivalents to JSON money amounts.
+    pub fn with_display_currency(mut self, currency: Option<String>, rates: ExchangeRates) -> Self {
+        self.display_currency = currency;
+        self.exchange_rates = rates;
+        self
+    }
+
+    /// Accepts connections until the task is cancelled.
+    pub async fn serve(self, listener: TcpListener) -> std::io::Result<()> {
+        let server = Arc::new(self);
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let server = Arc::clone(&server);
+            tokio::spawn(async move { server.handle_connection(stream, peer).await });
+        }
+    }
+
+    async fn handle_connection(&self, mut stream: TcpStream, peer: SocketAddr) {
+        let request =
+            match tokio::time::timeout(http::READ_TIMEOUT, http::read_request(&mut stream)).await {
+                Ok(Some(request)) => request,
+                Ok(None) => {
+                    let _ = Response::error(400, "bad request")
+                        .write_to(&mut stream)
+                        .await;
+                    return;
+                }
+                Err(_) => return,
+            };
+
+        debug!(%peer, method = %request.method, path = %request.path, "Request");
+        let response = self.respond(&request).await;
+        if let Err(e) = response.write_to(&mut stream).await {
+            debug!(%peer, error = %e, "Failed to write response");
+        }
+    }
+
+    /// Routes a request.
+    pub async fn respond(&self, request: &Request) -> Response {
+        if request.method != "GET" {
+            return Response::error(405, "method not allowed").with_header("Allow", "GET");
+        }
+
+        if request.path == "/healthz" {
+            return Response::text("ok\n");
+        }
+
+        let path = request.path.trim_end_matches('/');
+        if let Some(token) = &self.token {
+            let authorized = request.has_bearer(token)
+                || (path == "/calendar.ics" && request.has_token_param(token));
+            if !authorized {
+                return Response::error(401, "unauthorized")
+                    .with_header("WWW-Authenticate", "Bearer realm=\"exactobar\"");
+            }
+        }
+
+        match path {
+            "/metrics" => self.metrics(request).await,
+            "/calendar.ics" => self.calendar().await,
+            "/v1/snapshots" => self.snapshots().await,
+            path => match path.strip_prefix("/v1/snapshots/") {
+                Some(name) => self.snapshot(name).await,
+                None => Response::error(404, "not found"),
+            },
+        }
+    }
+
+    async fn metrics(&self, request: &Request) -> Response {
+        let openmetrics = metrics::wants_openmetrics(request.header("accept"));
+        let body = metrics::render(&self.cache.states().await, Utc::now(), openmetrics);
+        let content_type = if openmetrics {
+            metrics::OPENMETRICS_CONTENT_TYPE
+        } else {
+            metrics::PROMETHEUS_CONTENT_TYPE
+        };
+        Response::new(200, content_type, body)
+    }
+
+    async fn calendar(&self) -> Response {
+        let states = self.cache.states().await;
+        let snapshots: Vec<_> = states
+            .iter()
+            .filter_map(|state| state.result.as_ref().ok().map(|s| (state.provider, s)))
+            .collect();
+        Response::new(
+            200,
+            calendar::CONTENT_TYPE,
+            calendar::render(&snapshots, Utc::now()),
+        )
+    }
+
+    async fn snapshots(&self) -> Response {
+        if !self.cache.is_ready().await {
+            return Response::error(503, "first refresh still running");
+        }
+        let outputs: Vec<ProviderOutput> = self
+            .cache
+            .states()
+            .await
+            .iter()
+            .map(|state| self.output(state))
+            .collect();
+        self.json(&outputs)
+    }
+
+    async fn snapshot(&self, name: &str) -> Response {
+        let Some(desc) = ProviderRegistry::get_by_cli_name(name) else {
+            return Response::error(404, &format!("unknown provider: {}", name));
+        };
+        match self.cache.get(desc.id).await {
+            Some(state) => self.json(&self.output(&state)),
+            None => Response::error(404, &format!("{} is not being served", name)),
+        }
+    }
+
+    fn output(&self, state: &ProviderState) -> ProviderOutput {
+        self.formatter()
+            .provider_output(state.provider, &state.result, state.status.as_ref())
+    }
+
+    fn formatter(&self) -> JsonFormatter {
+        JsonFormatter::new(self.pretty)
+            .with_display_currency(self.display_currency.clone(), self.exchange_rates.clone())
+    }
+
+    fn json<T: serde::Serialize>(&self, data: &T) -> Response {
+        match self.formatter().format(data) {
+            Ok(body) => Response::json(body),
+            Err(e) => {
+                warn!(error = %e, "Failed to serialize response");
+                Response::error(500, "serialization failed")
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exactobar_core::{ProviderKind, UsageSnapshot, UsageWindow, WindowKind};
+    use std::time::Duration;
+
+    async fn server(token: Option<&str>) -> Server {
+        let cache = Arc::new(SnapshotCache::new());
+        let mut snapshot = UsageSnapshot::new();
+        snapshot
+            .windows
+            .push(UsageWindow::named("session", WindowKind::Session, 25.0));
+        cache
+            .put(ProviderState {
+                provider: ProviderKind::Claude,
+                result: Ok(snapshot),
+                attempts: Vec::new(),
+                duration: Duration::ZERO,
+                fetched_at: Utc::now(),
+                status: None,
+            })
+            .await;
+        Server::new(cache).with_token(token.map(str::to_string))
+    }
+
+    fn get(path: &str, auth: Option<&str>) -> Request {
+        let mut head = format!("GET {} HTTP/1.1\r\nHost: localhost\r\n", path);
+        if let Some(token) = auth {
+            head.push_str(&format!("Authorization: Bearer {}\r\n", token));
+        }
+        Request::parse(&head).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_routes() {
+        let server = server(None).await;
+
+        let res = server.respond(&get("/metrics", None)).await;
+        assert_eq!(res.status, 200);
+        assert!(res.body.contains("exactobar_window_used_percent"));
+
+        let res = server.respond(&get("/v1/snapshots", None)).await;
+        assert_eq!(res.status, 200);
+        let body: serde_json::Value = serde_json::from_str(&res.body).unwrap();
+        assert_eq!(body[0]["provider"], "claude");
+        assert_eq!(body[0]["usage"]["windows"][0]["usedPercent"], 25.0);
+
+        let res = server.respond(&get("/v1/snapshots/claude", None)).await;
+        assert_eq!(res.status, 200);
+        assert!(res.body.starts_with('{'));
+
+        assert_eq!(
+            server
+                .respond(&get("/v1/snapshots/codex", None))
+                .await
+                .status,
+            404
+        );
+        assert_eq!(
+            server
+                .respond(&get("/v1/snapshots/nope", None))
+                .await
+                .status,
+            404
+        );
+        assert_eq!(server.respond(&get("/nope", None)).await.status, 404);
+
+        let res = server.respond(&get("/calendar.ics", None)).await;
+        assert_eq!(res.status, 200);
+        assert!(res.body.starts_with("BEGIN:VCALENDAR"));
+
+        let post = Request::parse("POST /metrics HTTP/1.1\r\n").unwrap();
+        assert_eq!(server.respond(&post).await.status, 405);
+    }
+
+    #[tokio::test]
+    async fn test_bearer_auth() {
+        let server = server(Some("s3cret")).await;
+
+        assert_eq!(server.respond(&get("/metrics", None)).await.status, 401);
+        assert_eq!(
+            server.respond(&get("/metrics", Some("wrong"))).await.status,
+            401
+        );
+        assert_eq!(
+            server
+                .respond(&get("/metrics", Some("s3cret")))
+                .await
+                .status,
+            200
+        );
+        assert_eq!(server.respond(&get("/healthz", None)).await.status, 200);
+
+        // Only the calendar takes the token as a query parameter.
+        assert_eq!(
+            server
+                .respond(&get("/calendar.ics?token=s3cret", None))
+                .await
+                .status,
+            200
+        );
+        assert_eq!(
+            server
+                .respond(&get("/calendar.ics?token=wrong", None))
+                .await
+                .status,
+            401
+        );
+        assert_eq!(
+            server
+                .respond(&get("/metrics?token=s3cret", None))
+                .await
+                .status,
+            401
+        );
+    }
+
+    #[tokio::test]
+    async fn test_not_ready() {
+        let server = Server::new(Arc::new(SnapshotCache::new()));
+        assert_eq!(
+            server.respond(&get("/v1/snapshots", None)).await.status,
+            503
+        );
+    }
+}
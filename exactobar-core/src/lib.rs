@@ -33,6 +33,11 @@
 //! - [`Quota`] - Quota information
 //! - [`Credits`] - Credit-based usage tracking
 //!
+//! ### Money
+//! - [`Amount`] - Value with a currency or unit code
+//! - [`SpendLimit`] - Spend against a cap, with overage flags
+//! - [`ExchangeRates`] - User-supplied conversion table
+//!
 //! ### Cost Tracking
 //! - [`CostUsageSnapshot`] - Token cost tracking from local logs
 //! - [`DailyUsageEntry`] - Daily usage entry
@@ -52,17 +57,23 @@ pub use error::CoreError;
 
 // Re-export all model types
 pub use models::{
+    // Money
+    Amount,
+    CREDITS_UNIT,
     // Cost tracking
     CostUsageSnapshot,
     // Usage types
     Credits,
+    CustomProviderId,
     DailyUsageEntry,
+    ExchangeRates,
     // Status & Fetch
     FetchSource,
     // Provider types
     IconStyle,
     LoginMethod,
     ModelBreakdown,
+    ProjectBreakdown,
     Provider,
     ProviderBranding,
     ProviderColor,
@@ -71,10 +82,16 @@ pub use models::{
     ProviderMetadata,
     ProviderStatus,
     Quota,
+    SpendLimit,
     StatusIndicator,
+    USD,
     UsageData,
+    UsageLevel,
     UsageSnapshot,
     UsageWindow,
+    WindowKind,
+    WindowSlot,
+    is_currency_code,
 };
 
 // Re-export traits
@@ -4,6 +4,7 @@
 //! - [`CostUsageSnapshot`] - Container for cost data
 //! - [`DailyUsageEntry`] - Per-day usage breakdown
 //! - [`ModelBreakdown`] - Per-model cost breakdown
+//! - [`ProjectBreakdown`] - Per-project (or workspace) cost breakdown
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -120,6 +121,12 @@ pub struct DailyUsageEntry {
     /// Per-model cost breakdown.
     #[serde(default)]
     pub model_breakdowns: Option<Vec<ModelBreakdown>>,
+    /// Per-project (or workspace) cost breakdown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_breakdowns: Option<Vec<ProjectBreakdown>>,
+    /// Per-API-key token breakdown, keyed by API key ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_breakdowns: Option<Vec<ProjectBreakdown>>,
 }
 
 impl DailyUsageEntry {
@@ -135,6 +142,8 @@ impl DailyUsageEntry {
             cost_usd: None,
             models_used: None,
             model_breakdowns: None,
+            project_breakdowns: None,
+            api_key_breakdowns: None,
         }
     }
 
@@ -194,6 +203,51 @@ impl ModelBreakdown {
     }
 }
 
+// ============================================================================
+// Project Breakdown
+// ============================================================================
+
+/// Per-project cost breakdown.
+///
+/// Used for `OpenAI` projects, Anthropic workspaces and API keys.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectBreakdown {
+    /// Project, workspace or API key ID.
+    pub project_id: String,
+    /// Display name, if known.
+    #[serde(default)]
+    pub project_name: Option<String>,
+    /// Cost in USD for this project.
+    pub cost_usd: Option<f64>,
+    /// Input tokens for this project.
+    pub input_tokens: Option<u64>,
+    /// Output tokens for this project.
+    pub output_tokens: Option<u64>,
+}
+
+impl ProjectBreakdown {
+    /// Creates a new breakdown for the given project.
+    pub fn new(project_id: impl Into<String>) -> Self {
+        Self {
+            project_id: project_id.into(),
+            project_name: None,
+            cost_usd: None,
+            input_tokens: None,
+            output_tokens: None,
+        }
+    }
+
+    /// Returns the name if known, otherwise the ID.
+    pub fn display_name(&self) -> &str {
+        self.project_name.as_deref().unwrap_or(&self.project_id)
+    }
+
+    /// Returns total tokens for this project.
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens.unwrap_or(0) + self.output_tokens.unwrap_or(0)
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -8,21 +8,26 @@
 //!
 //! - [`provider`] - Provider types (`ProviderKind`, Identity, Metadata, Branding)
 //! - [`usage`] - Usage types (`UsageSnapshot`, `UsageWindow`, Credits, Quota)
-//! - [`cost`] - Cost tracking (`CostUsageSnapshot`, `DailyUsageEntry`)
+//! - [`money`] - Unit-aware amounts (`Amount`, `SpendLimit`, `ExchangeRates`)
+//! - [`cost`] - Cost tracking (`CostUsageSnapshot`, `DailyUsageEntry`, breakdowns)
 //! - [`status`] - Status and fetch types (`ProviderStatus`, `FetchSource`)
 
 mod cost;
+mod money;
 mod provider;
 mod status;
 mod usage;
 
 // Re-export everything at the models level
-pub use cost::{CostUsageSnapshot, DailyUsageEntry, ModelBreakdown};
+pub use cost::{CostUsageSnapshot, DailyUsageEntry, ModelBreakdown, ProjectBreakdown};
+pub use money::{Amount, CREDITS_UNIT, ExchangeRates, SpendLimit, USD, is_currency_code};
 pub use provider::{
-    IconStyle, LoginMethod, Provider, ProviderBranding, ProviderColor, ProviderIdentity,
-    ProviderKind, ProviderMetadata,
+    CustomProviderId, IconStyle, LoginMethod, Provider, ProviderBranding, ProviderColor,
+    ProviderIdentity, ProviderKind, ProviderMetadata,
 };
 pub use status::{FetchSource, ProviderStatus, StatusIndicator};
-pub use usage::{Credits, Quota, UsageData, UsageSnapshot, UsageWindow};
+pub use usage::{
+    Credits, Quota, UsageData, UsageLevel, UsageSnapshot, UsageWindow, WindowKind, WindowSlot,
+};
 #[cfg(test)]
 mod serde_tests;
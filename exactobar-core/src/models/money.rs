@@ -0,0 +1,324 @@
+//! Money and amount types.
+//!
+//! This module contains unit-aware amounts used for credits and spend:
+//! - [`Amount`] - A value tagged with a currency or unit code
+//! - [`SpendLimit`] - Spend against an optional cap, with overage flags
+//! - [`ExchangeRates`] - User-supplied rate table for display conversion
+
+use std::collections::HashMap;
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Unit code for provider-specific credits that are not money.
+pub const CREDITS_UNIT: &str = "credits";
+
+/// Currency code for US dollars.
+pub const USD: &str = "USD";
+
+// ============================================================================
+// Amount
+// ============================================================================
+
+/// A value tagged with a currency or unit code.
+///
+/// Currencies use ISO 4217 codes (`"USD"`, `"CNY"`, `"EUR"`). Anything
+/// else (`"credits"`, `"requests"`) is treated as an opaque unit that
+/// cannot be converted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Amount {
+    /// The numeric value.
+    pub value: f64,
+    /// Currency (ISO 4217) or unit code.
+    pub unit: String,
+}
+
+impl Amount {
+    /// Creates an amount with the given unit.
+    pub fn new(value: f64, unit: impl Into<String>) -> Self {
+        Self {
+            value,
+            unit: unit.into(),
+        }
+    }
+
+    /// Creates a US dollar amount.
+    pub fn usd(value: f64) -> Self {
+        Self::new(value, USD)
+    }
+
+    /// Creates a provider-credits amount.
+    pub fn credits(value: f64) -> Self {
+        Self::new(value, CREDITS_UNIT)
+    }
+
+    /// Returns true if the unit is an ISO 4217 currency code.
+    pub fn is_currency(&self) -> bool {
+        is_currency_code(&self.unit)
+    }
+
+    /// Returns true if both amounts share the same unit.
+    pub fn same_unit(&self, other: &Amount) -> bool {
+        self.unit.eq_ignore_ascii_case(&other.unit)
+    }
+
+    /// Subtracts another amount with the same unit.
+    pub fn checked_sub(&self, other: &Amount) -> Option<Amount> {
+        self.same_unit(other)
+            .then(|| Self::new(self.value - other.value, self.unit.clone()))
+    }
+
+    /// Returns true if the value is finite.
+    pub fn is_valid(&self) -> bool {
+        self.value.is_finite()
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.value < 0.0 { "-" } else { "" };
+        let value = self.value.abs();
+
+        if !self.is_currency() {
+            return if value.fract() == 0.0 {
+                write!(f, "{sign}{value:.0} {}", self.unit)
+            } else {
+                write!(f, "{sign}{value:.2} {}", self.unit)
+            };
+        }
+
+        let code = self.unit.to_ascii_uppercase();
+        let decimals = currency_decimals(&code);
+        match currency_symbol(&code) {
+            Some(symbol) => write!(f, "{sign}{symbol}{value:.decimals$}"),
+            None => write!(f, "{sign}{value:.decimals$} {code}"),
+        }
+    }
+}
+
+/// Three-letter units that are rate limits, not currencies.
+const NON_CURRENCY_UNITS: &[&str] = &["TPM", "RPM", "TPD", "RPD"];
+
+/// Returns true if the code looks like an ISO 4217 currency code.
+pub fn is_currency_code(code: &str) -> bool {
+    code.len() == 3
+        && code.bytes().all(|b| b.is_ascii_alphabetic())
+        && !NON_CURRENCY_UNITS
+            .iter()
+            .any(|unit| unit.eq_ignore_ascii_case(code))
+}
+
+/// Returns the display symbol for common currencies.
+fn currency_symbol(code: &str) -> Option<&'static str> {
+    match code {
+        "USD" => Some("$"),
+        "EUR" => Some("€"),
+        "GBP" => Some("£"),
+        "JPY" => Some("¥"),
+        "CNY" => Some("CN¥"),
+        "INR" => Some("₹"),
+        "KRW" => Some("₩"),
+        _ => None,
+    }
+}
+
+/// Returns the number of minor-unit digits to display.
+fn currency_decimals(code: &str) -> usize {
+    match code {
+        "JPY" | "KRW" => 0,
+        _ => 2,
+    }
+}
+
+// ============================================================================
+// Spend Limit
+// ============================================================================
+
+/// Spend against an optional cap (e.g., pay-as-you-go or extra usage).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpendLimit {
+    /// Amount spent in the current period.
+    pub used: Amount,
+    /// Spend cap, if one is configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cap: Option<Amount>,
+    /// When the spend counter resets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resets_at: Option<DateTime<Utc>>,
+    /// Whether usage beyond the included plan is billed.
+    #[serde(default)]
+    pub overage_enabled: bool,
+}
+
+impl SpendLimit {
+    /// Creates a spend limit with no cap.
+    pub fn new(used: Amount) -> Self {
+        Self {
+            used,
+            cap: None,
+            resets_at: None,
+            overage_enabled: false,
+        }
+    }
+
+    /// Sets the spend cap.
+    #[must_use]
+    pub fn with_cap(mut self, cap: Amount) -> Self {
+        self.cap = Some(cap);
+        self
+    }
+
+    /// Returns the amount left before the cap.
+    pub fn remaining(&self) -> Option<Amount> {
+        self.cap.as_ref()?.checked_sub(&self.used)
+    }
+
+    /// Returns spend as a percentage of the cap.
+    pub fn used_percent(&self) -> Option<f64> {
+        let cap = self.cap.as_ref().filter(|c| c.same_unit(&self.used))?;
+        (cap.value > 0.0).then(|| (self.used.value / cap.value) * 100.0)
+    }
+
+    /// Returns true if spend has reached the cap.
+    pub fn is_over_cap(&self) -> bool {
+        self.cap
+            .as_ref()
+            .is_some_and(|cap| cap.same_unit(&self.used) && self.used.value >= cap.value)
+    }
+}
+
+// ============================================================================
+// Exchange Rates
+// ============================================================================
+
+/// User-supplied exchange rate table.
+///
+/// Each rate is the number of units of a currency per one unit of
+/// `base`. For example, with base `"USD"`, `{"CNY": 7.2, "EUR": 0.92}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExchangeRates {
+    /// Base currency code.
+    pub base: String,
+    /// Units of each currency per one unit of `base`.
+    #[serde(default)]
+    pub rates: HashMap<String, f64>,
+}
+
+impl ExchangeRates {
+    /// Creates an empty table with the given base currency.
+    pub fn new(base: impl Into<String>) -> Self {
+        Self {
+            base: base.into(),
+            rates: HashMap::new(),
+        }
+    }
+
+    /// Adds a rate.
+    #[must_use]
+    pub fn with_rate(mut self, code: impl Into<String>, rate: f64) -> Self {
+        self.rates.insert(code.into(), rate);
+        self
+    }
+
+    /// Returns units of `code` per one unit of the base currency.
+    pub fn rate(&self, code: &str) -> Option<f64> {
+        if code.eq_ignore_ascii_case(&self.base) {
+            return Some(1.0);
+        }
+        self.rates
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(code))
+            .map(|(_, rate)| *rate)
+            .filter(|r| r.is_finite() && *r > 0.0)
+    }
+
+    /// Converts an amount to another currency.
+    ///
+    /// Returns `None` if either side is not a currency or has no rate.
+    /// Amounts already in the target currency are returned unchanged.
+    pub fn convert(&self, amount: &Amount, to: &str) -> Option<Amount> {
+        if amount.unit.eq_ignore_ascii_case(to) {
+            return Some(amount.clone());
+        }
+        if !amount.is_currency() || !is_currency_code(to) {
+            return None;
+        }
+        let from_rate = self.rate(&amount.unit)?;
+        let to_rate = self.rate(to)?;
+        Some(Amount::new(
+            amount.value / from_rate * to_rate,
+            to.to_ascii_uppercase(),
+        ))
+    }
+}
+
+impl Default for ExchangeRates {
+    fn default() -> Self {
+        Self::new(USD)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amount_display() {
+        assert_eq!(Amount::usd(12.5).to_string(), "$12.50");
+        assert_eq!(Amount::new(88.0, "CNY").to_string(), "CN¥88.00");
+        assert_eq!(Amount::new(1200.0, "JPY").to_string(), "¥1200");
+        assert_eq!(Amount::new(5.0, "CHF").to_string(), "5.00 CHF");
+        assert_eq!(Amount::usd(-3.0).to_string(), "-$3.00");
+        assert_eq!(Amount::credits(250.0).to_string(), "250 credits");
+        assert_eq!(Amount::credits(2.5).to_string(), "2.50 credits");
+    }
+
+    #[test]
+    fn test_is_currency() {
+        assert!(Amount::usd(1.0).is_currency());
+        assert!(Amount::new(1.0, "cny").is_currency());
+        assert!(!Amount::credits(1.0).is_currency());
+        assert!(!Amount::new(1.0, "TPM").is_currency());
+    }
+
+    #[test]
+    fn test_spend_limit() {
+        let limit = SpendLimit::new(Amount::usd(30.0)).with_cap(Amount::usd(40.0));
+        assert_eq!(limit.used_percent(), Some(75.0));
+        assert_eq!(limit.remaining(), Some(Amount::usd(10.0)));
+        assert!(!limit.is_over_cap());
+
+        let over = SpendLimit::new(Amount::usd(40.0)).with_cap(Amount::usd(40.0));
+        assert!(over.is_over_cap());
+
+        let uncapped = SpendLimit::new(Amount::usd(5.0));
+        assert_eq!(uncapped.used_percent(), None);
+        assert!(!uncapped.is_over_cap());
+    }
+
+    #[test]
+    fn test_exchange_rates_convert() {
+        let rates = ExchangeRates::new("USD")
+            .with_rate("CNY", 8.0)
+            .with_rate("EUR", 0.5);
+
+        let usd = rates.convert(&Amount::new(80.0, "CNY"), "USD").unwrap();
+        assert_eq!(usd, Amount::usd(10.0));
+
+        let eur = rates.convert(&Amount::new(80.0, "CNY"), "EUR").unwrap();
+        assert_eq!(eur, Amount::new(5.0, "EUR"));
+
+        assert!(rates.convert(&Amount::new(1.0, "GBP"), "USD").is_none());
+        assert!(rates.convert(&Amount::credits(1.0), "USD").is_none());
+        assert_eq!(
+            rates.convert(&Amount::credits(1.0), "credits"),
+            Some(Amount::credits(1.0))
+        );
+    }
+}
@@ -2,20 +2,27 @@
 //!
 //! This module contains types related to LLM providers:
 //! - [`ProviderKind`] - Enum of supported providers
+//! - [`CustomProviderId`] - Interned ID of a user-defined provider
 //! - [`Provider`] - Provider configuration
 //! - [`ProviderIdentity`] - Account identity (siloed per provider)
 //! - [`ProviderMetadata`] - Provider capabilities and display info
 //! - [`ProviderBranding`] - Visual styling
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+use super::usage::{UsageWindow, WindowSlot};
 
 // ============================================================================
 // Provider Kind
 // ============================================================================
 
 /// Supported LLM provider kinds.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+///
+/// Serializes as a lowercase string: the CLI name for built-in providers
+/// and `custom:<id>` for user-defined ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ProviderKind {
     /// `OpenAI` Codex
     Codex,
@@ -43,6 +50,20 @@ pub enum ProviderKind {
     MiniMax,
     /// Synthetic.new
     Synthetic,
+    /// Anthropic API (Console usage and cost reports)
+    Anthropic,
+    /// `OpenRouter`
+    OpenRouter,
+    /// Windsurf (Codeium)
+    Windsurf,
+    /// `LiteLLM` proxy (self-hosted gateway)
+    LiteLlm,
+    /// Amazon Bedrock (`CloudWatch` metrics and quotas)
+    Bedrock,
+    /// Azure `OpenAI` / AI Foundry (deployment quotas and Azure Monitor metrics)
+    Azure,
+    /// User-defined HTTP/JSON provider from settings
+    Custom(CustomProviderId),
 }
 
 impl ProviderKind {
@@ -62,10 +83,19 @@ impl ProviderKind {
             Self::Antigravity => "Antigravity",
             Self::MiniMax => "MiniMax",
             Self::Synthetic => "Synthetic.new",
+            Self::Anthropic => "Anthropic API",
+            Self::OpenRouter => "OpenRouter",
+            Self::Windsurf => "Windsurf",
+            Self::LiteLlm => "LiteLLM",
+            Self::Bedrock => "Amazon Bedrock",
+            Self::Azure => "Azure OpenAI",
+            Self::Custom(id) => id.display_name(),
         }
     }
 
-    /// Returns all available provider kinds.
+    /// Returns all built-in provider kinds.
+    ///
+    /// Custom providers are not included; the provider registry lists them.
     pub fn all() -> &'static [ProviderKind] {
         &[
             Self::Codex,
@@ -81,6 +111,12 @@ impl ProviderKind {
             Self::Antigravity,
             Self::MiniMax,
             Self::Synthetic,
+            Self::Anthropic,
+            Self::OpenRouter,
+            Self::Windsurf,
+            Self::LiteLlm,
+            Self::Bedrock,
+            Self::Azure,
         ]
     }
 
@@ -100,14 +136,50 @@ impl ProviderKind {
             Self::Antigravity => "antigravity",
             Self::MiniMax => "minimax",
             Self::Synthetic => "synthetic",
+            Self::Anthropic => "anthropic",
+            Self::OpenRouter => "openrouter",
+            Self::Windsurf => "windsurf",
+            Self::LiteLlm => "litellm",
+            Self::Bedrock => "bedrock",
+            Self::Azure => "azure",
+            Self::Custom(id) => id.as_str(),
         }
     }
 
+    /// Returns the kind for a registered custom provider ID.
+    ///
+    /// Returns `None` if no provider with this ID was registered (see
+    /// [`CustomProviderId::register`]).
+    pub fn custom(id: &str) -> Option<Self> {
+        CustomProviderId::get(id).map(Self::Custom)
+    }
+
+    /// Returns true for user-defined providers.
+    pub fn is_custom(&self) -> bool {
+        matches!(self, Self::Custom(_))
+    }
+
+    /// Returns the key this kind serializes as.
+    pub fn key(&self) -> String {
+        match self {
+            Self::Custom(id) => format!("{}{}", CustomProviderId::KEY_PREFIX, id.as_str()),
+            _ => self.cli_name().to_string(),
+        }
+    }
+
+    /// Parses a key produced by [`ProviderKind::key`].
+    pub fn from_key(key: &str) -> Option<Self> {
+        if let Some(id) = key.strip_prefix(CustomProviderId::KEY_PREFIX) {
+            return Self::custom(id);
+        }
+        Self::all().iter().find(|k| k.cli_name() == key).copied()
+    }
+
     /// Converts this provider to an index (position in the `all()` array).
     ///
-    /// Useful for compact serialization, e.g., storing in Objective-C ivars.
-    pub fn to_index(self) -> usize {
-        Self::all().iter().position(|&p| p == self).unwrap_or(0)
+    /// Returns `None` for custom providers, which are not in `all()`.
+    pub fn to_index(self) -> Option<usize> {
+        Self::all().iter().position(|&p| p == self)
     }
 
     /// Creates a provider from an index (position in the `all()` array).
@@ -118,6 +190,125 @@ impl ProviderKind {
     }
 }
 
+impl Serialize for ProviderKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.key())
+    }
+}
+
+impl<'de> Deserialize<'de> for ProviderKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let key = String::deserialize(deserializer)?;
+        Self::from_key(&key)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown provider: {key}")))
+    }
+}
+
+// ============================================================================
+// Custom Provider ID
+// ============================================================================
+
+/// Interned ID of a user-defined provider.
+///
+/// IDs are interned so [`ProviderKind`] stays `Copy`. Only IDs registered
+/// from the custom provider definitions in settings are interned; keys from
+/// anywhere else (deserialized JSON, IPC, CLI arguments) resolve through
+/// [`CustomProviderId::get`] and never allocate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CustomProviderId(&'static str);
+
+/// Registered IDs mapped to their display names.
+#[derive(Default)]
+struct CustomIds {
+    names: HashMap<&'static str, &'static str>,
+    /// Every string interned so far, so each is allocated only once.
+    pool: HashSet<&'static str>,
+}
+
+impl CustomIds {
+    fn intern(&mut self, value: &str) -> &'static str {
+        if let Some(interned) = self.pool.get(value) {
+            return interned;
+        }
+        let interned: &'static str = Box::leak(value.to_string().into_boxed_str());
+        self.pool.insert(interned);
+        interned
+    }
+}
+
+static CUSTOM_IDS: OnceLock<Mutex<CustomIds>> = OnceLock::new();
+
+fn custom_ids() -> std::sync::MutexGuard<'static, CustomIds> {
+    CUSTOM_IDS
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+impl CustomProviderId {
+    /// Prefix that marks a custom provider in serialized keys.
+    pub const KEY_PREFIX: &'static str = "custom:";
+
+    /// Maximum ID length.
+    pub const MAX_LEN: usize = 32;
+
+    /// Registers an ID from a custom provider definition.
+    ///
+    /// Valid IDs start with a lowercase letter and contain only lowercase
+    /// letters, digits, `-` and `_`, up to [`Self::MAX_LEN`] characters.
+    /// IDs that name a built-in provider are rejected.
+    pub fn register(id: &str) -> Option<Self> {
+        if !Self::is_valid(id) {
+            return None;
+        }
+
+        let mut ids = custom_ids();
+        if let Some((interned, _)) = ids.names.get_key_value(id) {
+            return Some(Self(interned));
+        }
+        let interned = ids.intern(id);
+        ids.names.insert(interned, interned);
+        Some(Self(interned))
+    }
+
+    /// Returns the registered ID, if any.
+    pub fn get(id: &str) -> Option<Self> {
+        custom_ids()
+            .names
+            .get_key_value(id)
+            .map(|(interned, _)| Self(interned))
+    }
+
+    /// Returns true if `id` is usable as a custom provider ID.
+    pub fn is_valid(id: &str) -> bool {
+        let mut chars = id.chars();
+        chars.next().is_some_and(|c| c.is_ascii_lowercase())
+            && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+            && id.len() <= Self::MAX_LEN
+            && !ProviderKind::all().iter().any(|k| k.cli_name() == id)
+    }
+
+    /// Returns the ID.
+    pub fn as_str(self) -> &'static str {
+        self.0
+    }
+
+    /// Returns the display name, or the ID if none was set.
+    pub fn display_name(self) -> &'static str {
+        custom_ids().names.get(self.0).copied().unwrap_or(self.0)
+    }
+
+    /// Sets the display name.
+    ///
+    /// Names are interned like IDs, so switching between names already
+    /// seen allocates nothing.
+    pub fn set_display_name(self, name: &str) {
+        let mut ids = custom_ids();
+        let interned = ids.intern(name);
+        ids.names.insert(self.0, interned);
+    }
+}
+
 // ============================================================================
 // Provider Configuration
 // ============================================================================
@@ -243,6 +434,9 @@ pub struct ProviderMetadata {
     pub opus_label: Option<String>,
     /// Whether this provider supports opus/premium tier.
     pub supports_opus: bool,
+    /// Labels for named windows, keyed by window ID.
+    #[serde(default)]
+    pub window_labels: HashMap<String, String>,
     /// Whether this provider uses a credit system.
     pub supports_credits: bool,
     /// Hint text for credits display.
@@ -274,10 +468,11 @@ impl ProviderMetadata {
         Self {
             id: kind,
             display_name: name.to_string(),
-            session_label: "Session".to_string(),
-            weekly_label: "Weekly".to_string(),
+            session_label: WindowSlot::Primary.default_label().to_string(),
+            weekly_label: WindowSlot::Secondary.default_label().to_string(),
             opus_label: None,
             supports_opus: false,
+            window_labels: HashMap::new(),
             supports_credits: false,
             credits_hint: String::new(),
             toggle_title: format!("Show {name} usage"),
@@ -291,6 +486,29 @@ impl ProviderMetadata {
             status_link_url: None,
         }
     }
+
+    /// Returns the display label for a window.
+    ///
+    /// Resolution order: the window's own label, `window_labels` by ID,
+    /// the slot label (session/weekly/opus, falling back to
+    /// [`WindowSlot::default_label`]), then the window ID.
+    pub fn window_label(&self, window: &UsageWindow) -> String {
+        if let Some(label) = &window.label {
+            return label.clone();
+        }
+        if let Some(label) = self.window_labels.get(&window.id) {
+            return label.clone();
+        }
+        match window.slot {
+            Some(WindowSlot::Primary) => self.session_label.clone(),
+            Some(WindowSlot::Secondary) => self.weekly_label.clone(),
+            Some(slot @ WindowSlot::Tertiary) => self
+                .opus_label
+                .clone()
+                .unwrap_or_else(|| slot.default_label().to_string()),
+            None => window.id.clone(),
+        }
+    }
 }
 
 // ============================================================================
@@ -327,6 +545,15 @@ impl ProviderBranding {
             }
             ProviderKind::MiniMax => (IconStyle::MiniMax, ProviderColor::new(0.9, 0.1, 0.3)),
             ProviderKind::Synthetic => (IconStyle::Synthetic, ProviderColor::new(0.0, 0.8, 0.7)),
+            ProviderKind::Anthropic => (IconStyle::Anthropic, ProviderColor::new(0.85, 0.47, 0.34)),
+            ProviderKind::OpenRouter => {
+                (IconStyle::OpenRouter, ProviderColor::new(0.39, 0.4, 0.95))
+            }
+            ProviderKind::Windsurf => (IconStyle::Windsurf, ProviderColor::new(0.04, 0.71, 0.64)),
+            ProviderKind::LiteLlm => (IconStyle::LiteLlm, ProviderColor::new(0.96, 0.62, 0.04)),
+            ProviderKind::Bedrock => (IconStyle::Bedrock, ProviderColor::new(1.0, 0.6, 0.0)),
+            ProviderKind::Azure => (IconStyle::Azure, ProviderColor::new(0.0, 0.47, 0.83)),
+            ProviderKind::Custom(_) => (IconStyle::Custom, ProviderColor::default()),
         };
 
         Self {
@@ -364,6 +591,20 @@ impl ProviderColor {
         )
     }
 
+    /// Parses a hex string (e.g., "#FF6600" or "ff6600").
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.trim().trim_start_matches('#');
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .ok()
+                .map(|v| f32::from(v) / 255.0)
+        };
+        Some(Self::new(channel(0)?, channel(2)?, channel(4)?))
+    }
+
     /// Converts to hex string (e.g., "#FF6600").
     pub fn to_hex(&self) -> String {
         let (r, g, b) = self.to_rgb8();
@@ -408,6 +649,20 @@ pub enum IconStyle {
     MiniMax,
     /// Synthetic.new icon.
     Synthetic,
+    /// Anthropic API icon.
+    Anthropic,
+    /// `OpenRouter` icon.
+    OpenRouter,
+    /// Windsurf icon.
+    Windsurf,
+    /// `LiteLLM` icon.
+    LiteLlm,
+    /// Amazon Bedrock icon.
+    Bedrock,
+    /// Azure `OpenAI` icon.
+    Azure,
+    /// User-defined provider (monogram).
+    Custom,
     /// Combined/aggregate view icon.
     Combined,
 }
@@ -438,6 +693,100 @@ mod tests {
         assert_eq!(color.to_hex(), "#FF7F00");
     }
 
+    #[test]
+    fn test_provider_color_from_hex() {
+        let color = ProviderColor::from_hex("#FF7F00").unwrap();
+        assert_eq!(color.to_hex(), "#FF7F00");
+        assert_eq!(
+            ProviderColor::from_hex("0a0b0c").unwrap().to_hex(),
+            "#0A0B0C"
+        );
+        assert!(ProviderColor::from_hex("#FFF").is_none());
+        assert!(ProviderColor::from_hex("#GG0000").is_none());
+    }
+
+    #[test]
+    fn test_provider_kind_serde_keys() {
+        let json = serde_json::to_string(&ProviderKind::VertexAI).unwrap();
+        assert_eq!(json, "\"vertexai\"");
+        for kind in ProviderKind::all() {
+            let json = serde_json::to_string(kind).unwrap();
+            assert_eq!(serde_json::from_str::<ProviderKind>(&json).unwrap(), *kind);
+        }
+        assert!(serde_json::from_str::<ProviderKind>("\"nope\"").is_err());
+    }
+
+    #[test]
+    fn test_custom_provider_kind() {
+        assert!(ProviderKind::custom("gateway").is_none());
+        assert!(serde_json::from_str::<ProviderKind>("\"custom:gateway\"").is_err());
+
+        CustomProviderId::register("gateway").unwrap();
+        let kind = ProviderKind::custom("gateway").unwrap();
+        assert!(kind.is_custom());
+        assert_eq!(kind, ProviderKind::custom("gateway").unwrap());
+        assert_eq!(kind.cli_name(), "gateway");
+        assert_eq!(kind.display_name(), "gateway");
+        assert_eq!(kind.key(), "custom:gateway");
+        assert_eq!(kind.to_index(), None);
+        assert_eq!(
+            ProviderKind::from_index(ProviderKind::Claude.to_index().unwrap()),
+            Some(ProviderKind::Claude)
+        );
+
+        let ProviderKind::Custom(id) = kind else {
+            unreachable!()
+        };
+        id.set_display_name("Internal Gateway");
+        assert_eq!(kind.display_name(), "Internal Gateway");
+
+        let json = serde_json::to_string(&kind).unwrap();
+        assert_eq!(json, "\"custom:gateway\"");
+        assert_eq!(serde_json::from_str::<ProviderKind>(&json).unwrap(), kind);
+
+        // Usable as a JSON map key
+        let map = HashMap::from([(kind, 1), (ProviderKind::Claude, 2)]);
+        let json = serde_json::to_string(&map).unwrap();
+        let back: HashMap<ProviderKind, i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, map);
+    }
+
+    #[test]
+    fn test_custom_provider_id_validation() {
+        assert!(CustomProviderId::is_valid("my-gateway_2"));
+        assert!(!CustomProviderId::is_valid(""));
+        assert!(!CustomProviderId::is_valid("2fast"));
+        assert!(!CustomProviderId::is_valid("Upper"));
+        assert!(!CustomProviderId::is_valid("has space"));
+        assert!(!CustomProviderId::is_valid("claude"));
+        assert!(!CustomProviderId::is_valid(&"x".repeat(33)));
+    }
+
+    #[test]
+    fn test_metadata_window_label() {
+        let mut metadata = ProviderMetadata::for_provider(ProviderKind::Claude);
+        metadata
+            .window_labels
+            .insert("sonnet".to_string(), "Sonnet".to_string());
+
+        let mut session = UsageWindow::new(10.0);
+        session.slot = Some(WindowSlot::Primary);
+        assert_eq!(metadata.window_label(&session), "Session");
+
+        let mut tertiary = UsageWindow::named("seven_day_opus", crate::WindowKind::Model, 10.0);
+        tertiary.slot = Some(WindowSlot::Tertiary);
+        assert_eq!(metadata.window_label(&tertiary), "Opus");
+        metadata.opus_label = Some("Premium".to_string());
+        assert_eq!(metadata.window_label(&tertiary), "Premium");
+
+        let sonnet = UsageWindow::named("sonnet", crate::WindowKind::Weekly, 10.0);
+        assert_eq!(metadata.window_label(&sonnet), "Sonnet");
+
+        let model = UsageWindow::named("gpt-5", crate::WindowKind::Model, 10.0);
+        assert_eq!(metadata.window_label(&model), "gpt-5");
+        assert_eq!(metadata.window_label(&model.with_label("GPT-5")), "GPT-5");
+    }
+
     #[test]
     fn test_identity_display_string() {
         let mut identity = ProviderIdentity::new(ProviderKind::Claude);
@@ -7,10 +7,10 @@ use chrono::{Duration, Utc};
 use serde_json;
 
 use crate::{
-    CostUsageSnapshot, Credits, DailyUsageEntry, FetchSource, IconStyle, LoginMethod,
-    ModelBreakdown, Provider, ProviderBranding, ProviderColor, ProviderIdentity, ProviderKind,
-    ProviderMetadata, ProviderStatus, Quota, StatusIndicator, UsageData, UsageSnapshot,
-    UsageWindow,
+    Amount, CostUsageSnapshot, Credits, DailyUsageEntry, ExchangeRates, FetchSource, IconStyle,
+    LoginMethod, ModelBreakdown, Provider, ProviderBranding, ProviderColor, ProviderIdentity,
+    ProviderKind, ProviderMetadata, ProviderStatus, Quota, SpendLimit, StatusIndicator, UsageData,
+    UsageSnapshot, UsageWindow, WindowKind,
 };
 
 // ============================================================================
@@ -66,9 +66,9 @@ fn test_usage_snapshot_empty_roundtrip() {
     let json = serde_json::to_string(&snapshot).unwrap();
     let deserialized: UsageSnapshot = serde_json::from_str(&json).unwrap();
 
-    assert!(deserialized.primary.is_none());
-    assert!(deserialized.secondary.is_none());
-    assert!(deserialized.tertiary.is_none());
+    assert!(deserialized.primary().is_none());
+    assert!(deserialized.secondary().is_none());
+    assert!(deserialized.tertiary().is_none());
 }
 
 #[test]
@@ -76,15 +76,15 @@ fn test_usage_snapshot_empty_roundtrip() {
 fn test_usage_snapshot_full_roundtrip() {
     let mut snapshot = UsageSnapshot::new();
 
-    snapshot.primary = Some(UsageWindow {
-        used_percent: 45.5,
+    snapshot.set_primary(Some(UsageWindow {
         window_minutes: Some(300),
         resets_at: Some(Utc::now() + Duration::hours(2)),
         reset_description: Some("in 2 hours".to_string()),
-    });
+        ..UsageWindow::new(45.5)
+    }));
 
-    snapshot.secondary = Some(UsageWindow::new(20.0));
-    snapshot.tertiary = Some(UsageWindow::new(75.0));
+    snapshot.set_secondary(Some(UsageWindow::new(20.0)));
+    snapshot.set_tertiary(Some(UsageWindow::new(75.0)));
     snapshot.fetch_source = FetchSource::CLI;
 
     let mut identity = ProviderIdentity::new(ProviderKind::Claude);
@@ -95,14 +95,11 @@ fn test_usage_snapshot_full_roundtrip() {
     let json = serde_json::to_string(&snapshot).unwrap();
     let deserialized: UsageSnapshot = serde_json::from_str(&json).unwrap();
 
-    assert!(deserialized.primary.is_some());
-    assert_eq!(deserialized.primary.as_ref().unwrap().used_percent, 45.5);
-    assert_eq!(
-        deserialized.primary.as_ref().unwrap().window_minutes,
-        Some(300)
-    );
-    assert!(deserialized.secondary.is_some());
-    assert!(deserialized.tertiary.is_some());
+    assert!(deserialized.primary().is_some());
+    assert_eq!(deserialized.primary().unwrap().used_percent, 45.5);
+    assert_eq!(deserialized.primary().unwrap().window_minutes, Some(300));
+    assert!(deserialized.secondary().is_some());
+    assert!(deserialized.tertiary().is_some());
     assert!(deserialized.identity.is_some());
     assert_eq!(
         deserialized.identity.as_ref().unwrap().account_email,
@@ -180,6 +177,53 @@ fn test_credits_zero_total() {
     assert_eq!(deserialized.remaining_percent(), Some(100.0));
 }
 
+#[test]
+fn test_credits_legacy_without_unit() {
+    let json = r#"{"remaining": 12.0, "total": null, "updated_at": "2024-01-15T10:00:00Z"}"#;
+    let credits: Credits = serde_json::from_str(json).unwrap();
+    assert_eq!(credits.unit, "credits");
+    assert!(credits.spend_limit.is_none());
+}
+
+#[test]
+fn test_credits_with_spend_limit_roundtrip() {
+    let mut spend = SpendLimit::new(Amount::usd(12.5)).with_cap(Amount::usd(50.0));
+    spend.resets_at = Some(Utc::now() + Duration::days(10));
+    spend.overage_enabled = true;
+    let credits = Credits::from_amount(Amount::new(88.0, "CNY")).with_spend_limit(spend);
+
+    let json = serde_json::to_string(&credits).unwrap();
+    let deserialized: Credits = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(deserialized.unit, "CNY");
+    let spend = deserialized.spend_limit.unwrap();
+    assert_eq!(spend.used, Amount::usd(12.5));
+    assert_eq!(spend.cap, Some(Amount::usd(50.0)));
+    assert!(spend.overage_enabled);
+    assert!(spend.resets_at.is_some());
+}
+
+#[test]
+fn test_snapshot_credits_roundtrip() {
+    let mut snapshot = UsageSnapshot::new();
+    snapshot.credits = Some(Credits::from_amount(Amount::usd(4.2)));
+
+    let json = serde_json::to_string(&snapshot).unwrap();
+    let deserialized: UsageSnapshot = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.credits.unwrap().unit, "USD");
+
+    let bare = serde_json::to_string(&UsageSnapshot::new()).unwrap();
+    assert!(!bare.contains("credits"));
+}
+
+#[test]
+fn test_exchange_rates_deserialize() {
+    let json = r#"{"base": "USD", "rates": {"cny": 7.0}}"#;
+    let rates: ExchangeRates = serde_json::from_str(json).unwrap();
+    let converted = rates.convert(&Amount::new(70.0, "CNY"), "USD").unwrap();
+    assert!((converted.value - 10.0).abs() < 0.001);
+}
+
 // ============================================================================
 // ProviderIdentity Serde Tests
 // ============================================================================
@@ -483,10 +527,53 @@ fn test_deserialize_minimal_usage_snapshot() {
     }"#;
 
     let snapshot: UsageSnapshot = serde_json::from_str(json).unwrap();
-    assert!(snapshot.primary.is_none());
+    assert!(snapshot.primary().is_none());
     assert!(snapshot.identity.is_none());
 }
 
+#[test]
+fn test_deserialize_legacy_slot_fields() {
+    // Snapshots written before named windows used fixed fields
+    let json = r#"{
+        "primary": {"used_percent": 25.0, "window_minutes": 300},
+        "secondary": {"used_percent": 50.0},
+        "updated_at": "2024-01-15T10:00:00Z"
+    }"#;
+
+    let snapshot: UsageSnapshot = serde_json::from_str(json).unwrap();
+    assert_eq!(snapshot.windows.len(), 2);
+    assert!((snapshot.primary().unwrap().used_percent - 25.0).abs() < 0.001);
+    assert_eq!(snapshot.primary().unwrap().id, "primary");
+    assert_eq!(snapshot.primary().unwrap().kind, WindowKind::Session);
+    assert!((snapshot.secondary().unwrap().used_percent - 50.0).abs() < 0.001);
+}
+
+#[test]
+fn test_named_windows_roundtrip() {
+    let mut snapshot = UsageSnapshot::new();
+    snapshot.set_primary(Some(UsageWindow::named(
+        "session",
+        WindowKind::Session,
+        10.0,
+    )));
+    snapshot.push_window(
+        UsageWindow::named("gemini-2.5-pro", WindowKind::Model, 40.0)
+            .with_label("Gemini 2.5 Pro")
+            .with_counts(40.0, Some(100.0), "requests"),
+    );
+
+    let json = serde_json::to_string(&snapshot).unwrap();
+    let deserialized: UsageSnapshot = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(deserialized.windows.len(), 2);
+    assert_eq!(deserialized.primary().unwrap().id, "session");
+    let model = deserialized.window("gemini-2.5-pro").unwrap();
+    assert_eq!(model.kind, WindowKind::Model);
+    assert_eq!(model.label.as_deref(), Some("Gemini 2.5 Pro"));
+    assert_eq!(model.limit, Some(100.0));
+    assert!(model.slot.is_none());
+}
+
 #[test]
 fn test_deserialize_with_unknown_fields() {
     // Test that unknown fields are ignored (forward compatibility)
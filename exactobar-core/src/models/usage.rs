@@ -5,12 +5,14 @@
 //! - [`UsageWindow`] - Individual usage window
 //! - [`UsageData`] - Legacy simple format
 //! - [`Quota`] - Quota information
-//! - [`Credits`] - Credit-based systems
+//! - [`Credits`] - Credit balances and spend, with currency or unit
 
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 
 use super::ProviderIdentity;
+use super::cost::CostUsageSnapshot;
+use super::money::{Amount, CREDITS_UNIT, SpendLimit};
 use super::provider::ProviderKind;
 use super::status::FetchSource;
 use crate::error::CoreError;
@@ -19,42 +21,183 @@ use crate::error::CoreError;
 // Usage Snapshot & Windows
 // ============================================================================
 
-/// A snapshot of usage data with primary, secondary, and tertiary windows.
+/// A snapshot of usage data with any number of named windows.
 ///
-/// This is the main container for usage information:
+/// This is the main container for usage information. Windows are kept in
+/// display order. The first three well-known windows are addressable
+/// through compatibility slots:
 /// - **Primary** = session window (e.g., 5 hours for Claude)
 /// - **Secondary** = weekly/monthly window
 /// - **Tertiary** = opus/premium tier (Claude-specific)
+///
+/// Additional windows (e.g., per-model quotas) have no slot.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "UsageSnapshotRepr")]
 pub struct UsageSnapshot {
-    /// Primary usage window (session-based).
-    pub primary: Option<UsageWindow>,
-    /// Secondary usage window (weekly/monthly).
-    pub secondary: Option<UsageWindow>,
-    /// Tertiary usage window (opus/premium tier).
-    pub tertiary: Option<UsageWindow>,
+    /// All usage windows, in display order.
+    pub windows: Vec<UsageWindow>,
     /// When this snapshot was last updated.
     pub updated_at: DateTime<Utc>,
     /// Account identity for this provider.
     pub identity: Option<ProviderIdentity>,
+    /// Credit balance or spend, for providers that bill that way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credits: Option<Credits>,
+    /// Token and cost report, for providers with a billing API.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<CostUsageSnapshot>,
     /// How this data was fetched.
-    #[serde(default)]
     pub fetch_source: FetchSource,
 }
 
+/// Wire format for [`UsageSnapshot`], accepting the legacy
+/// `primary`/`secondary`/`tertiary` fields.
+#[derive(Deserialize)]
+struct UsageSnapshotRepr {
+    #[serde(default)]
+    windows: Vec<UsageWindow>,
+    #[serde(default)]
+    primary: Option<UsageWindow>,
+    #[serde(default)]
+    secondary: Option<UsageWindow>,
+    #[serde(default)]
+    tertiary: Option<UsageWindow>,
+    updated_at: DateTime<Utc>,
+    #[serde(default)]
+    identity: Option<ProviderIdentity>,
+    #[serde(default)]
+    credits: Option<Credits>,
+    #[serde(default)]
+    cost: Option<CostUsageSnapshot>,
+    #[serde(default)]
+    fetch_source: FetchSource,
+}
+
+impl From<UsageSnapshotRepr> for UsageSnapshot {
+    fn from(repr: UsageSnapshotRepr) -> Self {
+        let mut snapshot = Self {
+            windows: repr.windows,
+            updated_at: repr.updated_at,
+            identity: repr.identity,
+            credits: repr.credits,
+            cost: repr.cost,
+            fetch_source: repr.fetch_source,
+        };
+        for (slot, window) in [
+            (WindowSlot::Primary, repr.primary),
+            (WindowSlot::Secondary, repr.secondary),
+            (WindowSlot::Tertiary, repr.tertiary),
+        ] {
+            if window.is_some() && snapshot.slot(slot).is_none() {
+                snapshot.set_slot(slot, window);
+            }
+        }
+        snapshot
+    }
+}
+
 impl UsageSnapshot {
     /// Creates a new empty usage snapshot.
     pub fn new() -> Self {
         Self {
-            primary: None,
-            secondary: None,
-            tertiary: None,
+            windows: Vec::new(),
             updated_at: Utc::now(),
             identity: None,
+            credits: None,
+            cost: None,
             fetch_source: FetchSource::default(),
         }
     }
 
+    /// Returns the primary (session) window.
+    pub fn primary(&self) -> Option<&UsageWindow> {
+        self.slot(WindowSlot::Primary)
+    }
+
+    /// Returns the secondary (weekly/monthly) window.
+    pub fn secondary(&self) -> Option<&UsageWindow> {
+        self.slot(WindowSlot::Secondary)
+    }
+
+    /// Returns the tertiary (opus/premium) window.
+    pub fn tertiary(&self) -> Option<&UsageWindow> {
+        self.slot(WindowSlot::Tertiary)
+    }
+
+    /// Sets or clears the primary window.
+    pub fn set_primary(&mut self, window: Option<UsageWindow>) {
+        self.set_slot(WindowSlot::Primary, window);
+    }
+
+    /// Sets or clears the secondary window.
+    pub fn set_secondary(&mut self, window: Option<UsageWindow>) {
+        self.set_slot(WindowSlot::Secondary, window);
+    }
+
+    /// Sets or clears the tertiary window.
+    pub fn set_tertiary(&mut self, window: Option<UsageWindow>) {
+        self.set_slot(WindowSlot::Tertiary, window);
+    }
+
+    /// Returns the window in the given compatibility slot.
+    pub fn slot(&self, slot: WindowSlot) -> Option<&UsageWindow> {
+        self.windows.iter().find(|w| w.slot == Some(slot))
+    }
+
+    /// Sets or clears the window in the given compatibility slot.
+    ///
+    /// Slotted windows are kept ahead of unslotted ones, in slot order.
+    /// A window without an ID gets the slot name as its ID, and a window
+    /// without a kind gets one inferred from its duration.
+    pub fn set_slot(&mut self, slot: WindowSlot, window: Option<UsageWindow>) {
+        let existing = self.windows.iter().position(|w| w.slot == Some(slot));
+
+        let Some(mut window) = window else {
+            if let Some(index) = existing {
+                self.windows.remove(index);
+            }
+            return;
+        };
+
+        window.slot = Some(slot);
+        if window.id.is_empty() {
+            window.id = slot.name().to_string();
+        }
+        if window.kind == WindowKind::Other {
+            window.kind = WindowKind::from_minutes(window.window_minutes);
+        }
+
+        if let Some(index) = existing {
+            self.windows[index] = window;
+        } else {
+            let index = self
+                .windows
+                .iter()
+                .take_while(|w| w.slot.is_some_and(|s| s < slot))
+                .count();
+            self.windows.insert(index, window);
+        }
+    }
+
+    /// Returns the window with the given ID.
+    pub fn window(&self, id: &str) -> Option<&UsageWindow> {
+        self.windows.iter().find(|w| w.id == id)
+    }
+
+    /// Adds a window, replacing any existing window with the same ID.
+    pub fn push_window(&mut self, window: UsageWindow) {
+        if let Some(existing) = self.windows.iter_mut().find(|w| w.id == window.id) {
+            *existing = window;
+        } else {
+            self.windows.push(window);
+        }
+    }
+
+    /// Returns all windows of the given kind.
+    pub fn windows_of_kind(&self, kind: WindowKind) -> impl Iterator<Item = &UsageWindow> {
+        self.windows.iter().filter(move |w| w.kind == kind)
+    }
+
     /// Returns true if this snapshot is stale (older than threshold).
     pub fn is_stale(&self, threshold: Duration) -> bool {
         Utc::now() - self.updated_at > threshold
@@ -62,35 +205,20 @@ impl UsageSnapshot {
 
     /// Returns true if any window is approaching its limit (>80%).
     pub fn is_approaching_limit(&self) -> bool {
-        self.primary.as_ref().is_some_and(|w| w.used_percent > 80.0)
-            || self
-                .secondary
-                .as_ref()
-                .is_some_and(|w| w.used_percent > 80.0)
-            || self
-                .tertiary
-                .as_ref()
-                .is_some_and(|w| w.used_percent > 80.0)
+        self.windows.iter().any(|w| w.used_percent > 80.0)
     }
 
     /// Returns the highest usage percentage across all windows.
     pub fn max_usage_percent(&self) -> f64 {
-        let mut max = 0.0_f64;
-        if let Some(ref w) = self.primary {
-            max = max.max(w.used_percent);
-        }
-        if let Some(ref w) = self.secondary {
-            max = max.max(w.used_percent);
-        }
-        if let Some(ref w) = self.tertiary {
-            max = max.max(w.used_percent);
-        }
-        max
+        self.windows
+            .iter()
+            .map(|w| w.used_percent)
+            .fold(0.0_f64, f64::max)
     }
 
-    /// Returns true if any window data is present.
+    /// Returns true if any window, credit or cost data is present.
     pub fn has_data(&self) -> bool {
-        self.primary.is_some() || self.secondary.is_some() || self.tertiary.is_some()
+        !self.windows.is_empty() || self.credits.is_some() || self.cost.is_some()
     }
 }
 
@@ -112,20 +240,15 @@ impl UsageSnapshot {
     /// Returns `CoreError::InvalidData` if any usage window contains
     /// invalid percentage values (negative, > 100, or non-finite).
     pub fn validate(&self) -> Result<(), CoreError> {
-        if let Some(ref primary) = self.primary {
-            primary
-                .validate()
-                .map_err(|e| CoreError::InvalidData(format!("primary window: {e}")))?;
-        }
-        if let Some(ref secondary) = self.secondary {
-            secondary
+        for window in &self.windows {
+            window
                 .validate()
-                .map_err(|e| CoreError::InvalidData(format!("secondary window: {e}")))?;
+                .map_err(|e| CoreError::InvalidData(format!("{} window: {e}", window.id)))?;
         }
-        if let Some(ref tertiary) = self.tertiary {
-            tertiary
+        if let Some(credits) = &self.credits {
+            credits
                 .validate()
-                .map_err(|e| CoreError::InvalidData(format!("tertiary window: {e}")))?;
+                .map_err(|e| CoreError::InvalidData(format!("credits: {e}")))?;
         }
         Ok(())
     }
@@ -136,23 +259,153 @@ impl UsageSnapshot {
     /// of returning an error. Use when you want to be lenient with
     /// potentially buggy API responses.
     pub fn sanitize(&mut self) {
-        if let Some(ref mut primary) = self.primary {
-            primary.sanitize();
+        for window in &mut self.windows {
+            window.sanitize();
+        }
+        if self.credits.as_ref().is_some_and(|c| c.validate().is_err()) {
+            self.credits = None;
+        }
+    }
+}
+
+/// Compatibility slot for the three well-known windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowSlot {
+    /// Primary window (session).
+    Primary,
+    /// Secondary window (weekly/monthly).
+    Secondary,
+    /// Tertiary window (opus/premium tier).
+    Tertiary,
+}
+
+impl WindowSlot {
+    /// Returns the slot name, used as the default window ID.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Primary => "primary",
+            Self::Secondary => "secondary",
+            Self::Tertiary => "tertiary",
+        }
+    }
+
+    /// Returns the label shown when a provider doesn't name the slot.
+    pub fn default_label(&self) -> &'static str {
+        match self {
+            Self::Primary => "Session",
+            Self::Secondary => "Weekly",
+            Self::Tertiary => "Opus",
+        }
+    }
+}
+
+/// The kind of period a usage window covers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowKind {
+    /// Short rolling session window (e.g., 5 hours).
+    Session,
+    /// Weekly window.
+    Weekly,
+    /// Monthly or billing-cycle window.
+    Monthly,
+    /// Per-model quota.
+    Model,
+    /// Unknown or provider-specific window.
+    #[default]
+    Other,
+}
+
+impl WindowKind {
+    /// Infers the kind from a window duration in minutes.
+    pub fn from_minutes(minutes: Option<u32>) -> Self {
+        match minutes {
+            Some(m) if m <= 24 * 60 => Self::Session,
+            Some(m) if m <= 7 * 24 * 60 => Self::Weekly,
+            Some(_) => Self::Monthly,
+            None => Self::Other,
+        }
+    }
+}
+
+/// How close a window is to its limit.
+///
+/// Shared by the menu bar icon and the status bar outputs so every surface
+/// changes color at the same point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageLevel {
+    /// Under [`UsageLevel::WARNING_PERCENT`] used.
+    Good,
+    /// Between the warning and danger thresholds.
+    Warning,
+    /// At or over [`UsageLevel::DANGER_PERCENT`] used.
+    Danger,
+}
+
+impl UsageLevel {
+    /// Used percent at which a window becomes [`UsageLevel::Warning`].
+    pub const WARNING_PERCENT: f64 = 50.0;
+    /// Used percent at which a window becomes [`UsageLevel::Danger`].
+    pub const DANGER_PERCENT: f64 = 80.0;
+
+    /// Returns the level for a used percentage.
+    pub fn from_used_percent(used_percent: f64) -> Self {
+        if used_percent < Self::WARNING_PERCENT {
+            Self::Good
+        } else if used_percent < Self::DANGER_PERCENT {
+            Self::Warning
+        } else {
+            Self::Danger
         }
-        if let Some(ref mut secondary) = self.secondary {
-            secondary.sanitize();
+    }
+
+    /// Returns the lowercase name (`good`, `warning`, `danger`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Good => "good",
+            Self::Warning => "warning",
+            Self::Danger => "danger",
         }
-        if let Some(ref mut tertiary) = self.tertiary {
-            tertiary.sanitize();
+    }
+
+    /// Returns the display color: `brand` when good, amber or red otherwise.
+    pub fn rgb8(self, brand: (u8, u8, u8)) -> (u8, u8, u8) {
+        match self {
+            Self::Good => brand,
+            Self::Warning => (255, 193, 7),
+            Self::Danger => (244, 67, 54),
         }
     }
 }
 
-/// Represents a single usage window (session, weekly, or tier).
+/// Represents a single usage window (session, weekly, tier, or model).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UsageWindow {
+    /// Stable identifier (e.g., "session", "weekly", "gemini-2.5-pro").
+    #[serde(default)]
+    pub id: String,
+    /// Display label (falls back to provider metadata when absent).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// The kind of period this window covers.
+    #[serde(default)]
+    pub kind: WindowKind,
+    /// Compatibility slot, if this is one of the three well-known windows.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot: Option<WindowSlot>,
     /// Percentage of quota used (0-100).
     pub used_percent: f64,
+    /// Absolute amount used, in `unit`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub used: Option<f64>,
+    /// Absolute limit, in `unit`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<f64>,
+    /// Unit for `used`/`limit` (e.g., "requests", "tokens", "credits").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
     /// Window duration in minutes (300 = 5 hours, 10080 = 1 week).
     pub window_minutes: Option<u32>,
     /// When this window resets.
@@ -165,18 +418,66 @@ impl UsageWindow {
     /// Creates a new usage window with the given percentage.
     pub fn new(used_percent: f64) -> Self {
         Self {
+            id: String::new(),
+            label: None,
+            kind: WindowKind::Other,
+            slot: None,
             used_percent,
+            used: None,
+            limit: None,
+            unit: None,
             window_minutes: None,
             resets_at: None,
             reset_description: None,
         }
     }
 
+    /// Creates a named window with the given ID and kind.
+    pub fn named(id: impl Into<String>, kind: WindowKind, used_percent: f64) -> Self {
+        Self {
+            id: id.into(),
+            kind,
+            ..Self::new(used_percent)
+        }
+    }
+
+    /// Sets the ID and kind.
+    #[must_use]
+    pub fn with_id(mut self, id: impl Into<String>, kind: WindowKind) -> Self {
+        self.id = id.into();
+        self.kind = kind;
+        self
+    }
+
+    /// Sets the display label.
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Sets absolute used/limit counts and their unit.
+    #[must_use]
+    pub fn with_counts(mut self, used: f64, limit: Option<f64>, unit: impl Into<String>) -> Self {
+        self.used = Some(used);
+        self.limit = limit;
+        self.unit = Some(unit.into());
+        self
+    }
+
     /// Returns the remaining percentage (100 - used).
     pub fn remaining_percent(&self) -> f64 {
         (100.0 - self.used_percent).max(0.0)
     }
 
+    /// Returns the remaining absolute amount, if counts are known.
+    pub fn remaining(&self) -> Option<f64> {
+        match (self.used, self.limit) {
+            (Some(used), Some(limit)) => Some((limit - used).max(0.0)),
+            _ => None,
+        }
+    }
+
     /// Returns true if usage is over the limit.
     pub fn is_over_limit(&self) -> bool {
         self.used_percent >= 100.0
@@ -232,11 +533,14 @@ impl UsageWindow {
     ///
     /// - Clamps `used_percent` to [0, 100]
     /// - Replaces NaN/Infinity with 0.0
+    /// - Drops negative or non-finite absolute counts
     pub fn sanitize(&mut self) {
         if !self.used_percent.is_finite() {
             self.used_percent = 0.0;
         }
         self.used_percent = self.used_percent.clamp(0.0, 100.0);
+        self.used = self.used.filter(|v| v.is_finite() && *v >= 0.0);
+        self.limit = self.limit.filter(|v| v.is_finite() && *v >= 0.0);
     }
 }
 
@@ -247,27 +551,88 @@ impl UsageWindow {
 /// Credits information for providers that use credit systems.
 ///
 /// Some providers (like Cursor) use a credit system instead of
-/// percentage-based quotas.
+/// percentage-based quotas. Amounts carry a currency or unit code so
+/// dollar, yuan, and opaque credit balances are never mixed up.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Credits {
     /// Remaining credits.
     pub remaining: f64,
     /// Total credits (if known).
     pub total: Option<f64>,
+    /// Currency (ISO 4217) or unit code for `remaining` and `total`.
+    #[serde(default = "default_credits_unit")]
+    pub unit: String,
+    /// Pay-as-you-go or extra-usage spend, if the provider reports it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spend_limit: Option<SpendLimit>,
     /// When this was last updated.
     pub updated_at: DateTime<Utc>,
 }
 
+fn default_credits_unit() -> String {
+    CREDITS_UNIT.to_string()
+}
+
 impl Credits {
     /// Creates new credits with the given remaining amount.
     pub fn new(remaining: f64) -> Self {
         Self {
             remaining,
             total: None,
+            unit: default_credits_unit(),
+            spend_limit: None,
             updated_at: Utc::now(),
         }
     }
 
+    /// Creates credits from a remaining amount, taking its unit.
+    pub fn from_amount(remaining: Amount) -> Self {
+        Self {
+            unit: remaining.unit,
+            ..Self::new(remaining.value)
+        }
+    }
+
+    /// Creates credits from pay-as-you-go spend.
+    ///
+    /// With a cap, the balance is what's left under it. Uncapped spend has
+    /// no balance, so the amount is the spend itself rather than zero.
+    pub fn from_spend(spend: SpendLimit) -> Self {
+        let credits = match (spend.remaining(), &spend.cap) {
+            (Some(remaining), Some(cap)) => Self {
+                total: Some(cap.value),
+                ..Self::from_amount(Amount::new(remaining.value.max(0.0), remaining.unit))
+            },
+            _ => Self::from_amount(spend.used.clone()),
+        };
+        credits.with_spend_limit(spend)
+    }
+
+    /// Sets the total credits.
+    #[must_use]
+    pub fn with_total(mut self, total: f64) -> Self {
+        self.total = Some(total);
+        self
+    }
+
+    /// Sets the spend limit.
+    #[must_use]
+    pub fn with_spend_limit(mut self, spend_limit: SpendLimit) -> Self {
+        self.spend_limit = Some(spend_limit);
+        self
+    }
+
+    /// Returns the remaining balance as an amount.
+    pub fn remaining_amount(&self) -> Amount {
+        Amount::new(self.remaining, self.unit.clone())
+    }
+
+    /// Returns the total as an amount, if known.
+    pub fn total_amount(&self) -> Option<Amount> {
+        self.total
+            .map(|total| Amount::new(total, self.unit.clone()))
+    }
+
     /// Returns the usage percentage if total is known.
     pub fn usage_percent(&self) -> Option<f64> {
         self.total.map(|total| {
@@ -289,6 +654,27 @@ impl Credits {
             }
         })
     }
+
+    /// Validates that all amounts are finite.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CoreError::InvalidData` if any amount is NaN or infinite.
+    pub fn validate(&self) -> Result<(), CoreError> {
+        let spend_ok = self.spend_limit.as_ref().is_none_or(|spend| {
+            spend.used.is_valid() && spend.cap.as_ref().is_none_or(Amount::is_valid)
+        });
+        let finite =
+            self.remaining.is_finite() && self.total.is_none_or(f64::is_finite) && spend_ok;
+
+        if finite {
+            Ok(())
+        } else {
+            Err(CoreError::InvalidData(
+                "credit amounts must be finite".to_string(),
+            ))
+        }
+    }
 }
 
 impl Default for Credits {
@@ -348,20 +734,17 @@ impl UsageData {
     /// Converts to a `UsageSnapshot`.
     pub fn to_snapshot(&self) -> UsageSnapshot {
         let window = self.usage_percentage().map(|pct| UsageWindow {
-            used_percent: pct,
-            window_minutes: None,
             resets_at: self.period_end,
-            reset_description: None,
+            ..UsageWindow::new(pct).with_counts(self.current_usage, self.limit, &self.unit)
         });
 
-        UsageSnapshot {
-            primary: window,
-            secondary: None,
-            tertiary: None,
+        let mut snapshot = UsageSnapshot {
             updated_at: self.fetched_at,
-            identity: None,
             fetch_source: FetchSource::Auto,
-        }
+            ..UsageSnapshot::new()
+        };
+        snapshot.set_primary(window);
+        snapshot
     }
 }
 
@@ -395,10 +778,12 @@ impl Quota {
     /// Converts to a `UsageWindow`.
     pub fn to_window(&self) -> UsageWindow {
         UsageWindow {
-            used_percent: self.usage_percentage(),
-            window_minutes: None,
             resets_at: self.resets_at,
-            reset_description: None,
+            ..UsageWindow::new(self.usage_percentage()).with_counts(
+                self.used,
+                Some(self.total),
+                &self.unit,
+            )
         }
     }
 }
@@ -423,6 +808,15 @@ mod tests {
         assert!(high_window.is_approaching_limit()); // 85% is > 80%
     }
 
+    #[test]
+    fn test_usage_level_thresholds() {
+        assert_eq!(UsageLevel::from_used_percent(49.9), UsageLevel::Good);
+        assert_eq!(UsageLevel::from_used_percent(50.0), UsageLevel::Warning);
+        assert_eq!(UsageLevel::from_used_percent(80.0), UsageLevel::Danger);
+        assert_eq!(UsageLevel::Good.rgb8((1, 2, 3)), (1, 2, 3));
+        assert_eq!(UsageLevel::Danger.as_str(), "danger");
+    }
+
     #[test]
     fn test_usage_window_over_limit() {
         let window = UsageWindow::new(100.0);
@@ -433,14 +827,59 @@ mod tests {
     #[test]
     fn test_usage_snapshot_max_usage() {
         let mut snapshot = UsageSnapshot::new();
-        snapshot.primary = Some(UsageWindow::new(50.0));
-        snapshot.secondary = Some(UsageWindow::new(85.0)); // > 80%
-        snapshot.tertiary = Some(UsageWindow::new(30.0));
+        snapshot.set_primary(Some(UsageWindow::new(50.0)));
+        snapshot.set_secondary(Some(UsageWindow::new(85.0))); // > 80%
+        snapshot.set_tertiary(Some(UsageWindow::new(30.0)));
 
         assert_eq!(snapshot.max_usage_percent(), 85.0);
         assert!(snapshot.is_approaching_limit());
     }
 
+    #[test]
+    fn test_usage_snapshot_slots() {
+        let mut snapshot = UsageSnapshot::new();
+        snapshot.push_window(UsageWindow::named("gpt-5", WindowKind::Model, 10.0));
+        snapshot.set_secondary(Some(UsageWindow::new(20.0)));
+        snapshot.set_primary(Some(UsageWindow::named(
+            "session",
+            WindowKind::Session,
+            30.0,
+        )));
+
+        // Slotted windows come first, in slot order
+        let ids: Vec<_> = snapshot.windows.iter().map(|w| w.id.as_str()).collect();
+        assert_eq!(ids, ["session", "secondary", "gpt-5"]);
+        assert_eq!(snapshot.primary().unwrap().used_percent, 30.0);
+        assert!(snapshot.tertiary().is_none());
+
+        // Replacing keeps position, clearing removes
+        snapshot.set_primary(Some(UsageWindow::new(40.0)));
+        assert_eq!(snapshot.windows[0].used_percent, 40.0);
+        snapshot.set_secondary(None);
+        assert_eq!(snapshot.windows.len(), 2);
+        assert_eq!(snapshot.windows_of_kind(WindowKind::Model).count(), 1);
+    }
+
+    #[test]
+    fn test_window_kind_from_minutes() {
+        assert_eq!(WindowKind::from_minutes(Some(300)), WindowKind::Session);
+        assert_eq!(WindowKind::from_minutes(Some(10080)), WindowKind::Weekly);
+        assert_eq!(WindowKind::from_minutes(Some(43200)), WindowKind::Monthly);
+        assert_eq!(WindowKind::from_minutes(None), WindowKind::Other);
+    }
+
+    #[test]
+    fn test_usage_window_counts() {
+        let window = UsageWindow::named("requests", WindowKind::Monthly, 25.0).with_counts(
+            50.0,
+            Some(200.0),
+            "requests",
+        );
+        assert_eq!(window.remaining(), Some(150.0));
+        assert_eq!(window.unit.as_deref(), Some("requests"));
+        assert_eq!(UsageWindow::new(25.0).remaining(), None);
+    }
+
     #[test]
     fn test_credits_percentage() {
         let mut credits = Credits::new(25.0);
@@ -450,6 +889,38 @@ mod tests {
         assert_eq!(credits.remaining_percent(), Some(25.0));
     }
 
+    #[test]
+    fn test_credits_amounts() {
+        let credits = Credits::from_amount(Amount::new(88.0, "CNY")).with_total(100.0);
+        assert_eq!(credits.unit, "CNY");
+        assert_eq!(credits.remaining_amount(), Amount::new(88.0, "CNY"));
+        assert_eq!(credits.total_amount(), Some(Amount::new(100.0, "CNY")));
+        assert_eq!(Credits::new(5.0).unit, CREDITS_UNIT);
+    }
+
+    #[test]
+    fn test_credits_from_spend() {
+        let capped =
+            Credits::from_spend(SpendLimit::new(Amount::usd(60.0)).with_cap(Amount::usd(50.0)));
+        assert_eq!(capped.remaining_amount(), Amount::usd(0.0));
+        assert_eq!(capped.total, Some(50.0));
+
+        let uncapped = Credits::from_spend(SpendLimit::new(Amount::new(3.0, "EUR")));
+        assert_eq!(uncapped.remaining_amount(), Amount::new(3.0, "EUR"));
+        assert!(uncapped.total.is_none());
+        assert!(uncapped.spend_limit.is_some());
+    }
+
+    #[test]
+    fn test_snapshot_sanitize_drops_invalid_credits() {
+        let mut snapshot = UsageSnapshot::new();
+        snapshot.credits = Some(Credits::new(f64::NAN));
+        assert!(snapshot.validate().is_err());
+
+        snapshot.sanitize();
+        assert!(snapshot.credits.is_none());
+    }
+
     #[test]
     fn test_usage_percentage() {
         let usage = UsageData {
@@ -482,8 +953,8 @@ mod tests {
         };
 
         let snapshot = usage.to_snapshot();
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.unwrap().used_percent, 50.0);
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 50.0);
     }
 
     // ==========================================================================
@@ -535,23 +1006,23 @@ mod tests {
     #[test]
     fn test_usage_snapshot_validate() {
         let mut snapshot = UsageSnapshot::new();
-        snapshot.primary = Some(UsageWindow::new(50.0));
+        snapshot.set_primary(Some(UsageWindow::new(50.0)));
         assert!(snapshot.validate().is_ok());
 
         // Invalid primary
-        snapshot.primary = Some(UsageWindow::new(150.0));
+        snapshot.set_primary(Some(UsageWindow::new(150.0)));
         assert!(snapshot.validate().is_err());
     }
 
     #[test]
     fn test_usage_snapshot_sanitize() {
         let mut snapshot = UsageSnapshot::new();
-        snapshot.primary = Some(UsageWindow::new(150.0));
-        snapshot.secondary = Some(UsageWindow::new(-20.0));
+        snapshot.set_primary(Some(UsageWindow::new(150.0)));
+        snapshot.set_secondary(Some(UsageWindow::new(-20.0)));
 
         snapshot.sanitize();
 
-        assert_eq!(snapshot.primary.as_ref().unwrap().used_percent, 100.0);
-        assert_eq!(snapshot.secondary.as_ref().unwrap().used_percent, 0.0);
+        assert_eq!(snapshot.primary().unwrap().used_percent, 100.0);
+        assert_eq!(snapshot.secondary().unwrap().used_percent, 0.0);
     }
 }
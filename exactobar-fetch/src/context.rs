@@ -3,9 +3,12 @@
 //! The fetch context is passed to all strategies and provides unified
 //! access to system resources like keychain, HTTP client, process runner, etc.
 
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
+use exactobar_core::ProviderKind;
 use tracing::warn;
 
 use crate::host::{
@@ -55,6 +58,21 @@ impl SourceMode {
     }
 }
 
+// ============================================================================
+// Provider Options
+// ============================================================================
+
+/// User-configured options for a single provider.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProviderOptions {
+    /// Monthly spend budget in USD, for providers that report spend.
+    pub monthly_budget: Option<f64>,
+    /// API base URL, for self-hosted providers.
+    pub base_url: Option<String>,
+    /// Cloud resource to read usage for (e.g., an Azure resource ID).
+    pub resource_id: Option<String>,
+}
+
 // ============================================================================
 // Fetch Settings
 // ============================================================================
@@ -72,6 +90,14 @@ pub struct FetchSettings {
     pub max_retries: u32,
     /// Delay between retries.
     pub retry_delay: Duration,
+    /// Whether refreshed OAuth credentials are written back to the file or
+    /// keychain entry they were loaded from.
+    pub persist_refreshed_credentials: bool,
+    /// Whether to merge in extras only web strategies can see (e.g., Claude
+    /// credits and organization). This runs every strategy on each fetch.
+    pub web_extras: bool,
+    /// Per-provider options.
+    pub provider_options: HashMap<ProviderKind, ProviderOptions>,
 }
 
 impl Default for FetchSettings {
@@ -82,6 +108,9 @@ impl Default for FetchSettings {
             web_debug_dump_html: false,
             max_retries: 2,
             retry_delay: Duration::from_secs(1),
+            persist_refreshed_credentials: false,
+            web_extras: false,
+            provider_options: HashMap::new(),
         }
     }
 }
@@ -116,6 +145,65 @@ impl FetchSettings {
     }
 }
 
+// ============================================================================
+// Extensions
+// ============================================================================
+
+/// Typed values attached to a context by embedders.
+///
+/// Lets crates above this one scope their own state, such as a provider
+/// registry, to a context. Holds at most one value per type.
+#[derive(Clone, Default)]
+pub struct Extensions {
+    map: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl Extensions {
+    /// Creates an empty set of extensions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a value, returning the previous value of the same type.
+    pub fn insert<T: Any + Send + Sync>(&mut self, value: Arc<T>) -> Option<Arc<T>> {
+        self.map
+            .insert(TypeId::of::<T>(), value)
+            .and_then(|old| old.downcast().ok())
+    }
+
+    /// Returns the value of the given type.
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        self.map
+            .get(&TypeId::of::<T>())
+            .and_then(|value| Arc::clone(value).downcast().ok())
+    }
+
+    /// Removes and returns the value of the given type.
+    pub fn remove<T: Any + Send + Sync>(&mut self) -> Option<Arc<T>> {
+        self.map
+            .remove(&TypeId::of::<T>())
+            .and_then(|old| old.downcast().ok())
+    }
+
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if no values are attached.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl std::fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Extensions")
+            .field("len", &self.map.len())
+            .finish()
+    }
+}
+
 // ============================================================================
 // Fetch Context
 // ============================================================================
@@ -128,6 +216,7 @@ impl FetchSettings {
 /// - Process runner for CLI commands
 /// - Browser cookie importer for web strategies
 /// - Status poller for health checks
+/// - Extensions for embedder state
 pub struct FetchContext {
     /// Secure credential storage.
     pub keychain: Arc<dyn KeychainApi>,
@@ -141,6 +230,8 @@ pub struct FetchContext {
     pub status: Arc<StatusPoller>,
     /// Fetch settings.
     pub settings: FetchSettings,
+    /// Embedder state scoped to this context.
+    pub extensions: Extensions,
 }
 
 impl FetchContext {
@@ -166,6 +257,7 @@ impl FetchContext {
             browser: Arc::new(BrowserCookieImporter::new()),
             status: Arc::new(StatusPoller::new()),
             settings,
+            extensions: Extensions::new(),
         }
     }
 
@@ -179,6 +271,15 @@ impl FetchContext {
         self.settings.timeout
     }
 
+    /// Returns the options for a provider (defaults if none are set).
+    pub fn provider_options(&self, provider: ProviderKind) -> ProviderOptions {
+        self.settings
+            .provider_options
+            .get(&provider)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     /// Returns true if the given source mode is allowed.
     pub fn allows_source(&self, mode: SourceMode) -> bool {
         self.settings.source_mode == SourceMode::Auto || self.settings.source_mode == mode
@@ -211,6 +312,7 @@ pub struct FetchContextBuilder {
     browser: Option<Arc<BrowserCookieImporter>>,
     status: Option<Arc<StatusPoller>>,
     settings: FetchSettings,
+    extensions: Extensions,
 }
 
 impl FetchContextBuilder {
@@ -223,6 +325,7 @@ impl FetchContextBuilder {
             browser: None,
             status: None,
             settings: FetchSettings::default(),
+            extensions: Extensions::new(),
         }
     }
 
@@ -274,6 +377,30 @@ impl FetchContextBuilder {
         self
     }
 
+    /// Sets whether refreshed OAuth credentials are written back.
+    pub fn persist_refreshed_credentials(mut self, persist: bool) -> Self {
+        self.settings.persist_refreshed_credentials = persist;
+        self
+    }
+
+    /// Sets whether web-only extras are merged into snapshots.
+    pub fn web_extras(mut self, enabled: bool) -> Self {
+        self.settings.web_extras = enabled;
+        self
+    }
+
+    /// Sets the per-provider options.
+    pub fn provider_options(mut self, options: HashMap<ProviderKind, ProviderOptions>) -> Self {
+        self.settings.provider_options = options;
+        self
+    }
+
+    /// Attaches an extension value.
+    pub fn extension<T: Any + Send + Sync>(mut self, value: Arc<T>) -> Self {
+        self.extensions.insert(value);
+        self
+    }
+
     /// Builds the fetch context.
     pub fn build(self) -> FetchContext {
         FetchContext {
@@ -289,6 +416,7 @@ impl FetchContextBuilder {
                 .unwrap_or_else(|| Arc::new(BrowserCookieImporter::new())),
             status: self.status.unwrap_or_else(|| Arc::new(StatusPoller::new())),
             settings: self.settings,
+            extensions: self.extensions,
         }
     }
 }
@@ -332,6 +460,27 @@ mod tests {
         assert_eq!(ctx.settings.timeout, Duration::from_secs(60));
     }
 
+    #[test]
+    fn test_extensions() {
+        struct Scope(&'static str);
+
+        let ctx = FetchContext::builder()
+            .extension(Arc::new(Scope("team")))
+            .extension(Arc::new(7_u32))
+            .build();
+        assert_eq!(ctx.extensions.len(), 2);
+        assert_eq!(ctx.extensions.get::<Scope>().unwrap().0, "team");
+        assert_eq!(*ctx.extensions.get::<u32>().unwrap(), 7);
+        assert!(ctx.extensions.get::<String>().is_none());
+
+        let mut extensions = ctx.extensions.clone();
+        let old = extensions.insert(Arc::new(Scope("org"))).unwrap();
+        assert_eq!(old.0, "team");
+        assert_eq!(extensions.remove::<Scope>().unwrap().0, "org");
+        assert!(extensions.get::<Scope>().is_none());
+        assert!(FetchContext::new().extensions.is_empty());
+    }
+
     #[test]
     fn test_default_context() {
         let ctx = FetchContext::new();
@@ -1,5 +1,7 @@
 //! Fetch error types.
 
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::time::Duration;
 use thiserror::Error;
 
@@ -74,6 +76,88 @@ pub enum FetchError {
     DomainNotAllowed(String),
 }
 
+impl FetchError {
+    /// Returns the broad category of this error.
+    pub fn kind(&self) -> FetchErrorKind {
+        match self {
+            Self::Timeout(_)
+            | Self::Process(ProcessError::Timeout(_))
+            | Self::Pty(PtyError::Timeout(_) | PtyError::IdleTimeout(_)) => FetchErrorKind::Timeout,
+            Self::Http(e) if e.is_timeout() => FetchErrorKind::Timeout,
+            Self::Http(e)
+                if e.status().is_some_and(|s| {
+                    s == reqwest::StatusCode::UNAUTHORIZED || s == reqwest::StatusCode::FORBIDDEN
+                }) =>
+            {
+                FetchErrorKind::Auth
+            }
+            Self::AuthenticationFailed(_)
+            | Self::Keychain(KeychainError::NotFound { .. } | KeychainError::AccessDenied) => {
+                FetchErrorKind::Auth
+            }
+            Self::Process(ProcessError::NotFound(_)) | Self::Pty(PtyError::NotFound(_)) => {
+                FetchErrorKind::ProviderMissing
+            }
+            _ => FetchErrorKind::Failed,
+        }
+    }
+}
+
+// ============================================================================
+// Fetch Failure
+// ============================================================================
+
+/// Broad category of a failed fetch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FetchErrorKind {
+    /// Credentials are missing or were rejected.
+    Auth,
+    /// The fetch timed out.
+    Timeout,
+    /// The provider isn't registered or its tool isn't installed.
+    ProviderMissing,
+    /// Any other failure.
+    #[default]
+    Failed,
+}
+
+/// A fetch error flattened for display and transport, keeping its kind.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FetchFailure {
+    /// Broad category of the error.
+    pub kind: FetchErrorKind,
+    /// Error message.
+    pub message: String,
+}
+
+impl FetchFailure {
+    /// Creates a failure.
+    pub fn new(kind: FetchErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// Creates a failure for a provider that isn't registered.
+    pub fn provider_missing(message: impl Into<String>) -> Self {
+        Self::new(FetchErrorKind::ProviderMissing, message)
+    }
+}
+
+impl From<FetchError> for FetchFailure {
+    fn from(error: FetchError) -> Self {
+        Self::new(error.kind(), error.to_string())
+    }
+}
+
+impl fmt::Display for FetchFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
 // ============================================================================
 // HTTP Error
 // ============================================================================
@@ -103,6 +103,40 @@ pub fn get_password_cached(service: &str, account: &str) -> Option<String> {
     result
 }
 
+/// Write a password to the keychain and update the cache.
+///
+/// Unlike [`SystemKeychain`], the service name is used as-is, so this can
+/// update entries owned by other tools (e.g., a CLI's OAuth credentials).
+///
+/// # Errors
+///
+/// Returns an error if the keychain entry cannot be created or written.
+///
+/// # Panics
+///
+/// Panics if the cache mutex is poisoned.
+pub fn set_password_cached(
+    service: &str,
+    account: &str,
+    secret: &str,
+) -> Result<(), KeychainError> {
+    let entry = Entry::new(service, account).map_err(|e| KeychainError::Platform(e.to_string()))?;
+
+    entry.set_password(secret).map_err(|e| {
+        warn!(service = %service, account = %account, error = %e, "Failed to write password to keychain");
+        KeychainError::from(e)
+    })?;
+
+    let cache_key = format!("{service}:{account}");
+    get_cache()
+        .lock()
+        .unwrap()
+        .insert(cache_key, Some(secret.to_string()));
+
+    debug!(service = %service, account = %account, "Wrote password to keychain");
+    Ok(())
+}
+
 /// Invalidate a specific cache entry.
 ///
 /// Call this when you know a credential has changed (e.g., after storing a new value).
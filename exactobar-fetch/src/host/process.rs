@@ -133,8 +133,11 @@ impl ProcessRunner {
         let mut command = Command::new(&cmd_path);
         command
             .args(args)
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+            .stderr(Stdio::piped())
+            // Don't leave timed-out children running
+            .kill_on_drop(true);
 
         // Add environment variables
         for (key, value) in env {
@@ -65,7 +65,8 @@ pub mod strategy;
 
 // Errors
 pub use error::{
-    BrowserError, FetchError, HttpError, KeychainError, ProcessError, PtyError, StatusError,
+    BrowserError, FetchError, FetchErrorKind, FetchFailure, HttpError, KeychainError, ProcessError,
+    PtyError, StatusError,
 };
 
 // Host APIs
@@ -79,9 +80,11 @@ pub use host::{
 };
 
 // Strategy & Pipeline
-pub use context::{FetchContext, FetchContextBuilder, FetchSettings, SourceMode};
-pub use pipeline::{FetchAttempt, FetchOutcome, FetchPipeline};
-pub use strategy::{FetchKind, FetchResult, FetchStrategy, StrategyInfo};
+pub use context::{
+    Extensions, FetchContext, FetchContextBuilder, FetchSettings, ProviderOptions, SourceMode,
+};
+pub use pipeline::{FetchAttempt, FetchOutcome, FetchPipeline, PipelineMode};
+pub use strategy::{FetchKind, FetchResult, FetchStrategy, SnapshotField, StrategyInfo};
 
 // Legacy exports (for compatibility)
 pub use client::HttpClient as LegacyHttpClient;
@@ -1,14 +1,16 @@
 //! Fetch pipeline for executing strategies in order.
 //!
 //! The pipeline takes a list of fetch strategies and executes them in
-//! priority order until one succeeds.
+//! priority order until one succeeds. In [`PipelineMode::Merge`], it keeps
+//! going and combines partial snapshots field by field instead.
 
+use exactobar_core::ProviderIdentity;
 use std::time::{Duration, Instant};
 use tracing::{debug, info, instrument, warn};
 
 use crate::context::FetchContext;
 use crate::error::FetchError;
-use crate::strategy::{FetchKind, FetchResult, FetchStrategy};
+use crate::strategy::{FetchKind, FetchResult, FetchStrategy, SnapshotField};
 
 // ============================================================================
 // Fetch Attempt
@@ -98,6 +100,27 @@ impl FetchOutcome {
     }
 }
 
+// ============================================================================
+// Pipeline Mode
+// ============================================================================
+
+/// How the pipeline combines strategy results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PipelineMode {
+    /// Return the first successful result.
+    #[default]
+    FirstSuccess,
+    /// Keep fetching and merge results field by field.
+    ///
+    /// Each field is taken from the highest-priority strategy that is
+    /// authoritative for it (see [`FetchStrategy::authoritative_fields`]),
+    /// falling back to any strategy that returned a value. Execution stops
+    /// once every field has an authoritative value, and last-resort
+    /// strategies (see [`FetchStrategy::is_last_resort`]) are skipped once
+    /// any data has been fetched.
+    Merge,
+}
+
 // ============================================================================
 // Fetch Pipeline
 // ============================================================================
@@ -108,6 +131,7 @@ impl FetchOutcome {
 /// Strategies can opt out of fallback on certain errors.
 pub struct FetchPipeline {
     strategies: Vec<Box<dyn FetchStrategy>>,
+    mode: PipelineMode,
 }
 
 impl FetchPipeline {
@@ -115,22 +139,51 @@ impl FetchPipeline {
     pub fn new() -> Self {
         Self {
             strategies: Vec::new(),
+            mode: PipelineMode::default(),
         }
     }
 
     /// Creates a pipeline with the given strategies.
     pub fn with_strategies(strategies: Vec<Box<dyn FetchStrategy>>) -> Self {
-        let mut pipeline = Self { strategies };
+        let mut pipeline = Self {
+            strategies,
+            mode: PipelineMode::default(),
+        };
         pipeline.sort_by_priority();
         pipeline
     }
 
+    /// Sets the pipeline mode.
+    pub fn with_mode(mut self, mode: PipelineMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Returns the pipeline mode.
+    pub fn mode(&self) -> PipelineMode {
+        self.mode
+    }
+
     /// Adds a strategy to the pipeline.
     pub fn add_strategy(&mut self, strategy: Box<dyn FetchStrategy>) {
         self.strategies.push(strategy);
         self.sort_by_priority();
     }
 
+    /// Removes the strategy with the given ID.
+    ///
+    /// Returns true if a strategy was removed.
+    pub fn remove_strategy(&mut self, id: &str) -> bool {
+        let len = self.strategies.len();
+        self.strategies.retain(|s| s.id() != id);
+        self.strategies.len() != len
+    }
+
+    /// Returns the strategy IDs, in priority order.
+    pub fn strategy_ids(&self) -> Vec<&str> {
+        self.strategies.iter().map(|s| s.id()).collect()
+    }
+
     /// Sorts strategies by priority (highest first).
     fn sort_by_priority(&mut self) {
         self.strategies
@@ -157,8 +210,14 @@ impl FetchPipeline {
     }
 
     /// Execute the pipeline, trying strategies in order until one succeeds.
+    ///
+    /// In [`PipelineMode::Merge`] this delegates to [`Self::execute_merge`].
     #[instrument(skip(self, ctx), fields(strategies = self.strategies.len()))]
     pub async fn execute(&self, ctx: &FetchContext) -> FetchOutcome {
+        if self.mode == PipelineMode::Merge {
+            return self.execute_merge(ctx).await;
+        }
+
         let start = Instant::now();
         let mut attempts = Vec::new();
 
@@ -319,6 +378,227 @@ impl FetchPipeline {
             duration: start.elapsed(),
         }
     }
+
+    /// Execute all strategies and merge their snapshots field by field.
+    ///
+    /// Stops early once every field has a value from an authoritative
+    /// strategy, or when a strategy fails without allowing fallback.
+    pub async fn execute_merge(&self, ctx: &FetchContext) -> FetchOutcome {
+        let start = Instant::now();
+        let mut attempts = Vec::new();
+
+        if self.strategies.is_empty() {
+            return FetchOutcome {
+                result: Err(FetchError::StrategyNotAvailable(
+                    "No strategies configured".to_string(),
+                )),
+                attempts,
+                duration: start.elapsed(),
+            };
+        }
+
+        info!(
+            count = self.strategies.len(),
+            "Executing fetch pipeline (merge)"
+        );
+
+        let mut results: Vec<(FetchResult, &[SnapshotField])> = Vec::new();
+        let mut stop_error = None;
+
+        for strategy in &self.strategies {
+            let strategy_id = strategy.id();
+            let kind = strategy.kind();
+
+            if strategy.is_last_resort() && !results.is_empty() {
+                debug!(strategy = %strategy_id, "Data already fetched, skipping last resort");
+                continue;
+            }
+
+            if !strategy.is_available(ctx).await {
+                debug!(strategy = %strategy_id, "Strategy not available, skipping");
+                attempts.push(FetchAttempt::failure(
+                    strategy_id,
+                    kind,
+                    "Not available",
+                    Duration::ZERO,
+                ));
+                continue;
+            }
+
+            let attempt_start = Instant::now();
+            match strategy.fetch(ctx).await {
+                Ok(result) => {
+                    let duration = attempt_start.elapsed();
+                    debug!(strategy = %strategy_id, duration = ?duration, "Strategy succeeded");
+                    attempts.push(FetchAttempt::success(strategy_id, kind, duration));
+                    results.push((result, strategy.authoritative_fields()));
+
+                    if is_fully_covered(&results) {
+                        debug!("All fields covered by authoritative strategies");
+                        break;
+                    }
+                }
+                Err(error) => {
+                    let duration = attempt_start.elapsed();
+                    warn!(
+                        strategy = %strategy_id,
+                        error = %error,
+                        duration = ?duration,
+                        "Strategy failed"
+                    );
+                    attempts.push(FetchAttempt::failure(
+                        strategy_id,
+                        kind,
+                        error.to_string(),
+                        duration,
+                    ));
+
+                    if !strategy.should_fallback(&error) {
+                        debug!(strategy = %strategy_id, "Strategy indicates no fallback");
+                        stop_error = Some(error);
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Partial data beats an error; otherwise surface a no-fallback
+        // error as-is, like `execute` does.
+        let result = if !results.is_empty() {
+            Ok(merge_results(&results))
+        } else if let Some(error) = stop_error {
+            Err(error)
+        } else {
+            warn!("All strategies failed");
+            Err(FetchError::AllStrategiesFailed)
+        };
+
+        FetchOutcome {
+            result,
+            attempts,
+            duration: start.elapsed(),
+        }
+    }
+}
+
+/// Returns true if every field has a value from an authoritative strategy.
+fn is_fully_covered(results: &[(FetchResult, &[SnapshotField])]) -> bool {
+    SnapshotField::ALL.iter().all(|field| {
+        results
+            .iter()
+            .any(|(r, auth)| auth.contains(field) && field.is_present(&r.snapshot))
+    })
+}
+
+/// Merges results (in priority order) field by field.
+///
+/// The merged result carries the ID, kind and metadata of the first
+/// (highest-priority) result.
+fn merge_results(results: &[(FetchResult, &[SnapshotField])]) -> FetchResult {
+    let mut merged = results[0].0.clone();
+    merged.provenance.clear();
+    merged.window_provenance.clear();
+
+    for field in SnapshotField::ALL {
+        let source = results
+            .iter()
+            .find(|(r, auth)| auth.contains(field) && field.is_present(&r.snapshot))
+            .or_else(|| results.iter().find(|(r, _)| field.is_present(&r.snapshot)));
+
+        if let Some((result, _)) = source {
+            field.copy_into(&result.snapshot, &mut merged.snapshot);
+            merged.provenance.insert(*field, result.strategy_id.clone());
+        }
+    }
+
+    merge_named_windows(results, &mut merged);
+
+    // Identity fields come from different places (OAuth knows the plan,
+    // the CLI and web know the account), so fill gaps from the others
+    if let Some(identity) = merged.snapshot.identity.as_mut() {
+        for (result, _) in results {
+            if let Some(other) = &result.snapshot.identity {
+                fill_identity(identity, other);
+            }
+        }
+    }
+
+    // Cost reports are whole documents; take the first one
+    if merged.snapshot.cost.is_none() {
+        merged.snapshot.cost = results.iter().find_map(|(r, _)| r.snapshot.cost.clone());
+    }
+
+    merged
+}
+
+/// Merges unslotted windows by ID and records where every window came from.
+///
+/// Named windows have no [`SnapshotField`] of their own, so each is taken
+/// from the highest-priority strategy authoritative for windows that has it,
+/// falling back to any strategy that does. New IDs are appended in the
+/// order strategies returned them.
+fn merge_named_windows(results: &[(FetchResult, &[SnapshotField])], merged: &mut FetchResult) {
+    let mut ids: Vec<&str> = Vec::new();
+    for (result, _) in results {
+        for window in result.snapshot.windows.iter().filter(|w| w.slot.is_none()) {
+            if !ids.contains(&window.id.as_str()) {
+                ids.push(&window.id);
+            }
+        }
+    }
+
+    for id in ids {
+        // A slotted window already owns this ID
+        if merged.snapshot.window(id).is_some_and(|w| w.slot.is_some()) {
+            continue;
+        }
+
+        let source = results
+            .iter()
+            .filter(|(_, auth)| auth.iter().any(|f| f.slot().is_some()))
+            .chain(results)
+            .find_map(|(r, _)| {
+                r.snapshot
+                    .window(id)
+                    .filter(|w| w.slot.is_none())
+                    .map(|w| (r, w))
+            });
+
+        if let Some((result, window)) = source {
+            merged.snapshot.push_window(window.clone());
+            merged
+                .window_provenance
+                .insert(id.to_string(), result.strategy_id.clone());
+        }
+    }
+
+    for field in SnapshotField::WINDOWS {
+        if let (Some(slot), Some(source)) = (field.slot(), merged.provenance.get(field)) {
+            if let Some(window) = merged.snapshot.slot(slot) {
+                merged
+                    .window_provenance
+                    .insert(window.id.clone(), source.clone());
+            }
+        }
+    }
+}
+
+/// Fills fields missing from `identity` with values from `other`.
+fn fill_identity(identity: &mut ProviderIdentity, other: &ProviderIdentity) {
+    if identity.account_email.is_none() {
+        identity.account_email.clone_from(&other.account_email);
+    }
+    if identity.account_organization.is_none() {
+        identity
+            .account_organization
+            .clone_from(&other.account_organization);
+    }
+    if identity.plan_name.is_none() {
+        identity.plan_name.clone_from(&other.plan_name);
+    }
+    if identity.login_method.is_none() {
+        identity.login_method = other.login_method;
+    }
 }
 
 impl Default for FetchPipeline {
@@ -335,7 +615,7 @@ impl Default for FetchPipeline {
 mod tests {
     use super::*;
     use async_trait::async_trait;
-    use exactobar_core::UsageSnapshot;
+    use exactobar_core::{Credits, ProviderKind, UsageSnapshot, UsageWindow, WindowKind};
 
     struct MockSuccessStrategy {
         id: String,
@@ -433,6 +713,91 @@ mod tests {
         }
     }
 
+    struct MockPartialStrategy {
+        id: String,
+        priority: u32,
+        snapshot: UsageSnapshot,
+        authoritative: &'static [SnapshotField],
+        last_resort: bool,
+    }
+
+    impl MockPartialStrategy {
+        fn new(id: &str, priority: u32, authoritative: &'static [SnapshotField]) -> Self {
+            Self {
+                id: id.to_string(),
+                priority,
+                snapshot: UsageSnapshot::new(),
+                authoritative,
+                last_resort: false,
+            }
+        }
+
+        fn last_resort(mut self) -> Self {
+            self.last_resort = true;
+            self
+        }
+
+        fn with_primary(mut self, used_percent: f64) -> Self {
+            self.snapshot
+                .set_primary(Some(UsageWindow::new(used_percent)));
+            self
+        }
+
+        fn with_named(mut self, id: &str, used_percent: f64) -> Self {
+            self.snapshot
+                .push_window(UsageWindow::new(used_percent).with_id(id, WindowKind::Model));
+            self
+        }
+
+        fn with_identity(mut self) -> Self {
+            self.snapshot.identity = Some(ProviderIdentity::new(ProviderKind::Claude));
+            self
+        }
+
+        fn with_account(mut self, email: Option<&str>, plan: Option<&str>) -> Self {
+            let mut identity = ProviderIdentity::new(ProviderKind::Claude);
+            identity.account_email = email.map(str::to_string);
+            identity.plan_name = plan.map(str::to_string);
+            self.snapshot.identity = Some(identity);
+            self
+        }
+    }
+
+    #[async_trait]
+    impl FetchStrategy for MockPartialStrategy {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn kind(&self) -> FetchKind {
+            FetchKind::OAuth
+        }
+
+        async fn is_available(&self, _ctx: &FetchContext) -> bool {
+            true
+        }
+
+        async fn fetch(&self, _ctx: &FetchContext) -> Result<FetchResult, FetchError> {
+            Ok(FetchResult::new(
+                self.snapshot.clone(),
+                self.id.clone(),
+                self.kind(),
+            ))
+        }
+
+        fn priority(&self) -> u32 {
+            self.priority
+        }
+
+        fn authoritative_fields(&self) -> &[SnapshotField] {
+            self.authoritative
+        }
+
+        fn is_last_resort(&self) -> bool {
+            self.last_resort
+        }
+    }
+
     #[tokio::test]
     async fn test_empty_pipeline() {
         let pipeline = FetchPipeline::new();
@@ -508,4 +873,226 @@ mod tests {
         assert!(outcome.is_success());
         assert_eq!(outcome.successful_strategy(), Some("test.available"));
     }
+
+    #[test]
+    fn test_remove_strategy() {
+        let mut pipeline = FetchPipeline::with_strategies(vec![
+            Box::new(MockSuccessStrategy::new("test.low", true).with_priority(50)),
+            Box::new(MockSuccessStrategy::new("test.high", true).with_priority(100)),
+        ]);
+        assert_eq!(pipeline.strategy_ids(), vec!["test.high", "test.low"]);
+
+        assert!(pipeline.remove_strategy("test.high"));
+        assert!(!pipeline.remove_strategy("test.high"));
+        assert_eq!(pipeline.strategy_ids(), vec!["test.low"]);
+    }
+
+    #[tokio::test]
+    async fn test_merge_combines_fields() {
+        let pipeline = FetchPipeline::with_strategies(vec![
+            Box::new(
+                MockPartialStrategy::new("test.oauth", 100, SnapshotField::WINDOWS)
+                    .with_primary(20.0),
+            ),
+            Box::new(MockPartialStrategy::new("test.web", 50, SnapshotField::ALL).with_identity()),
+        ])
+        .with_mode(PipelineMode::Merge);
+
+        let ctx = FetchContext::new();
+        let outcome = pipeline.execute(&ctx).await;
+
+        let result = outcome.result.unwrap();
+        assert_eq!(result.strategy_id, "test.oauth");
+        assert!(result.snapshot.primary().is_some());
+        assert!(result.snapshot.identity.is_some());
+        assert_eq!(result.source_of(SnapshotField::Primary), Some("test.oauth"));
+        assert_eq!(result.source_of(SnapshotField::Identity), Some("test.web"));
+        assert_eq!(result.source_of(SnapshotField::Secondary), None);
+    }
+
+    #[tokio::test]
+    async fn test_merge_combines_named_windows() {
+        let pipeline = FetchPipeline::with_strategies(vec![
+            Box::new(
+                MockPartialStrategy::new("test.oauth", 100, SnapshotField::WINDOWS)
+                    .with_primary(20.0)
+                    .with_named("seven_day_sonnet", 30.0),
+            ),
+            Box::new(
+                MockPartialStrategy::new("test.web", 50, &[SnapshotField::Identity])
+                    .with_named("seven_day_sonnet", 99.0)
+                    .with_named("extra_usage", 40.0),
+            ),
+        ])
+        .with_mode(PipelineMode::Merge);
+
+        let ctx = FetchContext::new();
+        let result = pipeline.execute(&ctx).await.result.unwrap();
+
+        let ids: Vec<&str> = result
+            .snapshot
+            .windows
+            .iter()
+            .map(|w| w.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["primary", "seven_day_sonnet", "extra_usage"]);
+        let sonnet = result.snapshot.window("seven_day_sonnet").unwrap();
+        assert!((sonnet.used_percent - 30.0).abs() < f64::EPSILON);
+        assert_eq!(result.window_source_of("primary"), Some("test.oauth"));
+        assert_eq!(
+            result.window_source_of("seven_day_sonnet"),
+            Some("test.oauth")
+        );
+        assert_eq!(result.window_source_of("extra_usage"), Some("test.web"));
+        assert_eq!(result.window_source_of("missing"), None);
+    }
+
+    #[tokio::test]
+    async fn test_merge_fills_identity_fields() {
+        // OAuth knows the plan tier, the CLI knows the account
+        let pipeline = FetchPipeline::with_strategies(vec![
+            Box::new(
+                MockPartialStrategy::new("test.oauth", 100, SnapshotField::WINDOWS)
+                    .with_primary(20.0)
+                    .with_account(None, Some("Max 20x")),
+            ),
+            Box::new(
+                MockPartialStrategy::new("test.cli", 80, &[SnapshotField::Identity])
+                    .with_account(Some("user@example.com"), None),
+            ),
+        ])
+        .with_mode(PipelineMode::Merge);
+
+        let ctx = FetchContext::new();
+        let result = pipeline.execute(&ctx).await.result.unwrap();
+
+        let identity = result.snapshot.identity.as_ref().unwrap();
+        assert_eq!(identity.account_email.as_deref(), Some("user@example.com"));
+        assert_eq!(identity.plan_name.as_deref(), Some("Max 20x"));
+        assert_eq!(result.source_of(SnapshotField::Identity), Some("test.cli"));
+    }
+
+    #[tokio::test]
+    async fn test_merge_prefers_authoritative_value() {
+        // Higher priority but not authoritative for the primary window
+        let pipeline = FetchPipeline::with_strategies(vec![
+            Box::new(
+                MockPartialStrategy::new("test.cli", 100, &[SnapshotField::Identity])
+                    .with_primary(90.0),
+            ),
+            Box::new(
+                MockPartialStrategy::new("test.oauth", 50, SnapshotField::WINDOWS)
+                    .with_primary(10.0),
+            ),
+        ])
+        .with_mode(PipelineMode::Merge);
+
+        let ctx = FetchContext::new();
+        let result = pipeline.execute(&ctx).await.result.unwrap();
+
+        let primary = result.snapshot.primary().unwrap();
+        assert!((primary.used_percent - 10.0).abs() < f64::EPSILON);
+        assert_eq!(
+            result
+                .provenance
+                .get(&SnapshotField::Primary)
+                .map(String::as_str),
+            Some("test.oauth")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_merge_stops_when_covered() {
+        let mut full = MockPartialStrategy::new("test.full", 100, SnapshotField::ALL)
+            .with_primary(10.0)
+            .with_identity();
+        full.snapshot.set_secondary(Some(UsageWindow::new(20.0)));
+        full.snapshot.set_tertiary(Some(UsageWindow::new(30.0)));
+        full.snapshot.credits = Some(Credits::new(5.0));
+
+        let pipeline = FetchPipeline::with_strategies(vec![
+            Box::new(full),
+            Box::new(MockSuccessStrategy::new("test.never", true).with_priority(10)),
+        ])
+        .with_mode(PipelineMode::Merge);
+
+        let ctx = FetchContext::new();
+        let outcome = pipeline.execute(&ctx).await;
+
+        assert!(outcome.is_success());
+        assert_eq!(outcome.attempts_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_merge_skips_last_resort_after_data() {
+        let pipeline = FetchPipeline::with_strategies(vec![
+            Box::new(
+                MockPartialStrategy::new("test.oauth", 100, SnapshotField::WINDOWS)
+                    .with_primary(20.0),
+            ),
+            Box::new(
+                MockPartialStrategy::new("test.pty", 40, SnapshotField::ALL)
+                    .with_identity()
+                    .last_resort(),
+            ),
+        ])
+        .with_mode(PipelineMode::Merge);
+
+        let ctx = FetchContext::new();
+        let outcome = pipeline.execute(&ctx).await;
+
+        assert_eq!(outcome.attempts_count(), 1);
+        assert!(outcome.result.unwrap().snapshot.identity.is_none());
+
+        // With nothing fetched yet, the last resort still runs
+        let pipeline = FetchPipeline::with_strategies(vec![
+            Box::new(MockFailStrategy::new("test.oauth", true).with_priority(100)),
+            Box::new(
+                MockPartialStrategy::new("test.pty", 40, SnapshotField::ALL)
+                    .with_primary(30.0)
+                    .last_resort(),
+            ),
+        ])
+        .with_mode(PipelineMode::Merge);
+
+        let result = pipeline.execute(&ctx).await.result.unwrap();
+        assert_eq!(result.strategy_id, "test.pty");
+    }
+
+    #[tokio::test]
+    async fn test_merge_keeps_partial_data_on_failure() {
+        let pipeline = FetchPipeline::with_strategies(vec![
+            Box::new(
+                MockPartialStrategy::new("test.oauth", 100, SnapshotField::WINDOWS)
+                    .with_primary(20.0),
+            ),
+            Box::new(MockFailStrategy::new("test.fail", false).with_priority(50)),
+        ])
+        .with_mode(PipelineMode::Merge);
+
+        let ctx = FetchContext::new();
+        let outcome = pipeline.execute(&ctx).await;
+
+        assert!(outcome.is_success());
+        assert_eq!(outcome.attempts_count(), 2);
+        assert_eq!(outcome.errors().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_merge_no_fallback_error_surfaces() {
+        let pipeline = FetchPipeline::with_strategies(vec![
+            Box::new(MockFailStrategy::new("test.fail", false).with_priority(100)),
+            Box::new(MockSuccessStrategy::new("test.success", true).with_priority(50)),
+        ])
+        .with_mode(PipelineMode::Merge);
+
+        let ctx = FetchContext::new();
+        let outcome = pipeline.execute(&ctx).await;
+
+        assert!(matches!(
+            outcome.result,
+            Err(FetchError::InvalidResponse(_))
+        ));
+        assert_eq!(outcome.attempts_count(), 1);
+    }
 }
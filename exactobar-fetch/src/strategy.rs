@@ -5,8 +5,9 @@
 //! that are tried in priority order.
 
 use async_trait::async_trait;
-use exactobar_core::{FetchSource, UsageSnapshot};
+use exactobar_core::{FetchSource, UsageSnapshot, WindowSlot};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
 
 use crate::context::FetchContext;
@@ -65,6 +66,94 @@ impl fmt::Display for FetchKind {
     }
 }
 
+// ============================================================================
+// Snapshot Field
+// ============================================================================
+
+/// A field of [`UsageSnapshot`] that a strategy can be authoritative for.
+///
+/// Used by the merge pipeline mode to combine partial snapshots from
+/// several strategies field by field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotField {
+    /// Primary window (usually session).
+    Primary,
+    /// Secondary window (usually weekly).
+    Secondary,
+    /// Tertiary window (usually model-specific).
+    Tertiary,
+    /// Account identity (email, organization, plan).
+    Identity,
+    /// Credit balance and spend.
+    Credits,
+}
+
+impl SnapshotField {
+    /// All snapshot fields.
+    pub const ALL: &'static [SnapshotField] = &[
+        Self::Primary,
+        Self::Secondary,
+        Self::Tertiary,
+        Self::Identity,
+        Self::Credits,
+    ];
+
+    /// The usage window fields.
+    pub const WINDOWS: &'static [SnapshotField] = &[Self::Primary, Self::Secondary, Self::Tertiary];
+
+    /// Returns the field name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Primary => "primary",
+            Self::Secondary => "secondary",
+            Self::Tertiary => "tertiary",
+            Self::Identity => "identity",
+            Self::Credits => "credits",
+        }
+    }
+
+    /// Returns the window slot for window fields.
+    pub fn slot(&self) -> Option<WindowSlot> {
+        match self {
+            Self::Primary => Some(WindowSlot::Primary),
+            Self::Secondary => Some(WindowSlot::Secondary),
+            Self::Tertiary => Some(WindowSlot::Tertiary),
+            Self::Identity | Self::Credits => None,
+        }
+    }
+
+    /// Returns true if the snapshot has a value for this field.
+    pub fn is_present(&self, snapshot: &UsageSnapshot) -> bool {
+        match self {
+            Self::Identity => snapshot.identity.is_some(),
+            Self::Credits => snapshot.credits.is_some(),
+            Self::Primary | Self::Secondary | Self::Tertiary => self
+                .slot()
+                .is_some_and(|slot| snapshot.slot(slot).is_some()),
+        }
+    }
+
+    /// Copies this field from `source` into `target`.
+    pub fn copy_into(&self, source: &UsageSnapshot, target: &mut UsageSnapshot) {
+        match self {
+            Self::Identity => target.identity.clone_from(&source.identity),
+            Self::Credits => target.credits.clone_from(&source.credits),
+            Self::Primary | Self::Secondary | Self::Tertiary => {
+                if let Some(slot) = self.slot() {
+                    target.set_slot(slot, source.slot(slot).cloned());
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for SnapshotField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
 // ============================================================================
 // Fetch Result
 // ============================================================================
@@ -78,6 +167,14 @@ pub struct FetchResult {
     pub strategy_id: String,
     /// The kind of fetch used.
     pub kind: FetchKind,
+    /// Per-field provenance for merged results (field -> strategy ID).
+    ///
+    /// Empty unless the result was produced by a merging pipeline.
+    pub provenance: HashMap<SnapshotField, String>,
+    /// Per-window provenance for merged results (window ID -> strategy ID).
+    ///
+    /// Covers named windows as well as the slotted ones.
+    pub window_provenance: HashMap<String, String>,
 }
 
 impl FetchResult {
@@ -87,7 +184,34 @@ impl FetchResult {
             snapshot,
             strategy_id: strategy_id.into(),
             kind,
+            provenance: HashMap::new(),
+            window_provenance: HashMap::new(),
+        }
+    }
+
+    /// Returns the strategy that supplied the given field.
+    ///
+    /// Falls back to [`Self::strategy_id`] when no provenance was recorded.
+    pub fn source_of(&self, field: SnapshotField) -> Option<&str> {
+        if let Some(id) = self.provenance.get(&field) {
+            return Some(id);
         }
+        field
+            .is_present(&self.snapshot)
+            .then_some(self.strategy_id.as_str())
+    }
+
+    /// Returns the strategy that supplied the window with the given ID.
+    ///
+    /// Falls back to [`Self::strategy_id`] when no provenance was recorded.
+    pub fn window_source_of(&self, id: &str) -> Option<&str> {
+        if let Some(source) = self.window_provenance.get(id) {
+            return Some(source);
+        }
+        self.snapshot
+            .window(id)
+            .is_some()
+            .then_some(self.strategy_id.as_str())
     }
 }
 
@@ -185,6 +309,28 @@ pub trait FetchStrategy: Send + Sync {
             FetchKind::LocalProbe => 10,
         }
     }
+
+    /// Whether this strategy only runs as a last resort when merging.
+    ///
+    /// Pipelines in [`PipelineMode::Merge`] skip it once an earlier strategy
+    /// has returned data. Use this for slow fallbacks (e.g., PTY probes) that
+    /// add nothing the faster sources don't already cover.
+    ///
+    /// [`PipelineMode::Merge`]: crate::pipeline::PipelineMode::Merge
+    fn is_last_resort(&self) -> bool {
+        false
+    }
+
+    /// Snapshot fields this strategy is authoritative for.
+    ///
+    /// Only consulted by pipelines in [`PipelineMode::Merge`]: an
+    /// authoritative value always wins over a non-authoritative one, and
+    /// the pipeline stops once every field has an authoritative value.
+    ///
+    /// [`PipelineMode::Merge`]: crate::pipeline::PipelineMode::Merge
+    fn authoritative_fields(&self) -> &[SnapshotField] {
+        SnapshotField::ALL
+    }
 }
 
 // ============================================================================
@@ -235,4 +381,28 @@ mod tests {
         assert_eq!(FetchKind::CLI.to_fetch_source(), FetchSource::CLI);
         assert_eq!(FetchKind::OAuth.to_fetch_source(), FetchSource::OAuth);
     }
+
+    #[test]
+    fn test_snapshot_field_copy_into() {
+        let mut source = UsageSnapshot::new();
+        source.set_secondary(Some(exactobar_core::UsageWindow::new(40.0)));
+
+        let mut target = UsageSnapshot::new();
+        target.set_primary(Some(exactobar_core::UsageWindow::new(10.0)));
+
+        SnapshotField::Secondary.copy_into(&source, &mut target);
+        assert!(SnapshotField::Primary.is_present(&target));
+        assert!(SnapshotField::Secondary.is_present(&target));
+        assert!(!SnapshotField::Identity.is_present(&target));
+    }
+
+    #[test]
+    fn test_source_of_without_provenance() {
+        let mut snapshot = UsageSnapshot::new();
+        snapshot.set_primary(Some(exactobar_core::UsageWindow::new(10.0)));
+        let result = FetchResult::new(snapshot, "test.cli", FetchKind::CLI);
+
+        assert_eq!(result.source_of(SnapshotField::Primary), Some("test.cli"));
+        assert_eq!(result.source_of(SnapshotField::Identity), None);
+    }
 }
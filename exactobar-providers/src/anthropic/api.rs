@@ -0,0 +1,803 @@
+//! Anthropic Admin API client for usage and cost reports.
+//!
+//! These endpoints need an Admin API key (`sk-ant-admin...`), created in the
+//! Console under Settings → Admin keys. Regular API keys are rejected.
+//!
+//! # API Endpoints
+//!
+//! ```text
+//! GET https://api.anthropic.com/v1/organizations/usage_report/messages
+//!     ?starting_at=2025-01-01T00:00:00Z&bucket_width=1d
+//!     &group_by[]=model&group_by[]=workspace_id&group_by[]=api_key_id
+//! GET https://api.anthropic.com/v1/organizations/cost_report
+//!     ?starting_at=2025-01-01T00:00:00Z
+//!     &group_by[]=workspace_id&group_by[]=description
+//! GET https://api.anthropic.com/v1/organizations/workspaces
+//! GET https://api.anthropic.com/v1/organizations/api_keys
+//! GET https://api.anthropic.com/v1/organizations/me
+//! x-api-key: sk-ant-admin...
+//! anthropic-version: 2023-06-01
+//! ```
+//!
+//! # Response Format
+//!
+//! ```json
+//! {
+//!   "data": [{
+//!     "starting_at": "2025-01-01T00:00:00Z",
+//!     "ending_at": "2025-01-02T00:00:00Z",
+//!     "results": [{
+//!       "currency": "USD",
+//!       "amount": "1234.5",
+//!       "workspace_id": "wrkspc_01...",
+//!       "model": "claude-sonnet-4-20250514"
+//!     }]
+//!   }],
+//!   "has_more": false,
+//!   "next_page": null
+//! }
+//! ```
+//!
+//! Cost amounts are decimal strings in cents.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use exactobar_core::{
+    CostUsageSnapshot, DailyUsageEntry, FetchSource, LoginMethod, ModelBreakdown, ProjectBreakdown,
+    ProviderIdentity, ProviderKind, USD, UsageSnapshot,
+};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use tracing::{debug, instrument, warn};
+
+use super::error::AnthropicError;
+use crate::spend::{monthly_spend_window, start_of_month};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Anthropic API base URL.
+pub const API_BASE_URL: &str = "https://api.anthropic.com/v1";
+
+/// API version header value.
+const API_VERSION: &str = "2023-06-01";
+
+/// Environment variable for the admin key.
+pub const ADMIN_KEY_ENV: &str = "ANTHROPIC_ADMIN_KEY";
+
+/// Usage report endpoint.
+const USAGE_ENDPOINT: &str = "/organizations/usage_report/messages";
+
+/// Cost report endpoint.
+const COST_ENDPOINT: &str = "/organizations/cost_report";
+
+/// Workspaces endpoint.
+const WORKSPACES_ENDPOINT: &str = "/organizations/workspaces";
+
+/// API keys endpoint.
+const API_KEYS_ENDPOINT: &str = "/organizations/api_keys";
+
+/// Organization endpoint.
+const ORGANIZATION_ENDPOINT: &str = "/organizations/me";
+
+/// Breakdown ID for requests without a workspace.
+const DEFAULT_WORKSPACE: &str = "default";
+
+/// Maximum pages to follow per report.
+const MAX_PAGES: usize = 10;
+
+// ============================================================================
+// Response Structures
+// ============================================================================
+
+/// A page of report buckets.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReportPage<T> {
+    /// Time buckets.
+    #[serde(default = "Vec::new")]
+    pub data: Vec<ReportBucket<T>>,
+    /// Whether more pages follow.
+    #[serde(default)]
+    pub has_more: bool,
+    /// Cursor for the next page.
+    pub next_page: Option<String>,
+}
+
+/// A single report bucket.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReportBucket<T> {
+    /// Bucket start.
+    pub starting_at: DateTime<Utc>,
+    /// Bucket end.
+    pub ending_at: DateTime<Utc>,
+    /// Grouped results.
+    #[serde(default = "Vec::new")]
+    pub results: Vec<T>,
+}
+
+impl<T> ReportBucket<T> {
+    /// Returns the bucket date ("YYYY-MM-DD").
+    pub fn date(&self) -> String {
+        self.starting_at.format("%Y-%m-%d").to_string()
+    }
+}
+
+/// Message usage result within a bucket.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct MessagesUsageResult {
+    /// Input tokens not read from or written to the cache.
+    pub uncached_input_tokens: u64,
+    /// Input tokens written to the cache.
+    pub cache_creation: CacheCreation,
+    /// Input tokens read from the cache.
+    pub cache_read_input_tokens: u64,
+    /// Output tokens.
+    pub output_tokens: u64,
+    /// Model (when grouped by model).
+    pub model: Option<String>,
+    /// Workspace ID; `None` is the default workspace.
+    pub workspace_id: Option<String>,
+    /// API key ID (when grouped by API key).
+    pub api_key_id: Option<String>,
+}
+
+impl MessagesUsageResult {
+    /// Returns all input tokens, cached or not.
+    pub fn input_tokens(&self) -> u64 {
+        self.uncached_input_tokens + self.cache_creation.total() + self.cache_read_input_tokens
+    }
+}
+
+/// Cache write tokens by TTL.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CacheCreation {
+    /// Tokens written with a 1 hour TTL.
+    pub ephemeral_1h_input_tokens: u64,
+    /// Tokens written with a 5 minute TTL.
+    pub ephemeral_5m_input_tokens: u64,
+}
+
+impl CacheCreation {
+    /// Returns all cache write tokens.
+    pub fn total(&self) -> u64 {
+        self.ephemeral_1h_input_tokens + self.ephemeral_5m_input_tokens
+    }
+}
+
+/// Cost result within a bucket.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CostReportResult {
+    /// Currency code (always "USD" today).
+    pub currency: Option<String>,
+    /// Amount in cents.
+    pub amount: DecimalAmount,
+    /// Workspace ID; `None` is the default workspace.
+    pub workspace_id: Option<String>,
+    /// Line item description (when grouped by description).
+    pub description: Option<String>,
+    /// Model (when grouped by description).
+    pub model: Option<String>,
+}
+
+impl CostReportResult {
+    /// Returns the amount in dollars.
+    pub fn dollars(&self) -> f64 {
+        self.amount.value() / 100.0
+    }
+}
+
+/// Decimal amount, sent as a string but accepted as a number too.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum DecimalAmount {
+    /// Decimal string, e.g. "123.45".
+    Text(String),
+    /// Plain number.
+    Number(f64),
+}
+
+impl DecimalAmount {
+    /// Returns the numeric value, or 0 if unparseable.
+    pub fn value(&self) -> f64 {
+        match self {
+            Self::Text(s) => s.trim().parse().unwrap_or(0.0),
+            Self::Number(n) => *n,
+        }
+    }
+}
+
+/// Paged list of named objects (workspaces, API keys).
+#[derive(Debug, Clone, Deserialize)]
+struct NamedList {
+    #[serde(default)]
+    data: Vec<NamedObject>,
+}
+
+/// Object with an ID and a name.
+#[derive(Debug, Clone, Deserialize)]
+struct NamedObject {
+    id: String,
+    name: Option<String>,
+}
+
+// ============================================================================
+// Organization Report
+// ============================================================================
+
+/// Usage and cost reports for an organization.
+#[derive(Debug, Clone, Default)]
+pub struct OrganizationReport {
+    /// Daily message usage grouped by model, workspace and API key.
+    pub usage: Vec<ReportBucket<MessagesUsageResult>>,
+    /// Daily costs grouped by workspace and line item.
+    pub costs: Vec<ReportBucket<CostReportResult>>,
+    /// Workspace names by ID.
+    pub workspace_names: HashMap<String, String>,
+    /// API key names by ID.
+    pub api_key_names: HashMap<String, String>,
+    /// Organization name.
+    pub organization: Option<String>,
+}
+
+impl OrganizationReport {
+    /// Returns the total cost in dollars of buckets starting at or after `since`.
+    pub fn cost_since(&self, since: DateTime<Utc>) -> f64 {
+        self.costs
+            .iter()
+            .filter(|b| b.starting_at >= since)
+            .flat_map(|b| &b.results)
+            .map(CostReportResult::dollars)
+            .sum()
+    }
+
+    /// Returns total tokens of buckets starting at or after `since`.
+    pub fn tokens_since(&self, since: DateTime<Utc>) -> u64 {
+        self.usage
+            .iter()
+            .filter(|b| b.starting_at >= since)
+            .flat_map(|b| &b.results)
+            .map(|r| r.input_tokens() + r.output_tokens)
+            .sum()
+    }
+
+    /// Returns the report currency, defaulting to USD.
+    pub fn currency(&self) -> &str {
+        self.costs
+            .iter()
+            .flat_map(|b| &b.results)
+            .find_map(|r| r.currency.as_deref())
+            .unwrap_or(USD)
+    }
+
+    /// Builds a daily cost report with model, workspace and API key breakdowns.
+    pub fn to_cost_snapshot(&self, now: DateTime<Utc>) -> CostUsageSnapshot {
+        let mut days: BTreeMap<String, DayTotals> = BTreeMap::new();
+
+        for bucket in &self.costs {
+            let day = days.entry(bucket.date()).or_default();
+            for result in &bucket.results {
+                let dollars = result.dollars();
+                day.cost += dollars;
+
+                let workspace = entry(&mut day.workspaces, result.workspace_id.as_deref());
+                *workspace.cost_usd.get_or_insert(0.0) += dollars;
+
+                if let Some(ref model) = result.model {
+                    let breakdown = day
+                        .models
+                        .entry(model.clone())
+                        .or_insert_with(|| ModelBreakdown::new(model));
+                    *breakdown.cost_usd.get_or_insert(0.0) += dollars;
+                }
+            }
+        }
+
+        for bucket in &self.usage {
+            let day = days.entry(bucket.date()).or_default();
+            for result in &bucket.results {
+                let input = result.input_tokens();
+                day.uncached += result.uncached_input_tokens;
+                day.cache_creation += result.cache_creation.total();
+                day.cache_read += result.cache_read_input_tokens;
+                day.output += result.output_tokens;
+
+                let model = result.model.as_deref().unwrap_or("unknown");
+                let breakdown = day
+                    .models
+                    .entry(model.to_string())
+                    .or_insert_with(|| ModelBreakdown::new(model));
+                *breakdown.input_tokens.get_or_insert(0) += input;
+                *breakdown.output_tokens.get_or_insert(0) += result.output_tokens;
+
+                let workspace = entry(&mut day.workspaces, result.workspace_id.as_deref());
+                *workspace.input_tokens.get_or_insert(0) += input;
+                *workspace.output_tokens.get_or_insert(0) += result.output_tokens;
+
+                if let Some(ref key_id) = result.api_key_id {
+                    let key = entry(&mut day.api_keys, Some(key_id));
+                    *key.input_tokens.get_or_insert(0) += input;
+                    *key.output_tokens.get_or_insert(0) += result.output_tokens;
+                }
+            }
+        }
+
+        let mut snapshot = CostUsageSnapshot::new();
+        snapshot.updated_at = now;
+        snapshot.daily = days
+            .into_iter()
+            .map(|(date, day)| day.into_entry(date, self))
+            .collect();
+
+        let since = now - Duration::days(30);
+        snapshot.last_30_days_cost_usd = Some(self.cost_since(since));
+        snapshot.last_30_days_tokens = Some(self.tokens_since(since));
+        snapshot
+    }
+
+    /// Builds a usage snapshot with a monthly spend window and the cost report.
+    pub fn to_snapshot(&self, budget: Option<f64>, now: DateTime<Utc>) -> UsageSnapshot {
+        let spent = self.cost_since(start_of_month(now));
+
+        let mut snapshot = UsageSnapshot::new();
+        snapshot.fetch_source = FetchSource::Api;
+        snapshot.set_primary(Some(monthly_spend_window(
+            spent,
+            budget,
+            self.currency(),
+            now,
+        )));
+        snapshot.cost = Some(self.to_cost_snapshot(now));
+
+        let mut identity = ProviderIdentity::new(ProviderKind::Anthropic);
+        identity.account_organization = self.organization.clone();
+        identity.login_method = Some(LoginMethod::ApiKey);
+        snapshot.identity = Some(identity);
+
+        snapshot
+    }
+}
+
+/// Per-day accumulator.
+#[derive(Default)]
+struct DayTotals {
+    cost: f64,
+    uncached: u64,
+    cache_creation: u64,
+    cache_read: u64,
+    output: u64,
+    models: BTreeMap<String, ModelBreakdown>,
+    workspaces: BTreeMap<String, ProjectBreakdown>,
+    api_keys: BTreeMap<String, ProjectBreakdown>,
+}
+
+impl DayTotals {
+    fn into_entry(self, date: String, report: &OrganizationReport) -> DailyUsageEntry {
+        let mut entry = DailyUsageEntry::new(date);
+        entry.cost_usd = Some(self.cost);
+
+        let total = self.uncached + self.cache_creation + self.cache_read + self.output;
+        if total > 0 {
+            entry.input_tokens = Some(self.uncached);
+            entry.output_tokens = Some(self.output);
+            entry.cache_read_tokens = Some(self.cache_read);
+            entry.cache_creation_tokens = Some(self.cache_creation);
+            entry.total_tokens = Some(total);
+        }
+        if !self.models.is_empty() {
+            entry.models_used = Some(self.models.keys().cloned().collect());
+            entry.model_breakdowns = Some(self.models.into_values().collect());
+        }
+        entry.project_breakdowns = named(self.workspaces, &report.workspace_names);
+        entry.api_key_breakdowns = named(self.api_keys, &report.api_key_names);
+        entry
+    }
+}
+
+/// Returns the breakdown for an ID, creating it if needed.
+fn entry<'a>(
+    map: &'a mut BTreeMap<String, ProjectBreakdown>,
+    id: Option<&str>,
+) -> &'a mut ProjectBreakdown {
+    let id = id.unwrap_or(DEFAULT_WORKSPACE);
+    map.entry(id.to_string())
+        .or_insert_with(|| ProjectBreakdown::new(id))
+}
+
+/// Attaches names to breakdowns, or returns `None` if there are none.
+fn named(
+    map: BTreeMap<String, ProjectBreakdown>,
+    names: &HashMap<String, String>,
+) -> Option<Vec<ProjectBreakdown>> {
+    if map.is_empty() {
+        return None;
+    }
+    Some(
+        map.into_values()
+            .map(|mut b| {
+                b.project_name = names.get(&b.project_id).cloned();
+                b
+            })
+            .collect(),
+    )
+}
+
+// ============================================================================
+// API Client
+// ============================================================================
+
+/// Anthropic Admin API client.
+#[derive(Debug, Clone)]
+pub struct AnthropicAdminClient {
+    base_url: String,
+}
+
+impl Default for AnthropicAdminClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnthropicAdminClient {
+    /// Creates a new client.
+    pub fn new() -> Self {
+        Self {
+            base_url: API_BASE_URL.to_string(),
+        }
+    }
+
+    /// Creates a client with a custom base URL.
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+        }
+    }
+
+    /// Get the admin key from Keychain first, then environment variable.
+    ///
+    /// The lookup order is:
+    /// 1. System keychain (stored via Settings UI)
+    /// 2. Environment variable `ANTHROPIC_ADMIN_KEY`
+    pub fn get_admin_key() -> Result<String, AnthropicError> {
+        if let Some(key) =
+            exactobar_store::get_api_key(exactobar_store::keychain::providers::ANTHROPIC)
+        {
+            return Ok(key);
+        }
+
+        std::env::var(ADMIN_KEY_ENV).map_err(|_| AnthropicError::AdminKeyNotFound)
+    }
+
+    /// Fetches usage, costs and names since the start of the month or the
+    /// last 30 days, whichever is earlier.
+    ///
+    /// The cost report is required; everything else is best-effort.
+    #[instrument(skip(self, admin_key))]
+    pub async fn fetch_report(
+        &self,
+        admin_key: &str,
+        now: DateTime<Utc>,
+    ) -> Result<OrganizationReport, AnthropicError> {
+        let start = start_of_month(now).min(now - Duration::days(30));
+
+        let costs = self.fetch_costs(admin_key, start).await?;
+
+        let usage = self
+            .fetch_usage(admin_key, start)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "Failed to fetch Anthropic usage report");
+                Vec::new()
+            });
+
+        let workspace_names = self
+            .fetch_names(admin_key, WORKSPACES_ENDPOINT)
+            .await
+            .unwrap_or_else(|e| {
+                debug!(error = %e, "Failed to fetch Anthropic workspaces");
+                HashMap::new()
+            });
+
+        let api_key_names = self
+            .fetch_names(admin_key, API_KEYS_ENDPOINT)
+            .await
+            .unwrap_or_else(|e| {
+                debug!(error = %e, "Failed to fetch Anthropic API keys");
+                HashMap::new()
+            });
+
+        let organization = self
+            .get::<NamedObject>(admin_key, ORGANIZATION_ENDPOINT, &[])
+            .await
+            .ok()
+            .and_then(|org| org.name);
+
+        Ok(OrganizationReport {
+            usage,
+            costs,
+            workspace_names,
+            api_key_names,
+            organization,
+        })
+    }
+
+    /// Fetches daily message usage grouped by model, workspace and API key.
+    pub async fn fetch_usage(
+        &self,
+        admin_key: &str,
+        start: DateTime<Utc>,
+    ) -> Result<Vec<ReportBucket<MessagesUsageResult>>, AnthropicError> {
+        let query = [
+            ("starting_at", rfc3339(start)),
+            ("bucket_width", "1d".to_string()),
+            ("group_by[]", "model".to_string()),
+            ("group_by[]", "workspace_id".to_string()),
+            ("group_by[]", "api_key_id".to_string()),
+            ("limit", "31".to_string()),
+        ];
+        self.get_buckets(admin_key, USAGE_ENDPOINT, &query).await
+    }
+
+    /// Fetches daily costs grouped by workspace and line item.
+    pub async fn fetch_costs(
+        &self,
+        admin_key: &str,
+        start: DateTime<Utc>,
+    ) -> Result<Vec<ReportBucket<CostReportResult>>, AnthropicError> {
+        let query = [
+            ("starting_at", rfc3339(start)),
+            ("group_by[]", "workspace_id".to_string()),
+            ("group_by[]", "description".to_string()),
+            ("limit", "31".to_string()),
+        ];
+        self.get_buckets(admin_key, COST_ENDPOINT, &query).await
+    }
+
+    /// Fetches names by ID from a list endpoint.
+    async fn fetch_names(
+        &self,
+        admin_key: &str,
+        endpoint: &str,
+    ) -> Result<HashMap<String, String>, AnthropicError> {
+        let query = [("limit", "100".to_string())];
+        let list: NamedList = self.get(admin_key, endpoint, &query).await?;
+        Ok(list
+            .data
+            .into_iter()
+            .filter_map(|o| o.name.map(|name| (o.id, name)))
+            .collect())
+    }
+
+    /// Fetches every page of a report.
+    async fn get_buckets<T: DeserializeOwned>(
+        &self,
+        admin_key: &str,
+        endpoint: &str,
+        query: &[(&str, String)],
+    ) -> Result<Vec<ReportBucket<T>>, AnthropicError> {
+        let mut buckets = Vec::new();
+        let mut page: Option<String> = None;
+
+        for _ in 0..MAX_PAGES {
+            let mut params = query.to_vec();
+            if let Some(ref cursor) = page {
+                params.push(("page", cursor.clone()));
+            }
+
+            let response: ReportPage<T> = self.get(admin_key, endpoint, &params).await?;
+            buckets.extend(response.data);
+
+            match response.next_page {
+                Some(next) if response.has_more => page = Some(next),
+                _ => return Ok(buckets),
+            }
+        }
+
+        warn!(endpoint = endpoint, "Stopped following pages at the limit");
+        Ok(buckets)
+    }
+
+    /// Sends an authenticated GET request and parses the JSON response.
+    async fn get<T: DeserializeOwned>(
+        &self,
+        admin_key: &str,
+        endpoint: &str,
+        query: &[(&str, String)],
+    ) -> Result<T, AnthropicError> {
+        let url = format!("{}{}", self.base_url, endpoint);
+
+        debug!(url = %url, "Fetching Anthropic organization data");
+
+        let client = reqwest::Client::new();
+        let response = client
+            .get(&url)
+            .query(query)
+            .header("x-api-key", admin_key)
+            .header("anthropic-version", API_VERSION)
+            .send()
+            .await?;
+
+        let status = response.status();
+
+        if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+            return Err(AnthropicError::AuthenticationFailed(
+                "admin key rejected (an Admin API key is required)".to_string(),
+            ));
+        }
+
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(AnthropicError::ApiError(format!(
+                "HTTP {}: {}",
+                status, body
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| AnthropicError::ParseError(e.to_string()))
+    }
+}
+
+/// Formats a timestamp the way the Admin API expects.
+fn rfc3339(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const USAGE_JSON: &str = r#"{
+        "data": [
+            {
+                "starting_at": "2025-08-01T00:00:00Z",
+                "ending_at": "2025-08-02T00:00:00Z",
+                "results": [
+                    {
+                        "uncached_input_tokens": 1500,
+                        "cache_creation": {"ephemeral_1h_input_tokens": 1000, "ephemeral_5m_input_tokens": 500},
+                        "cache_read_input_tokens": 200,
+                        "output_tokens": 500,
+                        "server_tool_use": {"web_search_requests": 10},
+                        "api_key_id": "apikey_01",
+                        "workspace_id": "wrkspc_01",
+                        "model": "claude-sonnet-4-20250514",
+                        "service_tier": "standard",
+                        "context_window": "0-200k"
+                    },
+                    {
+                        "uncached_input_tokens": 100,
+                        "cache_creation": {"ephemeral_1h_input_tokens": 0, "ephemeral_5m_input_tokens": 0},
+                        "cache_read_input_tokens": 0,
+                        "output_tokens": 50,
+                        "api_key_id": null,
+                        "workspace_id": null,
+                        "model": "claude-opus-4-20250514"
+                    }
+                ]
+            }
+        ],
+        "has_more": false,
+        "next_page": null
+    }"#;
+
+    const COST_JSON: &str = r#"{
+        "data": [
+            {
+                "starting_at": "2025-07-25T00:00:00Z",
+                "ending_at": "2025-07-26T00:00:00Z",
+                "results": [
+                    {"currency": "USD", "amount": "700", "workspace_id": null, "description": "Claude Opus 4 Usage - Output Tokens", "model": "claude-opus-4-20250514"}
+                ]
+            },
+            {
+                "starting_at": "2025-08-01T00:00:00Z",
+                "ending_at": "2025-08-02T00:00:00Z",
+                "results": [
+                    {"currency": "USD", "amount": "123.5", "workspace_id": "wrkspc_01", "description": "Claude Sonnet 4 Usage - Input Tokens", "model": "claude-sonnet-4-20250514"},
+                    {"currency": "USD", "amount": 76.5, "workspace_id": null, "description": "Web Search", "model": null}
+                ]
+            }
+        ],
+        "has_more": true,
+        "next_page": "page_2"
+    }"#;
+
+    fn sample_report() -> OrganizationReport {
+        let usage: ReportPage<MessagesUsageResult> = serde_json::from_str(USAGE_JSON).unwrap();
+        let costs: ReportPage<CostReportResult> = serde_json::from_str(COST_JSON).unwrap();
+        OrganizationReport {
+            usage: usage.data,
+            costs: costs.data,
+            workspace_names: HashMap::from([("wrkspc_01".to_string(), "Prod".to_string())]),
+            api_key_names: HashMap::from([("apikey_01".to_string(), "ci".to_string())]),
+            organization: Some("Acme".to_string()),
+        }
+    }
+
+    /// 2025-08-01 18:00 UTC.
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 8, 1, 18, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_reports() {
+        let costs: ReportPage<CostReportResult> = serde_json::from_str(COST_JSON).unwrap();
+        assert!(costs.has_more);
+        assert_eq!(costs.next_page.as_deref(), Some("page_2"));
+        assert!((costs.data[1].results[0].dollars() - 1.235).abs() < 0.001);
+        assert!((costs.data[1].results[1].dollars() - 0.765).abs() < 0.001);
+
+        let usage: ReportPage<MessagesUsageResult> = serde_json::from_str(USAGE_JSON).unwrap();
+        assert_eq!(usage.data[0].date(), "2025-08-01");
+        assert_eq!(usage.data[0].results[0].input_tokens(), 3200);
+    }
+
+    #[test]
+    fn test_cost_snapshot_breakdowns() {
+        let cost = sample_report().to_cost_snapshot(now());
+        assert_eq!(cost.daily.len(), 2);
+
+        let day = cost.daily.iter().find(|d| d.date == "2025-08-01").unwrap();
+        assert!((day.cost_usd.unwrap() - 2.0).abs() < 0.001);
+        assert_eq!(day.input_tokens, Some(1600));
+        assert_eq!(day.cache_creation_tokens, Some(1500));
+        assert_eq!(day.cache_read_tokens, Some(200));
+        assert_eq!(day.output_tokens, Some(550));
+        assert_eq!(day.total_tokens, Some(3850));
+
+        let models = day.model_breakdowns.as_ref().unwrap();
+        let sonnet = models
+            .iter()
+            .find(|m| m.model_name == "claude-sonnet-4-20250514")
+            .unwrap();
+        assert_eq!(sonnet.total_tokens(), 3700);
+        assert!((sonnet.cost_usd.unwrap() - 1.235).abs() < 0.001);
+
+        let workspaces = day.project_breakdowns.as_ref().unwrap();
+        let prod = workspaces
+            .iter()
+            .find(|w| w.project_id == "wrkspc_01")
+            .unwrap();
+        assert_eq!(prod.display_name(), "Prod");
+        assert!(workspaces.iter().any(|w| w.project_id == "default"));
+
+        let keys = day.api_key_breakdowns.as_ref().unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].display_name(), "ci");
+
+        assert!((cost.last_30_days_cost_usd.unwrap() - 9.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_snapshot_monthly_spend() {
+        let snapshot = sample_report().to_snapshot(Some(10.0), now());
+        let window = snapshot.primary().unwrap();
+
+        // Only August counts toward the month
+        assert!((window.used.unwrap() - 2.0).abs() < 0.001);
+        assert!((window.used_percent - 20.0).abs() < 0.001);
+        assert_eq!(window.unit.as_deref(), Some("USD"));
+
+        let identity = snapshot.identity.as_ref().unwrap();
+        assert_eq!(identity.account_organization.as_deref(), Some("Acme"));
+        assert!(snapshot.cost.is_some());
+        assert!(snapshot.validate().is_ok());
+    }
+
+    #[test]
+    fn test_rfc3339_query_format() {
+        let start = Utc.with_ymd_and_hms(2025, 8, 1, 0, 0, 0).unwrap();
+        assert_eq!(rfc3339(start), "2025-08-01T00:00:00Z");
+    }
+}
@@ -0,0 +1,104 @@
+//! Anthropic API provider descriptor.
+
+use exactobar_core::{IconStyle, ProviderBranding, ProviderColor, ProviderKind, ProviderMetadata};
+use exactobar_fetch::{FetchContext, FetchPipeline, SourceMode};
+use std::collections::HashMap;
+
+use super::strategies::AnthropicAdminStrategy;
+use crate::descriptor::{CliConfig, FetchPlan, ProviderDescriptor, TokenCostConfig};
+use crate::spend::MONTHLY_SPEND_WINDOW;
+
+// ============================================================================
+// Descriptor
+// ============================================================================
+
+/// Creates the Anthropic API provider descriptor.
+pub fn anthropic_descriptor() -> ProviderDescriptor {
+    ProviderDescriptor {
+        id: ProviderKind::Anthropic,
+        metadata: anthropic_metadata(),
+        branding: anthropic_branding(),
+        token_cost: TokenCostConfig::default(),
+        fetch_plan: anthropic_fetch_plan(),
+        cli: anthropic_cli_config(),
+    }
+}
+
+// ============================================================================
+// Metadata
+// ============================================================================
+
+fn anthropic_metadata() -> ProviderMetadata {
+    ProviderMetadata {
+        id: ProviderKind::Anthropic,
+        display_name: "Anthropic API".to_string(),
+        session_label: "Monthly spend".to_string(),
+        weekly_label: "Monthly".to_string(),
+        opus_label: None,
+        supports_opus: false,
+        window_labels: HashMap::from([(
+            MONTHLY_SPEND_WINDOW.to_string(),
+            "Monthly spend".to_string(),
+        )]),
+        supports_credits: false,
+        credits_hint: String::new(),
+        toggle_title: "Show Anthropic API spend".to_string(),
+        cli_name: "anthropic".to_string(),
+        default_enabled: false,
+        is_primary_provider: false,
+        uses_account_fallback: false,
+        dashboard_url: Some("https://console.anthropic.com/usage".to_string()),
+        subscription_dashboard_url: Some(
+            "https://console.anthropic.com/settings/billing".to_string(),
+        ),
+        status_page_url: Some("https://status.anthropic.com/api/v2/status.json".to_string()),
+        status_link_url: Some("https://status.anthropic.com".to_string()),
+    }
+}
+
+// ============================================================================
+// Branding
+// ============================================================================
+
+fn anthropic_branding() -> ProviderBranding {
+    ProviderBranding {
+        icon_style: IconStyle::Anthropic,
+        icon_resource_name: "icon_anthropic".to_string(),
+        // Anthropic clay
+        color: ProviderColor::new(0.85, 0.47, 0.34),
+    }
+}
+
+// ============================================================================
+// Fetch Plan
+// ============================================================================
+
+fn anthropic_fetch_plan() -> FetchPlan {
+    FetchPlan {
+        source_modes: vec![SourceMode::ApiKey],
+        build_pipeline: Box::new(build_anthropic_pipeline),
+    }
+}
+
+fn build_anthropic_pipeline(ctx: &FetchContext) -> FetchPipeline {
+    let mut strategies: Vec<Box<dyn exactobar_fetch::FetchStrategy>> = Vec::new();
+
+    if ctx.settings.source_mode.allows_api_key() {
+        strategies.push(Box::new(AnthropicAdminStrategy::new()));
+    }
+
+    FetchPipeline::with_strategies(strategies)
+}
+
+// ============================================================================
+// CLI Config
+// ============================================================================
+
+fn anthropic_cli_config() -> CliConfig {
+    CliConfig {
+        name: "anthropic",
+        aliases: &["anthropic-api", "console"],
+        version_args: &["--version"],
+        usage_args: &["usage"],
+    }
+}
@@ -0,0 +1,33 @@
+//! Anthropic API-specific errors.
+
+use thiserror::Error;
+
+/// Anthropic Admin API errors.
+#[derive(Debug, Error)]
+pub enum AnthropicError {
+    /// Admin key not found in keychain or environment.
+    #[error("Admin API key not found (set ANTHROPIC_ADMIN_KEY env var)")]
+    AdminKeyNotFound,
+
+    /// HTTP request failed.
+    #[error("HTTP error: {0}")]
+    HttpError(String),
+
+    /// Parse error.
+    #[error("Parse error: {0}")]
+    ParseError(String),
+
+    /// API error.
+    #[error("API error: {0}")]
+    ApiError(String),
+
+    /// Authentication failed.
+    #[error("Authentication failed: {0}")]
+    AuthenticationFailed(String),
+}
+
+impl From<reqwest::Error> for AnthropicError {
+    fn from(err: reqwest::Error) -> Self {
+        AnthropicError::HttpError(err.to_string())
+    }
+}
@@ -0,0 +1,22 @@
+//! Anthropic API provider implementation.
+//!
+//! Tracks API spend on console.anthropic.com, separately from the Claude
+//! subscription windows. Uses an Admin API key to read the organization
+//! usage report (tokens by model, workspace and API key, with cache reads
+//! and writes) and the cost report.
+//!
+//! The key is read from the keychain (`anthropic`) or `ANTHROPIC_ADMIN_KEY`.
+//! Month-to-date spend is shown against the optional monthly budget from
+//! provider settings.
+
+mod api;
+mod descriptor;
+mod error;
+mod strategies;
+
+pub use api::{
+    ADMIN_KEY_ENV, AnthropicAdminClient, CostReportResult, MessagesUsageResult, OrganizationReport,
+};
+pub use descriptor::anthropic_descriptor;
+pub use error::AnthropicError;
+pub use strategies::AnthropicAdminStrategy;
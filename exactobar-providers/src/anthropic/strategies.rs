@@ -0,0 +1,92 @@
+//! Anthropic API fetch strategies.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use exactobar_core::ProviderKind;
+use exactobar_fetch::{FetchContext, FetchError, FetchKind, FetchResult, FetchStrategy};
+use tracing::{debug, instrument};
+
+use super::api::AnthropicAdminClient;
+use super::error::AnthropicError;
+
+// ============================================================================
+// Admin API Strategy
+// ============================================================================
+
+/// Admin API key strategy for the Anthropic Console.
+///
+/// Reads the usage and cost reports with an Admin API key from the keychain
+/// or the `ANTHROPIC_ADMIN_KEY` environment variable.
+pub struct AnthropicAdminStrategy;
+
+impl AnthropicAdminStrategy {
+    /// Creates a new strategy.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for AnthropicAdminStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FetchStrategy for AnthropicAdminStrategy {
+    fn id(&self) -> &str {
+        "anthropic.admin"
+    }
+
+    fn kind(&self) -> FetchKind {
+        FetchKind::ApiKey
+    }
+
+    #[instrument(skip(self, _ctx))]
+    async fn is_available(&self, _ctx: &FetchContext) -> bool {
+        AnthropicAdminClient::get_admin_key().is_ok()
+    }
+
+    #[instrument(skip(self, ctx))]
+    async fn fetch(&self, ctx: &FetchContext) -> Result<FetchResult, FetchError> {
+        debug!("Fetching Anthropic usage and cost reports");
+
+        let admin_key = AnthropicAdminClient::get_admin_key()
+            .map_err(|e| FetchError::AuthenticationFailed(e.to_string()))?;
+
+        let budget = ctx.provider_options(ProviderKind::Anthropic).monthly_budget;
+        let now = Utc::now();
+
+        let report = AnthropicAdminClient::new()
+            .fetch_report(&admin_key, now)
+            .await
+            .map_err(|e| match e {
+                AnthropicError::AuthenticationFailed(msg) => FetchError::AuthenticationFailed(msg),
+                other => FetchError::InvalidResponse(other.to_string()),
+            })?;
+
+        let snapshot = report.to_snapshot(budget, now);
+        Ok(FetchResult::new(snapshot, self.id(), self.kind()))
+    }
+
+    fn priority(&self) -> u32 {
+        60 // API Key priority
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_admin_strategy() {
+        let s = AnthropicAdminStrategy::new();
+        assert_eq!(s.id(), "anthropic.admin");
+        assert_eq!(s.kind(), FetchKind::ApiKey);
+        assert_eq!(s.priority(), 60);
+    }
+}
@@ -2,6 +2,7 @@
 
 use exactobar_core::{IconStyle, ProviderBranding, ProviderColor, ProviderKind, ProviderMetadata};
 use exactobar_fetch::{FetchContext, FetchPipeline, SourceMode};
+use std::collections::HashMap;
 
 use super::strategies::AntigravityLocalStrategy;
 use crate::descriptor::{CliConfig, FetchPlan, ProviderDescriptor, TokenCostConfig};
@@ -25,6 +26,7 @@ fn antigravity_metadata() -> ProviderMetadata {
         weekly_label: "Daily".to_string(),
         opus_label: None,
         supports_opus: false,
+        window_labels: HashMap::new(),
         supports_credits: false,
         credits_hint: String::new(),
         toggle_title: "Show Antigravity usage".to_string(),
@@ -50,7 +52,7 @@ fn antigravity_branding() -> ProviderBranding {
 fn antigravity_fetch_plan() -> FetchPlan {
     FetchPlan {
         source_modes: vec![SourceMode::Auto],
-        build_pipeline: build_antigravity_pipeline,
+        build_pipeline: Box::new(build_antigravity_pipeline),
     }
 }
 
@@ -2,6 +2,8 @@
 
 use thiserror::Error;
 
+use crate::codeium::CodeiumError;
+
 /// Antigravity-specific errors.
 #[derive(Debug, Error)]
 pub enum AntigravityError {
@@ -33,3 +35,15 @@ pub enum AntigravityError {
     #[error("API error: {0}")]
     ApiError(String),
 }
+
+impl From<CodeiumError> for AntigravityError {
+    fn from(err: CodeiumError) -> Self {
+        match err {
+            CodeiumError::NotRunning(_) => AntigravityError::NotRunning,
+            CodeiumError::ConnectionFailed(msg) => AntigravityError::ConnectionFailed(msg),
+            CodeiumError::InvalidResponse(msg) => AntigravityError::InvalidResponse(msg),
+            CodeiumError::PortDetectionFailed(msg) => AntigravityError::PortDetectionFailed(msg),
+            CodeiumError::ApiError(msg) => AntigravityError::ApiError(msg),
+        }
+    }
+}
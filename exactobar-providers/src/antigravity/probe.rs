@@ -1,262 +1,37 @@
 //! Antigravity local language server probe.
 //!
-//! Detects running Antigravity process, extracts CSRF token,
-//! and queries the gRPC-style API for usage quotas.
+//! Queries Antigravity's Codeium language server (see [`crate::codeium`])
+//! for per-model usage quotas.
 
 use chrono::{DateTime, Utc};
 use exactobar_core::{
     FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageSnapshot, UsageWindow,
+    WindowKind, WindowSlot,
 };
-use reqwest::header::CONTENT_TYPE;
-use serde::Deserialize;
-use std::process::Command;
 use tracing::{debug, instrument};
 
 use super::error::AntigravityError;
+use crate::codeium::{
+    LanguageServerClient, LanguageServerTarget, ModelConfig, UserStatus, parse_timestamp,
+};
 
 // ============================================================================
-// Constants
-// ============================================================================
-
-const PROCESS_NAME: &str = "language_server_macos";
-const GET_USER_STATUS_PATH: &str = "/exa.language_server_pb.LanguageServerService/GetUserStatus";
-const GET_COMMAND_MODEL_PATH: &str =
-    "/exa.language_server_pb.LanguageServerService/GetCommandModelConfigs";
-
-// ============================================================================
-// Process Detection
+// Language Server Target
 // ============================================================================
 
-#[derive(Debug)]
-struct ProcessInfo {
-    pid: u32,
-    csrf_token: String,
-    extension_port: Option<u16>,
-}
-
-/// Detect running Antigravity process and extract CSRF token
-fn detect_process() -> Result<ProcessInfo, AntigravityError> {
-    let output = Command::new("/bin/ps")
-        .args(["-ax", "-o", "pid=,command="])
-        .output()
-        .map_err(|_e| AntigravityError::NotRunning)?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-
-    for line in stdout.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-
-        // Parse "PID command..."
-        let parts: Vec<&str> = trimmed.splitn(2, ' ').collect();
-        if parts.len() != 2 {
-            continue;
-        }
-
-        let pid: u32 = match parts[0].trim().parse() {
-            Ok(p) => p,
-            Err(_) => continue,
-        };
-        let command = parts[1];
-        let lower = command.to_lowercase();
-
-        // Check if this is Antigravity
-        if !lower.contains(PROCESS_NAME) {
-            continue;
-        }
-        if !is_antigravity_command(&lower) {
-            continue;
-        }
-
-        // Extract CSRF token
-        if let Some(token) = extract_flag("--csrf_token", command) {
-            let port =
-                extract_flag("--extension_server_port", command).and_then(|s| s.parse().ok());
-
-            return Ok(ProcessInfo {
-                pid,
-                csrf_token: token,
-                extension_port: port,
-            });
-        }
-    }
-
-    Err(AntigravityError::NotRunning)
-}
+/// Antigravity's language server.
+const ANTIGRAVITY_LANGUAGE_SERVER: LanguageServerTarget = LanguageServerTarget {
+    name: "Antigravity",
+    process_name: "language_server_macos",
+    ide_name: "antigravity",
+    matches_command: is_antigravity_command,
+};
 
 fn is_antigravity_command(command: &str) -> bool {
     (command.contains("--app_data_dir") && command.contains("antigravity"))
         || command.contains("/antigravity/")
 }
 
-fn extract_flag(flag: &str, command: &str) -> Option<String> {
-    // Match --flag=value or --flag value
-    let patterns = [format!("{}=", flag), format!("{} ", flag)];
-
-    for pattern in &patterns {
-        if let Some(start) = command.find(pattern) {
-            let value_start = start + pattern.len();
-            let rest = &command[value_start..];
-            let value_end = rest.find(' ').unwrap_or(rest.len());
-            return Some(rest[..value_end].to_string());
-        }
-    }
-    None
-}
-
-// ============================================================================
-// Port Detection
-// ============================================================================
-
-fn detect_listening_ports(pid: u32) -> Result<Vec<u16>, AntigravityError> {
-    let lsof_paths = ["/usr/sbin/lsof", "/usr/bin/lsof"];
-    let lsof = lsof_paths
-        .iter()
-        .find(|p| std::path::Path::new(p).exists())
-        .ok_or_else(|| AntigravityError::PortDetectionFailed("lsof not available".into()))?;
-
-    let output = Command::new(lsof)
-        .args(["-nP", "-iTCP", "-sTCP:LISTEN", "-a", "-p", &pid.to_string()])
-        .output()
-        .map_err(|e| AntigravityError::PortDetectionFailed(e.to_string()))?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let mut ports = Vec::new();
-
-    // Parse lines like: "... :PORT (LISTEN)"
-    for line in stdout.lines() {
-        if let Some(port) = parse_port_from_lsof_line(line) {
-            if !ports.contains(&port) {
-                ports.push(port);
-            }
-        }
-    }
-
-    ports.sort();
-
-    if ports.is_empty() {
-        return Err(AntigravityError::PortDetectionFailed(
-            "no listening ports found".into(),
-        ));
-    }
-
-    Ok(ports)
-}
-
-fn parse_port_from_lsof_line(line: &str) -> Option<u16> {
-    // Look for pattern like ":12345 (LISTEN)"
-    let listen_idx = line.find("(LISTEN)")?;
-    let before = &line[..listen_idx];
-    let colon_idx = before.rfind(':')?;
-    let port_str = before[colon_idx + 1..].trim();
-    port_str.parse().ok()
-}
-
-// ============================================================================
-// API Response Types (matching POC exactly)
-// ============================================================================
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct UserStatusResponse {
-    code: Option<CodeValue>,
-    #[allow(dead_code)]
-    message: Option<String>,
-    user_status: Option<UserStatus>,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct CommandModelConfigResponse {
-    code: Option<CodeValue>,
-    #[allow(dead_code)]
-    message: Option<String>,
-    client_model_configs: Option<Vec<ModelConfig>>,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct UserStatus {
-    email: Option<String>,
-    plan_status: Option<PlanStatus>,
-    cascade_model_config_data: Option<ModelConfigData>,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct PlanStatus {
-    plan_info: Option<PlanInfo>,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct PlanInfo {
-    plan_name: Option<String>,
-    plan_display_name: Option<String>,
-    display_name: Option<String>,
-    product_name: Option<String>,
-    #[allow(dead_code)]
-    plan_short_name: Option<String>,
-}
-
-impl PlanInfo {
-    fn preferred_name(&self) -> Option<&str> {
-        self.plan_display_name
-            .as_deref()
-            .filter(|s| !s.is_empty())
-            .or(self.display_name.as_deref().filter(|s| !s.is_empty()))
-            .or(self.product_name.as_deref().filter(|s| !s.is_empty()))
-            .or(self.plan_name.as_deref().filter(|s| !s.is_empty()))
-    }
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct ModelConfigData {
-    client_model_configs: Option<Vec<ModelConfig>>,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct ModelConfig {
-    label: String,
-    model_or_alias: ModelAlias,
-    quota_info: Option<QuotaInfoResponse>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ModelAlias {
-    model: String,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct QuotaInfoResponse {
-    remaining_fraction: Option<f64>,
-    reset_time: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(untagged)]
-enum CodeValue {
-    Int(i32),
-    String(String),
-}
-
-impl CodeValue {
-    fn is_ok(&self) -> bool {
-        match self {
-            CodeValue::Int(v) => *v == 0,
-            CodeValue::String(s) => {
-                let lower = s.to_lowercase();
-                lower == "ok" || lower == "success" || s == "0"
-            }
-        }
-    }
-}
-
 // ============================================================================
 // Model Quota Selection (matching POC logic)
 // ============================================================================
@@ -286,9 +61,26 @@ impl ModelQuota {
     pub fn used_percent(&self) -> f64 {
         100.0 - self.remaining_percent()
     }
+
+    /// Convert to a per-model usage window.
+    pub fn to_window(&self) -> UsageWindow {
+        let id = if self.model_id.is_empty() {
+            &self.label
+        } else {
+            &self.model_id
+        };
+        UsageWindow {
+            resets_at: self.reset_time,
+            reset_description: Some(self.label.clone()),
+            ..UsageWindow::named(id, WindowKind::Model, self.used_percent()).with_label(&self.label)
+        }
+    }
 }
 
-/// Select and order models by priority (matching POC logic).
+/// Order models by priority (matching POC logic).
+///
+/// The preferred models come first, followed by every remaining model,
+/// most used first. No model is dropped.
 fn select_models(models: &[ModelQuota]) -> Vec<&ModelQuota> {
     let mut ordered = Vec::new();
 
@@ -311,16 +103,17 @@ fn select_models(models: &[ModelQuota]) -> Vec<&ModelQuota> {
         }
     }
 
-    // Fallback: sort by usage (most used first)
-    if ordered.is_empty() {
-        let mut sorted: Vec<_> = models.iter().collect();
-        sorted.sort_by(|a, b| {
-            b.used_percent()
-                .partial_cmp(&a.used_percent())
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-        return sorted;
-    }
+    // Remaining models, sorted by usage (most used first)
+    let mut rest: Vec<_> = models
+        .iter()
+        .filter(|m| !ordered.iter().any(|o| o.label == m.label))
+        .collect();
+    rest.sort_by(|a, b| {
+        b.used_percent()
+            .partial_cmp(&a.used_percent())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    ordered.extend(rest);
 
     ordered
 }
@@ -361,39 +154,27 @@ impl AntigravitySnapshot {
     /// Convert to a UsageSnapshot for display.
     pub fn to_usage_snapshot(&self) -> Result<UsageSnapshot, AntigravityError> {
         let ordered = select_models(&self.model_quotas);
-        let primary = ordered
-            .first()
-            .ok_or_else(|| AntigravityError::InvalidResponse("No quota models available".into()))?;
+        if ordered.is_empty() {
+            return Err(AntigravityError::InvalidResponse(
+                "No quota models available".into(),
+            ));
+        }
 
         let mut snapshot = UsageSnapshot::new();
         snapshot.fetch_source = FetchSource::LocalProbe;
 
-        // Primary window (most important model - usually Claude)
-        snapshot.primary = Some(UsageWindow {
-            used_percent: primary.used_percent(),
-            window_minutes: None,
-            resets_at: primary.reset_time,
-            reset_description: Some(primary.label.clone()),
-        });
-
-        // Secondary window
-        if let Some(secondary) = ordered.get(1) {
-            snapshot.secondary = Some(UsageWindow {
-                used_percent: secondary.used_percent(),
-                window_minutes: None,
-                resets_at: secondary.reset_time,
-                reset_description: Some(secondary.label.clone()),
-            });
-        }
-
-        // Tertiary window
-        if let Some(tertiary) = ordered.get(2) {
-            snapshot.tertiary = Some(UsageWindow {
-                used_percent: tertiary.used_percent(),
-                window_minutes: None,
-                resets_at: tertiary.reset_time,
-                reset_description: Some(tertiary.label.clone()),
-            });
+        // The three most important models (usually Claude first) fill the
+        // primary/secondary/tertiary slots; the rest follow as extra windows.
+        let slots = [
+            WindowSlot::Primary,
+            WindowSlot::Secondary,
+            WindowSlot::Tertiary,
+        ];
+        for (index, model) in ordered.iter().enumerate() {
+            match slots.get(index) {
+                Some(slot) => snapshot.set_slot(*slot, Some(model.to_window())),
+                None => snapshot.push_window(model.to_window()),
+            }
         }
 
         // Identity
@@ -414,51 +195,39 @@ impl AntigravitySnapshot {
 /// Antigravity local probe.
 #[derive(Debug)]
 pub struct AntigravityProbe {
-    http: reqwest::Client,
+    client: LanguageServerClient,
 }
 
 impl AntigravityProbe {
     /// Create a new probe.
     pub fn new() -> Self {
-        // Accept self-signed certs for localhost HTTPS
-        let http = reqwest::Client::builder()
-            .danger_accept_invalid_certs(true)
-            .timeout(std::time::Duration::from_secs(8))
-            .build()
-            .expect("Failed to build HTTP client");
-
-        Self { http }
+        Self {
+            client: LanguageServerClient::new(ANTIGRAVITY_LANGUAGE_SERVER),
+        }
     }
 
     /// Check if Antigravity is running.
     #[instrument(skip(self))]
     pub async fn is_running(&self) -> bool {
-        detect_process().is_ok()
+        self.client.is_running()
     }
 
     /// Fetch usage data from Antigravity.
     #[instrument(skip(self))]
     pub async fn fetch(&self) -> Result<AntigravitySnapshot, AntigravityError> {
-        let process = detect_process()?;
-        debug!(pid = process.pid, "Found Antigravity process");
-
-        let ports = if let Some(port) = process.extension_port {
-            vec![port]
-        } else {
-            detect_listening_ports(process.pid)?
-        };
-        debug!(?ports, "Detected listening ports");
-
-        let port = self.find_working_port(&ports, &process.csrf_token).await?;
-        debug!(port, "Found working API port");
+        let connection = self.client.connect().await?;
 
         // Try GetUserStatus first, fallback to GetCommandModelConfigs
-        match self.fetch_user_status(port, &process.csrf_token).await {
-            Ok(snapshot) => Ok(snapshot),
+        match self.client.get_user_status(&connection).await {
+            Ok(status) => Ok(snapshot_from_user_status(&status)),
             Err(e) => {
                 debug!(error = %e, "GetUserStatus failed, trying GetCommandModelConfigs");
-                self.fetch_command_model_configs(port, &process.csrf_token)
-                    .await
+                let configs = self.client.get_command_model_configs(&connection).await?;
+                Ok(AntigravitySnapshot {
+                    model_quotas: configs.iter().filter_map(quota_from_config).collect(),
+                    account_email: None,
+                    account_plan: None,
+                })
             }
         }
     }
@@ -469,106 +238,6 @@ impl AntigravityProbe {
         let snapshot = self.fetch().await?;
         snapshot.to_usage_snapshot()
     }
-
-    /// Find a working API port by testing connectivity.
-    async fn find_working_port(
-        &self,
-        ports: &[u16],
-        csrf_token: &str,
-    ) -> Result<u16, AntigravityError> {
-        for &port in ports {
-            if self.test_port(port, csrf_token).await {
-                return Ok(port);
-            }
-        }
-        Err(AntigravityError::PortDetectionFailed(
-            "no working API port".into(),
-        ))
-    }
-
-    /// Quick connectivity test using GetUnleashData endpoint.
-    async fn test_port(&self, port: u16, csrf_token: &str) -> bool {
-        let url = format!(
-            "https://127.0.0.1:{}/exa.language_server_pb.LanguageServerService/GetUnleashData",
-            port
-        );
-        let body = serde_json::json!({
-            "context": {
-                "properties": {
-                    "ide": "antigravity",
-                    "installationId": "exactobar"
-                }
-            }
-        });
-
-        self.post_request(&url, csrf_token, &body).await.is_ok()
-    }
-
-    /// Fetch user status from GetUserStatus endpoint.
-    async fn fetch_user_status(
-        &self,
-        port: u16,
-        csrf_token: &str,
-    ) -> Result<AntigravitySnapshot, AntigravityError> {
-        let url = format!("https://127.0.0.1:{}{}", port, GET_USER_STATUS_PATH);
-        let body = default_request_body();
-
-        let data = self.post_request(&url, csrf_token, &body).await?;
-        let response: UserStatusResponse = serde_json::from_slice(&data)
-            .map_err(|e| AntigravityError::InvalidResponse(e.to_string()))?;
-
-        parse_user_status_response(response)
-    }
-
-    /// Fetch model configs from GetCommandModelConfigs endpoint.
-    async fn fetch_command_model_configs(
-        &self,
-        port: u16,
-        csrf_token: &str,
-    ) -> Result<AntigravitySnapshot, AntigravityError> {
-        let url = format!("https://127.0.0.1:{}{}", port, GET_COMMAND_MODEL_PATH);
-        let body = default_request_body();
-
-        let data = self.post_request(&url, csrf_token, &body).await?;
-        let response: CommandModelConfigResponse = serde_json::from_slice(&data)
-            .map_err(|e| AntigravityError::InvalidResponse(e.to_string()))?;
-
-        parse_command_model_response(response)
-    }
-
-    /// Send a POST request to the API.
-    async fn post_request(
-        &self,
-        url: &str,
-        csrf_token: &str,
-        body: &serde_json::Value,
-    ) -> Result<Vec<u8>, AntigravityError> {
-        let response = self
-            .http
-            .post(url)
-            .header(CONTENT_TYPE, "application/json")
-            .header("Connect-Protocol-Version", "1")
-            .header("X-Codeium-Csrf-Token", csrf_token)
-            .json(body)
-            .send()
-            .await
-            .map_err(|e| AntigravityError::ConnectionFailed(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(AntigravityError::ApiError(format!(
-                "HTTP {}: {}",
-                status, body
-            )));
-        }
-
-        response
-            .bytes()
-            .await
-            .map(|b| b.to_vec())
-            .map_err(|e| AntigravityError::InvalidResponse(e.to_string()))
-    }
 }
 
 impl Default for AntigravityProbe {
@@ -578,75 +247,25 @@ impl Default for AntigravityProbe {
 }
 
 // ============================================================================
-// Request/Response Helpers
+// Response Helpers
 // ============================================================================
 
-fn default_request_body() -> serde_json::Value {
-    serde_json::json!({
-        "metadata": {
-            "ideName": "antigravity",
-            "extensionName": "antigravity",
-            "ideVersion": "unknown",
-            "locale": "en"
-        }
-    })
-}
-
-fn parse_user_status_response(
-    response: UserStatusResponse,
-) -> Result<AntigravitySnapshot, AntigravityError> {
-    if let Some(code) = &response.code {
-        if !code.is_ok() {
-            return Err(AntigravityError::ApiError(format!("code: {:?}", code)));
-        }
+fn snapshot_from_user_status(status: &UserStatus) -> AntigravitySnapshot {
+    AntigravitySnapshot {
+        model_quotas: status
+            .model_configs()
+            .iter()
+            .filter_map(quota_from_config)
+            .collect(),
+        account_email: status.email.clone(),
+        account_plan: status.plan_name(),
     }
-
-    let user_status = response
-        .user_status
-        .ok_or_else(|| AntigravityError::InvalidResponse("Missing userStatus".into()))?;
-
-    let model_configs = user_status
-        .cascade_model_config_data
-        .and_then(|d| d.client_model_configs)
-        .unwrap_or_default();
-
-    let models = model_configs.iter().filter_map(quota_from_config).collect();
-
-    let plan_name = user_status
-        .plan_status
-        .and_then(|ps| ps.plan_info)
-        .and_then(|pi| pi.preferred_name().map(String::from));
-
-    Ok(AntigravitySnapshot {
-        model_quotas: models,
-        account_email: user_status.email,
-        account_plan: plan_name,
-    })
-}
-
-fn parse_command_model_response(
-    response: CommandModelConfigResponse,
-) -> Result<AntigravitySnapshot, AntigravityError> {
-    if let Some(code) = &response.code {
-        if !code.is_ok() {
-            return Err(AntigravityError::ApiError(format!("code: {:?}", code)));
-        }
-    }
-
-    let model_configs = response.client_model_configs.unwrap_or_default();
-    let models = model_configs.iter().filter_map(quota_from_config).collect();
-
-    Ok(AntigravitySnapshot {
-        model_quotas: models,
-        account_email: None,
-        account_plan: None,
-    })
 }
 
 fn quota_from_config(config: &ModelConfig) -> Option<ModelQuota> {
     let quota = config.quota_info.as_ref()?;
 
-    let reset_time = quota.reset_time.as_ref().and_then(|s| parse_reset_time(s));
+    let reset_time = quota.reset_time.as_deref().and_then(parse_timestamp);
 
     Some(ModelQuota {
         label: config.label.clone(),
@@ -656,18 +275,6 @@ fn quota_from_config(config: &ModelConfig) -> Option<ModelQuota> {
     })
 }
 
-fn parse_reset_time(s: &str) -> Option<DateTime<Utc>> {
-    // Try ISO8601/RFC3339 first
-    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
-        return Some(dt.with_timezone(&Utc));
-    }
-    // Try unix timestamp (seconds)
-    if let Ok(secs) = s.parse::<i64>() {
-        return DateTime::from_timestamp(secs, 0);
-    }
-    None
-}
-
 // ============================================================================
 // Tests
 // ============================================================================
@@ -681,32 +288,6 @@ mod tests {
         let _ = AntigravityProbe::new();
     }
 
-    #[test]
-    fn test_extract_flag_equals() {
-        let cmd = "./server --csrf_token=abc123 --port=8080";
-        assert_eq!(
-            extract_flag("--csrf_token", cmd),
-            Some("abc123".to_string())
-        );
-        assert_eq!(extract_flag("--port", cmd), Some("8080".to_string()));
-    }
-
-    #[test]
-    fn test_extract_flag_space() {
-        let cmd = "./server --csrf_token abc123 --port 8080";
-        assert_eq!(
-            extract_flag("--csrf_token", cmd),
-            Some("abc123".to_string())
-        );
-        assert_eq!(extract_flag("--port", cmd), Some("8080".to_string()));
-    }
-
-    #[test]
-    fn test_extract_flag_missing() {
-        let cmd = "./server --other_flag=value";
-        assert_eq!(extract_flag("--csrf_token", cmd), None);
-    }
-
     #[test]
     fn test_is_antigravity_command() {
         assert!(is_antigravity_command(
@@ -718,18 +299,6 @@ mod tests {
         assert!(!is_antigravity_command("--app_data_dir /path/other/data"));
     }
 
-    #[test]
-    fn test_parse_port_from_lsof() {
-        let line = "node    12345 user   23u  IPv4 0x123  0t0  TCP 127.0.0.1:42069 (LISTEN)";
-        assert_eq!(parse_port_from_lsof_line(line), Some(42069));
-    }
-
-    #[test]
-    fn test_parse_port_no_listen() {
-        let line = "node    12345 user   23u  IPv4 0x123  0t0  TCP 127.0.0.1:42069";
-        assert_eq!(parse_port_from_lsof_line(line), None);
-    }
-
     #[test]
     fn test_model_quota_percent() {
         let quota = ModelQuota {
@@ -772,6 +341,7 @@ mod tests {
         ];
         let selected = select_models(&models);
         assert_eq!(selected[0].label, "Claude without Thinking");
+        assert_eq!(selected.len(), 2);
     }
 
     #[test]
@@ -796,38 +366,8 @@ mod tests {
     }
 
     #[test]
-    fn test_code_value_is_ok() {
-        assert!(CodeValue::Int(0).is_ok());
-        assert!(!CodeValue::Int(1).is_ok());
-        assert!(CodeValue::String("ok".to_string()).is_ok());
-        assert!(CodeValue::String("OK".to_string()).is_ok());
-        assert!(CodeValue::String("success".to_string()).is_ok());
-        assert!(!CodeValue::String("error".to_string()).is_ok());
-    }
-
-    #[test]
-    fn test_parse_reset_time_rfc3339() {
-        let result = parse_reset_time("2024-01-15T12:00:00Z");
-        assert!(result.is_some());
-    }
-
-    #[test]
-    fn test_parse_reset_time_unix() {
-        let result = parse_reset_time("1705320000");
-        assert!(result.is_some());
-    }
-
-    #[test]
-    fn test_parse_reset_time_invalid() {
-        let result = parse_reset_time("not-a-date");
-        assert!(result.is_none());
-    }
-
-    #[test]
-    fn test_parse_user_status_response() {
+    fn test_snapshot_from_user_status() {
         let json = r#"{
-            "code": 0,
-            "userStatus": {
                 "email": "test@example.com",
                 "planStatus": {
                     "planInfo": {
@@ -843,11 +383,10 @@ mod tests {
                         }
                     ]
                 }
-            }
         }"#;
 
-        let response: UserStatusResponse = serde_json::from_str(json).unwrap();
-        let snapshot = parse_user_status_response(response).unwrap();
+        let status: UserStatus = serde_json::from_str(json).unwrap();
+        let snapshot = snapshot_from_user_status(&status);
 
         assert_eq!(snapshot.account_email, Some("test@example.com".to_string()));
         assert_eq!(snapshot.account_plan, Some("Pro Plan".to_string()));
@@ -869,8 +408,43 @@ mod tests {
         };
 
         let usage = snapshot.to_usage_snapshot().unwrap();
-        assert!(usage.primary.is_some());
-        assert_eq!(usage.primary.as_ref().unwrap().used_percent, 40.0);
+        assert!(usage.primary().is_some());
+        assert_eq!(usage.primary().unwrap().used_percent, 40.0);
         assert!(usage.identity.is_some());
     }
+
+    #[test]
+    fn test_snapshot_keeps_every_model() {
+        let quota = |label: &str, model_id: &str, remaining: f64| ModelQuota {
+            label: label.to_string(),
+            model_id: model_id.to_string(),
+            remaining_fraction: Some(remaining),
+            reset_time: None,
+        };
+        let snapshot = AntigravitySnapshot {
+            model_quotas: vec![
+                quota("Gemini 3 Pro (High)", "gemini-pro-high", 0.9),
+                quota("Gemini 3 Pro (Low)", "gemini-pro-low", 0.8),
+                quota("Claude Sonnet 4.5", "claude-sonnet", 0.5),
+                quota("Gemini 3 Flash", "gemini-flash", 0.7),
+                quota("GPT-OSS 120B", "gpt-oss", 0.2),
+            ],
+            account_email: None,
+            account_plan: None,
+        };
+
+        let usage = snapshot.to_usage_snapshot().unwrap();
+
+        assert_eq!(usage.windows.len(), 5);
+        assert_eq!(usage.primary().unwrap().id, "claude-sonnet");
+        assert_eq!(usage.secondary().unwrap().id, "gemini-pro-low");
+        assert_eq!(usage.tertiary().unwrap().id, "gemini-flash");
+        // Remaining models follow, most used first
+        assert_eq!(usage.windows[3].id, "gpt-oss");
+        assert_eq!(usage.windows[4].id, "gemini-pro-high");
+        assert_eq!(
+            usage.window("gpt-oss").unwrap().label.as_deref(),
+            Some("GPT-OSS 120B")
+        );
+    }
 }
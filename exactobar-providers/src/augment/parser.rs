@@ -1,7 +1,7 @@
 //! Augment response parser.
 
 use exactobar_core::{
-    FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageSnapshot, UsageWindow,
+    Credits, FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageSnapshot, UsageWindow,
 };
 use exactobar_fetch::FetchError;
 use serde::Deserialize;
@@ -45,7 +45,8 @@ pub fn parse_augment_response(json_str: &str) -> Result<UsageSnapshot, FetchErro
             } else {
                 0.0
             };
-            snapshot.primary = Some(UsageWindow::new(percent));
+            snapshot.set_primary(Some(UsageWindow::new(percent)));
+            snapshot.credits = Some(Credits::new((total - used).max(0.0)).with_total(total));
         }
 
         if let (Some(monthly_used), Some(monthly_total)) =
@@ -56,7 +57,7 @@ pub fn parse_augment_response(json_str: &str) -> Result<UsageSnapshot, FetchErro
             } else {
                 0.0
             };
-            snapshot.secondary = Some(UsageWindow::new(percent));
+            snapshot.set_secondary(Some(UsageWindow::new(percent)));
         }
     }
 
@@ -82,16 +83,20 @@ mod tests {
             "user": {"email": "user@example.com"}
         }"#;
         let snapshot = parse_augment_response(json).unwrap();
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.unwrap().used_percent, 25.0);
-        assert!(snapshot.secondary.is_some());
-        assert_eq!(snapshot.secondary.unwrap().used_percent, 25.0);
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 25.0);
+        assert!(snapshot.secondary().is_some());
+        assert_eq!(snapshot.secondary().unwrap().used_percent, 25.0);
+
+        let credits = snapshot.credits.unwrap();
+        assert_eq!(credits.remaining, 75.0);
+        assert_eq!(credits.total, Some(100.0));
     }
 
     #[test]
     fn test_parse_empty() {
         let json = r#"{}"#;
         let snapshot = parse_augment_response(json).unwrap();
-        assert!(snapshot.primary.is_none());
+        assert!(snapshot.primary().is_none());
     }
 }
@@ -92,7 +92,7 @@ impl AugmentUsageResponse {
         snapshot.fetch_source = FetchSource::Web;
 
         if let Some(percent) = self.get_percent() {
-            snapshot.primary = Some(UsageWindow::new(percent));
+            snapshot.set_primary(Some(UsageWindow::new(percent)));
         }
 
         let mut identity = ProviderIdentity::new(ProviderKind::Augment);
@@ -259,7 +259,7 @@ mod tests {
         };
 
         let snapshot = response.to_snapshot();
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.unwrap().used_percent, 50.0);
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 50.0);
     }
 }
@@ -0,0 +1,1087 @@
+//! Azure OpenAI usage via Azure Resource Manager and Azure Monitor.
+//!
+//! # API Endpoints
+//!
+//! ```text
+//! GET {arm}{resource_id}?api-version=2024-10-01
+//! GET {arm}{resource_id}/deployments?api-version=2024-10-01
+//! GET {arm}{resource_id}/providers/Microsoft.Insights/metrics
+//!     ?api-version=2023-10-01&metricnames=ProcessedPromptTokens,GeneratedTokens,AzureOpenAIRequests
+//!     &timespan={start}/{end}&interval=PT1M&aggregation=Total
+//!     &$filter=ModelDeploymentName eq '*'
+//! Authorization: Bearer {ARM token}
+//! ```
+//!
+//! `resource_id` is the Azure OpenAI (or AI Foundry) account, e.g.
+//! `/subscriptions/{sub}/resourceGroups/{rg}/providers/Microsoft.CognitiveServices/accounts/{name}`.
+//!
+//! # Quotas
+//!
+//! Each deployment reports its limits in `properties.rateLimits`:
+//!
+//! ```json
+//! {"name": "gpt-4o", "sku": {"name": "GlobalStandard", "capacity": 450},
+//!  "properties": {"model": {"format": "OpenAI", "name": "gpt-4o", "version": "2024-08-06"},
+//!   "rateLimits": [{"key": "request", "renewalPeriod": 10, "count": 450},
+//!                  {"key": "token", "renewalPeriod": 60, "count": 450000}]}}
+//! ```
+//!
+//! Older deployments without `rateLimits` get 1,000 TPM and 6 RPM per unit
+//! of `sku.capacity`. Provisioned (PTU) deployments have no TPM quota.
+
+use chrono::{DateTime, Duration, NaiveDate, SecondsFormat, Utc};
+use exactobar_core::{
+    CostUsageSnapshot, DailyUsageEntry, FetchSource, LoginMethod, ModelBreakdown, ProviderIdentity,
+    ProviderKind, UsageSnapshot, UsageWindow, WindowKind, WindowSlot,
+};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+use tracing::{debug, instrument, warn};
+
+use super::az::AzureToken;
+use super::error::AzureError;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Public-cloud Azure Resource Manager endpoint.
+pub const ARM_BASE_URL: &str = "https://management.azure.com";
+
+/// Environment variable for the account's resource ID.
+pub const RESOURCE_ID_ENV: &str = "AZURE_OPENAI_RESOURCE_ID";
+
+const ACCOUNTS_API_VERSION: &str = "2024-10-01";
+const METRICS_API_VERSION: &str = "2023-10-01";
+
+const PROMPT_TOKENS_METRIC: &str = "ProcessedPromptTokens";
+const GENERATED_TOKENS_METRIC: &str = "GeneratedTokens";
+const REQUESTS_METRIC: &str = "AzureOpenAIRequests";
+
+/// Unit for token counts on usage windows.
+const TOKENS_UNIT: &str = "tokens";
+
+/// Days of daily token history to read.
+const HISTORY_DAYS: i64 = 30;
+
+/// TPM per unit of `sku.capacity` for standard deployments.
+const TOKENS_PER_CAPACITY_UNIT: f64 = 1000.0;
+
+/// RPM per unit of `sku.capacity` for standard deployments.
+const REQUESTS_PER_CAPACITY_UNIT: f64 = 6.0;
+
+// ============================================================================
+// Resource ID
+// ============================================================================
+
+/// An Azure OpenAI account's resource ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AzureResourceId {
+    /// Subscription ID.
+    pub subscription_id: String,
+    /// Resource group.
+    pub resource_group: String,
+    /// Account name.
+    pub account_name: String,
+}
+
+impl AzureResourceId {
+    /// Parses a `Microsoft.CognitiveServices/accounts` resource ID.
+    pub fn parse(id: &str) -> Result<Self, AzureError> {
+        let invalid = || AzureError::InvalidResource(id.to_string());
+        let parts: Vec<&str> = id.trim().trim_matches('/').split('/').collect();
+        let [
+            "subscriptions" | "Subscriptions",
+            subscription_id,
+            resource_groups,
+            resource_group,
+            providers,
+            namespace,
+            accounts,
+            account_name,
+        ] = parts.as_slice()
+        else {
+            return Err(invalid());
+        };
+
+        let keys_ok = resource_groups.eq_ignore_ascii_case("resourceGroups")
+            && providers.eq_ignore_ascii_case("providers")
+            && namespace.eq_ignore_ascii_case("Microsoft.CognitiveServices")
+            && accounts.eq_ignore_ascii_case("accounts");
+        let values_ok = [subscription_id, resource_group, account_name]
+            .iter()
+            .all(|v| !v.is_empty());
+        if !keys_ok || !values_ok {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            subscription_id: (*subscription_id).to_string(),
+            resource_group: (*resource_group).to_string(),
+            account_name: (*account_name).to_string(),
+        })
+    }
+
+    /// Returns the canonical resource path.
+    pub fn path(&self) -> String {
+        format!(
+            "/subscriptions/{}/resourceGroups/{}/providers/Microsoft.CognitiveServices/accounts/{}",
+            self.subscription_id, self.resource_group, self.account_name
+        )
+    }
+}
+
+// ============================================================================
+// Wire Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct ListResponse<T> {
+    #[serde(default = "Vec::new")]
+    value: Vec<T>,
+    #[serde(rename = "nextLink", default)]
+    next_link: Option<String>,
+}
+
+/// Account details.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AzureAccount {
+    /// Account name.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Region.
+    #[serde(default)]
+    pub location: Option<String>,
+    /// Account kind ("OpenAI" or "AIServices").
+    #[serde(default)]
+    pub kind: Option<String>,
+}
+
+/// Deployment SKU.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DeploymentSku {
+    /// SKU name (e.g., "Standard", "GlobalStandard", "ProvisionedManaged").
+    #[serde(default)]
+    pub name: String,
+    /// Capacity units.
+    #[serde(default)]
+    pub capacity: Option<f64>,
+}
+
+impl DeploymentSku {
+    /// Returns true for provisioned throughput (PTU) deployments.
+    pub fn is_provisioned(&self) -> bool {
+        self.name.to_lowercase().contains("provisioned")
+    }
+}
+
+/// Deployed model.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DeploymentModel {
+    /// Model name (e.g., "gpt-4o").
+    #[serde(default)]
+    pub name: String,
+    /// Model version.
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+/// A rate limit on a deployment.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimit {
+    /// "token" or "request".
+    pub key: String,
+    /// Window length in seconds.
+    #[serde(default)]
+    pub renewal_period: Option<f64>,
+    /// Allowed count per window.
+    #[serde(default)]
+    pub count: Option<f64>,
+}
+
+impl RateLimit {
+    /// The limit scaled to one minute.
+    pub fn per_minute(&self) -> Option<f64> {
+        let count = self.count?;
+        let period = self.renewal_period.filter(|p| *p > 0.0).unwrap_or(60.0);
+        Some(count * 60.0 / period)
+    }
+}
+
+/// Deployment properties.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeploymentProperties {
+    /// Deployed model.
+    #[serde(default)]
+    pub model: Option<DeploymentModel>,
+    /// Rate limits.
+    #[serde(default)]
+    pub rate_limits: Vec<RateLimit>,
+}
+
+/// A model deployment.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AzureDeployment {
+    /// Deployment name.
+    pub name: String,
+    /// SKU.
+    #[serde(default)]
+    pub sku: Option<DeploymentSku>,
+    /// Properties.
+    #[serde(default)]
+    pub properties: DeploymentProperties,
+}
+
+impl AzureDeployment {
+    fn limit(&self, key: &str, per_unit: f64) -> Option<f64> {
+        if let Some(limit) = self
+            .properties
+            .rate_limits
+            .iter()
+            .find(|l| l.key.eq_ignore_ascii_case(key))
+            .and_then(RateLimit::per_minute)
+        {
+            return Some(limit);
+        }
+        let sku = self.sku.as_ref()?;
+        if sku.is_provisioned() {
+            return None;
+        }
+        sku.capacity.map(|c| c * per_unit)
+    }
+
+    /// Tokens-per-minute quota.
+    pub fn tokens_per_minute(&self) -> Option<f64> {
+        self.limit("token", TOKENS_PER_CAPACITY_UNIT)
+    }
+
+    /// Requests-per-minute quota.
+    pub fn requests_per_minute(&self) -> Option<f64> {
+        self.limit("request", REQUESTS_PER_CAPACITY_UNIT)
+    }
+
+    /// The deployed model's name.
+    pub fn model_name(&self) -> Option<&str> {
+        self.properties.model.as_ref().map(|m| m.name.as_str())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct MetricsResponse {
+    #[serde(default)]
+    value: Vec<Metric>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Metric {
+    name: LocalizableString,
+    #[serde(default)]
+    timeseries: Vec<TimeSeries>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LocalizableString {
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimeSeries {
+    #[serde(default)]
+    metadatavalues: Vec<MetadataValue>,
+    #[serde(default)]
+    data: Vec<MetricValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetadataValue {
+    name: LocalizableString,
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MetricValue {
+    time_stamp: DateTime<Utc>,
+    #[serde(default)]
+    total: Option<f64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ArmErrorBody {
+    #[serde(default)]
+    error: Option<ArmError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArmError {
+    #[serde(default)]
+    code: String,
+    #[serde(default)]
+    message: String,
+}
+
+/// One metric sample for a deployment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricSample {
+    /// Metric name.
+    pub metric: String,
+    /// Deployment name.
+    pub deployment: String,
+    /// Start of the interval.
+    pub time: DateTime<Utc>,
+    /// Total over the interval.
+    pub total: f64,
+}
+
+// ============================================================================
+// Usage Model
+// ============================================================================
+
+/// Token and request counts for one deployment on one day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DayTokens {
+    /// Prompt tokens.
+    pub prompt: u64,
+    /// Generated (completion) tokens.
+    pub generated: u64,
+    /// Requests.
+    pub requests: u64,
+}
+
+impl DayTokens {
+    /// Prompt plus generated tokens.
+    pub fn total(&self) -> u64 {
+        self.prompt + self.generated
+    }
+}
+
+/// Usage for one deployment.
+#[derive(Debug, Clone, Default)]
+pub struct DeploymentUsage {
+    /// Deployment name.
+    pub name: String,
+    /// Deployed model.
+    pub model: Option<String>,
+    /// Tokens-per-minute quota.
+    pub tpm_limit: Option<f64>,
+    /// Requests-per-minute quota.
+    pub rpm_limit: Option<f64>,
+    /// Highest tokens per minute over the last hour.
+    pub peak_tokens_per_minute: f64,
+    /// Highest requests per minute over the last hour.
+    pub peak_requests_per_minute: f64,
+    /// Daily counts.
+    pub daily: BTreeMap<NaiveDate, DayTokens>,
+}
+
+impl DeploymentUsage {
+    /// Creates usage for a deployment with its quotas.
+    pub fn from_deployment(deployment: &AzureDeployment) -> Self {
+        Self {
+            name: deployment.name.clone(),
+            model: deployment.model_name().map(String::from),
+            tpm_limit: deployment.tokens_per_minute(),
+            rpm_limit: deployment.requests_per_minute(),
+            ..Self::default()
+        }
+    }
+
+    /// Peak TPM as a percentage of the quota.
+    pub fn tpm_used_percent(&self) -> Option<f64> {
+        percent(self.peak_tokens_per_minute, self.tpm_limit)
+    }
+
+    /// Peak RPM as a percentage of the quota.
+    pub fn rpm_used_percent(&self) -> Option<f64> {
+        percent(self.peak_requests_per_minute, self.rpm_limit)
+    }
+
+    fn label(&self) -> String {
+        match &self.model {
+            Some(model) if *model != self.name => format!("{} ({})", self.name, model),
+            _ => self.name.clone(),
+        }
+    }
+
+    /// Per-deployment window: the tighter of TPM and RPM headroom, with
+    /// token counts.
+    fn window(&self) -> Option<UsageWindow> {
+        let used_percent = match (self.tpm_used_percent(), self.rpm_used_percent()) {
+            (Some(tpm), Some(rpm)) => tpm.max(rpm),
+            (tpm, rpm) => tpm.or(rpm)?,
+        };
+        let window = UsageWindow::named(&self.name, WindowKind::Model, used_percent)
+            .with_label(self.label());
+        let window = if self.tpm_limit.is_some() {
+            window.with_counts(self.peak_tokens_per_minute, self.tpm_limit, TOKENS_UNIT)
+        } else {
+            window.with_counts(self.peak_requests_per_minute, self.rpm_limit, "requests")
+        };
+        Some(UsageWindow {
+            window_minutes: Some(1),
+            ..window
+        })
+    }
+}
+
+fn percent(used: f64, limit: Option<f64>) -> Option<f64> {
+    let limit = limit.filter(|l| *l > 0.0)?;
+    Some((used / limit * 100.0).clamp(0.0, 100.0))
+}
+
+/// Azure OpenAI usage for one account.
+#[derive(Debug, Clone)]
+pub struct AzureUsage {
+    /// The account.
+    pub resource: AzureResourceId,
+    /// Account details, if readable.
+    pub account: AzureAccount,
+    /// Per-deployment usage.
+    pub deployments: Vec<DeploymentUsage>,
+    /// Signed-in user or service principal.
+    pub signed_in_as: Option<String>,
+}
+
+impl AzureUsage {
+    /// Total tokens across deployments on a day.
+    pub fn tokens_on(&self, date: NaiveDate) -> u64 {
+        self.deployments
+            .iter()
+            .filter_map(|d| d.daily.get(&date))
+            .map(DayTokens::total)
+            .sum()
+    }
+
+    /// Builds the daily token snapshot, newest day first.
+    ///
+    /// Breakdowns are per deployment, since one model can back several.
+    fn to_cost_snapshot(&self, now: DateTime<Utc>) -> CostUsageSnapshot {
+        let mut days: BTreeMap<NaiveDate, Vec<(&str, DayTokens)>> = BTreeMap::new();
+        for deployment in &self.deployments {
+            for (date, tokens) in &deployment.daily {
+                days.entry(*date)
+                    .or_default()
+                    .push((deployment.name.as_str(), *tokens));
+            }
+        }
+
+        let mut cost = CostUsageSnapshot::new();
+        cost.updated_at = now;
+        cost.daily = days
+            .into_iter()
+            .rev()
+            .map(|(date, deployments)| {
+                let mut entry = DailyUsageEntry::new(date.format("%Y-%m-%d").to_string());
+                let prompt = deployments.iter().map(|(_, t)| t.prompt).sum();
+                let generated = deployments.iter().map(|(_, t)| t.generated).sum();
+                entry.input_tokens = Some(prompt);
+                entry.output_tokens = Some(generated);
+                entry.total_tokens = Some(prompt + generated);
+                entry.models_used = Some(
+                    deployments
+                        .iter()
+                        .map(|(name, _)| (*name).to_string())
+                        .collect(),
+                );
+                entry.model_breakdowns = Some(
+                    deployments
+                        .iter()
+                        .map(|(name, tokens)| ModelBreakdown {
+                            input_tokens: Some(tokens.prompt),
+                            output_tokens: Some(tokens.generated),
+                            ..ModelBreakdown::new(*name)
+                        })
+                        .collect(),
+                );
+                entry
+            })
+            .collect();
+
+        cost.last_30_days_tokens = Some(cost.total_daily_tokens());
+        cost.session_tokens = Some(self.tokens_on(now.date_naive()));
+        cost
+    }
+
+    /// Convert to a UsageSnapshot for display.
+    ///
+    /// The primary window is the busiest deployment's peak TPM over the last
+    /// hour against its quota, and the secondary its peak RPM. Every
+    /// deployment with a quota gets its own window. Without quotas, the
+    /// primary window shows today's tokens instead.
+    pub fn to_snapshot(&self, fetch_source: FetchSource, now: DateTime<Utc>) -> UsageSnapshot {
+        let mut snapshot = UsageSnapshot::new();
+        snapshot.fetch_source = fetch_source;
+
+        let token_peak = self
+            .deployments
+            .iter()
+            .filter_map(|d| d.tpm_used_percent().map(|p| (p, d)))
+            .max_by(|a, b| a.0.total_cmp(&b.0));
+        let request_peak = self
+            .deployments
+            .iter()
+            .filter_map(|d| d.rpm_used_percent().map(|p| (p, d)))
+            .max_by(|a, b| a.0.total_cmp(&b.0));
+
+        let primary = if let Some((used_percent, d)) = token_peak {
+            UsageWindow {
+                window_minutes: Some(1),
+                ..UsageWindow::named("tpm_headroom", WindowKind::Other, used_percent)
+                    .with_label(format!("Peak TPM ({})", d.name))
+                    .with_counts(d.peak_tokens_per_minute, d.tpm_limit, TOKENS_UNIT)
+            }
+        } else {
+            #[allow(clippy::cast_precision_loss)]
+            let today = self.tokens_on(now.date_naive()) as f64;
+            UsageWindow {
+                window_minutes: Some(24 * 60),
+                ..UsageWindow::named("tokens_today", WindowKind::Session, 0.0)
+                    .with_label("Tokens today")
+                    .with_counts(today, None, TOKENS_UNIT)
+            }
+        };
+        snapshot.set_slot(WindowSlot::Primary, Some(primary));
+
+        snapshot.set_slot(
+            WindowSlot::Secondary,
+            request_peak.map(|(used_percent, d)| UsageWindow {
+                window_minutes: Some(1),
+                ..UsageWindow::named("rpm_headroom", WindowKind::Other, used_percent)
+                    .with_label(format!("Peak RPM ({})", d.name))
+                    .with_counts(d.peak_requests_per_minute, d.rpm_limit, "requests")
+            }),
+        );
+
+        for window in self.deployments.iter().filter_map(DeploymentUsage::window) {
+            snapshot.push_window(window);
+        }
+
+        snapshot.cost = Some(self.to_cost_snapshot(now));
+
+        let mut identity = ProviderIdentity::new(ProviderKind::Azure);
+        identity.account_email = self.signed_in_as.clone();
+        identity.account_organization = Some(match &self.account.location {
+            Some(location) => format!("{} ({})", self.resource.account_name, location),
+            None => self.resource.account_name.clone(),
+        });
+        identity.login_method = Some(match fetch_source {
+            FetchSource::CLI => LoginMethod::CLI,
+            _ => LoginMethod::OAuth,
+        });
+        snapshot.identity = Some(identity);
+
+        snapshot
+    }
+}
+
+// ============================================================================
+// API Client
+// ============================================================================
+
+/// Azure Resource Manager client for one Azure OpenAI account.
+#[derive(Debug, Clone)]
+pub struct AzureApiClient {
+    base_url: String,
+    resource: AzureResourceId,
+    http: reqwest::Client,
+}
+
+impl AzureApiClient {
+    /// Creates a client for the public cloud.
+    pub fn new(resource: AzureResourceId) -> Self {
+        Self::with_base_url(ARM_BASE_URL, resource)
+    }
+
+    /// Creates a client with a custom Resource Manager URL.
+    pub fn with_base_url(base_url: impl Into<String>, resource: AzureResourceId) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            resource,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Resolves the resource ID from settings, then `AZURE_OPENAI_RESOURCE_ID`.
+    pub fn resolve_resource(configured: Option<&str>) -> Result<AzureResourceId, AzureError> {
+        let id = configured
+            .map(String::from)
+            .or_else(|| std::env::var(RESOURCE_ID_ENV).ok())
+            .filter(|id| !id.trim().is_empty())
+            .ok_or(AzureError::ResourceNotConfigured)?;
+        AzureResourceId::parse(&id)
+    }
+
+    /// Fetches deployments, their quotas and Azure Monitor token metrics.
+    ///
+    /// Deployments are required; account details and metrics are
+    /// best-effort.
+    #[instrument(skip(self, token))]
+    pub async fn fetch_usage(
+        &self,
+        token: &AzureToken,
+        now: DateTime<Utc>,
+    ) -> Result<AzureUsage, AzureError> {
+        let deployments = self.list_deployments(token).await?;
+        debug!(count = deployments.len(), "Found Azure OpenAI deployments");
+
+        let account = self
+            .get::<AzureAccount>(
+                token,
+                &self.resource.path(),
+                &[("api-version", ACCOUNTS_API_VERSION)],
+            )
+            .await
+            .unwrap_or_else(|e| {
+                debug!(error = %e, "Failed to fetch Azure OpenAI account");
+                AzureAccount::default()
+            });
+
+        let mut usage: Vec<DeploymentUsage> = deployments
+            .iter()
+            .map(DeploymentUsage::from_deployment)
+            .collect();
+
+        let day_start = (now - Duration::days(HISTORY_DAYS - 1))
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .map_or(now, |dt| dt.and_utc());
+        match self.get_metrics(token, day_start, now, "P1D").await {
+            Ok(samples) => apply_daily(&mut usage, &samples),
+            Err(e) => warn!(error = %e, "Failed to fetch Azure OpenAI daily metrics"),
+        }
+        match self
+            .get_metrics(token, now - Duration::hours(1), now, "PT1M")
+            .await
+        {
+            Ok(samples) => apply_peaks(&mut usage, &samples),
+            Err(e) => warn!(error = %e, "Failed to fetch Azure OpenAI minute metrics"),
+        }
+
+        Ok(AzureUsage {
+            resource: self.resource.clone(),
+            account,
+            deployments: usage,
+            signed_in_as: token.account.clone(),
+        })
+    }
+
+    /// Lists the account's deployments.
+    pub async fn list_deployments(
+        &self,
+        token: &AzureToken,
+    ) -> Result<Vec<AzureDeployment>, AzureError> {
+        let path = format!("{}/deployments", self.resource.path());
+        let mut page: ListResponse<AzureDeployment> = self
+            .get(token, &path, &[("api-version", ACCOUNTS_API_VERSION)])
+            .await?;
+        let mut deployments = std::mem::take(&mut page.value);
+
+        while let Some(next) = page.next_link.take() {
+            page = self.get_url(token, &next, &[]).await?;
+            deployments.append(&mut page.value);
+        }
+
+        Ok(deployments)
+    }
+
+    /// Reads token and request totals per deployment.
+    pub async fn get_metrics(
+        &self,
+        token: &AzureToken,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        interval: &str,
+    ) -> Result<Vec<MetricSample>, AzureError> {
+        let path = format!(
+            "{}/providers/Microsoft.Insights/metrics",
+            self.resource.path()
+        );
+        let metric_names = [
+            PROMPT_TOKENS_METRIC,
+            GENERATED_TOKENS_METRIC,
+            REQUESTS_METRIC,
+        ]
+        .join(",");
+        let timespan = format!(
+            "{}/{}",
+            start.to_rfc3339_opts(SecondsFormat::Secs, true),
+            end.to_rfc3339_opts(SecondsFormat::Secs, true)
+        );
+
+        let response: MetricsResponse = self
+            .get(
+                token,
+                &path,
+                &[
+                    ("api-version", METRICS_API_VERSION),
+                    ("metricnames", &metric_names),
+                    ("timespan", &timespan),
+                    ("interval", interval),
+                    ("aggregation", "Total"),
+                    ("$filter", "ModelDeploymentName eq '*'"),
+                ],
+            )
+            .await?;
+
+        let mut samples = Vec::new();
+        for metric in response.value {
+            for series in metric.timeseries {
+                let Some(deployment) = series
+                    .metadatavalues
+                    .iter()
+                    .find(|m| m.name.value.eq_ignore_ascii_case("ModelDeploymentName"))
+                    .map(|m| m.value.clone())
+                else {
+                    continue;
+                };
+                samples.extend(series.data.into_iter().filter_map(|point| {
+                    Some(MetricSample {
+                        metric: metric.name.value.clone(),
+                        deployment: deployment.clone(),
+                        time: point.time_stamp,
+                        total: point.total?,
+                    })
+                }));
+            }
+        }
+        Ok(samples)
+    }
+
+    async fn get<T: DeserializeOwned>(
+        &self,
+        token: &AzureToken,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, AzureError> {
+        let url = format!("{}{}", self.base_url, path);
+        self.get_url(token, &url, query).await
+    }
+
+    async fn get_url<T: DeserializeOwned>(
+        &self,
+        token: &AzureToken,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, AzureError> {
+        debug!(url = %url, "Azure Resource Manager request");
+        let response = self
+            .http
+            .get(url)
+            .query(query)
+            .bearer_auth(&token.access_token)
+            .send()
+            .await?;
+
+        let status = response.status();
+        let text = response.text().await?;
+        if !status.is_success() {
+            return Err(api_error(status, &text));
+        }
+
+        serde_json::from_str(&text).map_err(|e| AzureError::ParseError(e.to_string()))
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn deployment_mut<'a>(
+    usage: &'a mut [DeploymentUsage],
+    name: &str,
+) -> Option<&'a mut DeploymentUsage> {
+    usage.iter_mut().find(|d| d.name.eq_ignore_ascii_case(name))
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn apply_daily(usage: &mut [DeploymentUsage], samples: &[MetricSample]) {
+    for sample in samples {
+        let Some(deployment) = deployment_mut(usage, &sample.deployment) else {
+            continue;
+        };
+        let day = deployment
+            .daily
+            .entry(sample.time.date_naive())
+            .or_default();
+        let value = sample.total.max(0.0).round() as u64;
+        match sample.metric.as_str() {
+            PROMPT_TOKENS_METRIC => day.prompt += value,
+            GENERATED_TOKENS_METRIC => day.generated += value,
+            REQUESTS_METRIC => day.requests += value,
+            _ => {}
+        }
+    }
+}
+
+fn apply_peaks(usage: &mut [DeploymentUsage], samples: &[MetricSample]) {
+    // Prompt and generated tokens share the per-minute budget
+    let mut tokens: BTreeMap<(String, DateTime<Utc>), f64> = BTreeMap::new();
+    let mut requests: BTreeMap<(String, DateTime<Utc>), f64> = BTreeMap::new();
+    for sample in samples {
+        let key = (sample.deployment.to_lowercase(), sample.time);
+        match sample.metric.as_str() {
+            PROMPT_TOKENS_METRIC | GENERATED_TOKENS_METRIC => {
+                *tokens.entry(key).or_default() += sample.total;
+            }
+            REQUESTS_METRIC => *requests.entry(key).or_default() += sample.total,
+            _ => {}
+        }
+    }
+
+    for deployment in usage.iter_mut() {
+        let name = deployment.name.to_lowercase();
+        let peak = |map: &BTreeMap<(String, DateTime<Utc>), f64>| {
+            map.iter()
+                .filter(|((n, _), _)| *n == name)
+                .map(|(_, v)| *v)
+                .fold(0.0, f64::max)
+        };
+        deployment.peak_tokens_per_minute = peak(&tokens);
+        deployment.peak_requests_per_minute = peak(&requests);
+    }
+}
+
+/// Maps a Resource Manager error response to an error.
+fn api_error(status: reqwest::StatusCode, text: &str) -> AzureError {
+    let body: ArmErrorBody = serde_json::from_str(text).unwrap_or_default();
+    let (code, message) = body
+        .error
+        .map_or((String::new(), text.to_string()), |e| (e.code, e.message));
+
+    if status == reqwest::StatusCode::UNAUTHORIZED
+        || status == reqwest::StatusCode::FORBIDDEN
+        || code == "ExpiredAuthenticationToken"
+        || code == "InvalidAuthenticationToken"
+    {
+        return AzureError::AuthenticationFailed(format!("{}: {}", code, message));
+    }
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return AzureError::InvalidResource(message);
+    }
+
+    AzureError::ApiError(format!("HTTP {}: {} {}", status, code, message))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::serve_stub;
+    use chrono::TimeZone;
+
+    const RESOURCE_ID: &str = "/subscriptions/sub-1/resourceGroups/ai-rg/providers/Microsoft.CognitiveServices/accounts/contoso-openai";
+
+    const DEPLOYMENTS_JSON: &str = r#"{"value": [
+        {"name": "gpt-4o", "sku": {"name": "GlobalStandard", "capacity": 450},
+         "properties": {"model": {"format": "OpenAI", "name": "gpt-4o", "version": "2024-08-06"},
+          "rateLimits": [{"key": "request", "renewalPeriod": 10, "count": 75},
+                         {"key": "token", "renewalPeriod": 60, "count": 450000}]}},
+        {"name": "embeddings", "sku": {"name": "Standard", "capacity": 120},
+         "properties": {"model": {"format": "OpenAI", "name": "text-embedding-3-small", "version": "1"}}},
+        {"name": "ptu", "sku": {"name": "ProvisionedManaged", "capacity": 100},
+         "properties": {"model": {"format": "OpenAI", "name": "gpt-4o", "version": "2024-08-06"}}}
+    ]}"#;
+
+    const ACCOUNT_JSON: &str =
+        r#"{"name": "contoso-openai", "location": "eastus2", "kind": "OpenAI"}"#;
+
+    const METRICS_JSON: &str = r#"{"value": [
+        {"name": {"value": "ProcessedPromptTokens"}, "timeseries": [
+            {"metadatavalues": [{"name": {"value": "modeldeploymentname"}, "value": "gpt-4o"}],
+             "data": [{"timeStamp": "2024-06-10T11:58:00Z", "total": 90000},
+                      {"timeStamp": "2024-06-10T11:59:00Z", "total": 10000}]}
+        ]},
+        {"name": {"value": "GeneratedTokens"}, "timeseries": [
+            {"metadatavalues": [{"name": {"value": "modeldeploymentname"}, "value": "gpt-4o"}],
+             "data": [{"timeStamp": "2024-06-10T11:58:00Z", "total": 22500},
+                      {"timeStamp": "2024-06-10T11:59:00Z"}]}
+        ]},
+        {"name": {"value": "AzureOpenAIRequests"}, "timeseries": [
+            {"metadatavalues": [{"name": {"value": "modeldeploymentname"}, "value": "gpt-4o"}],
+             "data": [{"timeStamp": "2024-06-10T11:58:00Z", "total": 45}]}
+        ]}
+    ]}"#;
+
+    #[test]
+    fn test_parse_resource_id() {
+        let resource = AzureResourceId::parse(RESOURCE_ID).unwrap();
+        assert_eq!(resource.subscription_id, "sub-1");
+        assert_eq!(resource.resource_group, "ai-rg");
+        assert_eq!(resource.account_name, "contoso-openai");
+        assert_eq!(resource.path(), RESOURCE_ID);
+
+        // Case-insensitive, trailing slash
+        let lower = RESOURCE_ID.to_lowercase() + "/";
+        assert!(AzureResourceId::parse(&lower).is_ok());
+
+        assert!(AzureResourceId::parse("/subscriptions/sub-1/resourceGroups/ai-rg").is_err());
+        assert!(
+            AzureResourceId::parse(
+                "/subscriptions/s/resourceGroups/rg/providers/Microsoft.Storage/storageAccounts/x"
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_deployment_limits() {
+        let page: ListResponse<AzureDeployment> = serde_json::from_str(DEPLOYMENTS_JSON).unwrap();
+        let [gpt, embeddings, ptu] = page.value.as_slice() else {
+            panic!("expected three deployments");
+        };
+
+        assert_eq!(gpt.tokens_per_minute(), Some(450_000.0));
+        assert_eq!(gpt.requests_per_minute(), Some(450.0));
+
+        // No rateLimits: derived from capacity
+        assert_eq!(embeddings.tokens_per_minute(), Some(120_000.0));
+        assert_eq!(embeddings.requests_per_minute(), Some(720.0));
+
+        assert!(ptu.tokens_per_minute().is_none());
+        assert!(ptu.sku.as_ref().unwrap().is_provisioned());
+    }
+
+    #[test]
+    fn test_api_error_mapping() {
+        let err = api_error(
+            reqwest::StatusCode::UNAUTHORIZED,
+            r#"{"error": {"code": "ExpiredAuthenticationToken", "message": "expired"}}"#,
+        );
+        assert!(matches!(err, AzureError::AuthenticationFailed(_)));
+
+        let err = api_error(
+            reqwest::StatusCode::NOT_FOUND,
+            r#"{"error": {"code": "ResourceNotFound", "message": "gone"}}"#,
+        );
+        assert!(matches!(err, AzureError::InvalidResource(_)));
+    }
+
+    /// Serves canned JSON by path suffix; the longest matching suffix wins.
+    async fn serve_routes(routes: Vec<(&'static str, &'static str)>) -> String {
+        serve_stub(move |request| {
+            routes
+                .iter()
+                .filter(|(suffix, _)| request.path.ends_with(suffix))
+                .max_by_key(|(suffix, _)| suffix.len())
+                .map_or((404, "{}"), |(_, body)| (200, *body))
+        })
+        .await
+    }
+
+    fn token() -> AzureToken {
+        AzureToken {
+            access_token: "token".to_string(),
+            expires_at: None,
+            tenant_id: Some("tid".to_string()),
+            account: Some("dev@contoso.com".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_usage_against_stub() {
+        let base_url = serve_routes(vec![
+            ("/accounts/contoso-openai", ACCOUNT_JSON),
+            ("/deployments", DEPLOYMENTS_JSON),
+            ("/providers/Microsoft.Insights/metrics", METRICS_JSON),
+        ])
+        .await;
+
+        let client =
+            AzureApiClient::with_base_url(base_url, AzureResourceId::parse(RESOURCE_ID).unwrap());
+        let now = Utc.with_ymd_and_hms(2024, 6, 10, 12, 0, 0).unwrap();
+        let usage = client.fetch_usage(&token(), now).await.unwrap();
+
+        assert_eq!(usage.deployments.len(), 3);
+        assert_eq!(usage.account.location.as_deref(), Some("eastus2"));
+
+        let gpt = &usage.deployments[0];
+        // 90,000 prompt + 22,500 generated in the busiest minute
+        assert!((gpt.peak_tokens_per_minute - 112_500.0).abs() < 0.001);
+        assert!((gpt.peak_requests_per_minute - 45.0).abs() < 0.001);
+        assert!((gpt.tpm_used_percent().unwrap() - 25.0).abs() < 0.001);
+        assert!((gpt.rpm_used_percent().unwrap() - 10.0).abs() < 0.001);
+
+        // The same stub answers the daily query, so totals land on June 10
+        let day = gpt.daily[&NaiveDate::from_ymd_opt(2024, 6, 10).unwrap()];
+        assert_eq!(day.prompt, 100_000);
+        assert_eq!(day.generated, 22_500);
+        assert_eq!(day.requests, 45);
+
+        let snapshot = usage.to_snapshot(FetchSource::OAuth, now);
+        let primary = snapshot.primary().unwrap();
+        assert_eq!(primary.id, "tpm_headroom");
+        assert!((primary.used_percent - 25.0).abs() < 0.001);
+        let secondary = snapshot.secondary().unwrap();
+        assert_eq!(secondary.id, "rpm_headroom");
+        assert!((secondary.used_percent - 10.0).abs() < 0.001);
+
+        let window = snapshot.window("gpt-4o").unwrap();
+        assert_eq!(window.kind, WindowKind::Model);
+        assert!((window.used_percent - 25.0).abs() < 0.001);
+        // Embeddings has a quota but no traffic; PTU has none
+        assert!(snapshot.window("embeddings").is_some());
+        assert!(snapshot.window("ptu").is_none());
+
+        let cost = snapshot.cost.unwrap();
+        assert_eq!(cost.session_tokens, Some(122_500));
+
+        let identity = snapshot.identity.unwrap();
+        assert_eq!(identity.provider_id, ProviderKind::Azure);
+        assert_eq!(identity.account_email.as_deref(), Some("dev@contoso.com"));
+        assert_eq!(
+            identity.account_organization.as_deref(),
+            Some("contoso-openai (eastus2)")
+        );
+        assert_eq!(identity.login_method, Some(LoginMethod::OAuth));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_usage_missing_resource() {
+        let base_url = serve_routes(vec![]).await;
+        let client =
+            AzureApiClient::with_base_url(base_url, AzureResourceId::parse(RESOURCE_ID).unwrap());
+        assert!(matches!(
+            client.fetch_usage(&token(), Utc::now()).await,
+            Err(AzureError::InvalidResource(_))
+        ));
+    }
+
+    #[test]
+    fn test_to_snapshot_without_quotas() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 10, 12, 0, 0).unwrap();
+        let mut deployment = DeploymentUsage {
+            name: "ptu".to_string(),
+            ..DeploymentUsage::default()
+        };
+        deployment.daily.insert(
+            now.date_naive(),
+            DayTokens {
+                prompt: 700,
+                generated: 300,
+                requests: 3,
+            },
+        );
+        let usage = AzureUsage {
+            resource: AzureResourceId::parse(RESOURCE_ID).unwrap(),
+            account: AzureAccount::default(),
+            deployments: vec![deployment],
+            signed_in_as: None,
+        };
+
+        let snapshot = usage.to_snapshot(FetchSource::CLI, now);
+        let primary = snapshot.primary().unwrap();
+        assert_eq!(primary.id, "tokens_today");
+        assert_eq!(primary.used, Some(1000.0));
+        assert!(snapshot.secondary().is_none());
+        assert_eq!(
+            snapshot.identity.unwrap().login_method,
+            Some(LoginMethod::CLI)
+        );
+    }
+}
@@ -0,0 +1,531 @@
+//! Azure credential reader.
+//!
+//! This module gets Azure Resource Manager access tokens.
+//!
+//! ## Credential Sources
+//!
+//! 1. **Service principal**
+//!    - `AZURE_TENANT_ID`, `AZURE_CLIENT_ID` and `AZURE_CLIENT_SECRET`
+//!    - The secret may also live in the keychain (`azure`)
+//!    - Exchanged for a token with the client credentials flow
+//!
+//! 2. **az CLI token cache**
+//!    - `~/.azure/msal_token_cache.json` (or `$AZURE_CONFIG_DIR`)
+//!    - Holds the ARM tokens `az login` cached, valid for about an hour
+//!
+//! 3. **az CLI**
+//!    - `az account get-access-token --resource https://management.azure.com/`
+//!    - Refreshes the token when the cache is stale
+//!
+//! ## Example
+//!
+//! ```ignore
+//! let token = AzCredentials::new().load_from_cache(Utc::now())?;
+//! ```
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::{debug, instrument};
+
+use super::error::AzureError;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Default Microsoft Entra ID authority.
+pub const DEFAULT_AUTHORITY_HOST: &str = "https://login.microsoftonline.com";
+
+/// Resource Manager scope for the client credentials flow.
+const ARM_SCOPE: &str = "https://management.azure.com/.default";
+
+/// Resource passed to `az account get-access-token`.
+pub const ARM_RESOURCE: &str = "https://management.azure.com/";
+
+/// Audiences the az CLI uses for Resource Manager tokens.
+const ARM_AUDIENCES: &[&str] = &["management.azure.com", "management.core.windows.net"];
+
+/// Arguments for `az` to print an ARM token.
+pub const AZ_TOKEN_ARGS: &[&str] = &[
+    "account",
+    "get-access-token",
+    "--resource",
+    ARM_RESOURCE,
+    "--output",
+    "json",
+];
+
+// ============================================================================
+// Token
+// ============================================================================
+
+/// Azure Resource Manager access token.
+#[derive(Clone)]
+pub struct AzureToken {
+    /// The bearer token.
+    pub access_token: String,
+
+    /// When the token expires (if known).
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// Tenant the token was issued by.
+    pub tenant_id: Option<String>,
+
+    /// The signed-in user or service principal client ID.
+    pub account: Option<String>,
+}
+
+impl AzureToken {
+    /// Check if the token is expired (with five minutes of slack).
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at
+            .is_some_and(|exp| exp < now + chrono::Duration::minutes(5))
+    }
+}
+
+// Keep the token out of logs
+impl std::fmt::Debug for AzureToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AzureToken")
+            .field("expires_at", &self.expires_at)
+            .field("tenant_id", &self.tenant_id)
+            .field("account", &self.account)
+            .finish_non_exhaustive()
+    }
+}
+
+// ============================================================================
+// Service Principal
+// ============================================================================
+
+/// Service principal (app registration) credentials.
+#[derive(Clone)]
+pub struct ServicePrincipal {
+    /// Directory (tenant) ID.
+    pub tenant_id: String,
+    /// Application (client) ID.
+    pub client_id: String,
+    /// Client secret.
+    pub client_secret: String,
+}
+
+impl std::fmt::Debug for ServicePrincipal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServicePrincipal")
+            .field("tenant_id", &self.tenant_id)
+            .field("client_id", &self.client_id)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ClientCredentialsResponse {
+    access_token: String,
+    expires_in: Option<i64>,
+}
+
+impl ServicePrincipal {
+    /// Reads the service principal from the environment, with the secret
+    /// falling back to the keychain.
+    pub fn from_env() -> Option<Self> {
+        let env = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let client_secret = env("AZURE_CLIENT_SECRET").or_else(|| {
+            exactobar_store::get_api_key(exactobar_store::keychain::providers::AZURE)
+        })?;
+
+        Some(Self {
+            tenant_id: env("AZURE_TENANT_ID")?,
+            client_id: env("AZURE_CLIENT_ID")?,
+            client_secret,
+        })
+    }
+
+    /// Returns the authority host (`AZURE_AUTHORITY_HOST` or the public cloud).
+    pub fn authority_host() -> String {
+        std::env::var("AZURE_AUTHORITY_HOST")
+            .ok()
+            .map(|h| h.trim().trim_end_matches('/').to_string())
+            .filter(|h| !h.is_empty())
+            .unwrap_or_else(|| DEFAULT_AUTHORITY_HOST.to_string())
+    }
+
+    /// Gets an ARM token with the client credentials flow.
+    #[instrument(skip(self))]
+    pub async fn fetch_token(&self, authority_host: &str) -> Result<AzureToken, AzureError> {
+        let url = format!(
+            "{}/{}/oauth2/v2.0/token",
+            authority_host.trim_end_matches('/'),
+            self.tenant_id
+        );
+        debug!(url = %url, "Requesting Azure token for service principal");
+
+        let response = reqwest::Client::new()
+            .post(&url)
+            .form(&[
+                ("grant_type", "client_credentials"),
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.as_str()),
+                ("scope", ARM_SCOPE),
+            ])
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(AzureError::AuthenticationFailed(format!(
+                "token request failed ({}): {}",
+                status, text
+            )));
+        }
+
+        let body: ClientCredentialsResponse = response
+            .json()
+            .await
+            .map_err(|e| AzureError::ParseError(e.to_string()))?;
+
+        Ok(AzureToken {
+            access_token: body.access_token,
+            expires_at: body
+                .expires_in
+                .map(|secs| Utc::now() + chrono::Duration::seconds(secs)),
+            tenant_id: Some(self.tenant_id.clone()),
+            account: Some(self.client_id.clone()),
+        })
+    }
+}
+
+// ============================================================================
+// az CLI
+// ============================================================================
+
+/// Output of `az account get-access-token --output json`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzCliToken {
+    access_token: String,
+    /// Epoch seconds (az 2.54+).
+    #[serde(rename = "expires_on", default)]
+    expires_on: Option<i64>,
+    #[serde(default)]
+    tenant: Option<String>,
+}
+
+/// Parses the JSON `az account get-access-token` prints.
+pub fn parse_cli_token(stdout: &str) -> Result<AzureToken, AzureError> {
+    let token: AzCliToken =
+        serde_json::from_str(stdout.trim()).map_err(|e| AzureError::ParseError(e.to_string()))?;
+    if token.access_token.is_empty() {
+        return Err(AzureError::AzCliError("Empty token".to_string()));
+    }
+
+    Ok(AzureToken {
+        access_token: token.access_token,
+        expires_at: token
+            .expires_on
+            .and_then(|secs| DateTime::from_timestamp(secs, 0)),
+        tenant_id: token.tenant,
+        account: None,
+    })
+}
+
+// ============================================================================
+// Token Cache
+// ============================================================================
+
+/// MSAL token cache (`msal_token_cache.json`).
+#[derive(Debug, Default, Deserialize)]
+struct MsalCache {
+    #[serde(rename = "AccessToken", default)]
+    access_tokens: HashMap<String, MsalAccessToken>,
+    #[serde(rename = "Account", default)]
+    accounts: HashMap<String, MsalAccount>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MsalAccessToken {
+    secret: String,
+    #[serde(default)]
+    home_account_id: Option<String>,
+    #[serde(default)]
+    realm: Option<String>,
+    #[serde(default)]
+    target: String,
+    /// Epoch seconds, as a string.
+    #[serde(default)]
+    expires_on: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MsalAccount {
+    #[serde(default)]
+    home_account_id: Option<String>,
+    #[serde(default)]
+    username: Option<String>,
+}
+
+/// Default subscription from `azureProfile.json`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AzureSubscription {
+    /// Subscription ID.
+    pub id: String,
+    /// Subscription name.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Tenant ID.
+    #[serde(default)]
+    pub tenant_id: Option<String>,
+    /// Whether this is the CLI's default subscription.
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct AzureProfile {
+    #[serde(default)]
+    subscriptions: Vec<AzureSubscription>,
+}
+
+/// az CLI credential reader.
+#[derive(Debug, Clone)]
+pub struct AzCredentials {
+    config_dir: PathBuf,
+}
+
+impl Default for AzCredentials {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AzCredentials {
+    /// Creates a reader for `$AZURE_CONFIG_DIR` or `~/.azure`.
+    pub fn new() -> Self {
+        let config_dir = std::env::var("AZURE_CONFIG_DIR")
+            .ok()
+            .filter(|d| !d.trim().is_empty())
+            .map_or_else(
+                || dirs::home_dir().unwrap_or_default().join(".azure"),
+                PathBuf::from,
+            );
+        Self { config_dir }
+    }
+
+    /// Creates a reader for a specific config directory.
+    pub fn with_config_dir(config_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            config_dir: config_dir.into(),
+        }
+    }
+
+    /// Path to the MSAL token cache.
+    pub fn token_cache_path(&self) -> PathBuf {
+        self.config_dir.join("msal_token_cache.json")
+    }
+
+    /// Check if the az CLI has cached tokens.
+    pub fn has_token_cache(&self) -> bool {
+        self.token_cache_path().exists()
+    }
+
+    /// Check if the az CLI is installed.
+    pub fn is_cli_available() -> bool {
+        which::which("az").is_ok()
+    }
+
+    /// Reads the CLI's default subscription.
+    pub fn default_subscription(&self) -> Option<AzureSubscription> {
+        let text = std::fs::read_to_string(self.config_dir.join("azureProfile.json")).ok()?;
+        // The CLI writes this file with a UTF-8 BOM
+        let profile: AzureProfile =
+            serde_json::from_str(text.trim_start_matches('\u{feff}')).ok()?;
+        profile.subscriptions.into_iter().find(|s| s.is_default)
+    }
+
+    /// Loads an unexpired Resource Manager token from the token cache.
+    ///
+    /// Prefers tokens for the default subscription's tenant, then the
+    /// longest-lived one.
+    #[instrument(skip(self))]
+    pub fn load_from_cache(&self, now: DateTime<Utc>) -> Result<AzureToken, AzureError> {
+        let text = std::fs::read_to_string(self.token_cache_path())
+            .map_err(|_| AzureError::NoCredentials)?;
+        let cache: MsalCache =
+            serde_json::from_str(&text).map_err(|e| AzureError::ParseError(e.to_string()))?;
+        let tenant = self.default_subscription().and_then(|s| s.tenant_id);
+
+        let token = cache
+            .access_tokens
+            .values()
+            .filter(|t| ARM_AUDIENCES.iter().any(|aud| t.target.contains(aud)))
+            .map(|t| {
+                let expires_at = t
+                    .expires_on
+                    .as_deref()
+                    .and_then(|s| s.parse::<i64>().ok())
+                    .and_then(|secs| DateTime::from_timestamp(secs, 0));
+                (t, expires_at)
+            })
+            .filter(|(_, expires_at)| expires_at.is_some_and(|exp| exp > now))
+            .max_by_key(|(t, expires_at)| (t.realm.is_some() && t.realm == tenant, *expires_at))
+            .ok_or_else(|| {
+                AzureError::TokenExpired("no current token in the az CLI cache".to_string())
+            })?;
+
+        let (entry, expires_at) = token;
+        let account = cache
+            .accounts
+            .values()
+            .find(|a| a.home_account_id.is_some() && a.home_account_id == entry.home_account_id)
+            .and_then(|a| a.username.clone());
+        debug!(tenant = ?entry.realm, "Got Azure token from az CLI cache");
+
+        Ok(AzureToken {
+            access_token: entry.secret.clone(),
+            expires_at,
+            tenant_id: entry.realm.clone(),
+            account,
+        })
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const CACHE_JSON: &str = r#"{
+        "AccessToken": {
+            "a": {
+                "credential_type": "AccessToken", "secret": "graph-token",
+                "home_account_id": "oid.tid", "realm": "tid",
+                "target": "https://graph.microsoft.com/.default",
+                "expires_on": "1718028000"
+            },
+            "b": {
+                "credential_type": "AccessToken", "secret": "arm-old",
+                "home_account_id": "oid.tid", "realm": "tid",
+                "target": "https://management.core.windows.net//user_impersonation https://management.core.windows.net//.default",
+                "expires_on": "1718010000"
+            },
+            "c": {
+                "credential_type": "AccessToken", "secret": "arm-current",
+                "home_account_id": "oid.tid", "realm": "tid",
+                "target": "https://management.core.windows.net//.default",
+                "expires_on": "1718024400"
+            },
+            "d": {
+                "credential_type": "AccessToken", "secret": "arm-other-tenant",
+                "home_account_id": "oid.other", "realm": "other",
+                "target": "https://management.core.windows.net//.default",
+                "expires_on": "1718028000"
+            }
+        },
+        "Account": {
+            "oid.tid-login.microsoftonline.com-tid": {
+                "home_account_id": "oid.tid", "username": "dev@contoso.com"
+            }
+        }
+    }"#;
+
+    const PROFILE_JSON: &str = "\u{feff}{\"subscriptions\": [
+        {\"id\": \"sub-1\", \"name\": \"Dev\", \"tenantId\": \"tid\", \"isDefault\": true},
+        {\"id\": \"sub-2\", \"name\": \"Other\", \"tenantId\": \"other\", \"isDefault\": false}
+    ]}";
+
+    fn config_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "exactobar-azure-{}-{}",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("msal_token_cache.json"), CACHE_JSON).unwrap();
+        std::fs::write(dir.join("azureProfile.json"), PROFILE_JSON).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_default_subscription() {
+        let dir = config_dir();
+        let sub = AzCredentials::with_config_dir(&dir)
+            .default_subscription()
+            .unwrap();
+        assert_eq!(sub.id, "sub-1");
+        assert_eq!(sub.tenant_id.as_deref(), Some("tid"));
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_load_from_cache_prefers_default_tenant() {
+        let dir = config_dir();
+        let now = Utc.with_ymd_and_hms(2024, 6, 10, 12, 0, 0).unwrap();
+        let token = AzCredentials::with_config_dir(&dir)
+            .load_from_cache(now)
+            .unwrap();
+        assert_eq!(token.access_token, "arm-current");
+        assert_eq!(token.tenant_id.as_deref(), Some("tid"));
+        assert_eq!(token.account.as_deref(), Some("dev@contoso.com"));
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_load_from_cache_expired() {
+        let dir = config_dir();
+        let later = Utc.with_ymd_and_hms(2024, 6, 11, 0, 0, 0).unwrap();
+        assert!(matches!(
+            AzCredentials::with_config_dir(&dir).load_from_cache(later),
+            Err(AzureError::TokenExpired(_))
+        ));
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_load_from_missing_cache() {
+        let creds = AzCredentials::with_config_dir("/nonexistent/azure");
+        assert!(!creds.has_token_cache());
+        assert!(matches!(
+            creds.load_from_cache(Utc::now()),
+            Err(AzureError::NoCredentials)
+        ));
+    }
+
+    #[test]
+    fn test_parse_cli_token() {
+        let token = parse_cli_token(
+            r#"{"accessToken": "eyJ0", "expiresOn": "2024-06-10 13:00:00.000000",
+                "expires_on": 1718024400, "subscription": "sub-1", "tenant": "tid",
+                "tokenType": "Bearer"}"#,
+        )
+        .unwrap();
+        assert_eq!(token.access_token, "eyJ0");
+        assert_eq!(token.tenant_id.as_deref(), Some("tid"));
+        assert_eq!(
+            token.expires_at,
+            Some(Utc.with_ymd_and_hms(2024, 6, 10, 13, 0, 0).unwrap())
+        );
+
+        assert!(parse_cli_token("ERROR: Please run 'az login'").is_err());
+    }
+
+    #[test]
+    fn test_token_expiry() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 10, 12, 0, 0).unwrap();
+        let token = AzureToken {
+            access_token: "t".to_string(),
+            expires_at: Some(now + chrono::Duration::minutes(3)),
+            tenant_id: None,
+            account: None,
+        };
+        assert!(token.is_expired(now));
+        assert!(!format!("{:?}", token).contains("\"t\""));
+    }
+}
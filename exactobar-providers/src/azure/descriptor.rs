@@ -0,0 +1,109 @@
+//! Azure OpenAI provider descriptor.
+
+use exactobar_core::{IconStyle, ProviderBranding, ProviderColor, ProviderKind, ProviderMetadata};
+use exactobar_fetch::{FetchContext, FetchPipeline, SourceMode};
+use std::collections::HashMap;
+
+use super::strategies::{AzureCliStrategy, AzureOAuthStrategy};
+use crate::descriptor::{CliConfig, FetchPlan, ProviderDescriptor, TokenCostConfig};
+
+// ============================================================================
+// Descriptor
+// ============================================================================
+
+/// Creates the Azure OpenAI provider descriptor.
+pub fn azure_descriptor() -> ProviderDescriptor {
+    ProviderDescriptor {
+        id: ProviderKind::Azure,
+        metadata: azure_metadata(),
+        branding: azure_branding(),
+        token_cost: TokenCostConfig::default(),
+        fetch_plan: azure_fetch_plan(),
+        cli: azure_cli_config(),
+    }
+}
+
+// ============================================================================
+// Metadata
+// ============================================================================
+
+fn azure_metadata() -> ProviderMetadata {
+    ProviderMetadata {
+        id: ProviderKind::Azure,
+        display_name: "Azure OpenAI".to_string(),
+        session_label: "Peak TPM".to_string(),
+        weekly_label: "Peak RPM".to_string(),
+        opus_label: None,
+        supports_opus: false,
+        window_labels: HashMap::from([
+            ("tpm_headroom".to_string(), "Peak TPM".to_string()),
+            ("rpm_headroom".to_string(), "Peak RPM".to_string()),
+            ("tokens_today".to_string(), "Tokens today".to_string()),
+        ]),
+        supports_credits: false,
+        credits_hint: String::new(),
+        toggle_title: "Show Azure OpenAI usage".to_string(),
+        cli_name: "azure".to_string(),
+        default_enabled: false,
+        is_primary_provider: false,
+        uses_account_fallback: false,
+        dashboard_url: Some("https://ai.azure.com".to_string()),
+        subscription_dashboard_url: Some(
+            "https://portal.azure.com/#view/Microsoft_Azure_CostManagement/Menu/~/costanalysis"
+                .to_string(),
+        ),
+        status_page_url: Some("https://azure.status.microsoft/en-us/status".to_string()),
+        status_link_url: None,
+    }
+}
+
+// ============================================================================
+// Branding
+// ============================================================================
+
+fn azure_branding() -> ProviderBranding {
+    ProviderBranding {
+        icon_style: IconStyle::Azure,
+        icon_resource_name: "icon_azure".to_string(),
+        // Azure blue
+        color: ProviderColor::new(0.0, 0.47, 0.83),
+    }
+}
+
+// ============================================================================
+// Fetch Plan
+// ============================================================================
+
+fn azure_fetch_plan() -> FetchPlan {
+    FetchPlan {
+        source_modes: vec![SourceMode::OAuth, SourceMode::CLI],
+        build_pipeline: Box::new(build_azure_pipeline),
+    }
+}
+
+fn build_azure_pipeline(ctx: &FetchContext) -> FetchPipeline {
+    let mut strategies: Vec<Box<dyn exactobar_fetch::FetchStrategy>> = Vec::new();
+
+    if ctx.settings.source_mode.allows_oauth() {
+        strategies.push(Box::new(AzureOAuthStrategy::new()));
+    }
+
+    if ctx.settings.source_mode.allows_cli() {
+        strategies.push(Box::new(AzureCliStrategy::new()));
+    }
+
+    FetchPipeline::with_strategies(strategies)
+}
+
+// ============================================================================
+// CLI Config
+// ============================================================================
+
+fn azure_cli_config() -> CliConfig {
+    CliConfig {
+        name: "azure",
+        aliases: &["azure-openai", "aoai", "foundry"],
+        version_args: &["--version"],
+        usage_args: &[],
+    }
+}
@@ -0,0 +1,51 @@
+//! Azure OpenAI-specific errors.
+
+use thiserror::Error;
+
+/// Azure OpenAI errors.
+#[derive(Debug, Error)]
+pub enum AzureError {
+    /// No service principal or az CLI login found.
+    #[error("No Azure credentials found (set AZURE_CLIENT_ID/SECRET or run `az login`)")]
+    NoCredentials,
+
+    /// Cached token has expired.
+    #[error("Token expired: {0}")]
+    TokenExpired(String),
+
+    /// az CLI failed.
+    #[error("az CLI error: {0}")]
+    AzCliError(String),
+
+    /// No Azure OpenAI account configured.
+    #[error(
+        "Azure OpenAI resource not configured (set it in Settings or AZURE_OPENAI_RESOURCE_ID)"
+    )]
+    ResourceNotConfigured,
+
+    /// Resource ID is malformed or the account does not exist.
+    #[error("Invalid Azure OpenAI resource: {0}")]
+    InvalidResource(String),
+
+    /// HTTP request failed.
+    #[error("HTTP error: {0}")]
+    HttpError(String),
+
+    /// Parse error.
+    #[error("Parse error: {0}")]
+    ParseError(String),
+
+    /// API error.
+    #[error("API error: {0}")]
+    ApiError(String),
+
+    /// Authentication failed.
+    #[error("Authentication failed: {0}")]
+    AuthenticationFailed(String),
+}
+
+impl From<reqwest::Error> for AzureError {
+    fn from(err: reqwest::Error) -> Self {
+        AzureError::HttpError(err.to_string())
+    }
+}
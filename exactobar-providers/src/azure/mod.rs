@@ -0,0 +1,33 @@
+//! Azure OpenAI provider implementation.
+//!
+//! Reads the deployments of an Azure OpenAI (or AI Foundry) account from
+//! Azure Resource Manager, with each deployment's tokens-per-minute and
+//! requests-per-minute quota, and compares them to the token and request
+//! metrics Azure Monitor records per deployment.
+//!
+//! The account is configured as a resource ID in Settings
+//! (`exactobar config resource azure ...`) or `AZURE_OPENAI_RESOURCE_ID`.
+//!
+//! ## Fetch Strategies
+//!
+//! 1. **OAuth Strategy** (priority 100): Service principal from
+//!    `AZURE_TENANT_ID`/`AZURE_CLIENT_ID`/`AZURE_CLIENT_SECRET`, otherwise
+//!    the az CLI's cached token
+//! 2. **CLI Strategy** (priority 80): `az account get-access-token`
+//!
+//! The provider's `base_url` setting replaces the Resource Manager URL, for
+//! sovereign clouds or a local stand-in.
+
+mod api;
+pub mod az;
+mod descriptor;
+mod error;
+mod strategies;
+
+pub use api::{
+    AzureAccount, AzureApiClient, AzureDeployment, AzureResourceId, AzureUsage, DeploymentUsage,
+};
+pub use az::{AzCredentials, AzureSubscription, AzureToken, ServicePrincipal};
+pub use descriptor::azure_descriptor;
+pub use error::AzureError;
+pub use strategies::{AzureCliStrategy, AzureOAuthStrategy};
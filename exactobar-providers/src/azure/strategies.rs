@@ -0,0 +1,202 @@
+//! Azure OpenAI fetch strategies.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use exactobar_core::{FetchSource, ProviderKind};
+use exactobar_fetch::{FetchContext, FetchError, FetchKind, FetchResult, FetchStrategy};
+use tracing::{debug, instrument};
+
+use super::api::{ARM_BASE_URL, AzureApiClient, AzureResourceId};
+use super::az::{AZ_TOKEN_ARGS, AzCredentials, AzureToken, ServicePrincipal, parse_cli_token};
+use super::error::AzureError;
+
+/// Builds a client from the configured resource and Resource Manager URL.
+fn client(ctx: &FetchContext) -> Result<AzureApiClient, AzureError> {
+    let options = ctx.provider_options(ProviderKind::Azure);
+    let resource: AzureResourceId =
+        AzureApiClient::resolve_resource(options.resource_id.as_deref())?;
+    let base_url = options.base_url.as_deref().unwrap_or(ARM_BASE_URL);
+    Ok(AzureApiClient::with_base_url(base_url, resource))
+}
+
+/// Fetches usage with a token and wraps it in a result.
+async fn fetch_with_token(
+    ctx: &FetchContext,
+    token: &AzureToken,
+    strategy: &dyn FetchStrategy,
+    source: FetchSource,
+) -> Result<FetchResult, FetchError> {
+    let client = client(ctx).map_err(|e| FetchError::StrategyNotAvailable(e.to_string()))?;
+
+    let now = Utc::now();
+    let usage = client.fetch_usage(token, now).await.map_err(|e| match e {
+        AzureError::AuthenticationFailed(msg) => FetchError::AuthenticationFailed(msg),
+        other => FetchError::InvalidResponse(other.to_string()),
+    })?;
+
+    Ok(FetchResult::new(
+        usage.to_snapshot(source, now),
+        strategy.id(),
+        strategy.kind(),
+    ))
+}
+
+// ============================================================================
+// OAuth Strategy
+// ============================================================================
+
+/// Azure OAuth strategy.
+///
+/// Uses a service principal when `AZURE_CLIENT_ID` and friends are set,
+/// otherwise the token the az CLI cached in `~/.azure`.
+pub struct AzureOAuthStrategy {
+    az: AzCredentials,
+}
+
+impl AzureOAuthStrategy {
+    /// Creates a new strategy.
+    pub fn new() -> Self {
+        Self {
+            az: AzCredentials::new(),
+        }
+    }
+
+    async fn token(&self) -> Result<AzureToken, AzureError> {
+        if let Some(principal) = ServicePrincipal::from_env() {
+            debug!(client_id = %principal.client_id, "Using Azure service principal");
+            return principal
+                .fetch_token(&ServicePrincipal::authority_host())
+                .await;
+        }
+        self.az.load_from_cache(Utc::now())
+    }
+}
+
+impl Default for AzureOAuthStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FetchStrategy for AzureOAuthStrategy {
+    fn id(&self) -> &str {
+        "azure.oauth"
+    }
+
+    fn kind(&self) -> FetchKind {
+        FetchKind::OAuth
+    }
+
+    #[instrument(skip(self, ctx))]
+    async fn is_available(&self, ctx: &FetchContext) -> bool {
+        client(ctx).is_ok() && (ServicePrincipal::from_env().is_some() || self.az.has_token_cache())
+    }
+
+    #[instrument(skip(self, ctx))]
+    async fn fetch(&self, ctx: &FetchContext) -> Result<FetchResult, FetchError> {
+        debug!("Fetching Azure OpenAI usage via OAuth token");
+
+        let token = self
+            .token()
+            .await
+            .map_err(|e| FetchError::AuthenticationFailed(e.to_string()))?;
+        fetch_with_token(ctx, &token, self, FetchSource::OAuth).await
+    }
+
+    fn priority(&self) -> u32 {
+        100
+    }
+}
+
+// ============================================================================
+// CLI Strategy
+// ============================================================================
+
+/// Azure CLI strategy.
+///
+/// Asks `az` for a fresh token, which refreshes an expired cache entry.
+pub struct AzureCliStrategy {
+    command: &'static str,
+}
+
+impl AzureCliStrategy {
+    /// Creates a new strategy.
+    pub fn new() -> Self {
+        Self { command: "az" }
+    }
+}
+
+impl Default for AzureCliStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FetchStrategy for AzureCliStrategy {
+    fn id(&self) -> &str {
+        "azure.cli"
+    }
+
+    fn kind(&self) -> FetchKind {
+        FetchKind::CLI
+    }
+
+    #[instrument(skip(self, ctx))]
+    async fn is_available(&self, ctx: &FetchContext) -> bool {
+        client(ctx).is_ok() && ctx.process.command_exists(self.command)
+    }
+
+    #[instrument(skip(self, ctx))]
+    async fn fetch(&self, ctx: &FetchContext) -> Result<FetchResult, FetchError> {
+        debug!("Fetching Azure OpenAI usage via az CLI token");
+
+        let output = ctx
+            .process
+            .run_with_timeout(self.command, AZ_TOKEN_ARGS, ctx.timeout())
+            .await
+            .map_err(FetchError::Process)?;
+
+        if !output.success() {
+            return Err(FetchError::AuthenticationFailed(format!(
+                "az exited with code {}: {}",
+                output.exit_code,
+                output.stderr.trim()
+            )));
+        }
+
+        let token = parse_cli_token(&output.stdout)
+            .map_err(|e| FetchError::InvalidResponse(e.to_string()))?;
+        fetch_with_token(ctx, &token, self, FetchSource::CLI).await
+    }
+
+    fn priority(&self) -> u32 {
+        80
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oauth_strategy() {
+        let s = AzureOAuthStrategy::new();
+        assert_eq!(s.id(), "azure.oauth");
+        assert_eq!(s.kind(), FetchKind::OAuth);
+        assert_eq!(s.priority(), 100);
+    }
+
+    #[test]
+    fn test_cli_strategy() {
+        let s = AzureCliStrategy::new();
+        assert_eq!(s.id(), "azure.cli");
+        assert_eq!(s.kind(), FetchKind::CLI);
+        assert_eq!(s.priority(), 80);
+    }
+}
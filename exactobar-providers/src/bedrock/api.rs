@@ -0,0 +1,1261 @@
+//! Amazon Bedrock usage via CloudWatch and Service Quotas.
+//!
+//! # API Endpoints
+//!
+//! Both services speak the AWS JSON protocol: a SigV4-signed `POST /` with
+//! the operation in `X-Amz-Target`.
+//!
+//! ```text
+//! POST https://monitoring.{region}.amazonaws.com/
+//! X-Amz-Target: GraniteServiceVersion20100801.ListMetrics
+//! X-Amz-Target: GraniteServiceVersion20100801.GetMetricData
+//!
+//! POST https://servicequotas.{region}.amazonaws.com/
+//! X-Amz-Target: ServiceQuotasV20190624.ListServiceQuotas
+//! ```
+//!
+//! # Metrics
+//!
+//! Bedrock publishes `InputTokenCount`, `OutputTokenCount` and `Invocations`
+//! to the `AWS/Bedrock` namespace, one series per `ModelId`. Daily sums
+//! cover the last 30 days; one-minute sums over the last hour give the peak
+//! tokens per minute, which is compared to the account's TPM quota.
+//!
+//! # Endpoint Overrides
+//!
+//! `AWS_ENDPOINT_URL_CLOUDWATCH`, `AWS_ENDPOINT_URL_SERVICE_QUOTAS`,
+//! `AWS_ENDPOINT_URL_SSO` and `AWS_ENDPOINT_URL` are honored like the AWS
+//! CLI does. The provider's `base_url` setting sends every request to one
+//! URL, for use with a local stand-in such as LocalStack.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use exactobar_core::{
+    CostUsageSnapshot, DailyUsageEntry, FetchSource, LoginMethod, ModelBreakdown, ProviderIdentity,
+    ProviderKind, UsageSnapshot, UsageWindow, WindowKind, WindowSlot,
+};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, BTreeSet};
+use tracing::{debug, instrument, warn};
+
+use super::credentials::AwsCredentials;
+use super::error::BedrockError;
+use super::sigv4::{SignableRequest, sign};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// CloudWatch namespace for Bedrock runtime metrics.
+pub const METRIC_NAMESPACE: &str = "AWS/Bedrock";
+
+/// Service Quotas service code for Bedrock.
+pub const SERVICE_CODE: &str = "bedrock";
+
+/// Unit for token counts on usage windows.
+pub const TOKENS_UNIT: &str = "tokens";
+
+const CLOUDWATCH_SERVICE: &str = "monitoring";
+const CLOUDWATCH_CONTENT_TYPE: &str = "application/x-amz-json-1.0";
+const CLOUDWATCH_TARGET_PREFIX: &str = "GraniteServiceVersion20100801";
+
+const QUOTAS_SERVICE: &str = "servicequotas";
+const QUOTAS_CONTENT_TYPE: &str = "application/x-amz-json-1.1";
+const QUOTAS_TARGET_PREFIX: &str = "ServiceQuotasV20190624";
+
+/// Days of daily token history to read.
+const HISTORY_DAYS: i64 = 30;
+
+/// CloudWatch accepts at most 500 queries per `GetMetricData` call.
+const MAX_QUERIES_PER_REQUEST: usize = 500;
+
+/// Region prefixes on cross-region inference profile IDs.
+const INFERENCE_PROFILE_PREFIXES: &[&str] = &["us.", "eu.", "apac.", "us-gov.", "global."];
+
+// ============================================================================
+// Endpoints
+// ============================================================================
+
+/// Service endpoints for one region.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BedrockEndpoints {
+    /// CloudWatch endpoint.
+    pub cloudwatch: String,
+    /// Service Quotas endpoint.
+    pub service_quotas: String,
+    /// IAM Identity Center portal; `None` uses the profile's SSO region.
+    pub sso_portal: Option<String>,
+}
+
+impl BedrockEndpoints {
+    /// Returns the public AWS endpoints for a region.
+    pub fn for_region(region: &str) -> Self {
+        Self {
+            cloudwatch: format!("https://monitoring.{}.amazonaws.com", region),
+            service_quotas: format!("https://servicequotas.{}.amazonaws.com", region),
+            sso_portal: None,
+        }
+    }
+
+    /// Sends every request to the same URL.
+    pub fn single(url: &str) -> Self {
+        let url = url.trim_end_matches('/').to_string();
+        Self {
+            cloudwatch: url.clone(),
+            service_quotas: url.clone(),
+            sso_portal: Some(url),
+        }
+    }
+
+    /// Resolves endpoints from the configured base URL, then the
+    /// `AWS_ENDPOINT_URL_*` variables, then the public defaults.
+    pub fn resolve(region: &str, base_url: Option<&str>) -> Self {
+        if let Some(url) = base_url.filter(|u| !u.trim().is_empty()) {
+            return Self::single(url);
+        }
+
+        let env = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|v| v.trim().trim_end_matches('/').to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let global = env("AWS_ENDPOINT_URL");
+        let defaults = Self::for_region(region);
+
+        Self {
+            cloudwatch: env("AWS_ENDPOINT_URL_CLOUDWATCH")
+                .or_else(|| global.clone())
+                .unwrap_or(defaults.cloudwatch),
+            service_quotas: env("AWS_ENDPOINT_URL_SERVICE_QUOTAS")
+                .or_else(|| global.clone())
+                .unwrap_or(defaults.service_quotas),
+            sso_portal: env("AWS_ENDPOINT_URL_SSO").or(global),
+        }
+    }
+}
+
+// ============================================================================
+// Wire Types
+// ============================================================================
+
+/// A CloudWatch metric dimension.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Dimension {
+    /// Dimension name.
+    pub name: String,
+    /// Dimension value.
+    pub value: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct MetricDescriptor {
+    #[serde(default)]
+    dimensions: Vec<Dimension>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListMetricsResponse {
+    #[serde(default)]
+    metrics: Vec<MetricDescriptor>,
+    next_token: Option<String>,
+}
+
+/// One series from `GetMetricData`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct MetricDataResult {
+    /// Query ID.
+    pub id: String,
+    /// Datapoint times, in epoch seconds.
+    #[serde(default)]
+    pub timestamps: Vec<f64>,
+    /// Datapoint values.
+    #[serde(default)]
+    pub values: Vec<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GetMetricDataResponse {
+    #[serde(default)]
+    metric_data_results: Vec<MetricDataResult>,
+    next_token: Option<String>,
+}
+
+/// A Service Quotas quota.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ServiceQuota {
+    /// Quota name, e.g. "On-demand model inference tokens per minute for
+    /// Anthropic Claude 3.5 Sonnet".
+    pub quota_name: String,
+    /// Quota code.
+    #[serde(default)]
+    pub quota_code: Option<String>,
+    /// Applied value.
+    pub value: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListServiceQuotasResponse {
+    #[serde(default)]
+    quotas: Vec<ServiceQuota>,
+    next_token: Option<String>,
+}
+
+/// AWS JSON protocol error body.
+#[derive(Debug, Default, Deserialize)]
+struct AwsErrorBody {
+    #[serde(rename = "__type", default)]
+    error_type: Option<String>,
+    #[serde(alias = "Message", default)]
+    message: Option<String>,
+}
+
+// ============================================================================
+// Usage Model
+// ============================================================================
+
+/// Token counts for one model on one day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DayTokens {
+    /// Input tokens.
+    pub input: u64,
+    /// Output tokens.
+    pub output: u64,
+    /// Model invocations.
+    pub invocations: u64,
+}
+
+impl DayTokens {
+    /// Input plus output tokens.
+    pub fn total(&self) -> u64 {
+        self.input + self.output
+    }
+}
+
+/// Usage for one Bedrock model ID.
+#[derive(Debug, Clone, Default)]
+pub struct ModelUsage {
+    /// Model or inference profile ID.
+    pub model_id: String,
+    /// Daily token counts.
+    pub daily: BTreeMap<NaiveDate, DayTokens>,
+    /// Highest tokens per minute over the last hour.
+    pub peak_tokens_per_minute: f64,
+    /// Tokens-per-minute quota that applies to this model.
+    pub tpm_quota: Option<f64>,
+}
+
+impl ModelUsage {
+    /// Creates empty usage for a model.
+    pub fn new(model_id: impl Into<String>) -> Self {
+        Self {
+            model_id: model_id.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Peak TPM as a percentage of the quota, if there is one.
+    pub fn headroom_used_percent(&self) -> Option<f64> {
+        let quota = self.tpm_quota.filter(|q| *q > 0.0)?;
+        Some((self.peak_tokens_per_minute / quota * 100.0).clamp(0.0, 100.0))
+    }
+
+    fn tpm_window(&self) -> Option<UsageWindow> {
+        let used_percent = self.headroom_used_percent()?;
+        Some(UsageWindow {
+            window_minutes: Some(1),
+            ..UsageWindow::named(&self.model_id, WindowKind::Model, used_percent)
+                .with_label(&self.model_id)
+                .with_counts(self.peak_tokens_per_minute, self.tpm_quota, TOKENS_UNIT)
+        })
+    }
+}
+
+/// Bedrock usage for a region.
+#[derive(Debug, Clone, Default)]
+pub struct BedrockUsage {
+    /// Region the metrics came from.
+    pub region: String,
+    /// Per-model usage, sorted by model ID.
+    pub models: Vec<ModelUsage>,
+}
+
+impl BedrockUsage {
+    /// Builds the daily cost snapshot, newest day first.
+    fn to_cost_snapshot(&self, now: DateTime<Utc>) -> CostUsageSnapshot {
+        let mut days: BTreeMap<NaiveDate, Vec<(&str, DayTokens)>> = BTreeMap::new();
+        for model in &self.models {
+            for (date, tokens) in &model.daily {
+                days.entry(*date)
+                    .or_default()
+                    .push((model.model_id.as_str(), *tokens));
+            }
+        }
+
+        let mut cost = CostUsageSnapshot::new();
+        cost.updated_at = now;
+        cost.daily = days
+            .into_iter()
+            .rev()
+            .map(|(date, models)| {
+                let mut entry = DailyUsageEntry::new(date.format("%Y-%m-%d").to_string());
+                let input = models.iter().map(|(_, t)| t.input).sum();
+                let output = models.iter().map(|(_, t)| t.output).sum();
+                entry.input_tokens = Some(input);
+                entry.output_tokens = Some(output);
+                entry.total_tokens = Some(input + output);
+                entry.models_used = Some(models.iter().map(|(id, _)| (*id).to_string()).collect());
+                entry.model_breakdowns = Some(
+                    models
+                        .iter()
+                        .map(|(id, tokens)| ModelBreakdown {
+                            input_tokens: Some(tokens.input),
+                            output_tokens: Some(tokens.output),
+                            ..ModelBreakdown::new(*id)
+                        })
+                        .collect(),
+                );
+                entry
+            })
+            .collect();
+
+        cost.last_30_days_tokens = Some(cost.total_daily_tokens());
+        cost.session_tokens = Some(self.tokens_on(now.date_naive()));
+        cost
+    }
+
+    /// Total tokens across models on a day.
+    pub fn tokens_on(&self, date: NaiveDate) -> u64 {
+        self.models
+            .iter()
+            .filter_map(|m| m.daily.get(&date))
+            .map(DayTokens::total)
+            .sum()
+    }
+
+    /// Convert to a UsageSnapshot for display.
+    ///
+    /// The primary window is the throttling headroom of the busiest model:
+    /// its peak tokens per minute over the last hour against its TPM quota.
+    /// Every model with a quota also gets its own window. Without any
+    /// matching quota, the primary window shows today's tokens instead.
+    pub fn to_snapshot(&self, now: DateTime<Utc>) -> UsageSnapshot {
+        let mut snapshot = UsageSnapshot::new();
+        snapshot.fetch_source = FetchSource::Api;
+
+        let model_windows: Vec<UsageWindow> = self
+            .models
+            .iter()
+            .filter_map(ModelUsage::tpm_window)
+            .collect();
+
+        let busiest = model_windows
+            .iter()
+            .max_by(|a, b| a.used_percent.total_cmp(&b.used_percent));
+
+        let primary = if let Some(busiest) = busiest {
+            UsageWindow {
+                window_minutes: Some(1),
+                ..UsageWindow::named("tpm_headroom", WindowKind::Other, busiest.used_percent)
+                    .with_label(format!("Peak TPM ({})", busiest.id))
+                    .with_counts(busiest.used.unwrap_or(0.0), busiest.limit, TOKENS_UNIT)
+            }
+        } else {
+            #[allow(clippy::cast_precision_loss)]
+            let today = self.tokens_on(now.date_naive()) as f64;
+            UsageWindow {
+                window_minutes: Some(24 * 60),
+                ..UsageWindow::named("tokens_today", WindowKind::Session, 0.0)
+                    .with_label("Tokens today")
+                    .with_counts(today, None, TOKENS_UNIT)
+            }
+        };
+        snapshot.set_slot(WindowSlot::Primary, Some(primary));
+
+        for window in model_windows {
+            snapshot.push_window(window);
+        }
+
+        snapshot.cost = Some(self.to_cost_snapshot(now));
+
+        let mut identity = ProviderIdentity::new(ProviderKind::Bedrock);
+        identity.account_organization = Some(self.region.clone());
+        identity.login_method = Some(LoginMethod::ApiKey);
+        snapshot.identity = Some(identity);
+
+        snapshot
+    }
+}
+
+// ============================================================================
+// Quota Matching
+// ============================================================================
+
+/// Splits text into lowercase alphanumeric tokens, also splitting where
+/// letters meet digits ("llama3" -> "llama", "3").
+fn name_tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut last_digit: Option<bool> = None;
+
+    for c in text.chars().map(|c| c.to_ascii_lowercase()) {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+            last_digit = None;
+            continue;
+        }
+        let digit = c.is_ascii_digit();
+        if last_digit.is_some_and(|d| d != digit) && !current.is_empty() {
+            tokens.push(std::mem::take(&mut current));
+        }
+        current.push(c);
+        last_digit = Some(digit);
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// Returns true if the ID is a cross-region inference profile.
+fn is_inference_profile(model_id: &str) -> bool {
+    INFERENCE_PROFILE_PREFIXES
+        .iter()
+        .any(|p| model_id.starts_with(p))
+}
+
+/// Tokens that identify a model, e.g. `anthropic.claude-3-5-sonnet-20241022-v2:0`
+/// becomes `anthropic claude 3 5 sonnet v 2`.
+///
+/// The region prefix, release date, throughput suffix and a `v1` version
+/// are dropped, since quota names leave them out.
+fn model_tokens(model_id: &str) -> Vec<String> {
+    let mut id = model_id;
+    for prefix in INFERENCE_PROFILE_PREFIXES {
+        if let Some(rest) = id.strip_prefix(prefix) {
+            id = rest;
+            break;
+        }
+    }
+    let id = id.split(':').next().unwrap_or(id);
+
+    let mut parts: Vec<&str> = id.split('-').collect();
+    if parts.last().is_some_and(|p| *p == "v1") {
+        parts.pop();
+    }
+    parts.retain(|p| !(p.len() == 8 && p.bytes().all(|b| b.is_ascii_digit())));
+
+    name_tokens(&parts.join(" "))
+}
+
+/// Finds the tokens-per-minute quota for a model.
+///
+/// Picks the quota for the same inference type (on-demand or cross-region)
+/// whose name contains every model token, with the fewest extra tokens.
+pub fn match_tpm_quota<'a>(model_id: &str, quotas: &'a [ServiceQuota]) -> Option<&'a ServiceQuota> {
+    let wanted = model_tokens(model_id);
+    if wanted.is_empty() {
+        return None;
+    }
+    let cross_region = is_inference_profile(model_id);
+
+    quotas
+        .iter()
+        .filter_map(|quota| {
+            let name = quota.quota_name.to_lowercase();
+            let (kind, model) = name.split_once(" for ")?;
+            if !kind.contains("tokens per minute") || kind.contains("cross-region") != cross_region
+            {
+                return None;
+            }
+            // Skip variants like batch or provisioned quotas
+            if !cross_region && !kind.contains("on-demand") {
+                return None;
+            }
+
+            let have = name_tokens(model);
+            if !wanted.iter().all(|t| have.contains(t)) {
+                return None;
+            }
+            let unique: BTreeSet<&String> = have.iter().collect();
+            Some((unique.len().saturating_sub(wanted.len()), quota))
+        })
+        .min_by_key(|(extra, _)| *extra)
+        .map(|(_, quota)| quota)
+}
+
+// ============================================================================
+// API Client
+// ============================================================================
+
+/// Signed client for CloudWatch and Service Quotas.
+#[derive(Debug, Clone)]
+pub struct BedrockApiClient {
+    region: String,
+    endpoints: BedrockEndpoints,
+    http: reqwest::Client,
+}
+
+impl BedrockApiClient {
+    /// Creates a client for a region.
+    pub fn new(region: impl Into<String>, endpoints: BedrockEndpoints) -> Self {
+        Self {
+            region: region.into(),
+            endpoints,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Returns the region.
+    pub fn region(&self) -> &str {
+        &self.region
+    }
+
+    /// Fetches daily tokens, peak TPM and quotas for every model with
+    /// metrics.
+    ///
+    /// Metrics are required; quotas are best-effort.
+    #[instrument(skip(self, credentials))]
+    pub async fn fetch_usage(
+        &self,
+        credentials: &AwsCredentials,
+        now: DateTime<Utc>,
+    ) -> Result<BedrockUsage, BedrockError> {
+        let model_ids = self.list_model_ids(credentials).await?;
+        debug!(count = model_ids.len(), "Found Bedrock models with metrics");
+
+        let mut models: Vec<ModelUsage> = model_ids.iter().map(ModelUsage::new).collect();
+        if !models.is_empty() {
+            self.fill_daily(credentials, &mut models, now).await?;
+            self.fill_peak_tpm(credentials, &mut models, now).await?;
+
+            match self.list_tpm_quotas(credentials).await {
+                Ok(quotas) => {
+                    for model in &mut models {
+                        model.tpm_quota =
+                            match_tpm_quota(&model.model_id, &quotas).map(|q| q.value);
+                    }
+                }
+                Err(e) => warn!(error = %e, "Failed to fetch Bedrock quotas"),
+            }
+        }
+
+        Ok(BedrockUsage {
+            region: self.region.clone(),
+            models,
+        })
+    }
+
+    /// Lists model IDs that have published invocation metrics.
+    pub async fn list_model_ids(
+        &self,
+        credentials: &AwsCredentials,
+    ) -> Result<Vec<String>, BedrockError> {
+        let mut ids = BTreeSet::new();
+        let mut next_token: Option<String> = None;
+
+        loop {
+            let mut body = json!({
+                "Namespace": METRIC_NAMESPACE,
+                "MetricName": "Invocations",
+            });
+            if let Some(token) = &next_token {
+                body["NextToken"] = json!(token);
+            }
+
+            let page: ListMetricsResponse =
+                self.cloudwatch(credentials, "ListMetrics", &body).await?;
+            for metric in page.metrics {
+                // Only the per-model series; skip aggregates over other dimensions
+                if let [dimension] = metric.dimensions.as_slice() {
+                    if dimension.name == "ModelId" {
+                        ids.insert(dimension.value.clone());
+                    }
+                }
+            }
+
+            next_token = page.next_token;
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(ids.into_iter().collect())
+    }
+
+    /// Fills daily input, output and invocation sums.
+    async fn fill_daily(
+        &self,
+        credentials: &AwsCredentials,
+        models: &mut [ModelUsage],
+        now: DateTime<Utc>,
+    ) -> Result<(), BedrockError> {
+        let end = now;
+        let start = (now - Duration::days(HISTORY_DAYS - 1))
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .map_or(now, |dt| dt.and_utc());
+
+        let mut queries = Vec::new();
+        for (i, model) in models.iter().enumerate() {
+            for (suffix, metric) in [
+                ("in", "InputTokenCount"),
+                ("out", "OutputTokenCount"),
+                ("inv", "Invocations"),
+            ] {
+                queries.push(metric_query(
+                    &format!("d{}_{}", i, suffix),
+                    metric,
+                    &model.model_id,
+                    86_400,
+                ));
+            }
+        }
+
+        for result in self
+            .get_metric_data(credentials, queries, start, end)
+            .await?
+        {
+            let Some((index, suffix)) = parse_query_id(&result.id, 'd') else {
+                continue;
+            };
+            let Some(model) = models.get_mut(index) else {
+                continue;
+            };
+            for (timestamp, value) in result.timestamps.iter().zip(&result.values) {
+                let Some(date) = epoch_to_datetime(*timestamp).map(|t| t.date_naive()) else {
+                    continue;
+                };
+                let day = model.daily.entry(date).or_default();
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let value = value.max(0.0).round() as u64;
+                match suffix {
+                    "in" => day.input += value,
+                    "out" => day.output += value,
+                    _ => day.invocations += value,
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fills the peak tokens per minute over the last hour.
+    async fn fill_peak_tpm(
+        &self,
+        credentials: &AwsCredentials,
+        models: &mut [ModelUsage],
+        now: DateTime<Utc>,
+    ) -> Result<(), BedrockError> {
+        let mut queries = Vec::new();
+        for (i, model) in models.iter().enumerate() {
+            for (suffix, metric) in [("in", "InputTokenCount"), ("out", "OutputTokenCount")] {
+                queries.push(metric_query(
+                    &format!("p{}_{}", i, suffix),
+                    metric,
+                    &model.model_id,
+                    60,
+                ));
+            }
+        }
+
+        let results = self
+            .get_metric_data(credentials, queries, now - Duration::hours(1), now)
+            .await?;
+
+        // Input and output share the per-minute budget
+        let mut per_minute: Vec<BTreeMap<i64, f64>> = vec![BTreeMap::new(); models.len()];
+        for result in results {
+            let Some((index, _)) = parse_query_id(&result.id, 'p') else {
+                continue;
+            };
+            let Some(minutes) = per_minute.get_mut(index) else {
+                continue;
+            };
+            for (timestamp, value) in result.timestamps.iter().zip(&result.values) {
+                #[allow(clippy::cast_possible_truncation)]
+                let minute = timestamp.round() as i64;
+                *minutes.entry(minute).or_default() += value;
+            }
+        }
+
+        for (model, minutes) in models.iter_mut().zip(per_minute) {
+            model.peak_tokens_per_minute = minutes.values().copied().fold(0.0, f64::max);
+        }
+
+        Ok(())
+    }
+
+    /// Runs metric queries, splitting and paginating as needed.
+    async fn get_metric_data(
+        &self,
+        credentials: &AwsCredentials,
+        queries: Vec<Value>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<MetricDataResult>, BedrockError> {
+        let mut results: Vec<MetricDataResult> = Vec::new();
+
+        for chunk in queries.chunks(MAX_QUERIES_PER_REQUEST) {
+            let mut next_token: Option<String> = None;
+            loop {
+                let mut body = json!({
+                    "MetricDataQueries": chunk,
+                    "StartTime": start.timestamp(),
+                    "EndTime": end.timestamp(),
+                });
+                if let Some(token) = &next_token {
+                    body["NextToken"] = json!(token);
+                }
+
+                let page: GetMetricDataResponse =
+                    self.cloudwatch(credentials, "GetMetricData", &body).await?;
+                // Later pages continue the same series
+                for result in page.metric_data_results {
+                    if let Some(existing) = results.iter_mut().find(|r| r.id == result.id) {
+                        existing.timestamps.extend(result.timestamps);
+                        existing.values.extend(result.values);
+                    } else {
+                        results.push(result);
+                    }
+                }
+
+                next_token = page.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Lists the applied Bedrock quotas measured in tokens per minute.
+    pub async fn list_tpm_quotas(
+        &self,
+        credentials: &AwsCredentials,
+    ) -> Result<Vec<ServiceQuota>, BedrockError> {
+        let mut quotas = Vec::new();
+        let mut next_token: Option<String> = None;
+
+        loop {
+            let mut body = json!({
+                "ServiceCode": SERVICE_CODE,
+                "MaxResults": 100,
+            });
+            if let Some(token) = &next_token {
+                body["NextToken"] = json!(token);
+            }
+
+            let page: ListServiceQuotasResponse = self
+                .call(
+                    credentials,
+                    &self.endpoints.service_quotas,
+                    QUOTAS_SERVICE,
+                    QUOTAS_CONTENT_TYPE,
+                    &format!("{}.ListServiceQuotas", QUOTAS_TARGET_PREFIX),
+                    &body,
+                )
+                .await?;
+            quotas.extend(
+                page.quotas
+                    .into_iter()
+                    .filter(|q| q.quota_name.to_lowercase().contains("tokens per minute")),
+            );
+
+            next_token = page.next_token;
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(quotas)
+    }
+
+    async fn cloudwatch<T: DeserializeOwned>(
+        &self,
+        credentials: &AwsCredentials,
+        operation: &str,
+        body: &Value,
+    ) -> Result<T, BedrockError> {
+        self.call(
+            credentials,
+            &self.endpoints.cloudwatch,
+            CLOUDWATCH_SERVICE,
+            CLOUDWATCH_CONTENT_TYPE,
+            &format!("{}.{}", CLOUDWATCH_TARGET_PREFIX, operation),
+            body,
+        )
+        .await
+    }
+
+    /// Sends a signed AWS JSON protocol request.
+    async fn call<T: DeserializeOwned>(
+        &self,
+        credentials: &AwsCredentials,
+        endpoint: &str,
+        service: &str,
+        content_type: &str,
+        target: &str,
+        body: &Value,
+    ) -> Result<T, BedrockError> {
+        let url = reqwest::Url::parse(endpoint)
+            .map_err(|e| BedrockError::ApiError(format!("invalid endpoint {}: {}", endpoint, e)))?;
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => {
+                return Err(BedrockError::ApiError(format!(
+                    "invalid endpoint {}",
+                    endpoint
+                )));
+            }
+        };
+        let path = if url.path().is_empty() {
+            "/"
+        } else {
+            url.path()
+        };
+        let payload =
+            serde_json::to_vec(body).map_err(|e| BedrockError::ParseError(e.to_string()))?;
+
+        let headers = [("content-type", content_type), ("x-amz-target", target)];
+        let signed = sign(
+            &SignableRequest {
+                method: "POST",
+                host: &host,
+                path,
+                query: &[],
+                headers: &headers,
+                body: &payload,
+            },
+            credentials,
+            &self.region,
+            service,
+            Utc::now(),
+        );
+
+        debug!(target = %target, endpoint = %endpoint, "AWS request");
+        let mut request = self.http.post(url.clone()).body(payload);
+        for (name, value) in headers.iter().copied() {
+            request = request.header(name, value);
+        }
+        for (name, value) in &signed {
+            request = request.header(name.as_str(), value.as_str());
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        let text = response.text().await?;
+
+        if !status.is_success() {
+            return Err(api_error(status, &text));
+        }
+
+        serde_json::from_str(&text).map_err(|e| BedrockError::ParseError(e.to_string()))
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Builds a `GetMetricData` query for one model's metric sum.
+fn metric_query(id: &str, metric: &str, model_id: &str, period: u32) -> Value {
+    json!({
+        "Id": id,
+        "MetricStat": {
+            "Metric": {
+                "Namespace": METRIC_NAMESPACE,
+                "MetricName": metric,
+                "Dimensions": [{"Name": "ModelId", "Value": model_id}],
+            },
+            "Period": period,
+            "Stat": "Sum",
+        },
+        "ReturnData": true,
+    })
+}
+
+/// Parses "d3_in" into (3, "in").
+fn parse_query_id(id: &str, prefix: char) -> Option<(usize, &str)> {
+    let (index, suffix) = id.strip_prefix(prefix)?.split_once('_')?;
+    Some((index.parse().ok()?, suffix))
+}
+
+fn epoch_to_datetime(seconds: f64) -> Option<DateTime<Utc>> {
+    #[allow(clippy::cast_possible_truncation)]
+    DateTime::from_timestamp(seconds.round() as i64, 0)
+}
+
+/// Maps an AWS error response to an error.
+fn api_error(status: reqwest::StatusCode, text: &str) -> BedrockError {
+    let body: AwsErrorBody = serde_json::from_str(text).unwrap_or_default();
+    // "__type" may carry a namespace: "com.amazon.coral.service#ExpiredTokenException"
+    let error_type = body
+        .error_type
+        .as_deref()
+        .map(|t| t.rsplit('#').next().unwrap_or(t))
+        .unwrap_or_default();
+    let message = body.message.unwrap_or_else(|| text.to_string());
+
+    let auth_error = matches!(
+        error_type,
+        "UnrecognizedClientException"
+            | "InvalidClientTokenId"
+            | "ExpiredToken"
+            | "ExpiredTokenException"
+            | "InvalidSignatureException"
+            | "SignatureDoesNotMatch"
+            | "AccessDeniedException"
+            | "AccessDenied"
+    );
+    if auth_error
+        || status == reqwest::StatusCode::UNAUTHORIZED
+        || status == reqwest::StatusCode::FORBIDDEN
+    {
+        let detail = if error_type.is_empty() {
+            message
+        } else {
+            format!("{}: {}", error_type, message)
+        };
+        return BedrockError::AuthenticationFailed(detail);
+    }
+
+    BedrockError::ApiError(format!("HTTP {}: {} {}", status, error_type, message))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::serve_stub;
+    use chrono::TimeZone;
+
+    fn quota(name: &str, value: f64) -> ServiceQuota {
+        ServiceQuota {
+            quota_name: name.to_string(),
+            quota_code: None,
+            value,
+        }
+    }
+
+    fn quotas() -> Vec<ServiceQuota> {
+        vec![
+            quota(
+                "On-demand model inference tokens per minute for Anthropic Claude 3.5 Sonnet",
+                400_000.0,
+            ),
+            quota(
+                "On-demand model inference tokens per minute for Anthropic Claude 3.5 Sonnet V2",
+                800_000.0,
+            ),
+            quota(
+                "Cross-region model inference tokens per minute for Anthropic Claude 3.5 Sonnet V2",
+                1_600_000.0,
+            ),
+            quota(
+                "On-demand model inference tokens per minute for Anthropic Claude 3 Sonnet",
+                200_000.0,
+            ),
+            quota(
+                "On-demand model inference tokens per minute for Meta Llama 3 70B Instruct",
+                300_000.0,
+            ),
+            quota(
+                "Batch inference tokens per minute for Anthropic Claude 3 Sonnet",
+                1.0,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_name_tokens() {
+        assert_eq!(
+            name_tokens("Meta Llama3 70B-Instruct"),
+            vec!["meta", "llama", "3", "70", "b", "instruct"]
+        );
+    }
+
+    #[test]
+    fn test_model_tokens() {
+        assert_eq!(
+            model_tokens("us.anthropic.claude-3-5-sonnet-20241022-v2:0"),
+            vec!["anthropic", "claude", "3", "5", "sonnet", "v", "2"]
+        );
+        assert_eq!(
+            model_tokens("anthropic.claude-3-5-sonnet-20240620-v1:0"),
+            vec!["anthropic", "claude", "3", "5", "sonnet"]
+        );
+    }
+
+    #[test]
+    fn test_match_tpm_quota() {
+        let quotas = quotas();
+        let value = |id: &str| match_tpm_quota(id, &quotas).map(|q| q.value);
+
+        assert_eq!(
+            value("anthropic.claude-3-5-sonnet-20240620-v1:0"),
+            Some(400_000.0)
+        );
+        assert_eq!(
+            value("anthropic.claude-3-5-sonnet-20241022-v2:0"),
+            Some(800_000.0)
+        );
+        assert_eq!(
+            value("us.anthropic.claude-3-5-sonnet-20241022-v2:0"),
+            Some(1_600_000.0)
+        );
+        assert_eq!(
+            value("anthropic.claude-3-sonnet-20240229-v1:0"),
+            Some(200_000.0)
+        );
+        assert_eq!(value("meta.llama3-70b-instruct-v1:0"), Some(300_000.0));
+        assert_eq!(value("amazon.titan-text-express-v1"), None);
+    }
+
+    #[test]
+    fn test_endpoints_for_region() {
+        let endpoints = BedrockEndpoints::for_region("eu-west-1");
+        assert_eq!(
+            endpoints.cloudwatch,
+            "https://monitoring.eu-west-1.amazonaws.com"
+        );
+        assert_eq!(
+            endpoints.service_quotas,
+            "https://servicequotas.eu-west-1.amazonaws.com"
+        );
+    }
+
+    #[test]
+    fn test_endpoints_base_url_overrides_all() {
+        let endpoints = BedrockEndpoints::resolve("us-east-1", Some("http://localhost:4566/"));
+        assert_eq!(endpoints.cloudwatch, "http://localhost:4566");
+        assert_eq!(endpoints.service_quotas, "http://localhost:4566");
+        assert_eq!(
+            endpoints.sso_portal.as_deref(),
+            Some("http://localhost:4566")
+        );
+    }
+
+    #[test]
+    fn test_parse_query_id() {
+        assert_eq!(parse_query_id("d12_out", 'd'), Some((12, "out")));
+        assert_eq!(parse_query_id("p0_in", 'd'), None);
+    }
+
+    #[test]
+    fn test_api_error_mapping() {
+        let err = api_error(
+            reqwest::StatusCode::BAD_REQUEST,
+            r#"{"__type": "com.amazon.coral.service#ExpiredTokenException", "message": "expired"}"#,
+        );
+        assert!(matches!(err, BedrockError::AuthenticationFailed(_)));
+
+        let err = api_error(
+            reqwest::StatusCode::BAD_REQUEST,
+            r#"{"__type": "InvalidParameterValueException", "Message": "bad"}"#,
+        );
+        assert!(matches!(err, BedrockError::ApiError(_)));
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 6, day).unwrap()
+    }
+
+    fn usage() -> BedrockUsage {
+        let mut sonnet = ModelUsage::new("anthropic.claude-3-5-sonnet-20240620-v1:0");
+        sonnet.daily.insert(
+            date(9),
+            DayTokens {
+                input: 1000,
+                output: 200,
+                invocations: 4,
+            },
+        );
+        sonnet.daily.insert(
+            date(10),
+            DayTokens {
+                input: 3000,
+                output: 500,
+                invocations: 7,
+            },
+        );
+        sonnet.peak_tokens_per_minute = 100_000.0;
+        sonnet.tpm_quota = Some(400_000.0);
+
+        let mut llama = ModelUsage::new("meta.llama3-70b-instruct-v1:0");
+        llama.daily.insert(
+            date(10),
+            DayTokens {
+                input: 400,
+                output: 100,
+                invocations: 2,
+            },
+        );
+        llama.peak_tokens_per_minute = 150_000.0;
+        llama.tpm_quota = Some(300_000.0);
+
+        BedrockUsage {
+            region: "us-east-1".to_string(),
+            models: vec![sonnet, llama],
+        }
+    }
+
+    #[test]
+    fn test_to_snapshot() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 10, 12, 0, 0).unwrap();
+        let snapshot = usage().to_snapshot(now);
+
+        // Llama is at 50%, Sonnet at 25%
+        let primary = snapshot.primary().unwrap();
+        assert_eq!(primary.id, "tpm_headroom");
+        assert!((primary.used_percent - 50.0).abs() < 0.001);
+        assert_eq!(primary.limit, Some(300_000.0));
+        assert_eq!(primary.window_minutes, Some(1));
+
+        let sonnet = snapshot
+            .window("anthropic.claude-3-5-sonnet-20240620-v1:0")
+            .unwrap();
+        assert_eq!(sonnet.kind, WindowKind::Model);
+        assert!((sonnet.used_percent - 25.0).abs() < 0.001);
+        assert_eq!(sonnet.unit.as_deref(), Some(TOKENS_UNIT));
+
+        let cost = snapshot.cost.unwrap();
+        assert_eq!(cost.daily.len(), 2);
+        assert_eq!(cost.daily[0].date, "2024-06-10");
+        assert_eq!(cost.daily[0].total_tokens, Some(4000));
+        assert_eq!(cost.daily[0].model_breakdowns.as_ref().unwrap().len(), 2);
+        assert_eq!(cost.session_tokens, Some(4000));
+        assert_eq!(cost.last_30_days_tokens, Some(5200));
+
+        let identity = snapshot.identity.unwrap();
+        assert_eq!(identity.provider_id, ProviderKind::Bedrock);
+        assert_eq!(identity.account_organization.as_deref(), Some("us-east-1"));
+    }
+
+    #[test]
+    fn test_to_snapshot_without_quotas() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 10, 12, 0, 0).unwrap();
+        let mut usage = usage();
+        for model in &mut usage.models {
+            model.tpm_quota = None;
+        }
+        let snapshot = usage.to_snapshot(now);
+
+        let primary = snapshot.primary().unwrap();
+        assert_eq!(primary.id, "tokens_today");
+        assert_eq!(primary.used, Some(4000.0));
+        assert!(primary.limit.is_none());
+        assert_eq!(snapshot.windows_of_kind(WindowKind::Model).count(), 0);
+    }
+
+    /// Serves canned responses keyed by `X-Amz-Target` and checks every
+    /// request is signed.
+    async fn serve_targets(routes: Vec<(&'static str, &'static str)>) -> String {
+        serve_stub(move |request| {
+            let signed = request
+                .header("authorization")
+                .is_some_and(|auth| auth.starts_with("AWS4-HMAC-SHA256 "));
+            let target = request.header("x-amz-target").unwrap_or_default();
+            routes
+                .iter()
+                .find(|(name, _)| target.eq_ignore_ascii_case(name))
+                .filter(|_| signed)
+                .map_or(
+                    (400, r#"{"__type": "UnknownOperationException"}"#),
+                    |(_, body)| (200, *body),
+                )
+        })
+        .await
+    }
+
+    const LIST_METRICS_JSON: &str = r#"{
+        "Metrics": [
+            {"Namespace": "AWS/Bedrock", "MetricName": "Invocations",
+             "Dimensions": [{"Name": "ModelId", "Value": "anthropic.claude-3-5-sonnet-20240620-v1:0"}]},
+            {"Namespace": "AWS/Bedrock", "MetricName": "Invocations", "Dimensions": []}
+        ]
+    }"#;
+
+    // 2024-06-10T00:00:00Z and 2024-06-10T11:58/11:59:00Z
+    const METRIC_DATA_JSON: &str = r#"{
+        "MetricDataResults": [
+            {"Id": "d0_in", "Timestamps": [1717977600], "Values": [3000], "StatusCode": "Complete"},
+            {"Id": "d0_out", "Timestamps": [1717977600], "Values": [500], "StatusCode": "Complete"},
+            {"Id": "d0_inv", "Timestamps": [1717977600], "Values": [7], "StatusCode": "Complete"},
+            {"Id": "p0_in", "Timestamps": [1718020680, 1718020740], "Values": [60000, 20000]},
+            {"Id": "p0_out", "Timestamps": [1718020680, 1718020740], "Values": [40000, 5000]}
+        ]
+    }"#;
+
+    const QUOTAS_JSON: &str = r#"{
+        "Quotas": [
+            {"ServiceCode": "bedrock", "QuotaCode": "L-A50569E5", "Value": 400000.0,
+             "QuotaName": "On-demand model inference tokens per minute for Anthropic Claude 3.5 Sonnet"},
+            {"ServiceCode": "bedrock", "QuotaCode": "L-1", "Value": 20.0,
+             "QuotaName": "On-demand model inference requests per minute for Anthropic Claude 3.5 Sonnet"}
+        ]
+    }"#;
+
+    #[tokio::test]
+    async fn test_fetch_usage_against_stub() {
+        let url = serve_targets(vec![
+            (
+                "GraniteServiceVersion20100801.ListMetrics",
+                LIST_METRICS_JSON,
+            ),
+            (
+                "GraniteServiceVersion20100801.GetMetricData",
+                METRIC_DATA_JSON,
+            ),
+            ("ServiceQuotasV20190624.ListServiceQuotas", QUOTAS_JSON),
+        ])
+        .await;
+
+        let client = BedrockApiClient::new("us-east-1", BedrockEndpoints::single(&url));
+        let credentials = AwsCredentials::new("AKIDEXAMPLE", "secret");
+        let now = Utc.with_ymd_and_hms(2024, 6, 10, 12, 0, 0).unwrap();
+        let usage = client.fetch_usage(&credentials, now).await.unwrap();
+
+        assert_eq!(usage.models.len(), 1);
+        let model = &usage.models[0];
+        assert_eq!(
+            model.daily.get(&date(10)),
+            Some(&DayTokens {
+                input: 3000,
+                output: 500,
+                invocations: 7
+            })
+        );
+        assert!((model.peak_tokens_per_minute - 100_000.0).abs() < 0.001);
+        assert_eq!(model.tpm_quota, Some(400_000.0));
+
+        let snapshot = usage.to_snapshot(now);
+        assert!((snapshot.primary().unwrap().used_percent - 25.0).abs() < 0.001);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_usage_api_error() {
+        let url = serve_targets(vec![]).await;
+        let client = BedrockApiClient::new("us-east-1", BedrockEndpoints::single(&url));
+        let credentials = AwsCredentials::new("AKIDEXAMPLE", "secret");
+
+        // The stub answers unknown targets with a 400, not an auth error
+        let result = client.fetch_usage(&credentials, Utc::now()).await;
+        assert!(matches!(result, Err(BedrockError::ApiError(_))));
+    }
+}
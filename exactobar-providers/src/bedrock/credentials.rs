@@ -0,0 +1,613 @@
+//! AWS credential chain.
+//!
+//! Resolves credentials the way the AWS CLI does, in order:
+//!
+//! 1. `AWS_ACCESS_KEY_ID` / `AWS_SECRET_ACCESS_KEY` / `AWS_SESSION_TOKEN`
+//! 2. Static keys for the profile in `~/.aws/credentials` or `~/.aws/config`
+//! 3. IAM Identity Center (SSO) profiles, using the token the AWS CLI caches
+//!    in `~/.aws/sso/cache` after `aws sso login`
+//!
+//! The profile is `AWS_PROFILE` or `default`. File locations honor
+//! `AWS_SHARED_CREDENTIALS_FILE` and `AWS_CONFIG_FILE`. Role assumption and
+//! `credential_process` are not supported.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use ring::digest;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::{debug, instrument};
+
+use super::error::BedrockError;
+use super::sigv4::hex;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Region used when none is configured.
+pub const DEFAULT_REGION: &str = "us-east-1";
+
+/// Profile used when `AWS_PROFILE` is unset.
+pub const DEFAULT_PROFILE: &str = "default";
+
+// ============================================================================
+// Credentials
+// ============================================================================
+
+/// AWS access credentials.
+#[derive(Clone)]
+pub struct AwsCredentials {
+    /// Access key ID.
+    pub access_key_id: String,
+    /// Secret access key.
+    pub secret_access_key: String,
+    /// Session token for temporary credentials.
+    pub session_token: Option<String>,
+    /// When temporary credentials expire.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl AwsCredentials {
+    /// Creates long-term credentials.
+    pub fn new(access_key_id: impl Into<String>, secret_access_key: impl Into<String>) -> Self {
+        Self {
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+            expires_at: None,
+        }
+    }
+
+    /// Reads credentials from the standard environment variables.
+    pub fn from_env() -> Option<Self> {
+        let access_key_id = non_empty_env("AWS_ACCESS_KEY_ID")?;
+        let secret_access_key = non_empty_env("AWS_SECRET_ACCESS_KEY")?;
+        Some(Self {
+            session_token: non_empty_env("AWS_SESSION_TOKEN"),
+            ..Self::new(access_key_id, secret_access_key)
+        })
+    }
+}
+
+// Keep secrets out of logs
+impl std::fmt::Debug for AwsCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AwsCredentials")
+            .field("access_key_id", &self.access_key_id)
+            .field("session_token", &self.session_token.as_ref().map(|_| "***"))
+            .field("expires_at", &self.expires_at)
+            .finish_non_exhaustive()
+    }
+}
+
+fn non_empty_env(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.trim().is_empty())
+}
+
+// ============================================================================
+// Config Files
+// ============================================================================
+
+/// Parsed INI sections, keyed by section name then lowercase key.
+type IniSections = HashMap<String, HashMap<String, String>>;
+
+/// Parses an AWS-style INI file.
+fn parse_ini(text: &str) -> IniSections {
+    let mut sections = IniSections::new();
+    let mut current: Option<String> = None;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let name = name.trim().to_string();
+            sections.entry(name.clone()).or_default();
+            current = Some(name);
+            continue;
+        }
+        if let (Some(section), Some((key, value))) = (&current, line.split_once('=')) {
+            sections
+                .entry(section.clone())
+                .or_default()
+                .insert(key.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    sections
+}
+
+/// Locations of the AWS CLI's shared files.
+#[derive(Debug, Clone)]
+pub struct AwsConfigFiles {
+    /// Shared credentials file.
+    pub credentials_path: PathBuf,
+    /// Shared config file.
+    pub config_path: PathBuf,
+    /// SSO token cache directory.
+    pub sso_cache_dir: PathBuf,
+}
+
+impl AwsConfigFiles {
+    /// Resolves file locations from the environment and home directory.
+    pub fn from_env() -> Self {
+        let aws_dir = dirs::home_dir().unwrap_or_default().join(".aws");
+        Self {
+            credentials_path: non_empty_env("AWS_SHARED_CREDENTIALS_FILE")
+                .map_or_else(|| aws_dir.join("credentials"), PathBuf::from),
+            config_path: non_empty_env("AWS_CONFIG_FILE")
+                .map_or_else(|| aws_dir.join("config"), PathBuf::from),
+            sso_cache_dir: aws_dir.join("sso").join("cache"),
+        }
+    }
+
+    /// Uses the given `.aws` directory for every file.
+    pub fn in_dir(aws_dir: &Path) -> Self {
+        Self {
+            credentials_path: aws_dir.join("credentials"),
+            config_path: aws_dir.join("config"),
+            sso_cache_dir: aws_dir.join("sso").join("cache"),
+        }
+    }
+
+    fn read(path: &Path) -> IniSections {
+        std::fs::read_to_string(path)
+            .map(|text| parse_ini(&text))
+            .unwrap_or_default()
+    }
+
+    /// Loads the named profile from both files.
+    pub fn load_profile(&self, name: &str) -> AwsProfile {
+        let credentials = Self::read(&self.credentials_path);
+        let config = Self::read(&self.config_path);
+
+        // The config file prefixes every profile but `default` with "profile "
+        let config_section = if name == DEFAULT_PROFILE {
+            config.get(DEFAULT_PROFILE)
+        } else {
+            config.get(&format!("profile {}", name))
+        };
+
+        let mut values = config_section.cloned().unwrap_or_default();
+        // Keys in the credentials file win
+        if let Some(section) = credentials.get(name) {
+            values.extend(section.clone());
+        }
+
+        let sso_session = values
+            .get("sso_session")
+            .and_then(|session| config.get(&format!("sso-session {}", session)))
+            .cloned()
+            .unwrap_or_default();
+
+        AwsProfile {
+            name: name.to_string(),
+            values,
+            sso_session,
+        }
+    }
+}
+
+/// Returns the active profile name.
+pub fn profile_name() -> String {
+    non_empty_env("AWS_PROFILE").unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+// ============================================================================
+// Profile
+// ============================================================================
+
+/// A named profile from the shared files.
+#[derive(Debug, Clone, Default)]
+pub struct AwsProfile {
+    /// Profile name.
+    pub name: String,
+    values: HashMap<String, String>,
+    sso_session: HashMap<String, String>,
+}
+
+impl AwsProfile {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.values
+            .get(key)
+            .map(String::as_str)
+            .filter(|v| !v.is_empty())
+    }
+
+    /// Returns the profile's region.
+    pub fn region(&self) -> Option<&str> {
+        self.get("region")
+    }
+
+    /// Returns static keys, if the profile has them.
+    pub fn static_credentials(&self) -> Option<AwsCredentials> {
+        let access_key_id = self.get("aws_access_key_id")?;
+        let secret_access_key = self.get("aws_secret_access_key")?;
+        Some(AwsCredentials {
+            session_token: self.get("aws_session_token").map(String::from),
+            ..AwsCredentials::new(access_key_id, secret_access_key)
+        })
+    }
+
+    /// Returns the SSO settings, if this is an SSO profile.
+    pub fn sso(&self) -> Option<SsoProfile> {
+        let session_name = self.get("sso_session").map(String::from);
+        let from_session = |key: &str| {
+            self.sso_session
+                .get(key)
+                .map(String::as_str)
+                .filter(|v| !v.is_empty())
+        };
+
+        let start_url = from_session("sso_start_url").or_else(|| self.get("sso_start_url"))?;
+        let sso_region = from_session("sso_region").or_else(|| self.get("sso_region"))?;
+
+        Some(SsoProfile {
+            start_url: start_url.to_string(),
+            sso_region: sso_region.to_string(),
+            account_id: self.get("sso_account_id")?.to_string(),
+            role_name: self.get("sso_role_name")?.to_string(),
+            session_name,
+        })
+    }
+}
+
+/// Resolves the region from the environment, then the profile.
+pub fn resolve_region(profile: &AwsProfile) -> String {
+    non_empty_env("AWS_REGION")
+        .or_else(|| non_empty_env("AWS_DEFAULT_REGION"))
+        .or_else(|| profile.region().map(String::from))
+        .unwrap_or_else(|| DEFAULT_REGION.to_string())
+}
+
+// ============================================================================
+// SSO
+// ============================================================================
+
+/// IAM Identity Center settings for a profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SsoProfile {
+    /// Access portal URL.
+    pub start_url: String,
+    /// Region of the Identity Center instance.
+    pub sso_region: String,
+    /// Account to get credentials for.
+    pub account_id: String,
+    /// Permission set (role) name.
+    pub role_name: String,
+    /// `sso-session` name, for the newer config format.
+    pub session_name: Option<String>,
+}
+
+impl SsoProfile {
+    /// Returns the token cache file name.
+    ///
+    /// The AWS CLI names it after the SHA-1 of the session name, or of the
+    /// start URL for legacy profiles.
+    pub fn cache_file_name(&self) -> String {
+        let key = self.session_name.as_deref().unwrap_or(&self.start_url);
+        let hash = digest::digest(&digest::SHA1_FOR_LEGACY_USE_ONLY, key.as_bytes());
+        format!("{}.json", hex(hash.as_ref()))
+    }
+}
+
+/// A cached SSO access token.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SsoToken {
+    /// Bearer token for the access portal.
+    pub access_token: String,
+    /// Expiry, e.g. "2024-01-01T00:00:00Z" (older CLIs write "...UTC").
+    pub expires_at: String,
+}
+
+impl SsoToken {
+    /// Parses the expiry time.
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        if let Ok(dt) = DateTime::parse_from_rfc3339(&self.expires_at) {
+            return Some(dt.with_timezone(&Utc));
+        }
+        NaiveDateTime::parse_from_str(&self.expires_at, "%Y-%m-%dT%H:%M:%SUTC")
+            .ok()
+            .map(|dt| dt.and_utc())
+    }
+
+    /// Returns true if the token has expired (or the expiry is unreadable).
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at().is_none_or(|at| at <= now)
+    }
+}
+
+/// Reads the cached SSO token for a profile.
+pub fn read_sso_token(files: &AwsConfigFiles, sso: &SsoProfile) -> Result<SsoToken, BedrockError> {
+    let path = files.sso_cache_dir.join(sso.cache_file_name());
+    let text = std::fs::read_to_string(&path).map_err(|_| {
+        BedrockError::CredentialsNotFound(format!(
+            "no cached SSO token at {} (run `aws sso login`)",
+            path.display()
+        ))
+    })?;
+    serde_json::from_str(&text).map_err(|e| BedrockError::ParseError(e.to_string()))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RoleCredentialsResponse {
+    role_credentials: RoleCredentials,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RoleCredentials {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: String,
+    /// Milliseconds since the epoch.
+    expiration: i64,
+}
+
+/// Exchanges a cached SSO token for role credentials.
+#[instrument(skip(token))]
+pub async fn fetch_sso_role_credentials(
+    portal_url: &str,
+    sso: &SsoProfile,
+    token: &SsoToken,
+) -> Result<AwsCredentials, BedrockError> {
+    let url = format!(
+        "{}/federation/credentials",
+        portal_url.trim_end_matches('/')
+    );
+    debug!(url = %url, account = %sso.account_id, "Fetching SSO role credentials");
+
+    let response = reqwest::Client::new()
+        .get(&url)
+        .query(&[
+            ("account_id", sso.account_id.as_str()),
+            ("role_name", sso.role_name.as_str()),
+        ])
+        .header("x-amz-sso_bearer_token", &token.access_token)
+        .send()
+        .await?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        return Err(BedrockError::AuthenticationFailed(
+            "SSO token rejected (run `aws sso login`)".to_string(),
+        ));
+    }
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        return Err(BedrockError::ApiError(format!("HTTP {}: {}", status, text)));
+    }
+
+    let body: RoleCredentialsResponse = response
+        .json()
+        .await
+        .map_err(|e| BedrockError::ParseError(e.to_string()))?;
+    let role = body.role_credentials;
+
+    Ok(AwsCredentials {
+        session_token: Some(role.session_token),
+        expires_at: DateTime::from_timestamp_millis(role.expiration),
+        ..AwsCredentials::new(role.access_key_id, role.secret_access_key)
+    })
+}
+
+/// Returns the access portal URL for an SSO region.
+pub fn sso_portal_url(sso_region: &str) -> String {
+    format!("https://portal.sso.{}.amazonaws.com", sso_region)
+}
+
+// ============================================================================
+// Chain
+// ============================================================================
+
+/// Returns true if any credential source is configured.
+///
+/// This only checks that something is there; SSO tokens may still need a
+/// refresh with `aws sso login`.
+pub fn has_credentials(files: &AwsConfigFiles, profile: &AwsProfile) -> bool {
+    AwsCredentials::from_env().is_some()
+        || profile.static_credentials().is_some()
+        || profile
+            .sso()
+            .is_some_and(|sso| files.sso_cache_dir.join(sso.cache_file_name()).exists())
+}
+
+/// Resolves credentials from the environment, profile keys, then SSO.
+///
+/// `sso_portal` overrides the access portal URL.
+pub async fn resolve_credentials(
+    files: &AwsConfigFiles,
+    profile: &AwsProfile,
+    sso_portal: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<AwsCredentials, BedrockError> {
+    if let Some(credentials) = AwsCredentials::from_env() {
+        debug!("Using AWS credentials from environment");
+        return Ok(credentials);
+    }
+
+    if let Some(credentials) = profile.static_credentials() {
+        debug!(profile = %profile.name, "Using AWS credentials from profile");
+        return Ok(credentials);
+    }
+
+    if let Some(sso) = profile.sso() {
+        debug!(profile = %profile.name, "Using AWS SSO credentials");
+        let token = read_sso_token(files, &sso)?;
+        if token.is_expired(now) {
+            return Err(BedrockError::AuthenticationFailed(
+                "SSO token expired (run `aws sso login`)".to_string(),
+            ));
+        }
+        let portal = sso_portal.map_or_else(|| sso_portal_url(&sso.sso_region), String::from);
+        return fetch_sso_role_credentials(&portal, &sso, &token).await;
+    }
+
+    Err(BedrockError::CredentialsNotFound(format!(
+        "no credentials for profile '{}'",
+        profile.name
+    )))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const CONFIG: &str = "
+[default]
+region = eu-west-1
+
+[profile legacy-sso]
+sso_start_url = https://example.awsapps.com/start
+sso_region = us-east-1
+sso_account_id = 111122223333
+sso_role_name = ReadOnly
+
+[profile prod]
+sso_session = corp
+sso_account_id = 444455556666
+sso_role_name = Billing
+region = us-west-2
+
+[sso-session corp]
+sso_start_url = https://corp.awsapps.com/start
+sso_region = eu-central-1
+";
+
+    const CREDENTIALS: &str = "
+# Long-term keys
+[default]
+aws_access_key_id = AKIDDEFAULT
+aws_secret_access_key = secret
+
+[temp]
+aws_access_key_id=AKIDTEMP
+aws_secret_access_key=secret2
+aws_session_token=token
+";
+
+    fn write_files() -> (PathBuf, AwsConfigFiles) {
+        let dir = std::env::temp_dir().join(format!(
+            "exactobar-aws-{}-{}",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(dir.join("sso").join("cache")).unwrap();
+        std::fs::write(dir.join("config"), CONFIG).unwrap();
+        std::fs::write(dir.join("credentials"), CREDENTIALS).unwrap();
+        let files = AwsConfigFiles::in_dir(&dir);
+        (dir, files)
+    }
+
+    #[test]
+    fn test_parse_ini() {
+        let sections = parse_ini(CREDENTIALS);
+        assert_eq!(sections["default"]["aws_access_key_id"], "AKIDDEFAULT");
+        assert_eq!(sections["temp"]["aws_session_token"], "token");
+    }
+
+    #[test]
+    fn test_static_profiles() {
+        let (dir, files) = write_files();
+
+        let default = files.load_profile("default");
+        assert_eq!(default.region(), Some("eu-west-1"));
+        let credentials = default.static_credentials().unwrap();
+        assert_eq!(credentials.access_key_id, "AKIDDEFAULT");
+        assert!(credentials.session_token.is_none());
+
+        let temp = files.load_profile("temp").static_credentials().unwrap();
+        assert_eq!(temp.session_token.as_deref(), Some("token"));
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_sso_profiles() {
+        let (dir, files) = write_files();
+
+        let legacy = files.load_profile("legacy-sso").sso().unwrap();
+        assert_eq!(legacy.start_url, "https://example.awsapps.com/start");
+        assert_eq!(legacy.account_id, "111122223333");
+        assert!(legacy.session_name.is_none());
+
+        let prod = files.load_profile("prod");
+        assert_eq!(prod.region(), Some("us-west-2"));
+        let sso = prod.sso().unwrap();
+        assert_eq!(sso.start_url, "https://corp.awsapps.com/start");
+        assert_eq!(sso.sso_region, "eu-central-1");
+        assert_eq!(sso.session_name.as_deref(), Some("corp"));
+
+        assert!(files.load_profile("default").sso().is_none());
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_sso_cache_file_name() {
+        let sso = SsoProfile {
+            start_url: "https://example.awsapps.com/start".to_string(),
+            sso_region: "us-east-1".to_string(),
+            account_id: "1".to_string(),
+            role_name: "r".to_string(),
+            session_name: Some("corp".to_string()),
+        };
+        // SHA-1 of "corp"
+        assert_eq!(
+            sso.cache_file_name(),
+            "ee0bfd2552fbd840c02cc48b6e823320543c450f.json"
+        );
+    }
+
+    #[test]
+    fn test_sso_token_expiry() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+        let token = SsoToken {
+            access_token: "t".to_string(),
+            expires_at: "2024-06-01T13:00:00Z".to_string(),
+        };
+        assert!(!token.is_expired(now));
+
+        let legacy = SsoToken {
+            access_token: "t".to_string(),
+            expires_at: "2024-06-01T11:00:00UTC".to_string(),
+        };
+        assert!(legacy.is_expired(now));
+    }
+
+    #[test]
+    fn test_read_sso_token() {
+        let (dir, files) = write_files();
+        let sso = files.load_profile("prod").sso().unwrap();
+        assert!(matches!(
+            read_sso_token(&files, &sso),
+            Err(BedrockError::CredentialsNotFound(_))
+        ));
+
+        std::fs::write(
+            files.sso_cache_dir.join(sso.cache_file_name()),
+            r#"{"startUrl": "https://corp.awsapps.com/start", "region": "eu-central-1",
+                "accessToken": "abc", "expiresAt": "2030-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+        let token = read_sso_token(&files, &sso).unwrap();
+        assert_eq!(token.access_token, "abc");
+        assert!(has_credentials(&files, &files.load_profile("prod")));
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_debug_hides_secrets() {
+        let credentials = AwsCredentials::new("AKID", "very-secret");
+        assert!(!format!("{:?}", credentials).contains("very-secret"));
+    }
+}
@@ -0,0 +1,103 @@
+//! Amazon Bedrock provider descriptor.
+
+use exactobar_core::{IconStyle, ProviderBranding, ProviderColor, ProviderKind, ProviderMetadata};
+use exactobar_fetch::{FetchContext, FetchPipeline, SourceMode};
+use std::collections::HashMap;
+
+use super::strategies::BedrockApiStrategy;
+use crate::descriptor::{CliConfig, FetchPlan, ProviderDescriptor, TokenCostConfig};
+
+// ============================================================================
+// Descriptor
+// ============================================================================
+
+/// Creates the Amazon Bedrock provider descriptor.
+pub fn bedrock_descriptor() -> ProviderDescriptor {
+    ProviderDescriptor {
+        id: ProviderKind::Bedrock,
+        metadata: bedrock_metadata(),
+        branding: bedrock_branding(),
+        token_cost: TokenCostConfig::default(),
+        fetch_plan: bedrock_fetch_plan(),
+        cli: bedrock_cli_config(),
+    }
+}
+
+// ============================================================================
+// Metadata
+// ============================================================================
+
+fn bedrock_metadata() -> ProviderMetadata {
+    ProviderMetadata {
+        id: ProviderKind::Bedrock,
+        display_name: "Amazon Bedrock".to_string(),
+        session_label: "Peak TPM".to_string(),
+        weekly_label: "Tokens today".to_string(),
+        opus_label: None,
+        supports_opus: false,
+        window_labels: HashMap::from([
+            ("tpm_headroom".to_string(), "Peak TPM".to_string()),
+            ("tokens_today".to_string(), "Tokens today".to_string()),
+        ]),
+        supports_credits: false,
+        credits_hint: String::new(),
+        toggle_title: "Show Amazon Bedrock usage".to_string(),
+        cli_name: "bedrock".to_string(),
+        default_enabled: false,
+        is_primary_provider: false,
+        uses_account_fallback: false,
+        dashboard_url: Some("https://console.aws.amazon.com/bedrock/home".to_string()),
+        subscription_dashboard_url: Some(
+            "https://console.aws.amazon.com/servicequotas/home/services/bedrock/quotas".to_string(),
+        ),
+        status_page_url: Some("https://health.aws.amazon.com/health/status".to_string()),
+        status_link_url: None,
+    }
+}
+
+// ============================================================================
+// Branding
+// ============================================================================
+
+fn bedrock_branding() -> ProviderBranding {
+    ProviderBranding {
+        icon_style: IconStyle::Bedrock,
+        icon_resource_name: "icon_bedrock".to_string(),
+        // AWS "smile" orange
+        color: ProviderColor::new(1.0, 0.6, 0.0),
+    }
+}
+
+// ============================================================================
+// Fetch Plan
+// ============================================================================
+
+fn bedrock_fetch_plan() -> FetchPlan {
+    FetchPlan {
+        source_modes: vec![SourceMode::ApiKey],
+        build_pipeline: Box::new(build_bedrock_pipeline),
+    }
+}
+
+fn build_bedrock_pipeline(ctx: &FetchContext) -> FetchPipeline {
+    let mut strategies: Vec<Box<dyn exactobar_fetch::FetchStrategy>> = Vec::new();
+
+    if ctx.settings.source_mode.allows_api_key() {
+        strategies.push(Box::new(BedrockApiStrategy::new()));
+    }
+
+    FetchPipeline::with_strategies(strategies)
+}
+
+// ============================================================================
+// CLI Config
+// ============================================================================
+
+fn bedrock_cli_config() -> CliConfig {
+    CliConfig {
+        name: "bedrock",
+        aliases: &["aws-bedrock", "aws"],
+        version_args: &["--version"],
+        usage_args: &[],
+    }
+}
@@ -0,0 +1,33 @@
+//! Amazon Bedrock-specific errors.
+
+use thiserror::Error;
+
+/// Amazon Bedrock errors.
+#[derive(Debug, Error)]
+pub enum BedrockError {
+    /// No AWS credentials found in the environment, profiles or SSO cache.
+    #[error("AWS credentials not found: {0}")]
+    CredentialsNotFound(String),
+
+    /// HTTP request failed.
+    #[error("HTTP error: {0}")]
+    HttpError(String),
+
+    /// Parse error.
+    #[error("Parse error: {0}")]
+    ParseError(String),
+
+    /// API error.
+    #[error("API error: {0}")]
+    ApiError(String),
+
+    /// Authentication failed.
+    #[error("Authentication failed: {0}")]
+    AuthenticationFailed(String),
+}
+
+impl From<reqwest::Error> for BedrockError {
+    fn from(err: reqwest::Error) -> Self {
+        BedrockError::HttpError(err.to_string())
+    }
+}
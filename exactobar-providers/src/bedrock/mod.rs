@@ -0,0 +1,26 @@
+//! Amazon Bedrock provider implementation.
+//!
+//! Bedrock has no usage API of its own, so the provider reads the token
+//! metrics Bedrock publishes to CloudWatch and the tokens-per-minute quotas
+//! from Service Quotas. Requests are signed with SigV4 using credentials
+//! from the standard AWS chain: environment, `~/.aws` profiles, then the
+//! AWS CLI's SSO token cache.
+//!
+//! The profile is `AWS_PROFILE` and the region `AWS_REGION`,
+//! `AWS_DEFAULT_REGION` or the profile's region.
+
+mod api;
+mod credentials;
+mod descriptor;
+mod error;
+mod sigv4;
+mod strategies;
+
+pub use api::{
+    BedrockApiClient, BedrockEndpoints, BedrockUsage, DayTokens, ModelUsage, ServiceQuota,
+    match_tpm_quota,
+};
+pub use credentials::{AwsConfigFiles, AwsCredentials, AwsProfile, SsoProfile};
+pub use descriptor::bedrock_descriptor;
+pub use error::BedrockError;
+pub use strategies::BedrockApiStrategy;
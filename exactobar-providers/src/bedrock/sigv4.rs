@@ -0,0 +1,314 @@
+//! AWS Signature Version 4 request signing.
+//!
+//! Implements the header-based variant described in the AWS General
+//! Reference. Only what the Bedrock provider needs: `Authorization` and
+//! `X-Amz-Date` headers, plus `X-Amz-Security-Token` for temporary
+//! credentials. Request paths are not double-encoded, since every request
+//! this provider sends goes to `/`.
+
+use chrono::{DateTime, Utc};
+use ring::{digest, hmac};
+use std::fmt::Write;
+
+use super::credentials::AwsCredentials;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+// ============================================================================
+// Request
+// ============================================================================
+
+/// The parts of an HTTP request that are signed.
+#[derive(Debug, Clone)]
+pub struct SignableRequest<'a> {
+    /// HTTP method (e.g., "POST").
+    pub method: &'a str,
+    /// Host header value, including a non-default port.
+    pub host: &'a str,
+    /// Absolute request path (e.g., "/").
+    pub path: &'a str,
+    /// Query parameters, unencoded.
+    pub query: &'a [(&'a str, &'a str)],
+    /// Extra headers to sign (e.g., `content-type`, `x-amz-target`).
+    pub headers: &'a [(&'a str, &'a str)],
+    /// Request body.
+    pub body: &'a [u8],
+}
+
+/// Signs a request and returns the headers to add to it.
+pub fn sign(
+    request: &SignableRequest<'_>,
+    credentials: &AwsCredentials,
+    region: &str,
+    service: &str,
+    time: DateTime<Utc>,
+) -> Vec<(String, String)> {
+    let amz_date = time.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = time.format("%Y%m%d").to_string();
+
+    let mut headers: Vec<(String, String)> = request
+        .headers
+        .iter()
+        .map(|(name, value)| (name.to_lowercase(), normalize_header_value(value)))
+        .collect();
+    headers.push(("host".to_string(), request.host.to_string()));
+    headers.push(("x-amz-date".to_string(), amz_date.clone()));
+    if let Some(token) = &credentials.session_token {
+        headers.push(("x-amz-security-token".to_string(), token.clone()));
+    }
+    headers.sort();
+
+    let signed_headers = headers
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+    let canonical_headers = headers
+        .iter()
+        .fold(String::new(), |mut out, (name, value)| {
+            let _ = writeln!(out, "{}:{}", name, value);
+            out
+        });
+
+    let canonical_request = [
+        request.method,
+        &uri_encode(request.path, false),
+        &canonical_query(request.query),
+        &canonical_headers,
+        &signed_headers,
+        &hex_sha256(request.body),
+    ]
+    .join("\n");
+
+    let scope = format!("{}/{}/{}/aws4_request", date, region, service);
+    let string_to_sign = format!(
+        "{}\n{}\n{}\n{}",
+        ALGORITHM,
+        amz_date,
+        scope,
+        hex_sha256(canonical_request.as_bytes())
+    );
+
+    let key = signing_key(&credentials.secret_access_key, &date, region, service);
+    let signature = hex(hmac_sha256(&key, string_to_sign.as_bytes()).as_ref());
+
+    let mut out = vec![
+        (
+            "authorization".to_string(),
+            format!(
+                "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+                ALGORITHM, credentials.access_key_id, scope, signed_headers, signature
+            ),
+        ),
+        ("x-amz-date".to_string(), amz_date),
+    ];
+    if let Some(token) = &credentials.session_token {
+        out.push(("x-amz-security-token".to_string(), token.clone()));
+    }
+    out
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Derives the signing key for a date, region and service.
+fn signing_key(secret: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let mut key = format!("AWS4{}", secret).into_bytes();
+    for part in [date, region, service, "aws4_request"] {
+        key = hmac_sha256(&key, part.as_bytes()).as_ref().to_vec();
+    }
+    key
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> hmac::Tag {
+    hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key), data)
+}
+
+fn canonical_query(query: &[(&str, &str)]) -> String {
+    let mut pairs: Vec<(String, String)> = query
+        .iter()
+        .map(|(k, v)| (uri_encode(k, true), uri_encode(v, true)))
+        .collect();
+    pairs.sort();
+    pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Percent-encodes everything except unreserved characters (RFC 3986).
+fn uri_encode(input: &str, encode_slash: bool) -> String {
+    let mut out = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char);
+            }
+            b'/' if !encode_slash => out.push('/'),
+            _ => {
+                let _ = write!(out, "%{:02X}", byte);
+            }
+        }
+    }
+    out
+}
+
+/// Trims a header value and collapses runs of spaces.
+fn normalize_header_value(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+pub(crate) fn hex_sha256(data: &[u8]) -> String {
+    hex(digest::digest(&digest::SHA256, data).as_ref())
+}
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut out, b| {
+            let _ = write!(out, "{:02x}", b);
+            out
+        })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// Credentials from the AWS SigV4 test suite.
+    fn example_credentials() -> AwsCredentials {
+        AwsCredentials::new("AKIDEXAMPLE", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY")
+    }
+
+    fn suite_time() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2015, 8, 30, 12, 36, 0).unwrap()
+    }
+
+    fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+        headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn test_signing_key_matches_aws_example() {
+        // From "Examples of how to derive a signing key" in the AWS docs
+        let key = signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex(&key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn test_get_vanilla() {
+        let request = SignableRequest {
+            method: "GET",
+            host: "example.amazonaws.com",
+            path: "/",
+            query: &[],
+            headers: &[],
+            body: b"",
+        };
+        let headers = sign(
+            &request,
+            &example_credentials(),
+            "us-east-1",
+            "service",
+            suite_time(),
+        );
+
+        assert_eq!(header(&headers, "x-amz-date"), Some("20150830T123600Z"));
+        assert_eq!(
+            header(&headers, "authorization"),
+            Some(
+                "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+                 SignedHeaders=host;x-amz-date, \
+                 Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+            )
+        );
+        assert!(header(&headers, "x-amz-security-token").is_none());
+    }
+
+    #[test]
+    fn test_get_vanilla_query_order() {
+        let request = SignableRequest {
+            method: "GET",
+            host: "example.amazonaws.com",
+            path: "/",
+            query: &[("Param2", "value2"), ("Param1", "value1")],
+            headers: &[],
+            body: b"",
+        };
+        let headers = sign(
+            &request,
+            &example_credentials(),
+            "us-east-1",
+            "service",
+            suite_time(),
+        );
+
+        assert!(header(&headers, "authorization").unwrap().ends_with(
+            "Signature=b97d918cfa904a5beff61c982a1b6f458b799221646efd99d3219ec94cdf2500"
+        ));
+    }
+
+    #[test]
+    fn test_session_token_is_signed() {
+        let mut credentials = example_credentials();
+        credentials.session_token = Some("token".to_string());
+        let request = SignableRequest {
+            method: "POST",
+            host: "monitoring.us-east-1.amazonaws.com",
+            path: "/",
+            query: &[],
+            headers: &[("Content-Type", "application/x-amz-json-1.0")],
+            body: b"{}",
+        };
+        let headers = sign(
+            &request,
+            &credentials,
+            "us-east-1",
+            "monitoring",
+            suite_time(),
+        );
+
+        assert_eq!(header(&headers, "x-amz-security-token"), Some("token"));
+        assert!(
+            header(&headers, "authorization")
+                .unwrap()
+                .contains("SignedHeaders=content-type;host;x-amz-date;x-amz-security-token,")
+        );
+    }
+
+    #[test]
+    fn test_uri_encode() {
+        assert_eq!(uri_encode("a b/c~d", true), "a%20b%2Fc~d");
+        assert_eq!(uri_encode("/path/x", false), "/path/x");
+    }
+
+    #[test]
+    fn test_hex_sha256_empty() {
+        assert_eq!(
+            hex_sha256(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! Amazon Bedrock fetch strategies.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use exactobar_core::ProviderKind;
+use exactobar_fetch::{FetchContext, FetchError, FetchKind, FetchResult, FetchStrategy};
+use tracing::{debug, instrument};
+
+use super::api::{BedrockApiClient, BedrockEndpoints};
+use super::credentials::{
+    AwsConfigFiles, has_credentials, profile_name, resolve_credentials, resolve_region,
+};
+use super::error::BedrockError;
+
+// ============================================================================
+// API Strategy
+// ============================================================================
+
+/// AWS credentials strategy for Bedrock.
+///
+/// Reads CloudWatch metrics and Service Quotas with SigV4-signed requests.
+/// The provider's `base_url` setting redirects every request, e.g. to
+/// LocalStack.
+pub struct BedrockApiStrategy;
+
+impl BedrockApiStrategy {
+    /// Creates a new strategy.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for BedrockApiStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FetchStrategy for BedrockApiStrategy {
+    fn id(&self) -> &str {
+        "bedrock.api"
+    }
+
+    fn kind(&self) -> FetchKind {
+        FetchKind::ApiKey
+    }
+
+    #[instrument(skip(self, _ctx))]
+    async fn is_available(&self, _ctx: &FetchContext) -> bool {
+        let files = AwsConfigFiles::from_env();
+        has_credentials(&files, &files.load_profile(&profile_name()))
+    }
+
+    #[instrument(skip(self, ctx))]
+    async fn fetch(&self, ctx: &FetchContext) -> Result<FetchResult, FetchError> {
+        debug!("Fetching Bedrock usage from CloudWatch");
+
+        let files = AwsConfigFiles::from_env();
+        let profile = files.load_profile(&profile_name());
+        let region = resolve_region(&profile);
+        let options = ctx.provider_options(ProviderKind::Bedrock);
+        let endpoints = BedrockEndpoints::resolve(&region, options.base_url.as_deref());
+
+        let now = Utc::now();
+        let credentials =
+            resolve_credentials(&files, &profile, endpoints.sso_portal.as_deref(), now)
+                .await
+                .map_err(|e| FetchError::AuthenticationFailed(e.to_string()))?;
+
+        let usage = BedrockApiClient::new(region, endpoints)
+            .fetch_usage(&credentials, now)
+            .await
+            .map_err(|e| match e {
+                BedrockError::AuthenticationFailed(msg) => FetchError::AuthenticationFailed(msg),
+                other => FetchError::InvalidResponse(other.to_string()),
+            })?;
+
+        Ok(FetchResult::new(
+            usage.to_snapshot(now),
+            self.id(),
+            self.kind(),
+        ))
+    }
+
+    fn priority(&self) -> u32 {
+        60 // API Key priority
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_strategy() {
+        let s = BedrockApiStrategy::new();
+        assert_eq!(s.id(), "bedrock.api");
+        assert_eq!(s.kind(), FetchKind::ApiKey);
+        assert_eq!(s.priority(), 60);
+    }
+}
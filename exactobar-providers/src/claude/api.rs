@@ -16,10 +16,15 @@
 //! {
 //!   "five_hour": { "utilization": 6.0, "resets_at": "2025-11-04T04:59:59.943648+00:00" },
 //!   "seven_day": { "utilization": 35.0, "resets_at": "2025-11-06T03:59:59.943679+00:00" },
-//!   "seven_day_opus": { "utilization": 0.0, "resets_at": null }
+//!   "seven_day_opus": { "utilization": 0.0, "resets_at": null },
+//!   "seven_day_sonnet": { "utilization": 12.0, "resets_at": "2025-11-06T03:59:59.943679+00:00" },
+//!   "seven_day_oauth_apps": null,
+//!   "extra_usage": { "is_enabled": true, "monthly_limit": 5000, "used_credits": 1250.0, "utilization": 25.0 }
 //! }
 //! ```
 
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use tracing::{debug, info, instrument, warn};
@@ -46,6 +51,10 @@ pub const ANTHROPIC_BETA_HEADER: &str = "oauth-2025-04-20";
 
 /// Response from the OAuth usage API.
 /// Note: Uses snake_case field names matching the actual API response.
+///
+/// Buckets that are not listed here (or that the plan doesn't have, which
+/// the API reports as `null`) are kept in `other` so new ones don't break
+/// parsing.
 #[derive(Debug, Clone, Deserialize)]
 pub struct OAuthUsageResponse {
     /// 5-hour usage window.
@@ -54,9 +63,18 @@ pub struct OAuthUsageResponse {
     pub seven_day: Option<OAuthUsageWindow>,
     /// 7-day Opus usage window.
     pub seven_day_opus: Option<OAuthUsageWindow>,
-    /// 7-day OAuth apps usage window (optional).
-    #[allow(dead_code)]
+    /// 7-day Sonnet usage window.
+    #[serde(default)]
+    pub seven_day_sonnet: Option<OAuthUsageWindow>,
+    /// 7-day OAuth apps usage window.
+    #[serde(default)]
     pub seven_day_oauth_apps: Option<OAuthUsageWindow>,
+    /// Extra usage (monthly pay-as-you-go spend).
+    #[serde(default)]
+    pub extra_usage: Option<OAuthExtraUsage>,
+    /// Any other buckets, as raw JSON.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
 }
 
 /// Individual usage window from OAuth API.
@@ -85,30 +103,81 @@ impl OAuthUsageWindow {
     }
 }
 
+/// Extra usage from the OAuth API.
+///
+/// Amounts are in minor currency units (cents).
+#[derive(Debug, Clone, Deserialize)]
+pub struct OAuthExtraUsage {
+    /// Whether extra usage is enabled.
+    pub is_enabled: Option<bool>,
+    /// Monthly spend cap, in cents.
+    pub monthly_limit: Option<f64>,
+    /// Spend this month, in cents.
+    pub used_credits: Option<f64>,
+    /// Spend as a percentage of the cap.
+    pub utilization: Option<f64>,
+    /// Currency (defaults to USD).
+    pub currency: Option<String>,
+}
+
+impl From<OAuthUsageWindow> for UsageWindow {
+    fn from(window: OAuthUsageWindow) -> Self {
+        Self {
+            utilization: window.utilization,
+            resets_at: window.resets_at,
+            remaining: None,
+            used_percent: None,
+        }
+    }
+}
+
+impl From<OAuthExtraUsage> for ExtraUsage {
+    fn from(extra: OAuthExtraUsage) -> Self {
+        Self {
+            is_enabled: extra.is_enabled,
+            used_credits: extra.used_credits.map(|c| c / 100.0),
+            monthly_limit: extra.monthly_limit.map(|c| c / 100.0),
+            currency: extra.currency,
+        }
+    }
+}
+
 impl OAuthUsageResponse {
+    /// Returns the buckets not covered by a named field.
+    ///
+    /// Null buckets and values that aren't usage windows are skipped.
+    pub fn other_buckets(&self) -> BTreeMap<String, OAuthUsageWindow> {
+        self.other
+            .iter()
+            .filter(|(_, value)| !value.is_null())
+            .filter_map(|(key, value)| {
+                match serde_json::from_value::<OAuthUsageWindow>(value.clone()) {
+                    Ok(window) => Some((key.clone(), window)),
+                    Err(e) => {
+                        debug!(bucket = %key, error = %e, "Skipping unrecognized usage bucket");
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
     /// Convert to UsageApiResponse for compatibility.
     pub fn into_usage_api_response(self) -> UsageApiResponse {
+        let other_buckets = self
+            .other_buckets()
+            .into_iter()
+            .map(|(key, window)| (key, window.into()))
+            .collect();
+
         UsageApiResponse {
-            five_hour: self.five_hour.map(|w| UsageWindow {
-                utilization: w.utilization,
-                resets_at: w.resets_at,
-                remaining: None,
-                used_percent: None,
-            }),
-            seven_day: self.seven_day.map(|w| UsageWindow {
-                utilization: w.utilization,
-                resets_at: w.resets_at,
-                remaining: None,
-                used_percent: None,
-            }),
-            // Map seven_day_opus to seven_day_sonnet for compatibility
-            seven_day_sonnet: self.seven_day_opus.map(|w| UsageWindow {
-                utilization: w.utilization,
-                resets_at: w.resets_at,
-                remaining: None,
-                used_percent: None,
-            }),
-            extra_usage: None,
+            five_hour: self.five_hour.map(Into::into),
+            seven_day: self.seven_day.map(Into::into),
+            seven_day_opus: self.seven_day_opus.map(Into::into),
+            seven_day_sonnet: self.seven_day_sonnet.map(Into::into),
+            seven_day_oauth_apps: self.seven_day_oauth_apps.map(Into::into),
+            other_buckets,
+            extra_usage: self.extra_usage.map(Into::into),
             account: None,
         }
     }
@@ -126,8 +195,17 @@ pub struct UsageApiResponse {
     pub five_hour: Option<UsageWindow>,
     /// 7-day usage window (all models).
     pub seven_day: Option<UsageWindow>,
-    /// 7-day Sonnet/Opus usage window.
+    /// 7-day Opus usage window.
+    #[serde(default)]
+    pub seven_day_opus: Option<UsageWindow>,
+    /// 7-day Sonnet usage window.
     pub seven_day_sonnet: Option<UsageWindow>,
+    /// 7-day OAuth apps usage window.
+    #[serde(default)]
+    pub seven_day_oauth_apps: Option<UsageWindow>,
+    /// Any other buckets, keyed by their API name.
+    #[serde(default)]
+    pub other_buckets: BTreeMap<String, UsageWindow>,
     /// Extra usage/credits info.
     pub extra_usage: Option<ExtraUsage>,
     /// Account info.
@@ -180,14 +258,38 @@ impl UsageWindow {
 pub struct ExtraUsage {
     /// Whether extra usage is enabled.
     pub is_enabled: Option<bool>,
-    /// Credits used this month.
+    /// Amount spent this month (major currency units).
     pub used_credits: Option<f64>,
-    /// Monthly credit limit.
+    /// Monthly spend cap (major currency units).
     pub monthly_limit: Option<f64>,
     /// Currency (e.g., "USD").
     pub currency: Option<String>,
 }
 
+impl ExtraUsage {
+    /// Convert extra-usage spend to credits with a spend cap.
+    ///
+    /// Returns `None` when extra usage is off and nothing was spent.
+    pub fn to_credits(&self) -> Option<exactobar_core::Credits> {
+        use exactobar_core::{Amount, Credits, SpendLimit, USD};
+
+        let used = self.used_credits.unwrap_or(0.0);
+        let enabled = self.is_enabled.unwrap_or(self.monthly_limit.is_some());
+        if !enabled && used <= 0.0 {
+            return None;
+        }
+
+        let currency = self.currency.clone().unwrap_or_else(|| USD.to_string());
+        let cap = self.monthly_limit.filter(|limit| *limit > 0.0);
+
+        let mut spend = SpendLimit::new(Amount::new(used, &currency));
+        spend.cap = cap.map(|limit| Amount::new(limit, currency));
+        spend.overage_enabled = enabled;
+
+        Some(Credits::from_spend(spend))
+    }
+}
+
 /// Account information from API.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -300,67 +402,28 @@ impl ClaudeApiClient {
         })?;
 
         info!(
-            "Parsed OAuth usage: five_hour={:?}, seven_day={:?}, seven_day_opus={:?}",
-            oauth_response.five_hour, oauth_response.seven_day, oauth_response.seven_day_opus
+            "Parsed OAuth usage: five_hour={:?}, seven_day={:?}, seven_day_opus={:?}, seven_day_sonnet={:?}, extra_usage={:?}",
+            oauth_response.five_hour,
+            oauth_response.seven_day,
+            oauth_response.seven_day_opus,
+            oauth_response.seven_day_sonnet,
+            oauth_response.extra_usage
         );
 
         // Convert to UsageApiResponse for internal compatibility
-        Ok(oauth_response.into_usage_api_response())
-    }
-
-    /// Fetch usage using the access token directly.
-    #[instrument(skip(self, access_token))]
-    pub async fn fetch_usage_with_token(
-        &self,
-        access_token: &str,
-    ) -> Result<UsageApiResponse, ClaudeError> {
-        let url = format!("{}{}", self.base_url, USAGE_ENDPOINT);
-
-        debug!(url = %url, "Fetching usage from API with token");
-
-        let client = reqwest::Client::builder()
-            .build()
-            .map_err(|e| ClaudeError::HttpError(e.to_string()))?;
-
-        let response = client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", access_token))
-            .header("anthropic-beta", ANTHROPIC_BETA_HEADER) // ESSENTIAL!
-            .header("Content-Type", "application/json")
-            .header("User-Agent", "claude-code/2.0.32")
-            .header("Accept", "application/json, text/plain, */*")
-            .send()
-            .await
-            .map_err(|e| ClaudeError::HttpError(e.to_string()))?;
-
-        let status = response.status();
+        let mut response = oauth_response.into_usage_api_response();
 
-        if status == reqwest::StatusCode::UNAUTHORIZED {
-            return Err(ClaudeError::AuthenticationFailed(
-                "Token rejected".to_string(),
-            ));
-        }
-
-        if !status.is_success() {
-            let body = response.text().await.unwrap_or_default();
-            return Err(ClaudeError::ApiError(format!("HTTP {}: {}", status, body)));
+        // The usage endpoint carries no account info; the plan comes from the tier
+        if let Some(plan) = credentials.plan_name() {
+            response.account = Some(AccountInfo {
+                email: None,
+                name: None,
+                plan: Some(plan),
+                organization: None,
+            });
         }
 
-        let body = response
-            .text()
-            .await
-            .map_err(|e| ClaudeError::HttpError(e.to_string()))?;
-
-        debug!(
-            "Raw OAuth usage response: {}",
-            &body[..body.len().min(2000)]
-        );
-
-        // Parse OAuth usage response
-        let oauth_response: OAuthUsageResponse =
-            serde_json::from_str(&body).map_err(|e| ClaudeError::ParseError(e.to_string()))?;
-
-        Ok(oauth_response.into_usage_api_response())
+        Ok(response)
     }
 }
 
@@ -371,41 +434,67 @@ impl ClaudeApiClient {
 impl UsageApiResponse {
     /// Convert to a UsageSnapshot.
     pub fn to_snapshot(&self) -> exactobar_core::UsageSnapshot {
-        use exactobar_core::{FetchSource, LoginMethod, ProviderIdentity, ProviderKind};
+        use exactobar_core::{
+            FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageWindow, WindowKind,
+        };
 
         let mut snapshot = exactobar_core::UsageSnapshot::new();
         snapshot.fetch_source = FetchSource::OAuth;
 
         // Primary = 5-hour window
         if let Some(ref window) = self.five_hour {
-            snapshot.primary = Some(exactobar_core::UsageWindow {
-                used_percent: window.get_used_percent(),
+            snapshot.set_primary(Some(UsageWindow {
                 window_minutes: Some(300), // 5 hours
                 resets_at: window.get_resets_at(),
-                reset_description: None,
-            });
+                ..UsageWindow::named("session", WindowKind::Session, window.get_used_percent())
+            }));
         }
 
         // Secondary = 7-day window (all models)
         if let Some(ref window) = self.seven_day {
-            snapshot.secondary = Some(exactobar_core::UsageWindow {
-                used_percent: window.get_used_percent(),
+            snapshot.set_secondary(Some(UsageWindow {
                 window_minutes: Some(10080), // 7 days
                 resets_at: window.get_resets_at(),
-                reset_description: None,
-            });
+                ..UsageWindow::named("weekly", WindowKind::Weekly, window.get_used_percent())
+            }));
         }
 
-        // Tertiary = 7-day Opus window (mapped from seven_day_sonnet for compat)
-        if let Some(ref window) = self.seven_day_sonnet {
-            snapshot.tertiary = Some(exactobar_core::UsageWindow {
-                used_percent: window.get_used_percent(),
+        // Tertiary = 7-day Opus window
+        if let Some(ref window) = self.seven_day_opus {
+            snapshot.set_tertiary(Some(UsageWindow {
                 window_minutes: Some(10080), // 7 days
                 resets_at: window.get_resets_at(),
-                reset_description: None,
+                ..UsageWindow::named("opus", WindowKind::Weekly, window.get_used_percent())
+            }));
+        }
+
+        // Remaining buckets are unslotted named windows
+        let named = [
+            ("sonnet", self.seven_day_sonnet.as_ref()),
+            ("oauth_apps", self.seven_day_oauth_apps.as_ref()),
+        ]
+        .into_iter()
+        .filter_map(|(id, window)| Some((id, Some(10080), window?)));
+        let other = self
+            .other_buckets
+            .iter()
+            .map(|(key, window)| (key.as_str(), bucket_window_minutes(key), window));
+
+        for (id, window_minutes, window) in named.chain(other) {
+            snapshot.push_window(UsageWindow {
+                window_minutes,
+                resets_at: window.get_resets_at(),
+                ..UsageWindow::named(
+                    id,
+                    WindowKind::from_minutes(window_minutes),
+                    window.get_used_percent(),
+                )
             });
         }
 
+        // Extra usage spend
+        snapshot.credits = self.extra_usage.as_ref().and_then(ExtraUsage::to_credits);
+
         // Account identity
         if let Some(ref account) = self.account {
             let mut identity = ProviderIdentity::new(ProviderKind::Claude);
@@ -420,6 +509,19 @@ impl UsageApiResponse {
     }
 }
 
+/// Infers the window length from an OAuth bucket name.
+///
+/// Buckets are named after their length (`five_hour`, `seven_day_sonnet`).
+fn bucket_window_minutes(key: &str) -> Option<u32> {
+    if key.starts_with("five_hour") {
+        Some(300)
+    } else if key.starts_with("seven_day") {
+        Some(10080)
+    } else {
+        None
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -475,7 +577,10 @@ mod tests {
                 utilization: 10.0,
                 resets_at: None,
             }),
+            seven_day_sonnet: None,
             seven_day_oauth_apps: None,
+            extra_usage: None,
+            other: BTreeMap::new(),
         };
 
         let response = oauth_response.into_usage_api_response();
@@ -486,8 +591,96 @@ mod tests {
         assert!(response.seven_day.is_some());
         assert!((response.seven_day.as_ref().unwrap().utilization - 50.0).abs() < 0.01);
 
-        assert!(response.seven_day_sonnet.is_some());
-        assert!((response.seven_day_sonnet.as_ref().unwrap().utilization - 10.0).abs() < 0.01);
+        assert!(response.seven_day_opus.is_some());
+        assert!((response.seven_day_opus.as_ref().unwrap().utilization - 10.0).abs() < 0.01);
+        assert!(response.seven_day_sonnet.is_none());
+    }
+
+    #[test]
+    fn test_parse_all_buckets() {
+        let json = r#"{
+            "five_hour": { "utilization": 6.0, "resets_at": "2025-11-04T04:59:59.943648+00:00" },
+            "seven_day": { "utilization": 35.0, "resets_at": "2025-11-06T03:59:59.943679+00:00" },
+            "seven_day_opus": null,
+            "seven_day_sonnet": { "utilization": 12.0, "resets_at": null },
+            "seven_day_oauth_apps": { "utilization": 3.0, "resets_at": null },
+            "seven_day_haiku": { "utilization": 1.5, "resets_at": null },
+            "iguana_necktie": null,
+            "some_flag": true,
+            "extra_usage": {
+                "is_enabled": true,
+                "monthly_limit": 5000,
+                "used_credits": 1250.0,
+                "utilization": 25.0
+            }
+        }"#;
+
+        let oauth: OAuthUsageResponse = serde_json::from_str(json).unwrap();
+        let buckets = oauth.other_buckets();
+        assert_eq!(buckets.len(), 1);
+        assert!(buckets.contains_key("seven_day_haiku"));
+
+        let response = oauth.into_usage_api_response();
+        assert!(response.seven_day_opus.is_none());
+        assert!((response.seven_day_sonnet.as_ref().unwrap().utilization - 12.0).abs() < 0.01);
+
+        let extra = response.extra_usage.as_ref().unwrap();
+        assert!((extra.used_credits.unwrap() - 12.5).abs() < 0.001);
+        assert!((extra.monthly_limit.unwrap() - 50.0).abs() < 0.001);
+
+        let snapshot = response.to_snapshot();
+        assert!(snapshot.tertiary().is_none());
+
+        let sonnet = snapshot.window("sonnet").unwrap();
+        assert!((sonnet.used_percent - 12.0).abs() < 0.01);
+        assert_eq!(sonnet.window_minutes, Some(10080));
+        assert_eq!(sonnet.kind, exactobar_core::WindowKind::Weekly);
+        assert!(snapshot.window("oauth_apps").is_some());
+
+        let haiku = snapshot.window("seven_day_haiku").unwrap();
+        assert!((haiku.used_percent - 1.5).abs() < 0.01);
+        assert_eq!(haiku.window_minutes, Some(10080));
+        assert_eq!(snapshot.windows.len(), 5);
+    }
+
+    #[test]
+    fn test_extra_usage_to_credits() {
+        let extra = ExtraUsage {
+            is_enabled: Some(true),
+            used_credits: Some(12.5),
+            monthly_limit: Some(50.0),
+            currency: None,
+        };
+
+        let credits = extra.to_credits().unwrap();
+        assert_eq!(credits.unit, "USD");
+        assert!((credits.remaining - 37.5).abs() < 0.001);
+        assert_eq!(credits.total, Some(50.0));
+
+        let spend = credits.spend_limit.as_ref().unwrap();
+        assert!(spend.overage_enabled);
+        assert!((spend.used_percent().unwrap() - 25.0).abs() < 0.001);
+
+        let disabled = ExtraUsage {
+            is_enabled: Some(false),
+            used_credits: Some(0.0),
+            monthly_limit: None,
+            currency: None,
+        };
+        assert!(disabled.to_credits().is_none());
+
+        let uncapped = ExtraUsage {
+            is_enabled: Some(true),
+            used_credits: Some(3.0),
+            monthly_limit: None,
+            currency: Some("EUR".to_string()),
+        };
+        let credits = uncapped.to_credits().unwrap();
+        assert_eq!(credits.unit, "EUR");
+        // No cap means no balance: report the spend, not $0 left
+        assert!((credits.remaining - 3.0).abs() < 0.001);
+        assert!(credits.total.is_none());
+        assert!(credits.spend_limit.unwrap().used_percent().is_none());
     }
 
     #[test]
@@ -535,7 +728,10 @@ mod tests {
                 remaining: None,
                 used_percent: None,
             }),
+            seven_day_opus: None,
             seven_day_sonnet: None,
+            seven_day_oauth_apps: None,
+            other_buckets: BTreeMap::new(),
             extra_usage: None,
             account: Some(AccountInfo {
                 email: Some("test@example.com".to_string()),
@@ -547,13 +743,13 @@ mod tests {
 
         let snapshot = response.to_snapshot();
 
-        assert!(snapshot.primary.is_some());
-        assert!((snapshot.primary.as_ref().unwrap().used_percent - 25.0).abs() < 0.01);
+        assert!(snapshot.primary().is_some());
+        assert!((snapshot.primary().unwrap().used_percent - 25.0).abs() < 0.01);
 
-        assert!(snapshot.secondary.is_some());
-        assert!((snapshot.secondary.as_ref().unwrap().used_percent - 45.0).abs() < 0.01);
+        assert!(snapshot.secondary().is_some());
+        assert!((snapshot.secondary().unwrap().used_percent - 45.0).abs() < 0.01);
 
-        assert!(snapshot.tertiary.is_none());
+        assert!(snapshot.tertiary().is_none());
 
         assert!(snapshot.identity.is_some());
         assert_eq!(
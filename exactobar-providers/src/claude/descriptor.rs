@@ -1,7 +1,8 @@
 //! Claude provider descriptor.
 
 use exactobar_core::{IconStyle, ProviderBranding, ProviderColor, ProviderKind, ProviderMetadata};
-use exactobar_fetch::{FetchContext, FetchPipeline, SourceMode};
+use exactobar_fetch::{FetchContext, FetchPipeline, PipelineMode, SourceMode};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use super::strategies::{
@@ -30,8 +31,12 @@ fn claude_metadata() -> ProviderMetadata {
         weekly_label: "Weekly".to_string(),
         opus_label: Some("Opus".to_string()),
         supports_opus: true,
-        supports_credits: false,
-        credits_hint: String::new(),
+        window_labels: HashMap::from([
+            ("sonnet".to_string(), "Sonnet".to_string()),
+            ("oauth_apps".to_string(), "OAuth apps".to_string()),
+        ]),
+        supports_credits: true,
+        credits_hint: "Extra usage".to_string(),
         toggle_title: "Show Claude usage".to_string(),
         cli_name: "claude".to_string(),
         default_enabled: true,
@@ -71,7 +76,7 @@ fn claude_log_directory() -> Option<PathBuf> {
 fn claude_fetch_plan() -> FetchPlan {
     FetchPlan {
         source_modes: vec![SourceMode::OAuth, SourceMode::CLI, SourceMode::Web],
-        build_pipeline: build_claude_pipeline,
+        build_pipeline: Box::new(build_claude_pipeline),
     }
 }
 
@@ -99,7 +104,14 @@ fn build_claude_pipeline(ctx: &FetchContext) -> FetchPipeline {
         strategies.push(Box::new(ClaudePtyStrategy::new()));
     }
 
-    FetchPipeline::with_strategies(strategies)
+    // OAuth only reports windows; with web extras on, keep going and merge
+    // in credits and identity from the other sources
+    let pipeline = FetchPipeline::with_strategies(strategies);
+    if ctx.settings.web_extras {
+        pipeline.with_mode(PipelineMode::Merge)
+    } else {
+        pipeline
+    }
 }
 
 /// Claude CLI configuration.
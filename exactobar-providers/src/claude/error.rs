@@ -18,6 +18,14 @@ pub enum ClaudeError {
     #[error("OAuth token expired at {0}")]
     TokenExpired(String),
 
+    /// OAuth refresh token was revoked or has expired.
+    #[error("OAuth refresh token revoked ({0}); run `claude` to sign in again")]
+    RefreshTokenRevoked(String),
+
+    /// Failed to write refreshed credentials back.
+    #[error("Failed to write credentials: {0}")]
+    CredentialsWriteError(String),
+
     /// OAuth token missing required scope.
     #[error("OAuth token missing scope: {0}")]
     MissingScope(String),
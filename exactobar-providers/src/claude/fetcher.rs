@@ -21,6 +21,7 @@ use super::api::ClaudeApiClient;
 use super::error::ClaudeError;
 use super::oauth::ClaudeOAuthCredentials;
 use super::pty_probe::ClaudePtyProbe;
+use super::refresh::ensure_fresh;
 use super::web::ClaudeWebClient;
 
 // ============================================================================
@@ -55,6 +56,8 @@ pub enum ClaudeDataSource {
 pub struct ClaudeUsageFetcher {
     /// Which data source to use.
     data_source: ClaudeDataSource,
+    /// Whether refreshed OAuth tokens are written back to storage.
+    persist_refreshed_credentials: bool,
 }
 
 impl ClaudeUsageFetcher {
@@ -67,9 +70,20 @@ impl ClaudeUsageFetcher {
     pub fn with_source(source: ClaudeDataSource) -> Self {
         Self {
             data_source: source,
+            ..Self::default()
         }
     }
 
+    /// Write refreshed OAuth tokens back to storage.
+    ///
+    /// Expired tokens are refreshed either way. Off by default, in which case
+    /// the new pair only lives in memory and the Claude CLI keeps the old,
+    /// now-rotated refresh token.
+    pub fn with_persist_refreshed_credentials(mut self, persist: bool) -> Self {
+        self.persist_refreshed_credentials = persist;
+        self
+    }
+
     /// Create a fetcher that only uses OAuth.
     pub fn oauth_only() -> Self {
         Self::with_source(ClaudeDataSource::OAuth)
@@ -178,16 +192,8 @@ impl ClaudeUsageFetcher {
     async fn fetch_via_oauth(&self) -> Result<UsageSnapshot, ClaudeError> {
         debug!("Attempting OAuth fetch");
 
-        let credentials = ClaudeOAuthCredentials::load()?;
-
-        if credentials.is_expired() {
-            return Err(ClaudeError::TokenExpired(
-                credentials
-                    .expires_at
-                    .map(|t| t.to_rfc3339())
-                    .unwrap_or_else(|| "unknown".to_string()),
-            ));
-        }
+        let credentials = ClaudeOAuthCredentials::load_refreshable()?;
+        let credentials = ensure_fresh(credentials, self.persist_refreshed_credentials).await?;
 
         let client = ClaudeApiClient::new();
         let response = client.fetch_usage(&credentials).await?;
@@ -195,7 +201,9 @@ impl ClaudeUsageFetcher {
 
         debug!(
             "OAuth snapshot: primary={:?}, secondary={:?}, tertiary={:?}",
-            snapshot.primary, snapshot.secondary, snapshot.tertiary
+            snapshot.primary(),
+            snapshot.secondary(),
+            snapshot.tertiary()
         );
 
         Ok(snapshot)
@@ -272,6 +280,12 @@ mod tests {
     fn test_fetcher_creation() {
         let default = ClaudeUsageFetcher::new();
         assert_eq!(default.data_source, ClaudeDataSource::Auto);
+        assert!(!default.persist_refreshed_credentials);
+        assert!(
+            default
+                .with_persist_refreshed_credentials(true)
+                .persist_refreshed_credentials
+        );
 
         let oauth = ClaudeUsageFetcher::oauth_only();
         assert_eq!(oauth.data_source, ClaudeDataSource::OAuth);
@@ -35,6 +35,10 @@
 //! }
 //! ```
 //!
+//! Expired access tokens are refreshed with the stored refresh token. The
+//! refreshed pair is written back only when
+//! `FetchSettings::persist_refreshed_credentials` is set.
+//!
 //! ## Usage
 //!
 //! ```ignore
@@ -52,6 +56,7 @@ mod fetcher;
 mod oauth;
 pub(crate) mod parser;
 mod pty_probe;
+mod refresh;
 mod strategies;
 mod web;
 
@@ -60,8 +65,9 @@ pub use api::{ClaudeApiClient, UsageApiResponse};
 pub use descriptor::claude_descriptor;
 pub use error::ClaudeError;
 pub use fetcher::{ClaudeDataSource, ClaudeUsageFetcher};
-pub use oauth::{ClaudeOAuthCredentials, CredentialSource};
+pub use oauth::{ClaudeOAuthCredentials, CredentialSource, plan_name_from_tier};
 pub use pty_probe::{ClaudePtyProbe, ClaudeStatusSnapshot, parse_usage_output};
+pub use refresh::{ClaudeTokenRefresher, ensure_fresh, persist_credentials};
 pub use strategies::{
     ClaudeCliStrategy, ClaudeOAuthStrategy, ClaudePtyStrategy, ClaudeWebStrategy,
 };
@@ -13,7 +13,9 @@
 //!     "accessToken": "...",
 //!     "refreshToken": "...",
 //!     "expiresAt": 1735000000000,
-//!     "scopes": ["user:profile", "..."]
+//!     "scopes": ["user:profile", "..."],
+//!     "subscriptionType": "max",
+//!     "rateLimitTier": "default_claude_max_20x"
 //!   }
 //! }
 //! ```
@@ -69,6 +71,8 @@ pub struct OAuthCredentialsData {
     pub expires_at: Option<i64>,
     /// Granted scopes.
     pub scopes: Option<Vec<String>>,
+    /// Subscription type (e.g., "pro", "max").
+    pub subscription_type: Option<String>,
     /// Rate limit tier.
     pub rate_limit_tier: Option<String>,
 }
@@ -88,7 +92,9 @@ pub struct ClaudeOAuthCredentials {
     pub expires_at: Option<DateTime<Utc>>,
     /// Granted scopes.
     pub scopes: Vec<String>,
-    /// Rate limit tier (e.g., "free", "pro").
+    /// Subscription type (e.g., "pro", "max").
+    pub subscription_type: Option<String>,
+    /// Rate limit tier (e.g., "default_claude_max_20x").
     pub rate_limit_tier: Option<String>,
     /// Source of the credentials.
     pub source: CredentialSource,
@@ -128,6 +134,14 @@ impl ClaudeOAuthCredentials {
         self.has_scope(REQUIRED_SCOPE)
     }
 
+    /// Returns the display plan name (e.g., "Pro", "Max 20x").
+    pub fn plan_name(&self) -> Option<String> {
+        plan_name_from_tier(
+            self.subscription_type.as_deref(),
+            self.rate_limit_tier.as_deref(),
+        )
+    }
+
     /// Check if credentials are valid for use.
     pub fn is_valid(&self) -> bool {
         !self.is_expired() && (self.scopes.is_empty() || self.has_required_scope())
@@ -172,6 +186,28 @@ impl ClaudeOAuthCredentials {
         Err(ClaudeError::CredentialsNotFound)
     }
 
+    /// Load credentials, also accepting expired ones that can be refreshed.
+    ///
+    /// Valid credentials are preferred; otherwise the first expired entry
+    /// with a refresh token is returned.
+    pub fn load_refreshable() -> Result<Self, ClaudeError> {
+        if let Ok(creds) = Self::load() {
+            return Ok(creds);
+        }
+
+        [Self::load_from_file, Self::load_from_keychain]
+            .into_iter()
+            .filter_map(|load| load().ok())
+            .find(Self::can_refresh)
+            .ok_or(ClaudeError::CredentialsNotFound)
+    }
+
+    /// Check if the credentials carry a refresh token and the required scope.
+    pub fn can_refresh(&self) -> bool {
+        self.refresh_token.as_deref().is_some_and(|t| !t.is_empty())
+            && (self.scopes.is_empty() || self.has_required_scope())
+    }
+
     /// Load credentials from macOS Keychain.
     #[instrument]
     pub fn load_from_keychain() -> Result<Self, ClaudeError> {
@@ -215,6 +251,7 @@ impl ClaudeOAuthCredentials {
                 refresh_token: None,
                 expires_at: None,
                 scopes: vec![],
+                subscription_type: None,
                 rate_limit_tier: None,
                 source: CredentialSource::LegacyKeychain,
             })
@@ -272,6 +309,7 @@ impl ClaudeOAuthCredentials {
             refresh_token: data.refresh_token,
             expires_at,
             scopes: data.scopes.unwrap_or_default(),
+            subscription_type: data.subscription_type,
             rate_limit_tier: data.rate_limit_tier,
             source,
         }
@@ -287,6 +325,35 @@ pub fn credentials_file_path() -> Option<PathBuf> {
     dirs::home_dir().map(|h| h.join(".claude").join(".credentials.json"))
 }
 
+/// Derives a display plan name from the subscription type and rate limit tier.
+///
+/// The tier carries the Max multiplier (`default_claude_max_5x`,
+/// `default_claude_max_20x`); the subscription type is the fallback.
+pub fn plan_name_from_tier(
+    subscription_type: Option<&str>,
+    rate_limit_tier: Option<&str>,
+) -> Option<String> {
+    let tier = rate_limit_tier.unwrap_or_default().to_lowercase();
+    if tier.contains("max_20x") {
+        return Some("Max 20x".to_string());
+    }
+    if tier.contains("max_5x") {
+        return Some("Max 5x".to_string());
+    }
+
+    let plan = subscription_type
+        .map(str::to_lowercase)
+        .or_else(|| tier.rsplit('_').next().map(str::to_string))?;
+    match plan.as_str() {
+        "max" => Some("Max".to_string()),
+        "pro" => Some("Pro".to_string()),
+        "team" => Some("Team".to_string()),
+        "enterprise" => Some("Enterprise".to_string()),
+        "free" => Some("Free".to_string()),
+        _ => None,
+    }
+}
+
 /// Check if credentials file exists.
 #[allow(dead_code)]
 pub fn credentials_file_exists() -> bool {
@@ -328,6 +395,29 @@ mod tests {
         assert!(creds.has_scope("user:profile"));
         assert!(creds.has_required_scope());
         assert_eq!(creds.rate_limit_tier, Some("pro".to_string()));
+        assert_eq!(creds.plan_name(), Some("Pro".to_string()));
+    }
+
+    #[test]
+    fn test_plan_name_from_tier() {
+        assert_eq!(
+            plan_name_from_tier(Some("max"), Some("default_claude_max_20x")),
+            Some("Max 20x".to_string())
+        );
+        assert_eq!(
+            plan_name_from_tier(Some("max"), Some("default_claude_max_5x")),
+            Some("Max 5x".to_string())
+        );
+        assert_eq!(
+            plan_name_from_tier(Some("max"), None),
+            Some("Max".to_string())
+        );
+        assert_eq!(
+            plan_name_from_tier(None, Some("default_claude_pro")),
+            Some("Pro".to_string())
+        );
+        assert_eq!(plan_name_from_tier(Some("mystery"), None), None);
+        assert_eq!(plan_name_from_tier(None, None), None);
     }
 
     #[test]
@@ -350,6 +440,7 @@ mod tests {
             refresh_token: None,
             expires_at: Some(future),
             scopes: vec![],
+            subscription_type: None,
             rate_limit_tier: None,
             source: CredentialSource::File,
         };
@@ -364,6 +455,7 @@ mod tests {
             refresh_token: None,
             expires_at: Some(past),
             scopes: vec![],
+            subscription_type: None,
             rate_limit_tier: None,
             source: CredentialSource::File,
         };
@@ -377,6 +469,7 @@ mod tests {
             refresh_token: None,
             expires_at: None,
             scopes: vec![],
+            subscription_type: None,
             rate_limit_tier: None,
             source: CredentialSource::File,
         };
@@ -390,6 +483,7 @@ mod tests {
             refresh_token: None,
             expires_at: None,
             scopes: vec!["user:profile".to_string(), "conversations:read".to_string()],
+            subscription_type: None,
             rate_limit_tier: None,
             source: CredentialSource::File,
         };
@@ -412,6 +506,7 @@ mod tests {
             refresh_token: None,
             expires_at: None,
             scopes: vec!["user:profile".to_string()],
+            subscription_type: None,
             rate_limit_tier: None,
             source: CredentialSource::File,
         };
@@ -426,6 +521,7 @@ mod tests {
             refresh_token: None,
             expires_at: None,
             scopes: vec![],
+            subscription_type: None,
             rate_limit_tier: None,
             source: CredentialSource::File,
         };
@@ -439,6 +535,7 @@ mod tests {
             refresh_token: None,
             expires_at: None,
             scopes: vec!["other:scope".to_string()],
+            subscription_type: None,
             rate_limit_tier: None,
             source: CredentialSource::File,
         };
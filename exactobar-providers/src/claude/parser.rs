@@ -3,6 +3,7 @@
 use chrono::{DateTime, Utc};
 use exactobar_core::{
     FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageSnapshot, UsageWindow,
+    WindowKind,
 };
 use exactobar_fetch::FetchError;
 use serde::Deserialize;
@@ -85,17 +86,23 @@ pub fn parse_claude_api_response(json_str: &str) -> Result<UsageSnapshot, FetchE
 
     // Parse session (primary) window
     if let Some(session) = response.session {
-        snapshot.primary = Some(parse_usage_data(session));
+        snapshot.set_primary(Some(
+            parse_usage_data(session).with_id("session", WindowKind::Session),
+        ));
     }
 
     // Parse weekly (secondary) window
     if let Some(weekly) = response.weekly {
-        snapshot.secondary = Some(parse_usage_data(weekly));
+        snapshot.set_secondary(Some(
+            parse_usage_data(weekly).with_id("weekly", WindowKind::Weekly),
+        ));
     }
 
     // Parse opus (tertiary) window
     if let Some(opus) = response.opus {
-        snapshot.tertiary = Some(parse_usage_data(opus));
+        snapshot.set_tertiary(Some(
+            parse_usage_data(opus).with_id("opus", WindowKind::Weekly),
+        ));
     }
 
     // Parse identity
@@ -113,9 +120,9 @@ pub fn parse_claude_api_response(json_str: &str) -> Result<UsageSnapshot, FetchE
     }
 
     debug!(
-        has_primary = snapshot.primary.is_some(),
-        has_secondary = snapshot.secondary.is_some(),
-        has_tertiary = snapshot.tertiary.is_some(),
+        has_primary = snapshot.primary().is_some(),
+        has_secondary = snapshot.secondary().is_some(),
+        has_tertiary = snapshot.tertiary().is_some(),
         "Claude API response parsed"
     );
 
@@ -147,15 +154,15 @@ pub fn parse_claude_cli_output(output: &str, is_json: bool) -> Result<UsageSnaps
 
         if let Some(rest) = line.strip_prefix("Session:") {
             if let Some(window) = parse_text_usage_line(rest) {
-                snapshot.primary = Some(window);
+                snapshot.set_primary(Some(window.with_id("session", WindowKind::Session)));
             }
         } else if let Some(rest) = line.strip_prefix("Weekly:") {
             if let Some(window) = parse_text_usage_line(rest) {
-                snapshot.secondary = Some(window);
+                snapshot.set_secondary(Some(window.with_id("weekly", WindowKind::Weekly)));
             }
         } else if let Some(rest) = line.strip_prefix("Opus:") {
             if let Some(window) = parse_text_usage_line(rest) {
-                snapshot.tertiary = Some(window);
+                snapshot.set_tertiary(Some(window.with_id("opus", WindowKind::Weekly)));
             }
         }
     }
@@ -264,14 +271,14 @@ mod tests {
 
         let snapshot = parse_claude_api_response(json).unwrap();
 
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.as_ref().unwrap().used_percent, 45.5);
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 45.5);
 
-        assert!(snapshot.secondary.is_some());
-        assert_eq!(snapshot.secondary.as_ref().unwrap().used_percent, 20.0);
+        assert!(snapshot.secondary().is_some());
+        assert_eq!(snapshot.secondary().unwrap().used_percent, 20.0);
 
-        assert!(snapshot.tertiary.is_some());
-        assert_eq!(snapshot.tertiary.as_ref().unwrap().used_percent, 30.0);
+        assert!(snapshot.tertiary().is_some());
+        assert_eq!(snapshot.tertiary().unwrap().used_percent, 30.0);
 
         assert!(snapshot.identity.is_some());
         let identity = snapshot.identity.unwrap();
@@ -288,9 +295,9 @@ mod tests {
         }"#;
 
         let snapshot = parse_claude_api_response(json).unwrap();
-        assert!(snapshot.primary.is_some());
+        assert!(snapshot.primary().is_some());
         // remaining 60% means used 40%
-        assert_eq!(snapshot.primary.unwrap().used_percent, 40.0);
+        assert_eq!(snapshot.primary().unwrap().used_percent, 40.0);
     }
 
     #[test]
@@ -314,13 +321,13 @@ Opus: 30% used
 
         let snapshot = parse_claude_cli_output(output, false).unwrap();
 
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.as_ref().unwrap().used_percent, 45.0);
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 45.0);
 
-        assert!(snapshot.secondary.is_some());
-        assert_eq!(snapshot.secondary.as_ref().unwrap().used_percent, 20.0);
+        assert!(snapshot.secondary().is_some());
+        assert_eq!(snapshot.secondary().unwrap().used_percent, 20.0);
 
-        assert!(snapshot.tertiary.is_some());
-        assert_eq!(snapshot.tertiary.as_ref().unwrap().used_percent, 30.0);
+        assert!(snapshot.tertiary().is_some());
+        assert_eq!(snapshot.tertiary().unwrap().used_percent, 30.0);
     }
 }
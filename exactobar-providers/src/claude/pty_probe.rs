@@ -423,39 +423,40 @@ fn parse_line_by_line(text: &str, snapshot: &mut ClaudeStatusSnapshot) {
 impl ClaudeStatusSnapshot {
     /// Convert to a UsageSnapshot.
     pub fn to_snapshot(&self) -> exactobar_core::UsageSnapshot {
-        use exactobar_core::{FetchSource, LoginMethod, ProviderIdentity, ProviderKind};
+        use exactobar_core::{
+            FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageWindow, WindowKind,
+        };
 
         let mut snapshot = exactobar_core::UsageSnapshot::new();
         snapshot.fetch_source = FetchSource::CLI;
 
         // Primary = session
         if let Some(used) = self.session_used_percent() {
-            snapshot.primary = Some(exactobar_core::UsageWindow {
-                used_percent: used,
+            snapshot.set_primary(Some(UsageWindow {
                 window_minutes: Some(300), // 5 hours
                 resets_at: None,
                 reset_description: self.session_reset.clone(),
-            });
+                ..UsageWindow::named("session", WindowKind::Session, used)
+            }));
         }
 
         // Secondary = weekly
         if let Some(used) = self.weekly_used_percent() {
-            snapshot.secondary = Some(exactobar_core::UsageWindow {
-                used_percent: used,
+            snapshot.set_secondary(Some(UsageWindow {
                 window_minutes: Some(10080), // 7 days
                 resets_at: None,
                 reset_description: self.weekly_reset.clone(),
-            });
+                ..UsageWindow::named("weekly", WindowKind::Weekly, used)
+            }));
         }
 
         // Tertiary = opus/sonnet
         if let Some(used) = self.opus_used_percent() {
-            snapshot.tertiary = Some(exactobar_core::UsageWindow {
-                used_percent: used,
+            snapshot.set_tertiary(Some(UsageWindow {
                 window_minutes: Some(10080), // 7 days
                 resets_at: None,
-                reset_description: None,
-            });
+                ..UsageWindow::named("opus", WindowKind::Weekly, used)
+            }));
         }
 
         // Identity
@@ -598,16 +599,16 @@ mod tests {
         let snapshot = status.to_snapshot();
 
         // 72% left = 28% used
-        assert!(snapshot.primary.is_some());
-        assert!((snapshot.primary.as_ref().unwrap().used_percent - 28.0).abs() < 0.01);
+        assert!(snapshot.primary().is_some());
+        assert!((snapshot.primary().unwrap().used_percent - 28.0).abs() < 0.01);
 
         // 45% left = 55% used
-        assert!(snapshot.secondary.is_some());
-        assert!((snapshot.secondary.as_ref().unwrap().used_percent - 55.0).abs() < 0.01);
+        assert!(snapshot.secondary().is_some());
+        assert!((snapshot.secondary().unwrap().used_percent - 55.0).abs() < 0.01);
 
         // 80% left = 20% used
-        assert!(snapshot.tertiary.is_some());
-        assert!((snapshot.tertiary.as_ref().unwrap().used_percent - 20.0).abs() < 0.01);
+        assert!(snapshot.tertiary().is_some());
+        assert!((snapshot.tertiary().unwrap().used_percent - 20.0).abs() < 0.01);
 
         assert!(snapshot.identity.is_some());
     }
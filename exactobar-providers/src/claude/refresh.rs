@@ -0,0 +1,807 @@
+//! Claude OAuth access-token refresh.
+//!
+//! Access tokens issued to the Claude CLI are short-lived. When one has
+//! expired we exchange the stored refresh token for a new pair, the same
+//! way the CLI does.
+//!
+//! # Token Endpoint
+//!
+//! ```text
+//! POST https://console.anthropic.com/v1/oauth/token
+//! Content-Type: application/json
+//!
+//! { "grant_type": "refresh_token", "refresh_token": "...", "client_id": "..." }
+//! ```
+//!
+//! # Write-back
+//!
+//! The server rotates the refresh token on every exchange, so the previous
+//! one stops working. A refreshed pair must therefore be written back to the
+//! file or keychain entry it came from, or the CLI (and our next run) is left
+//! holding a dead token. Write-back is opt-in via
+//! `FetchSettings::persist_refreshed_credentials`; without it the refreshed
+//! pair only lives in memory for the rest of the process, and if the server
+//! rotated the refresh token we warn that the CLI will have to sign in again.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use tracing::{debug, info, instrument, warn};
+
+use super::error::ClaudeError;
+use super::oauth::{
+    ClaudeOAuthCredentials, CredentialSource, KEYCHAIN_ACCOUNT, KEYCHAIN_SERVICE,
+    credentials_file_path,
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// OAuth token endpoint.
+pub const TOKEN_URL: &str = "https://console.anthropic.com/v1/oauth/token";
+
+/// Public OAuth client ID used by the Claude CLI.
+pub const CLIENT_ID: &str = "9d1c250a-e61b-44d9-88ed-5944d1962f5e";
+
+/// OAuth error code for a revoked or expired refresh token.
+const INVALID_GRANT: &str = "invalid_grant";
+
+// ============================================================================
+// Token Response Structures
+// ============================================================================
+
+/// Successful response from the token endpoint.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenRefreshResponse {
+    /// New access token.
+    pub access_token: String,
+    /// New refresh token (rotated on every exchange).
+    pub refresh_token: Option<String>,
+    /// Access token lifetime in seconds.
+    pub expires_in: Option<i64>,
+    /// Space-separated granted scopes.
+    pub scope: Option<String>,
+}
+
+/// Error response from the token endpoint.
+#[derive(Debug, Clone, Deserialize)]
+struct TokenErrorResponse {
+    error: String,
+    error_description: Option<String>,
+}
+
+impl TokenRefreshResponse {
+    /// Applies the response to the credentials it was requested for.
+    ///
+    /// Fields the server didn't return (refresh token, scopes) are kept.
+    pub fn apply(self, credentials: &ClaudeOAuthCredentials) -> ClaudeOAuthCredentials {
+        let scopes = self.scope.map_or_else(
+            || credentials.scopes.clone(),
+            |s| s.split_whitespace().map(str::to_string).collect(),
+        );
+
+        ClaudeOAuthCredentials {
+            access_token: self.access_token,
+            refresh_token: self
+                .refresh_token
+                .or_else(|| credentials.refresh_token.clone()),
+            expires_at: self
+                .expires_in
+                .map(|secs| Utc::now() + Duration::seconds(secs)),
+            scopes,
+            ..credentials.clone()
+        }
+    }
+}
+
+/// Maps a failed token response to an error.
+fn classify_error(status: reqwest::StatusCode, body: &str) -> ClaudeError {
+    match serde_json::from_str::<TokenErrorResponse>(body) {
+        Ok(err) if err.error == INVALID_GRANT => {
+            ClaudeError::RefreshTokenRevoked(err.error_description.unwrap_or(err.error))
+        }
+        Ok(err) => ClaudeError::AuthenticationFailed(format!(
+            "token refresh failed: {}",
+            err.error_description.unwrap_or(err.error)
+        )),
+        Err(_) => ClaudeError::ApiError(format!("HTTP {}: {}", status, body)),
+    }
+}
+
+// ============================================================================
+// Token Refresher
+// ============================================================================
+
+/// Exchanges refresh tokens for new access tokens.
+#[derive(Debug, Clone)]
+pub struct ClaudeTokenRefresher {
+    token_url: String,
+    credentials_path: Option<PathBuf>,
+}
+
+impl Default for ClaudeTokenRefresher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClaudeTokenRefresher {
+    /// Create a refresher for the Anthropic token endpoint.
+    pub fn new() -> Self {
+        Self {
+            token_url: TOKEN_URL.to_string(),
+            credentials_path: None,
+        }
+    }
+
+    /// Create a refresher with a custom token URL.
+    pub fn with_token_url(token_url: impl Into<String>) -> Self {
+        Self {
+            token_url: token_url.into(),
+            credentials_path: None,
+        }
+    }
+
+    /// Write file-sourced credentials to this path instead of `~/.claude`.
+    pub fn with_credentials_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.credentials_path = Some(path.into());
+        self
+    }
+
+    /// Exchange the refresh token for new credentials.
+    #[instrument(skip(self, credentials))]
+    pub async fn refresh(
+        &self,
+        credentials: &ClaudeOAuthCredentials,
+    ) -> Result<ClaudeOAuthCredentials, ClaudeError> {
+        let refresh_token = credentials
+            .refresh_token
+            .as_deref()
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| {
+                ClaudeError::TokenExpired(
+                    credentials
+                        .expires_at
+                        .map(|t| t.to_rfc3339())
+                        .unwrap_or_else(|| "unknown".to_string()),
+                )
+            })?;
+
+        debug!(url = %self.token_url, "Refreshing Claude OAuth token");
+
+        let client = reqwest::Client::builder()
+            .build()
+            .map_err(|e| ClaudeError::HttpError(e.to_string()))?;
+
+        let response = client
+            .post(&self.token_url)
+            .header("Content-Type", "application/json")
+            .header("Accept", "application/json")
+            .json(&serde_json::json!({
+                "grant_type": "refresh_token",
+                "refresh_token": refresh_token,
+                "client_id": CLIENT_ID,
+            }))
+            .send()
+            .await
+            .map_err(|e| ClaudeError::HttpError(e.to_string()))?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| ClaudeError::HttpError(e.to_string()))?;
+
+        if !status.is_success() {
+            let error = classify_error(status, &body);
+            warn!(status = %status, error = %error, "Token refresh failed");
+            return Err(error);
+        }
+
+        let token: TokenRefreshResponse = serde_json::from_str(&body)
+            .map_err(|e| ClaudeError::ParseError(format!("Invalid token response: {}", e)))?;
+
+        info!("Refreshed Claude OAuth access token");
+        Ok(token.apply(credentials))
+    }
+
+    /// Write refreshed credentials back to where they were loaded from.
+    ///
+    /// Other fields in the stored JSON (subscription type, tier, anything the
+    /// CLI adds later) are preserved. Legacy keychain entries are left alone.
+    pub async fn persist(&self, credentials: &ClaudeOAuthCredentials) -> Result<(), ClaudeError> {
+        match credentials.source {
+            CredentialSource::File => {
+                let path = self
+                    .credentials_path
+                    .clone()
+                    .or_else(credentials_file_path)
+                    .ok_or_else(|| {
+                        ClaudeError::CredentialsWriteError(
+                            "Could not determine home directory".to_string(),
+                        )
+                    })?;
+                write_back_to_file(&path, credentials).await
+            }
+            CredentialSource::Keychain => write_back_to_keychain(credentials),
+            CredentialSource::LegacyKeychain => {
+                debug!("Not writing back to legacy keychain entry");
+                Ok(())
+            }
+        }
+    }
+}
+
+// ============================================================================
+// In-Memory Cache
+// ============================================================================
+
+/// Refreshed credentials, keyed by the refresh token found in storage.
+///
+/// If storage still holds that token on the next fetch, the cached
+/// credentials are newer than what's on disk: either write-back is off,
+/// another source wrote them back, or our own write-back failed and must be
+/// retried.
+struct RefreshedEntry {
+    stored_refresh_token: String,
+    credentials: ClaudeOAuthCredentials,
+    persisted: bool,
+}
+
+/// Held across check, exchange and write-back so concurrent fetches share a
+/// single exchange instead of racing on the same refresh token.
+static REFRESHED: tokio::sync::Mutex<Option<RefreshedEntry>> = tokio::sync::Mutex::const_new(None);
+
+/// Returns usable credentials, refreshing them if they have expired.
+///
+/// `stored` are the credentials as loaded from the file or keychain. Expired
+/// tokens are always exchanged and the new pair is kept in memory; `persist`
+/// only controls whether it is also written back to storage. A failed
+/// write-back is an error, and is retried on the next call. Without
+/// write-back, a rotated refresh token is logged as a warning because the
+/// copy in storage is now dead.
+pub async fn ensure_fresh(
+    stored: ClaudeOAuthCredentials,
+    persist: bool,
+) -> Result<ClaudeOAuthCredentials, ClaudeError> {
+    ensure_fresh_with(&ClaudeTokenRefresher::new(), stored, persist).await
+}
+
+/// Like [`ensure_fresh`], with a specific refresher.
+pub async fn ensure_fresh_with(
+    refresher: &ClaudeTokenRefresher,
+    stored: ClaudeOAuthCredentials,
+    persist: bool,
+) -> Result<ClaudeOAuthCredentials, ClaudeError> {
+    if !stored.is_expired() {
+        return Ok(stored);
+    }
+
+    // Whoever held the lock before us may already have refreshed this token,
+    // so the cache is only consulted once the lock is ours.
+    let mut cache = REFRESHED.lock().await;
+
+    let stored_token = stored.refresh_token.clone().unwrap_or_default();
+    let cached = cache
+        .as_ref()
+        .filter(|entry| !stored_token.is_empty() && entry.stored_refresh_token == stored_token)
+        .map(|entry| (entry.credentials.clone(), entry.persisted));
+
+    let mut current = stored;
+    if let Some((credentials, persisted)) = cached {
+        if persist && !persisted {
+            debug!("Retrying write-back of previously refreshed credentials");
+            write_back(refresher, &mut cache, &stored_token, &credentials).await?;
+        }
+        current = credentials;
+    }
+    if !current.is_expired() {
+        debug!("Using previously refreshed credentials");
+        return Ok(current);
+    }
+
+    let renewed = match refresher.refresh(&current).await {
+        Ok(renewed) => renewed,
+        Err(e) => {
+            if matches!(e, ClaudeError::RefreshTokenRevoked(_)) {
+                cache.take();
+            }
+            return Err(e);
+        }
+    };
+
+    if persist {
+        write_back(refresher, &mut cache, &stored_token, &renewed).await?;
+    } else {
+        if refresh_token_rotated(&stored_token, &renewed) {
+            warn!(
+                "Claude refresh token was rotated but write-back is off: the token stored \
+                 for the Claude CLI no longer works and it will ask you to sign in again. \
+                 Enable Claude OAuth write-back (claude_oauth_write_back_enabled) to keep \
+                 the CLI signed in"
+            );
+        } else {
+            debug!("Write-back is off, keeping refreshed credentials in memory");
+        }
+        *cache = Some(RefreshedEntry {
+            stored_refresh_token: stored_token,
+            credentials: renewed.clone(),
+            persisted: false,
+        });
+    }
+    Ok(renewed)
+}
+
+/// Returns true if the exchange replaced the refresh token found in storage,
+/// which invalidates the stored one.
+fn refresh_token_rotated(stored_token: &str, renewed: &ClaudeOAuthCredentials) -> bool {
+    renewed
+        .refresh_token
+        .as_deref()
+        .is_some_and(|token| !token.is_empty() && token != stored_token)
+}
+
+/// Persists refreshed credentials and caches them.
+///
+/// On failure the cache stays keyed by the token still in storage, so the
+/// next call retries the write instead of exchanging the revoked token.
+async fn write_back(
+    refresher: &ClaudeTokenRefresher,
+    cache: &mut Option<RefreshedEntry>,
+    stored_token: &str,
+    credentials: &ClaudeOAuthCredentials,
+) -> Result<(), ClaudeError> {
+    let result = refresher.persist(credentials).await;
+    let (key, persisted) = match &result {
+        Ok(()) => (credentials.refresh_token.clone().unwrap_or_default(), true),
+        Err(e) => {
+            warn!(error = %e, "Failed to write back refreshed credentials");
+            (stored_token.to_string(), false)
+        }
+    };
+
+    *cache = Some(RefreshedEntry {
+        stored_refresh_token: key,
+        credentials: credentials.clone(),
+        persisted,
+    });
+    result
+}
+
+// ============================================================================
+// Write-back
+// ============================================================================
+
+/// Write refreshed credentials back to where they were loaded from.
+///
+/// See [`ClaudeTokenRefresher::persist`].
+pub async fn persist_credentials(credentials: &ClaudeOAuthCredentials) -> Result<(), ClaudeError> {
+    ClaudeTokenRefresher::new().persist(credentials).await
+}
+
+/// Update a credentials file atomically (temp file + rename).
+pub async fn write_back_to_file(
+    path: &Path,
+    credentials: &ClaudeOAuthCredentials,
+) -> Result<(), ClaudeError> {
+    let mut root = match tokio::fs::read_to_string(path).await {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!(error = %e, "Credentials file is not valid JSON, rewriting it");
+            Value::Object(Map::new())
+        }),
+        Err(_) => Value::Object(Map::new()),
+    };
+
+    update_credentials_json(&mut root, credentials);
+
+    exactobar_store::persistence::save_json(path, &root)
+        .await
+        .map_err(|e| ClaudeError::CredentialsWriteError(e.to_string()))?;
+
+    debug!(path = %path.display(), "Wrote refreshed credentials to file");
+    Ok(())
+}
+
+/// Update the keychain entry the credentials were loaded from.
+fn write_back_to_keychain(credentials: &ClaudeOAuthCredentials) -> Result<(), ClaudeError> {
+    use exactobar_fetch::host::keychain::{get_password_cached, set_password_cached};
+
+    // Same lookup order as loading: username account first, then empty
+    let username = whoami::username();
+    let (account, existing) = match get_password_cached(KEYCHAIN_SERVICE, &username) {
+        Some(secret) => (username.as_str(), Some(secret)),
+        None => (
+            KEYCHAIN_ACCOUNT,
+            get_password_cached(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT),
+        ),
+    };
+
+    let mut root = existing
+        .and_then(|secret| serde_json::from_str(&secret).ok())
+        .unwrap_or_else(|| Value::Object(Map::new()));
+
+    update_credentials_json(&mut root, credentials);
+
+    set_password_cached(KEYCHAIN_SERVICE, account, &root.to_string())
+        .map_err(|e| ClaudeError::CredentialsWriteError(e.to_string()))?;
+
+    debug!("Wrote refreshed credentials to keychain");
+    Ok(())
+}
+
+/// Merge credentials into stored JSON, in either the full file format
+/// (`claudeAiOauth` wrapper) or the direct format.
+fn update_credentials_json(root: &mut Value, credentials: &ClaudeOAuthCredentials) {
+    if !root.is_object() {
+        *root = Value::Object(Map::new());
+    }
+
+    let direct = root.get("accessToken").is_some() && root.get("claudeAiOauth").is_none();
+    let target = if direct {
+        root
+    } else {
+        let entry = root
+            .as_object_mut()
+            .expect("root is an object")
+            .entry("claudeAiOauth")
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !entry.is_object() {
+            *entry = Value::Object(Map::new());
+        }
+        entry
+    };
+
+    let Some(fields) = target.as_object_mut() else {
+        return;
+    };
+    fields.insert(
+        "accessToken".to_string(),
+        Value::String(credentials.access_token.clone()),
+    );
+    if let Some(ref token) = credentials.refresh_token {
+        fields.insert("refreshToken".to_string(), Value::String(token.clone()));
+    }
+    if let Some(expires_at) = credentials.expires_at {
+        fields.insert(
+            "expiresAt".to_string(),
+            Value::from(expires_at.timestamp_millis()),
+        );
+    }
+    if !credentials.scopes.is_empty() {
+        fields.insert(
+            "scopes".to_string(),
+            Value::from(credentials.scopes.clone()),
+        );
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::serve_stub;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn expired_credentials() -> ClaudeOAuthCredentials {
+        ClaudeOAuthCredentials {
+            access_token: "old-access".to_string(),
+            refresh_token: Some("old-refresh".to_string()),
+            expires_at: Some(Utc::now() - Duration::hours(1)),
+            scopes: vec!["user:profile".to_string()],
+            subscription_type: Some("max".to_string()),
+            rate_limit_tier: Some("default_claude_max_5x".to_string()),
+            source: CredentialSource::File,
+        }
+    }
+
+    #[test]
+    fn test_apply_refresh_response() {
+        let json = r#"{
+            "token_type": "Bearer",
+            "access_token": "new-access",
+            "refresh_token": "new-refresh",
+            "expires_in": 28800,
+            "scope": "user:inference user:profile"
+        }"#;
+        let response: TokenRefreshResponse = serde_json::from_str(json).unwrap();
+        let creds = response.apply(&expired_credentials());
+
+        assert_eq!(creds.access_token, "new-access");
+        assert_eq!(creds.refresh_token.as_deref(), Some("new-refresh"));
+        assert!(!creds.is_expired());
+        assert!(creds.has_scope("user:inference"));
+        assert_eq!(creds.plan_name(), Some("Max 5x".to_string()));
+        assert_eq!(creds.source, CredentialSource::File);
+    }
+
+    #[test]
+    fn test_apply_keeps_missing_fields() {
+        let response = TokenRefreshResponse {
+            access_token: "new-access".to_string(),
+            refresh_token: None,
+            expires_in: Some(3600),
+            scope: None,
+        };
+        let creds = response.apply(&expired_credentials());
+
+        assert_eq!(creds.refresh_token.as_deref(), Some("old-refresh"));
+        assert_eq!(creds.scopes, vec!["user:profile".to_string()]);
+    }
+
+    #[test]
+    fn test_refresh_token_rotated() {
+        let mut creds = expired_credentials();
+        assert!(!refresh_token_rotated("old-refresh", &creds));
+
+        creds.refresh_token = Some("new-refresh".to_string());
+        assert!(refresh_token_rotated("old-refresh", &creds));
+
+        creds.refresh_token = None;
+        assert!(!refresh_token_rotated("old-refresh", &creds));
+    }
+
+    #[test]
+    fn test_classify_revoked_refresh_token() {
+        let body = r#"{"error": "invalid_grant", "error_description": "Refresh token not found or invalid"}"#;
+        let err = classify_error(reqwest::StatusCode::BAD_REQUEST, body);
+        assert!(matches!(err, ClaudeError::RefreshTokenRevoked(_)));
+        assert!(err.to_string().contains("sign in again"));
+
+        let err = classify_error(
+            reqwest::StatusCode::BAD_REQUEST,
+            r#"{"error": "invalid_client"}"#,
+        );
+        assert!(matches!(err, ClaudeError::AuthenticationFailed(_)));
+
+        let err = classify_error(reqwest::StatusCode::BAD_GATEWAY, "<html>");
+        assert!(matches!(err, ClaudeError::ApiError(_)));
+    }
+
+    #[test]
+    fn test_update_credentials_json_preserves_fields() {
+        let mut root: Value = serde_json::from_str(
+            r#"{
+                "claudeAiOauth": {
+                    "accessToken": "old-access",
+                    "refreshToken": "old-refresh",
+                    "expiresAt": 1,
+                    "subscriptionType": "max"
+                },
+                "mcpOAuth": {}
+            }"#,
+        )
+        .unwrap();
+
+        let creds = TokenRefreshResponse {
+            access_token: "new-access".to_string(),
+            refresh_token: Some("new-refresh".to_string()),
+            expires_in: Some(3600),
+            scope: None,
+        }
+        .apply(&expired_credentials());
+        update_credentials_json(&mut root, &creds);
+
+        let oauth = &root["claudeAiOauth"];
+        assert_eq!(oauth["accessToken"], "new-access");
+        assert_eq!(oauth["refreshToken"], "new-refresh");
+        assert_eq!(oauth["subscriptionType"], "max");
+        assert!(oauth["expiresAt"].as_i64().unwrap() > 1);
+        assert!(root.get("mcpOAuth").is_some());
+    }
+
+    #[test]
+    fn test_update_credentials_json_direct_format() {
+        let mut root: Value =
+            serde_json::from_str(r#"{"accessToken": "old-access", "expiresAt": 1}"#).unwrap();
+        update_credentials_json(&mut root, &expired_credentials());
+
+        assert!(root.get("claudeAiOauth").is_none());
+        assert_eq!(root["refreshToken"], "old-refresh");
+    }
+
+    #[tokio::test]
+    async fn test_write_back_to_file() {
+        let dir =
+            std::env::temp_dir().join(format!("exactobar-claude-refresh-{}", std::process::id()));
+        let path = dir.join(".credentials.json");
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        tokio::fs::write(
+            &path,
+            r#"{"claudeAiOauth": {"accessToken": "old-access", "rateLimitTier": "pro"}}"#,
+        )
+        .await
+        .unwrap();
+
+        write_back_to_file(&path, &expired_credentials())
+            .await
+            .unwrap();
+
+        let content = tokio::fs::read_to_string(&path).await.unwrap();
+        let root: Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(root["claudeAiOauth"]["refreshToken"], "old-refresh");
+        assert_eq!(root["claudeAiOauth"]["rateLimitTier"], "pro");
+        assert!(!dir.join(".credentials.json.tmp").exists());
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_ensure_fresh_passes_through_valid_credentials() {
+        let mut creds = expired_credentials();
+        creds.expires_at = Some(Utc::now() + Duration::hours(1));
+
+        let refresher = ClaudeTokenRefresher::with_token_url("http://127.0.0.1:9/unreachable");
+        let fresh = ensure_fresh_with(&refresher, creds, false).await.unwrap();
+        assert_eq!(fresh.access_token, "old-access");
+    }
+
+    #[tokio::test]
+    async fn test_ensure_fresh_without_refresh_token() {
+        let mut creds = expired_credentials();
+        creds.refresh_token = None;
+
+        let refresher = ClaudeTokenRefresher::with_token_url("http://127.0.0.1:9/unreachable");
+        let err = ensure_fresh_with(&refresher, creds, true)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClaudeError::TokenExpired(_)));
+    }
+
+    // ------------------------------------------------------------------------
+    // Token exchange against a stub endpoint
+    // ------------------------------------------------------------------------
+
+    const TOKEN_JSON: &str = r#"{
+        "access_token": "new-access",
+        "refresh_token": "new-refresh",
+        "expires_in": 28800
+    }"#;
+
+    /// The refreshed-credentials cache is process-wide; these tests take turns.
+    static CACHE_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+    /// Answers every request with `status` and `body`, counting requests.
+    async fn serve_token_stub(status: u16, body: &'static str) -> (String, Arc<AtomicUsize>) {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&hits);
+        let base_url = serve_stub(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            (status, body)
+        })
+        .await;
+
+        (format!("{}/v1/oauth/token", base_url), hits)
+    }
+
+    fn temp_credentials_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("exactobar-claude-{}-{}", name, std::process::id()))
+            .join(".credentials.json")
+    }
+
+    #[tokio::test]
+    async fn test_ensure_fresh_without_persist_refreshes_in_memory() {
+        let _guard = CACHE_LOCK.lock().await;
+        let (url, hits) = serve_token_stub(200, TOKEN_JSON).await;
+        let path = temp_credentials_path("in-memory");
+        let refresher = ClaudeTokenRefresher::with_token_url(url).with_credentials_path(&path);
+
+        let creds = ensure_fresh_with(&refresher, expired_credentials(), false)
+            .await
+            .unwrap();
+        assert_eq!(creds.access_token, "new-access");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        assert!(!path.exists());
+
+        // Storage still holds the old pair; the cached one is reused.
+        let creds = ensure_fresh_with(&refresher, expired_credentials(), false)
+            .await
+            .unwrap();
+        assert_eq!(creds.access_token, "new-access");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        REFRESHED.lock().await.take();
+    }
+
+    #[tokio::test]
+    async fn test_ensure_fresh_is_single_flight() {
+        let _guard = CACHE_LOCK.lock().await;
+        let (url, hits) = serve_token_stub(200, TOKEN_JSON).await;
+        let refresher = ClaudeTokenRefresher::with_token_url(url);
+
+        let (a, b) = tokio::join!(
+            ensure_fresh_with(&refresher, expired_credentials(), false),
+            ensure_fresh_with(&refresher, expired_credentials(), false),
+        );
+        assert_eq!(a.unwrap().access_token, "new-access");
+        assert_eq!(b.unwrap().access_token, "new-access");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        REFRESHED.lock().await.take();
+    }
+
+    #[tokio::test]
+    async fn test_ensure_fresh_exchanges_and_persists() {
+        let _guard = CACHE_LOCK.lock().await;
+        let (url, hits) = serve_token_stub(200, TOKEN_JSON).await;
+        let path = temp_credentials_path("persist");
+        let refresher = ClaudeTokenRefresher::with_token_url(url).with_credentials_path(&path);
+
+        let creds = ensure_fresh_with(&refresher, expired_credentials(), true)
+            .await
+            .unwrap();
+        assert_eq!(creds.access_token, "new-access");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        let content = tokio::fs::read_to_string(&path).await.unwrap();
+        let root: Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(root["claudeAiOauth"]["refreshToken"], "new-refresh");
+
+        tokio::fs::remove_dir_all(path.parent().unwrap())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_ensure_fresh_retries_failed_write_back() {
+        let _guard = CACHE_LOCK.lock().await;
+        let (url, hits) = serve_token_stub(200, TOKEN_JSON).await;
+
+        // A path under a regular file can't be written.
+        let blocker =
+            std::env::temp_dir().join(format!("exactobar-claude-blocker-{}", std::process::id()));
+        tokio::fs::write(&blocker, "").await.unwrap();
+        let failing =
+            ClaudeTokenRefresher::with_token_url(&url).with_credentials_path(blocker.join("creds"));
+
+        let err = ensure_fresh_with(&failing, expired_credentials(), true)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClaudeError::CredentialsWriteError(_)));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // The second call reuses the cached pair instead of sending the
+        // rotated-out refresh token again.
+        let path = temp_credentials_path("retry");
+        let working = ClaudeTokenRefresher::with_token_url(&url).with_credentials_path(&path);
+        let creds = ensure_fresh_with(&working, expired_credentials(), true)
+            .await
+            .unwrap();
+        assert_eq!(creds.access_token, "new-access");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        assert!(path.exists());
+
+        tokio::fs::remove_file(&blocker).await.unwrap();
+        tokio::fs::remove_dir_all(path.parent().unwrap())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_ensure_fresh_invalid_grant_clears_cache() {
+        let _guard = CACHE_LOCK.lock().await;
+        let (url, hits) = serve_token_stub(
+            400,
+            r#"{"error": "invalid_grant", "error_description": "Refresh token revoked"}"#,
+        )
+        .await;
+        *REFRESHED.lock().await = Some(RefreshedEntry {
+            stored_refresh_token: "old-refresh".to_string(),
+            credentials: expired_credentials(),
+            persisted: true,
+        });
+
+        let refresher = ClaudeTokenRefresher::with_token_url(url);
+        let err = ensure_fresh_with(&refresher, expired_credentials(), true)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClaudeError::RefreshTokenRevoked(_)));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        assert!(REFRESHED.lock().await.is_none());
+    }
+}
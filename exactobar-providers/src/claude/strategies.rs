@@ -9,7 +9,8 @@
 
 use async_trait::async_trait;
 use exactobar_fetch::{
-    FetchContext, FetchError, FetchKind, FetchResult, FetchStrategy, host::browser::Browser,
+    FetchContext, FetchError, FetchKind, FetchResult, FetchStrategy, SnapshotField,
+    host::browser::Browser,
 };
 use tracing::{debug, info, instrument};
 
@@ -18,6 +19,7 @@ use super::fetcher::ClaudeUsageFetcher;
 use super::oauth::ClaudeOAuthCredentials;
 use super::parser::parse_claude_cli_output;
 use super::pty_probe::ClaudePtyProbe;
+use super::refresh::ensure_fresh;
 use super::web::ClaudeWebClient;
 
 // ============================================================================
@@ -61,18 +63,16 @@ impl FetchStrategy for ClaudeOAuthStrategy {
         true
     }
 
-    #[instrument(skip(self, _ctx))]
-    async fn fetch(&self, _ctx: &FetchContext) -> Result<FetchResult, FetchError> {
+    #[instrument(skip(self, ctx))]
+    async fn fetch(&self, ctx: &FetchContext) -> Result<FetchResult, FetchError> {
         debug!("Fetching Claude usage via OAuth");
 
-        let credentials = ClaudeOAuthCredentials::load()
+        let credentials = ClaudeOAuthCredentials::load_refreshable()
             .map_err(|e| FetchError::AuthenticationFailed(e.to_string()))?;
 
-        if credentials.is_expired() {
-            return Err(FetchError::AuthenticationFailed(
-                "OAuth token expired".to_string(),
-            ));
-        }
+        let credentials = ensure_fresh(credentials, ctx.settings.persist_refreshed_credentials)
+            .await
+            .map_err(|e| FetchError::AuthenticationFailed(e.to_string()))?;
 
         let client = ClaudeApiClient::new();
         let response = client
@@ -82,15 +82,20 @@ impl FetchStrategy for ClaudeOAuthStrategy {
 
         // Debug logging to trace data flow
         info!(
-            "OAuth API Response: five_hour={:?}, seven_day={:?}, seven_day_sonnet={:?}",
-            response.five_hour, response.seven_day, response.seven_day_sonnet
+            "OAuth API Response: five_hour={:?}, seven_day={:?}, seven_day_opus={:?}, seven_day_sonnet={:?}",
+            response.five_hour,
+            response.seven_day,
+            response.seven_day_opus,
+            response.seven_day_sonnet
         );
 
         let snapshot = response.to_snapshot();
 
         info!(
             "OAuth Snapshot: primary={:?}, secondary={:?}, tertiary={:?}",
-            snapshot.primary, snapshot.secondary, snapshot.tertiary
+            snapshot.primary(),
+            snapshot.secondary(),
+            snapshot.tertiary()
         );
 
         Ok(FetchResult::new(snapshot, self.id(), self.kind()))
@@ -104,6 +109,16 @@ impl FetchStrategy for ClaudeOAuthStrategy {
         // Don't fallback on auth errors
         !matches!(error, FetchError::AuthenticationFailed(_))
     }
+
+    fn authoritative_fields(&self) -> &[SnapshotField] {
+        // The OAuth usage endpoint carries no account info (only the plan tier)
+        &[
+            SnapshotField::Primary,
+            SnapshotField::Secondary,
+            SnapshotField::Tertiary,
+            SnapshotField::Credits,
+        ]
+    }
 }
 
 // ============================================================================
@@ -161,6 +176,12 @@ impl FetchStrategy for ClaudePtyStrategy {
     fn priority(&self) -> u32 {
         40 // Fallback priority
     }
+
+    fn is_last_resort(&self) -> bool {
+        // Spawning an interactive session is slow, and everything it
+        // reports comes from OAuth or the CLI faster
+        true
+    }
 }
 
 // ============================================================================
@@ -390,4 +411,19 @@ mod tests {
         assert!(cli > web);
         assert!(web > pty);
     }
+
+    #[test]
+    fn test_oauth_not_authoritative_for_identity() {
+        let oauth = ClaudeOAuthStrategy::new();
+        assert!(
+            !oauth
+                .authoritative_fields()
+                .contains(&SnapshotField::Identity)
+        );
+        assert!(
+            ClaudeWebStrategy::new()
+                .authoritative_fields()
+                .contains(&SnapshotField::Identity)
+        );
+    }
 }
@@ -11,6 +11,7 @@
 use serde::Deserialize;
 use tracing::{debug, instrument, warn};
 
+use super::api::ExtraUsage;
 use super::error::ClaudeError;
 
 // ============================================================================
@@ -49,6 +50,9 @@ pub struct WebUsageResponse {
     pub organization: Option<WebOrganization>,
     /// User info.
     pub user: Option<WebUser>,
+    /// Extra usage (pay-as-you-go credits) for the organization.
+    #[serde(default, alias = "extra_usage")]
+    pub extra_usage: Option<ExtraUsage>,
 }
 
 /// Usage data from web API.
@@ -223,7 +227,9 @@ impl WebUsageResponse {
     /// Convert to a UsageSnapshot.
     pub fn to_snapshot(&self) -> exactobar_core::UsageSnapshot {
         use chrono::{DateTime, Utc};
-        use exactobar_core::{FetchSource, LoginMethod, ProviderIdentity, ProviderKind};
+        use exactobar_core::{
+            FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageWindow, WindowKind,
+        };
 
         let mut snapshot = exactobar_core::UsageSnapshot::new();
         snapshot.fetch_source = FetchSource::Web;
@@ -231,8 +237,7 @@ impl WebUsageResponse {
         if let Some(ref usage) = self.usage {
             // Primary = session
             if let Some(ref session) = usage.session {
-                snapshot.primary = Some(exactobar_core::UsageWindow {
-                    used_percent: session.get_used_percent(),
+                snapshot.set_primary(Some(UsageWindow {
                     window_minutes: Some(300),
                     resets_at: session.resets_at.as_ref().and_then(|s| {
                         DateTime::parse_from_rfc3339(s)
@@ -240,13 +245,13 @@ impl WebUsageResponse {
                             .map(|dt| dt.with_timezone(&Utc))
                     }),
                     reset_description: session.reset_description.clone(),
-                });
+                    ..UsageWindow::named("session", WindowKind::Session, session.get_used_percent())
+                }));
             }
 
             // Secondary = weekly
             if let Some(ref weekly) = usage.weekly {
-                snapshot.secondary = Some(exactobar_core::UsageWindow {
-                    used_percent: weekly.get_used_percent(),
+                snapshot.set_secondary(Some(UsageWindow {
                     window_minutes: Some(10080),
                     resets_at: weekly.resets_at.as_ref().and_then(|s| {
                         DateTime::parse_from_rfc3339(s)
@@ -254,14 +259,14 @@ impl WebUsageResponse {
                             .map(|dt| dt.with_timezone(&Utc))
                     }),
                     reset_description: weekly.reset_description.clone(),
-                });
+                    ..UsageWindow::named("weekly", WindowKind::Weekly, weekly.get_used_percent())
+                }));
             }
 
             // Tertiary = opus or sonnet
             let opus_or_sonnet = usage.opus.as_ref().or(usage.sonnet.as_ref());
             if let Some(window) = opus_or_sonnet {
-                snapshot.tertiary = Some(exactobar_core::UsageWindow {
-                    used_percent: window.get_used_percent(),
+                snapshot.set_tertiary(Some(UsageWindow {
                     window_minutes: Some(10080),
                     resets_at: window.resets_at.as_ref().and_then(|s| {
                         DateTime::parse_from_rfc3339(s)
@@ -269,10 +274,13 @@ impl WebUsageResponse {
                             .map(|dt| dt.with_timezone(&Utc))
                     }),
                     reset_description: window.reset_description.clone(),
-                });
+                    ..UsageWindow::named("opus", WindowKind::Weekly, window.get_used_percent())
+                }));
             }
         }
 
+        snapshot.credits = self.extra_usage.as_ref().and_then(ExtraUsage::to_credits);
+
         // Identity
         if self.user.is_some() || self.organization.is_some() {
             let mut identity = ProviderIdentity::new(ProviderKind::Claude);
@@ -347,6 +355,25 @@ mod tests {
             response.user.as_ref().unwrap().email,
             Some("user@example.com".to_string())
         );
+        assert!(response.to_snapshot().credits.is_none());
+    }
+
+    #[test]
+    fn test_parse_web_extra_usage() {
+        let json = r#"{
+            "usage": { "session": { "usedPercent": 10.0 } },
+            "extraUsage": {
+                "isEnabled": true,
+                "usedCredits": 12.5,
+                "monthlyLimit": 50.0,
+                "currency": "USD"
+            }
+        }"#;
+
+        let response: WebUsageResponse = serde_json::from_str(json).unwrap();
+        let credits = response.to_snapshot().credits.unwrap();
+        assert!((credits.remaining - 37.5).abs() < 0.01);
+        assert_eq!(credits.total, Some(50.0));
     }
 
     #[test]
@@ -408,14 +435,15 @@ mod tests {
                 name: None,
             }),
             organization: None,
+            extra_usage: None,
         };
 
         let snapshot = response.to_snapshot();
 
-        assert!(snapshot.primary.is_some());
-        assert!((snapshot.primary.as_ref().unwrap().used_percent - 25.0).abs() < 0.01);
+        assert!(snapshot.primary().is_some());
+        assert!((snapshot.primary().unwrap().used_percent - 25.0).abs() < 0.01);
 
-        assert!(snapshot.secondary.is_some());
+        assert!(snapshot.secondary().is_some());
 
         assert!(snapshot.identity.is_some());
     }
@@ -0,0 +1,672 @@
+//! Shared client for the local Codeium language server.
+//!
+//! Antigravity and Windsurf both ship the Codeium language server
+//! (`exa.language_server_pb.LanguageServerService`). It listens on a
+//! localhost HTTPS port and expects the CSRF token passed on its command
+//! line. This module finds the process for a given IDE, detects the port
+//! and sends the Connect-protocol JSON requests.
+
+use chrono::{DateTime, Utc};
+use reqwest::header::CONTENT_TYPE;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::process::Command;
+use thiserror::Error;
+use tracing::{debug, instrument};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+const SERVICE_PATH: &str = "/exa.language_server_pb.LanguageServerService";
+const GET_USER_STATUS: &str = "GetUserStatus";
+const GET_COMMAND_MODEL_CONFIGS: &str = "GetCommandModelConfigs";
+const GET_UNLEASH_DATA: &str = "GetUnleashData";
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Language server errors.
+#[derive(Debug, Error)]
+pub enum CodeiumError {
+    /// No matching language server process.
+    #[error("{0} language server not running")]
+    NotRunning(&'static str),
+
+    /// Connection failed.
+    #[error("Connection failed: {0}")]
+    ConnectionFailed(String),
+
+    /// Invalid response.
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+
+    /// Port detection failed.
+    #[error("Port detection failed: {0}")]
+    PortDetectionFailed(String),
+
+    /// API error from the language server.
+    #[error("API error: {0}")]
+    ApiError(String),
+}
+
+// ============================================================================
+// Target
+// ============================================================================
+
+/// Identifies which IDE's language server to talk to.
+#[derive(Debug, Clone, Copy)]
+pub struct LanguageServerTarget {
+    /// Display name used in errors and logs.
+    pub name: &'static str,
+    /// Substring of the language server binary name.
+    pub process_name: &'static str,
+    /// IDE name sent in request metadata.
+    pub ide_name: &'static str,
+    /// Returns true if a lowercased command line belongs to this IDE.
+    pub matches_command: fn(&str) -> bool,
+}
+
+// ============================================================================
+// Process Detection
+// ============================================================================
+
+/// A running language server process.
+#[derive(Debug, Clone)]
+struct ProcessInfo {
+    pid: u32,
+    csrf_token: String,
+    extension_port: Option<u16>,
+}
+
+/// Detects the target's language server process and extracts its CSRF token.
+fn detect_process(target: &LanguageServerTarget) -> Result<ProcessInfo, CodeiumError> {
+    let output = Command::new("/bin/ps")
+        .args(["-ax", "-o", "pid=,command="])
+        .output()
+        .map_err(|_e| CodeiumError::NotRunning(target.name))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    find_process(target, &stdout).ok_or(CodeiumError::NotRunning(target.name))
+}
+
+/// Finds the target's process in `ps -ax -o pid=,command=` output.
+fn find_process(target: &LanguageServerTarget, ps_output: &str) -> Option<ProcessInfo> {
+    for line in ps_output.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        // Parse "PID command..."
+        let Some((pid, command)) = trimmed.split_once(' ') else {
+            continue;
+        };
+        let Ok(pid) = pid.trim().parse::<u32>() else {
+            continue;
+        };
+        let lower = command.to_lowercase();
+
+        if !lower.contains(target.process_name) || !(target.matches_command)(&lower) {
+            continue;
+        }
+
+        if let Some(token) = extract_flag("--csrf_token", command) {
+            let port =
+                extract_flag("--extension_server_port", command).and_then(|s| s.parse().ok());
+
+            return Some(ProcessInfo {
+                pid,
+                csrf_token: token,
+                extension_port: port,
+            });
+        }
+    }
+
+    None
+}
+
+/// Extracts `--flag=value` or `--flag value` from a command line.
+pub fn extract_flag(flag: &str, command: &str) -> Option<String> {
+    let patterns = [format!("{}=", flag), format!("{} ", flag)];
+
+    for pattern in &patterns {
+        if let Some(start) = command.find(pattern) {
+            let value_start = start + pattern.len();
+            let rest = &command[value_start..];
+            let value_end = rest.find(' ').unwrap_or(rest.len());
+            return Some(rest[..value_end].to_string());
+        }
+    }
+    None
+}
+
+// ============================================================================
+// Port Detection
+// ============================================================================
+
+fn detect_listening_ports(pid: u32) -> Result<Vec<u16>, CodeiumError> {
+    let lsof_paths = ["/usr/sbin/lsof", "/usr/bin/lsof"];
+    let lsof = lsof_paths
+        .iter()
+        .find(|p| std::path::Path::new(p).exists())
+        .ok_or_else(|| CodeiumError::PortDetectionFailed("lsof not available".into()))?;
+
+    let output = Command::new(lsof)
+        .args(["-nP", "-iTCP", "-sTCP:LISTEN", "-a", "-p", &pid.to_string()])
+        .output()
+        .map_err(|e| CodeiumError::PortDetectionFailed(e.to_string()))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut ports = Vec::new();
+
+    // Parse lines like: "... :PORT (LISTEN)"
+    for line in stdout.lines() {
+        if let Some(port) = parse_port_from_lsof_line(line) {
+            if !ports.contains(&port) {
+                ports.push(port);
+            }
+        }
+    }
+
+    ports.sort();
+
+    if ports.is_empty() {
+        return Err(CodeiumError::PortDetectionFailed(
+            "no listening ports found".into(),
+        ));
+    }
+
+    Ok(ports)
+}
+
+fn parse_port_from_lsof_line(line: &str) -> Option<u16> {
+    // Look for pattern like ":12345 (LISTEN)"
+    let listen_idx = line.find("(LISTEN)")?;
+    let before = &line[..listen_idx];
+    let colon_idx = before.rfind(':')?;
+    let port_str = before[colon_idx + 1..].trim();
+    port_str.parse().ok()
+}
+
+// ============================================================================
+// API Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UserStatusResponse {
+    code: Option<CodeValue>,
+    user_status: Option<UserStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommandModelConfigResponse {
+    code: Option<CodeValue>,
+    client_model_configs: Option<Vec<ModelConfig>>,
+}
+
+/// User status from `GetUserStatus`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UserStatus {
+    /// Account email.
+    pub email: Option<String>,
+    /// Plan and credit status.
+    pub plan_status: Option<PlanStatus>,
+    /// Per-model quota configs (Antigravity).
+    pub cascade_model_config_data: Option<ModelConfigData>,
+}
+
+impl UserStatus {
+    /// Returns the preferred plan name, if any.
+    pub fn plan_name(&self) -> Option<String> {
+        self.plan_status
+            .as_ref()
+            .and_then(|ps| ps.plan_info.as_ref())
+            .and_then(|pi| pi.preferred_name().map(String::from))
+    }
+
+    /// Returns the per-model configs.
+    pub fn model_configs(&self) -> &[ModelConfig] {
+        self.cascade_model_config_data
+            .as_ref()
+            .and_then(|d| d.client_model_configs.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+/// Plan status, including Windsurf credit balances.
+///
+/// Credit amounts are in hundredths of a credit.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PlanStatus {
+    /// Plan details.
+    pub plan_info: Option<PlanInfo>,
+    /// Start of the current billing cycle.
+    pub plan_start: Option<String>,
+    /// End of the current billing cycle.
+    pub plan_end: Option<String>,
+    /// Prompt credits available this cycle.
+    pub available_prompt_credits: Option<LenientNumber>,
+    /// Prompt credits used this cycle.
+    pub used_prompt_credits: Option<LenientNumber>,
+    /// Flow action credits available this cycle.
+    pub available_flow_credits: Option<LenientNumber>,
+    /// Flow action credits used this cycle.
+    pub used_flow_credits: Option<LenientNumber>,
+    /// Add-on (flex) credits available.
+    pub available_flex_credits: Option<LenientNumber>,
+    /// Add-on (flex) credits used.
+    pub used_flex_credits: Option<LenientNumber>,
+}
+
+/// Plan details.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PlanInfo {
+    /// Internal plan name.
+    pub plan_name: Option<String>,
+    /// Display name of the plan.
+    pub plan_display_name: Option<String>,
+    /// Alternate display name.
+    pub display_name: Option<String>,
+    /// Product name.
+    pub product_name: Option<String>,
+    /// Short plan name.
+    pub plan_short_name: Option<String>,
+    /// Team tier (e.g., `TEAMS_TIER_PRO`).
+    pub teams_tier: Option<String>,
+    /// Monthly prompt credit allowance.
+    pub monthly_prompt_credits: Option<LenientNumber>,
+    /// Monthly flow action credit allowance.
+    pub monthly_flow_credits: Option<LenientNumber>,
+}
+
+impl PlanInfo {
+    /// Returns the first non-empty display name.
+    pub fn preferred_name(&self) -> Option<&str> {
+        self.plan_display_name
+            .as_deref()
+            .filter(|s| !s.is_empty())
+            .or(self.display_name.as_deref().filter(|s| !s.is_empty()))
+            .or(self.product_name.as_deref().filter(|s| !s.is_empty()))
+            .or(self.plan_name.as_deref().filter(|s| !s.is_empty()))
+    }
+}
+
+/// Container for per-model configs.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ModelConfigData {
+    /// Per-model configs.
+    pub client_model_configs: Option<Vec<ModelConfig>>,
+}
+
+/// Per-model config with optional quota.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelConfig {
+    /// Human-readable label.
+    pub label: String,
+    /// Model identifier.
+    pub model_or_alias: ModelAlias,
+    /// Quota, if the model is metered.
+    pub quota_info: Option<QuotaInfo>,
+}
+
+/// Model identifier wrapper.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModelAlias {
+    /// Model identifier.
+    pub model: String,
+}
+
+/// Per-model quota.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaInfo {
+    /// Remaining fraction (0.0 to 1.0).
+    pub remaining_fraction: Option<f64>,
+    /// Reset time (RFC 3339 or Unix seconds).
+    pub reset_time: Option<String>,
+}
+
+/// Number that protobuf JSON may encode as a string (int64).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum LenientNumber {
+    /// Plain number.
+    Number(f64),
+    /// Numeric string.
+    Text(String),
+}
+
+impl LenientNumber {
+    /// Returns the numeric value, or 0 if unparseable.
+    pub fn value(&self) -> f64 {
+        match self {
+            Self::Number(n) => *n,
+            Self::Text(s) => s.trim().parse().unwrap_or(0.0),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum CodeValue {
+    Int(i32),
+    String(String),
+}
+
+impl CodeValue {
+    fn is_ok(&self) -> bool {
+        match self {
+            CodeValue::Int(v) => *v == 0,
+            CodeValue::String(s) => {
+                let lower = s.to_lowercase();
+                lower == "ok" || lower == "success" || s == "0"
+            }
+        }
+    }
+}
+
+fn check_code(code: Option<&CodeValue>) -> Result<(), CodeiumError> {
+    match code {
+        Some(code) if !code.is_ok() => Err(CodeiumError::ApiError(format!("code: {:?}", code))),
+        _ => Ok(()),
+    }
+}
+
+/// Parses a timestamp given as RFC 3339 or Unix seconds.
+pub fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    if let Ok(secs) = s.parse::<i64>() {
+        return DateTime::from_timestamp(secs, 0);
+    }
+    None
+}
+
+// ============================================================================
+// Client
+// ============================================================================
+
+/// A connected language server (process found, port verified).
+#[derive(Debug, Clone)]
+pub struct LanguageServerConnection {
+    /// Process ID.
+    pub pid: u32,
+    /// Working API port.
+    pub port: u16,
+    csrf_token: String,
+}
+
+/// Client for a local Codeium language server.
+#[derive(Debug)]
+pub struct LanguageServerClient {
+    target: LanguageServerTarget,
+    http: reqwest::Client,
+}
+
+impl LanguageServerClient {
+    /// Creates a client for the given IDE.
+    pub fn new(target: LanguageServerTarget) -> Self {
+        // Accept self-signed certs for localhost HTTPS
+        let http = reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .timeout(std::time::Duration::from_secs(8))
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self { target, http }
+    }
+
+    /// Returns the target IDE.
+    pub fn target(&self) -> &LanguageServerTarget {
+        &self.target
+    }
+
+    /// Returns true if the language server is running.
+    pub fn is_running(&self) -> bool {
+        detect_process(&self.target).is_ok()
+    }
+
+    /// Finds the process and a working API port.
+    #[instrument(skip(self), fields(target = self.target.name))]
+    pub async fn connect(&self) -> Result<LanguageServerConnection, CodeiumError> {
+        let process = detect_process(&self.target)?;
+        debug!(pid = process.pid, "Found language server process");
+
+        let ports = if let Some(port) = process.extension_port {
+            vec![port]
+        } else {
+            detect_listening_ports(process.pid)?
+        };
+        debug!(?ports, "Detected listening ports");
+
+        for port in ports {
+            let connection = LanguageServerConnection {
+                pid: process.pid,
+                port,
+                csrf_token: process.csrf_token.clone(),
+            };
+            if self.test_port(&connection).await {
+                debug!(port, "Found working API port");
+                return Ok(connection);
+            }
+        }
+
+        Err(CodeiumError::PortDetectionFailed(
+            "no working API port".into(),
+        ))
+    }
+
+    /// Fetches the user status.
+    pub async fn get_user_status(
+        &self,
+        connection: &LanguageServerConnection,
+    ) -> Result<UserStatus, CodeiumError> {
+        let response: UserStatusResponse = self
+            .call(connection, GET_USER_STATUS, &self.metadata_body())
+            .await?;
+        check_code(response.code.as_ref())?;
+        response
+            .user_status
+            .ok_or_else(|| CodeiumError::InvalidResponse("Missing userStatus".into()))
+    }
+
+    /// Fetches the per-model configs.
+    pub async fn get_command_model_configs(
+        &self,
+        connection: &LanguageServerConnection,
+    ) -> Result<Vec<ModelConfig>, CodeiumError> {
+        let response: CommandModelConfigResponse = self
+            .call(connection, GET_COMMAND_MODEL_CONFIGS, &self.metadata_body())
+            .await?;
+        check_code(response.code.as_ref())?;
+        Ok(response.client_model_configs.unwrap_or_default())
+    }
+
+    /// Quick connectivity test using the `GetUnleashData` endpoint.
+    async fn test_port(&self, connection: &LanguageServerConnection) -> bool {
+        let body = serde_json::json!({
+            "context": {
+                "properties": {
+                    "ide": self.target.ide_name,
+                    "installationId": "exactobar"
+                }
+            }
+        });
+
+        self.post(connection, GET_UNLEASH_DATA, &body).await.is_ok()
+    }
+
+    fn metadata_body(&self) -> serde_json::Value {
+        serde_json::json!({
+            "metadata": {
+                "ideName": self.target.ide_name,
+                "extensionName": self.target.ide_name,
+                "ideVersion": "unknown",
+                "locale": "en"
+            }
+        })
+    }
+
+    /// Calls a service method and parses the JSON response.
+    async fn call<T: DeserializeOwned>(
+        &self,
+        connection: &LanguageServerConnection,
+        method: &str,
+        body: &serde_json::Value,
+    ) -> Result<T, CodeiumError> {
+        let data = self.post(connection, method, body).await?;
+        serde_json::from_slice(&data).map_err(|e| CodeiumError::InvalidResponse(e.to_string()))
+    }
+
+    /// Sends a POST request to a service method.
+    async fn post(
+        &self,
+        connection: &LanguageServerConnection,
+        method: &str,
+        body: &serde_json::Value,
+    ) -> Result<Vec<u8>, CodeiumError> {
+        let url = format!(
+            "https://127.0.0.1:{}{}/{}",
+            connection.port, SERVICE_PATH, method
+        );
+
+        let response = self
+            .http
+            .post(&url)
+            .header(CONTENT_TYPE, "application/json")
+            .header("Connect-Protocol-Version", "1")
+            .header("X-Codeium-Csrf-Token", &connection.csrf_token)
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| CodeiumError::ConnectionFailed(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(CodeiumError::ApiError(format!("HTTP {}: {}", status, body)));
+        }
+
+        response
+            .bytes()
+            .await
+            .map(|b| b.to_vec())
+            .map_err(|e| CodeiumError::InvalidResponse(e.to_string()))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_TARGET: LanguageServerTarget = LanguageServerTarget {
+        name: "Test",
+        process_name: "language_server",
+        ide_name: "test",
+        matches_command: |command| command.contains("/test/"),
+    };
+
+    #[test]
+    fn test_extract_flag_equals() {
+        let cmd = "./server --csrf_token=abc123 --port=8080";
+        assert_eq!(
+            extract_flag("--csrf_token", cmd),
+            Some("abc123".to_string())
+        );
+        assert_eq!(extract_flag("--port", cmd), Some("8080".to_string()));
+    }
+
+    #[test]
+    fn test_extract_flag_space() {
+        let cmd = "./server --csrf_token abc123 --port 8080";
+        assert_eq!(
+            extract_flag("--csrf_token", cmd),
+            Some("abc123".to_string())
+        );
+        assert_eq!(extract_flag("--port", cmd), Some("8080".to_string()));
+    }
+
+    #[test]
+    fn test_extract_flag_missing() {
+        let cmd = "./server --other_flag=value";
+        assert_eq!(extract_flag("--csrf_token", cmd), None);
+    }
+
+    #[test]
+    fn test_find_process() {
+        let ps = "\
+  101 /usr/bin/other --csrf_token=nope
+  202 /apps/Other/language_server_macos --csrf_token=wrong
+  303 /apps/Test/language_server_macos --csrf_token=secret --extension_server_port=4242
+";
+        let process = find_process(&TEST_TARGET, ps).unwrap();
+        assert_eq!(process.pid, 303);
+        assert_eq!(process.csrf_token, "secret");
+        assert_eq!(process.extension_port, Some(4242));
+
+        assert!(find_process(&TEST_TARGET, "  1 /bin/zsh").is_none());
+    }
+
+    #[test]
+    fn test_parse_port_from_lsof() {
+        let line = "node    12345 user   23u  IPv4 0x123  0t0  TCP 127.0.0.1:42069 (LISTEN)";
+        assert_eq!(parse_port_from_lsof_line(line), Some(42069));
+    }
+
+    #[test]
+    fn test_parse_port_no_listen() {
+        let line = "node    12345 user   23u  IPv4 0x123  0t0  TCP 127.0.0.1:42069";
+        assert_eq!(parse_port_from_lsof_line(line), None);
+    }
+
+    #[test]
+    fn test_code_value_is_ok() {
+        assert!(CodeValue::Int(0).is_ok());
+        assert!(!CodeValue::Int(1).is_ok());
+        assert!(CodeValue::String("ok".to_string()).is_ok());
+        assert!(CodeValue::String("OK".to_string()).is_ok());
+        assert!(CodeValue::String("success".to_string()).is_ok());
+        assert!(!CodeValue::String("error".to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert!(parse_timestamp("2024-01-15T12:00:00Z").is_some());
+        assert!(parse_timestamp("1705320000").is_some());
+        assert!(parse_timestamp("not-a-date").is_none());
+    }
+
+    #[test]
+    fn test_lenient_number() {
+        let plan: PlanStatus = serde_json::from_str(
+            r#"{"availablePromptCredits": "50000", "usedPromptCredits": 1200}"#,
+        )
+        .unwrap();
+        assert!((plan.available_prompt_credits.unwrap().value() - 50000.0).abs() < 0.001);
+        assert!((plan.used_prompt_credits.unwrap().value() - 1200.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_user_status_plan_name() {
+        let status: UserStatus = serde_json::from_str(
+            r#"{"email": "a@b.c", "planStatus": {"planInfo": {"planName": "pro", "planDisplayName": ""}}}"#,
+        )
+        .unwrap();
+        assert_eq!(status.plan_name().as_deref(), Some("pro"));
+        assert!(status.model_configs().is_empty());
+    }
+}
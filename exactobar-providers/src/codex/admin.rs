@@ -0,0 +1,634 @@
+//! `OpenAI` organization Usage and Costs API client.
+//!
+//! These endpoints need an organization admin key (`sk-admin-...`), created
+//! under Organization settings → Admin keys. Project API keys are rejected.
+//!
+//! # API Endpoints
+//!
+//! ```text
+//! GET https://api.openai.com/v1/organization/costs
+//!     ?start_time=1730419200&bucket_width=1d&group_by=project_id&limit=31
+//! GET https://api.openai.com/v1/organization/usage/completions
+//!     ?start_time=1730419200&bucket_width=1d&group_by=model,project_id&limit=31
+//! GET https://api.openai.com/v1/organization/projects?limit=100
+//! Authorization: Bearer sk-admin-...
+//! ```
+//!
+//! # Response Format
+//!
+//! ```json
+//! {
+//!   "object": "page",
+//!   "data": [{
+//!     "object": "bucket",
+//!     "start_time": 1730419200,
+//!     "end_time": 1730505600,
+//!     "results": [{
+//!       "object": "organization.costs.result",
+//!       "amount": { "value": 0.06, "currency": "usd" },
+//!       "project_id": "proj_abc"
+//!     }]
+//!   }],
+//!   "has_more": false,
+//!   "next_page": null
+//! }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use exactobar_core::{
+    CostUsageSnapshot, DailyUsageEntry, FetchSource, ModelBreakdown, ProjectBreakdown, USD,
+    UsageSnapshot,
+};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use tracing::{debug, instrument, warn};
+
+use super::error::CodexError;
+use crate::spend::{monthly_spend_window, start_of_month};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Base URL for the `OpenAI` API.
+pub const ADMIN_API_BASE_URL: &str = "https://api.openai.com/v1";
+
+/// Environment variable for the admin key.
+pub const ADMIN_KEY_ENV: &str = "OPENAI_ADMIN_KEY";
+
+/// Prefix of organization admin keys.
+pub const ADMIN_KEY_PREFIX: &str = "sk-admin-";
+
+/// Costs endpoint.
+const COSTS_ENDPOINT: &str = "/organization/costs";
+
+/// Completions usage endpoint.
+const COMPLETIONS_USAGE_ENDPOINT: &str = "/organization/usage/completions";
+
+/// Projects endpoint.
+const PROJECTS_ENDPOINT: &str = "/organization/projects";
+
+/// Maximum pages to follow per request.
+const MAX_PAGES: usize = 10;
+
+// ============================================================================
+// Response Structures
+// ============================================================================
+
+/// A page of time buckets.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BucketPage<T> {
+    /// Time buckets.
+    #[serde(default = "Vec::new")]
+    pub data: Vec<Bucket<T>>,
+    /// Whether more pages follow.
+    #[serde(default)]
+    pub has_more: bool,
+    /// Cursor for the next page.
+    pub next_page: Option<String>,
+}
+
+/// A single time bucket.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Bucket<T> {
+    /// Bucket start (Unix seconds).
+    pub start_time: i64,
+    /// Bucket end (Unix seconds).
+    pub end_time: i64,
+    /// Grouped results.
+    #[serde(default = "Vec::new")]
+    pub results: Vec<T>,
+}
+
+impl<T> Bucket<T> {
+    /// Returns the bucket start time.
+    pub fn start(&self) -> DateTime<Utc> {
+        Utc.timestamp_opt(self.start_time, 0)
+            .single()
+            .unwrap_or_default()
+    }
+
+    /// Returns the bucket date ("YYYY-MM-DD").
+    pub fn date(&self) -> String {
+        self.start().format("%Y-%m-%d").to_string()
+    }
+}
+
+/// Cost result within a bucket.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CostResult {
+    /// Cost amount.
+    pub amount: CostAmount,
+    /// Line item (when grouped by line item).
+    pub line_item: Option<String>,
+    /// Project ID (when grouped by project).
+    pub project_id: Option<String>,
+}
+
+/// Cost amount.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CostAmount {
+    /// Amount value.
+    #[serde(default)]
+    pub value: f64,
+    /// Lowercase currency code.
+    #[serde(default)]
+    pub currency: Option<String>,
+}
+
+/// Completions usage result within a bucket.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CompletionsUsageResult {
+    /// Input tokens (including cached).
+    pub input_tokens: u64,
+    /// Output tokens.
+    pub output_tokens: u64,
+    /// Cached input tokens.
+    pub input_cached_tokens: u64,
+    /// Number of requests.
+    pub num_model_requests: u64,
+    /// Project ID (when grouped by project).
+    pub project_id: Option<String>,
+    /// Model (when grouped by model).
+    pub model: Option<String>,
+}
+
+/// Projects list response.
+#[derive(Debug, Clone, Deserialize)]
+struct ProjectsResponse {
+    #[serde(default)]
+    data: Vec<Project>,
+}
+
+/// Organization project.
+#[derive(Debug, Clone, Deserialize)]
+struct Project {
+    id: String,
+    name: Option<String>,
+}
+
+// ============================================================================
+// Organization Usage
+// ============================================================================
+
+/// Costs, token usage and project names for an organization.
+#[derive(Debug, Clone, Default)]
+pub struct OrganizationUsage {
+    /// Daily cost buckets grouped by project.
+    pub costs: Vec<Bucket<CostResult>>,
+    /// Daily completions usage grouped by model and project.
+    pub usage: Vec<Bucket<CompletionsUsageResult>>,
+    /// Project names by ID.
+    pub project_names: HashMap<String, String>,
+}
+
+impl OrganizationUsage {
+    /// Returns the total cost of buckets starting at or after `since`.
+    pub fn cost_since(&self, since: DateTime<Utc>) -> f64 {
+        self.costs
+            .iter()
+            .filter(|b| b.start() >= since)
+            .flat_map(|b| &b.results)
+            .map(|r| r.amount.value)
+            .sum()
+    }
+
+    /// Returns total tokens of buckets starting at or after `since`.
+    pub fn tokens_since(&self, since: DateTime<Utc>) -> u64 {
+        self.usage
+            .iter()
+            .filter(|b| b.start() >= since)
+            .flat_map(|b| &b.results)
+            .map(|r| r.input_tokens + r.output_tokens)
+            .sum()
+    }
+
+    /// Builds a daily cost report with per-model and per-project breakdowns.
+    pub fn to_cost_snapshot(&self, now: DateTime<Utc>) -> CostUsageSnapshot {
+        let mut days: BTreeMap<String, DayTotals> = BTreeMap::new();
+
+        for bucket in &self.costs {
+            let day = days.entry(bucket.date()).or_default();
+            for result in &bucket.results {
+                day.cost += result.amount.value;
+                let project = day.project(result.project_id.as_deref());
+                *project.cost_usd.get_or_insert(0.0) += result.amount.value;
+            }
+        }
+
+        for bucket in &self.usage {
+            let day = days.entry(bucket.date()).or_default();
+            for result in &bucket.results {
+                day.input += result.input_tokens;
+                day.output += result.output_tokens;
+                day.cached += result.input_cached_tokens;
+
+                let model = result.model.as_deref().unwrap_or("unknown");
+                let breakdown = day
+                    .models
+                    .entry(model.to_string())
+                    .or_insert_with(|| ModelBreakdown::new(model));
+                *breakdown.input_tokens.get_or_insert(0) += result.input_tokens;
+                *breakdown.output_tokens.get_or_insert(0) += result.output_tokens;
+
+                let project = day.project(result.project_id.as_deref());
+                *project.input_tokens.get_or_insert(0) += result.input_tokens;
+                *project.output_tokens.get_or_insert(0) += result.output_tokens;
+            }
+        }
+
+        let mut snapshot = CostUsageSnapshot::new();
+        snapshot.updated_at = now;
+        snapshot.daily = days
+            .into_iter()
+            .map(|(date, day)| day.into_entry(date, &self.project_names))
+            .collect();
+
+        let since = now - Duration::days(30);
+        snapshot.last_30_days_cost_usd = Some(self.cost_since(since));
+        snapshot.last_30_days_tokens = Some(self.tokens_since(since));
+        snapshot
+    }
+
+    /// Builds a usage snapshot with a monthly spend window and the cost report.
+    ///
+    /// The spend window is unslotted so it survives a merge with the RPC
+    /// session and weekly windows.
+    pub fn to_snapshot(&self, budget: Option<f64>, now: DateTime<Utc>) -> UsageSnapshot {
+        let spent = self.cost_since(start_of_month(now));
+        let window = monthly_spend_window(spent, budget, USD, now);
+
+        let mut snapshot = UsageSnapshot::new();
+        snapshot.fetch_source = FetchSource::Api;
+        snapshot.push_window(window);
+        snapshot.cost = Some(self.to_cost_snapshot(now));
+        snapshot
+    }
+}
+
+/// Per-day accumulator.
+#[derive(Default)]
+struct DayTotals {
+    cost: f64,
+    input: u64,
+    output: u64,
+    cached: u64,
+    models: BTreeMap<String, ModelBreakdown>,
+    projects: BTreeMap<String, ProjectBreakdown>,
+}
+
+impl DayTotals {
+    fn project(&mut self, id: Option<&str>) -> &mut ProjectBreakdown {
+        let id = id.unwrap_or("default");
+        self.projects
+            .entry(id.to_string())
+            .or_insert_with(|| ProjectBreakdown::new(id))
+    }
+
+    fn into_entry(self, date: String, names: &HashMap<String, String>) -> DailyUsageEntry {
+        let mut entry = DailyUsageEntry::new(date);
+        entry.cost_usd = Some(self.cost);
+        if self.input + self.output > 0 {
+            entry.input_tokens = Some(self.input);
+            entry.output_tokens = Some(self.output);
+            entry.cache_read_tokens = Some(self.cached);
+            // Cached tokens are already counted in input
+            entry.total_tokens = Some(self.input + self.output);
+        }
+        if !self.models.is_empty() {
+            entry.models_used = Some(self.models.keys().cloned().collect());
+            entry.model_breakdowns = Some(self.models.into_values().collect());
+        }
+        if !self.projects.is_empty() {
+            entry.project_breakdowns = Some(
+                self.projects
+                    .into_values()
+                    .map(|mut p| {
+                        p.project_name = names.get(&p.project_id).cloned();
+                        p
+                    })
+                    .collect(),
+            );
+        }
+        entry
+    }
+}
+
+// ============================================================================
+// API Client
+// ============================================================================
+
+/// Client for the organization Usage and Costs API.
+#[derive(Debug, Clone)]
+pub struct OpenAiAdminClient {
+    base_url: String,
+}
+
+impl Default for OpenAiAdminClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OpenAiAdminClient {
+    /// Creates a new client.
+    pub fn new() -> Self {
+        Self {
+            base_url: ADMIN_API_BASE_URL.to_string(),
+        }
+    }
+
+    /// Creates a client with a custom base URL.
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+        }
+    }
+
+    /// Returns true if the key looks like an organization admin key.
+    pub fn is_admin_key(key: &str) -> bool {
+        key.starts_with(ADMIN_KEY_PREFIX)
+    }
+
+    /// Fetches costs, usage and project names since the start of the month
+    /// or the last 30 days, whichever is earlier.
+    ///
+    /// Costs are required; usage and project names are best-effort.
+    #[instrument(skip(self, admin_key))]
+    pub async fn fetch_organization_usage(
+        &self,
+        admin_key: &str,
+        now: DateTime<Utc>,
+    ) -> Result<OrganizationUsage, CodexError> {
+        let start = start_of_month(now).min(now - Duration::days(30));
+
+        let costs = self.fetch_costs(admin_key, start).await?;
+
+        let usage = self
+            .fetch_completions_usage(admin_key, start)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "Failed to fetch OpenAI completions usage");
+                Vec::new()
+            });
+
+        let project_names = self
+            .fetch_project_names(admin_key)
+            .await
+            .unwrap_or_else(|e| {
+                debug!(error = %e, "Failed to fetch OpenAI project names");
+                HashMap::new()
+            });
+
+        Ok(OrganizationUsage {
+            costs,
+            usage,
+            project_names,
+        })
+    }
+
+    /// Fetches daily cost buckets grouped by project.
+    pub async fn fetch_costs(
+        &self,
+        admin_key: &str,
+        start: DateTime<Utc>,
+    ) -> Result<Vec<Bucket<CostResult>>, CodexError> {
+        let query = [
+            ("start_time", start.timestamp().to_string()),
+            ("bucket_width", "1d".to_string()),
+            ("group_by", "project_id".to_string()),
+            ("limit", "31".to_string()),
+        ];
+        self.get_buckets(admin_key, COSTS_ENDPOINT, &query).await
+    }
+
+    /// Fetches daily completions usage grouped by model and project.
+    pub async fn fetch_completions_usage(
+        &self,
+        admin_key: &str,
+        start: DateTime<Utc>,
+    ) -> Result<Vec<Bucket<CompletionsUsageResult>>, CodexError> {
+        let query = [
+            ("start_time", start.timestamp().to_string()),
+            ("bucket_width", "1d".to_string()),
+            ("group_by", "model,project_id".to_string()),
+            ("limit", "31".to_string()),
+        ];
+        self.get_buckets(admin_key, COMPLETIONS_USAGE_ENDPOINT, &query)
+            .await
+    }
+
+    /// Fetches project names by ID.
+    pub async fn fetch_project_names(
+        &self,
+        admin_key: &str,
+    ) -> Result<HashMap<String, String>, CodexError> {
+        let query = [("limit", "100".to_string())];
+        let response: ProjectsResponse = self.get(admin_key, PROJECTS_ENDPOINT, &query).await?;
+        Ok(response
+            .data
+            .into_iter()
+            .filter_map(|p| p.name.map(|name| (p.id, name)))
+            .collect())
+    }
+
+    /// Fetches every page of a bucketed endpoint.
+    async fn get_buckets<T: DeserializeOwned>(
+        &self,
+        admin_key: &str,
+        endpoint: &str,
+        query: &[(&str, String)],
+    ) -> Result<Vec<Bucket<T>>, CodexError> {
+        let mut buckets = Vec::new();
+        let mut page: Option<String> = None;
+
+        for _ in 0..MAX_PAGES {
+            let mut params = query.to_vec();
+            if let Some(ref cursor) = page {
+                params.push(("page", cursor.clone()));
+            }
+
+            let response: BucketPage<T> = self.get(admin_key, endpoint, &params).await?;
+            buckets.extend(response.data);
+
+            match response.next_page {
+                Some(next) if response.has_more => page = Some(next),
+                _ => return Ok(buckets),
+            }
+        }
+
+        warn!(endpoint = endpoint, "Stopped following pages at the limit");
+        Ok(buckets)
+    }
+
+    /// Sends an authenticated GET request and parses the JSON response.
+    async fn get<T: DeserializeOwned>(
+        &self,
+        admin_key: &str,
+        endpoint: &str,
+        query: &[(&str, String)],
+    ) -> Result<T, CodexError> {
+        let url = format!("{}{}", self.base_url, endpoint);
+
+        debug!(url = %url, "Fetching OpenAI organization data");
+
+        let client = reqwest::Client::new();
+        let response = client
+            .get(&url)
+            .query(query)
+            .header("Authorization", format!("Bearer {}", admin_key))
+            .header("Content-Type", "application/json")
+            .send()
+            .await
+            .map_err(|e| CodexError::HttpError(e.to_string()))?;
+
+        let status = response.status();
+
+        if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+            return Err(CodexError::AuthenticationFailed(
+                "admin key rejected (organization owner admin key required)".to_string(),
+            ));
+        }
+
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(CodexError::ApiError(format!("HTTP {}: {}", status, body)));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| CodexError::ParseError(e.to_string()))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spend::MONTHLY_SPEND_WINDOW;
+    use exactobar_core::WindowKind;
+
+    const COSTS_JSON: &str = r#"{
+        "object": "page",
+        "data": [
+            {
+                "object": "bucket",
+                "start_time": 1730332800,
+                "end_time": 1730419200,
+                "results": [
+                    {"object": "organization.costs.result", "amount": {"value": 4.0, "currency": "usd"}, "line_item": null, "project_id": "proj_a"}
+                ]
+            },
+            {
+                "object": "bucket",
+                "start_time": 1730419200,
+                "end_time": 1730505600,
+                "results": [
+                    {"object": "organization.costs.result", "amount": {"value": 1.5, "currency": "usd"}, "line_item": null, "project_id": "proj_a"},
+                    {"object": "organization.costs.result", "amount": {"value": 0.5, "currency": "usd"}, "line_item": null, "project_id": null}
+                ]
+            }
+        ],
+        "has_more": false,
+        "next_page": null
+    }"#;
+
+    const USAGE_JSON: &str = r#"{
+        "object": "page",
+        "data": [
+            {
+                "object": "bucket",
+                "start_time": 1730419200,
+                "end_time": 1730505600,
+                "results": [
+                    {"object": "organization.usage.completions.result", "input_tokens": 1000, "output_tokens": 200, "input_cached_tokens": 300, "num_model_requests": 4, "project_id": "proj_a", "model": "gpt-4o"},
+                    {"object": "organization.usage.completions.result", "input_tokens": 500, "output_tokens": 50, "input_cached_tokens": 0, "num_model_requests": 1, "project_id": "proj_a", "model": "gpt-4o-mini"}
+                ]
+            }
+        ],
+        "has_more": true,
+        "next_page": "page_2"
+    }"#;
+
+    fn sample_usage() -> OrganizationUsage {
+        let costs: BucketPage<CostResult> = serde_json::from_str(COSTS_JSON).unwrap();
+        let usage: BucketPage<CompletionsUsageResult> = serde_json::from_str(USAGE_JSON).unwrap();
+        OrganizationUsage {
+            costs: costs.data,
+            usage: usage.data,
+            project_names: HashMap::from([("proj_a".to_string(), "Website".to_string())]),
+        }
+    }
+
+    /// 2024-11-01 12:00 UTC.
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 11, 1, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_bucket_pages() {
+        let usage: BucketPage<CompletionsUsageResult> = serde_json::from_str(USAGE_JSON).unwrap();
+        assert!(usage.has_more);
+        assert_eq!(usage.next_page.as_deref(), Some("page_2"));
+        assert_eq!(usage.data[0].results.len(), 2);
+        assert_eq!(usage.data[0].date(), "2024-11-01");
+    }
+
+    #[test]
+    fn test_cost_snapshot_breakdowns() {
+        let cost = sample_usage().to_cost_snapshot(now());
+        assert_eq!(cost.daily.len(), 2);
+
+        let day = cost.daily.iter().find(|d| d.date == "2024-11-01").unwrap();
+        assert!((day.cost_usd.unwrap() - 2.0).abs() < 0.001);
+        assert_eq!(day.input_tokens, Some(1500));
+        assert_eq!(day.cache_read_tokens, Some(300));
+        assert_eq!(day.total_tokens, Some(1750));
+        assert_eq!(
+            day.models_used,
+            Some(vec!["gpt-4o".to_string(), "gpt-4o-mini".to_string()])
+        );
+
+        let projects = day.project_breakdowns.as_ref().unwrap();
+        let website = projects.iter().find(|p| p.project_id == "proj_a").unwrap();
+        assert_eq!(website.display_name(), "Website");
+        assert!((website.cost_usd.unwrap() - 1.5).abs() < 0.001);
+        assert_eq!(website.total_tokens(), 1750);
+        assert!(projects.iter().any(|p| p.project_id == "default"));
+
+        assert!((cost.last_30_days_cost_usd.unwrap() - 6.0).abs() < 0.001);
+        assert_eq!(cost.last_30_days_tokens, Some(1750));
+    }
+
+    #[test]
+    fn test_monthly_spend_window_with_budget() {
+        let snapshot = sample_usage().to_snapshot(Some(8.0), now());
+        let window = snapshot.window(MONTHLY_SPEND_WINDOW).unwrap();
+
+        assert!(window.slot.is_none());
+        assert_eq!(window.kind, WindowKind::Monthly);
+        // Only November counts toward the month
+        assert!((window.used.unwrap() - 2.0).abs() < 0.001);
+        assert!((window.used_percent - 25.0).abs() < 0.001);
+        assert_eq!(window.limit, Some(8.0));
+        assert_eq!(
+            window.resets_at,
+            Some(Utc.with_ymd_and_hms(2024, 12, 1, 0, 0, 0).unwrap())
+        );
+        assert!(snapshot.cost.is_some());
+        assert!(snapshot.validate().is_ok());
+    }
+
+    #[test]
+    fn test_is_admin_key() {
+        assert!(OpenAiAdminClient::is_admin_key("sk-admin-abc"));
+        assert!(!OpenAiAdminClient::is_admin_key("sk-proj-abc"));
+    }
+}
@@ -1,7 +1,8 @@
 //! Codex provider descriptor.
 
 use exactobar_core::{IconStyle, ProviderBranding, ProviderColor, ProviderKind, ProviderMetadata};
-use exactobar_fetch::{FetchContext, FetchPipeline, SourceMode};
+use exactobar_fetch::{FetchContext, FetchPipeline, PipelineMode, SourceMode};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use super::strategies::{CodexApiStrategy, CodexCliStrategy, CodexPtyStrategy, CodexRpcStrategy};
@@ -28,6 +29,7 @@ fn codex_metadata() -> ProviderMetadata {
         weekly_label: "Weekly".to_string(),
         opus_label: None,
         supports_opus: false,
+        window_labels: HashMap::from([("monthly_spend".to_string(), "Monthly spend".to_string())]),
         supports_credits: true,
         credits_hint: "OpenAI API credits".to_string(),
         toggle_title: "Show Codex usage".to_string(),
@@ -71,7 +73,7 @@ fn codex_log_directory() -> Option<PathBuf> {
 fn codex_fetch_plan() -> FetchPlan {
     FetchPlan {
         source_modes: vec![SourceMode::CLI, SourceMode::ApiKey, SourceMode::Web],
-        build_pipeline: build_codex_pipeline,
+        build_pipeline: Box::new(build_codex_pipeline),
     }
 }
 
@@ -99,7 +101,10 @@ fn build_codex_pipeline(ctx: &FetchContext) -> FetchPipeline {
         strategies.push(Box::new(CodexApiStrategy::new()));
     }
 
-    FetchPipeline::with_strategies(strategies)
+    // RPC reports rate limits and credits, the organization API reports
+    // costs; merge them rather than stopping at RPC. PTY and CLI only run
+    // when RPC returned nothing.
+    FetchPipeline::with_strategies(strategies).with_mode(PipelineMode::Merge)
 }
 
 /// Codex CLI configuration.
@@ -66,6 +66,18 @@ pub enum CodexError {
     #[error("No usage data available")]
     NoData,
 
+    /// HTTP request failed.
+    #[error("HTTP error: {0}")]
+    HttpError(String),
+
+    /// API returned an error.
+    #[error("API error: {0}")]
+    ApiError(String),
+
+    /// API key was rejected.
+    #[error("Authentication failed: {0}")]
+    AuthenticationFailed(String),
+
     /// All strategies failed.
     #[error("All fetch strategies failed")]
     AllStrategiesFailed,
@@ -11,12 +11,13 @@
 //! ```ignore
 //! let fetcher = CodexUsageFetcher::new();
 //! let snapshot = fetcher.fetch_usage().await?;
-//! println!("Primary: {}% used", snapshot.primary.unwrap().used_percent);
+//! println!("Primary: {}% used", snapshot.primary().unwrap().used_percent);
 //! ```
 
 use chrono::{DateTime, TimeZone, Utc};
 use exactobar_core::{
     Credits, FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageSnapshot, UsageWindow,
+    WindowKind,
 };
 use tracing::{debug, info, instrument, warn};
 
@@ -232,22 +233,20 @@ fn convert_rpc_to_snapshot(limits: RateLimitsResult) -> UsageSnapshot {
 
     // Primary window (5-hour)
     if let Some(primary) = limits.rate_limits.primary {
-        snapshot.primary = Some(UsageWindow {
-            used_percent: primary.used_percent,
+        snapshot.set_primary(Some(UsageWindow {
             window_minutes: primary.window_duration_mins,
             resets_at: primary.resets_at.map(|ts| timestamp_to_datetime(ts)),
-            reset_description: None,
-        });
+            ..UsageWindow::named("session", WindowKind::Session, primary.used_percent)
+        }));
     }
 
     // Secondary window (weekly)
     if let Some(secondary) = limits.rate_limits.secondary {
-        snapshot.secondary = Some(UsageWindow {
-            used_percent: secondary.used_percent,
+        snapshot.set_secondary(Some(UsageWindow {
             window_minutes: secondary.window_duration_mins,
             resets_at: secondary.resets_at.map(|ts| timestamp_to_datetime(ts)),
-            reset_description: None,
-        });
+            ..UsageWindow::named("weekly", WindowKind::Weekly, secondary.used_percent)
+        }));
     }
 
     // Note: Credits are in limits.rate_limits.credits but we don't have
@@ -263,12 +262,16 @@ fn convert_pty_to_snapshot(status: CodexStatusSnapshot) -> UsageSnapshot {
 
     // Primary window
     if let Some(used) = status.primary_used_percent {
-        snapshot.primary = Some(UsageWindow::new(used));
+        snapshot.set_primary(Some(UsageWindow::named(
+            "session",
+            WindowKind::Session,
+            used,
+        )));
     }
 
     // Secondary window
     if let Some(used) = status.secondary_used_percent {
-        snapshot.secondary = Some(UsageWindow::new(used));
+        snapshot.set_secondary(Some(UsageWindow::named("weekly", WindowKind::Weekly, used)));
     }
 
     // Build identity from PTY output
@@ -323,13 +326,13 @@ mod tests {
 
         let snapshot = convert_rpc_to_snapshot(limits);
 
-        assert!(snapshot.primary.is_some());
-        let primary = snapshot.primary.unwrap();
+        assert!(snapshot.primary().is_some());
+        let primary = snapshot.primary().unwrap();
         assert!((primary.used_percent - 28.5).abs() < 0.01);
         assert_eq!(primary.window_minutes, Some(300));
 
-        assert!(snapshot.secondary.is_some());
-        let secondary = snapshot.secondary.unwrap();
+        assert!(snapshot.secondary().is_some());
+        let secondary = snapshot.secondary().unwrap();
         assert!((secondary.used_percent - 59.2).abs() < 0.01);
     }
 
@@ -346,11 +349,11 @@ mod tests {
 
         let snapshot = convert_pty_to_snapshot(status);
 
-        assert!(snapshot.primary.is_some());
-        assert!((snapshot.primary.unwrap().used_percent - 28.0).abs() < 0.01);
+        assert!(snapshot.primary().is_some());
+        assert!((snapshot.primary().unwrap().used_percent - 28.0).abs() < 0.01);
 
-        assert!(snapshot.secondary.is_some());
-        assert!((snapshot.secondary.unwrap().used_percent - 55.0).abs() < 0.01);
+        assert!(snapshot.secondary().is_some());
+        assert!((snapshot.secondary().unwrap().used_percent - 55.0).abs() < 0.01);
 
         assert!(snapshot.identity.is_some());
         let identity = snapshot.identity.unwrap();
@@ -18,8 +18,10 @@
 //! 3. **CLI Strategy** (priority 80): `codex usage --json`
 //!    - Legacy strategy using JSON output
 //!
-//! 4. **API Strategy** (priority 60): OpenAI API with API key
-//!    - Validates API key but can't get usage data
+//! 4. **API Strategy** (priority 60): OpenAI organization Usage/Costs API
+//!    - Needs an organization admin key (`sk-admin-...`)
+//!    - Daily costs and tokens per model and project
+//!    - Monthly spend window against an optional budget
 //!
 //! ## Authentication
 //!
@@ -36,6 +38,7 @@
 //! ```
 
 // Modules
+mod admin;
 mod auth;
 mod descriptor;
 mod error;
@@ -47,6 +50,9 @@ mod rpc;
 mod strategies;
 
 // Re-exports
+pub use admin::{
+    ADMIN_KEY_ENV, CompletionsUsageResult, CostResult, OpenAiAdminClient, OrganizationUsage,
+};
 pub use auth::{AccountInfo, read_account_info, try_read_account_info};
 pub use descriptor::codex_descriptor;
 pub use error::CodexError;
@@ -3,6 +3,7 @@
 use chrono::{DateTime, Utc};
 use exactobar_core::{
     FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageSnapshot, UsageWindow,
+    WindowKind,
 };
 use exactobar_fetch::FetchError;
 use serde::Deserialize;
@@ -108,12 +109,16 @@ pub fn parse_codex_cli_output(json_str: &str) -> Result<UsageSnapshot, FetchErro
 
     // Parse session window
     if let Some(session) = response.session {
-        snapshot.primary = Some(parse_usage_window(session));
+        snapshot.set_primary(Some(
+            parse_usage_window(session).with_id("session", WindowKind::Session),
+        ));
     }
 
     // Parse weekly window
     if let Some(weekly) = response.weekly {
-        snapshot.secondary = Some(parse_usage_window(weekly));
+        snapshot.set_secondary(Some(
+            parse_usage_window(weekly).with_id("weekly", WindowKind::Weekly),
+        ));
     }
 
     // Parse identity
@@ -127,8 +132,8 @@ pub fn parse_codex_cli_output(json_str: &str) -> Result<UsageSnapshot, FetchErro
     }
 
     debug!(
-        has_primary = snapshot.primary.is_some(),
-        has_secondary = snapshot.secondary.is_some(),
+        has_primary = snapshot.primary().is_some(),
+        has_secondary = snapshot.secondary().is_some(),
         has_identity = snapshot.identity.is_some(),
         "Codex CLI output parsed"
     );
@@ -194,14 +199,14 @@ mod tests {
 
         let snapshot = parse_codex_cli_output(json).unwrap();
 
-        assert!(snapshot.primary.is_some());
-        let primary = snapshot.primary.unwrap();
+        assert!(snapshot.primary().is_some());
+        let primary = snapshot.primary().unwrap();
         assert_eq!(primary.used_percent, 45.5);
         assert_eq!(primary.window_minutes, Some(300));
         assert_eq!(primary.reset_description, Some("in 2 hours".to_string()));
 
-        assert!(snapshot.secondary.is_some());
-        let secondary = snapshot.secondary.unwrap();
+        assert!(snapshot.secondary().is_some());
+        let secondary = snapshot.secondary().unwrap();
         assert_eq!(secondary.used_percent, 20.0);
 
         assert!(snapshot.identity.is_some());
@@ -214,8 +219,8 @@ mod tests {
         let json = r#"{}"#;
 
         let snapshot = parse_codex_cli_output(json).unwrap();
-        assert!(snapshot.primary.is_none());
-        assert!(snapshot.secondary.is_none());
+        assert!(snapshot.primary().is_none());
+        assert!(snapshot.secondary().is_none());
     }
 
     #[test]
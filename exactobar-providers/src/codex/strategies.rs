@@ -5,16 +5,19 @@
 //! 1. **RPC Strategy** - JSON-RPC to `codex app-server`
 //! 2. **PTY Strategy** - Interactive `/status` command
 //! 3. **CLI Strategy** - `codex usage --json`
-//! 4. **API Strategy** - OpenAI API with API key
+//! 4. **API Strategy** - OpenAI organization Usage/Costs API with an admin key
 
 use async_trait::async_trait;
-use exactobar_core::{FetchSource, UsageSnapshot};
+use chrono::Utc;
+use exactobar_core::ProviderKind;
 use exactobar_fetch::{
-    FetchContext, FetchError, FetchKind, FetchResult, FetchStrategy,
+    FetchContext, FetchError, FetchKind, FetchResult, FetchStrategy, SnapshotField,
     host::keychain::{accounts, services},
 };
 use tracing::{debug, instrument, warn};
 
+use super::admin::{ADMIN_API_BASE_URL, ADMIN_KEY_ENV, OpenAiAdminClient};
+use super::error::CodexError;
 use super::fetcher::CodexUsageFetcher;
 use super::parser::parse_codex_cli_output;
 use super::pty_probe::CodexPtyProbe;
@@ -74,6 +77,16 @@ impl FetchStrategy for CodexRpcStrategy {
     fn priority(&self) -> u32 {
         100 // Highest priority - RPC is most reliable
     }
+
+    fn authoritative_fields(&self) -> &[SnapshotField] {
+        // Rate limits, ChatGPT credits and the signed-in account; no tertiary window
+        &[
+            SnapshotField::Primary,
+            SnapshotField::Secondary,
+            SnapshotField::Credits,
+            SnapshotField::Identity,
+        ]
+    }
 }
 
 // ============================================================================
@@ -131,6 +144,11 @@ impl FetchStrategy for CodexPtyStrategy {
     fn priority(&self) -> u32 {
         90 // High priority - good fallback
     }
+
+    fn is_last_resort(&self) -> bool {
+        // Same data as RPC, from a slow interactive session
+        true
+    }
 }
 
 // ============================================================================
@@ -214,16 +232,22 @@ impl FetchStrategy for CodexCliStrategy {
     fn priority(&self) -> u32 {
         80 // Lower than RPC/PTY
     }
+
+    fn is_last_resort(&self) -> bool {
+        // Same data as RPC
+        true
+    }
 }
 
 // ============================================================================
 // API Strategy
 // ============================================================================
 
-/// Codex API strategy using OpenAI API with API key.
+/// Codex API strategy using the OpenAI organization Usage and Costs API.
 ///
-/// This strategy uses the OpenAI API directly with an API key
-/// stored in the system keychain or environment.
+/// These endpoints need an organization admin key (`sk-admin-...`). The key
+/// is read from the keychain (`openai_admin`), then `OPENAI_ADMIN_KEY`, and
+/// finally the regular OpenAI key if it happens to be an admin key.
 pub struct CodexApiStrategy {
     api_base: &'static str,
 }
@@ -232,11 +256,11 @@ impl CodexApiStrategy {
     /// Creates a new API strategy.
     pub fn new() -> Self {
         Self {
-            api_base: "https://api.openai.com/v1",
+            api_base: ADMIN_API_BASE_URL,
         }
     }
 
-    /// Gets the API key from keychain or environment.
+    /// Gets the regular API key from keychain or environment.
     async fn get_api_key(&self, ctx: &FetchContext) -> Option<String> {
         // Try keychain first
         if let Ok(Some(key)) = ctx.keychain.get(services::OPENAI, accounts::API_KEY).await {
@@ -246,6 +270,26 @@ impl CodexApiStrategy {
         // Fall back to environment
         std::env::var("OPENAI_API_KEY").ok()
     }
+
+    /// Gets an organization admin key.
+    async fn get_admin_key(&self, ctx: &FetchContext) -> Option<String> {
+        if let Some(key) =
+            exactobar_store::get_api_key(exactobar_store::keychain::providers::OPENAI_ADMIN)
+        {
+            return Some(key);
+        }
+
+        if let Some(key) = std::env::var(ADMIN_KEY_ENV)
+            .ok()
+            .filter(|key| !key.trim().is_empty())
+        {
+            return Some(key.trim().to_string());
+        }
+
+        self.get_api_key(ctx)
+            .await
+            .filter(|key| OpenAiAdminClient::is_admin_key(key))
+    }
 }
 
 impl Default for CodexApiStrategy {
@@ -266,43 +310,31 @@ impl FetchStrategy for CodexApiStrategy {
 
     #[instrument(skip(self, ctx))]
     async fn is_available(&self, ctx: &FetchContext) -> bool {
-        self.get_api_key(ctx).await.is_some()
+        self.get_admin_key(ctx).await.is_some()
     }
 
     #[instrument(skip(self, ctx))]
     async fn fetch(&self, ctx: &FetchContext) -> Result<FetchResult, FetchError> {
-        debug!("Fetching Codex usage via API");
-
-        let api_key = self.get_api_key(ctx).await.ok_or_else(|| {
-            FetchError::AuthenticationFailed("No OpenAI API key found".to_string())
-        })?;
+        debug!("Fetching Codex usage via organization API");
 
-        // OpenAI doesn't have a direct usage API endpoint that works with API keys
-        // in the same way - the usage endpoint requires organization-level access.
-        // For now, we return a placeholder indicating the API key is valid.
-        let url = format!("{}/models", self.api_base);
-        let auth_header = format!("Bearer {}", api_key);
-
-        let response = ctx
-            .http
-            .get_with_auth(&url, &auth_header)
-            .await
-            .map_err(|e| FetchError::InvalidResponse(e.to_string()))?;
-
-        if !response.status().is_success() {
-            return Err(FetchError::AuthenticationFailed(
-                "API key validation failed".to_string(),
+        let Some(admin_key) = self.get_admin_key(ctx).await else {
+            return Err(FetchError::StrategyNotAvailable(
+                "OpenAI usage and costs need an organization admin key (sk-admin-...)".to_string(),
             ));
-        }
+        };
 
-        // We can verify the key is valid but can't get usage from this endpoint
-        // Return minimal snapshot indicating connection works
-        let mut snapshot = UsageSnapshot::new();
-        snapshot.fetch_source = FetchSource::Api;
+        let budget = ctx.provider_options(ProviderKind::Codex).monthly_budget;
+        let now = Utc::now();
 
-        // Note: Real usage would require dashboard scraping or different auth
-        warn!("OpenAI API key validated but usage data requires dashboard access");
+        let usage = OpenAiAdminClient::with_base_url(self.api_base)
+            .fetch_organization_usage(&admin_key, now)
+            .await
+            .map_err(|e| match e {
+                CodexError::AuthenticationFailed(msg) => FetchError::AuthenticationFailed(msg),
+                other => FetchError::InvalidResponse(other.to_string()),
+            })?;
 
+        let snapshot = usage.to_snapshot(budget, now);
         Ok(FetchResult::new(snapshot, self.id(), self.kind()))
     }
 
@@ -314,6 +346,12 @@ impl FetchStrategy for CodexApiStrategy {
         // Don't fallback on auth errors - no point trying other strategies
         !matches!(error, FetchError::AuthenticationFailed(_))
     }
+
+    fn authoritative_fields(&self) -> &[SnapshotField] {
+        // Contributes the cost report; its monthly spend window only stands
+        // in for the primary window when the CLI sources have nothing
+        &[]
+    }
 }
 
 // ============================================================================
@@ -322,7 +360,12 @@ impl FetchStrategy for CodexApiStrategy {
 
 #[cfg(test)]
 mod tests {
+    use super::super::admin::OrganizationUsage;
     use super::*;
+    use crate::spend::MONTHLY_SPEND_WINDOW;
+    use exactobar_core::{UsageSnapshot, UsageWindow};
+    use exactobar_fetch::{FetchPipeline, PipelineMode};
+    use std::collections::HashMap;
 
     #[test]
     fn test_rpc_strategy_id() {
@@ -368,4 +411,73 @@ mod tests {
         assert!(pty > cli);
         assert!(cli > api);
     }
+
+    /// Returns a canned snapshot under a real strategy's ID and fields.
+    struct Canned<S> {
+        strategy: S,
+        snapshot: UsageSnapshot,
+    }
+
+    #[async_trait]
+    impl<S: FetchStrategy> FetchStrategy for Canned<S> {
+        fn id(&self) -> &str {
+            self.strategy.id()
+        }
+
+        fn kind(&self) -> FetchKind {
+            self.strategy.kind()
+        }
+
+        async fn is_available(&self, _ctx: &FetchContext) -> bool {
+            true
+        }
+
+        async fn fetch(&self, _ctx: &FetchContext) -> Result<FetchResult, FetchError> {
+            Ok(FetchResult::new(
+                self.snapshot.clone(),
+                self.id(),
+                self.kind(),
+            ))
+        }
+
+        fn authoritative_fields(&self) -> &[SnapshotField] {
+            self.strategy.authoritative_fields()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_merge_keeps_admin_spend_next_to_rpc() {
+        let mut rpc = UsageSnapshot::new();
+        rpc.set_primary(Some(UsageWindow::new(30.0)));
+        rpc.set_secondary(Some(UsageWindow::new(10.0)));
+
+        let admin = OrganizationUsage {
+            costs: Vec::new(),
+            usage: Vec::new(),
+            project_names: HashMap::new(),
+        }
+        .to_snapshot(Some(50.0), Utc::now());
+
+        let pipeline = FetchPipeline::with_strategies(vec![
+            Box::new(Canned {
+                strategy: CodexRpcStrategy::new(),
+                snapshot: rpc,
+            }),
+            Box::new(Canned {
+                strategy: CodexApiStrategy::new(),
+                snapshot: admin,
+            }),
+        ])
+        .with_mode(PipelineMode::Merge);
+
+        let merged = pipeline.execute(&FetchContext::new()).await.result.unwrap();
+        assert_eq!(merged.snapshot.primary().unwrap().used_percent, 30.0);
+        assert_eq!(merged.source_of(SnapshotField::Primary), Some("codex.rpc"));
+        assert!(merged.snapshot.window(MONTHLY_SPEND_WINDOW).is_some());
+        assert_eq!(
+            merged.window_source_of(MONTHLY_SPEND_WINDOW),
+            Some("codex.api")
+        );
+        assert!(merged.snapshot.cost.is_some());
+    }
 }
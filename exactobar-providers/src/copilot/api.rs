@@ -182,7 +182,7 @@ impl CopilotUsage {
             if let Some(rate) = usage.get_acceptance_rate() {
                 // Use acceptance rate as the "usage" metric
                 // Higher acceptance rate = better usage
-                snapshot.primary = Some(UsageWindow::new(rate));
+                snapshot.set_primary(Some(UsageWindow::new(rate)));
             }
         }
 
@@ -498,12 +498,12 @@ mod tests {
 
         let snapshot = usage.to_snapshot();
         assert!(snapshot.identity.is_some());
-        let identity = snapshot.identity.unwrap();
+        let identity = snapshot.identity.as_ref().unwrap();
         assert_eq!(identity.account_email, Some("test@example.com".to_string()));
         assert_eq!(identity.plan_name, Some("copilot_individual".to_string()));
 
         // Should have acceptance rate as primary usage
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.unwrap().used_percent, 20.0);
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 20.0);
     }
 }
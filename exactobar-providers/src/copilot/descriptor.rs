@@ -2,6 +2,7 @@
 
 use exactobar_core::{IconStyle, ProviderBranding, ProviderColor, ProviderKind, ProviderMetadata};
 use exactobar_fetch::{FetchContext, FetchPipeline, SourceMode};
+use std::collections::HashMap;
 
 use super::strategies::{CopilotApiStrategy, CopilotEnvStrategy};
 use crate::descriptor::{CliConfig, FetchPlan, ProviderDescriptor, TokenCostConfig};
@@ -25,6 +26,7 @@ fn copilot_metadata() -> ProviderMetadata {
         weekly_label: "Monthly".to_string(),
         opus_label: None,
         supports_opus: false,
+        window_labels: HashMap::new(),
         supports_credits: false,
         credits_hint: String::new(),
         toggle_title: "Show Copilot usage".to_string(),
@@ -50,7 +52,7 @@ fn copilot_branding() -> ProviderBranding {
 fn copilot_fetch_plan() -> FetchPlan {
     FetchPlan {
         source_modes: vec![SourceMode::OAuth, SourceMode::ApiKey],
-        build_pipeline: build_copilot_pipeline,
+        build_pipeline: Box::new(build_copilot_pipeline),
     }
 }
 
@@ -42,7 +42,7 @@ pub fn parse_copilot_response(json_str: &str) -> Result<UsageSnapshot, FetchErro
     if let Some(completions) = response.completions {
         // Use acceptance rate as a proxy for "usage"
         if let Some(rate) = completions.acceptance_rate {
-            snapshot.primary = Some(UsageWindow::new(rate * 100.0));
+            snapshot.set_primary(Some(UsageWindow::new(rate * 100.0)));
         }
     }
 
@@ -68,8 +68,8 @@ mod tests {
             "user": {"login": "octocat", "plan": "pro"}
         }"#;
         let snapshot = parse_copilot_response(json).unwrap();
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.unwrap().used_percent, 50.0);
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 50.0);
         assert!(snapshot.identity.is_some());
     }
 
@@ -77,6 +77,6 @@ mod tests {
     fn test_parse_empty() {
         let json = r#"{}"#;
         let snapshot = parse_copilot_response(json).unwrap();
-        assert!(snapshot.primary.is_none());
+        assert!(snapshot.primary().is_none());
     }
 }
@@ -2,6 +2,7 @@
 
 use exactobar_core::{IconStyle, ProviderBranding, ProviderColor, ProviderKind, ProviderMetadata};
 use exactobar_fetch::{FetchContext, FetchPipeline, SourceMode};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use super::strategies::{CursorLocalStrategy, CursorWebStrategy};
@@ -28,6 +29,7 @@ fn cursor_metadata() -> ProviderMetadata {
         weekly_label: "Monthly".to_string(),
         opus_label: None,
         supports_opus: false,
+        window_labels: HashMap::new(),
         supports_credits: true,
         credits_hint: "Pro plan credits".to_string(),
         toggle_title: "Show Cursor usage".to_string(),
@@ -63,7 +65,7 @@ fn cursor_token_cost() -> TokenCostConfig {
 fn cursor_fetch_plan() -> FetchPlan {
     FetchPlan {
         source_modes: vec![SourceMode::Web, SourceMode::Auto],
-        build_pipeline: build_cursor_pipeline,
+        build_pipeline: Box::new(build_cursor_pipeline),
     }
 }
 
@@ -234,11 +234,11 @@ impl CursorLocalReader {
             if let Ok(usage) = serde_json::from_str::<CursorLocalUsage>(usage_str) {
                 if usage.has_data() {
                     if let Some(percent) = usage.get_primary_percent() {
-                        snapshot.primary = Some(exactobar_core::UsageWindow::new(percent));
+                        snapshot.set_primary(Some(exactobar_core::UsageWindow::new(percent)));
                     }
 
                     if let Some(percent) = usage.get_secondary_percent() {
-                        snapshot.secondary = Some(exactobar_core::UsageWindow::new(percent));
+                        snapshot.set_secondary(Some(exactobar_core::UsageWindow::new(percent)));
                     }
                 }
             }
@@ -305,7 +305,7 @@ impl CursorLocalReader {
                 "cursorAuth/cachedUsage" => {
                     if let Ok(usage) = serde_json::from_str::<CursorLocalUsage>(&value) {
                         if let Some(percent) = usage.get_primary_percent() {
-                            snapshot.primary = Some(exactobar_core::UsageWindow::new(percent));
+                            snapshot.set_primary(Some(exactobar_core::UsageWindow::new(percent)));
                         }
                     }
                 }
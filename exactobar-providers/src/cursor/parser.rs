@@ -141,7 +141,7 @@ pub fn parse_cursor_api_response(json_str: &str) -> Result<UsageSnapshot, FetchE
                 }
             }
 
-            snapshot.primary = Some(window);
+            snapshot.set_primary(Some(window));
         }
 
         // Secondary window: premium requests
@@ -152,7 +152,7 @@ pub fn parse_cursor_api_response(json_str: &str) -> Result<UsageSnapshot, FetchE
             } else {
                 0.0
             };
-            snapshot.secondary = Some(UsageWindow::new(percent));
+            snapshot.set_secondary(Some(UsageWindow::new(percent)));
         }
     }
 
@@ -173,8 +173,8 @@ pub fn parse_cursor_api_response(json_str: &str) -> Result<UsageSnapshot, FetchE
     }
 
     debug!(
-        has_primary = snapshot.primary.is_some(),
-        has_secondary = snapshot.secondary.is_some(),
+        has_primary = snapshot.primary().is_some(),
+        has_secondary = snapshot.secondary().is_some(),
         "Cursor API response parsed"
     );
 
@@ -247,12 +247,12 @@ mod tests {
 
         let snapshot = parse_cursor_api_response(json).unwrap();
 
-        assert!(snapshot.primary.is_some());
-        let primary = snapshot.primary.unwrap();
+        assert!(snapshot.primary().is_some());
+        let primary = snapshot.primary().unwrap();
         assert_eq!(primary.used_percent, 30.0); // 150/500 * 100
 
-        assert!(snapshot.secondary.is_some());
-        let secondary = snapshot.secondary.unwrap();
+        assert!(snapshot.secondary().is_some());
+        let secondary = snapshot.secondary().unwrap();
         assert_eq!(secondary.used_percent, 20.0); // 10/50 * 100
 
         assert!(snapshot.identity.is_some());
@@ -265,7 +265,7 @@ mod tests {
     fn test_parse_cursor_api_minimal() {
         let json = r#"{}"#;
         let snapshot = parse_cursor_api_response(json).unwrap();
-        assert!(snapshot.primary.is_none());
+        assert!(snapshot.primary().is_none());
     }
 
     #[test]
@@ -4,7 +4,10 @@
 //! using browser cookies for authentication.
 
 use chrono::{DateTime, Utc};
-use exactobar_core::{LoginMethod, ProviderIdentity, ProviderKind, UsageSnapshot, UsageWindow};
+use exactobar_core::{
+    Amount, Credits, LoginMethod, ProviderIdentity, ProviderKind, SpendLimit, UsageSnapshot,
+    UsageWindow,
+};
 use reqwest::header::{ACCEPT, COOKIE, HeaderMap, HeaderValue, USER_AGENT};
 use serde::Deserialize;
 use tracing::{debug, instrument, warn};
@@ -79,6 +82,18 @@ pub struct CursorUsageResponse {
     #[serde(default, alias = "monthly_cost")]
     pub monthly_cost_usd: Option<f64>,
 
+    /// On-demand (usage-based) spend limit in USD.
+    #[serde(default, alias = "hard_limit", alias = "hardLimit")]
+    pub spend_limit_usd: Option<f64>,
+
+    /// Whether on-demand usage beyond the plan is enabled.
+    #[serde(
+        default,
+        alias = "usage_based_enabled",
+        alias = "usageBasedPricingEnabled"
+    )]
+    pub on_demand_enabled: Option<bool>,
+
     /// User's plan.
     #[serde(default)]
     pub plan: Option<String>,
@@ -140,6 +155,18 @@ impl CursorUsageResponse {
         None
     }
 
+    /// Get on-demand spend as credits, if reported.
+    pub fn get_on_demand_spend(&self) -> Option<Credits> {
+        let used = self.monthly_cost_usd?;
+
+        let mut spend = SpendLimit::new(Amount::usd(used));
+        spend.cap = self.spend_limit_usd.map(Amount::usd);
+        spend.resets_at = self.get_reset_time();
+        spend.overage_enabled = self.on_demand_enabled.unwrap_or(spend.cap.is_some());
+
+        Some(Credits::from_spend(spend))
+    }
+
     /// Convert to UsageSnapshot.
     pub fn to_snapshot(&self) -> UsageSnapshot {
         let mut snapshot = UsageSnapshot::new();
@@ -150,14 +177,17 @@ impl CursorUsageResponse {
             let mut window = UsageWindow::new(percent);
             window.resets_at = self.get_reset_time();
 
-            snapshot.primary = Some(window);
+            snapshot.set_primary(Some(window));
         }
 
         // Secondary usage window (slow requests)
         if let Some(percent) = self.get_secondary_percent() {
-            snapshot.secondary = Some(UsageWindow::new(percent));
+            snapshot.set_secondary(Some(UsageWindow::new(percent)));
         }
 
+        // On-demand spend
+        snapshot.credits = self.get_on_demand_spend();
+
         // Identity
         if self.email.is_some() || self.plan.is_some() {
             let mut identity = ProviderIdentity::new(ProviderKind::Cursor);
@@ -371,6 +401,33 @@ mod tests {
         assert_eq!(response.get_secondary_percent(), Some(25.0));
     }
 
+    #[test]
+    fn test_on_demand_spend() {
+        let json = r#"{
+            "monthlyCostUsd": 12.5,
+            "hardLimit": 50.0,
+            "periodEnd": "2025-02-01"
+        }"#;
+
+        let response: CursorUsageResponse = serde_json::from_str(json).unwrap();
+        let credits = response.to_snapshot().credits.unwrap();
+        assert_eq!(credits.unit, "USD");
+        assert_eq!(credits.remaining, 37.5);
+
+        let spend = credits.spend_limit.unwrap();
+        assert_eq!(spend.used, Amount::usd(12.5));
+        assert_eq!(spend.used_percent(), Some(25.0));
+        assert!(spend.overage_enabled);
+        assert!(spend.resets_at.is_some());
+
+        // Uncapped spend reports the spend, not an empty balance
+        let json = r#"{"monthlyCostUsd": 12.5, "onDemandEnabled": true}"#;
+        let response: CursorUsageResponse = serde_json::from_str(json).unwrap();
+        let credits = response.to_snapshot().credits.unwrap();
+        assert_eq!(credits.remaining, 12.5);
+        assert!(credits.total.is_none());
+    }
+
     #[test]
     fn test_parse_usage_response_alt_names() {
         let json = r#"{
@@ -399,19 +456,21 @@ mod tests {
             period_start: None,
             period_end: Some("2025-02-01T00:00:00Z".to_string()),
             monthly_cost_usd: None,
+            spend_limit_usd: None,
+            on_demand_enabled: None,
             plan: Some("pro".to_string()),
             email: Some("user@example.com".to_string()),
         };
 
         let snapshot = response.to_snapshot();
 
-        assert!(snapshot.primary.is_some());
-        let primary = snapshot.primary.unwrap();
+        assert!(snapshot.primary().is_some());
+        let primary = snapshot.primary().unwrap();
         assert_eq!(primary.used_percent, 20.0);
         assert!(primary.resets_at.is_some());
 
-        assert!(snapshot.secondary.is_some());
-        let secondary = snapshot.secondary.unwrap();
+        assert!(snapshot.secondary().is_some());
+        let secondary = snapshot.secondary().unwrap();
         assert_eq!(secondary.used_percent, 25.0);
 
         assert!(snapshot.identity.is_some());
@@ -432,6 +491,8 @@ mod tests {
             period_start: None,
             period_end: Some("2025-02-01T00:00:00Z".to_string()),
             monthly_cost_usd: None,
+            spend_limit_usd: None,
+            on_demand_enabled: None,
             plan: None,
             email: None,
         };
@@ -0,0 +1,472 @@
+//! Custom provider definition and HTTP client.
+//!
+//! Command providers are run by the [`command`](super::command) module.
+
+use chrono::{DateTime, Utc};
+use exactobar_core::{Credits, FetchSource, ProviderKind, UsageSnapshot, UsageWindow, WindowKind};
+use exactobar_fetch::FetchContext;
+use exactobar_fetch::host::browser::{Browser, BrowserCookieImporter};
+use exactobar_store::{CustomAuth, CustomProviderConfig};
+use serde_json::Value;
+use tracing::{debug, instrument};
+
+use super::command;
+use super::error::CustomProviderError;
+use super::json_path::JsonPath;
+
+/// Window ID for the mapped usage.
+pub const USAGE_WINDOW_ID: &str = "usage";
+
+// ============================================================================
+// Definition
+// ============================================================================
+
+/// Compiled field paths.
+#[derive(Debug, Clone, Default)]
+struct FieldPaths {
+    used: Option<JsonPath>,
+    limit: Option<JsonPath>,
+    percent: Option<JsonPath>,
+    resets_at: Option<JsonPath>,
+    credits: Option<JsonPath>,
+}
+
+/// A validated custom provider, ready to fetch.
+#[derive(Debug, Clone)]
+pub struct CustomProvider {
+    kind: ProviderKind,
+    config: CustomProviderConfig,
+    paths: FieldPaths,
+}
+
+impl CustomProvider {
+    /// Validates a definition and compiles its field paths.
+    pub fn new(config: CustomProviderConfig) -> Result<Self, CustomProviderError> {
+        config
+            .validate()
+            .map_err(|e| CustomProviderError::InvalidConfig(e.to_string()))?;
+        let kind = config
+            .kind()
+            .map_err(|e| CustomProviderError::InvalidConfig(e.to_string()))?;
+
+        let compile = |path: &Option<String>| path.as_deref().map(JsonPath::parse).transpose();
+        let fields = &config.fields;
+        let paths = FieldPaths {
+            used: compile(&fields.used)?,
+            limit: compile(&fields.limit)?,
+            percent: compile(&fields.percent)?,
+            resets_at: compile(&fields.resets_at)?,
+            credits: compile(&fields.credits)?,
+        };
+
+        Ok(Self {
+            kind,
+            config,
+            paths,
+        })
+    }
+
+    /// Returns the provider kind.
+    pub fn kind(&self) -> ProviderKind {
+        self.kind
+    }
+
+    /// Returns the definition.
+    pub fn config(&self) -> &CustomProviderConfig {
+        &self.config
+    }
+
+    /// Returns true if the provider runs a command instead of a request.
+    pub fn is_command(&self) -> bool {
+        self.config.is_command()
+    }
+
+    /// Returns true if requests authenticate with browser cookies.
+    pub fn uses_cookies(&self) -> bool {
+        matches!(self.config.auth, CustomAuth::Cookie { .. })
+    }
+
+    /// Reads the configured secret from the environment, then the keychain.
+    ///
+    /// Returns `Ok(None)` for auth modes without a secret.
+    pub fn secret(&self) -> Result<Option<String>, CustomProviderError> {
+        let (env, keychain) = match &self.config.auth {
+            CustomAuth::Bearer { env, keychain } | CustomAuth::Header { env, keychain, .. } => {
+                (env, keychain)
+            }
+            CustomAuth::None | CustomAuth::Cookie { .. } => return Ok(None),
+        };
+
+        if let Some(value) = env
+            .as_deref()
+            .and_then(|var| std::env::var(var).ok())
+            .filter(|v| !v.trim().is_empty())
+        {
+            return Ok(Some(value.trim().to_string()));
+        }
+
+        let account = keychain.as_deref().unwrap_or(&self.config.id);
+        if let Some(value) = exactobar_store::get_api_key(account) {
+            return Ok(Some(value));
+        }
+
+        let mut sources = Vec::new();
+        if let Some(var) = env {
+            sources.push(format!("${var}"));
+        }
+        sources.push(format!("keychain '{account}'"));
+        Err(CustomProviderError::SecretNotFound(sources.join(" or ")))
+    }
+
+    /// Builds the request headers, including auth.
+    async fn headers(
+        &self,
+        ctx: &FetchContext,
+    ) -> Result<Vec<(String, String)>, CustomProviderError> {
+        let mut headers: Vec<(String, String)> = self
+            .config
+            .headers
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+
+        match &self.config.auth {
+            CustomAuth::None => {}
+            CustomAuth::Bearer { .. } => {
+                let token = self.secret()?.unwrap_or_default();
+                headers.push(("Authorization".to_string(), format!("Bearer {token}")));
+            }
+            CustomAuth::Header { name, .. } => {
+                let value = self.secret()?.unwrap_or_default();
+                headers.push((name.clone(), value));
+            }
+            CustomAuth::Cookie { domain } => {
+                let (browser, cookies) = ctx
+                    .browser
+                    .import_cookies_auto(domain, Browser::default_priority())
+                    .await
+                    .map_err(|e| CustomProviderError::AuthenticationFailed(e.to_string()))?;
+                debug!(browser = %browser.display_name(), "Using browser cookies");
+                headers.push((
+                    "Cookie".to_string(),
+                    BrowserCookieImporter::cookies_to_header(&cookies),
+                ));
+            }
+        }
+
+        Ok(headers)
+    }
+
+    /// Fetches the endpoint and maps the response, or runs the command.
+    #[instrument(skip(self, ctx), fields(provider = %self.config.id))]
+    pub async fn fetch(
+        &self,
+        ctx: &FetchContext,
+        now: DateTime<Utc>,
+    ) -> Result<UsageSnapshot, CustomProviderError> {
+        if let Some(cmd) = &self.config.command {
+            return command::run(self, cmd, ctx, now).await;
+        }
+
+        let headers = self.headers(ctx).await?;
+
+        debug!(url = %self.config.url, "Fetching custom provider usage");
+        let mut request = ctx
+            .http
+            .inner()
+            .get(&self.config.url)
+            .header("Accept", "application/json")
+            .timeout(ctx.timeout());
+        for (name, value) in &headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        let response = request.send().await?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+            return Err(CustomProviderError::AuthenticationFailed(format!(
+                "HTTP {status}"
+            )));
+        }
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(CustomProviderError::ApiError(format!(
+                "HTTP {status}: {}",
+                text.chars().take(200).collect::<String>()
+            )));
+        }
+
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| CustomProviderError::ParseError(e.to_string()))?;
+        self.to_snapshot(&body, now)
+    }
+
+    /// Maps a response body to a snapshot.
+    ///
+    /// `used`, `percent` and `credits` must be present when mapped; `limit`
+    /// and `resets_at` are optional, since many APIs send null for
+    /// "unlimited" or "never".
+    pub fn to_snapshot(
+        &self,
+        body: &Value,
+        now: DateTime<Utc>,
+    ) -> Result<UsageSnapshot, CustomProviderError> {
+        let paths = &self.paths;
+        let used = required_number(body, paths.used.as_ref())?;
+        let percent = required_number(body, paths.percent.as_ref())?;
+        let limit = paths
+            .limit
+            .as_ref()
+            .and_then(|p| p.select(body))
+            .and_then(as_number)
+            .filter(|l| *l > 0.0);
+        let resets_at = paths
+            .resets_at
+            .as_ref()
+            .and_then(|p| p.select(body))
+            .and_then(as_timestamp);
+
+        let mut snapshot = UsageSnapshot::new();
+        snapshot.fetch_source = if self.uses_cookies() {
+            FetchSource::Web
+        } else {
+            FetchSource::Api
+        };
+        snapshot.updated_at = now;
+
+        if used.is_some() || percent.is_some() {
+            let used_percent = percent
+                .or_else(|| used.zip(limit).map(|(u, l)| u / l * 100.0))
+                .unwrap_or(0.0);
+            let label = self.config.window_label.as_deref().unwrap_or("Usage");
+            let mut window = UsageWindow::named(USAGE_WINDOW_ID, WindowKind::Other, used_percent)
+                .with_label(label);
+            if let Some(used) = used {
+                let unit = self.config.unit.as_deref().unwrap_or("units");
+                window = window.with_counts(used, limit, unit);
+            }
+            window.resets_at = resets_at;
+            snapshot.set_primary(Some(window));
+        }
+
+        if let Some(remaining) = required_number(body, paths.credits.as_ref())? {
+            let mut credits = Credits::new(remaining);
+            if let Some(unit) = &self.config.unit {
+                credits.unit.clone_from(unit);
+            }
+            snapshot.credits = Some(credits);
+        }
+
+        snapshot.sanitize();
+        Ok(snapshot)
+    }
+}
+
+// ============================================================================
+// Value Helpers
+// ============================================================================
+
+/// Selects a number, failing if the path is mapped but has no number.
+fn required_number(
+    body: &Value,
+    path: Option<&JsonPath>,
+) -> Result<Option<f64>, CustomProviderError> {
+    let Some(path) = path else {
+        return Ok(None);
+    };
+    path.select(body)
+        .and_then(as_number)
+        .map(Some)
+        .ok_or_else(|| CustomProviderError::MissingField(path.to_string()))
+}
+
+/// Reads a number or numeric string.
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+    .filter(|n: &f64| n.is_finite())
+}
+
+/// Reads an RFC 3339 string or Unix seconds/milliseconds.
+fn as_timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    if let Some(s) = value.as_str() {
+        if let Ok(dt) = DateTime::parse_from_rfc3339(s.trim()) {
+            return Some(dt.with_timezone(&Utc));
+        }
+    }
+    let epoch = as_number(value)?;
+    // Anything past year 5138 in seconds is milliseconds
+    #[allow(clippy::cast_possible_truncation)]
+    let millis = if epoch > 1e11 { epoch } else { epoch * 1000.0 } as i64;
+    DateTime::from_timestamp_millis(millis)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::serve_stub;
+    use exactobar_store::CustomFieldPaths;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    fn config(fields: CustomFieldPaths) -> CustomProviderConfig {
+        CustomProviderConfig {
+            id: "apitest".to_string(),
+            name: "API Test".to_string(),
+            color: None,
+            url: "https://gateway.example.com/usage".to_string(),
+            command: None,
+            auth: CustomAuth::None,
+            headers: BTreeMap::new(),
+            fields,
+            window_label: Some("Daily requests".to_string()),
+            unit: Some("requests".to_string()),
+            dashboard_url: None,
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-01-15T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_snapshot_from_used_and_limit() {
+        let provider = CustomProvider::new(config(CustomFieldPaths {
+            used: Some("$.usage.used".to_string()),
+            limit: Some("$.usage.limit".to_string()),
+            resets_at: Some("$.usage.resets".to_string()),
+            ..Default::default()
+        }))
+        .unwrap();
+        let body = json!({"usage": {"used": "250", "limit": 1000, "resets": 1_736_985_600}});
+
+        let snapshot = provider.to_snapshot(&body, now()).unwrap();
+        let window = snapshot.primary().unwrap();
+        assert_eq!(window.id, USAGE_WINDOW_ID);
+        assert_eq!(window.label.as_deref(), Some("Daily requests"));
+        assert!((window.used_percent - 25.0).abs() < 0.001);
+        assert_eq!(window.limit, Some(1000.0));
+        assert_eq!(window.unit.as_deref(), Some("requests"));
+        assert_eq!(
+            window.resets_at.unwrap().to_rfc3339(),
+            "2025-01-16T00:00:00+00:00"
+        );
+        assert_eq!(snapshot.fetch_source, FetchSource::Api);
+        assert!(snapshot.credits.is_none());
+    }
+
+    #[test]
+    fn test_snapshot_percent_and_credits() {
+        let provider = CustomProvider::new(config(CustomFieldPaths {
+            percent: Some("data.pct".to_string()),
+            limit: Some("data.limit".to_string()),
+            resets_at: Some("data.reset".to_string()),
+            credits: Some("data.balance".to_string()),
+            ..Default::default()
+        }))
+        .unwrap();
+        let body = json!({"data": {
+            "pct": 80.5,
+            "limit": null,
+            "reset": "2025-01-20T00:00:00Z",
+            "balance": 12.5
+        }});
+
+        let snapshot = provider.to_snapshot(&body, now()).unwrap();
+        let window = snapshot.primary().unwrap();
+        assert!((window.used_percent - 80.5).abs() < 0.001);
+        assert!(window.used.is_none());
+        assert!(window.resets_at.is_some());
+        let credits = snapshot.credits.unwrap();
+        assert!((credits.remaining - 12.5).abs() < 0.001);
+        assert_eq!(credits.unit, "requests");
+    }
+
+    #[test]
+    fn test_snapshot_missing_field() {
+        let provider = CustomProvider::new(config(CustomFieldPaths {
+            percent: Some("$.pct".to_string()),
+            ..Default::default()
+        }))
+        .unwrap();
+        let err = provider
+            .to_snapshot(&json!({"other": 1}), now())
+            .unwrap_err();
+        assert!(matches!(err, CustomProviderError::MissingField(path) if path == "$.pct"));
+    }
+
+    #[test]
+    fn test_invalid_definition() {
+        let err = CustomProvider::new(config(CustomFieldPaths {
+            used: Some("$..used".to_string()),
+            ..Default::default()
+        }))
+        .unwrap_err();
+        assert!(matches!(err, CustomProviderError::InvalidConfig(_)));
+    }
+
+    #[test]
+    fn test_timestamp_formats() {
+        let secs = as_timestamp(&json!(1_736_985_600)).unwrap();
+        let millis = as_timestamp(&json!(1_736_985_600_000_i64)).unwrap();
+        assert_eq!(secs, millis);
+        assert_eq!(as_timestamp(&json!("1736985600")), Some(secs));
+        assert!(as_timestamp(&json!("soon")).is_none());
+    }
+
+    /// Answers with `body` only when the configured header arrived.
+    async fn serve_checked(status: u16, body: &'static str) -> String {
+        serve_stub(move |request| {
+            let body = if request.header("x-api-key") == Some("sekrit") {
+                body
+            } else {
+                "{}"
+            };
+            (status, body)
+        })
+        .await
+    }
+
+    fn header_config(url: String) -> CustomProviderConfig {
+        let mut cfg = config(CustomFieldPaths {
+            used: Some("$.used".to_string()),
+            limit: Some("$.limit".to_string()),
+            ..Default::default()
+        });
+        cfg.url = url;
+        cfg.headers = BTreeMap::from([("X-Api-Key".to_string(), "sekrit".to_string())]);
+        cfg
+    }
+
+    #[tokio::test]
+    async fn test_fetch_against_stub() {
+        let url = serve_checked(200, r#"{"used": 5, "limit": 10}"#).await;
+        let provider = CustomProvider::new(header_config(url)).unwrap();
+
+        let snapshot = provider.fetch(&FetchContext::new(), now()).await.unwrap();
+        assert!((snapshot.primary().unwrap().used_percent - 50.0).abs() < 0.001);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_auth_failure() {
+        let url = serve_checked(401, "{}").await;
+        let provider = CustomProvider::new(header_config(url)).unwrap();
+
+        let err = provider
+            .fetch(&FetchContext::new(), now())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CustomProviderError::AuthenticationFailed(_)));
+    }
+}
@@ -0,0 +1,390 @@
+//! Command-backed custom providers.
+//!
+//! The command is run without a shell through the fetch context's
+//! [`ProcessRunner`](exactobar_fetch::host::process::ProcessRunner), with
+//! stdin closed and the configured timeout (or the fetch timeout).
+//!
+//! ## Protocol (version 1)
+//!
+//! The command prints a `UsageSnapshot` as JSON on stdout, a JSON array of
+//! them, or one per line. `updated_at` (on the snapshot and its credits)
+//! defaults to now, and `identity.provider_id` is always the custom
+//! provider. Several snapshots merge in order: later windows replace earlier
+//! ones with the same ID or slot, and later credits, cost and identity win.
+//!
+//! ```json
+//! {
+//!   "windows": [
+//!     {"id": "daily", "label": "Daily", "used_percent": 42.0,
+//!      "used": 420, "limit": 1000, "unit": "requests",
+//!      "window_minutes": 1440, "resets_at": "2025-01-16T00:00:00Z"}
+//!   ],
+//!   "credits": {"remaining": 12.5, "unit": "USD"}
+//! }
+//! ```
+//!
+//! Invalid values are logged and clamped rather than rejected. Exit status
+//! 77 (`EX_NOPERM`) reports missing or expired credentials; any other
+//! non-zero status is a failure, with stderr as the message.
+//!
+//! The environment is inherited, plus the configured variables and:
+//!
+//! - `EXACTOBAR_PROVIDER`: the provider ID
+//! - `EXACTOBAR_PROTOCOL`: the protocol version
+//! - `EXACTOBAR_TIMEOUT_SECS`: seconds before the command is killed
+//! - `EXACTOBAR_SECRET`: the configured bearer/header secret, if any
+
+use chrono::{DateTime, Utc};
+use exactobar_core::{FetchSource, ProviderKind, UsageSnapshot};
+use exactobar_fetch::FetchContext;
+use exactobar_fetch::error::ProcessError;
+use exactobar_store::{CustomAuth, CustomCommand};
+use serde_json::Value;
+use std::time::Duration;
+use tracing::{debug, instrument, warn};
+
+use super::api::{CustomProvider, USAGE_WINDOW_ID};
+use super::error::CustomProviderError;
+
+/// Output protocol version, passed as `EXACTOBAR_PROTOCOL`.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Exit status a command uses to report missing or expired credentials.
+pub const EXIT_AUTH_REQUIRED: i32 = 77;
+
+// ============================================================================
+// Execution
+// ============================================================================
+
+/// Resolves the program path, expanding a leading `~/`.
+pub fn program_path(command: &CustomCommand) -> String {
+    let program = command.program.trim();
+    match (program.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).to_string_lossy().into_owned(),
+        _ => program.to_string(),
+    }
+}
+
+/// Runs the command and parses its snapshots.
+#[instrument(skip(provider, command, ctx), fields(provider = %provider.config().id))]
+pub(crate) async fn run(
+    provider: &CustomProvider,
+    command: &CustomCommand,
+    ctx: &FetchContext,
+    now: DateTime<Utc>,
+) -> Result<UsageSnapshot, CustomProviderError> {
+    let program = program_path(command);
+    let timeout = command
+        .timeout_secs
+        .map_or_else(|| ctx.timeout(), Duration::from_secs);
+
+    let secret = match provider.config().auth {
+        CustomAuth::None => None,
+        _ => provider.secret()?,
+    };
+    let protocol = PROTOCOL_VERSION.to_string();
+    let timeout_secs = timeout.as_secs().max(1).to_string();
+
+    // Later entries win, so the protocol variables can't be overridden
+    let mut env: Vec<(&str, &str)> = command
+        .env
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect();
+    env.push(("EXACTOBAR_PROVIDER", &provider.config().id));
+    env.push(("EXACTOBAR_PROTOCOL", &protocol));
+    env.push(("EXACTOBAR_TIMEOUT_SECS", &timeout_secs));
+    if let Some(secret) = &secret {
+        env.push(("EXACTOBAR_SECRET", secret));
+    }
+
+    let args: Vec<&str> = command.args.iter().map(String::as_str).collect();
+    debug!(program = %program, "Running custom provider command");
+    let output = ctx
+        .process
+        .run_with_options(&program, &args, &env, Some(timeout))
+        .await?;
+
+    if !output.success() {
+        let stderr = output.stderr.trim().chars().take(200).collect::<String>();
+        if output.exit_code == EXIT_AUTH_REQUIRED {
+            return Err(CustomProviderError::AuthenticationFailed(stderr));
+        }
+        return Err(ProcessError::NonZeroExit {
+            code: output.exit_code,
+            stderr,
+        }
+        .into());
+    }
+
+    parse_output(&output.stdout, provider.kind(), now)
+}
+
+// ============================================================================
+// Output Parsing
+// ============================================================================
+
+/// Parses command output into one merged snapshot.
+///
+/// # Errors
+///
+/// Returns `ParseError` if the output is not snapshot JSON or has no usage
+/// data.
+pub fn parse_output(
+    stdout: &str,
+    kind: ProviderKind,
+    now: DateTime<Utc>,
+) -> Result<UsageSnapshot, CustomProviderError> {
+    let text = stdout.trim();
+    if text.is_empty() {
+        return Err(CustomProviderError::ParseError(
+            "command printed nothing".to_string(),
+        ));
+    }
+
+    let values = match serde_json::from_str::<Value>(text) {
+        Ok(Value::Array(items)) => items,
+        Ok(value) => vec![value],
+        // Not a single document: try one snapshot per line
+        Err(_) => text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(n, line)| {
+                serde_json::from_str(line)
+                    .map_err(|e| CustomProviderError::ParseError(format!("line {}: {e}", n + 1)))
+            })
+            .collect::<Result<_, _>>()?,
+    };
+
+    let mut merged: Option<UsageSnapshot> = None;
+    for value in values {
+        let snapshot = snapshot_from_value(value, kind, now)?;
+        match merged.as_mut() {
+            Some(merged) => merge(merged, snapshot),
+            None => merged = Some(snapshot),
+        }
+    }
+
+    let mut snapshot = merged.ok_or_else(|| {
+        CustomProviderError::ParseError("command printed an empty array".to_string())
+    })?;
+    snapshot.fetch_source = FetchSource::CLI;
+
+    if let Err(e) = snapshot.validate() {
+        warn!(error = %e, "Command printed invalid usage, clamping");
+    }
+    snapshot.sanitize();
+
+    if !snapshot.has_data() {
+        return Err(CustomProviderError::ParseError(
+            "snapshot has no windows, credits or cost".to_string(),
+        ));
+    }
+    Ok(snapshot)
+}
+
+/// Deserializes one snapshot, filling in protocol defaults.
+fn snapshot_from_value(
+    mut value: Value,
+    kind: ProviderKind,
+    now: DateTime<Utc>,
+) -> Result<UsageSnapshot, CustomProviderError> {
+    let Some(object) = value.as_object_mut() else {
+        return Err(CustomProviderError::ParseError(
+            "expected a snapshot object".to_string(),
+        ));
+    };
+    let stamp = Value::String(now.to_rfc3339());
+    object.entry("updated_at").or_insert_with(|| stamp.clone());
+    if let Some(credits) = object.get_mut("credits").and_then(Value::as_object_mut) {
+        credits.entry("updated_at").or_insert(stamp);
+    }
+    if let Some(identity) = object.get_mut("identity").and_then(Value::as_object_mut) {
+        // Commands can't speak for other providers
+        identity.insert("provider_id".to_string(), Value::String(kind.key()));
+    }
+
+    let mut snapshot: UsageSnapshot = serde_json::from_value(value)
+        .map_err(|e| CustomProviderError::ParseError(e.to_string()))?;
+
+    let mut unnamed = 0;
+    for window in snapshot.windows.iter_mut().filter(|w| w.id.is_empty()) {
+        unnamed += 1;
+        window.id = if unnamed == 1 {
+            USAGE_WINDOW_ID.to_string()
+        } else {
+            format!("{USAGE_WINDOW_ID}-{unnamed}")
+        };
+    }
+    Ok(snapshot)
+}
+
+/// Merges a later snapshot into an earlier one.
+fn merge(into: &mut UsageSnapshot, from: UsageSnapshot) {
+    for window in from.windows {
+        match window.slot {
+            Some(slot) => into.set_slot(slot, Some(window)),
+            None => into.push_window(window),
+        }
+    }
+    into.updated_at = into.updated_at.max(from.updated_at);
+    if from.identity.is_some() {
+        into.identity = from.identity;
+    }
+    if from.credits.is_some() {
+        into.credits = from.credits;
+    }
+    if from.cost.is_some() {
+        into.cost = from.cost;
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exactobar_core::{CustomProviderId, WindowSlot};
+    use exactobar_store::CustomProviderConfig;
+
+    fn kind() -> ProviderKind {
+        ProviderKind::Custom(CustomProviderId::register("cmdtest").unwrap())
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-01-15T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_single_snapshot() {
+        let out = r#"{"windows": [{"used_percent": 42.0, "used": 420, "limit": 1000}],
+                      "identity": {"plan_name": "Team"}}"#;
+        let snapshot = parse_output(out, kind(), now()).unwrap();
+
+        let window = snapshot.window(USAGE_WINDOW_ID).unwrap();
+        assert!((window.used_percent - 42.0).abs() < 0.001);
+        assert_eq!(snapshot.updated_at, now());
+        assert_eq!(snapshot.fetch_source, FetchSource::CLI);
+        let identity = snapshot.identity.unwrap();
+        assert_eq!(identity.provider_id, kind());
+        assert_eq!(identity.plan_name.as_deref(), Some("Team"));
+    }
+
+    #[test]
+    fn test_parse_merges_lines() {
+        let out = concat!(
+            r#"{"primary": {"used_percent": 10.0, "window_minutes": 300}}"#,
+            "\n\n",
+            r#"{"windows": [{"id": "gpu", "used_percent": 5.0}], "credits": {"remaining": 3.0}}"#,
+            "\n",
+            r#"{"primary": {"used_percent": 20.0, "window_minutes": 300}}"#,
+        );
+        let snapshot = parse_output(out, kind(), now()).unwrap();
+
+        assert_eq!(snapshot.windows.len(), 2);
+        let primary = snapshot.slot(WindowSlot::Primary).unwrap();
+        assert!((primary.used_percent - 20.0).abs() < 0.001);
+        assert!(snapshot.window("gpu").is_some());
+        assert!((snapshot.credits.unwrap().remaining - 3.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_parse_array_and_clamping() {
+        let out = r#"[{"windows": [{"used_percent": 140.0}, {"used_percent": -3.0}]}]"#;
+        let snapshot = parse_output(out, kind(), now()).unwrap();
+
+        assert_eq!(snapshot.windows[0].id, USAGE_WINDOW_ID);
+        assert_eq!(snapshot.windows[1].id, "usage-2");
+        assert!((snapshot.windows[0].used_percent - 100.0).abs() < 0.001);
+        assert!(snapshot.windows[1].used_percent.abs() < 0.001);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for out in ["", "[]", "not json", "{\"windows\": []}", "42"] {
+            let err = parse_output(out, kind(), now()).unwrap_err();
+            assert!(matches!(err, CustomProviderError::ParseError(_)), "{out}");
+        }
+    }
+
+    #[test]
+    fn test_program_path() {
+        let command = CustomCommand {
+            program: "~/bin/quota".to_string(),
+            ..Default::default()
+        };
+        let path = program_path(&command);
+        assert!(!path.starts_with('~'));
+        assert!(path.ends_with("bin/quota"));
+    }
+
+    fn provider(program: &str, script: &str) -> CustomProvider {
+        CustomProvider::new(CustomProviderConfig {
+            id: "cmdtest".to_string(),
+            name: "Command Test".to_string(),
+            color: None,
+            url: String::new(),
+            command: Some(CustomCommand {
+                program: program.to_string(),
+                args: vec!["-c".to_string(), script.to_string()],
+                env: [("QUOTA_USED".to_string(), "30".to_string())].into(),
+                timeout_secs: Some(5),
+            }),
+            auth: CustomAuth::None,
+            headers: std::collections::BTreeMap::new(),
+            fields: exactobar_store::CustomFieldPaths::default(),
+            window_label: None,
+            unit: None,
+            dashboard_url: None,
+        })
+        .unwrap()
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_command_with_env() {
+        let provider = provider(
+            "sh",
+            r#"echo "{\"windows\": [{\"id\": \"$EXACTOBAR_PROVIDER\", \"used_percent\": $QUOTA_USED}]}""#,
+        );
+        let snapshot = provider.fetch(&FetchContext::new(), now()).await.unwrap();
+
+        let window = snapshot.window("cmdtest").unwrap();
+        assert!((window.used_percent - 30.0).abs() < 0.001);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_command_failures() {
+        let ctx = FetchContext::new();
+
+        let err = provider("sh", "echo expired >&2; exit 77")
+            .fetch(&ctx, now())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CustomProviderError::AuthenticationFailed(msg) if msg == "expired"));
+
+        let err = provider("sh", "exit 2")
+            .fetch(&ctx, now())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            CustomProviderError::Process(ProcessError::NonZeroExit { code: 2, .. })
+        ));
+
+        let err = provider("exactobar-no-such-program", "")
+            .fetch(&ctx, now())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            CustomProviderError::Process(ProcessError::NotFound(_))
+        ));
+    }
+}
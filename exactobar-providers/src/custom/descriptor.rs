@@ -0,0 +1,177 @@
+//! Custom provider descriptors.
+
+use exactobar_core::{IconStyle, ProviderBranding, ProviderMetadata};
+use exactobar_fetch::{FetchContext, FetchPipeline, SourceMode};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::api::{CustomProvider, USAGE_WINDOW_ID};
+use super::strategies::{CustomCommandStrategy, CustomHttpStrategy};
+use crate::descriptor::{CliConfig, FetchPlan, ProviderDescriptor, TokenCostConfig};
+
+// ============================================================================
+// Descriptor
+// ============================================================================
+
+/// Creates the descriptor for a user-defined provider.
+pub fn custom_descriptor(provider: &Arc<CustomProvider>) -> ProviderDescriptor {
+    let kind = provider.kind();
+    ProviderDescriptor {
+        id: kind,
+        metadata: custom_metadata(provider),
+        branding: custom_branding(provider),
+        token_cost: TokenCostConfig::default(),
+        fetch_plan: custom_fetch_plan(provider),
+        cli: CliConfig {
+            name: kind.cli_name(),
+            aliases: &[],
+            version_args: &[],
+            usage_args: &[],
+        },
+    }
+}
+
+// ============================================================================
+// Metadata
+// ============================================================================
+
+fn custom_metadata(provider: &CustomProvider) -> ProviderMetadata {
+    let config = provider.config();
+    let label = config.window_label.as_deref().unwrap_or("Usage");
+
+    ProviderMetadata {
+        session_label: label.to_string(),
+        window_labels: HashMap::from([(USAGE_WINDOW_ID.to_string(), label.to_string())]),
+        supports_credits: config.is_command() || config.fields.credits.is_some(),
+        default_enabled: false,
+        dashboard_url: config.dashboard_url.clone(),
+        ..ProviderMetadata::for_provider(provider.kind())
+    }
+}
+
+// ============================================================================
+// Branding
+// ============================================================================
+
+fn custom_branding(provider: &CustomProvider) -> ProviderBranding {
+    ProviderBranding {
+        icon_style: IconStyle::Custom,
+        icon_resource_name: "icon_custom".to_string(),
+        color: provider.config().brand_color().unwrap_or_default(),
+    }
+}
+
+// ============================================================================
+// Fetch Plan
+// ============================================================================
+
+fn custom_fetch_plan(provider: &Arc<CustomProvider>) -> FetchPlan {
+    let provider = Arc::clone(provider);
+    FetchPlan::new(vec![custom_source_mode(&provider)], move |ctx| {
+        build_pipeline_for(&provider, ctx)
+    })
+}
+
+fn custom_source_mode(provider: &CustomProvider) -> SourceMode {
+    if provider.is_command() {
+        SourceMode::CLI
+    } else if provider.uses_cookies() {
+        SourceMode::Web
+    } else {
+        SourceMode::ApiKey
+    }
+}
+
+fn build_pipeline_for(provider: &Arc<CustomProvider>, ctx: &FetchContext) -> FetchPipeline {
+    let mut strategies: Vec<Box<dyn exactobar_fetch::FetchStrategy>> = Vec::new();
+
+    let mode = &ctx.settings.source_mode;
+    match custom_source_mode(provider) {
+        SourceMode::CLI if mode.allows_cli() => {
+            strategies.push(Box::new(CustomCommandStrategy::new(Arc::clone(provider))));
+        }
+        SourceMode::Web if mode.allows_web() => {
+            strategies.push(Box::new(CustomHttpStrategy::new(Arc::clone(provider))));
+        }
+        SourceMode::ApiKey if mode.allows_api_key() => {
+            strategies.push(Box::new(CustomHttpStrategy::new(Arc::clone(provider))));
+        }
+        _ => {}
+    }
+
+    FetchPipeline::with_strategies(strategies)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exactobar_fetch::FetchSettings;
+    use exactobar_store::{CustomAuth, CustomFieldPaths, CustomProviderConfig};
+
+    fn provider() -> Arc<CustomProvider> {
+        let config = CustomProviderConfig {
+            id: "desctest".to_string(),
+            name: "Descriptor Test".to_string(),
+            color: Some("#FF0000".to_string()),
+            url: "https://example.com/usage".to_string(),
+            command: None,
+            auth: CustomAuth::None,
+            headers: std::collections::BTreeMap::new(),
+            fields: CustomFieldPaths {
+                used: Some("$.used".to_string()),
+                credits: Some("$.balance".to_string()),
+                ..Default::default()
+            },
+            window_label: Some("Tokens".to_string()),
+            unit: None,
+            dashboard_url: Some("https://example.com".to_string()),
+        };
+        Arc::new(CustomProvider::new(config).unwrap())
+    }
+
+    #[test]
+    fn test_custom_descriptor() {
+        let provider = provider();
+        let desc = custom_descriptor(&provider);
+        assert!(desc.id.is_custom());
+        assert_eq!(desc.display_name(), "Descriptor Test");
+        assert_eq!(desc.cli_name(), "desctest");
+        assert_eq!(desc.metadata.session_label, "Tokens");
+        assert!(desc.metadata.supports_credits);
+        assert_eq!(desc.branding.color.to_hex(), "#FF0000");
+        assert_eq!(desc.fetch_plan.source_modes, vec![SourceMode::ApiKey]);
+    }
+
+    #[test]
+    fn test_pipeline_respects_source_mode() {
+        let provider = provider();
+
+        let ctx = FetchContext::new();
+        assert_eq!(build_pipeline_for(&provider, &ctx).len(), 1);
+
+        let ctx = FetchContext::with_settings(FetchSettings::cli_only());
+        assert_eq!(build_pipeline_for(&provider, &ctx).len(), 0);
+    }
+
+    #[test]
+    fn test_command_pipeline() {
+        let mut config = provider().config().clone();
+        config.id = "desccmd".to_string();
+        config.url = String::new();
+        config.command = Some(exactobar_store::CustomCommand {
+            program: "quota-report".to_string(),
+            ..Default::default()
+        });
+        let provider = Arc::new(CustomProvider::new(config).unwrap());
+
+        let desc = custom_descriptor(&provider);
+        assert_eq!(desc.fetch_plan.source_modes, vec![SourceMode::CLI]);
+
+        let ctx = FetchContext::with_settings(FetchSettings::cli_only());
+        assert_eq!(build_pipeline_for(&provider, &ctx).len(), 1);
+    }
+}
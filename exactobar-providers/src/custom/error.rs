@@ -0,0 +1,46 @@
+//! Custom provider errors.
+
+use exactobar_fetch::error::ProcessError;
+use thiserror::Error;
+
+/// Custom provider errors.
+#[derive(Debug, Error)]
+pub enum CustomProviderError {
+    /// The definition in settings is unusable.
+    #[error("Invalid custom provider: {0}")]
+    InvalidConfig(String),
+
+    /// The configured secret is not in the environment or keychain.
+    #[error("Secret not found: {0}")]
+    SecretNotFound(String),
+
+    /// A mapped field is missing from the response.
+    #[error("Field not found: {0}")]
+    MissingField(String),
+
+    /// HTTP request failed.
+    #[error("HTTP error: {0}")]
+    HttpError(String),
+
+    /// Parse error.
+    #[error("Parse error: {0}")]
+    ParseError(String),
+
+    /// API error.
+    #[error("API error: {0}")]
+    ApiError(String),
+
+    /// Authentication failed.
+    #[error("Authentication failed: {0}")]
+    AuthenticationFailed(String),
+
+    /// The provider command failed to run.
+    #[error("Command error: {0}")]
+    Process(#[from] ProcessError),
+}
+
+impl From<reqwest::Error> for CustomProviderError {
+    fn from(err: reqwest::Error) -> Self {
+        CustomProviderError::HttpError(err.to_string())
+    }
+}
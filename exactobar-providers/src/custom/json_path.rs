@@ -0,0 +1,170 @@
+//! A small JSONPath subset for custom provider field mappings.
+//!
+//! Supports what usage endpoints need: an optional `$` root, dotted keys,
+//! bracketed keys (`['rate-limit']`) and array indexes (`[0]`, `[-1]`).
+//! JMESPath-style paths without the `$` (`data.usage.used`) also work.
+
+use serde_json::Value;
+use std::fmt;
+
+use super::error::CustomProviderError;
+
+/// One step of a path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Key(String),
+    Index(i64),
+}
+
+/// A compiled path into a JSON document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPath {
+    source: String,
+    segments: Vec<Segment>,
+}
+
+impl JsonPath {
+    /// Parses a path.
+    pub fn parse(path: &str) -> Result<Self, CustomProviderError> {
+        let source = path.trim();
+        let invalid = |why: &str| {
+            CustomProviderError::InvalidConfig(format!("invalid path '{source}': {why}"))
+        };
+
+        let mut rest = source.strip_prefix('$').unwrap_or(source);
+        let mut segments = Vec::new();
+        // A leading bare key is allowed: `data.used` == `$.data.used`
+        let mut expect_key = !source.starts_with('$') && !rest.starts_with('[');
+
+        while !rest.is_empty() || expect_key {
+            if expect_key || rest.starts_with('.') {
+                if !expect_key {
+                    rest = &rest[1..];
+                }
+                expect_key = false;
+                let end = rest.find(['.', '[']).unwrap_or(rest.len());
+                let key = &rest[..end];
+                if key.is_empty() {
+                    return Err(invalid("empty key"));
+                }
+                segments.push(Segment::Key(key.to_string()));
+                rest = &rest[end..];
+            } else if let Some(inner) = rest.strip_prefix('[') {
+                let close = inner.find(']').ok_or_else(|| invalid("unclosed '['"))?;
+                let token = inner[..close].trim();
+                let quoted = token
+                    .strip_prefix('\'')
+                    .and_then(|t| t.strip_suffix('\''))
+                    .or_else(|| token.strip_prefix('"').and_then(|t| t.strip_suffix('"')));
+                let segment = match quoted {
+                    Some(key) if !key.is_empty() => Segment::Key(key.to_string()),
+                    Some(_) => return Err(invalid("empty key")),
+                    None => Segment::Index(token.parse().map_err(|_| invalid("bad index"))?),
+                };
+                segments.push(segment);
+                rest = &inner[close + 1..];
+            } else {
+                return Err(invalid("expected '.' or '['"));
+            }
+        }
+
+        Ok(Self {
+            source: source.to_string(),
+            segments,
+        })
+    }
+
+    /// Returns the value at this path, if present.
+    pub fn select<'a>(&self, root: &'a Value) -> Option<&'a Value> {
+        self.segments
+            .iter()
+            .try_fold(root, |value, segment| match segment {
+                Segment::Key(key) => value.get(key),
+                Segment::Index(index) => {
+                    let items = value.as_array()?;
+                    let i = if *index < 0 {
+                        items
+                            .len()
+                            .checked_sub(usize::try_from(index.unsigned_abs()).ok()?)?
+                    } else {
+                        usize::try_from(*index).ok()?
+                    };
+                    items.get(i)
+                }
+            })
+            .filter(|value| !value.is_null())
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn doc() -> Value {
+        json!({
+            "data": {
+                "usage": {"used": 42, "limit": 100},
+                "rate-limit": {"reset": "2025-01-01T00:00:00Z"},
+                "items": [{"pct": 10}, {"pct": 20}, {"pct": 30}],
+                "gone": null
+            }
+        })
+    }
+
+    #[test]
+    fn test_dotted_paths() {
+        let doc = doc();
+        let path = JsonPath::parse("$.data.usage.used").unwrap();
+        assert_eq!(path.select(&doc), Some(&json!(42)));
+        let path = JsonPath::parse("data.usage.limit").unwrap();
+        assert_eq!(path.select(&doc), Some(&json!(100)));
+        assert_eq!(JsonPath::parse("$").unwrap().select(&doc), Some(&doc));
+    }
+
+    #[test]
+    fn test_bracket_paths() {
+        let doc = doc();
+        let path = JsonPath::parse("$.data['rate-limit'].reset").unwrap();
+        assert_eq!(path.select(&doc), Some(&json!("2025-01-01T00:00:00Z")));
+        let path = JsonPath::parse(r#"$["data"].items[1].pct"#).unwrap();
+        assert_eq!(path.select(&doc), Some(&json!(20)));
+        let path = JsonPath::parse("$.data.items[-1].pct").unwrap();
+        assert_eq!(path.select(&doc), Some(&json!(30)));
+    }
+
+    #[test]
+    fn test_missing_values() {
+        let doc = doc();
+        for path in [
+            "$.data.nope",
+            "$.data.items[3]",
+            "$.data.items[-4]",
+            "$.data.gone",
+        ] {
+            assert!(
+                JsonPath::parse(path).unwrap().select(&doc).is_none(),
+                "{path}"
+            );
+        }
+        // Indexing an object or keying into an array finds nothing
+        assert!(JsonPath::parse("$.data[0]").unwrap().select(&doc).is_none());
+    }
+
+    #[test]
+    fn test_invalid_paths() {
+        for path in ["$.", "$..a", "$[", "$[x]", "$['']", "$a", "a..b"] {
+            assert!(JsonPath::parse(path).is_err(), "{path}");
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//! User-defined HTTP/JSON and command providers.
+//!
+//! Internal gateways and niche vendors can be added from settings instead of
+//! as Rust modules. A definition ([`CustomProviderConfig`]) names an
+//! endpoint, how to authenticate, and JSONPath-style paths to the used,
+//! limit, percent, reset and credit values in the response.
+//!
+//! Anything else can be a command that prints `UsageSnapshot` JSON; see the
+//! [`command`] module for the protocol.
+//!
+//! Definitions become [`ProviderKind::Custom`] providers when registered
+//! with [`ProviderRegistry::register_custom`] at startup.
+//!
+//! ## Authentication
+//!
+//! - **None**
+//! - **Bearer**: `Authorization: Bearer` token from an env var or keychain
+//! - **Header**: a named header with a secret from an env var or keychain
+//! - **Cookie**: browser cookies for a domain (HTTP only)
+//!
+//! Command providers get the bearer/header secret as `EXACTOBAR_SECRET`.
+//!
+//! [`CustomProviderConfig`]: exactobar_store::CustomProviderConfig
+//! [`ProviderKind::Custom`]: exactobar_core::ProviderKind::Custom
+//! [`ProviderRegistry::register_custom`]: crate::ProviderRegistry::register_custom
+
+mod api;
+pub mod command;
+mod descriptor;
+mod error;
+mod json_path;
+mod strategies;
+
+pub use api::{CustomProvider, USAGE_WINDOW_ID};
+pub use descriptor::custom_descriptor;
+pub use error::CustomProviderError;
+pub use json_path::JsonPath;
+pub use strategies::{CustomCommandStrategy, CustomHttpStrategy};
@@ -0,0 +1,202 @@
+//! Custom provider fetch strategies.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use exactobar_fetch::host::browser::Browser;
+use exactobar_fetch::{FetchContext, FetchError, FetchKind, FetchResult, FetchStrategy};
+use std::sync::Arc;
+use tracing::{debug, instrument};
+
+use super::api::CustomProvider;
+use super::command;
+use super::error::CustomProviderError;
+
+// ============================================================================
+// HTTP Strategy
+// ============================================================================
+
+/// Fetches a user-defined endpoint and maps its JSON.
+///
+/// Counts as an API key strategy, or a web strategy when the definition
+/// authenticates with browser cookies.
+pub struct CustomHttpStrategy {
+    provider: Arc<CustomProvider>,
+    id: String,
+}
+
+impl CustomHttpStrategy {
+    /// Creates a strategy for a provider.
+    pub fn new(provider: Arc<CustomProvider>) -> Self {
+        let id = format!("custom.{}", provider.config().id);
+        Self { provider, id }
+    }
+}
+
+#[async_trait]
+impl FetchStrategy for CustomHttpStrategy {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn kind(&self) -> FetchKind {
+        if self.provider.uses_cookies() {
+            FetchKind::WebCookies
+        } else {
+            FetchKind::ApiKey
+        }
+    }
+
+    #[instrument(skip(self, _ctx), fields(strategy = %self.id))]
+    async fn is_available(&self, _ctx: &FetchContext) -> bool {
+        if self.provider.uses_cookies() {
+            return Browser::default_priority().iter().any(|b| b.is_installed());
+        }
+        self.provider.secret().is_ok()
+    }
+
+    #[instrument(skip(self, ctx), fields(strategy = %self.id))]
+    async fn fetch(&self, ctx: &FetchContext) -> Result<FetchResult, FetchError> {
+        debug!("Fetching custom provider usage");
+
+        let snapshot = self
+            .provider
+            .fetch(ctx, Utc::now())
+            .await
+            .map_err(fetch_error)?;
+
+        Ok(FetchResult::new(snapshot, self.id(), self.kind()))
+    }
+
+    fn priority(&self) -> u32 {
+        60
+    }
+}
+
+// ============================================================================
+// Command Strategy
+// ============================================================================
+
+/// Runs a user-defined command that prints `UsageSnapshot` JSON.
+///
+/// See the [`command`] module for the output protocol.
+pub struct CustomCommandStrategy {
+    provider: Arc<CustomProvider>,
+    id: String,
+}
+
+impl CustomCommandStrategy {
+    /// Creates a strategy for a command provider.
+    pub fn new(provider: Arc<CustomProvider>) -> Self {
+        let id = format!("custom.{}", provider.config().id);
+        Self { provider, id }
+    }
+}
+
+#[async_trait]
+impl FetchStrategy for CustomCommandStrategy {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn kind(&self) -> FetchKind {
+        FetchKind::CLI
+    }
+
+    #[instrument(skip(self, ctx), fields(strategy = %self.id))]
+    async fn is_available(&self, ctx: &FetchContext) -> bool {
+        let Some(cmd) = &self.provider.config().command else {
+            return false;
+        };
+        ctx.process.command_exists(&command::program_path(cmd)) && self.provider.secret().is_ok()
+    }
+
+    #[instrument(skip(self, ctx), fields(strategy = %self.id))]
+    async fn fetch(&self, ctx: &FetchContext) -> Result<FetchResult, FetchError> {
+        debug!("Running custom provider command");
+
+        let snapshot = self
+            .provider
+            .fetch(ctx, Utc::now())
+            .await
+            .map_err(fetch_error)?;
+
+        Ok(FetchResult::new(snapshot, self.id(), self.kind()))
+    }
+
+    fn priority(&self) -> u32 {
+        60
+    }
+}
+
+/// Maps provider errors to fetch errors.
+fn fetch_error(err: CustomProviderError) -> FetchError {
+    match err {
+        CustomProviderError::AuthenticationFailed(msg)
+        | CustomProviderError::SecretNotFound(msg) => FetchError::AuthenticationFailed(msg),
+        CustomProviderError::Process(e) => FetchError::Process(e),
+        other => FetchError::InvalidResponse(other.to_string()),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exactobar_store::{CustomAuth, CustomFieldPaths, CustomProviderConfig};
+
+    fn provider(auth: CustomAuth) -> Arc<CustomProvider> {
+        let config = CustomProviderConfig {
+            id: "strattest".to_string(),
+            name: "Strategy Test".to_string(),
+            color: None,
+            url: "https://example.com/usage".to_string(),
+            command: None,
+            auth,
+            headers: std::collections::BTreeMap::new(),
+            fields: CustomFieldPaths {
+                percent: Some("$.pct".to_string()),
+                ..Default::default()
+            },
+            window_label: None,
+            unit: None,
+            dashboard_url: None,
+        };
+        Arc::new(CustomProvider::new(config).unwrap())
+    }
+
+    #[test]
+    fn test_http_strategy() {
+        let s = CustomHttpStrategy::new(provider(CustomAuth::None));
+        assert_eq!(s.id(), "custom.strattest");
+        assert_eq!(s.kind(), FetchKind::ApiKey);
+        assert_eq!(s.priority(), 60);
+
+        let s = CustomHttpStrategy::new(provider(CustomAuth::Cookie {
+            domain: "example.com".to_string(),
+        }));
+        assert_eq!(s.kind(), FetchKind::WebCookies);
+    }
+
+    #[tokio::test]
+    async fn test_no_auth_is_available() {
+        let s = CustomHttpStrategy::new(provider(CustomAuth::None));
+        assert!(s.is_available(&FetchContext::new()).await);
+    }
+
+    #[tokio::test]
+    async fn test_command_strategy() {
+        let mut config = provider(CustomAuth::None).config().clone();
+        config.url = String::new();
+        config.command = Some(exactobar_store::CustomCommand {
+            program: "exactobar-no-such-program".to_string(),
+            ..Default::default()
+        });
+        let s = CustomCommandStrategy::new(Arc::new(CustomProvider::new(config).unwrap()));
+        assert_eq!(s.id(), "custom.strattest");
+        assert_eq!(s.kind(), FetchKind::CLI);
+        assert!(!s.is_available(&FetchContext::new()).await);
+    }
+}
@@ -81,21 +81,43 @@ impl Default for TokenCostConfig {
 // Fetch Plan
 // ============================================================================
 
+/// Builds a provider's fetch pipeline for a context.
+pub type PipelineBuilder = Box<dyn Fn(&FetchContext) -> FetchPipeline + Send + Sync>;
+
 /// Configuration for how to fetch usage data.
 pub struct FetchPlan {
     /// Supported source modes in priority order.
     pub source_modes: Vec<SourceMode>,
-    /// Function to build the fetch pipeline.
-    pub build_pipeline: fn(&FetchContext) -> FetchPipeline,
+    /// Builds the fetch pipeline.
+    pub build_pipeline: PipelineBuilder,
 }
 
-impl Default for FetchPlan {
-    fn default() -> Self {
+impl FetchPlan {
+    /// Creates a plan from source modes and a pipeline builder.
+    pub fn new<F>(source_modes: Vec<SourceMode>, build_pipeline: F) -> Self
+    where
+        F: Fn(&FetchContext) -> FetchPipeline + Send + Sync + 'static,
+    {
         Self {
-            source_modes: vec![SourceMode::Auto],
-            build_pipeline: |_| FetchPipeline::new(),
+            source_modes,
+            build_pipeline: Box::new(build_pipeline),
         }
     }
+
+    /// Wraps the pipeline builder, e.g. to add or remove strategies.
+    pub fn map_pipeline<F>(&mut self, f: F)
+    where
+        F: Fn(&FetchContext, FetchPipeline) -> FetchPipeline + Send + Sync + 'static,
+    {
+        let inner = std::mem::replace(&mut self.build_pipeline, Box::new(|_| FetchPipeline::new()));
+        self.build_pipeline = Box::new(move |ctx| f(ctx, inner(ctx)));
+    }
+}
+
+impl Default for FetchPlan {
+    fn default() -> Self {
+        Self::new(vec![SourceMode::Auto], |_| FetchPipeline::new())
+    }
 }
 
 // ============================================================================
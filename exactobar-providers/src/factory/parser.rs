@@ -48,10 +48,10 @@ pub fn parse_factory_response(json_str: &str) -> Result<UsageSnapshot, FetchErro
 
     if let Some(usage) = response.usage {
         if let Some(session) = usage.session_percent {
-            snapshot.primary = Some(UsageWindow::new(session));
+            snapshot.set_primary(Some(UsageWindow::new(session)));
         }
         if let Some(monthly) = usage.monthly_percent {
-            snapshot.secondary = Some(UsageWindow::new(monthly));
+            snapshot.set_secondary(Some(UsageWindow::new(monthly)));
         }
     }
 
@@ -77,15 +77,15 @@ mod tests {
             "user": {"email": "user@example.com", "plan": "pro"}
         }"#;
         let snapshot = parse_factory_response(json).unwrap();
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.unwrap().used_percent, 30.0);
-        assert!(snapshot.secondary.is_some());
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 30.0);
+        assert!(snapshot.secondary().is_some());
     }
 
     #[test]
     fn test_parse_empty() {
         let json = r#"{}"#;
         let snapshot = parse_factory_response(json).unwrap();
-        assert!(snapshot.primary.is_none());
+        assert!(snapshot.primary().is_none());
     }
 }
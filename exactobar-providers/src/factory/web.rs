@@ -96,15 +96,15 @@ impl FactoryUsageResponse {
 
         // Use token usage as primary
         if let Some(percent) = self.get_token_percent() {
-            snapshot.primary = Some(UsageWindow::new(percent));
+            snapshot.set_primary(Some(UsageWindow::new(percent)));
         } else if let Some(percent) = self.get_request_percent() {
-            snapshot.primary = Some(UsageWindow::new(percent));
+            snapshot.set_primary(Some(UsageWindow::new(percent)));
         }
 
         // Use request usage as secondary
         if self.get_token_percent().is_some() {
             if let Some(percent) = self.get_request_percent() {
-                snapshot.secondary = Some(UsageWindow::new(percent));
+                snapshot.set_secondary(Some(UsageWindow::new(percent)));
             }
         }
 
@@ -342,7 +342,7 @@ mod tests {
         };
 
         let snapshot = response.to_snapshot();
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.unwrap().used_percent, 50.0);
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 50.0);
     }
 }
@@ -178,7 +178,7 @@ impl GeminiQuota {
 
         // If we have daily usage, use that as primary
         if let Some(percent) = self.get_daily_percent() {
-            snapshot.primary = Some(UsageWindow::new(percent));
+            snapshot.set_primary(Some(UsageWindow::new(percent)));
         }
 
         // Build identity
@@ -435,8 +435,8 @@ mod tests {
 
         let snapshot = quota.to_snapshot();
 
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.unwrap().used_percent, 50.0);
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 50.0);
 
         assert!(snapshot.identity.is_some());
         let identity = snapshot.identity.unwrap();
@@ -2,6 +2,7 @@
 
 use exactobar_core::{IconStyle, ProviderBranding, ProviderColor, ProviderKind, ProviderMetadata};
 use exactobar_fetch::{FetchContext, FetchPipeline, SourceMode};
+use std::collections::HashMap;
 
 use super::strategies::{GeminiCliStrategy, GeminiOAuthStrategy};
 use crate::descriptor::{CliConfig, FetchPlan, ProviderDescriptor, TokenCostConfig};
@@ -26,6 +27,7 @@ fn gemini_metadata() -> ProviderMetadata {
         weekly_label: "Daily".to_string(),
         opus_label: None,
         supports_opus: false,
+        window_labels: HashMap::new(),
         supports_credits: false,
         credits_hint: String::new(),
         toggle_title: "Show Gemini usage".to_string(),
@@ -51,7 +53,7 @@ fn gemini_branding() -> ProviderBranding {
 fn gemini_fetch_plan() -> FetchPlan {
     FetchPlan {
         source_modes: vec![SourceMode::OAuth, SourceMode::CLI],
-        build_pipeline: build_gemini_pipeline,
+        build_pipeline: Box::new(build_gemini_pipeline),
     }
 }
 
@@ -35,7 +35,7 @@ pub fn parse_gemini_response(json_str: &str) -> Result<UsageSnapshot, FetchError
             } else {
                 0.0
             };
-            snapshot.primary = Some(UsageWindow::new(percent));
+            snapshot.set_primary(Some(UsageWindow::new(percent)));
         }
     }
 
@@ -50,14 +50,14 @@ mod tests {
     fn test_parse_gemini() {
         let json = r#"{"requests": {"used": 50, "limit": 100}}"#;
         let snapshot = parse_gemini_response(json).unwrap();
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.unwrap().used_percent, 50.0);
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 50.0);
     }
 
     #[test]
     fn test_parse_empty() {
         let json = r#"{}"#;
         let snapshot = parse_gemini_response(json).unwrap();
-        assert!(snapshot.primary.is_none());
+        assert!(snapshot.primary().is_none());
     }
 }
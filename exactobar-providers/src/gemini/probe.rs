@@ -24,6 +24,7 @@ use tracing::{debug, info, warn};
 use super::error::GeminiError;
 use exactobar_core::{
     FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageSnapshot, UsageWindow,
+    WindowKind,
 };
 
 // ============================================================================
@@ -238,6 +239,16 @@ impl GeminiModelQuota {
     pub fn is_flash(&self) -> bool {
         self.model_id.to_lowercase().contains("flash")
     }
+
+    /// Convert to a per-model usage window (daily quota).
+    pub fn to_window(&self) -> UsageWindow {
+        UsageWindow {
+            window_minutes: Some(1440), // 24 hours
+            resets_at: self.reset_time,
+            ..UsageWindow::named(&self.model_id, WindowKind::Model, self.percent_used())
+                .with_label(&self.model_id)
+        }
+    }
 }
 
 // ============================================================================
@@ -278,22 +289,25 @@ impl GeminiSnapshot {
 
         // Primary = Pro model (the more expensive/limited one)
         if let Some(pro) = self.pro_quota() {
-            snapshot.primary = Some(UsageWindow {
-                used_percent: pro.percent_used(),
-                window_minutes: Some(1440), // 24 hours
-                resets_at: pro.reset_time,
+            snapshot.set_primary(Some(UsageWindow {
                 reset_description: Some(format!("Pro ({})", pro.model_id)),
-            });
+                ..pro.to_window()
+            }));
         }
 
         // Secondary = Flash model
         if let Some(flash) = self.flash_quota() {
-            snapshot.secondary = Some(UsageWindow {
-                used_percent: flash.percent_used(),
-                window_minutes: Some(1440), // 24 hours
-                resets_at: flash.reset_time,
+            snapshot.set_secondary(Some(UsageWindow {
                 reset_description: Some(format!("Flash ({})", flash.model_id)),
-            });
+                ..flash.to_window()
+            }));
+        }
+
+        // Every other model gets its own window
+        for quota in &self.model_quotas {
+            if snapshot.window(&quota.model_id).is_none() {
+                snapshot.push_window(quota.to_window());
+            }
         }
 
         // Build identity
@@ -618,12 +632,16 @@ mod tests {
         let usage = snapshot.to_usage_snapshot();
 
         // Primary should be Pro (25% used)
-        assert!(usage.primary.is_some());
-        assert_eq!(usage.primary.as_ref().unwrap().used_percent, 25.0);
+        assert!(usage.primary().is_some());
+        assert_eq!(usage.primary().unwrap().used_percent, 25.0);
 
         // Secondary should be Flash (10% used)
-        assert!(usage.secondary.is_some());
-        assert_eq!(usage.secondary.as_ref().unwrap().used_percent, 10.0);
+        assert!(usage.secondary().is_some());
+        assert_eq!(usage.secondary().unwrap().used_percent, 10.0);
+
+        // Every model has a window keyed by model ID
+        assert_eq!(usage.windows.len(), 2);
+        assert!(usage.window("gemini-2.5-pro").is_some());
 
         // Identity should have email
         assert!(usage.identity.is_some());
@@ -633,6 +651,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_snapshot_keeps_every_model() {
+        let quota = |model_id: &str, percent_left: f64| GeminiModelQuota {
+            model_id: model_id.to_string(),
+            percent_left,
+            reset_time: None,
+        };
+        let snapshot = GeminiSnapshot {
+            model_quotas: vec![
+                quota("gemini-2.5-pro", 75.0),
+                quota("gemini-2.5-flash", 90.0),
+                quota("gemini-2.0-flash", 50.0),
+                quota("gemini-embedding", 100.0),
+            ],
+            account_email: None,
+            account_plan: None,
+        };
+
+        let usage = snapshot.to_usage_snapshot();
+
+        assert_eq!(usage.windows.len(), 4);
+        assert_eq!(usage.primary().unwrap().id, "gemini-2.5-pro");
+        assert_eq!(usage.secondary().unwrap().id, "gemini-2.5-flash");
+        let extra = usage.window("gemini-2.0-flash").unwrap();
+        assert_eq!(extra.kind, WindowKind::Model);
+        assert_eq!(extra.used_percent, 50.0);
+        assert!(extra.slot.is_none());
+    }
+
     #[test]
     fn test_probe_creation() {
         let probe = GeminiProbe::new();
@@ -145,7 +145,7 @@ impl KiroUsage {
         snapshot.fetch_source = FetchSource::CLI;
 
         if let Some(percent) = self.get_percent() {
-            snapshot.primary = Some(UsageWindow::new(percent));
+            snapshot.set_primary(Some(UsageWindow::new(percent)));
         }
 
         let mut identity = ProviderIdentity::new(ProviderKind::Kiro);
@@ -2,6 +2,7 @@
 
 use exactobar_core::{IconStyle, ProviderBranding, ProviderColor, ProviderKind, ProviderMetadata};
 use exactobar_fetch::{FetchContext, FetchPipeline, SourceMode};
+use std::collections::HashMap;
 
 use super::strategies::KiroCliStrategy;
 use crate::descriptor::{CliConfig, FetchPlan, ProviderDescriptor, TokenCostConfig};
@@ -25,6 +26,7 @@ fn kiro_metadata() -> ProviderMetadata {
         weekly_label: "Monthly".to_string(),
         opus_label: None,
         supports_opus: false,
+        window_labels: HashMap::new(),
         supports_credits: true,
         credits_hint: "Kiro credits".to_string(),
         toggle_title: "Show Kiro usage".to_string(),
@@ -50,7 +52,7 @@ fn kiro_branding() -> ProviderBranding {
 fn kiro_fetch_plan() -> FetchPlan {
     FetchPlan {
         source_modes: vec![SourceMode::CLI],
-        build_pipeline: build_kiro_pipeline,
+        build_pipeline: Box::new(build_kiro_pipeline),
     }
 }
 
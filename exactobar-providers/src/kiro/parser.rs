@@ -110,7 +110,7 @@ impl KiroUsageResponse {
                 .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
                 .map(|dt| dt.with_timezone(&Utc));
 
-            snapshot.primary = Some(window);
+            snapshot.set_primary(Some(window));
         }
 
         // Secondary: bonus credits (if any)
@@ -123,7 +123,7 @@ impl KiroUsageResponse {
             window.resets_at = expiry;
             window.reset_description = self.bonus_expiry_days.map(|d| format!("expires in {}d", d));
 
-            snapshot.secondary = Some(window);
+            snapshot.set_secondary(Some(window));
         } else if let Some(credits) = &self.credits {
             // Try nested monthly credits as secondary
             if let (Some(monthly_used), Some(monthly_total)) =
@@ -131,7 +131,7 @@ impl KiroUsageResponse {
             {
                 if monthly_total > 0.0 {
                     let percent = (monthly_used / monthly_total) * 100.0;
-                    snapshot.secondary = Some(UsageWindow::new(percent));
+                    snapshot.set_secondary(Some(UsageWindow::new(percent)));
                 }
             }
         }
@@ -188,8 +188,8 @@ mod tests {
             "creditsTotal": 100.0
         }"#;
         let snapshot = parse_kiro_response(json).unwrap();
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.unwrap().used_percent, 40.0);
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 40.0);
     }
 
     #[test]
@@ -199,8 +199,8 @@ mod tests {
             "user": {"email": "user@example.com", "plan": "Free"}
         }"#;
         let snapshot = parse_kiro_response(json).unwrap();
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.unwrap().used_percent, 40.0);
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 40.0);
         assert!(snapshot.identity.is_some());
         let identity = snapshot.identity.unwrap();
         assert_eq!(identity.account_email, Some("user@example.com".to_string()));
@@ -216,18 +216,11 @@ mod tests {
             "bonusExpiryDays": 7
         }"#;
         let snapshot = parse_kiro_response(json).unwrap();
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.as_ref().unwrap().used_percent, 50.0);
-        assert!(snapshot.secondary.is_some());
-        assert_eq!(snapshot.secondary.as_ref().unwrap().used_percent, 20.0);
-        assert!(
-            snapshot
-                .secondary
-                .as_ref()
-                .unwrap()
-                .reset_description
-                .is_some()
-        );
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 50.0);
+        assert!(snapshot.secondary().is_some());
+        assert_eq!(snapshot.secondary().unwrap().used_percent, 20.0);
+        assert!(snapshot.secondary().unwrap().reset_description.is_some());
     }
 
     #[test]
@@ -238,8 +231,8 @@ mod tests {
             "resetsAt": "2025-02-01T00:00:00Z"
         }"#;
         let snapshot = parse_kiro_response(json).unwrap();
-        assert!(snapshot.primary.is_some());
-        let primary = snapshot.primary.unwrap();
+        assert!(snapshot.primary().is_some());
+        let primary = snapshot.primary().unwrap();
         assert_eq!(primary.used_percent, 25.0);
         assert!(primary.resets_at.is_some());
     }
@@ -248,6 +241,6 @@ mod tests {
     fn test_parse_empty() {
         let json = r#"{}"#;
         let snapshot = parse_kiro_response(json).unwrap();
-        assert!(snapshot.primary.is_none());
+        assert!(snapshot.primary().is_none());
     }
 }
@@ -51,7 +51,7 @@
 //! - **Strategies**: Fetch strategy implementations (CLI, OAuth, Web)
 //! - **Parser**: Response parsing for various formats
 //!
-//! ## Supported Providers (12 total)
+//! ## Supported Providers (19 total)
 //!
 //! | Provider | CLI | OAuth | API Key | Web | Local | Status |
 //! |----------|-----|-------|---------|-----|-------|--------|
@@ -67,6 +67,16 @@
 //! | Kiro (AWS) | ✅ | ❌ | ❌ | ❌ | ❌ | Active |
 //! | MiniMax | ❌ | ❌ | ❌ | ✅ | ✅ | Active |
 //! | Antigravity | ❌ | ❌ | ❌ | ❌ | ✅ | Active |
+//! | Windsurf | ❌ | ❌ | ❌ | ❌ | ✅ | Active |
+//! | Synthetic.new | ❌ | ❌ | ✅ | ❌ | ❌ | Active |
+//! | OpenRouter | ❌ | ❌ | ✅ | ❌ | ❌ | Active |
+//! | Anthropic API | ❌ | ❌ | ✅ | ❌ | ❌ | Active |
+//! | LiteLLM (proxy) | ❌ | ❌ | ✅ | ❌ | ❌ | Active |
+//! | Amazon Bedrock | ❌ | ❌ | ✅ | ❌ | ❌ | Active |
+//! | Azure OpenAI | ✅ | ✅ | ❌ | ❌ | ❌ | Active |
+//!
+//! User-defined HTTP/JSON providers from settings are added at runtime;
+//! see [`custom`].
 //!
 //! ## Usage
 //!
@@ -83,59 +93,107 @@
 //! let pipeline = desc.build_pipeline(&ctx);
 //! let outcome = pipeline.execute(&ctx).await;
 //! ```
+//!
+//! ## Embedding
+//!
+//! Tools that embed this crate can register their own providers, or add and
+//! replace strategies on built-in ones, with a [`ProviderSet`] scoped to a
+//! fetch context:
+//!
+//! ```ignore
+//! let mut set = ProviderSet::builtin();
+//! set.set_strategy(ProviderKind::Claude, |ctx| {
+//!     ctx.settings.source_mode.allows_api_key()
+//!         .then(|| Box::new(GatewayStrategy::new()) as Box<dyn FetchStrategy>)
+//! });
+//!
+//! let mut ctx = FetchContext::new();
+//! Arc::new(set).attach(&mut ctx);
+//! let pipeline = ProviderRegistry::for_context(&ctx)
+//!     .build_pipeline(ProviderKind::Claude, &ctx)
+//!     .unwrap();
+//! ```
 
+pub mod codeium;
 pub mod descriptor;
+pub mod ranking;
 pub mod registry;
+pub(crate) mod spend;
 
 // Provider modules (alphabetical)
+pub mod anthropic;
 pub mod antigravity;
 pub mod augment;
+pub mod azure;
+pub mod bedrock;
 pub mod claude;
 pub mod codex;
 pub mod copilot;
 pub mod cursor;
+pub mod custom;
 pub mod factory;
 pub mod gemini;
 pub mod kiro;
+pub mod litellm;
 pub mod minimax;
+pub mod openrouter;
 pub mod synthetic;
 pub mod vertexai;
+pub mod windsurf;
 pub mod zai;
 
 // Re-export key types
 pub use descriptor::{
-    CliConfig, FetchPlan, ProviderDescriptor, ProviderDescriptorBuilder, TokenCostConfig,
+    CliConfig, FetchPlan, PipelineBuilder, ProviderDescriptor, ProviderDescriptorBuilder,
+    TokenCostConfig,
 };
-pub use registry::ProviderRegistry;
+pub use ranking::{Candidate, Ranker, Ranking};
+pub use registry::{ProviderRegistry, ProviderSet};
 
 // Re-export provider descriptors
+pub use anthropic::anthropic_descriptor;
 pub use antigravity::antigravity_descriptor;
 pub use augment::augment_descriptor;
+pub use azure::azure_descriptor;
+pub use bedrock::bedrock_descriptor;
 pub use claude::claude_descriptor;
 pub use codex::codex_descriptor;
 pub use copilot::copilot_descriptor;
 pub use cursor::cursor_descriptor;
+pub use custom::custom_descriptor;
 pub use factory::factory_descriptor;
 pub use gemini::gemini_descriptor;
 pub use kiro::kiro_descriptor;
+pub use litellm::litellm_descriptor;
 pub use minimax::minimax_descriptor;
+pub use openrouter::openrouter_descriptor;
 pub use synthetic::synthetic_descriptor;
 pub use vertexai::vertexai_descriptor;
+pub use windsurf::windsurf_descriptor;
 pub use zai::zai_descriptor;
 
 // Re-export strategy types for convenience
+pub use anthropic::AnthropicAdminStrategy;
 pub use antigravity::AntigravityLocalStrategy;
 pub use augment::AugmentWebStrategy;
+pub use azure::{AzureCliStrategy, AzureOAuthStrategy};
+pub use bedrock::BedrockApiStrategy;
 pub use claude::{ClaudeCliStrategy, ClaudeOAuthStrategy, ClaudeWebStrategy};
 pub use codex::{CodexApiStrategy, CodexCliStrategy};
 pub use copilot::{CopilotApiStrategy, CopilotEnvStrategy};
 pub use cursor::{CursorLocalStrategy, CursorWebStrategy};
+pub use custom::CustomHttpStrategy;
 pub use factory::{FactoryLocalStrategy, FactoryWebStrategy};
 pub use gemini::{GeminiCliStrategy, GeminiOAuthStrategy};
 pub use kiro::KiroCliStrategy;
+pub use litellm::LiteLlmApiStrategy;
 pub use minimax::{MiniMaxLocalStrategy, MiniMaxWebStrategy};
+pub use openrouter::OpenRouterApiStrategy;
 pub use synthetic::SyntheticApiStrategy;
 pub use vertexai::{VertexAILocalStrategy, VertexAIOAuthStrategy};
+pub use windsurf::WindsurfLocalStrategy;
 pub use zai::ZaiApiStrategy;
 #[cfg(test)]
 mod parser_edge_tests;
+#[cfg(test)]
+mod test_support;
@@ -0,0 +1,641 @@
+//! LiteLLM proxy API client.
+//!
+//! # API Endpoints
+//!
+//! ```text
+//! GET {base_url}/key/info
+//! GET {base_url}/user/info?user_id=...
+//! GET {base_url}/team/info?team_id=...
+//! Authorization: Bearer sk-...
+//! ```
+//!
+//! # Response Format
+//!
+//! ```json
+//! {"key": "sk-...", "info": {"key_alias": "ci", "spend": 12.5, "max_budget": 50,
+//!   "budget_duration": "30d", "budget_reset_at": "2024-12-01T00:00:00Z",
+//!   "user_id": "alice", "team_id": "platform"}}
+//! {"user_id": "alice", "user_info": {"user_email": "alice@example.com", "spend": 40, ...}}
+//! {"team_id": "platform", "team_info": {"team_alias": "Platform", "spend": 300, ...}}
+//! ```
+//!
+//! All amounts are in US dollars.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use exactobar_core::{
+    Amount, Credits, FetchSource, LoginMethod, ProviderIdentity, ProviderKind, USD, UsageSnapshot,
+    UsageWindow, WindowKind, WindowSlot,
+};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use tracing::{debug, instrument, warn};
+
+use super::error::LiteLlmError;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Environment variable for the proxy base URL.
+pub const BASE_URL_ENV: &str = "LITELLM_BASE_URL";
+
+/// Environment variable for the virtual key.
+pub const API_KEY_ENV: &str = "LITELLM_API_KEY";
+
+/// Key info endpoint (the calling key when no key is given).
+pub const KEY_INFO_ENDPOINT: &str = "/key/info";
+
+/// User info endpoint.
+pub const USER_INFO_ENDPOINT: &str = "/user/info";
+
+/// Team info endpoint.
+pub const TEAM_INFO_ENDPOINT: &str = "/team/info";
+
+// ============================================================================
+// API Response Types
+// ============================================================================
+
+/// Budget fields shared by keys, users and teams.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LiteLlmBudget {
+    /// Spend in the current budget period, in USD.
+    pub spend: Option<f64>,
+    /// Budget cap in USD, if one is set.
+    pub max_budget: Option<f64>,
+    /// Budget period (e.g., "30d", "1h", "1mo").
+    pub budget_duration: Option<String>,
+    /// When the spend resets.
+    pub budget_reset_at: Option<String>,
+}
+
+impl LiteLlmBudget {
+    /// Returns the budget cap if it is positive.
+    pub fn limit(&self) -> Option<f64> {
+        self.max_budget.filter(|b| b.is_finite() && *b > 0.0)
+    }
+
+    /// Returns the spend, treating missing values as zero.
+    pub fn spent(&self) -> f64 {
+        self.spend.unwrap_or(0.0).max(0.0)
+    }
+
+    /// Returns the budget left, if a cap is set.
+    pub fn remaining(&self) -> Option<f64> {
+        self.limit().map(|limit| (limit - self.spent()).max(0.0))
+    }
+
+    /// Returns when the budget resets.
+    pub fn resets_at(&self) -> Option<DateTime<Utc>> {
+        self.budget_reset_at.as_deref().and_then(parse_timestamp)
+    }
+
+    /// Returns the budget period in minutes.
+    pub fn window_minutes(&self) -> Option<u32> {
+        self.budget_duration
+            .as_deref()
+            .and_then(parse_duration_minutes)
+    }
+
+    /// Converts to a usage window if a cap is set.
+    pub fn to_window(&self, id: &str, label: &str) -> Option<UsageWindow> {
+        let limit = self.limit()?;
+        let spent = self.spent();
+        let percent = (spent / limit * 100.0).clamp(0.0, 100.0);
+        let window_minutes = self.window_minutes();
+
+        Some(UsageWindow {
+            window_minutes,
+            resets_at: self.resets_at(),
+            ..UsageWindow::named(id, WindowKind::from_minutes(window_minutes), percent)
+                .with_label(label)
+                .with_counts(spent, Some(limit), USD)
+        })
+    }
+}
+
+/// Information about a virtual key.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LiteLlmKeyInfo {
+    /// Redacted key name (e.g., "sk-...abcd").
+    pub key_name: Option<String>,
+    /// User-assigned key alias.
+    pub key_alias: Option<String>,
+    /// Owning user.
+    pub user_id: Option<String>,
+    /// Owning team.
+    pub team_id: Option<String>,
+    /// Key budget.
+    #[serde(flatten)]
+    pub budget: LiteLlmBudget,
+}
+
+/// Information about a proxy user.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LiteLlmUserInfo {
+    /// User email.
+    pub user_email: Option<String>,
+    /// User budget.
+    #[serde(flatten)]
+    pub budget: LiteLlmBudget,
+}
+
+/// Information about a team.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LiteLlmTeamInfo {
+    /// Team display name.
+    pub team_alias: Option<String>,
+    /// Team budget.
+    #[serde(flatten)]
+    pub budget: LiteLlmBudget,
+}
+
+#[derive(Debug, Deserialize)]
+struct KeyInfoResponse {
+    info: LiteLlmKeyInfo,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserInfoResponse {
+    user_info: Option<LiteLlmUserInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeamInfoResponse {
+    team_info: LiteLlmTeamInfo,
+}
+
+/// Parses a timestamp with or without a UTC offset.
+fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f")
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+/// Parses a LiteLLM budget duration ("30s", "30m", "30h", "30d", "1mo").
+fn parse_duration_minutes(s: &str) -> Option<u32> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit())?;
+    let (value, unit) = s.split_at(split);
+    let value: u32 = value.parse().ok()?;
+
+    let minutes = match unit {
+        "s" => value.div_ceil(60),
+        "m" => value,
+        "h" => value.checked_mul(60)?,
+        "d" => value.checked_mul(24 * 60)?,
+        "w" => value.checked_mul(7 * 24 * 60)?,
+        "mo" => value.checked_mul(30 * 24 * 60)?,
+        _ => return None,
+    };
+    Some(minutes)
+}
+
+// ============================================================================
+// Usage
+// ============================================================================
+
+/// Key, user and team budgets for a virtual key.
+#[derive(Debug, Clone, Default)]
+pub struct LiteLlmUsage {
+    /// The key's own budget.
+    pub key: LiteLlmKeyInfo,
+    /// The owning user, if known.
+    pub user: Option<LiteLlmUserInfo>,
+    /// The owning team, if known.
+    pub team: Option<LiteLlmTeamInfo>,
+}
+
+impl LiteLlmUsage {
+    /// Builds a snapshot of every budget that has a cap.
+    ///
+    /// Key, team and user budgets fill the primary, secondary and tertiary
+    /// slots in that order. The credit balance is the smallest budget left,
+    /// since whichever runs out first blocks requests. Without any cap the
+    /// key's spend is shown on its own.
+    pub fn to_snapshot(&self) -> UsageSnapshot {
+        let mut snapshot = UsageSnapshot::new();
+        snapshot.fetch_source = FetchSource::Api;
+
+        let budgets = [
+            Some(("key_budget", "Key budget", &self.key.budget)),
+            self.team
+                .as_ref()
+                .map(|t| ("team_budget", "Team budget", &t.budget)),
+            self.user
+                .as_ref()
+                .map(|u| ("user_budget", "User budget", &u.budget)),
+        ];
+
+        let windows: Vec<UsageWindow> = budgets
+            .iter()
+            .flatten()
+            .filter_map(|(id, label, budget)| budget.to_window(id, label))
+            .collect();
+
+        if windows.is_empty() {
+            let spend = UsageWindow::named("key_spend", WindowKind::Other, 0.0)
+                .with_label("Key spend")
+                .with_counts(self.key.budget.spent(), None, USD);
+            snapshot.set_primary(Some(spend));
+        }
+
+        let slots = [
+            WindowSlot::Primary,
+            WindowSlot::Secondary,
+            WindowSlot::Tertiary,
+        ];
+        for (slot, window) in slots.into_iter().zip(windows) {
+            snapshot.set_slot(slot, Some(window));
+        }
+
+        snapshot.credits = budgets
+            .iter()
+            .flatten()
+            .filter_map(|(_, _, budget)| Some((budget.remaining()?, budget.limit()?)))
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(remaining, limit)| {
+                Credits::from_amount(Amount::usd(remaining)).with_total(limit)
+            });
+
+        let mut identity = ProviderIdentity::new(ProviderKind::LiteLlm);
+        identity.account_email = self.user.as_ref().and_then(|u| u.user_email.clone());
+        identity.account_organization = self
+            .team
+            .as_ref()
+            .and_then(|t| t.team_alias.clone())
+            .or_else(|| self.key.team_id.clone());
+        identity.plan_name = self
+            .key
+            .key_alias
+            .clone()
+            .or_else(|| self.key.key_name.clone());
+        identity.login_method = Some(LoginMethod::ApiKey);
+        snapshot.identity = Some(identity);
+
+        snapshot
+    }
+}
+
+// ============================================================================
+// API Client
+// ============================================================================
+
+/// LiteLLM proxy API client.
+#[derive(Debug, Clone)]
+pub struct LiteLlmApiClient {
+    base_url: String,
+}
+
+impl LiteLlmApiClient {
+    /// Creates a client for the proxy at `base_url`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Get the virtual key from Keychain first, then environment variable.
+    ///
+    /// The lookup order is:
+    /// 1. System keychain (stored via Settings UI)
+    /// 2. Environment variable `LITELLM_API_KEY`
+    pub fn get_api_key() -> Result<String, LiteLlmError> {
+        if let Some(key) =
+            exactobar_store::get_api_key(exactobar_store::keychain::providers::LITELLM)
+        {
+            return Ok(key);
+        }
+
+        std::env::var(API_KEY_ENV).map_err(|_| LiteLlmError::ApiKeyNotFound)
+    }
+
+    /// Resolves the proxy base URL from settings, then the environment.
+    pub fn resolve_base_url(configured: Option<&str>) -> Result<String, LiteLlmError> {
+        configured
+            .map(String::from)
+            .or_else(|| std::env::var(BASE_URL_ENV).ok())
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())
+            .ok_or(LiteLlmError::BaseUrlNotFound)
+    }
+
+    /// Fetches the key budget, then the owning user's and team's.
+    ///
+    /// Virtual keys may not be allowed to read user or team info, so those
+    /// are best-effort.
+    #[instrument(skip(self, api_key))]
+    pub async fn fetch_usage(&self, api_key: &str) -> Result<LiteLlmUsage, LiteLlmError> {
+        let key = self.fetch_key_info(api_key).await?;
+
+        let user = match key.user_id.as_deref() {
+            Some(user_id) => match self.fetch_user_info(api_key, user_id).await {
+                Ok(user) => user,
+                Err(e) => {
+                    warn!(error = %e, "Failed to fetch LiteLLM user info");
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let team = match key.team_id.as_deref() {
+            Some(team_id) => match self.fetch_team_info(api_key, team_id).await {
+                Ok(team) => Some(team),
+                Err(e) => {
+                    warn!(error = %e, "Failed to fetch LiteLLM team info");
+                    None
+                }
+            },
+            None => None,
+        };
+
+        Ok(LiteLlmUsage { key, user, team })
+    }
+
+    /// Fetches information about the calling key.
+    #[instrument(skip(self, api_key))]
+    pub async fn fetch_key_info(&self, api_key: &str) -> Result<LiteLlmKeyInfo, LiteLlmError> {
+        let response: KeyInfoResponse = self.get(api_key, KEY_INFO_ENDPOINT, &[]).await?;
+        Ok(response.info)
+    }
+
+    /// Fetches information about a user.
+    #[instrument(skip(self, api_key))]
+    pub async fn fetch_user_info(
+        &self,
+        api_key: &str,
+        user_id: &str,
+    ) -> Result<Option<LiteLlmUserInfo>, LiteLlmError> {
+        let response: UserInfoResponse = self
+            .get(api_key, USER_INFO_ENDPOINT, &[("user_id", user_id)])
+            .await?;
+        Ok(response.user_info)
+    }
+
+    /// Fetches information about a team.
+    #[instrument(skip(self, api_key))]
+    pub async fn fetch_team_info(
+        &self,
+        api_key: &str,
+        team_id: &str,
+    ) -> Result<LiteLlmTeamInfo, LiteLlmError> {
+        let response: TeamInfoResponse = self
+            .get(api_key, TEAM_INFO_ENDPOINT, &[("team_id", team_id)])
+            .await?;
+        Ok(response.team_info)
+    }
+
+    /// Sends an authenticated GET request.
+    async fn get<T: DeserializeOwned>(
+        &self,
+        api_key: &str,
+        endpoint: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, LiteLlmError> {
+        let url = format!("{}{}", self.base_url, endpoint);
+
+        debug!(url = %url, "Fetching LiteLLM data");
+
+        let client = reqwest::Client::new();
+        let response = client
+            .get(&url)
+            .query(query)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .header("Content-Type", "application/json")
+            .send()
+            .await?;
+
+        let status = response.status();
+
+        if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+            return Err(LiteLlmError::AuthenticationFailed(
+                "Virtual key rejected".to_string(),
+            ));
+        }
+
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(LiteLlmError::ApiError(format!("HTTP {}: {}", status, text)));
+        }
+
+        response
+            .json::<T>()
+            .await
+            .map_err(|e| LiteLlmError::ParseError(e.to_string()))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::serve_stub;
+    use chrono::TimeZone;
+
+    const KEY_JSON: &str = r#"{
+        "key": "sk-test",
+        "info": {
+            "key_name": "sk-...test",
+            "key_alias": "ci-runner",
+            "spend": 12.5,
+            "max_budget": 50.0,
+            "budget_duration": "30d",
+            "budget_reset_at": "2024-12-01T00:00:00Z",
+            "user_id": "alice",
+            "team_id": "platform",
+            "models": [],
+            "tpm_limit": null
+        }
+    }"#;
+
+    const USER_JSON: &str = r#"{
+        "user_id": "alice",
+        "user_info": {
+            "user_id": "alice",
+            "user_email": "alice@example.com",
+            "spend": 40.0,
+            "max_budget": null
+        },
+        "keys": [],
+        "teams": []
+    }"#;
+
+    const TEAM_JSON: &str = r#"{
+        "team_id": "platform",
+        "team_info": {
+            "team_alias": "Platform",
+            "spend": 990.0,
+            "max_budget": 1000.0,
+            "budget_duration": "1mo",
+            "budget_reset_at": "2024-12-01T00:00:00.000000"
+        }
+    }"#;
+
+    /// Serves canned JSON by path prefix.
+    async fn serve_routes(routes: Vec<(&'static str, u16, &'static str)>) -> String {
+        serve_stub(move |request| {
+            routes
+                .iter()
+                .find(|(prefix, _, _)| request.path.starts_with(prefix))
+                .map_or((404, "{}"), |(_, status, body)| (*status, *body))
+        })
+        .await
+    }
+
+    #[test]
+    fn test_client_trims_base_url() {
+        let client = LiteLlmApiClient::new("http://localhost:4000/");
+        assert_eq!(client.base_url, "http://localhost:4000");
+    }
+
+    #[test]
+    fn test_parse_duration_minutes() {
+        assert_eq!(parse_duration_minutes("90s"), Some(2));
+        assert_eq!(parse_duration_minutes("30m"), Some(30));
+        assert_eq!(parse_duration_minutes("1h"), Some(60));
+        assert_eq!(parse_duration_minutes("30d"), Some(43_200));
+        assert_eq!(parse_duration_minutes("1mo"), Some(43_200));
+        assert_eq!(parse_duration_minutes("soon"), None);
+        assert_eq!(parse_duration_minutes("5y"), None);
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        let expected = Utc.with_ymd_and_hms(2024, 12, 1, 0, 0, 0).unwrap();
+        assert_eq!(parse_timestamp("2024-12-01T00:00:00Z"), Some(expected));
+        assert_eq!(parse_timestamp("2024-12-01T00:00:00+00:00"), Some(expected));
+        assert_eq!(
+            parse_timestamp("2024-12-01T00:00:00.000000"),
+            Some(expected)
+        );
+        assert!(parse_timestamp("tomorrow").is_none());
+    }
+
+    #[test]
+    fn test_budget_window() {
+        let response: KeyInfoResponse = serde_json::from_str(KEY_JSON).unwrap();
+        let window = response
+            .info
+            .budget
+            .to_window("key_budget", "Key budget")
+            .unwrap();
+
+        assert!((window.used_percent - 25.0).abs() < 0.001);
+        assert_eq!(window.kind, WindowKind::Monthly);
+        assert_eq!(window.window_minutes, Some(43_200));
+        assert_eq!(window.limit, Some(50.0));
+        assert!(window.resets_at.is_some());
+    }
+
+    #[test]
+    fn test_snapshot_uses_tightest_budget_for_credits() {
+        let usage = LiteLlmUsage {
+            key: serde_json::from_str::<KeyInfoResponse>(KEY_JSON)
+                .unwrap()
+                .info,
+            user: serde_json::from_str::<UserInfoResponse>(USER_JSON)
+                .unwrap()
+                .user_info,
+            team: Some(
+                serde_json::from_str::<TeamInfoResponse>(TEAM_JSON)
+                    .unwrap()
+                    .team_info,
+            ),
+        };
+        let snapshot = usage.to_snapshot();
+
+        assert_eq!(snapshot.primary().unwrap().id, "key_budget");
+        assert_eq!(snapshot.secondary().unwrap().id, "team_budget");
+        // The user has no cap, so no tertiary window
+        assert!(snapshot.tertiary().is_none());
+
+        // Team has $10 left, key has $37.50
+        let credits = snapshot.credits.as_ref().unwrap();
+        assert!((credits.remaining - 10.0).abs() < 0.001);
+        assert_eq!(credits.total, Some(1000.0));
+        assert_eq!(credits.unit, USD);
+
+        let identity = snapshot.identity.as_ref().unwrap();
+        assert_eq!(identity.account_email.as_deref(), Some("alice@example.com"));
+        assert_eq!(identity.account_organization.as_deref(), Some("Platform"));
+        assert_eq!(identity.plan_name.as_deref(), Some("ci-runner"));
+        assert!(snapshot.validate().is_ok());
+    }
+
+    #[test]
+    fn test_snapshot_without_budget_shows_spend() {
+        let usage = LiteLlmUsage {
+            key: LiteLlmKeyInfo {
+                budget: LiteLlmBudget {
+                    spend: Some(3.25),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let snapshot = usage.to_snapshot();
+
+        let window = snapshot.primary().unwrap();
+        assert_eq!(window.id, "key_spend");
+        assert_eq!(window.used, Some(3.25));
+        assert!(window.limit.is_none());
+        assert!(snapshot.credits.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_usage_from_stub() {
+        let base_url = serve_routes(vec![
+            (KEY_INFO_ENDPOINT, 200, KEY_JSON),
+            (USER_INFO_ENDPOINT, 200, USER_JSON),
+            (TEAM_INFO_ENDPOINT, 200, TEAM_JSON),
+        ])
+        .await;
+
+        let client = LiteLlmApiClient::new(base_url);
+        let usage = client.fetch_usage("sk-test").await.unwrap();
+
+        assert_eq!(usage.key.key_alias.as_deref(), Some("ci-runner"));
+        assert_eq!(
+            usage.user.unwrap().user_email.as_deref(),
+            Some("alice@example.com")
+        );
+        assert_eq!(usage.team.unwrap().team_alias.as_deref(), Some("Platform"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_usage_tolerates_forbidden_team() {
+        let base_url = serve_routes(vec![
+            (KEY_INFO_ENDPOINT, 200, KEY_JSON),
+            (USER_INFO_ENDPOINT, 200, USER_JSON),
+            (TEAM_INFO_ENDPOINT, 403, r#"{"error": "forbidden"}"#),
+        ])
+        .await;
+
+        let usage = LiteLlmApiClient::new(base_url)
+            .fetch_usage("sk-test")
+            .await
+            .unwrap();
+        assert!(usage.user.is_some());
+        assert!(usage.team.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_rejected_key() {
+        let base_url =
+            serve_routes(vec![(KEY_INFO_ENDPOINT, 401, r#"{"error": "bad key"}"#)]).await;
+
+        let result = LiteLlmApiClient::new(base_url).fetch_usage("sk-bad").await;
+        assert!(matches!(result, Err(LiteLlmError::AuthenticationFailed(_))));
+    }
+}
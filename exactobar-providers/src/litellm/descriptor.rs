@@ -0,0 +1,104 @@
+//! LiteLLM provider descriptor.
+
+use exactobar_core::{IconStyle, ProviderBranding, ProviderColor, ProviderKind, ProviderMetadata};
+use exactobar_fetch::{FetchContext, FetchPipeline, SourceMode};
+use std::collections::HashMap;
+
+use super::strategies::LiteLlmApiStrategy;
+use crate::descriptor::{CliConfig, FetchPlan, ProviderDescriptor, TokenCostConfig};
+
+// ============================================================================
+// Descriptor
+// ============================================================================
+
+/// Creates the LiteLLM provider descriptor.
+pub fn litellm_descriptor() -> ProviderDescriptor {
+    ProviderDescriptor {
+        id: ProviderKind::LiteLlm,
+        metadata: litellm_metadata(),
+        branding: litellm_branding(),
+        token_cost: TokenCostConfig::default(),
+        fetch_plan: litellm_fetch_plan(),
+        cli: litellm_cli_config(),
+    }
+}
+
+// ============================================================================
+// Metadata
+// ============================================================================
+
+fn litellm_metadata() -> ProviderMetadata {
+    ProviderMetadata {
+        id: ProviderKind::LiteLlm,
+        display_name: "LiteLLM".to_string(),
+        session_label: "Key budget".to_string(),
+        weekly_label: "Team budget".to_string(),
+        opus_label: None,
+        supports_opus: false,
+        window_labels: HashMap::from([
+            ("key_budget".to_string(), "Key budget".to_string()),
+            ("team_budget".to_string(), "Team budget".to_string()),
+            ("user_budget".to_string(), "User budget".to_string()),
+            ("key_spend".to_string(), "Key spend".to_string()),
+        ]),
+        supports_credits: true,
+        credits_hint: "Budget left before the proxy blocks requests".to_string(),
+        toggle_title: "Show LiteLLM usage".to_string(),
+        cli_name: "litellm".to_string(),
+        default_enabled: false,
+        is_primary_provider: false,
+        uses_account_fallback: false,
+        // Self-hosted; the dashboard lives on the configured proxy
+        dashboard_url: None,
+        subscription_dashboard_url: None,
+        status_page_url: None,
+        status_link_url: None,
+    }
+}
+
+// ============================================================================
+// Branding
+// ============================================================================
+
+fn litellm_branding() -> ProviderBranding {
+    ProviderBranding {
+        icon_style: IconStyle::LiteLlm,
+        icon_resource_name: "icon_litellm".to_string(),
+        // LiteLLM brand amber
+        color: ProviderColor::new(0.96, 0.62, 0.04),
+    }
+}
+
+// ============================================================================
+// Fetch Plan
+// ============================================================================
+
+fn litellm_fetch_plan() -> FetchPlan {
+    FetchPlan {
+        source_modes: vec![SourceMode::ApiKey],
+        build_pipeline: Box::new(build_litellm_pipeline),
+    }
+}
+
+fn build_litellm_pipeline(ctx: &FetchContext) -> FetchPipeline {
+    let mut strategies: Vec<Box<dyn exactobar_fetch::FetchStrategy>> = Vec::new();
+
+    if ctx.settings.source_mode.allows_api_key() {
+        strategies.push(Box::new(LiteLlmApiStrategy::new()));
+    }
+
+    FetchPipeline::with_strategies(strategies)
+}
+
+// ============================================================================
+// CLI Config
+// ============================================================================
+
+fn litellm_cli_config() -> CliConfig {
+    CliConfig {
+        name: "litellm",
+        aliases: &["litellm-proxy"],
+        version_args: &["--version"],
+        usage_args: &[],
+    }
+}
@@ -0,0 +1,37 @@
+//! LiteLLM-specific errors.
+
+use thiserror::Error;
+
+/// LiteLLM proxy errors.
+#[derive(Debug, Error)]
+pub enum LiteLlmError {
+    /// Virtual key not found in keychain or environment.
+    #[error("Virtual key not found (set LITELLM_API_KEY env var)")]
+    ApiKeyNotFound,
+
+    /// Proxy base URL not configured.
+    #[error("Proxy URL not configured (set it in Settings or LITELLM_BASE_URL)")]
+    BaseUrlNotFound,
+
+    /// HTTP request failed.
+    #[error("HTTP error: {0}")]
+    HttpError(String),
+
+    /// Parse error.
+    #[error("Parse error: {0}")]
+    ParseError(String),
+
+    /// API error.
+    #[error("API error: {0}")]
+    ApiError(String),
+
+    /// Authentication failed.
+    #[error("Authentication failed: {0}")]
+    AuthenticationFailed(String),
+}
+
+impl From<reqwest::Error> for LiteLlmError {
+    fn from(err: reqwest::Error) -> Self {
+        LiteLlmError::HttpError(err.to_string())
+    }
+}
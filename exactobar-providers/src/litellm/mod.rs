@@ -0,0 +1,21 @@
+//! LiteLLM proxy provider implementation.
+//!
+//! LiteLLM is a self-hosted gateway that enforces spend budgets per
+//! virtual key, user and team. The provider reads the key's budget from
+//! `/key/info`, then the owning user's and team's budgets.
+//!
+//! The proxy URL comes from the provider's `base_url` setting or
+//! `LITELLM_BASE_URL`. The virtual key is read from the keychain
+//! (`litellm`) or `LITELLM_API_KEY`.
+
+mod api;
+mod descriptor;
+mod error;
+mod strategies;
+
+pub use api::{
+    LiteLlmApiClient, LiteLlmBudget, LiteLlmKeyInfo, LiteLlmTeamInfo, LiteLlmUsage, LiteLlmUserInfo,
+};
+pub use descriptor::litellm_descriptor;
+pub use error::LiteLlmError;
+pub use strategies::LiteLlmApiStrategy;
@@ -0,0 +1,98 @@
+//! LiteLLM fetch strategies.
+
+use async_trait::async_trait;
+use exactobar_core::ProviderKind;
+use exactobar_fetch::{FetchContext, FetchError, FetchKind, FetchResult, FetchStrategy};
+use tracing::{debug, instrument};
+
+use super::api::LiteLlmApiClient;
+use super::error::LiteLlmError;
+
+// ============================================================================
+// API Key Strategy
+// ============================================================================
+
+/// Virtual key strategy for a LiteLLM proxy.
+///
+/// Needs both a virtual key and the proxy URL, from the provider's
+/// `base_url` setting or `LITELLM_BASE_URL`.
+pub struct LiteLlmApiStrategy;
+
+impl LiteLlmApiStrategy {
+    /// Creates a new strategy.
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn base_url(ctx: &FetchContext) -> Result<String, LiteLlmError> {
+        let options = ctx.provider_options(ProviderKind::LiteLlm);
+        LiteLlmApiClient::resolve_base_url(options.base_url.as_deref())
+    }
+}
+
+impl Default for LiteLlmApiStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FetchStrategy for LiteLlmApiStrategy {
+    fn id(&self) -> &str {
+        "litellm.api"
+    }
+
+    fn kind(&self) -> FetchKind {
+        FetchKind::ApiKey
+    }
+
+    #[instrument(skip(self, ctx))]
+    async fn is_available(&self, ctx: &FetchContext) -> bool {
+        Self::base_url(ctx).is_ok() && LiteLlmApiClient::get_api_key().is_ok()
+    }
+
+    #[instrument(skip(self, ctx))]
+    async fn fetch(&self, ctx: &FetchContext) -> Result<FetchResult, FetchError> {
+        debug!("Fetching LiteLLM budgets via virtual key");
+
+        let base_url =
+            Self::base_url(ctx).map_err(|e| FetchError::StrategyNotAvailable(e.to_string()))?;
+        let api_key = LiteLlmApiClient::get_api_key()
+            .map_err(|e| FetchError::AuthenticationFailed(e.to_string()))?;
+
+        let usage = LiteLlmApiClient::new(base_url)
+            .fetch_usage(&api_key)
+            .await
+            .map_err(|e| match e {
+                LiteLlmError::AuthenticationFailed(msg) => FetchError::AuthenticationFailed(msg),
+                other => FetchError::InvalidResponse(other.to_string()),
+            })?;
+
+        Ok(FetchResult::new(
+            usage.to_snapshot(),
+            self.id(),
+            self.kind(),
+        ))
+    }
+
+    fn priority(&self) -> u32 {
+        60 // API Key priority
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_strategy() {
+        let s = LiteLlmApiStrategy::new();
+        assert_eq!(s.id(), "litellm.api");
+        assert_eq!(s.kind(), FetchKind::ApiKey);
+        assert_eq!(s.priority(), 60);
+    }
+}
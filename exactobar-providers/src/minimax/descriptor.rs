@@ -2,6 +2,7 @@
 
 use exactobar_core::{IconStyle, ProviderBranding, ProviderColor, ProviderKind, ProviderMetadata};
 use exactobar_fetch::{FetchContext, FetchPipeline, SourceMode};
+use std::collections::HashMap;
 
 use super::strategies::{
     HailuoaiWebStrategy, MiniMaxLocalStorageStrategy, MiniMaxLocalStrategy, MiniMaxWebStrategy,
@@ -27,6 +28,7 @@ fn minimax_metadata() -> ProviderMetadata {
         weekly_label: "Monthly".to_string(),
         opus_label: None,
         supports_opus: false,
+        window_labels: HashMap::new(),
         supports_credits: true,
         credits_hint: "MiniMax credits".to_string(),
         toggle_title: "Show MiniMax usage".to_string(),
@@ -52,7 +54,7 @@ fn minimax_branding() -> ProviderBranding {
 fn minimax_fetch_plan() -> FetchPlan {
     FetchPlan {
         source_modes: vec![SourceMode::Web, SourceMode::Auto],
-        build_pipeline: build_minimax_pipeline,
+        build_pipeline: Box::new(build_minimax_pipeline),
     }
 }
 
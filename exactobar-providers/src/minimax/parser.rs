@@ -1,12 +1,15 @@
 //! MiniMax response parser.
 
 use exactobar_core::{
-    FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageSnapshot, UsageWindow,
+    Amount, Credits, FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageSnapshot,
+    UsageWindow,
 };
 use exactobar_fetch::FetchError;
 use serde::Deserialize;
 use tracing::debug;
 
+use super::web::MINIMAX_CURRENCY;
+
 #[derive(Debug, Deserialize)]
 pub struct MiniMaxUsageResponse {
     #[serde(default)]
@@ -27,6 +30,8 @@ pub struct MiniMaxTokens {
 pub struct MiniMaxCredits {
     pub used: Option<f64>,
     pub total: Option<f64>,
+    #[serde(default)]
+    pub currency: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -52,7 +57,7 @@ pub fn parse_minimax_response(json_str: &str) -> Result<UsageSnapshot, FetchErro
             } else {
                 0.0
             };
-            snapshot.primary = Some(UsageWindow::new(percent));
+            snapshot.set_primary(Some(UsageWindow::new(percent)));
         }
     }
 
@@ -64,7 +69,13 @@ pub fn parse_minimax_response(json_str: &str) -> Result<UsageSnapshot, FetchErro
             } else {
                 0.0
             };
-            snapshot.secondary = Some(UsageWindow::new(percent));
+            snapshot.set_secondary(Some(UsageWindow::new(percent)));
+
+            let currency = credits.currency.as_deref().unwrap_or(MINIMAX_CURRENCY);
+            snapshot.credits = Some(
+                Credits::from_amount(Amount::new((total - used).max(0.0), currency))
+                    .with_total(total),
+            );
         }
     }
 
@@ -92,16 +103,20 @@ mod tests {
             "user": {"email": "user@example.com"}
         }"#;
         let snapshot = parse_minimax_response(json).unwrap();
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.unwrap().used_percent, 50.0);
-        assert!(snapshot.secondary.is_some());
-        assert_eq!(snapshot.secondary.unwrap().used_percent, 25.0);
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 50.0);
+        assert!(snapshot.secondary().is_some());
+        assert_eq!(snapshot.secondary().unwrap().used_percent, 25.0);
+
+        let credits = snapshot.credits.unwrap();
+        assert_eq!(credits.remaining, 75.0);
+        assert_eq!(credits.unit, "CNY");
     }
 
     #[test]
     fn test_parse_empty() {
         let json = r#"{}"#;
         let snapshot = parse_minimax_response(json).unwrap();
-        assert!(snapshot.primary.is_none());
+        assert!(snapshot.primary().is_none());
     }
 }
@@ -9,7 +9,8 @@
 use std::path::PathBuf;
 
 use exactobar_core::{
-    FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageSnapshot, UsageWindow,
+    Amount, Credits, FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageSnapshot,
+    UsageWindow,
 };
 use reqwest::header::{ACCEPT, AUTHORIZATION, COOKIE, HeaderMap, HeaderValue, USER_AGENT};
 use serde::Deserialize;
@@ -39,6 +40,9 @@ pub const MINIMAX_DOMAIN: &str = "minimax.chat";
 /// Hailuoai cookie domain (MiniMax's web interface).
 pub const HAILUOAI_DOMAIN: &str = "hailuoai.com";
 
+/// Currency MiniMax bills in when the response doesn't say.
+pub const MINIMAX_CURRENCY: &str = "CNY";
+
 /// Session cookie names for MiniMax.
 const SESSION_COOKIE_NAMES: &[&str] = &["__session", "minimax_session", "session"];
 
@@ -81,6 +85,10 @@ pub struct MiniMaxUsageResponse {
     #[serde(default)]
     pub balance: Option<f64>,
 
+    /// Currency of `balance` and credit amounts.
+    #[serde(default)]
+    pub currency: Option<String>,
+
     /// Reset time.
     #[serde(default, alias = "reset_at")]
     pub reset_at: Option<String>,
@@ -114,15 +122,29 @@ impl MiniMaxUsageResponse {
         None
     }
 
+    /// Convert the account balance (or credit allowance) to credits.
+    pub fn to_credits(&self) -> Option<Credits> {
+        let currency = self.currency.as_deref().unwrap_or(MINIMAX_CURRENCY);
+
+        if let Some(balance) = self.balance {
+            return Some(Credits::from_amount(Amount::new(balance, currency)));
+        }
+
+        let (used, limit) = (self.credits_used?, self.credit_limit?);
+        Some(Credits::from_amount(Amount::new((limit - used).max(0.0), currency)).with_total(limit))
+    }
+
     /// Convert to UsageSnapshot.
     pub fn to_snapshot(&self, source: FetchSource) -> UsageSnapshot {
         let mut snapshot = UsageSnapshot::new();
         snapshot.fetch_source = source;
 
         if let Some(percent) = self.get_percent() {
-            snapshot.primary = Some(UsageWindow::new(percent));
+            snapshot.set_primary(Some(UsageWindow::new(percent)));
         }
 
+        snapshot.credits = self.to_credits();
+
         let mut identity = ProviderIdentity::new(ProviderKind::MiniMax);
         identity.account_email = self.email.clone();
         identity.plan_name = self.plan.clone();
@@ -686,6 +708,22 @@ mod tests {
 
         let response: MiniMaxUsageResponse = serde_json::from_str(json).unwrap();
         assert_eq!(response.get_percent(), Some(50.0));
+
+        let credits = response.to_credits().unwrap();
+        assert_eq!(credits.remaining, 50.0);
+        assert_eq!(credits.unit, MINIMAX_CURRENCY);
+    }
+
+    #[test]
+    fn test_balance_currency() {
+        let json = r#"{"balance": 12.5, "currency": "USD"}"#;
+        let response: MiniMaxUsageResponse = serde_json::from_str(json).unwrap();
+        let credits = response.to_snapshot(FetchSource::Api).credits.unwrap();
+        assert_eq!(credits.remaining_amount(), Amount::usd(12.5));
+
+        let json = r#"{"balance": 88.0}"#;
+        let response: MiniMaxUsageResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.to_credits().unwrap().unit, "CNY");
     }
 
     #[test]
@@ -696,14 +734,15 @@ mod tests {
             credits_used: None,
             credit_limit: None,
             balance: None,
+            currency: None,
             reset_at: None,
             plan: Some("pro".to_string()),
             email: Some("user@example.com".to_string()),
         };
 
         let snapshot = response.to_snapshot(FetchSource::Web);
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.unwrap().used_percent, 50.0);
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 50.0);
     }
 
     #[test]
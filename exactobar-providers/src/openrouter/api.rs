@@ -0,0 +1,423 @@
+//! OpenRouter API client.
+//!
+//! # API Endpoints
+//!
+//! ```text
+//! GET https://openrouter.ai/api/v1/key
+//! GET https://openrouter.ai/api/v1/credits
+//! Authorization: Bearer sk-or-v1-...
+//! ```
+//!
+//! # Response Format
+//!
+//! ```json
+//! {"data": {"label": "sk-or-v1-abc...", "limit": 50, "limit_remaining": 38.5,
+//!           "limit_reset": "monthly", "usage": 120.25, "usage_monthly": 11.5,
+//!           "is_free_tier": false}}
+//! {"data": {"total_credits": 200, "total_usage": 120.25}}
+//! ```
+//!
+//! All amounts are in US dollars.
+
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Utc};
+use exactobar_core::{
+    Amount, Credits, FetchSource, LoginMethod, ProviderIdentity, ProviderKind, SpendLimit, USD,
+    UsageSnapshot, UsageWindow, WindowKind,
+};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use tracing::{debug, instrument};
+
+use super::error::OpenRouterError;
+use crate::spend::start_of_next_month;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// OpenRouter API base URL.
+pub const API_BASE_URL: &str = "https://openrouter.ai/api/v1";
+
+/// Current key endpoint.
+pub const KEY_ENDPOINT: &str = "/key";
+
+/// Account credits endpoint.
+pub const CREDITS_ENDPOINT: &str = "/credits";
+
+// ============================================================================
+// API Response Types
+// ============================================================================
+
+/// `{"data": ...}` envelope used by every endpoint.
+#[derive(Debug, Clone, Deserialize)]
+struct Envelope<T> {
+    data: T,
+}
+
+/// Information about the API key in use.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct OpenRouterKeyInfo {
+    /// Key label (usually a redacted key prefix).
+    pub label: Option<String>,
+    /// Spend limit in USD, if one is set.
+    pub limit: Option<f64>,
+    /// Remaining spend before the limit.
+    pub limit_remaining: Option<f64>,
+    /// Limit reset period: "daily", "weekly", "monthly" or none.
+    pub limit_reset: Option<String>,
+    /// All-time spend on this key.
+    pub usage: f64,
+    /// Spend today (UTC).
+    pub usage_daily: Option<f64>,
+    /// Spend this week (UTC, from Monday).
+    pub usage_weekly: Option<f64>,
+    /// Spend this month (UTC).
+    pub usage_monthly: Option<f64>,
+    /// Whether the account has never purchased credits.
+    pub is_free_tier: bool,
+}
+
+/// Account credit totals.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct OpenRouterCredits {
+    /// Credits purchased, in USD.
+    pub total_credits: f64,
+    /// Credits used, in USD.
+    pub total_usage: f64,
+}
+
+impl OpenRouterCredits {
+    /// Returns the remaining balance.
+    pub fn remaining(&self) -> f64 {
+        (self.total_credits - self.total_usage).max(0.0)
+    }
+}
+
+impl OpenRouterKeyInfo {
+    /// Returns spend counted against the limit in the current period.
+    pub fn period_usage(&self) -> f64 {
+        if let (Some(limit), Some(remaining)) = (self.limit, self.limit_remaining) {
+            return (limit - remaining).max(0.0);
+        }
+        match self.limit_reset.as_deref() {
+            Some("daily") => self.usage_daily,
+            Some("weekly") => self.usage_weekly,
+            Some("monthly") => self.usage_monthly,
+            _ => None,
+        }
+        .unwrap_or(self.usage)
+    }
+
+    /// Returns the next limit reset time, if the limit resets.
+    pub fn resets_at(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let midnight = |date: chrono::NaiveDate| date.and_time(NaiveTime::MIN).and_utc();
+        match self.limit_reset.as_deref()? {
+            "daily" => Some(midnight(now.date_naive() + Duration::days(1))),
+            "weekly" => {
+                let days = 7 - i64::from(now.weekday().num_days_from_monday());
+                Some(midnight(now.date_naive() + Duration::days(days)))
+            }
+            "monthly" => Some(start_of_next_month(now)),
+            _ => None,
+        }
+    }
+
+    /// Builds the key limit window, if the key has a limit.
+    pub fn to_window(&self, now: DateTime<Utc>) -> Option<UsageWindow> {
+        let limit = self.limit.filter(|l| *l > 0.0)?;
+        let used = self.period_usage();
+        let percent = (used / limit * 100.0).clamp(0.0, 100.0);
+
+        let (kind, window_minutes) = match self.limit_reset.as_deref() {
+            Some("daily") => (WindowKind::Session, Some(24 * 60)),
+            Some("weekly") => (WindowKind::Weekly, Some(7 * 24 * 60)),
+            Some("monthly") => (WindowKind::Monthly, None),
+            _ => (WindowKind::Other, None),
+        };
+
+        Some(UsageWindow {
+            window_minutes,
+            resets_at: self.resets_at(now),
+            ..UsageWindow::named("key_limit", kind, percent)
+                .with_label("Key limit")
+                .with_counts(used, Some(limit), USD)
+        })
+    }
+}
+
+/// Builds a snapshot from key info and, if available, account credits.
+///
+/// The key limit is the primary window and the spend limit on the credits.
+/// Keys without a limit fall back to a window of credits used out of
+/// credits purchased.
+pub fn to_snapshot(
+    key: &OpenRouterKeyInfo,
+    credits: Option<&OpenRouterCredits>,
+    now: DateTime<Utc>,
+) -> UsageSnapshot {
+    let mut snapshot = UsageSnapshot::new();
+    snapshot.fetch_source = FetchSource::Api;
+
+    let key_window = key.to_window(now);
+
+    let balance = credits.map(|c| {
+        let balance = Credits::from_amount(Amount::usd(c.remaining())).with_total(c.total_credits);
+        match key.limit.filter(|l| *l > 0.0) {
+            Some(limit) => {
+                let mut spend =
+                    SpendLimit::new(Amount::usd(key.period_usage())).with_cap(Amount::usd(limit));
+                spend.resets_at = key.resets_at(now);
+                balance.with_spend_limit(spend)
+            }
+            None => balance,
+        }
+    });
+
+    let primary = key_window.or_else(|| {
+        credits.filter(|c| c.total_credits > 0.0).map(|c| {
+            let percent = (c.total_usage / c.total_credits * 100.0).clamp(0.0, 100.0);
+            UsageWindow::named("credits", WindowKind::Other, percent)
+                .with_label("Credits")
+                .with_counts(c.total_usage, Some(c.total_credits), USD)
+        })
+    });
+    snapshot.set_primary(primary);
+    snapshot.credits = balance;
+
+    let mut identity = ProviderIdentity::new(ProviderKind::OpenRouter);
+    identity.plan_name = Some(if key.is_free_tier { "Free" } else { "Paid" }.to_string());
+    identity.login_method = Some(LoginMethod::ApiKey);
+    snapshot.identity = Some(identity);
+
+    snapshot
+}
+
+// ============================================================================
+// API Client
+// ============================================================================
+
+/// OpenRouter API client.
+#[derive(Debug, Clone)]
+pub struct OpenRouterApiClient {
+    base_url: String,
+}
+
+impl Default for OpenRouterApiClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OpenRouterApiClient {
+    /// Creates a new client.
+    pub fn new() -> Self {
+        Self {
+            base_url: API_BASE_URL.to_string(),
+        }
+    }
+
+    /// Creates a client with a custom base URL.
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+        }
+    }
+
+    /// Get API key from Keychain first, then environment variable.
+    ///
+    /// The lookup order is:
+    /// 1. System keychain (stored via Settings UI)
+    /// 2. Environment variable `OPENROUTER_API_KEY`
+    pub fn get_api_key() -> Result<String, OpenRouterError> {
+        if let Some(key) =
+            exactobar_store::get_api_key(exactobar_store::keychain::providers::OPENROUTER)
+        {
+            return Ok(key);
+        }
+
+        std::env::var("OPENROUTER_API_KEY").map_err(|_| OpenRouterError::ApiKeyNotFound)
+    }
+
+    /// Fetches information about the API key.
+    #[instrument(skip(self, api_key))]
+    pub async fn fetch_key_info(
+        &self,
+        api_key: &str,
+    ) -> Result<OpenRouterKeyInfo, OpenRouterError> {
+        self.get(api_key, KEY_ENDPOINT).await
+    }
+
+    /// Fetches the account credit balance.
+    #[instrument(skip(self, api_key))]
+    pub async fn fetch_credits(&self, api_key: &str) -> Result<OpenRouterCredits, OpenRouterError> {
+        self.get(api_key, CREDITS_ENDPOINT).await
+    }
+
+    /// Sends an authenticated GET request and unwraps the `data` envelope.
+    async fn get<T: DeserializeOwned>(
+        &self,
+        api_key: &str,
+        endpoint: &str,
+    ) -> Result<T, OpenRouterError> {
+        let url = format!("{}{}", self.base_url, endpoint);
+
+        debug!(url = %url, "Fetching OpenRouter data");
+
+        let client = reqwest::Client::new();
+        let response = client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .header("Content-Type", "application/json")
+            .send()
+            .await?;
+
+        let status = response.status();
+
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(OpenRouterError::AuthenticationFailed(
+                "API key rejected".to_string(),
+            ));
+        }
+
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(OpenRouterError::ApiError(format!(
+                "HTTP {}: {}",
+                status, text
+            )));
+        }
+
+        response
+            .json::<Envelope<T>>()
+            .await
+            .map(|envelope| envelope.data)
+            .map_err(|e| OpenRouterError::ParseError(e.to_string()))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const KEY_JSON: &str = r#"{
+        "data": {
+            "label": "sk-or-v1-abc...123",
+            "limit": 50,
+            "limit_remaining": 38.5,
+            "limit_reset": "monthly",
+            "usage": 120.25,
+            "usage_daily": 1.0,
+            "usage_weekly": 4.0,
+            "usage_monthly": 11.5,
+            "is_free_tier": false,
+            "rate_limit": {"requests": 1000, "interval": "10s"}
+        }
+    }"#;
+
+    const CREDITS_JSON: &str = r#"{"data": {"total_credits": 200, "total_usage": 120.25}}"#;
+
+    /// Wednesday 2025-01-15 10:00 UTC.
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap()
+    }
+
+    fn parse<T: DeserializeOwned>(json: &str) -> T {
+        serde_json::from_str::<Envelope<T>>(json).unwrap().data
+    }
+
+    #[test]
+    fn test_client_creation() {
+        let client = OpenRouterApiClient::new();
+        assert_eq!(client.base_url, API_BASE_URL);
+    }
+
+    #[test]
+    fn test_key_limit_window() {
+        let key: OpenRouterKeyInfo = parse(KEY_JSON);
+        let window = key.to_window(now()).unwrap();
+
+        assert_eq!(window.id, "key_limit");
+        assert_eq!(window.kind, WindowKind::Monthly);
+        assert!((window.used.unwrap() - 11.5).abs() < 0.001);
+        assert!((window.used_percent - 23.0).abs() < 0.001);
+        assert_eq!(
+            window.resets_at,
+            Some(Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_reset_periods() {
+        let mut key = OpenRouterKeyInfo {
+            limit_reset: Some("daily".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            key.resets_at(now()),
+            Some(Utc.with_ymd_and_hms(2025, 1, 16, 0, 0, 0).unwrap())
+        );
+
+        key.limit_reset = Some("weekly".to_string());
+        assert_eq!(
+            key.resets_at(now()),
+            Some(Utc.with_ymd_and_hms(2025, 1, 20, 0, 0, 0).unwrap())
+        );
+
+        key.limit_reset = None;
+        assert!(key.resets_at(now()).is_none());
+    }
+
+    #[test]
+    fn test_snapshot_with_limit_and_credits() {
+        let key: OpenRouterKeyInfo = parse(KEY_JSON);
+        let credits: OpenRouterCredits = parse(CREDITS_JSON);
+        let snapshot = to_snapshot(&key, Some(&credits), now());
+
+        assert_eq!(snapshot.primary().unwrap().id, "key_limit");
+
+        let balance = snapshot.credits.as_ref().unwrap();
+        assert!((balance.remaining - 79.75).abs() < 0.001);
+        assert_eq!(balance.total, Some(200.0));
+        assert_eq!(balance.unit, "USD");
+
+        let spend = balance.spend_limit.as_ref().unwrap();
+        assert!((spend.used_percent().unwrap() - 23.0).abs() < 0.001);
+        assert!(snapshot.validate().is_ok());
+    }
+
+    #[test]
+    fn test_snapshot_without_limit_uses_credits() {
+        let key = OpenRouterKeyInfo {
+            usage: 5.0,
+            is_free_tier: true,
+            ..Default::default()
+        };
+        let credits: OpenRouterCredits = parse(CREDITS_JSON);
+        let snapshot = to_snapshot(&key, Some(&credits), now());
+
+        let window = snapshot.primary().unwrap();
+        assert_eq!(window.id, "credits");
+        assert!((window.used_percent - 60.125).abs() < 0.001);
+        assert!(snapshot.credits.as_ref().unwrap().spend_limit.is_none());
+        assert_eq!(
+            snapshot.identity.as_ref().unwrap().plan_name.as_deref(),
+            Some("Free")
+        );
+    }
+
+    #[test]
+    fn test_snapshot_key_only() {
+        let key: OpenRouterKeyInfo = parse(KEY_JSON);
+        let snapshot = to_snapshot(&key, None, now());
+
+        assert_eq!(snapshot.primary().unwrap().id, "key_limit");
+        assert!(snapshot.credits.is_none());
+    }
+}
@@ -0,0 +1,101 @@
+//! OpenRouter provider descriptor.
+
+use exactobar_core::{IconStyle, ProviderBranding, ProviderColor, ProviderKind, ProviderMetadata};
+use exactobar_fetch::{FetchContext, FetchPipeline, SourceMode};
+use std::collections::HashMap;
+
+use super::strategies::OpenRouterApiStrategy;
+use crate::descriptor::{CliConfig, FetchPlan, ProviderDescriptor, TokenCostConfig};
+
+// ============================================================================
+// Descriptor
+// ============================================================================
+
+/// Creates the OpenRouter provider descriptor.
+pub fn openrouter_descriptor() -> ProviderDescriptor {
+    ProviderDescriptor {
+        id: ProviderKind::OpenRouter,
+        metadata: openrouter_metadata(),
+        branding: openrouter_branding(),
+        token_cost: TokenCostConfig::default(),
+        fetch_plan: openrouter_fetch_plan(),
+        cli: openrouter_cli_config(),
+    }
+}
+
+// ============================================================================
+// Metadata
+// ============================================================================
+
+fn openrouter_metadata() -> ProviderMetadata {
+    ProviderMetadata {
+        id: ProviderKind::OpenRouter,
+        display_name: "OpenRouter".to_string(),
+        session_label: "Key limit".to_string(),
+        weekly_label: "Credits".to_string(),
+        opus_label: None,
+        supports_opus: false,
+        window_labels: HashMap::from([
+            ("key_limit".to_string(), "Key limit".to_string()),
+            ("credits".to_string(), "Credits".to_string()),
+        ]),
+        supports_credits: true,
+        credits_hint: "OpenRouter credit balance".to_string(),
+        toggle_title: "Show OpenRouter usage".to_string(),
+        cli_name: "openrouter".to_string(),
+        default_enabled: false,
+        is_primary_provider: false,
+        uses_account_fallback: false,
+        dashboard_url: Some("https://openrouter.ai/activity".to_string()),
+        subscription_dashboard_url: Some("https://openrouter.ai/settings/credits".to_string()),
+        status_page_url: Some("https://status.openrouter.ai/api/v2/status.json".to_string()),
+        status_link_url: Some("https://status.openrouter.ai".to_string()),
+    }
+}
+
+// ============================================================================
+// Branding
+// ============================================================================
+
+fn openrouter_branding() -> ProviderBranding {
+    ProviderBranding {
+        icon_style: IconStyle::OpenRouter,
+        icon_resource_name: "icon_openrouter".to_string(),
+        // OpenRouter brand indigo
+        color: ProviderColor::new(0.39, 0.4, 0.95),
+    }
+}
+
+// ============================================================================
+// Fetch Plan
+// ============================================================================
+
+fn openrouter_fetch_plan() -> FetchPlan {
+    FetchPlan {
+        source_modes: vec![SourceMode::ApiKey],
+        build_pipeline: Box::new(build_openrouter_pipeline),
+    }
+}
+
+fn build_openrouter_pipeline(ctx: &FetchContext) -> FetchPipeline {
+    let mut strategies: Vec<Box<dyn exactobar_fetch::FetchStrategy>> = Vec::new();
+
+    if ctx.settings.source_mode.allows_api_key() {
+        strategies.push(Box::new(OpenRouterApiStrategy::new()));
+    }
+
+    FetchPipeline::with_strategies(strategies)
+}
+
+// ============================================================================
+// CLI Config
+// ============================================================================
+
+fn openrouter_cli_config() -> CliConfig {
+    CliConfig {
+        name: "openrouter",
+        aliases: &["or"],
+        version_args: &["--version"],
+        usage_args: &["usage"],
+    }
+}
@@ -0,0 +1,33 @@
+//! OpenRouter-specific errors.
+
+use thiserror::Error;
+
+/// OpenRouter-specific errors.
+#[derive(Debug, Error)]
+pub enum OpenRouterError {
+    /// API key not found in keychain or environment.
+    #[error("API key not found (set OPENROUTER_API_KEY env var)")]
+    ApiKeyNotFound,
+
+    /// HTTP request failed.
+    #[error("HTTP error: {0}")]
+    HttpError(String),
+
+    /// Parse error.
+    #[error("Parse error: {0}")]
+    ParseError(String),
+
+    /// API error.
+    #[error("API error: {0}")]
+    ApiError(String),
+
+    /// Authentication failed.
+    #[error("Authentication failed: {0}")]
+    AuthenticationFailed(String),
+}
+
+impl From<reqwest::Error> for OpenRouterError {
+    fn from(err: reqwest::Error) -> Self {
+        OpenRouterError::HttpError(err.to_string())
+    }
+}
@@ -0,0 +1,17 @@
+//! OpenRouter provider implementation.
+//!
+//! OpenRouter routes requests to many model vendors behind one prepaid
+//! credit balance. API keys can carry their own spend limit, optionally
+//! resetting daily, weekly or monthly.
+//!
+//! The key is read from the keychain (`openrouter`) or `OPENROUTER_API_KEY`.
+
+mod api;
+mod descriptor;
+mod error;
+mod strategies;
+
+pub use api::{OpenRouterApiClient, OpenRouterCredits, OpenRouterKeyInfo};
+pub use descriptor::openrouter_descriptor;
+pub use error::OpenRouterError;
+pub use strategies::OpenRouterApiStrategy;
@@ -0,0 +1,94 @@
+//! OpenRouter fetch strategies.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use exactobar_fetch::{FetchContext, FetchError, FetchKind, FetchResult, FetchStrategy};
+use tracing::{debug, instrument, warn};
+
+use super::api::{OpenRouterApiClient, to_snapshot};
+use super::error::OpenRouterError;
+
+// ============================================================================
+// API Key Strategy
+// ============================================================================
+
+/// API key strategy for OpenRouter.
+///
+/// Reads the key limit from `/key` and the account balance from `/credits`.
+/// The balance is best-effort, since some keys can't read it.
+pub struct OpenRouterApiStrategy;
+
+impl OpenRouterApiStrategy {
+    /// Creates a new strategy.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for OpenRouterApiStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FetchStrategy for OpenRouterApiStrategy {
+    fn id(&self) -> &str {
+        "openrouter.api"
+    }
+
+    fn kind(&self) -> FetchKind {
+        FetchKind::ApiKey
+    }
+
+    #[instrument(skip(self, _ctx))]
+    async fn is_available(&self, _ctx: &FetchContext) -> bool {
+        OpenRouterApiClient::get_api_key().is_ok()
+    }
+
+    #[instrument(skip(self, _ctx))]
+    async fn fetch(&self, _ctx: &FetchContext) -> Result<FetchResult, FetchError> {
+        debug!("Fetching OpenRouter usage via API key");
+
+        let api_key = OpenRouterApiClient::get_api_key()
+            .map_err(|e| FetchError::AuthenticationFailed(e.to_string()))?;
+
+        let client = OpenRouterApiClient::new();
+        let key = client.fetch_key_info(&api_key).await.map_err(|e| match e {
+            OpenRouterError::AuthenticationFailed(msg) => FetchError::AuthenticationFailed(msg),
+            other => FetchError::InvalidResponse(other.to_string()),
+        })?;
+
+        let credits = match client.fetch_credits(&api_key).await {
+            Ok(credits) => Some(credits),
+            Err(e) => {
+                warn!(error = %e, "Failed to fetch OpenRouter credits");
+                None
+            }
+        };
+
+        let snapshot = to_snapshot(&key, credits.as_ref(), Utc::now());
+        Ok(FetchResult::new(snapshot, self.id(), self.kind()))
+    }
+
+    fn priority(&self) -> u32 {
+        60 // API Key priority
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_strategy() {
+        let s = OpenRouterApiStrategy::new();
+        assert_eq!(s.id(), "openrouter.api");
+        assert_eq!(s.kind(), FetchKind::ApiKey);
+        assert_eq!(s.priority(), 60);
+    }
+}
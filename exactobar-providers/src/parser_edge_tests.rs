@@ -20,7 +20,7 @@ mod claude_parser_edge_tests {
         let result = parse_claude_api_response(json);
         assert!(result.is_ok());
         let snapshot = result.unwrap();
-        assert!(snapshot.primary.is_none());
+        assert!(snapshot.primary().is_none());
     }
 
     #[test]
@@ -51,7 +51,7 @@ mod claude_parser_edge_tests {
         let result = parse_claude_api_response(json);
         assert!(result.is_ok());
         let snapshot = result.unwrap();
-        assert!(snapshot.primary.is_some());
+        assert!(snapshot.primary().is_some());
     }
 
     #[test]
@@ -59,17 +59,17 @@ mod claude_parser_edge_tests {
         // Test 0%
         let json = r#"{"session": {"used_percent": 0.0}}"#;
         let result = parse_claude_api_response(json).unwrap();
-        assert_eq!(result.primary.as_ref().unwrap().used_percent, 0.0);
+        assert_eq!(result.primary().unwrap().used_percent, 0.0);
 
         // Test 100%
         let json = r#"{"session": {"used_percent": 100.0}}"#;
         let result = parse_claude_api_response(json).unwrap();
-        assert_eq!(result.primary.as_ref().unwrap().used_percent, 100.0);
+        assert_eq!(result.primary().unwrap().used_percent, 100.0);
 
         // Test over 100% (should still parse)
         let json = r#"{"session": {"used_percent": 150.0}}"#;
         let result = parse_claude_api_response(json).unwrap();
-        assert_eq!(result.primary.as_ref().unwrap().used_percent, 150.0);
+        assert_eq!(result.primary().unwrap().used_percent, 150.0);
     }
 
     #[test]
@@ -77,7 +77,7 @@ mod claude_parser_edge_tests {
         // When only remaining is provided, used should be calculated as 100 - remaining
         let json = r#"{"session": {"remaining": 25.0}}"#;
         let result = parse_claude_api_response(json).unwrap();
-        assert_eq!(result.primary.as_ref().unwrap().used_percent, 75.0);
+        assert_eq!(result.primary().unwrap().used_percent, 75.0);
     }
 
     #[test]
@@ -92,7 +92,7 @@ mod claude_parser_edge_tests {
         let result = parse_claude_api_response(json);
         assert!(result.is_ok());
         let snapshot = result.unwrap();
-        assert!(snapshot.primary.as_ref().unwrap().resets_at.is_none());
+        assert!(snapshot.primary().unwrap().resets_at.is_none());
     }
 
     #[test]
@@ -126,7 +126,7 @@ mod claude_parser_edge_tests {
         let result = parse_claude_cli_output(output, false);
         assert!(result.is_ok());
         let snapshot = result.unwrap();
-        assert!(snapshot.primary.is_none());
+        assert!(snapshot.primary().is_none());
     }
 
     #[test]
@@ -144,8 +144,8 @@ mod claude_parser_edge_tests {
         assert!(result.is_ok());
         let snapshot = result.unwrap();
         // Should only match "Session:" with capital S
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.as_ref().unwrap().used_percent, 70.0);
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 70.0);
     }
 
     #[test]
@@ -209,7 +209,7 @@ mod cursor_parser_edge_tests {
         assert!(result.is_ok());
         let snapshot = result.unwrap();
         // Should handle gracefully (0% when limit is 0)
-        assert_eq!(snapshot.primary.as_ref().unwrap().used_percent, 0.0);
+        assert_eq!(snapshot.primary().unwrap().used_percent, 0.0);
     }
 
     #[test]
@@ -224,7 +224,7 @@ mod cursor_parser_edge_tests {
         let result = parse_cursor_api_response(json);
         assert!(result.is_ok());
         let snapshot = result.unwrap();
-        assert_eq!(snapshot.primary.as_ref().unwrap().used_percent, 200.0);
+        assert_eq!(snapshot.primary().unwrap().used_percent, 200.0);
     }
 
     #[test]
@@ -271,7 +271,7 @@ mod codex_parser_edge_tests {
         let result = parse_codex_cli_output(json);
         assert!(result.is_ok());
         let snapshot = result.unwrap();
-        assert!(snapshot.primary.as_ref().unwrap().resets_at.is_some());
+        assert!(snapshot.primary().unwrap().resets_at.is_some());
     }
 
     #[test]
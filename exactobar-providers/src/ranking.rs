@@ -0,0 +1,346 @@
+//! Ranking providers by quota headroom.
+//!
+//! For dispatching work to whichever subscription has room. A provider's
+//! headroom is the remaining share of its tightest window, with credit for
+//! windows about to reset. Status-page incidents subtract from it, and
+//! preferred providers get a small bonus.
+//!
+//! ```ignore
+//! let rankings = Ranker::new()
+//!     .with_prefer(vec![ProviderKind::Claude])
+//!     .rank(&[
+//!         Candidate::new(ProviderKind::Claude, &claude).with_status(claude_status.as_ref()),
+//!         Candidate::new(ProviderKind::Codex, &codex),
+//!     ]);
+//! let best = rankings.first().filter(|r| r.available);
+//! ```
+
+use chrono::{DateTime, Duration, Utc};
+use exactobar_core::{ProviderKind, ProviderStatus, StatusIndicator, UsageSnapshot, UsageWindow};
+use exactobar_fetch::FetchFailure;
+use serde::Serialize;
+use std::cmp::Ordering;
+
+/// Score subtracted per status severity level.
+const STATUS_PENALTY: f64 = 15.0;
+
+/// Score added for providers named in `--prefer`.
+const PREFER_BONUS: f64 = 10.0;
+
+/// Windows resetting within this long get credit for their used share.
+const RESET_HORIZON_MINUTES: i64 = 60;
+
+/// A provider to rank, with its latest usage and status.
+#[derive(Debug, Clone, Copy)]
+pub struct Candidate<'a> {
+    /// The provider.
+    pub provider: ProviderKind,
+    /// Latest usage, or the fetch error.
+    pub usage: Result<&'a UsageSnapshot, &'a str>,
+    /// Status page state, if known.
+    pub status: Option<&'a ProviderStatus>,
+}
+
+impl<'a> Candidate<'a> {
+    /// Creates a candidate from a fetch result.
+    pub fn new(provider: ProviderKind, usage: &'a Result<UsageSnapshot, FetchFailure>) -> Self {
+        Self {
+            provider,
+            usage: usage.as_ref().map_err(|e| e.message.as_str()),
+            status: None,
+        }
+    }
+
+    /// Sets the status page state.
+    pub fn with_status(mut self, status: Option<&'a ProviderStatus>) -> Self {
+        self.status = status;
+        self
+    }
+}
+
+/// One provider's place in the ranking.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ranking {
+    /// The provider.
+    pub provider: ProviderKind,
+    /// Whether work can be sent here now.
+    pub available: bool,
+    /// Ranking score; higher is better.
+    pub score: f64,
+    /// Remaining percent of the tightest window.
+    pub headroom: Option<f64>,
+    /// ID of the tightest window.
+    pub tightest_window: Option<String>,
+    /// When the tightest window resets.
+    pub resets_at: Option<DateTime<Utc>>,
+    /// Status page indicator, if known.
+    pub status: Option<StatusIndicator>,
+    /// Whether the provider was preferred.
+    pub preferred: bool,
+    /// Fetch error, if usage is unknown.
+    pub error: Option<String>,
+    /// Human-readable explanation.
+    pub reason: String,
+    #[serde(skip)]
+    prefer_rank: usize,
+}
+
+/// Ranks candidates by headroom, reset time and status.
+#[derive(Debug, Clone, Default)]
+pub struct Ranker {
+    prefer: Vec<ProviderKind>,
+}
+
+impl Ranker {
+    /// Creates a ranker with no preferences.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prefers these providers, earlier ones first, when others are close.
+    pub fn with_prefer(mut self, prefer: Vec<ProviderKind>) -> Self {
+        self.prefer = prefer;
+        self
+    }
+
+    /// Returns the candidates best first. Unavailable providers come last,
+    /// ordered by which comes back soonest.
+    pub fn rank(&self, candidates: &[Candidate<'_>]) -> Vec<Ranking> {
+        let mut rankings: Vec<Ranking> = candidates.iter().map(|c| self.score(c)).collect();
+        rankings.sort_by(compare);
+        rankings
+    }
+
+    fn score(&self, candidate: &Candidate<'_>) -> Ranking {
+        let prefer_rank = self
+            .prefer
+            .iter()
+            .position(|p| *p == candidate.provider)
+            .unwrap_or(usize::MAX);
+        let status = candidate.status.map(|s| s.indicator);
+        let mut ranking = Ranking {
+            provider: candidate.provider,
+            available: false,
+            score: 0.0,
+            headroom: None,
+            tightest_window: None,
+            resets_at: None,
+            status,
+            preferred: prefer_rank != usize::MAX,
+            error: None,
+            reason: String::new(),
+            prefer_rank,
+        };
+
+        let snapshot = match candidate.usage {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                ranking.error = Some(e.to_string());
+                ranking.reason = format!("usage unavailable: {}", e);
+                return ranking;
+            }
+        };
+        let Some(tightest) = tightest_window(&snapshot.windows) else {
+            ranking.reason = "no usage windows".to_string();
+            return ranking;
+        };
+
+        let headroom = tightest.remaining_percent();
+        let effective = snapshot
+            .windows
+            .iter()
+            .map(effective_headroom)
+            .fold(f64::INFINITY, f64::min);
+        let penalty = status.map_or(0.0, status_penalty);
+        let bonus = if ranking.preferred { PREFER_BONUS } else { 0.0 };
+
+        ranking.headroom = Some(headroom);
+        ranking.tightest_window = Some(tightest.id.clone());
+        ranking.resets_at = tightest.resets_at;
+        ranking.available = headroom > 0.0 && status != Some(StatusIndicator::Critical);
+        ranking.score = effective - penalty + bonus;
+
+        let mut reason = vec![format!("{:.0}% headroom ({})", headroom, tightest.id)];
+        if let Some(until) = tightest.time_until_reset() {
+            reason.push(format!("resets in {}", humanize(until)));
+        }
+        if let Some(status) = status.filter(|s| *s != StatusIndicator::None) {
+            reason.push(status.label().to_lowercase());
+        }
+        if ranking.preferred {
+            reason.push("preferred".to_string());
+        }
+        ranking.reason = reason.join(", ");
+        ranking
+    }
+}
+
+/// Returns the window with the least headroom, the later reset on ties.
+fn tightest_window(windows: &[UsageWindow]) -> Option<&UsageWindow> {
+    windows.iter().min_by(|a, b| {
+        a.remaining_percent()
+            .total_cmp(&b.remaining_percent())
+            .then_with(|| b.resets_at.cmp(&a.resets_at))
+    })
+}
+
+/// Remaining percent, plus part of the used share when the window resets
+/// within the hour.
+fn effective_headroom(window: &UsageWindow) -> f64 {
+    let remaining = window.remaining_percent();
+    let Some(until) = window.time_until_reset() else {
+        return remaining;
+    };
+    let horizon = Duration::minutes(RESET_HORIZON_MINUTES);
+    let soon = 1.0 - until.num_seconds().max(0) as f64 / horizon.num_seconds() as f64;
+    remaining + window.used_percent * soon.clamp(0.0, 1.0) * 0.5
+}
+
+/// Returns the score penalty for a status. Unknown status is not penalized.
+fn status_penalty(indicator: StatusIndicator) -> f64 {
+    match indicator {
+        StatusIndicator::Unknown => 0.0,
+        other => f64::from(other.severity()) * STATUS_PENALTY,
+    }
+}
+
+fn compare(a: &Ranking, b: &Ranking) -> Ordering {
+    b.available.cmp(&a.available).then_with(|| {
+        if a.available {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.prefer_rank.cmp(&b.prefer_rank))
+                .then_with(|| soonest(a.resets_at, b.resets_at))
+        } else {
+            a.error
+                .is_some()
+                .cmp(&b.error.is_some())
+                .then_with(|| soonest(a.resets_at, b.resets_at))
+                .then_with(|| a.prefer_rank.cmp(&b.prefer_rank))
+        }
+    })
+}
+
+/// Orders earlier resets first and unknown resets last.
+fn soonest(a: Option<DateTime<Utc>>, b: Option<DateTime<Utc>>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn humanize(duration: Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    if days > 0 {
+        format!("{}d{}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h{}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exactobar_core::WindowKind;
+    use exactobar_fetch::FetchErrorKind;
+
+    fn snapshot(windows: &[(&str, f64, Option<i64>)]) -> Result<UsageSnapshot, FetchFailure> {
+        let mut snapshot = UsageSnapshot::new();
+        for (id, used, reset_minutes) in windows {
+            let mut window = UsageWindow::named(*id, WindowKind::from_minutes(None), *used);
+            window.resets_at = reset_minutes.map(|m| Utc::now() + Duration::minutes(m));
+            snapshot.push_window(window);
+        }
+        Ok(snapshot)
+    }
+
+    fn order(rankings: &[Ranking]) -> Vec<ProviderKind> {
+        rankings.iter().map(|r| r.provider).collect()
+    }
+
+    #[test]
+    fn test_ranks_by_tightest_window() {
+        let claude = snapshot(&[("session", 10.0, None), ("weekly", 90.0, Some(3000))]);
+        let codex = snapshot(&[("session", 40.0, None), ("weekly", 30.0, None)]);
+        let rankings = Ranker::new().rank(&[
+            Candidate::new(ProviderKind::Claude, &claude),
+            Candidate::new(ProviderKind::Codex, &codex),
+        ]);
+
+        assert_eq!(
+            order(&rankings),
+            [ProviderKind::Codex, ProviderKind::Claude]
+        );
+        assert_eq!(rankings[0].headroom, Some(60.0));
+        assert_eq!(rankings[1].tightest_window.as_deref(), Some("weekly"));
+        assert!(
+            rankings[1]
+                .reason
+                .starts_with("10% headroom (weekly), resets in 2d")
+        );
+    }
+
+    #[test]
+    fn test_reset_soon_and_status() {
+        // 90% used but resetting in 6 minutes beats 40% used.
+        let claude = snapshot(&[("session", 90.0, Some(6))]);
+        let codex = snapshot(&[("session", 60.0, None)]);
+        let candidates = [
+            Candidate::new(ProviderKind::Claude, &claude),
+            Candidate::new(ProviderKind::Codex, &codex),
+        ];
+        assert_eq!(
+            order(&Ranker::new().rank(&candidates)),
+            [ProviderKind::Claude, ProviderKind::Codex]
+        );
+
+        let degraded = ProviderStatus::new(StatusIndicator::Major, "Partial outage");
+        let rankings =
+            Ranker::new().rank(&[candidates[0].with_status(Some(&degraded)), candidates[1]]);
+        assert_eq!(
+            order(&rankings),
+            [ProviderKind::Codex, ProviderKind::Claude]
+        );
+        assert!(rankings[1].reason.ends_with("partial outage"));
+
+        let outage = ProviderStatus::new(StatusIndicator::Critical, "Outage");
+        let rankings = Ranker::new().rank(&[candidates[0].with_status(Some(&outage))]);
+        assert!(!rankings[0].available);
+    }
+
+    #[test]
+    fn test_prefer_and_unavailable() {
+        let claude = snapshot(&[("session", 20.0, None)]);
+        let codex = snapshot(&[("session", 15.0, None)]);
+        let gemini = snapshot(&[("daily", 100.0, Some(120))]);
+        let zai = Err(FetchFailure::new(FetchErrorKind::Auth, "Not logged in"));
+        let candidates = [
+            Candidate::new(ProviderKind::Zai, &zai),
+            Candidate::new(ProviderKind::Gemini, &gemini),
+            Candidate::new(ProviderKind::Codex, &codex),
+            Candidate::new(ProviderKind::Claude, &claude),
+        ];
+
+        let rankings = Ranker::new()
+            .with_prefer(vec![ProviderKind::Claude])
+            .rank(&candidates);
+        assert_eq!(
+            order(&rankings),
+            [
+                ProviderKind::Claude,
+                ProviderKind::Codex,
+                ProviderKind::Gemini,
+                ProviderKind::Zai
+            ]
+        );
+        assert!(rankings[0].preferred);
+        assert!(!rankings[2].available);
+        assert_eq!(rankings[3].error.as_deref(), Some("Not logged in"));
+    }
+}
@@ -2,44 +2,73 @@
 //!
 //! The registry provides static access to all provider configurations
 //! and is the central point for looking up providers.
+//!
+//! User-defined providers from settings are added with
+//! [`ProviderRegistry::register_custom`] before the registry is first used.
+//!
+//! Embedders that need their own providers, or different strategies for
+//! built-in ones, build a [`ProviderSet`] and attach it to a
+//! [`FetchContext`]; [`ProviderRegistry::for_context`] then resolves
+//! providers from it instead of the global registry.
 
 use exactobar_core::ProviderKind;
+use exactobar_fetch::{FetchContext, FetchPipeline, FetchStrategy, SourceMode};
+use exactobar_store::CustomProviderConfig;
 use std::collections::HashMap;
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
+use tracing::{debug, warn};
 
+use crate::anthropic::anthropic_descriptor;
 use crate::antigravity::antigravity_descriptor;
 use crate::augment::augment_descriptor;
+use crate::azure::azure_descriptor;
+use crate::bedrock::bedrock_descriptor;
 use crate::claude::claude_descriptor;
 use crate::codex::codex_descriptor;
 use crate::copilot::copilot_descriptor;
 use crate::cursor::cursor_descriptor;
+use crate::custom::{CustomProvider, custom_descriptor};
 use crate::descriptor::ProviderDescriptor;
 use crate::factory::factory_descriptor;
 use crate::gemini::gemini_descriptor;
 use crate::kiro::kiro_descriptor;
+use crate::litellm::litellm_descriptor;
 use crate::minimax::minimax_descriptor;
+use crate::openrouter::openrouter_descriptor;
 use crate::synthetic::synthetic_descriptor;
 use crate::vertexai::vertexai_descriptor;
+use crate::windsurf::windsurf_descriptor;
 use crate::zai::zai_descriptor;
 
 // ============================================================================
 // Static Registry
 // ============================================================================
 
-/// Static storage for all provider descriptors.
-static DESCRIPTORS: OnceLock<Vec<ProviderDescriptor>> = OnceLock::new();
+/// Static storage for the global registry.
+static GLOBAL: OnceLock<Arc<ProviderSet>> = OnceLock::new();
 
-/// Static storage for CLI name to provider kind mapping.
-static CLI_NAME_MAP: OnceLock<HashMap<String, ProviderKind>> = OnceLock::new();
+/// Static storage for user-defined providers.
+static CUSTOM_PROVIDERS: OnceLock<Vec<Arc<CustomProvider>>> = OnceLock::new();
 
-/// Initializes all provider descriptors.
+/// Initializes the global registry.
 ///
 /// Providers are ordered by priority/importance:
 /// 1. Primary providers (Codex, Claude)
-/// 2. Popular IDE providers (Cursor, Copilot)
-/// 3. Cloud providers (Gemini, VertexAI)
+/// 2. Popular IDE providers (Cursor, Copilot, Windsurf)
+/// 3. Cloud providers (Gemini, VertexAI, Bedrock, Azure)
 /// 4. Other providers (Factory, Zai, Augment, Kiro, MiniMax, Antigravity)
-fn init_descriptors() -> Vec<ProviderDescriptor> {
+/// 5. Billing providers (Anthropic API, OpenRouter, LiteLLM)
+/// 6. Custom providers, in settings order
+fn init_global() -> Arc<ProviderSet> {
+    let mut set = ProviderSet::builtin();
+    for provider in ProviderRegistry::custom_providers() {
+        set.register(custom_descriptor(provider));
+    }
+    Arc::new(set)
+}
+
+/// Creates the built-in provider descriptors.
+fn builtin_descriptors() -> Vec<ProviderDescriptor> {
     vec![
         // Primary providers
         codex_descriptor(),
@@ -47,9 +76,12 @@ fn init_descriptors() -> Vec<ProviderDescriptor> {
         // IDE providers
         cursor_descriptor(),
         copilot_descriptor(),
+        windsurf_descriptor(),
         // Cloud providers
         gemini_descriptor(),
         vertexai_descriptor(),
+        bedrock_descriptor(),
+        azure_descriptor(),
         // Other providers
         factory_descriptor(),
         zai_descriptor(),
@@ -58,6 +90,10 @@ fn init_descriptors() -> Vec<ProviderDescriptor> {
         minimax_descriptor(),
         antigravity_descriptor(),
         synthetic_descriptor(),
+        // Billing providers
+        anthropic_descriptor(),
+        openrouter_descriptor(),
+        litellm_descriptor(),
     ]
 }
 
@@ -89,62 +125,309 @@ fn build_cli_name_map(descriptors: &[ProviderDescriptor]) -> HashMap<String, Pro
 pub struct ProviderRegistry;
 
 impl ProviderRegistry {
+    /// Registers user-defined providers from settings.
+    ///
+    /// Must run before the registry is first used, since descriptors are
+    /// handed out as `'static` references. Invalid definitions and IDs that
+    /// clash with a built-in name or alias are skipped with a warning.
+    ///
+    /// Returns the registered kinds, or `None` if providers were already
+    /// registered or the registry is already in use.
+    pub fn register_custom(configs: &[CustomProviderConfig]) -> Option<Vec<ProviderKind>> {
+        if GLOBAL.get().is_some() {
+            warn!("Provider registry already initialized; custom providers ignored");
+            return None;
+        }
+
+        let reserved = build_cli_name_map(&builtin_descriptors());
+        let mut providers: Vec<Arc<CustomProvider>> = Vec::new();
+        for config in configs {
+            if reserved.contains_key(&config.id) {
+                warn!(id = %config.id, "Custom provider ID clashes with a built-in provider");
+                continue;
+            }
+            if providers.iter().any(|p| p.config().id == config.id) {
+                warn!(id = %config.id, "Duplicate custom provider ID");
+                continue;
+            }
+            match CustomProvider::new(config.clone()) {
+                Ok(provider) => providers.push(Arc::new(provider)),
+                Err(e) => warn!(id = %config.id, error = %e, "Skipping custom provider"),
+            }
+        }
+
+        let kinds: Vec<ProviderKind> = providers.iter().map(|p| p.kind()).collect();
+        CUSTOM_PROVIDERS.set(providers).ok()?;
+        debug!(count = kinds.len(), "Registered custom providers");
+        Some(kinds)
+    }
+
+    /// Returns the registered user-defined providers.
+    pub fn custom_providers() -> &'static [Arc<CustomProvider>] {
+        CUSTOM_PROVIDERS.get_or_init(Vec::new)
+    }
+
+    /// Gets a registered user-defined provider by kind.
+    pub fn custom_provider(id: ProviderKind) -> Option<&'static Arc<CustomProvider>> {
+        Self::custom_providers().iter().find(|p| p.kind() == id)
+    }
+
+    /// Returns the global registry.
+    pub fn global() -> &'static ProviderSet {
+        GLOBAL.get_or_init(init_global)
+    }
+
+    /// Returns a shared handle to the global registry.
+    pub fn shared() -> Arc<ProviderSet> {
+        Arc::clone(GLOBAL.get_or_init(init_global))
+    }
+
+    /// Returns the registry scoped to a context, or the global registry.
+    pub fn for_context(ctx: &FetchContext) -> Arc<ProviderSet> {
+        ProviderSet::from_context(ctx).unwrap_or_else(Self::shared)
+    }
+
     /// Returns all provider descriptors.
     pub fn all() -> &'static [ProviderDescriptor] {
-        DESCRIPTORS.get_or_init(init_descriptors)
+        Self::global().all()
     }
 
     /// Gets a provider descriptor by kind.
     pub fn get(id: ProviderKind) -> Option<&'static ProviderDescriptor> {
-        Self::all().iter().find(|d| d.id == id)
+        Self::global().get(id)
     }
 
     /// Returns the CLI name to provider kind mapping.
     pub fn cli_name_map() -> &'static HashMap<String, ProviderKind> {
-        CLI_NAME_MAP.get_or_init(|| build_cli_name_map(Self::all()))
+        Self::global().cli_name_map()
     }
 
     /// Looks up a provider by CLI name.
     pub fn get_by_cli_name(name: &str) -> Option<&'static ProviderDescriptor> {
-        let kind = Self::cli_name_map().get(name)?;
-        Self::get(*kind)
+        Self::global().get_by_cli_name(name)
     }
 
     /// Returns all enabled-by-default providers.
     pub fn default_enabled() -> Vec<&'static ProviderDescriptor> {
-        Self::all()
+        Self::global().default_enabled()
+    }
+
+    /// Returns all primary providers.
+    pub fn primary_providers() -> Vec<&'static ProviderDescriptor> {
+        Self::global().primary_providers()
+    }
+
+    /// Returns the number of registered providers.
+    pub fn count() -> usize {
+        Self::global().count()
+    }
+
+    /// Returns all provider kinds.
+    pub fn kinds() -> Vec<ProviderKind> {
+        Self::global().kinds()
+    }
+
+    /// Returns providers that support the given source mode.
+    pub fn with_source_mode(mode: SourceMode) -> Vec<&'static ProviderDescriptor> {
+        Self::global().with_source_mode(mode)
+    }
+}
+
+// ============================================================================
+// Provider Set
+// ============================================================================
+
+/// A provider registry instance.
+///
+/// Unlike the global [`ProviderRegistry`], a set can be changed at runtime:
+/// descriptors can be added or replaced, and strategies can be added to or
+/// removed from any provider's pipeline, built-in ones included.
+///
+/// ```ignore
+/// let mut set = ProviderSet::builtin();
+/// set.set_strategy(ProviderKind::Claude, |_| {
+///     Some(Box::new(CompanyGatewayStrategy::new()) as Box<dyn FetchStrategy>)
+/// });
+/// set.remove_strategy(ProviderKind::Claude, "claude.web");
+///
+/// let mut ctx = FetchContext::new();
+/// Arc::new(set).attach(&mut ctx);
+/// let registry = ProviderRegistry::for_context(&ctx);
+/// ```
+pub struct ProviderSet {
+    descriptors: Vec<ProviderDescriptor>,
+    cli_names: HashMap<String, ProviderKind>,
+}
+
+impl ProviderSet {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self {
+            descriptors: Vec::new(),
+            cli_names: HashMap::new(),
+        }
+    }
+
+    /// Creates a registry with the built-in providers.
+    pub fn builtin() -> Self {
+        let descriptors = builtin_descriptors();
+        let cli_names = build_cli_name_map(&descriptors);
+        Self {
+            descriptors,
+            cli_names,
+        }
+    }
+
+    /// Returns the registry attached to a context.
+    pub fn from_context(ctx: &FetchContext) -> Option<Arc<Self>> {
+        ctx.extensions.get::<Self>()
+    }
+
+    /// Attaches this registry to a context, replacing any attached one.
+    pub fn attach(self: &Arc<Self>, ctx: &mut FetchContext) {
+        ctx.extensions.insert(Arc::clone(self));
+    }
+
+    /// Registers a descriptor, replacing any with the same kind.
+    ///
+    /// A replacement keeps its position; a new provider goes last. Returns
+    /// the replaced descriptor.
+    pub fn register(&mut self, descriptor: ProviderDescriptor) -> Option<ProviderDescriptor> {
+        let existing = self.descriptors.iter().position(|d| d.id == descriptor.id);
+        let replaced = if let Some(index) = existing {
+            Some(std::mem::replace(&mut self.descriptors[index], descriptor))
+        } else {
+            self.descriptors.push(descriptor);
+            None
+        };
+        self.cli_names = build_cli_name_map(&self.descriptors);
+        replaced
+    }
+
+    /// Removes a provider, returning its descriptor.
+    pub fn unregister(&mut self, id: ProviderKind) -> Option<ProviderDescriptor> {
+        let index = self.descriptors.iter().position(|d| d.id == id)?;
+        let removed = self.descriptors.remove(index);
+        self.cli_names = build_cli_name_map(&self.descriptors);
+        Some(removed)
+    }
+
+    /// Adds a strategy to a provider's pipeline.
+    ///
+    /// The factory runs each time the pipeline is built and may return
+    /// `None` to leave the strategy out (e.g., for a disallowed source mode).
+    /// A strategy with the same ID as an existing one replaces it.
+    ///
+    /// Returns false if the provider isn't registered.
+    pub fn set_strategy<F>(&mut self, id: ProviderKind, factory: F) -> bool
+    where
+        F: Fn(&FetchContext) -> Option<Box<dyn FetchStrategy>> + Send + Sync + 'static,
+    {
+        let Some(descriptor) = self.descriptors.iter_mut().find(|d| d.id == id) else {
+            return false;
+        };
+        descriptor
+            .fetch_plan
+            .map_pipeline(move |ctx, mut pipeline| {
+                if let Some(strategy) = factory(ctx) {
+                    pipeline.remove_strategy(strategy.id());
+                    pipeline.add_strategy(strategy);
+                }
+                pipeline
+            });
+        true
+    }
+
+    /// Removes a strategy from a provider's pipeline by ID.
+    ///
+    /// Returns false if the provider isn't registered.
+    pub fn remove_strategy(&mut self, id: ProviderKind, strategy_id: impl Into<String>) -> bool {
+        let Some(descriptor) = self.descriptors.iter_mut().find(|d| d.id == id) else {
+            return false;
+        };
+        let strategy_id = strategy_id.into();
+        descriptor.fetch_plan.map_pipeline(move |_, mut pipeline| {
+            pipeline.remove_strategy(&strategy_id);
+            pipeline
+        });
+        true
+    }
+
+    /// Builds the fetch pipeline for a provider.
+    pub fn build_pipeline(&self, id: ProviderKind, ctx: &FetchContext) -> Option<FetchPipeline> {
+        self.get(id).map(|d| d.build_pipeline(ctx))
+    }
+
+    /// Returns all provider descriptors.
+    pub fn all(&self) -> &[ProviderDescriptor] {
+        &self.descriptors
+    }
+
+    /// Gets a provider descriptor by kind.
+    pub fn get(&self, id: ProviderKind) -> Option<&ProviderDescriptor> {
+        self.descriptors.iter().find(|d| d.id == id)
+    }
+
+    /// Returns the CLI name to provider kind mapping.
+    pub fn cli_name_map(&self) -> &HashMap<String, ProviderKind> {
+        &self.cli_names
+    }
+
+    /// Looks up a provider by CLI name.
+    pub fn get_by_cli_name(&self, name: &str) -> Option<&ProviderDescriptor> {
+        let kind = self.cli_names.get(name)?;
+        self.get(*kind)
+    }
+
+    /// Returns all enabled-by-default providers.
+    pub fn default_enabled(&self) -> Vec<&ProviderDescriptor> {
+        self.descriptors
             .iter()
             .filter(|d| d.metadata.default_enabled)
             .collect()
     }
 
     /// Returns all primary providers.
-    pub fn primary_providers() -> Vec<&'static ProviderDescriptor> {
-        Self::all()
+    pub fn primary_providers(&self) -> Vec<&ProviderDescriptor> {
+        self.descriptors
             .iter()
             .filter(|d| d.metadata.is_primary_provider)
             .collect()
     }
 
     /// Returns the number of registered providers.
-    pub fn count() -> usize {
-        Self::all().len()
+    pub fn count(&self) -> usize {
+        self.descriptors.len()
     }
 
     /// Returns all provider kinds.
-    pub fn kinds() -> Vec<ProviderKind> {
-        Self::all().iter().map(|d| d.id).collect()
+    pub fn kinds(&self) -> Vec<ProviderKind> {
+        self.descriptors.iter().map(|d| d.id).collect()
     }
 
     /// Returns providers that support the given source mode.
-    pub fn with_source_mode(mode: exactobar_fetch::SourceMode) -> Vec<&'static ProviderDescriptor> {
-        Self::all()
+    pub fn with_source_mode(&self, mode: SourceMode) -> Vec<&ProviderDescriptor> {
+        self.descriptors
             .iter()
             .filter(|d| d.fetch_plan.source_modes.contains(&mode))
             .collect()
     }
 }
 
+impl Default for ProviderSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for ProviderSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProviderSet")
+            .field("providers", &self.kinds())
+            .finish()
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -154,9 +437,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_registry_all_13_providers() {
+    fn test_registry_all_19_providers() {
         let all = ProviderRegistry::all();
-        assert_eq!(all.len(), 13, "Should have exactly 13 providers");
+        assert_eq!(all.len(), 19, "Should have exactly 19 providers");
     }
 
     #[test]
@@ -176,6 +459,12 @@ mod tests {
             ProviderKind::MiniMax,
             ProviderKind::Antigravity,
             ProviderKind::Synthetic,
+            ProviderKind::Anthropic,
+            ProviderKind::OpenRouter,
+            ProviderKind::Windsurf,
+            ProviderKind::LiteLlm,
+            ProviderKind::Bedrock,
+            ProviderKind::Azure,
         ];
 
         for kind in kinds {
@@ -231,12 +520,178 @@ mod tests {
 
     #[test]
     fn test_provider_count() {
-        assert_eq!(ProviderRegistry::count(), 13);
+        assert_eq!(ProviderRegistry::count(), 19);
     }
 
     #[test]
     fn test_all_kinds_returned() {
         let kinds = ProviderRegistry::kinds();
-        assert_eq!(kinds.len(), 13);
+        assert_eq!(kinds.len(), 19);
+    }
+
+    // ========================================================================
+    // Provider Set
+    // ========================================================================
+
+    use crate::descriptor::{CliConfig, FetchPlan};
+    use async_trait::async_trait;
+    use exactobar_core::{CustomProviderId, UsageSnapshot};
+    use exactobar_fetch::{FetchError, FetchKind, FetchResult, PipelineMode};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct StubStrategy(String);
+
+    #[async_trait]
+    impl FetchStrategy for StubStrategy {
+        fn id(&self) -> &str {
+            &self.0
+        }
+
+        fn kind(&self) -> FetchKind {
+            FetchKind::ApiKey
+        }
+
+        async fn is_available(&self, _ctx: &FetchContext) -> bool {
+            true
+        }
+
+        async fn fetch(&self, _ctx: &FetchContext) -> Result<FetchResult, FetchError> {
+            Ok(FetchResult::new(
+                UsageSnapshot::new(),
+                self.0.clone(),
+                self.kind(),
+            ))
+        }
+    }
+
+    #[test]
+    fn test_provider_set_builtin() {
+        let set = ProviderSet::builtin();
+        assert_eq!(set.count(), 19);
+        assert_eq!(
+            set.get_by_cli_name("openai").unwrap().id,
+            ProviderKind::Codex
+        );
+        assert!(ProviderSet::new().all().is_empty());
+    }
+
+    #[test]
+    fn test_provider_set_register() {
+        let kind = ProviderKind::Custom(CustomProviderId::register("embedded").unwrap());
+        let builds = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&builds);
+        let descriptor = || {
+            ProviderDescriptor::builder(kind)
+                .cli(CliConfig {
+                    name: "embedded",
+                    aliases: &["emb"],
+                    ..CliConfig::default()
+                })
+                .build()
+        };
+
+        let mut set = ProviderSet::builtin();
+        let mut first = descriptor();
+        first.fetch_plan = FetchPlan::new(vec![SourceMode::ApiKey], move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            FetchPipeline::with_strategies(vec![Box::new(StubStrategy("embedded.api".to_string()))])
+        });
+        assert!(set.register(first).is_none());
+        assert_eq!(set.count(), 20);
+        assert_eq!(set.get_by_cli_name("emb").unwrap().id, kind);
+
+        let ctx = FetchContext::new();
+        let pipeline = set.build_pipeline(kind, &ctx).unwrap();
+        assert_eq!(pipeline.strategy_ids(), vec!["embedded.api"]);
+        assert_eq!(builds.load(Ordering::SeqCst), 1);
+
+        assert!(set.register(descriptor()).is_some());
+        assert_eq!(set.count(), 20);
+        assert!(set.build_pipeline(kind, &ctx).unwrap().is_empty());
+
+        assert!(set.unregister(kind).is_some());
+        assert!(set.get_by_cli_name("emb").is_none());
+        assert!(set.build_pipeline(kind, &ctx).is_none());
+    }
+
+    #[test]
+    fn test_provider_set_strategy_overrides() {
+        let ctx = FetchContext::new();
+        let mut set = ProviderSet::builtin();
+        let builtin = ProviderRegistry::get(ProviderKind::Claude)
+            .unwrap()
+            .build_pipeline(&ctx);
+        let builtin_ids = builtin.strategy_ids();
+        assert!(builtin_ids.len() >= 2);
+
+        // New strategy is added, same ID replaces
+        assert!(set.set_strategy(ProviderKind::Claude, |_| {
+            Some(Box::new(StubStrategy("claude.company".to_string())) as Box<dyn FetchStrategy>)
+        }));
+        let replaced = builtin_ids[0].to_string();
+        let id = replaced.clone();
+        assert!(set.set_strategy(ProviderKind::Claude, move |_| {
+            Some(Box::new(StubStrategy(id.clone())) as Box<dyn FetchStrategy>)
+        }));
+        assert!(set.remove_strategy(ProviderKind::Claude, builtin_ids[1]));
+
+        let ids = set
+            .build_pipeline(ProviderKind::Claude, &ctx)
+            .unwrap()
+            .strategy_ids()
+            .into_iter()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(ids.len(), builtin_ids.len());
+        assert!(ids.iter().any(|id| id == "claude.company"));
+        assert!(ids.contains(&replaced));
+        assert!(!ids.iter().any(|id| id == builtin_ids[1]));
+
+        // The global registry is untouched
+        assert_eq!(
+            builtin_ids,
+            ProviderRegistry::get(ProviderKind::Claude)
+                .unwrap()
+                .build_pipeline(&ctx)
+                .strategy_ids()
+        );
+
+        assert!(!ProviderSet::new().set_strategy(ProviderKind::Claude, |_| None));
+        assert!(!ProviderSet::new().remove_strategy(ProviderKind::Claude, "claude.web"));
+    }
+
+    #[test]
+    fn test_claude_merges_only_with_web_extras() {
+        let claude = ProviderRegistry::get(ProviderKind::Claude).unwrap();
+        let pipeline = claude.build_pipeline(&FetchContext::new());
+        assert_eq!(pipeline.mode(), PipelineMode::FirstSuccess);
+
+        let ctx = FetchContext::builder().web_extras(true).build();
+        assert_eq!(claude.build_pipeline(&ctx).mode(), PipelineMode::Merge);
+    }
+
+    #[test]
+    fn test_codex_merges_rpc_and_api() {
+        let codex = ProviderRegistry::get(ProviderKind::Codex).unwrap();
+        let pipeline = codex.build_pipeline(&FetchContext::new());
+        assert_eq!(pipeline.mode(), PipelineMode::Merge);
+    }
+
+    #[test]
+    fn test_registry_for_context() {
+        let mut ctx = FetchContext::new();
+        assert!(Arc::ptr_eq(
+            &ProviderRegistry::for_context(&ctx),
+            &ProviderRegistry::shared()
+        ));
+
+        let mut set = ProviderSet::new();
+        set.register(claude_descriptor());
+        let set = Arc::new(set);
+        set.attach(&mut ctx);
+
+        let scoped = ProviderRegistry::for_context(&ctx);
+        assert!(Arc::ptr_eq(&scoped, &set));
+        assert_eq!(scoped.kinds(), vec![ProviderKind::Claude]);
     }
 }
@@ -0,0 +1,89 @@
+//! Shared helpers for providers that report billing spend.
+//!
+//! Billing APIs (OpenAI and Anthropic organization reports) have no quota,
+//! so usage is shown as month-to-date spend against an optional budget.
+
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use exactobar_core::{UsageWindow, WindowKind};
+
+/// ID of the monthly spend window.
+pub const MONTHLY_SPEND_WINDOW: &str = "monthly_spend";
+
+/// Returns midnight UTC on the first day of the month.
+pub fn start_of_month(now: DateTime<Utc>) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now)
+}
+
+/// Returns midnight UTC on the first day of the next month.
+pub fn start_of_next_month(now: DateTime<Utc>) -> DateTime<Utc> {
+    let (year, month) = if now.month() == 12 {
+        (now.year() + 1, 1)
+    } else {
+        (now.year(), now.month() + 1)
+    };
+    Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now)
+}
+
+/// Builds the monthly spend window.
+///
+/// The percentage is spend over budget, capped at 100. Without a budget the
+/// window shows spend only (0%).
+pub fn monthly_spend_window(
+    spent: f64,
+    budget: Option<f64>,
+    currency: &str,
+    now: DateTime<Utc>,
+) -> UsageWindow {
+    let budget = budget.filter(|b| b.is_finite() && *b > 0.0);
+    let percent = budget.map_or(0.0, |b| (spent / b * 100.0).clamp(0.0, 100.0));
+
+    UsageWindow {
+        resets_at: Some(start_of_next_month(now)),
+        ..UsageWindow::named(MONTHLY_SPEND_WINDOW, WindowKind::Monthly, percent)
+            .with_label("Monthly spend")
+            .with_counts(spent, budget, currency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_month_boundaries() {
+        let dec = Utc.with_ymd_and_hms(2024, 12, 15, 8, 0, 0).unwrap();
+        assert_eq!(
+            start_of_next_month(dec),
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            start_of_month(dec),
+            Utc.with_ymd_and_hms(2024, 12, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_monthly_spend_window() {
+        let now = Utc.with_ymd_and_hms(2024, 11, 1, 12, 0, 0).unwrap();
+
+        let window = monthly_spend_window(2.0, Some(8.0), "USD", now);
+        assert_eq!(window.id, MONTHLY_SPEND_WINDOW);
+        assert_eq!(window.kind, WindowKind::Monthly);
+        assert!((window.used_percent - 25.0).abs() < 0.001);
+        assert_eq!(
+            window.resets_at,
+            Some(Utc.with_ymd_and_hms(2024, 12, 1, 0, 0, 0).unwrap())
+        );
+
+        let unbudgeted = monthly_spend_window(2.0, Some(f64::NAN), "USD", now);
+        assert!(unbudgeted.used_percent.abs() < 0.001);
+        assert!(unbudgeted.limit.is_none());
+
+        let over = monthly_spend_window(2.0, Some(1.0), "USD", now);
+        assert!((over.used_percent - 100.0).abs() < 0.001);
+    }
+}
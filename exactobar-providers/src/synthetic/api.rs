@@ -54,7 +54,9 @@ pub struct SearchInfo {
 impl SyntheticQuotaResponse {
     /// Convert to UsageSnapshot.
     pub fn to_snapshot(&self) -> exactobar_core::UsageSnapshot {
-        use exactobar_core::{FetchSource, LoginMethod, ProviderIdentity, ProviderKind};
+        use exactobar_core::{
+            FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageWindow, WindowKind,
+        };
 
         let mut snapshot = exactobar_core::UsageSnapshot::new();
         snapshot.fetch_source = FetchSource::Api;
@@ -74,12 +76,15 @@ impl SyntheticQuotaResponse {
                     .map(|dt| dt.with_timezone(&Utc))
             });
 
-            snapshot.primary = Some(exactobar_core::UsageWindow {
-                used_percent,
+            snapshot.set_primary(Some(UsageWindow {
                 window_minutes: Some(43200), // ~30 days in minutes
                 resets_at,
-                reset_description: None,
-            });
+                ..UsageWindow::named("requests", WindowKind::Monthly, used_percent).with_counts(
+                    sub.requests,
+                    Some(sub.limit as f64),
+                    "requests",
+                )
+            }));
 
             // Add identity with plan info
             let mut identity = ProviderIdentity::new(ProviderKind::Synthetic);
@@ -227,12 +232,12 @@ mod tests {
                     requests: 50.0,
                     renews_at: Some("2025-09-21T14:36:14.288Z".to_string()),
                 },
-            })
+            }),
         };
 
         let snapshot = response.to_snapshot();
-        assert!(snapshot.primary.is_some());
-        let primary = snapshot.primary.unwrap();
+        assert!(snapshot.primary().is_some());
+        let primary = snapshot.primary().unwrap();
         assert_eq!(primary.used_percent, 50.0);
         assert!(primary.resets_at.is_some());
     }
@@ -252,12 +257,12 @@ mod tests {
                     requests: 50.0,
                     renews_at: Some("2025-09-21T14:36:14.288Z".to_string()),
                 },
-            })
+            }),
         };
 
         let snapshot = response.to_snapshot();
-        assert!(snapshot.primary.is_some());
-        let primary = snapshot.primary.unwrap();
+        assert!(snapshot.primary().is_some());
+        let primary = snapshot.primary().unwrap();
         assert_eq!(primary.used_percent, 0.0);
     }
 }
@@ -2,6 +2,7 @@
 
 use exactobar_core::{IconStyle, ProviderBranding, ProviderColor, ProviderKind, ProviderMetadata};
 use exactobar_fetch::{FetchContext, FetchPipeline, SourceMode};
+use std::collections::HashMap;
 
 use super::strategies::SyntheticApiStrategy;
 use crate::descriptor::{CliConfig, FetchPlan, ProviderDescriptor, TokenCostConfig};
@@ -34,6 +35,7 @@ fn synthetic_metadata() -> ProviderMetadata {
         weekly_label: "Monthly".to_string(),
         opus_label: None,
         supports_opus: false,
+        window_labels: HashMap::new(),
         supports_credits: false,
         credits_hint: String::new(),
         toggle_title: "Show Synthetic.new usage".to_string(),
@@ -68,7 +70,7 @@ fn synthetic_branding() -> ProviderBranding {
 fn synthetic_fetch_plan() -> FetchPlan {
     FetchPlan {
         source_modes: vec![SourceMode::ApiKey],
-        build_pipeline: build_synthetic_pipeline,
+        build_pipeline: Box::new(build_synthetic_pipeline),
     }
 }
 
@@ -2,6 +2,7 @@
 
 use exactobar_core::{IconStyle, ProviderBranding, ProviderColor, ProviderKind, ProviderMetadata};
 use exactobar_fetch::{FetchContext, FetchPipeline, SourceMode};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use super::strategies::{VertexAILocalStrategy, VertexAIOAuthStrategy};
@@ -26,6 +27,7 @@ fn vertexai_metadata() -> ProviderMetadata {
         weekly_label: "Daily".to_string(),
         opus_label: None,
         supports_opus: false,
+        window_labels: HashMap::new(),
         supports_credits: false,
         credits_hint: String::new(),
         toggle_title: "Show Vertex AI usage".to_string(),
@@ -62,7 +64,7 @@ fn vertexai_log_directory() -> Option<PathBuf> {
 fn vertexai_fetch_plan() -> FetchPlan {
     FetchPlan {
         source_modes: vec![SourceMode::OAuth, SourceMode::Auto],
-        build_pipeline: build_vertexai_pipeline,
+        build_pipeline: Box::new(build_vertexai_pipeline),
     }
 }
 
@@ -77,8 +77,8 @@ impl VertexAIUsageFetcher {
             // Enrich with log data if available
             if let Ok(log_snapshot) = self.fetch_via_logs() {
                 // Merge log data into OAuth snapshot
-                if snapshot.primary.is_none() {
-                    snapshot.primary = log_snapshot.primary;
+                if snapshot.primary().is_none() {
+                    snapshot.set_primary(log_snapshot.primary().cloned());
                 }
             }
 
@@ -40,6 +40,6 @@ mod tests {
     fn test_parse_empty() {
         let json = r#"{}"#;
         let snapshot = parse_vertexai_response(json).unwrap();
-        assert!(snapshot.primary.is_none());
+        assert!(snapshot.primary().is_none());
     }
 }
@@ -0,0 +1,78 @@
+//! Windsurf provider descriptor.
+
+use exactobar_core::{IconStyle, ProviderBranding, ProviderColor, ProviderKind, ProviderMetadata};
+use exactobar_fetch::{FetchContext, FetchPipeline, SourceMode};
+use std::collections::HashMap;
+
+use super::strategies::WindsurfLocalStrategy;
+use crate::descriptor::{CliConfig, FetchPlan, ProviderDescriptor, TokenCostConfig};
+
+pub fn windsurf_descriptor() -> ProviderDescriptor {
+    ProviderDescriptor {
+        id: ProviderKind::Windsurf,
+        metadata: windsurf_metadata(),
+        branding: windsurf_branding(),
+        token_cost: TokenCostConfig::default(),
+        fetch_plan: windsurf_fetch_plan(),
+        cli: windsurf_cli_config(),
+    }
+}
+
+fn windsurf_metadata() -> ProviderMetadata {
+    ProviderMetadata {
+        id: ProviderKind::Windsurf,
+        display_name: "Windsurf".to_string(),
+        session_label: "Prompt credits".to_string(),
+        weekly_label: "Flow credits".to_string(),
+        opus_label: None,
+        supports_opus: false,
+        window_labels: HashMap::from([
+            ("prompt_credits".to_string(), "Prompt credits".to_string()),
+            ("flow_credits".to_string(), "Flow credits".to_string()),
+        ]),
+        supports_credits: true,
+        credits_hint: "Prompt and add-on credits left this cycle".to_string(),
+        toggle_title: "Show Windsurf usage".to_string(),
+        cli_name: "windsurf".to_string(),
+        default_enabled: false,
+        is_primary_provider: false,
+        uses_account_fallback: false,
+        dashboard_url: Some("https://windsurf.com/subscription/usage".to_string()),
+        subscription_dashboard_url: Some(
+            "https://windsurf.com/subscription/manage-plan".to_string(),
+        ),
+        status_page_url: Some("https://status.windsurf.com/api/v2/status.json".to_string()),
+        status_link_url: Some("https://status.windsurf.com".to_string()),
+    }
+}
+
+fn windsurf_branding() -> ProviderBranding {
+    ProviderBranding {
+        icon_style: IconStyle::Windsurf,
+        icon_resource_name: "icon_windsurf".to_string(),
+        color: ProviderColor::new(0.04, 0.71, 0.64), // Sea green
+    }
+}
+
+fn windsurf_fetch_plan() -> FetchPlan {
+    FetchPlan {
+        source_modes: vec![SourceMode::Auto],
+        build_pipeline: Box::new(build_windsurf_pipeline),
+    }
+}
+
+fn build_windsurf_pipeline(_ctx: &FetchContext) -> FetchPipeline {
+    let strategies: Vec<Box<dyn exactobar_fetch::FetchStrategy>> =
+        vec![Box::new(WindsurfLocalStrategy::new())];
+
+    FetchPipeline::with_strategies(strategies)
+}
+
+fn windsurf_cli_config() -> CliConfig {
+    CliConfig {
+        name: "windsurf",
+        aliases: &["codeium"],
+        version_args: &["--version"],
+        usage_args: &[],
+    }
+}
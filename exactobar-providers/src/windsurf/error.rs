@@ -0,0 +1,45 @@
+//! Windsurf-specific errors.
+
+use thiserror::Error;
+
+use crate::codeium::CodeiumError;
+
+/// Windsurf-specific errors.
+#[derive(Debug, Error)]
+pub enum WindsurfError {
+    /// App not running.
+    #[error("Windsurf app not running")]
+    NotRunning,
+
+    /// Connection failed.
+    #[error("Connection failed: {0}")]
+    ConnectionFailed(String),
+
+    /// Invalid response.
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+
+    /// No credit data in the user status.
+    #[error("No credit data available")]
+    NoData,
+
+    /// Port detection failed.
+    #[error("Port detection failed: {0}")]
+    PortDetectionFailed(String),
+
+    /// API error from the Windsurf language server.
+    #[error("API error: {0}")]
+    ApiError(String),
+}
+
+impl From<CodeiumError> for WindsurfError {
+    fn from(err: CodeiumError) -> Self {
+        match err {
+            CodeiumError::NotRunning(_) => WindsurfError::NotRunning,
+            CodeiumError::ConnectionFailed(msg) => WindsurfError::ConnectionFailed(msg),
+            CodeiumError::InvalidResponse(msg) => WindsurfError::InvalidResponse(msg),
+            CodeiumError::PortDetectionFailed(msg) => WindsurfError::PortDetectionFailed(msg),
+            CodeiumError::ApiError(msg) => WindsurfError::ApiError(msg),
+        }
+    }
+}
@@ -0,0 +1,16 @@
+//! Windsurf provider implementation.
+//!
+//! Windsurf (formerly Codeium) runs the same local language server as
+//! Antigravity. The probe finds the Windsurf process, extracts its CSRF
+//! token and reads prompt and flow action credits from `GetUserStatus`.
+//! No external auth is needed.
+
+mod descriptor;
+mod error;
+mod probe;
+mod strategies;
+
+pub use descriptor::windsurf_descriptor;
+pub use error::WindsurfError;
+pub use probe::{CreditPool, WindsurfProbe, WindsurfSnapshot};
+pub use strategies::WindsurfLocalStrategy;
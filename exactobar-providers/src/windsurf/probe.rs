@@ -0,0 +1,336 @@
+//! Windsurf local language server probe.
+//!
+//! Queries Windsurf's Codeium language server (see [`crate::codeium`]) for
+//! the plan's prompt and flow action credits.
+
+use chrono::{DateTime, Utc};
+use exactobar_core::{
+    Amount, CREDITS_UNIT, Credits, FetchSource, LoginMethod, ProviderIdentity, ProviderKind,
+    UsageSnapshot, UsageWindow, WindowKind, WindowSlot,
+};
+use tracing::{debug, instrument};
+
+use super::error::WindsurfError;
+use crate::codeium::{
+    LanguageServerClient, LanguageServerTarget, LenientNumber, UserStatus, parse_timestamp,
+};
+
+// ============================================================================
+// Language Server Target
+// ============================================================================
+
+/// Windsurf's language server.
+///
+/// The binary is `language_server_macos_arm`, `language_server_linux_x64`
+/// and so on, shipped inside the Windsurf app bundle.
+const WINDSURF_LANGUAGE_SERVER: LanguageServerTarget = LanguageServerTarget {
+    name: "Windsurf",
+    process_name: "language_server_",
+    ide_name: "windsurf",
+    matches_command: is_windsurf_command,
+};
+
+fn is_windsurf_command(command: &str) -> bool {
+    command.contains("windsurf") && !command.contains("antigravity")
+}
+
+/// The language server reports credits in hundredths.
+const CREDIT_SCALE: f64 = 100.0;
+
+// ============================================================================
+// Snapshot
+// ============================================================================
+
+/// Credits used out of those available for a billing cycle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CreditPool {
+    /// Credits used.
+    pub used: f64,
+    /// Credits available.
+    pub available: f64,
+}
+
+impl CreditPool {
+    /// Builds a pool from raw (hundredths) values.
+    ///
+    /// Returns `None` when nothing is available; negative totals mean the
+    /// plan is unlimited.
+    fn from_raw(used: Option<&LenientNumber>, available: Option<&LenientNumber>) -> Option<Self> {
+        let available = available?.value() / CREDIT_SCALE;
+        if available <= 0.0 {
+            return None;
+        }
+        let used = used.map_or(0.0, |u| u.value() / CREDIT_SCALE).max(0.0);
+        Some(Self { used, available })
+    }
+
+    /// Credits left.
+    pub fn remaining(&self) -> f64 {
+        (self.available - self.used).max(0.0)
+    }
+
+    /// Used percentage (0-100).
+    pub fn used_percent(&self) -> f64 {
+        (self.used / self.available * 100.0).clamp(0.0, 100.0)
+    }
+
+    fn to_window(self, id: &str, label: &str, resets_at: Option<DateTime<Utc>>) -> UsageWindow {
+        UsageWindow {
+            resets_at,
+            ..UsageWindow::named(id, WindowKind::Monthly, self.used_percent())
+                .with_label(label)
+                .with_counts(self.used, Some(self.available), CREDITS_UNIT)
+        }
+    }
+}
+
+/// Snapshot of Windsurf credit usage.
+#[derive(Debug, Clone, Default)]
+pub struct WindsurfSnapshot {
+    /// Prompt (user message) credits.
+    pub prompt_credits: Option<CreditPool>,
+    /// Flow action credits.
+    pub flow_credits: Option<CreditPool>,
+    /// Purchased add-on (flex) credits.
+    pub flex_credits: Option<CreditPool>,
+    /// End of the current billing cycle.
+    pub plan_end: Option<DateTime<Utc>>,
+    /// Account email.
+    pub account_email: Option<String>,
+    /// Account plan name.
+    pub account_plan: Option<String>,
+}
+
+impl WindsurfSnapshot {
+    /// Builds a snapshot from the language server's user status.
+    pub fn from_user_status(status: &UserStatus) -> Self {
+        let plan = status.plan_status.clone().unwrap_or_default();
+        let info = plan.plan_info.clone().unwrap_or_default();
+
+        Self {
+            prompt_credits: CreditPool::from_raw(
+                plan.used_prompt_credits.as_ref(),
+                plan.available_prompt_credits
+                    .as_ref()
+                    .or(info.monthly_prompt_credits.as_ref()),
+            ),
+            flow_credits: CreditPool::from_raw(
+                plan.used_flow_credits.as_ref(),
+                plan.available_flow_credits
+                    .as_ref()
+                    .or(info.monthly_flow_credits.as_ref()),
+            ),
+            flex_credits: CreditPool::from_raw(
+                plan.used_flex_credits.as_ref(),
+                plan.available_flex_credits.as_ref(),
+            ),
+            plan_end: plan.plan_end.as_deref().and_then(parse_timestamp),
+            account_email: status.email.clone(),
+            account_plan: status.plan_name(),
+        }
+    }
+
+    /// Convert to a UsageSnapshot for display.
+    ///
+    /// Prompt credits fill the primary slot and flow credits the secondary.
+    /// The credit balance is prompt plus add-on credits left.
+    pub fn to_usage_snapshot(&self) -> Result<UsageSnapshot, WindsurfError> {
+        if self.prompt_credits.is_none() && self.flow_credits.is_none() {
+            return Err(WindsurfError::NoData);
+        }
+
+        let mut snapshot = UsageSnapshot::new();
+        snapshot.fetch_source = FetchSource::LocalProbe;
+
+        snapshot.set_slot(
+            WindowSlot::Primary,
+            self.prompt_credits
+                .map(|p| p.to_window("prompt_credits", "Prompt credits", self.plan_end)),
+        );
+        snapshot.set_slot(
+            WindowSlot::Secondary,
+            self.flow_credits
+                .map(|p| p.to_window("flow_credits", "Flow credits", self.plan_end)),
+        );
+
+        let pools: Vec<CreditPool> = [self.prompt_credits, self.flex_credits]
+            .into_iter()
+            .flatten()
+            .collect();
+        if !pools.is_empty() {
+            let remaining = pools.iter().map(CreditPool::remaining).sum();
+            let total = pools.iter().map(|p| p.available).sum();
+            snapshot.credits =
+                Some(Credits::from_amount(Amount::credits(remaining)).with_total(total));
+        }
+
+        let mut identity = ProviderIdentity::new(ProviderKind::Windsurf);
+        identity.account_email = self.account_email.clone();
+        identity.plan_name = self.account_plan.clone();
+        identity.login_method = Some(LoginMethod::CLI);
+        snapshot.identity = Some(identity);
+
+        Ok(snapshot)
+    }
+}
+
+// ============================================================================
+// Probe Implementation
+// ============================================================================
+
+/// Windsurf local probe.
+#[derive(Debug)]
+pub struct WindsurfProbe {
+    client: LanguageServerClient,
+}
+
+impl WindsurfProbe {
+    /// Create a new probe.
+    pub fn new() -> Self {
+        Self {
+            client: LanguageServerClient::new(WINDSURF_LANGUAGE_SERVER),
+        }
+    }
+
+    /// Check if Windsurf is running.
+    pub fn is_running(&self) -> bool {
+        self.client.is_running()
+    }
+
+    /// Fetch credit usage from Windsurf.
+    #[instrument(skip(self))]
+    pub async fn fetch(&self) -> Result<WindsurfSnapshot, WindsurfError> {
+        let connection = self.client.connect().await?;
+        let status = self.client.get_user_status(&connection).await?;
+        debug!(port = connection.port, "Fetched Windsurf user status");
+        Ok(WindsurfSnapshot::from_user_status(&status))
+    }
+
+    /// Fetch and convert to UsageSnapshot (convenience method).
+    #[instrument(skip(self))]
+    pub async fn fetch_usage(&self) -> Result<UsageSnapshot, WindsurfError> {
+        self.fetch().await?.to_usage_snapshot()
+    }
+}
+
+impl Default for WindsurfProbe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER_STATUS_JSON: &str = r#"{
+        "email": "dev@example.com",
+        "planStatus": {
+            "planInfo": {
+                "teamsTier": "TEAMS_TIER_PRO",
+                "planName": "Pro",
+                "monthlyPromptCredits": 50000,
+                "monthlyFlowCredits": 150000
+            },
+            "planStart": "2024-11-01T00:00:00Z",
+            "planEnd": "2024-12-01T00:00:00Z",
+            "availablePromptCredits": 50000,
+            "usedPromptCredits": 12500,
+            "availableFlowCredits": "150000",
+            "usedFlowCredits": "30000",
+            "availableFlexCredits": 10000,
+            "usedFlexCredits": 2500
+        }
+    }"#;
+
+    fn snapshot() -> WindsurfSnapshot {
+        let status: UserStatus = serde_json::from_str(USER_STATUS_JSON).unwrap();
+        WindsurfSnapshot::from_user_status(&status)
+    }
+
+    #[test]
+    fn test_probe_creation() {
+        let _ = WindsurfProbe::new();
+    }
+
+    #[test]
+    fn test_is_windsurf_command() {
+        assert!(is_windsurf_command(
+            "/applications/windsurf.app/contents/resources/app/extensions/windsurf/bin/language_server_macos_arm --csrf_token x"
+        ));
+        assert!(!is_windsurf_command(
+            "/applications/antigravity.app/windsurf/language_server_macos"
+        ));
+        assert!(!is_windsurf_command("/usr/bin/language_server_linux_x64"));
+    }
+
+    #[test]
+    fn test_from_user_status() {
+        let snapshot = snapshot();
+        let prompt = snapshot.prompt_credits.unwrap();
+        assert!((prompt.available - 500.0).abs() < 0.001);
+        assert!((prompt.used - 125.0).abs() < 0.001);
+        assert!((prompt.used_percent() - 25.0).abs() < 0.001);
+
+        let flow = snapshot.flow_credits.unwrap();
+        assert!((flow.available - 1500.0).abs() < 0.001);
+        assert!((flow.remaining() - 1200.0).abs() < 0.001);
+
+        assert_eq!(snapshot.account_email.as_deref(), Some("dev@example.com"));
+        assert_eq!(snapshot.account_plan.as_deref(), Some("Pro"));
+        assert!(snapshot.plan_end.is_some());
+    }
+
+    #[test]
+    fn test_to_usage_snapshot() {
+        let usage = snapshot().to_usage_snapshot().unwrap();
+
+        let primary = usage.primary().unwrap();
+        assert_eq!(primary.id, "prompt_credits");
+        assert!((primary.used_percent - 25.0).abs() < 0.001);
+        assert_eq!(primary.kind, WindowKind::Monthly);
+        assert!(primary.resets_at.is_some());
+
+        let secondary = usage.secondary().unwrap();
+        assert_eq!(secondary.id, "flow_credits");
+        assert!((secondary.used_percent - 20.0).abs() < 0.001);
+
+        // 375 prompt + 75 flex credits left out of 600
+        let credits = usage.credits.unwrap();
+        assert!((credits.remaining - 450.0).abs() < 0.001);
+        assert!((credits.total.unwrap() - 600.0).abs() < 0.001);
+        assert_eq!(credits.unit, CREDITS_UNIT);
+
+        assert_eq!(usage.fetch_source, FetchSource::LocalProbe);
+        assert_eq!(usage.identity.unwrap().provider_id, ProviderKind::Windsurf);
+    }
+
+    #[test]
+    fn test_falls_back_to_monthly_allowance() {
+        let status: UserStatus = serde_json::from_str(
+            r#"{"planStatus": {"planInfo": {"monthlyPromptCredits": 2000}, "usedPromptCredits": 500}}"#,
+        )
+        .unwrap();
+        let snapshot = WindsurfSnapshot::from_user_status(&status);
+        let prompt = snapshot.prompt_credits.unwrap();
+        assert!((prompt.available - 20.0).abs() < 0.001);
+        assert!(snapshot.flow_credits.is_none());
+    }
+
+    #[test]
+    fn test_unlimited_plan_has_no_windows() {
+        let status: UserStatus = serde_json::from_str(
+            r#"{"planStatus": {"availablePromptCredits": -1, "availableFlowCredits": -1}}"#,
+        )
+        .unwrap();
+        let snapshot = WindsurfSnapshot::from_user_status(&status);
+        assert!(matches!(
+            snapshot.to_usage_snapshot(),
+            Err(WindsurfError::NoData)
+        ));
+    }
+}
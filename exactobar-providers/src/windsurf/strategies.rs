@@ -0,0 +1,74 @@
+//! Windsurf fetch strategies.
+
+use async_trait::async_trait;
+use exactobar_fetch::{FetchContext, FetchError, FetchKind, FetchResult, FetchStrategy};
+use tracing::{debug, instrument};
+
+use super::probe::WindsurfProbe;
+
+/// Local probe strategy for Windsurf.
+///
+/// Queries the Windsurf language server for the account's credit usage.
+pub struct WindsurfLocalStrategy {
+    probe: WindsurfProbe,
+}
+
+impl WindsurfLocalStrategy {
+    pub fn new() -> Self {
+        Self {
+            probe: WindsurfProbe::new(),
+        }
+    }
+}
+
+impl Default for WindsurfLocalStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FetchStrategy for WindsurfLocalStrategy {
+    fn id(&self) -> &str {
+        "windsurf.local"
+    }
+
+    fn kind(&self) -> FetchKind {
+        FetchKind::LocalProbe
+    }
+
+    #[instrument(skip(self, _ctx))]
+    async fn is_available(&self, _ctx: &FetchContext) -> bool {
+        self.probe.is_running()
+    }
+
+    #[instrument(skip(self, _ctx))]
+    async fn fetch(&self, _ctx: &FetchContext) -> Result<FetchResult, FetchError> {
+        debug!("Fetching Windsurf credits via local probe");
+
+        let snapshot = self
+            .probe
+            .fetch_usage()
+            .await
+            .map_err(|e| FetchError::InvalidResponse(e.to_string()))?;
+
+        Ok(FetchResult::new(snapshot, self.id(), self.kind()))
+    }
+
+    fn priority(&self) -> u32 {
+        100
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_strategy() {
+        let s = WindsurfLocalStrategy::new();
+        assert_eq!(s.id(), "windsurf.local");
+        assert_eq!(s.kind(), FetchKind::LocalProbe);
+        assert_eq!(s.priority(), 100);
+    }
+}
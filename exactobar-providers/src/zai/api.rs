@@ -1,7 +1,7 @@
 //! z.ai API client.
 
 use exactobar_core::{
-    FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageSnapshot, UsageWindow,
+    Credits, FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageSnapshot, UsageWindow,
 };
 use reqwest::header::{ACCEPT, AUTHORIZATION, HeaderMap, HeaderValue, USER_AGENT};
 use serde::Deserialize;
@@ -82,7 +82,11 @@ impl ZaiUsageResponse {
         snapshot.fetch_source = FetchSource::OAuth;
 
         if let Some(percent) = self.get_percent() {
-            snapshot.primary = Some(UsageWindow::new(percent));
+            snapshot.set_primary(Some(UsageWindow::new(percent)));
+        }
+
+        if let (Some(used), Some(limit)) = (self.credits_used, self.credit_limit) {
+            snapshot.credits = Some(Credits::new((limit - used).max(0.0)).with_total(limit));
         }
 
         if self.plan.is_some() {
@@ -208,6 +212,10 @@ mod tests {
 
         let response: ZaiUsageResponse = serde_json::from_str(json).unwrap();
         assert_eq!(response.get_percent(), Some(50.0));
+
+        let credits = response.to_snapshot().credits.unwrap();
+        assert_eq!(credits.remaining, 50.0);
+        assert_eq!(credits.total, Some(100.0));
     }
 
     #[test]
@@ -222,7 +230,7 @@ mod tests {
         };
 
         let snapshot = response.to_snapshot();
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.unwrap().used_percent, 50.0);
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 50.0);
     }
 }
@@ -2,6 +2,7 @@
 
 use exactobar_core::{IconStyle, ProviderBranding, ProviderColor, ProviderKind, ProviderMetadata};
 use exactobar_fetch::{FetchContext, FetchPipeline, SourceMode};
+use std::collections::HashMap;
 
 use super::strategies::ZaiApiStrategy;
 use crate::descriptor::{CliConfig, FetchPlan, ProviderDescriptor, TokenCostConfig};
@@ -25,6 +26,7 @@ fn zai_metadata() -> ProviderMetadata {
         weekly_label: "Monthly".to_string(),
         opus_label: None,
         supports_opus: false,
+        window_labels: HashMap::new(),
         supports_credits: true,
         credits_hint: "z.ai credits".to_string(),
         toggle_title: "Show z.ai usage".to_string(),
@@ -50,7 +52,7 @@ fn zai_branding() -> ProviderBranding {
 fn zai_fetch_plan() -> FetchPlan {
     FetchPlan {
         source_modes: vec![SourceMode::ApiKey],
-        build_pipeline: build_zai_pipeline,
+        build_pipeline: Box::new(build_zai_pipeline),
     }
 }
 
@@ -1,7 +1,7 @@
 //! Zai response parser.
 
 use exactobar_core::{
-    FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageSnapshot, UsageWindow,
+    Credits, FetchSource, LoginMethod, ProviderIdentity, ProviderKind, UsageSnapshot, UsageWindow,
 };
 use exactobar_fetch::FetchError;
 use serde::Deserialize;
@@ -12,7 +12,6 @@ pub struct ZaiUsageResponse {
     #[serde(default)]
     pub usage: Option<ZaiUsage>,
     #[serde(default)]
-    #[allow(dead_code)]
     pub credits: Option<ZaiCredits>,
     #[serde(default)]
     pub account: Option<ZaiAccount>,
@@ -24,7 +23,6 @@ pub struct ZaiUsage {
     pub limit: Option<u64>,
 }
 
-#[allow(dead_code)]
 #[derive(Debug, Deserialize)]
 pub struct ZaiCredits {
     pub remaining: Option<f64>,
@@ -53,7 +51,15 @@ pub fn parse_zai_response(json_str: &str) -> Result<UsageSnapshot, FetchError> {
             } else {
                 0.0
             };
-            snapshot.primary = Some(UsageWindow::new(percent));
+            snapshot.set_primary(Some(UsageWindow::new(percent)));
+        }
+    }
+
+    if let Some(credits) = response.credits {
+        if let Some(remaining) = credits.remaining {
+            let mut balance = Credits::new(remaining);
+            balance.total = credits.total;
+            snapshot.credits = Some(balance);
         }
     }
 
@@ -80,14 +86,24 @@ mod tests {
             "account": {"email": "user@example.com"}
         }"#;
         let snapshot = parse_zai_response(json).unwrap();
-        assert!(snapshot.primary.is_some());
-        assert_eq!(snapshot.primary.unwrap().used_percent, 50.0);
+        assert!(snapshot.primary().is_some());
+        assert_eq!(snapshot.primary().unwrap().used_percent, 50.0);
+    }
+
+    #[test]
+    fn test_parse_credits() {
+        let json = r#"{"credits": {"remaining": 40.0, "total": 100.0}}"#;
+        let snapshot = parse_zai_response(json).unwrap();
+        let credits = snapshot.credits.unwrap();
+        assert_eq!(credits.remaining, 40.0);
+        assert_eq!(credits.total, Some(100.0));
+        assert_eq!(credits.unit, "credits");
     }
 
     #[test]
     fn test_parse_empty() {
         let json = r#"{}"#;
         let snapshot = parse_zai_response(json).unwrap();
-        assert!(snapshot.primary.is_none());
+        assert!(snapshot.primary().is_none());
     }
 }
@@ -0,0 +1,356 @@
+//! User-defined provider definitions.
+//!
+//! A custom provider is either a single GET endpoint that returns JSON, plus
+//! paths that say where the usage numbers are in the response, or a command
+//! that prints `UsageSnapshot` JSON on stdout. Definitions live in
+//! [`Settings::custom_providers`](crate::Settings::custom_providers) and are
+//! turned into fetch strategies by the providers crate.
+
+use exactobar_core::{CustomProviderId, ProviderColor, ProviderKind};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::error::StoreError;
+
+// ============================================================================
+// Definition
+// ============================================================================
+
+/// A user-defined provider.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomProviderConfig {
+    /// Provider ID, also its CLI name (e.g., `gateway`).
+    pub id: String,
+
+    /// Display name (e.g., "Internal Gateway").
+    pub name: String,
+
+    /// Brand color as `#RRGGBB`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+
+    /// Usage endpoint, fetched with GET. Empty for command providers.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub url: String,
+
+    /// Command that prints snapshots, used instead of `url`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<CustomCommand>,
+
+    /// How requests authenticate.
+    #[serde(default)]
+    pub auth: CustomAuth,
+
+    /// Extra request headers.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+
+    /// Where the numbers are in the response (HTTP providers only).
+    #[serde(default)]
+    pub fields: CustomFieldPaths,
+
+    /// Label for the usage window (defaults to "Usage").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_label: Option<String>,
+
+    /// Unit of the used/limit values (e.g., "requests", "USD").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+
+    /// Dashboard URL opened from the menu.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dashboard_url: Option<String>,
+}
+
+/// An executable that prints usage snapshots.
+///
+/// The command is run without a shell. Its stdout must be a `UsageSnapshot`
+/// as JSON, a JSON array of them, or one per line.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomCommand {
+    /// Program path or name on `PATH` (a leading `~/` expands to home).
+    pub program: String,
+
+    /// Program arguments.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+
+    /// Extra environment variables.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+
+    /// Timeout in seconds (defaults to the fetch timeout).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+/// How a custom provider authenticates.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CustomAuth {
+    /// No authentication.
+    #[default]
+    None,
+
+    /// `Authorization: Bearer <token>`.
+    Bearer {
+        /// Environment variable holding the token.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        env: Option<String>,
+        /// Keychain account holding the token (defaults to the provider ID).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        keychain: Option<String>,
+    },
+
+    /// A named header carrying a secret (e.g., `X-Api-Key`).
+    Header {
+        /// Header name.
+        name: String,
+        /// Environment variable holding the value.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        env: Option<String>,
+        /// Keychain account holding the value (defaults to the provider ID).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        keychain: Option<String>,
+    },
+
+    /// Browser cookies for a domain.
+    Cookie {
+        /// Cookie domain (e.g., `gateway.example.com`).
+        domain: String,
+    },
+}
+
+/// Paths into the JSON response.
+///
+/// Paths use a `JSONPath` subset: `$.data.used`, `usage.limit`,
+/// `items[0].percent` or `$['rate-limit'].reset`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomFieldPaths {
+    /// Amount used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub used: Option<String>,
+
+    /// Limit for the amount used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+
+    /// Percent used (0-100); computed from used/limit when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percent: Option<String>,
+
+    /// Reset time (RFC 3339 or Unix seconds/milliseconds).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resets_at: Option<String>,
+
+    /// Remaining credits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credits: Option<String>,
+}
+
+impl CustomProviderConfig {
+    /// Returns the provider kind, interning the ID and display name.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the ID is invalid or names a built-in provider.
+    pub fn kind(&self) -> Result<ProviderKind, StoreError> {
+        let id = CustomProviderId::register(&self.id).ok_or_else(|| {
+            StoreError::Config(format!(
+                "custom provider ID '{}' must be lowercase letters, digits, '-' or '_' and not a built-in provider",
+                self.id
+            ))
+        })?;
+        id.set_display_name(self.display_name());
+        Ok(ProviderKind::Custom(id))
+    }
+
+    /// Returns the display name, or the ID if the name is blank.
+    pub fn display_name(&self) -> &str {
+        let name = self.name.trim();
+        if name.is_empty() { &self.id } else { name }
+    }
+
+    /// Returns true if the provider runs a command instead of a request.
+    pub fn is_command(&self) -> bool {
+        self.command.is_some()
+    }
+
+    /// Returns the parsed brand color.
+    pub fn brand_color(&self) -> Option<ProviderColor> {
+        self.color.as_deref().and_then(ProviderColor::from_hex)
+    }
+
+    /// Checks the definition for mistakes the fetch would only hit later.
+    ///
+    /// # Errors
+    ///
+    /// Returns error describing the first problem found.
+    pub fn validate(&self) -> Result<(), StoreError> {
+        self.kind()?;
+
+        let invalid = |msg: String| Err(StoreError::Config(format!("{}: {msg}", self.id)));
+
+        if let Some(color) = &self.color {
+            if ProviderColor::from_hex(color).is_none() {
+                return invalid(format!("color must be #RRGGBB: {color}"));
+            }
+        }
+
+        if let Some(command) = &self.command {
+            if !self.url.is_empty() {
+                return invalid("set either a URL or a command, not both".to_string());
+            }
+            if command.program.trim().is_empty() {
+                return invalid("command needs a program".to_string());
+            }
+            if command.timeout_secs == Some(0) {
+                return invalid("command timeout must be at least one second".to_string());
+            }
+            if matches!(self.auth, CustomAuth::Cookie { .. }) {
+                return invalid("command providers cannot use cookie auth".to_string());
+            }
+        } else {
+            if !(self.url.starts_with("https://") || self.url.starts_with("http://")) {
+                return invalid(format!("URL must be http(s): {}", self.url));
+            }
+            let fields = &self.fields;
+            if fields.percent.is_none() && fields.used.is_none() && fields.credits.is_none() {
+                return invalid("map at least one of percent, used or credits".to_string());
+            }
+        }
+
+        match &self.auth {
+            CustomAuth::Header { name, .. } if name.trim().is_empty() => {
+                invalid("header auth needs a header name".to_string())
+            }
+            CustomAuth::Cookie { domain } if domain.trim().is_empty() => {
+                invalid("cookie auth needs a domain".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> CustomProviderConfig {
+        CustomProviderConfig {
+            id: "gateway".to_string(),
+            name: "Internal Gateway".to_string(),
+            color: Some("#336699".to_string()),
+            url: "https://gateway.example.com/v1/usage".to_string(),
+            command: None,
+            auth: CustomAuth::Bearer {
+                env: Some("GATEWAY_TOKEN".to_string()),
+                keychain: None,
+            },
+            headers: BTreeMap::new(),
+            fields: CustomFieldPaths {
+                used: Some("$.usage.used".to_string()),
+                limit: Some("$.usage.limit".to_string()),
+                ..Default::default()
+            },
+            window_label: None,
+            unit: None,
+            dashboard_url: None,
+        }
+    }
+
+    #[test]
+    fn test_config_roundtrip() {
+        let json = r#"{
+            "id": "gateway",
+            "name": "Internal Gateway",
+            "url": "https://gateway.example.com/v1/usage",
+            "auth": {"type": "header", "name": "X-Api-Key", "env": "GATEWAY_KEY"},
+            "fields": {"percent": "$.pct", "resets_at": "$.reset"}
+        }"#;
+        let parsed: CustomProviderConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            parsed.auth,
+            CustomAuth::Header {
+                name: "X-Api-Key".to_string(),
+                env: Some("GATEWAY_KEY".to_string()),
+                keychain: None,
+            }
+        );
+        assert_eq!(parsed.fields.percent.as_deref(), Some("$.pct"));
+        assert!(parsed.validate().is_ok());
+
+        let back: CustomProviderConfig =
+            serde_json::from_str(&serde_json::to_string(&parsed).unwrap()).unwrap();
+        assert_eq!(back, parsed);
+    }
+
+    #[test]
+    fn test_config_kind() {
+        let kind = config().kind().unwrap();
+        assert_eq!(kind.cli_name(), "gateway");
+        assert_eq!(kind.display_name(), "Internal Gateway");
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(config().validate().is_ok());
+
+        let mut bad = config();
+        bad.id = "claude".to_string();
+        assert!(bad.validate().is_err());
+
+        let mut bad = config();
+        bad.url = "ftp://example.com".to_string();
+        assert!(bad.validate().is_err());
+
+        let mut bad = config();
+        bad.color = Some("blue".to_string());
+        assert!(bad.validate().is_err());
+
+        let mut bad = config();
+        bad.fields = CustomFieldPaths::default();
+        assert!(bad.validate().is_err());
+
+        let mut bad = config();
+        bad.auth = CustomAuth::Cookie {
+            domain: " ".to_string(),
+        };
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_command_config() {
+        let json = r##"{
+            "id": "budget",
+            "name": "Team Budget",
+            "color": "#aa3300",
+            "command": {"program": "~/bin/budget", "args": ["--json"], "timeout_secs": 5}
+        }"##;
+        let parsed: CustomProviderConfig = serde_json::from_str(json).unwrap();
+        assert!(parsed.is_command());
+        assert!(parsed.url.is_empty());
+        assert!(parsed.validate().is_ok());
+
+        let serialized = serde_json::to_string(&parsed).unwrap();
+        assert!(!serialized.contains("\"url\""));
+
+        let mut bad = parsed.clone();
+        bad.url = "https://example.com".to_string();
+        assert!(bad.validate().is_err());
+
+        let mut bad = parsed.clone();
+        bad.command.as_mut().unwrap().program = String::new();
+        assert!(bad.validate().is_err());
+
+        let mut bad = parsed;
+        bad.auth = CustomAuth::Cookie {
+            domain: "example.com".to_string(),
+        };
+        assert!(bad.validate().is_err());
+    }
+}
@@ -40,6 +40,10 @@ pub enum StoreError {
     /// Parse error.
     #[error("Parse error: {0}")]
     Parse(String),
+
+    /// Daemon IPC error.
+    #[error("Daemon error: {0}")]
+    Ipc(String),
 }
 
 impl StoreError {
@@ -0,0 +1,514 @@
+//! IPC with the background daemon.
+//!
+//! `exactobar daemon` owns a [`UsageStore`](crate::UsageStore) and the
+//! refresh loop, and serves it over a Unix domain socket so the CLI, the
+//! menu bar app and shell prompts share one set of fetches instead of each
+//! running their own probes against the same credentials.
+//!
+//! ## Protocol
+//!
+//! Messages are JSON objects, one per line, tagged by `type`. A client
+//! opens with [`DaemonRequest::Hello`] carrying [`PROTOCOL_VERSION`]; the
+//! daemon answers with its own version and closes the connection if they
+//! differ, so clients fall back to fetching in-process. After the
+//! handshake each request gets exactly one response, except
+//! [`DaemonRequest::Subscribe`], after which the daemon pushes
+//! [`DaemonResponse::Snapshots`] whenever its store changes.
+
+use exactobar_core::{ProviderKind, ProviderStatus, UsageSnapshot};
+use exactobar_fetch::{FetchErrorKind, FetchFailure};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::error::StoreError;
+use crate::persistence::default_cache_dir;
+
+/// Protocol version spoken by this build.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Environment variable overriding the socket path.
+pub const SOCKET_ENV: &str = "EXACTOBAR_SOCKET";
+
+/// Largest message either side accepts.
+pub const MAX_MESSAGE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Returns the daemon socket path.
+///
+/// Uses `$EXACTOBAR_SOCKET` if set, then `$XDG_RUNTIME_DIR/exactobar`, then
+/// the cache directory.
+pub fn default_socket_path() -> PathBuf {
+    if let Some(path) = std::env::var_os(SOCKET_ENV).filter(|p| !p.is_empty()) {
+        return PathBuf::from(path);
+    }
+    dirs::runtime_dir()
+        .map_or_else(default_cache_dir, |dir| dir.join("exactobar"))
+        .join("daemon.sock")
+}
+
+// ============================================================================
+// Messages
+// ============================================================================
+
+/// A message from a client to the daemon.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DaemonRequest {
+    /// Opens the session.
+    Hello {
+        /// Client protocol version.
+        version: u32,
+    },
+
+    /// Returns cached snapshots, refreshing any older than `max_age_secs`.
+    Get {
+        /// Providers to return (empty for every provider the daemon refreshes).
+        #[serde(default)]
+        providers: Vec<ProviderKind>,
+        /// Oldest acceptable snapshot, in seconds (defaults to the refresh interval).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_age_secs: Option<u64>,
+    },
+
+    /// Refreshes providers now and returns the results.
+    Refresh {
+        /// Providers to refresh (empty for every provider the daemon refreshes).
+        #[serde(default)]
+        providers: Vec<ProviderKind>,
+    },
+
+    /// Streams snapshots on every change until the client disconnects.
+    Subscribe {
+        /// Providers to stream (empty for every provider the daemon refreshes).
+        #[serde(default)]
+        providers: Vec<ProviderKind>,
+    },
+
+    /// Stops the daemon.
+    Shutdown,
+}
+
+/// A message from the daemon to a client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DaemonResponse {
+    /// Handshake reply.
+    Hello {
+        /// Daemon protocol version.
+        version: u32,
+        /// Daemon build version.
+        daemon_version: String,
+        /// Providers the daemon refreshes on its own.
+        providers: Vec<ProviderKind>,
+        /// Refresh interval in seconds, or `None` for manual.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        interval_secs: Option<u64>,
+    },
+
+    /// Provider state.
+    Snapshots {
+        /// One entry per requested provider.
+        entries: Vec<ProviderEntry>,
+    },
+
+    /// Acknowledges a request with no data (e.g., shutdown).
+    Ok,
+
+    /// The request failed.
+    Error {
+        /// What went wrong.
+        message: String,
+    },
+}
+
+/// The daemon's state for one provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderEntry {
+    /// The provider.
+    pub provider: ProviderKind,
+    /// Last successful snapshot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<UsageSnapshot>,
+    /// Error from the last refresh, if it failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Category of `error`; absent from daemons that predate it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<FetchErrorKind>,
+    /// Status page indicator, when status checks are enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ProviderStatus>,
+    /// Whether a refresh is running.
+    #[serde(default)]
+    pub refreshing: bool,
+}
+
+impl ProviderEntry {
+    /// Returns the entry as a fetch result, preferring the last error.
+    pub fn into_result(self) -> Result<UsageSnapshot, FetchFailure> {
+        match (self.error, self.snapshot) {
+            (Some(error), _) => Err(FetchFailure::new(
+                self.error_kind.unwrap_or_default(),
+                error,
+            )),
+            (None, Some(snapshot)) => Ok(snapshot),
+            (None, None) => Err(FetchFailure::new(FetchErrorKind::Failed, "no snapshot yet")),
+        }
+    }
+}
+
+// ============================================================================
+// Framing
+// ============================================================================
+
+/// Reads one message, or `None` at end of stream.
+///
+/// # Errors
+///
+/// Returns error if reading fails, the line is longer than
+/// [`MAX_MESSAGE_BYTES`], or it is not a valid message.
+pub async fn read_message<T, R>(reader: &mut R) -> Result<Option<T>, StoreError>
+where
+    T: DeserializeOwned,
+    R: AsyncBufRead + Unpin,
+{
+    let mut line = String::new();
+    let n = reader.take(MAX_MESSAGE_BYTES).read_line(&mut line).await?;
+    if n == 0 {
+        return Ok(None);
+    }
+    if !line.ends_with('\n') && n as u64 >= MAX_MESSAGE_BYTES {
+        return Err(StoreError::Ipc("message too large".to_string()));
+    }
+    Ok(Some(serde_json::from_str(line.trim_end())?))
+}
+
+/// Writes one message.
+///
+/// # Errors
+///
+/// Returns error if serialization or the write fails.
+pub async fn write_message<T, W>(writer: &mut W, message: &T) -> Result<(), StoreError>
+where
+    T: Serialize,
+    W: AsyncWrite + Unpin,
+{
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    writer.write_all(&line).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+// ============================================================================
+// Client
+// ============================================================================
+
+#[cfg(unix)]
+pub use client::{DaemonClient, DaemonInfo, Subscription};
+
+#[cfg(unix)]
+mod client {
+    use super::{
+        DaemonRequest, DaemonResponse, PROTOCOL_VERSION, ProviderEntry, default_socket_path,
+        read_message, write_message,
+    };
+    use crate::error::StoreError;
+    use exactobar_core::ProviderKind;
+    use std::path::Path;
+    use std::time::Duration;
+    use tokio::io::BufReader;
+    use tokio::net::UnixStream;
+    use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
+    use tracing::debug;
+
+    /// How long connecting and the handshake may take.
+    const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+    /// What the daemon said in its handshake.
+    #[derive(Debug, Clone)]
+    pub struct DaemonInfo {
+        /// Daemon build version.
+        pub daemon_version: String,
+        /// Providers the daemon refreshes on its own.
+        pub providers: Vec<ProviderKind>,
+        /// Refresh interval, or `None` for manual.
+        pub interval: Option<Duration>,
+    }
+
+    /// A connection to a running daemon.
+    pub struct DaemonClient {
+        reader: BufReader<OwnedReadHalf>,
+        writer: OwnedWriteHalf,
+        info: DaemonInfo,
+    }
+
+    impl DaemonClient {
+        /// Connects to the daemon at `path` and performs the handshake.
+        ///
+        /// # Errors
+        ///
+        /// Returns error if nothing is listening, the handshake times out,
+        /// or the daemon speaks another protocol version.
+        pub async fn connect(path: &Path) -> Result<Self, StoreError> {
+            tokio::time::timeout(CONNECT_TIMEOUT, Self::handshake(path))
+                .await
+                .map_err(|_| StoreError::Timeout)?
+        }
+
+        /// Connects to the daemon at the default socket, if one is running.
+        ///
+        /// Returns `None` (and logs why at debug level) when the caller
+        /// should fetch in-process instead.
+        pub async fn connect_default() -> Option<Self> {
+            let path = default_socket_path();
+            match Self::connect(&path).await {
+                Ok(client) => Some(client),
+                Err(e) => {
+                    debug!(path = %path.display(), error = %e, "Daemon not available");
+                    None
+                }
+            }
+        }
+
+        async fn handshake(path: &Path) -> Result<Self, StoreError> {
+            let (read, writer) = UnixStream::connect(path).await?.into_split();
+            let mut client = Self {
+                reader: BufReader::new(read),
+                writer,
+                info: DaemonInfo {
+                    daemon_version: String::new(),
+                    providers: Vec::new(),
+                    interval: None,
+                },
+            };
+
+            let hello = DaemonRequest::Hello {
+                version: PROTOCOL_VERSION,
+            };
+            match client.request(&hello).await? {
+                DaemonResponse::Hello {
+                    version,
+                    daemon_version,
+                    providers,
+                    interval_secs,
+                } if version == PROTOCOL_VERSION => {
+                    client.info = DaemonInfo {
+                        daemon_version,
+                        providers,
+                        interval: interval_secs.map(Duration::from_secs),
+                    };
+                    Ok(client)
+                }
+                DaemonResponse::Hello { version, .. } => Err(StoreError::Ipc(format!(
+                    "daemon speaks protocol {version}, expected {PROTOCOL_VERSION}"
+                ))),
+                other => Err(unexpected(&other)),
+            }
+        }
+
+        /// Returns the daemon's handshake details.
+        pub fn info(&self) -> &DaemonInfo {
+            &self.info
+        }
+
+        /// Sends a request and reads its response.
+        ///
+        /// # Errors
+        ///
+        /// Returns error if the connection fails or the daemon closes it.
+        pub async fn request(
+            &mut self,
+            request: &DaemonRequest,
+        ) -> Result<DaemonResponse, StoreError> {
+            write_message(&mut self.writer, request).await?;
+            read_message(&mut self.reader)
+                .await?
+                .ok_or_else(|| StoreError::Ipc("daemon closed the connection".to_string()))
+        }
+
+        /// Returns snapshots, letting the daemon refresh stale ones.
+        ///
+        /// # Errors
+        ///
+        /// Returns error if the request fails or the daemon reports an error.
+        pub async fn get(
+            &mut self,
+            providers: &[ProviderKind],
+            max_age: Option<Duration>,
+        ) -> Result<Vec<ProviderEntry>, StoreError> {
+            let request = DaemonRequest::Get {
+                providers: providers.to_vec(),
+                max_age_secs: max_age.map(|d| d.as_secs()),
+            };
+            entries(self.request(&request).await?)
+        }
+
+        /// Refreshes providers now.
+        ///
+        /// # Errors
+        ///
+        /// Returns error if the request fails or the daemon reports an error.
+        pub async fn refresh(
+            &mut self,
+            providers: &[ProviderKind],
+        ) -> Result<Vec<ProviderEntry>, StoreError> {
+            let request = DaemonRequest::Refresh {
+                providers: providers.to_vec(),
+            };
+            entries(self.request(&request).await?)
+        }
+
+        /// Subscribes to changes, consuming the connection.
+        ///
+        /// # Errors
+        ///
+        /// Returns error if the request cannot be sent.
+        pub async fn subscribe(
+            mut self,
+            providers: &[ProviderKind],
+        ) -> Result<Subscription, StoreError> {
+            let request = DaemonRequest::Subscribe {
+                providers: providers.to_vec(),
+            };
+            write_message(&mut self.writer, &request).await?;
+            Ok(Subscription {
+                reader: self.reader,
+                _writer: self.writer,
+            })
+        }
+
+        /// Asks the daemon to exit.
+        ///
+        /// # Errors
+        ///
+        /// Returns error if the request fails.
+        pub async fn shutdown(mut self) -> Result<(), StoreError> {
+            match self.request(&DaemonRequest::Shutdown).await? {
+                DaemonResponse::Ok => Ok(()),
+                other => Err(unexpected(&other)),
+            }
+        }
+    }
+
+    /// A stream of snapshot updates.
+    pub struct Subscription {
+        reader: BufReader<OwnedReadHalf>,
+        _writer: OwnedWriteHalf,
+    }
+
+    impl Subscription {
+        /// Waits for the next update, or `None` once the daemon goes away.
+        ///
+        /// # Errors
+        ///
+        /// Returns error if a message cannot be read or the daemon reports
+        /// an error.
+        pub async fn next(&mut self) -> Result<Option<Vec<ProviderEntry>>, StoreError> {
+            match read_message(&mut self.reader).await? {
+                Some(response) => entries(response).map(Some),
+                None => Ok(None),
+            }
+        }
+    }
+
+    fn entries(response: DaemonResponse) -> Result<Vec<ProviderEntry>, StoreError> {
+        match response {
+            DaemonResponse::Snapshots { entries } => Ok(entries),
+            other => Err(unexpected(&other)),
+        }
+    }
+
+    fn unexpected(response: &DaemonResponse) -> StoreError {
+        match response {
+            DaemonResponse::Error { message } => StoreError::Ipc(message.clone()),
+            other => StoreError::Ipc(format!("unexpected response: {other:?}")),
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::BufReader;
+
+    #[test]
+    fn test_request_wire_format() {
+        let request = DaemonRequest::Get {
+            providers: vec![ProviderKind::Claude, ProviderKind::Codex],
+            max_age_secs: Some(60),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"get","providers":["claude","codex"],"max_age_secs":60}"#
+        );
+
+        let parsed: DaemonRequest = serde_json::from_str(r#"{"type":"refresh"}"#).unwrap();
+        assert_eq!(parsed, DaemonRequest::Refresh { providers: vec![] });
+        assert!(serde_json::from_str::<DaemonRequest>(r#"{"type":"nope"}"#).is_err());
+    }
+
+    #[test]
+    fn test_entry_into_result() {
+        let entry = ProviderEntry {
+            provider: ProviderKind::Claude,
+            snapshot: Some(UsageSnapshot::new()),
+            error: None,
+            error_kind: None,
+            status: None,
+            refreshing: false,
+        };
+        assert!(entry.clone().into_result().is_ok());
+
+        let failed = ProviderEntry {
+            error: Some("expired".to_string()),
+            error_kind: Some(FetchErrorKind::Auth),
+            ..entry.clone()
+        };
+        assert_eq!(
+            failed.into_result().unwrap_err(),
+            FetchFailure::new(FetchErrorKind::Auth, "expired")
+        );
+
+        let legacy = ProviderEntry {
+            error: Some("expired".to_string()),
+            ..entry
+        };
+        assert_eq!(
+            legacy.into_result().unwrap_err().kind,
+            FetchErrorKind::Failed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_framing() {
+        let (client, server) = tokio::io::duplex(1024);
+        let (_, mut write) = tokio::io::split(client);
+        let (read, _) = tokio::io::split(server);
+        let mut reader = BufReader::new(read);
+
+        write_message(&mut write, &DaemonRequest::Shutdown)
+            .await
+            .unwrap();
+        write_message(
+            &mut write,
+            &DaemonRequest::Hello {
+                version: PROTOCOL_VERSION,
+            },
+        )
+        .await
+        .unwrap();
+        drop(write);
+
+        let first: DaemonRequest = read_message(&mut reader).await.unwrap().unwrap();
+        assert_eq!(first, DaemonRequest::Shutdown);
+        let second: DaemonRequest = read_message(&mut reader).await.unwrap().unwrap();
+        assert_eq!(second, DaemonRequest::Hello { version: 1 });
+    }
+}
@@ -43,6 +43,16 @@ pub mod providers {
     pub const CODEX: &str = "codex";
     /// Google Gemini provider.
     pub const GEMINI: &str = "gemini";
+    /// `OpenAI` organization admin key (usage and costs).
+    pub const OPENAI_ADMIN: &str = "openai_admin";
+    /// Anthropic Console admin key (usage and cost reports).
+    pub const ANTHROPIC: &str = "anthropic";
+    /// `OpenRouter` provider.
+    pub const OPENROUTER: &str = "openrouter";
+    /// `LiteLLM` proxy virtual key.
+    pub const LITELLM: &str = "litellm";
+    /// Azure service principal client secret.
+    pub const AZURE: &str = "azure";
 }
 
 /// Store an API key in the system keychain.
@@ -16,6 +16,7 @@
 //! - **`UsageStore`**: Main state for provider usage data with watch channels
 //! - **`SettingsStore`**: User preferences with persistence
 //! - **Persistence**: File I/O helpers for JSON data
+//! - **IPC**: Protocol and client for the background daemon
 //!
 //! ## Usage
 //!
@@ -37,13 +38,21 @@
 //! }
 //! ```
 
+pub mod custom_provider;
 pub mod error;
+pub mod ipc;
 pub mod keychain;
 pub mod persistence;
 pub mod settings_store;
 pub mod usage_store;
 
+pub use custom_provider::{CustomAuth, CustomCommand, CustomFieldPaths, CustomProviderConfig};
 pub use error::StoreError;
+#[cfg(unix)]
+pub use ipc::{DaemonClient, DaemonInfo, Subscription};
+pub use ipc::{
+    DaemonRequest, DaemonResponse, PROTOCOL_VERSION, ProviderEntry, default_socket_path,
+};
 pub use keychain::{delete_api_key, get_api_key, has_api_key, store_api_key};
 pub use persistence::{
     default_cache_dir, default_cache_path, default_config_dir, default_settings_path, load_json,
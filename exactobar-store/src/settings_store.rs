@@ -2,7 +2,8 @@
 //!
 //! Manages user settings with persistence and change notification.
 
-use exactobar_core::ProviderKind;
+use exactobar_core::{Amount, ExchangeRates, ProviderKind};
+use exactobar_fetch::ProviderOptions;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
@@ -11,6 +12,7 @@ use std::time::Duration;
 use tokio::sync::{RwLock, watch};
 use tracing::{debug, info, warn};
 
+use crate::custom_provider::CustomProviderConfig;
 use crate::error::StoreError;
 use crate::persistence::{default_settings_path, load_json, save_json};
 
@@ -68,6 +70,13 @@ pub struct Settings {
     /// Show provider icons in the in-menu switcher.
     pub switcher_shows_icons: bool,
 
+    /// Currency to convert money amounts into for display (e.g., "USD").
+    /// `None` shows amounts in the provider's own currency.
+    pub display_currency: Option<String>,
+
+    /// User-supplied exchange rates used for `display_currency`.
+    pub exchange_rates: ExchangeRates,
+
     // ========================================================================
     // Feature Toggles (new from CodexBar)
     // ========================================================================
@@ -86,6 +95,10 @@ pub struct Settings {
     /// Enable Claude web extras (via browser cookies).
     pub claude_web_extras_enabled: bool,
 
+    /// Write refreshed Claude OAuth credentials back to the Claude CLI's
+    /// credentials file or keychain entry.
+    pub claude_oauth_write_back_enabled: bool,
+
     /// Show optional credits and extra usage sections in menu.
     pub show_optional_credits_and_extra_usage: bool,
 
@@ -112,6 +125,12 @@ pub struct Settings {
 
     /// Whether provider detection has completed (for first-run experience).
     pub provider_detection_completed: bool,
+
+    // ========================================================================
+    // Custom Providers
+    // ========================================================================
+    /// User-defined HTTP/JSON providers.
+    pub custom_providers: Vec<CustomProviderConfig>,
 }
 
 impl Default for Settings {
@@ -137,6 +156,8 @@ impl Default for Settings {
             reset_times_show_absolute: false,
             menu_bar_shows_brand_icon_with_percent: false,
             switcher_shows_icons: true,
+            display_currency: None,
+            exchange_rates: ExchangeRates::default(),
 
             // Feature toggles - most enabled by default
             status_checks_enabled: true,
@@ -144,6 +165,7 @@ impl Default for Settings {
             cost_usage_enabled: false, // Off by default - requires local logs
             random_blink_enabled: false, // Off by default - can be annoying
             claude_web_extras_enabled: false, // Off by default - requires cookies
+            claude_oauth_write_back_enabled: false, // Off by default - touches CLI credentials
             show_optional_credits_and_extra_usage: true,
             openai_web_access_enabled: true,
 
@@ -155,10 +177,108 @@ impl Default for Settings {
             provider_order: vec![],
             debug_loading_pattern: None,
             provider_detection_completed: false,
+
+            // Custom providers
+            custom_providers: vec![],
         }
     }
 }
 
+impl Settings {
+    /// Parses settings JSON, registering custom provider IDs first.
+    ///
+    /// Provider-keyed fields only accept registered custom IDs, and the
+    /// definitions that register them live in the same document. Invalid
+    /// definitions and entries for unknown providers are dropped with a
+    /// warning rather than failing the whole document.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the JSON does not describe valid settings.
+    pub fn from_value(mut value: serde_json::Value) -> Result<Self, StoreError> {
+        if let Some(configs) = value
+            .get_mut("custom_providers")
+            .and_then(serde_json::Value::as_array_mut)
+        {
+            configs.retain(|config| {
+                let kind = CustomProviderConfig::deserialize(config)
+                    .map_err(StoreError::from)
+                    .and_then(|config| config.kind());
+                if let Err(e) = &kind {
+                    warn!(error = %e, "Skipping invalid custom provider");
+                }
+                kind.is_ok()
+            });
+        }
+
+        let known = |value: &serde_json::Value| {
+            let ok = ProviderKind::deserialize(value).is_ok();
+            if !ok {
+                warn!(provider = %value, "Dropping setting for unknown provider");
+            }
+            ok
+        };
+        for field in ["enabled_providers", "provider_order"] {
+            if let Some(kinds) = value
+                .get_mut(field)
+                .and_then(serde_json::Value::as_array_mut)
+            {
+                kinds.retain(known);
+            }
+        }
+        if let Some(settings) = value
+            .get_mut("provider_settings")
+            .and_then(serde_json::Value::as_object_mut)
+        {
+            settings.retain(|key, _| known(&serde_json::Value::String(key.clone())));
+        }
+        if let Some(selected) = value.get_mut("selected_provider") {
+            if !selected.is_null() && !known(selected) {
+                *selected = serde_json::Value::Null;
+            }
+        }
+
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Converts an amount to the display currency.
+    ///
+    /// Returns the amount unchanged when no display currency is set, the
+    /// amount is not money, or no rate is available.
+    pub fn display_amount(&self, amount: &Amount) -> Amount {
+        self.display_currency
+            .as_deref()
+            .and_then(|to| self.exchange_rates.convert(amount, to))
+            .unwrap_or_else(|| amount.clone())
+    }
+
+    /// Returns the per-provider options passed to fetch strategies.
+    pub fn provider_options(&self) -> HashMap<ProviderKind, ProviderOptions> {
+        self.provider_settings
+            .iter()
+            .map(|(kind, ps)| {
+                let options = ProviderOptions {
+                    monthly_budget: ps.monthly_budget.filter(|b| b.is_finite() && *b > 0.0),
+                    base_url: ps
+                        .base_url
+                        .as_deref()
+                        .map(|url| url.trim().trim_end_matches('/'))
+                        .filter(|url| !url.is_empty())
+                        .map(String::from),
+                    resource_id: ps
+                        .resource_id
+                        .as_deref()
+                        .map(str::trim)
+                        .filter(|id| !id.is_empty())
+                        .map(String::from),
+                };
+                (*kind, options)
+            })
+            .filter(|(_, options)| *options != ProviderOptions::default())
+            .collect()
+    }
+}
+
 /// Refresh cadence options.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
@@ -347,6 +467,15 @@ pub struct ProviderSettings {
 
     /// Manual cookie header (stored inline for simplicity).
     pub cookie_header: Option<String>,
+
+    /// Monthly spend budget in USD (for providers that report spend).
+    pub monthly_budget: Option<f64>,
+
+    /// API base URL (for self-hosted providers like `LiteLLM`).
+    pub base_url: Option<String>,
+
+    /// Cloud resource ID (for providers like Azure `OpenAI`).
+    pub resource_id: Option<String>,
 }
 
 // ============================================================================
@@ -390,10 +519,13 @@ impl SettingsStore {
     pub async fn load(path: PathBuf) -> Result<Self, StoreError> {
         let settings = if path.exists() {
             info!(path = %path.display(), "Loading settings");
-            load_json(&path).await.unwrap_or_else(|e| {
-                warn!(error = %e, "Failed to load settings, using defaults");
-                Settings::default()
-            })
+            load_json(&path)
+                .await
+                .and_then(Settings::from_value)
+                .unwrap_or_else(|e| {
+                    warn!(error = %e, "Failed to load settings, using defaults");
+                    Settings::default()
+                })
         } else {
             debug!(path = %path.display(), "Settings file not found, using defaults");
             Settings::default()
@@ -537,6 +669,26 @@ impl SettingsStore {
         self.update(|s| s.switcher_shows_icons = value).await;
     }
 
+    /// Gets the display currency, if conversion is enabled.
+    pub async fn display_currency(&self) -> Option<String> {
+        self.settings.read().await.display_currency.clone()
+    }
+
+    /// Sets the display currency (`None` disables conversion).
+    pub async fn set_display_currency(&self, currency: Option<String>) {
+        self.update(|s| s.display_currency = currency).await;
+    }
+
+    /// Gets the exchange rate table.
+    pub async fn exchange_rates(&self) -> ExchangeRates {
+        self.settings.read().await.exchange_rates.clone()
+    }
+
+    /// Sets the exchange rate table.
+    pub async fn set_exchange_rates(&self, rates: ExchangeRates) {
+        self.update(|s| s.exchange_rates = rates).await;
+    }
+
     // ========================================================================
     // Feature Toggle Methods
     // ========================================================================
@@ -595,6 +747,17 @@ impl SettingsStore {
         self.update(|s| s.claude_web_extras_enabled = value).await;
     }
 
+    /// Gets whether refreshed Claude OAuth credentials are written back.
+    pub async fn claude_oauth_write_back_enabled(&self) -> bool {
+        self.settings.read().await.claude_oauth_write_back_enabled
+    }
+
+    /// Sets whether refreshed Claude OAuth credentials are written back.
+    pub async fn set_claude_oauth_write_back_enabled(&self, value: bool) {
+        self.update(|s| s.claude_oauth_write_back_enabled = value)
+            .await;
+    }
+
     /// Gets whether optional credits/extra usage sections are shown.
     pub async fn show_optional_credits_and_extra_usage(&self) -> bool {
         self.settings
@@ -730,6 +893,107 @@ impl SettingsStore {
         .await;
     }
 
+    /// Gets the monthly spend budget for a provider.
+    pub async fn monthly_budget(&self, provider: ProviderKind) -> Option<f64> {
+        self.settings
+            .read()
+            .await
+            .provider_settings
+            .get(&provider)
+            .and_then(|ps| ps.monthly_budget)
+    }
+
+    /// Sets the monthly spend budget for a provider.
+    pub async fn set_monthly_budget(&self, provider: ProviderKind, budget: Option<f64>) {
+        self.update(|s| {
+            s.provider_settings
+                .entry(provider)
+                .or_default()
+                .monthly_budget = budget;
+        })
+        .await;
+    }
+
+    /// Gets the API base URL for a provider.
+    pub async fn base_url(&self, provider: ProviderKind) -> Option<String> {
+        self.settings
+            .read()
+            .await
+            .provider_settings
+            .get(&provider)
+            .and_then(|ps| ps.base_url.clone())
+    }
+
+    /// Sets the API base URL for a provider.
+    pub async fn set_base_url(&self, provider: ProviderKind, base_url: Option<String>) {
+        self.update(|s| {
+            s.provider_settings.entry(provider).or_default().base_url = base_url;
+        })
+        .await;
+    }
+
+    /// Gets the cloud resource ID for a provider.
+    pub async fn resource_id(&self, provider: ProviderKind) -> Option<String> {
+        self.settings
+            .read()
+            .await
+            .provider_settings
+            .get(&provider)
+            .and_then(|ps| ps.resource_id.clone())
+    }
+
+    /// Sets the cloud resource ID for a provider.
+    pub async fn set_resource_id(&self, provider: ProviderKind, resource_id: Option<String>) {
+        self.update(|s| {
+            s.provider_settings.entry(provider).or_default().resource_id = resource_id;
+        })
+        .await;
+    }
+
+    // ========================================================================
+    // Custom Provider Methods
+    // ========================================================================
+
+    /// Gets the user-defined providers.
+    pub async fn custom_providers(&self) -> Vec<CustomProviderConfig> {
+        self.settings.read().await.custom_providers.clone()
+    }
+
+    /// Adds a user-defined provider, replacing one with the same ID.
+    pub async fn set_custom_provider(&self, config: CustomProviderConfig) {
+        self.update(|s| {
+            if let Some(existing) = s.custom_providers.iter_mut().find(|c| c.id == config.id) {
+                *existing = config;
+            } else {
+                s.custom_providers.push(config);
+            }
+        })
+        .await;
+    }
+
+    /// Removes a user-defined provider and its per-provider settings.
+    ///
+    /// Returns false if no provider has the ID.
+    pub async fn remove_custom_provider(&self, id: &str) -> bool {
+        let mut removed = false;
+        self.update(|s| {
+            let before = s.custom_providers.len();
+            s.custom_providers.retain(|c| c.id != id);
+            removed = s.custom_providers.len() != before;
+
+            if let Some(kind) = ProviderKind::custom(id) {
+                s.enabled_providers.remove(&kind);
+                s.provider_settings.remove(&kind);
+                s.provider_order.retain(|k| *k != kind);
+                if s.selected_provider == Some(kind) {
+                    s.selected_provider = None;
+                }
+            }
+        })
+        .await;
+        removed
+    }
+
     // ========================================================================
     // Debug & Detection Methods
     // ========================================================================
@@ -824,6 +1088,7 @@ mod tests {
         assert!(!settings.cost_usage_enabled);
         assert!(!settings.random_blink_enabled);
         assert!(!settings.claude_web_extras_enabled);
+        assert!(!settings.claude_oauth_write_back_enabled);
         assert!(settings.show_optional_credits_and_extra_usage);
         assert!(settings.openai_web_access_enabled);
 
@@ -851,6 +1116,189 @@ mod tests {
         assert!(store.reset_times_show_absolute().await);
     }
 
+    #[tokio::test]
+    async fn test_display_currency() {
+        let store = SettingsStore::new(PathBuf::from("/tmp/test_display_currency.json"));
+        assert!(store.display_currency().await.is_none());
+
+        store.set_display_currency(Some("USD".to_string())).await;
+        store
+            .set_exchange_rates(ExchangeRates::new("USD").with_rate("CNY", 8.0))
+            .await;
+
+        let settings = store.get().await;
+        let converted = settings.display_amount(&Amount::new(80.0, "CNY"));
+        assert_eq!(converted, Amount::usd(10.0));
+
+        // Units without a rate are shown as-is
+        let credits = Amount::credits(5.0);
+        assert_eq!(settings.display_amount(&credits), credits);
+    }
+
+    #[tokio::test]
+    async fn test_monthly_budget_options() {
+        let store = SettingsStore::new(PathBuf::from("/tmp/test_monthly_budget.json"));
+        assert!(store.monthly_budget(ProviderKind::Codex).await.is_none());
+
+        store
+            .set_monthly_budget(ProviderKind::Codex, Some(100.0))
+            .await;
+        store
+            .set_cookie_source(ProviderKind::Claude, CookieSource::Manual)
+            .await;
+
+        let options = store.get().await.provider_options();
+        assert_eq!(options.len(), 1);
+        assert_eq!(options[&ProviderKind::Codex].monthly_budget, Some(100.0));
+    }
+
+    #[tokio::test]
+    async fn test_base_url_options() {
+        let store = SettingsStore::new(PathBuf::from("/tmp/test_base_url.json"));
+        assert!(store.base_url(ProviderKind::LiteLlm).await.is_none());
+
+        store
+            .set_base_url(
+                ProviderKind::LiteLlm,
+                Some(" http://localhost:4000/ ".to_string()),
+            )
+            .await;
+        store
+            .set_base_url(ProviderKind::Codex, Some("  ".to_string()))
+            .await;
+
+        let options = store.get().await.provider_options();
+        assert_eq!(options.len(), 1);
+        assert_eq!(
+            options[&ProviderKind::LiteLlm].base_url.as_deref(),
+            Some("http://localhost:4000")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resource_id_options() {
+        let store = SettingsStore::new(PathBuf::from("/tmp/test_resource_id.json"));
+        assert!(store.resource_id(ProviderKind::Codex).await.is_none());
+
+        store
+            .set_resource_id(
+                ProviderKind::Codex,
+                Some(" /subscriptions/sub/resourceGroups/rg ".to_string()),
+            )
+            .await;
+
+        let options = store.get().await.provider_options();
+        assert_eq!(
+            options[&ProviderKind::Codex].resource_id.as_deref(),
+            Some("/subscriptions/sub/resourceGroups/rg")
+        );
+        assert!(options[&ProviderKind::Codex].base_url.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_custom_providers() {
+        use crate::custom_provider::CustomFieldPaths;
+
+        let store = SettingsStore::new(PathBuf::from("/tmp/test_custom_providers.json"));
+        let config = CustomProviderConfig {
+            id: "gateway".to_string(),
+            name: "Gateway".to_string(),
+            color: None,
+            url: "https://gateway.example.com/usage".to_string(),
+            command: None,
+            auth: crate::custom_provider::CustomAuth::None,
+            headers: std::collections::BTreeMap::new(),
+            fields: CustomFieldPaths {
+                percent: Some("$.percent".to_string()),
+                ..Default::default()
+            },
+            window_label: None,
+            unit: None,
+            dashboard_url: None,
+        };
+        let kind = config.kind().unwrap();
+
+        store.set_custom_provider(config.clone()).await;
+        store
+            .set_custom_provider(CustomProviderConfig {
+                name: "Renamed".to_string(),
+                ..config
+            })
+            .await;
+        store.set_provider_enabled(kind, true).await;
+
+        let configs = store.custom_providers().await;
+        assert_eq!(configs.len(), 1);
+        assert_eq!(configs[0].name, "Renamed");
+
+        // Custom kinds survive a JSON round trip as set members and map keys
+        let json = serde_json::to_string(&store.get().await).unwrap();
+        let settings: Settings = serde_json::from_str(&json).unwrap();
+        assert!(settings.enabled_providers.contains(&kind));
+
+        assert!(store.remove_custom_provider("gateway").await);
+        assert!(!store.remove_custom_provider("gateway").await);
+        assert!(store.custom_providers().await.is_empty());
+        assert!(!store.is_provider_enabled(kind).await);
+    }
+
+    #[test]
+    fn test_from_value_registers_custom_ids_first() {
+        let json = serde_json::json!({
+            "enabled_providers": ["claude", "custom:fromvalue"],
+            "provider_order": ["custom:fromvalue"],
+            "custom_providers": [{
+                "id": "fromvalue",
+                "name": "From Value",
+                "url": "https://example.com/usage",
+                "fields": {"percent": "$.pct"}
+            }]
+        });
+
+        let settings = Settings::from_value(json).unwrap();
+        let kind = ProviderKind::custom("fromvalue").unwrap();
+        assert!(settings.enabled_providers.contains(&kind));
+        assert_eq!(kind.display_name(), "From Value");
+    }
+
+    #[test]
+    fn test_from_value_drops_unknown_custom_entries() {
+        let json = serde_json::json!({
+            "enabled_providers": ["claude", "custom:unregistered"],
+            "provider_order": ["custom:unregistered", "codex"],
+            "selected_provider": "custom:unregistered",
+            "provider_settings": {
+                "custom:unregistered": {"monthly_budget": 5.0},
+                "codex": {"monthly_budget": 20.0}
+            },
+            "custom_providers": [
+                {"id": "Not Valid", "name": "Bad", "url": "https://example.com"},
+                {"name": "Missing ID"},
+                {
+                    "id": "survivor",
+                    "name": "Survivor",
+                    "url": "https://example.com/usage",
+                    "fields": {"percent": "$.pct"}
+                }
+            ],
+            "merge_icons": false
+        });
+
+        let settings = Settings::from_value(json).unwrap();
+        assert!(settings.enabled_providers.contains(&ProviderKind::Claude));
+        assert_eq!(settings.enabled_providers.len(), 1);
+        assert_eq!(settings.provider_order, vec![ProviderKind::Codex]);
+        assert!(settings.selected_provider.is_none());
+        assert_eq!(
+            settings.provider_settings[&ProviderKind::Codex].monthly_budget,
+            Some(20.0)
+        );
+        assert_eq!(settings.provider_settings.len(), 1);
+        assert_eq!(settings.custom_providers.len(), 1);
+        assert_eq!(settings.custom_providers[0].id, "survivor");
+        assert!(!settings.merge_icons);
+    }
+
     #[tokio::test]
     async fn test_feature_toggles() {
         let store = SettingsStore::new(PathBuf::from("/tmp/test_feature_toggles.json"));
@@ -4,6 +4,7 @@
 
 use chrono::{DateTime, Utc};
 use exactobar_core::{Credits, ProviderKind, ProviderStatus, UsageSnapshot};
+use exactobar_fetch::FetchFailure;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
@@ -60,8 +61,8 @@ struct UsageStoreInner {
     last_refresh: Option<DateTime<Utc>>,
     /// Providers currently refreshing.
     refresh_in_progress: HashSet<ProviderKind>,
-    /// Error messages by provider.
-    errors: HashMap<ProviderKind, String>,
+    /// Errors by provider.
+    errors: HashMap<ProviderKind, FetchFailure>,
     /// Snapshot timestamps.
     snapshot_times: HashMap<ProviderKind, DateTime<Utc>>,
 }
@@ -295,12 +296,12 @@ impl UsageStore {
     // ========================================================================
 
     /// Gets the error for a provider.
-    pub async fn get_error(&self, provider: ProviderKind) -> Option<String> {
+    pub async fn get_error(&self, provider: ProviderKind) -> Option<FetchFailure> {
         self.inner.read().await.errors.get(&provider).cloned()
     }
 
     /// Sets an error for a provider.
-    pub async fn set_error(&self, provider: ProviderKind, error: String) {
+    pub async fn set_error(&self, provider: ProviderKind, error: FetchFailure) {
         {
             let mut inner = self.inner.write().await;
             inner.errors.insert(provider, error);
@@ -319,7 +320,7 @@ impl UsageStore {
     }
 
     /// Gets all errors.
-    pub async fn get_all_errors(&self) -> HashMap<ProviderKind, String> {
+    pub async fn get_all_errors(&self) -> HashMap<ProviderKind, FetchFailure> {
         self.inner.read().await.errors.clone()
     }
 
@@ -373,6 +374,7 @@ impl UsageStore {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use exactobar_fetch::FetchErrorKind;
 
     #[tokio::test]
     async fn test_new_store() {
@@ -429,7 +431,10 @@ mod tests {
         assert!(store.get_error(ProviderKind::Codex).await.is_none());
 
         store
-            .set_error(ProviderKind::Codex, "Test error".to_string())
+            .set_error(
+                ProviderKind::Codex,
+                FetchFailure::new(FetchErrorKind::Failed, "Test error"),
+            )
             .await;
         assert!(store.get_error(ProviderKind::Codex).await.is_some());
 